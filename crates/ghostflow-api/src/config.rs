@@ -0,0 +1,212 @@
+use axum::http::{HeaderName, HeaderValue, Method};
+use ghostflow_core::{GhostFlowError, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// Default for [`ApiConfig::max_body_bytes`]
+pub const DEFAULT_MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Default for [`ApiConfig::max_json_depth`]
+pub const DEFAULT_MAX_JSON_DEPTH: usize = 64;
+
+/// Settings for the HTTP API router.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub cors: CorsConfig,
+    /// Largest request body accepted; bigger ones are answered with 413
+    pub max_body_bytes: usize,
+    /// Deepest nesting of objects and arrays accepted in JSON bodies
+    pub max_json_depth: usize,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            cors: CorsConfig::default(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_json_depth: DEFAULT_MAX_JSON_DEPTH,
+        }
+    }
+}
+
+/// Which browser origins may call the API. The default allows none, so only
+/// same-origin pages (the bundled UI) can use it from a browser.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CorsConfig {
+    /// Exact origins such as `https://flows.example.com`; `*` allows any
+    /// origin but cannot be combined with `allow_credentials`
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    /// Let browsers send cookies and `Authorization` with cross-origin requests
+    pub allow_credentials: bool,
+    /// How long browsers may cache a preflight answer
+    pub max_age_secs: u64,
+    /// Allow everything. Only meant for local development.
+    pub permissive: bool,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: ["GET", "POST", "PUT", "DELETE"].map(String::from).to_vec(),
+            allowed_headers: ["authorization", "content-type", "idempotency-key"].map(String::from).to_vec(),
+            allow_credentials: false,
+            max_age_secs: 600,
+            permissive: false,
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Any origin, method and header, for running the UI from a dev server.
+    pub fn development() -> Self {
+        Self {
+            permissive: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_allowed_origin(mut self, origin: impl Into<String>) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    pub fn with_credentials(mut self) -> Self {
+        self.allow_credentials = true;
+        self
+    }
+
+    /// Build the layer, rejecting origins, methods or headers that do not parse.
+    pub fn layer(&self) -> Result<CorsLayer> {
+        if self.permissive {
+            return Ok(CorsLayer::permissive());
+        }
+
+        let wildcard = self.allowed_origins.iter().any(|origin| origin == "*");
+        if wildcard && self.allow_credentials {
+            return Err(config_error(
+                "CORS cannot allow credentials for every origin; list the origins explicitly",
+            ));
+        }
+
+        let allow_origin = if wildcard {
+            AllowOrigin::from(Any)
+        } else {
+            let origins = self
+                .allowed_origins
+                .iter()
+                .map(|origin| {
+                    HeaderValue::from_str(origin.trim_end_matches('/'))
+                        .map_err(|_| config_error(format!("Invalid CORS origin '{}'", origin)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            AllowOrigin::list(origins)
+        };
+        let methods = self
+            .allowed_methods
+            .iter()
+            .map(|method| {
+                Method::from_bytes(method.to_uppercase().as_bytes())
+                    .map_err(|_| config_error(format!("Invalid CORS method '{}'", method)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let headers = self
+            .allowed_headers
+            .iter()
+            .map(|header| {
+                HeaderName::from_bytes(header.to_lowercase().as_bytes())
+                    .map_err(|_| config_error(format!("Invalid CORS header '{}'", header)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .allow_credentials(self.allow_credentials)
+            .max_age(Duration::from_secs(self.max_age_secs)))
+    }
+}
+
+fn config_error(message: impl Into<String>) -> GhostFlowError {
+    GhostFlowError::ConfigurationError {
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    async fn preflight(config: &CorsConfig, origin: &str) -> axum::response::Response {
+        let router = Router::new().route("/api/flows", get(|| async { "[]" })).layer(config.layer().unwrap());
+        router
+            .oneshot(
+                Request::builder()
+                    .method(Method::OPTIONS)
+                    .uri("/api/flows")
+                    .header(header::ORIGIN, origin)
+                    .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                    .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    fn config() -> CorsConfig {
+        CorsConfig::default()
+            .with_allowed_origin("https://flows.example.com")
+            .with_credentials()
+    }
+
+    #[tokio::test]
+    async fn test_allowed_origin_gets_cors_headers() {
+        let response = preflight(&config(), "https://flows.example.com").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://flows.example.com");
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        let methods = headers[header::ACCESS_CONTROL_ALLOW_METHODS].to_str().unwrap();
+        assert!(methods.contains("POST"), "{}", methods);
+        let allowed_headers = headers[header::ACCESS_CONTROL_ALLOW_HEADERS].to_str().unwrap();
+        assert!(allowed_headers.contains("authorization"), "{}", allowed_headers);
+        // Lets browsers retry execution requests safely
+        assert!(allowed_headers.contains("idempotency-key"), "{}", allowed_headers);
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_origin_is_rejected() {
+        let response = preflight(&config(), "https://evil.example.net").await;
+        assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+
+        // The default policy allows no cross-origin callers at all
+        let response = preflight(&CorsConfig::default(), "https://flows.example.com").await;
+        assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_development_policy_allows_any_origin() {
+        let response = preflight(&CorsConfig::development(), "http://localhost:5173").await;
+        assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_some());
+    }
+
+    #[test]
+    fn test_wildcard_with_credentials_is_a_configuration_error() {
+        let config = CorsConfig::default().with_allowed_origin("*").with_credentials();
+        assert!(matches!(config.layer(), Err(GhostFlowError::ConfigurationError { .. })));
+        assert!(CorsConfig::default().with_allowed_origin("bad\norigin").layer().is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use ghostflow_core::{FieldError, GhostFlowError};
+
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    /// Request body failed schema validation; every failing field is reported
+    InvalidInput(Vec<FieldError>),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    PayloadTooLarge(String),
+    InternalServerError(String),
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message, fields) = match self {
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message, None),
+            ApiError::InvalidInput(fields) => (
+                StatusCode::BAD_REQUEST,
+                "Input does not match the flow's input schema".to_string(),
+                Some(fields),
+            ),
+            ApiError::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message, None),
+            ApiError::Forbidden(message) => (StatusCode::FORBIDDEN, message, None),
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, message, None),
+            ApiError::PayloadTooLarge(message) => (StatusCode::PAYLOAD_TOO_LARGE, message, None),
+            ApiError::InternalServerError(message) => (StatusCode::INTERNAL_SERVER_ERROR, message, None),
+        };
+
+        let mut body = serde_json::json!({
+            "error": message,
+            "status": status.as_u16()
+        });
+        if let Some(fields) = fields {
+            body["fields"] = serde_json::json!(fields);
+        }
+
+        (status, Json(body)).into_response()
+    }
+}
+
+impl From<GhostFlowError> for ApiError {
+    fn from(error: GhostFlowError) -> Self {
+        match error {
+            GhostFlowError::InvalidInput { errors } => ApiError::InvalidInput(errors),
+            GhostFlowError::ValidationError { message } => ApiError::BadRequest(message),
+            GhostFlowError::NotFoundError { .. } => ApiError::NotFound(error.to_string()),
+            GhostFlowError::AuthenticationError { message } => ApiError::Unauthorized(message),
+            GhostFlowError::AuthorizationError { message } => ApiError::Forbidden(message),
+            other => ApiError::InternalServerError(other.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_invalid_input_is_a_bad_request_with_fields() {
+        let error = ApiError::from(GhostFlowError::InvalidInput {
+            errors: vec![FieldError {
+                field: "vmid".to_string(),
+                message: "is required".to_string(),
+            }],
+        });
+
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["fields"][0]["field"], "vmid");
+        assert_eq!(body["fields"][0]["message"], "is required");
+    }
+}
@@ -0,0 +1,157 @@
+use axum::{
+    async_trait,
+    body::{Body, Bytes},
+    extract::{FromRequest, Request},
+    http::header,
+};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+
+use crate::{ApiError, AppState};
+
+/// JSON body extractor that enforces [`ApiConfig`](crate::ApiConfig)'s
+/// `max_body_bytes` and `max_json_depth` before deserializing, so a huge or
+/// deeply nested flow is refused instead of exhausting memory or the stack.
+/// Oversized bodies are 413, everything else that fails to parse is 400.
+pub struct LimitedJson<T>(pub T);
+
+#[async_trait]
+impl<T> FromRequest<Arc<AppState>> for LimitedJson<T>
+where
+    T: DeserializeOwned,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let is_json = request
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("application/json") || value.contains("+json"));
+        if !is_json {
+            return Err(ApiError::BadRequest("Expected a body with Content-Type: application/json".to_string()));
+        }
+
+        let body = read_body(request.into_body(), state.config.max_body_bytes).await?;
+        parse_json(&body, state.config.max_json_depth).map(LimitedJson)
+    }
+}
+
+/// Buffer `body`, failing with 413 as soon as it grows past `limit` bytes.
+pub async fn read_body(body: Body, limit: usize) -> Result<Bytes, ApiError> {
+    let mut stream = body.into_data_stream();
+    let mut buffer = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| ApiError::BadRequest(format!("Failed to read request body: {}", e)))?;
+        if buffer.len() + chunk.len() > limit {
+            return Err(ApiError::PayloadTooLarge(format!(
+                "Request body exceeds the {} byte limit",
+                limit
+            )));
+        }
+        buffer.extend_from_slice(&chunk);
+    }
+    Ok(Bytes::from(buffer))
+}
+
+/// Deserialize `body` after checking it nests no deeper than `max_depth`.
+pub fn parse_json<T: DeserializeOwned>(body: &[u8], max_depth: usize) -> Result<T, ApiError> {
+    check_json_depth(body, max_depth)?;
+    serde_json::from_slice(body).map_err(|e| ApiError::BadRequest(format!("Invalid JSON body: {}", e)))
+}
+
+/// Reject JSON whose objects and arrays nest deeper than `max_depth`. Scans
+/// the raw bytes, so it is safe to run before any parsing.
+pub fn check_json_depth(body: &[u8], max_depth: usize) -> Result<(), ApiError> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for &byte in body {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                if depth > max_depth {
+                    return Err(ApiError::BadRequest(format!(
+                        "JSON body nests deeper than {} levels",
+                        max_depth
+                    )));
+                }
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::app_state;
+    use crate::ApiConfig;
+    use axum::http::StatusCode;
+    use ghostflow_core::BasicNodeRegistry;
+    use tower::ServiceExt;
+
+    async fn post(uri: &str, body: String) -> (StatusCode, serde_json::Value) {
+        let config = ApiConfig {
+            max_body_bytes: 1024,
+            max_json_depth: 16,
+            ..ApiConfig::default()
+        };
+        let state = app_state(Arc::new(BasicNodeRegistry::new())).with_config(config);
+
+        let response = crate::create_api_router(Arc::new(state))
+            .unwrap()
+            .oneshot(
+                Request::post(uri)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_oversized_flow_is_payload_too_large() {
+        let body = serde_json::json!({ "name": "big", "description": "x".repeat(4096) }).to_string();
+        let (status, body) = post("/api/flows", body).await;
+
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["error"], "Request body exceeds the 1024 byte limit");
+    }
+
+    #[tokio::test]
+    async fn test_deeply_nested_input_is_rejected() {
+        let nested = format!("{}{}", "[".repeat(20), "]".repeat(20));
+        let body = format!(r#"{{"input_data": {{"payload": {}}}}}"#, nested);
+        let uri = format!("/api/flows/{}/execute", uuid::Uuid::new_v4());
+        let (status, body) = post(&uri, body).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "JSON body nests deeper than 16 levels");
+    }
+
+    #[test]
+    fn test_depth_ignores_brackets_inside_strings() {
+        let body = br#"{"note": "[[[[[[[[[[ \" {{{{{{{{", "list": [[1]]}"#;
+        assert!(check_json_depth(body, 3).is_ok());
+        assert!(check_json_depth(body, 2).is_err());
+    }
+}
@@ -3,22 +3,33 @@ pub mod websocket;
 pub mod auth;
 pub mod state;
 pub mod error;
+pub mod config;
+pub mod extract;
+#[cfg(test)]
+mod test_support;
 
 pub use routes::*;
 pub use websocket::*;
 pub use auth::*;
 pub use state::*;
 pub use error::*;
+pub use config::*;
+pub use extract::*;
 
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{get, post, put, delete},
     Router,
 };
-use tower_http::cors::CorsLayer;
 use std::sync::Arc;
 
-pub fn create_api_router(state: Arc<AppState>) -> Router {
-    Router::new()
+/// Build the API router. Fails when the state's [`ApiConfig`] holds an
+/// invalid CORS policy.
+pub fn create_api_router(state: Arc<AppState>) -> ghostflow_core::Result<Router> {
+    let cors = state.config.cors.layer()?;
+    let body_limit = DefaultBodyLimit::max(state.config.max_body_bytes);
+
+    Ok(Router::new()
         // Flow management
         .route("/api/flows", get(routes::flows::list_flows).post(routes::flows::create_flow))
         .route("/api/flows/:id", 
@@ -27,22 +38,45 @@ pub fn create_api_router(state: Arc<AppState>) -> Router {
             .delete(routes::flows::delete_flow))
         .route("/api/flows/:id/validate", post(routes::flows::validate_flow))
         .route("/api/flows/:id/execute", post(routes::flows::execute_flow))
+        .route("/api/flows/:id/versions", get(routes::flows::list_flow_versions))
+        .route("/api/flows/:id/rollback/:version", post(routes::flows::rollback_flow))
         
         // Execution management
         .route("/api/executions", get(routes::executions::list_executions))
         .route("/api/executions/:id", get(routes::executions::get_execution))
         .route("/api/executions/:id/cancel", post(routes::executions::cancel_execution))
+        .route("/api/executions/:id/replay", post(routes::executions::replay_execution))
+        .route("/api/executions/:id/approvals", get(routes::executions::list_pending_approvals))
+        .route("/api/executions/:id/approve", post(routes::executions::approve_execution))
+        .route("/api/executions/:id/reject", post(routes::executions::reject_execution))
+
+        // Dead letters
+        .route("/api/deadletter", get(routes::dead_letters::list_dead_letters))
+        .route("/api/deadletter/:id/retry", post(routes::dead_letters::retry_dead_letter))
+
+        // Inbound webhooks
+        .route("/api/webhooks/:flow_id", post(routes::webhooks::receive_webhook))
         
         // Node catalog
         .route("/api/nodes", get(routes::nodes::list_nodes))
         .route("/api/nodes/:id", get(routes::nodes::get_node))
+        .route("/api/nodes/:id/options/:param", get(routes::nodes::load_node_options))
+
+        // Template catalog
+        .route("/api/templates", get(routes::templates::list_templates))
+        .route("/api/templates/:id", get(routes::templates::get_template))
+
+        // Credential routes
+        .route("/api/credentials/:id/test", post(routes::credentials::test_credential))
         
         // WebSocket for real-time updates
         .route("/ws", get(websocket::websocket_handler))
         
         // Health check
         .route("/health", get(routes::health::health_check))
+        .route("/health/ready", get(routes::health::readiness_check))
         
-        .layer(CorsLayer::permissive())
-        .with_state(state)
+        .layer(body_limit)
+        .layer(cors)
+        .with_state(state))
 }
\ No newline at end of file
@@ -0,0 +1,305 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use ghostflow_core::{Credential, CredentialType, CredentialVault, NodeRegistry};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{ExecutionContext, NodeDefinition};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::{AppState, ApiError, ApiResult};
+
+/// How long a connection test may take before it is reported as failed.
+const CONNECTION_TEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TestCredentialRequest {
+    /// Node type whose connection test to run. Defaults to the credential's
+    /// `node_type` field, or the name of a custom credential type.
+    #[serde(default)]
+    pub node_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TestCredentialResponse {
+    pub credential_id: String,
+    pub node_type: String,
+    pub success: bool,
+    /// What the remote system reported on success, or why the test failed.
+    pub message: String,
+    pub duration_ms: u64,
+}
+
+/// `POST /api/credentials/:id/test` — check a stored credential against the
+/// system it is for. A failed check is still a 200 with `success: false`;
+/// errors are reserved for unknown credentials or node types.
+pub async fn test_credential(
+    Path(credential_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    request: Option<Json<TestCredentialRequest>>,
+) -> ApiResult<Json<TestCredentialResponse>> {
+    let vault = state.credential_vault.as_ref();
+    let credential = vault
+        .retrieve(&credential_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Credential '{}' not found", credential_id)))?;
+    let credential = decrypted(vault, credential).await?;
+
+    let node_type = request
+        .and_then(|Json(r)| r.node_type)
+        .or_else(|| credential_node_type(&credential))
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!("Specify which node type to test credential '{}' with", credential_id))
+        })?;
+
+    let response = run_connection_test(state.node_registry.as_ref(), &credential, &node_type).await?;
+    Ok(Json(response))
+}
+
+pub(crate) async fn decrypted(vault: &dyn CredentialVault, mut credential: Credential) -> ApiResult<Credential> {
+    if credential.encrypted {
+        for value in credential.data.values_mut() {
+            *value = vault.decrypt(value).await?;
+        }
+        credential.encrypted = false;
+    }
+    Ok(credential)
+}
+
+fn credential_node_type(credential: &Credential) -> Option<String> {
+    credential.data.get("node_type").cloned().or_else(|| match &credential.credential_type {
+        CredentialType::Custom(name) => Some(name.clone()),
+        _ => None,
+    })
+}
+
+/// Run `node_type`'s connection test with the credential's fields as input.
+pub async fn run_connection_test(
+    registry: &dyn NodeRegistry,
+    credential: &Credential,
+    node_type: &str,
+) -> ApiResult<TestCredentialResponse> {
+    let node = registry
+        .get_node(node_type)
+        .ok_or_else(|| ApiError::BadRequest(format!("Unknown node type '{}'", node_type)))?;
+    if !node.supports_connection_test() {
+        return Err(ApiError::BadRequest(format!("Node type '{}' has no connection test", node_type)));
+    }
+
+    let context = ExecutionContext {
+        execution_id: Uuid::new_v4(),
+        flow_id: Uuid::nil(),
+        node_id: node_type.to_string(),
+        input: credential_input(&node.definition(), &credential.data),
+        variables: HashMap::new(),
+        secrets: HashMap::new(),
+        artifacts: HashMap::new(),
+        node_outputs: HashMap::new(),
+        attempt: 1,
+    };
+
+    let started = Instant::now();
+    let result = match tokio::time::timeout(CONNECTION_TEST_TIMEOUT, node.test_connection(&context)).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err(format!("No answer within {}s", CONNECTION_TEST_TIMEOUT.as_secs())),
+    };
+
+    Ok(TestCredentialResponse {
+        credential_id: credential.id.clone(),
+        node_type: node_type.to_string(),
+        success: result.is_ok(),
+        message: result.unwrap_or_else(|error| error),
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// Credential fields as node input. Credentials store every value as a
+/// string, so fields the node declares as numbers or booleans are converted
+/// back when they parse. Whole numbers stay integers, since nodes read ports
+/// and counts with `as_u64`.
+pub(crate) fn credential_input(definition: &NodeDefinition, data: &HashMap<String, String>) -> Value {
+    let input = data
+        .iter()
+        .map(|(name, value)| {
+            let param_type = definition.parameters.iter().find(|p| &p.name == name).map(|p| &p.param_type);
+            let typed = match param_type {
+                Some(ParameterType::Number) => parse_number(value.trim()),
+                Some(ParameterType::Boolean) => value.trim().parse::<bool>().ok().map(Value::Bool),
+                _ => None,
+            };
+            (name.clone(), typed.unwrap_or_else(|| Value::String(value.clone())))
+        })
+        .collect();
+    Value::Object(input)
+}
+
+fn parse_number(value: &str) -> Option<Value> {
+    value
+        .parse::<u64>()
+        .map(Value::from)
+        .or_else(|_| value.parse::<i64>().map(Value::from))
+        .or_else(|_| value.parse::<f64>().map(Value::from))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use ghostflow_core::{BasicNodeRegistry, GhostFlowError, Node};
+    use ghostflow_schema::{NodeCategory, NodeParameter};
+
+    /// Accepts the token "valid" on the given port.
+    struct TokenNode;
+
+    #[async_trait]
+    impl Node for TokenNode {
+        fn definition(&self) -> NodeDefinition {
+            let param = |name: &str, param_type| NodeParameter {
+                name: name.to_string(),
+                display_name: name.to_string(),
+                description: None,
+                param_type,
+                default_value: None,
+                required: true,
+                options: None,
+                validation: None,
+            };
+            NodeDefinition {
+                id: "token_api".to_string(),
+                name: "Token API".to_string(),
+                description: "Test node".to_string(),
+                category: NodeCategory::Integration,
+                version: "1.0.0".to_string(),
+                inputs: vec![],
+                outputs: vec![],
+                parameters: vec![param("token", ParameterType::Secret), param("port", ParameterType::Number)],
+                icon: None,
+                color: None,
+            }
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> ghostflow_core::Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, _context: ExecutionContext) -> ghostflow_core::Result<Value> {
+            Ok(Value::Null)
+        }
+
+        fn supports_connection_test(&self) -> bool {
+            true
+        }
+
+        async fn test_connection(&self, context: &ExecutionContext) -> ghostflow_core::Result<String> {
+            match (context.input["token"].as_str(), context.input["port"].as_f64()) {
+                (Some("valid"), Some(port)) => Ok(format!("Authenticated on port {}", port)),
+                _ => Err(GhostFlowError::AuthenticationError {
+                    message: "token rejected".to_string(),
+                }),
+            }
+        }
+    }
+
+    struct PlainNode;
+
+    #[async_trait]
+    impl Node for PlainNode {
+        fn definition(&self) -> NodeDefinition {
+            let mut definition = TokenNode.definition();
+            definition.id = "plain".to_string();
+            definition
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> ghostflow_core::Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, _context: ExecutionContext) -> ghostflow_core::Result<Value> {
+            Ok(Value::Null)
+        }
+    }
+
+    fn registry() -> BasicNodeRegistry {
+        let mut registry = BasicNodeRegistry::new();
+        registry.register_node("token_api".to_string(), Arc::new(TokenNode)).unwrap();
+        registry.register_node("plain".to_string(), Arc::new(PlainNode)).unwrap();
+        registry
+    }
+
+    fn credential(token: &str) -> Credential {
+        Credential {
+            id: "cred-1".to_string(),
+            name: "Token API".to_string(),
+            credential_type: CredentialType::Custom("token_api".to_string()),
+            data: HashMap::from([
+                ("token".to_string(), token.to_string()),
+                ("port".to_string(), "8443".to_string()),
+            ]),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            workspace_id: "default".to_string(),
+            encrypted: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_valid_credential_passes() {
+        let credential = credential("valid");
+        let node_type = credential_node_type(&credential).unwrap();
+
+        let response = run_connection_test(&registry(), &credential, &node_type).await.unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.node_type, "token_api");
+        assert_eq!(response.message, "Authenticated on port 8443");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_credential_reports_failure() {
+        let response = run_connection_test(&registry(), &credential("expired"), "token_api").await.unwrap();
+
+        assert!(!response.success);
+        assert!(response.message.contains("token rejected"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_or_untestable_node_type_is_a_bad_request() {
+        for node_type in ["plain", "missing"] {
+            let error = run_connection_test(&registry(), &credential("valid"), node_type).await.unwrap_err();
+            assert!(matches!(error, ApiError::BadRequest(_)), "{}", node_type);
+        }
+    }
+
+    #[test]
+    fn test_numbers_keep_their_integer_type() {
+        let input = credential_input(
+            &TokenNode.definition(),
+            &HashMap::from([("port".to_string(), "8443".to_string()), ("token".to_string(), "42".to_string())]),
+        );
+        assert_eq!(input["port"].as_u64(), Some(8443));
+        assert_eq!(input["token"], "42");
+
+        assert_eq!(parse_number("-5").unwrap().as_i64(), Some(-5));
+        assert_eq!(parse_number("0.5").unwrap().as_f64(), Some(0.5));
+        assert_eq!(parse_number("22.0").unwrap().as_u64(), None);
+    }
+
+    #[test]
+    fn test_credential_port_reaches_sftp_node() {
+        let data = HashMap::from([
+            ("host".to_string(), "files.example.com".to_string()),
+            ("port".to_string(), "2222".to_string()),
+            ("username".to_string(), "deploy".to_string()),
+        ]);
+        let input = credential_input(&ghostflow_nodes::SftpNode::new().definition(), &data);
+
+        // The SFTP node reads its port with `as_u64`, falling back to 22
+        assert_eq!(input["port"].as_u64(), Some(2222));
+    }
+}
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{AppState, ApiError, ApiResult};
+use ghostflow_schema::{DeadLetter, FlowExecution};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLetterListResponse {
+    pub dead_letters: Vec<DeadLetter>,
+    pub total: usize,
+}
+
+/// `GET /api/deadletter` — failed executions awaiting retry, most recent first.
+pub async fn list_dead_letters(State(state): State<Arc<AppState>>) -> ApiResult<Json<DeadLetterListResponse>> {
+    let dead_letters = state.runtime.list_dead_letters().await?;
+    Ok(Json(DeadLetterListResponse {
+        total: dead_letters.len(),
+        dead_letters,
+    }))
+}
+
+/// `POST /api/deadletter/:id/retry` — run the failed execution's flow again
+/// with its original input. Returns the new execution.
+pub async fn retry_dead_letter(
+    Path(execution_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<FlowExecution>> {
+    let id = Uuid::parse_str(&execution_id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid execution id '{}'", execution_id)))?;
+    let execution = state.runtime.retry_dead_letter(&id).await?;
+    Ok(Json(execution))
+}
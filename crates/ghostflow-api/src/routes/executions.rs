@@ -0,0 +1,166 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{AppState, ApiError, ApiResult};
+use ghostflow_core::{Approval, ApprovalDecision, PendingApproval};
+use ghostflow_schema::FlowExecution;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExecutionListQuery {
+    pub flow_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExecutionListResponse {
+    pub executions: Vec<FlowExecution>,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CancelExecutionResponse {
+    pub execution_id: Uuid,
+    pub cancelled: bool,
+}
+
+pub async fn list_executions(
+    Query(query): Query<ExecutionListQuery>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<ExecutionListResponse>> {
+    let executions: Vec<FlowExecution> = state
+        .runtime
+        .list_executions()
+        .await
+        .into_iter()
+        .filter(|e| query.flow_id.map_or(true, |flow_id| e.flow_id == flow_id))
+        .collect();
+
+    Ok(Json(ExecutionListResponse {
+        total: executions.len(),
+        executions,
+    }))
+}
+
+/// `GET /api/executions/:id` — the execution including its per-node records.
+pub async fn get_execution(
+    Path(execution_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<FlowExecution>> {
+    let id = parse_execution_id(&execution_id)?;
+    let execution = state
+        .runtime
+        .get_execution(&id)
+        .await
+        .ok_or_else(|| ApiError::NotFound(format!("Execution '{}' not found", execution_id)))?;
+    Ok(Json(execution))
+}
+
+pub async fn cancel_execution(
+    Path(execution_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<CancelExecutionResponse>> {
+    let id = parse_execution_id(&execution_id)?;
+    let cancelled = state.runtime.cancel_execution(id);
+    Ok(Json(CancelExecutionResponse {
+        execution_id: id,
+        cancelled,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplayExecutionRequest {
+    /// Use recorded outputs for nodes that are not deterministic instead of
+    /// running them again
+    #[serde(default = "default_stub_non_deterministic")]
+    pub stub_non_deterministic: bool,
+}
+
+fn default_stub_non_deterministic() -> bool {
+    true
+}
+
+/// `POST /api/executions/:id/replay` — run the execution again with the
+/// inputs each node recorded. The body is optional.
+pub async fn replay_execution(
+    Path(execution_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    request: Option<Json<ReplayExecutionRequest>>,
+) -> ApiResult<Json<FlowExecution>> {
+    let id = parse_execution_id(&execution_id)?;
+    let stub = request.map_or(true, |Json(r)| r.stub_non_deterministic);
+    let execution = state.runtime.replay_execution(&id, stub).await?;
+    Ok(Json(execution))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    #[serde(default)]
+    pub approver: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// Approval node to answer; only needed when the execution waits on
+    /// more than one
+    #[serde(default)]
+    pub node_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApprovalResponse {
+    pub execution_id: Uuid,
+    pub node_id: String,
+    pub approval: Approval,
+}
+
+/// `GET /api/executions/:id/approvals` — approvals the execution is waiting on.
+pub async fn list_pending_approvals(
+    State(state): State<Arc<AppState>>,
+    Path(execution_id): Path<String>,
+) -> ApiResult<Json<Vec<PendingApproval>>> {
+    let id = parse_execution_id(&execution_id)?;
+    Ok(Json(state.runtime.services().approvals.pending(id)))
+}
+
+/// `POST /api/executions/:id/approve` — resume an execution suspended at an
+/// approval node. The body is optional.
+pub async fn approve_execution(
+    State(state): State<Arc<AppState>>,
+    Path(execution_id): Path<String>,
+    request: Option<Json<ApprovalRequest>>,
+) -> ApiResult<Json<ApprovalResponse>> {
+    decide(&state, &execution_id, ApprovalDecision::Approved, request.map(|Json(r)| r).unwrap_or_default())
+}
+
+/// `POST /api/executions/:id/reject` — fail the approval node, and with it
+/// the execution unless the flow handles the error. The body is optional.
+pub async fn reject_execution(
+    State(state): State<Arc<AppState>>,
+    Path(execution_id): Path<String>,
+    request: Option<Json<ApprovalRequest>>,
+) -> ApiResult<Json<ApprovalResponse>> {
+    decide(&state, &execution_id, ApprovalDecision::Rejected, request.map(|Json(r)| r).unwrap_or_default())
+}
+
+fn decide(
+    state: &AppState,
+    execution_id: &str,
+    decision: ApprovalDecision,
+    request: ApprovalRequest,
+) -> ApiResult<Json<ApprovalResponse>> {
+    let id = parse_execution_id(execution_id)?;
+    let approval = Approval::new(decision, request.approver, request.comment);
+    let pending = state.runtime.services().approvals.decide(id, request.node_id.as_deref(), approval.clone())?;
+    Ok(Json(ApprovalResponse {
+        execution_id: id,
+        node_id: pending.node_id,
+        approval,
+    }))
+}
+
+fn parse_execution_id(execution_id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(execution_id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid execution id '{}'", execution_id)))
+}
@@ -1,6 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Json,
 };
 use serde::{Deserialize, Serialize};
@@ -9,8 +9,12 @@ use std::sync::Arc;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-use crate::{AppState, ApiError, ApiResult};
-use ghostflow_schema::{Flow, FlowStatus, ExecutionStatus};
+use crate::{AppState, ApiError, ApiResult, LimitedJson};
+use ghostflow_engine::{Diagnostic, FlowValidator};
+use ghostflow_schema::{
+    ExecutionStatus, Flow, FlowEdge, FlowMetadata, FlowNode, FlowStatus, FlowTrigger, NodePosition,
+    NodeValidationReport, OverflowPolicy, TriggerType,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateFlowRequest {
@@ -20,6 +24,8 @@ pub struct CreateFlowRequest {
     pub edges: Vec<FlowEdgeRequest>,
     pub triggers: Vec<FlowTriggerRequest>,
     pub schedule: Option<String>,
+    /// Defaults to the `default` workspace
+    pub workspace_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,6 +82,8 @@ pub struct FlowResponse {
     pub id: String,
     pub name: String,
     pub description: Option<String>,
+    /// Storage version of this snapshot; see `FlowStorage::save_flow`
+    pub version: String,
     pub status: FlowStatus,
     pub nodes: Vec<FlowNodeResponse>,
     pub edges: Vec<FlowEdgeResponse>,
@@ -140,32 +148,24 @@ pub struct FlowSummary {
     pub execution_count: u64,
 }
 
+/// Errors make a flow invalid; warnings point at likely mistakes but do not.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ValidateFlowResponse {
     pub valid: bool,
-    pub errors: Vec<FlowValidationError>,
-    pub warnings: Vec<FlowValidationWarning>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FlowValidationError {
-    pub node_id: Option<String>,
-    pub edge_id: Option<String>,
-    pub error_type: String,
-    pub message: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FlowValidationWarning {
-    pub node_id: Option<String>,
-    pub warning_type: String,
-    pub message: String,
+    pub errors: Vec<Diagnostic>,
+    pub warnings: Vec<Diagnostic>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecuteFlowRequest {
     pub input_data: Option<HashMap<String, serde_json::Value>>,
     pub manual_trigger: bool,
+    /// Validate every node without executing any of them
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Environment to run in, supplying flow parameters and credentials
+    #[serde(default)]
+    pub environment: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -173,6 +173,9 @@ pub struct ExecuteFlowResponse {
     pub execution_id: String,
     pub status: ExecutionStatus,
     pub started_at: DateTime<Utc>,
+    /// Per-node findings, present only for dry runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_report: Option<Vec<NodeValidationReport>>,
 }
 
 // Flow management handlers
@@ -235,158 +238,212 @@ pub async fn list_flows(
 
 pub async fn create_flow(
     State(state): State<Arc<AppState>>,
-    Json(request): Json<CreateFlowRequest>,
+    LimitedJson(request): LimitedJson<CreateFlowRequest>,
 ) -> ApiResult<Json<FlowResponse>> {
-    let flow_id = Uuid::new_v4().to_string();
     let now = Utc::now();
-    
-    // TODO: Validate flow structure
-    // TODO: Save to database
-    
-    let response = FlowResponse {
-        id: flow_id,
+    let mut triggers = flow_triggers(request.triggers)?;
+    if let Some(expression) = request.schedule {
+        set_schedule(&mut triggers, expression);
+    }
+
+    let mut flow = Flow {
+        id: Uuid::new_v4(),
         name: request.name,
         description: request.description,
-        status: FlowStatus::Draft,
-        nodes: request.nodes.into_iter().map(|n| FlowNodeResponse {
-            id: n.id,
-            node_type: n.node_type,
-            position: n.position,
-            parameters: n.parameters,
-        }).collect(),
-        edges: request.edges.into_iter().map(|e| FlowEdgeResponse {
-            id: e.id,
-            source_node: e.source_node,
-            source_output: e.source_output,
-            target_node: e.target_node,
-            target_input: e.target_input,
-        }).collect(),
-        triggers: request.triggers.into_iter().map(|t| FlowTriggerResponse {
-            trigger_type: t.trigger_type,
-            configuration: t.configuration,
-        }).collect(),
-        schedule: request.schedule,
-        created_at: now,
-        updated_at: now,
-        last_execution: None,
-        execution_count: 0,
+        version: String::new(),
+        nodes: flow_nodes(request.nodes),
+        edges: flow_edges(request.edges),
+        triggers,
+        parameters: HashMap::new(),
+        secrets: vec![],
+        max_duration_ms: None,
+        max_concurrent_executions: None,
+        overflow_policy: OverflowPolicy::default(),
+        metadata: FlowMetadata {
+            created_at: now,
+            updated_at: now,
+            created_by: "api".to_string(),
+            tags: vec![],
+            category: None,
+            workspace_id: request.workspace_id.unwrap_or_else(|| "default".to_string()),
+        },
     };
-    
-    Ok(Json(response))
+    flow.version = state.flow_storage.save_flow(&flow).await?.to_string();
+
+    Ok(Json(flow_response(&flow)))
 }
 
 pub async fn get_flow(
     Path(flow_id): Path<String>,
     State(state): State<Arc<AppState>>,
 ) -> ApiResult<Json<FlowResponse>> {
-    // TODO: Get from database
-    // For now, return mock data
-    if flow_id == "flow_001" {
-        let response = FlowResponse {
-            id: flow_id,
-            name: "Discord Alert System".to_string(),
-            description: Some("Send security alerts to Discord channels with severity filtering".to_string()),
-            status: FlowStatus::Active,
-            nodes: vec![
-                FlowNodeResponse {
-                    id: "node_001".to_string(),
-                    node_type: "wazuh_api".to_string(),
-                    position: Position { x: 100.0, y: 100.0 },
-                    parameters: {
-                        let mut params = HashMap::new();
-                        params.insert("base_url".to_string(), serde_json::Value::String("https://wazuh:55000".to_string()));
-                        params.insert("operation".to_string(), serde_json::Value::String("get_alerts".to_string()));
-                        params
-                    },
-                },
-                FlowNodeResponse {
-                    id: "node_002".to_string(),
-                    node_type: "discord_alert_bot".to_string(),
-                    position: Position { x: 400.0, y: 100.0 },
-                    parameters: {
-                        let mut params = HashMap::new();
-                        params.insert("webhook_url".to_string(), serde_json::Value::String("https://discord.com/api/webhooks/...".to_string()));
-                        params.insert("alert_type".to_string(), serde_json::Value::String("critical".to_string()));
-                        params
-                    },
-                },
-            ],
-            edges: vec![
-                FlowEdgeResponse {
-                    id: "edge_001".to_string(),
-                    source_node: "node_001".to_string(),
-                    source_output: "alerts".to_string(),
-                    target_node: "node_002".to_string(),
-                    target_input: "trigger".to_string(),
-                },
-            ],
-            triggers: vec![
-                FlowTriggerResponse {
-                    trigger_type: "schedule".to_string(),
-                    configuration: {
-                        let mut config = HashMap::new();
-                        config.insert("cron".to_string(), serde_json::Value::String("0 */5 * * * *".to_string()));
-                        config
-                    },
-                },
-            ],
-            schedule: Some("0 */5 * * * *".to_string()),
-            created_at: Utc::now() - chrono::Duration::days(1),
-            updated_at: Utc::now() - chrono::Duration::hours(2),
-            last_execution: Some(ExecutionSummary {
-                id: "exec_001".to_string(),
-                status: ExecutionStatus::Completed,
-                started_at: Utc::now() - chrono::Duration::minutes(30),
-                completed_at: Some(Utc::now() - chrono::Duration::minutes(29)),
-                duration_ms: Some(60000),
-            }),
-            execution_count: 42,
-        };
-        
-        Ok(Json(response))
-    } else {
-        Err(ApiError::NotFound("Flow not found".to_string()))
-    }
+    let flow_id = Uuid::parse_str(&flow_id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid flow id '{}'", flow_id)))?;
+    let flow = state
+        .flow_storage
+        .get_flow(&flow_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Flow '{}' not found", flow_id)))?;
+
+    Ok(Json(flow_response(&flow)))
 }
 
+/// `PUT /api/flows/:id` — apply the fields that are set and save the result
+/// as a new version.
 pub async fn update_flow(
     Path(flow_id): Path<String>,
     State(state): State<Arc<AppState>>,
-    Json(request): Json<UpdateFlowRequest>,
+    LimitedJson(request): LimitedJson<UpdateFlowRequest>,
 ) -> ApiResult<Json<FlowResponse>> {
-    // TODO: Update in database
-    // For now, return updated mock data
-    
-    let mut response = FlowResponse {
-        id: flow_id,
-        name: request.name.unwrap_or_else(|| "Updated Flow".to_string()),
-        description: request.description,
-        status: request.status.unwrap_or(FlowStatus::Draft),
-        nodes: request.nodes.unwrap_or_default().into_iter().map(|n| FlowNodeResponse {
-            id: n.id,
-            node_type: n.node_type,
-            position: n.position,
-            parameters: n.parameters,
-        }).collect(),
-        edges: request.edges.unwrap_or_default().into_iter().map(|e| FlowEdgeResponse {
+    let flow_id = Uuid::parse_str(&flow_id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid flow id '{}'", flow_id)))?;
+    let mut flow = state
+        .flow_storage
+        .get_flow(&flow_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Flow '{}' not found", flow_id)))?;
+
+    if let Some(name) = request.name {
+        flow.name = name;
+    }
+    if request.description.is_some() {
+        flow.description = request.description;
+    }
+    if let Some(nodes) = request.nodes {
+        flow.nodes = flow_nodes(nodes);
+    }
+    if let Some(edges) = request.edges {
+        flow.edges = flow_edges(edges);
+    }
+    if let Some(triggers) = request.triggers {
+        flow.triggers = flow_triggers(triggers)?;
+    }
+    if let Some(expression) = request.schedule {
+        set_schedule(&mut flow.triggers, expression);
+    }
+    flow.metadata.updated_at = Utc::now();
+    flow.version = state.flow_storage.save_flow(&flow).await?.to_string();
+
+    Ok(Json(flow_response(&flow)))
+}
+
+fn flow_nodes(nodes: Vec<FlowNodeRequest>) -> HashMap<String, FlowNode> {
+    nodes
+        .into_iter()
+        .map(|n| {
+            let node = FlowNode {
+                id: n.id.clone(),
+                node_type: n.node_type,
+                name: n.id.clone(),
+                description: None,
+                parameters: n.parameters,
+                position: NodePosition { x: n.position.x, y: n.position.y },
+                retry_config: None,
+                timeout_ms: None,
+                cache_ttl_ms: None,
+                node_version: None,
+            };
+            (n.id, node)
+        })
+        .collect()
+}
+
+fn flow_edges(edges: Vec<FlowEdgeRequest>) -> Vec<FlowEdge> {
+    edges
+        .into_iter()
+        .map(|e| FlowEdge {
             id: e.id,
             source_node: e.source_node,
-            source_output: e.source_output,
             target_node: e.target_node,
-            target_input: e.target_input,
-        }).collect(),
-        triggers: request.triggers.unwrap_or_default().into_iter().map(|t| FlowTriggerResponse {
-            trigger_type: t.trigger_type,
-            configuration: t.configuration,
-        }).collect(),
-        schedule: request.schedule,
-        created_at: Utc::now() - chrono::Duration::days(1),
-        updated_at: Utc::now(),
+            source_port: Some(e.source_output),
+            target_port: Some(e.target_input),
+            condition: None,
+            coerce: false,
+        })
+        .collect()
+}
+
+/// Each request trigger names a [`TriggerType`] whose fields are read from
+/// its configuration; the whole configuration is kept as the trigger config.
+fn flow_triggers(triggers: Vec<FlowTriggerRequest>) -> ApiResult<Vec<FlowTrigger>> {
+    triggers
+        .into_iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let trigger_type = serde_json::from_value(serde_json::json!({ "type": t.trigger_type, "config": t.configuration }))
+                .or_else(|_| serde_json::from_value(serde_json::json!({ "type": t.trigger_type })))
+                .map_err(|e| ApiError::BadRequest(format!("Invalid '{}' trigger: {}", t.trigger_type, e)))?;
+            Ok(FlowTrigger {
+                id: format!("trigger_{}", i + 1),
+                trigger_type,
+                config: t.configuration,
+                enabled: true,
+                priority: 0,
+            })
+        })
+        .collect()
+}
+
+/// Replace any cron triggers with one running `expression`
+fn set_schedule(triggers: &mut Vec<FlowTrigger>, expression: String) {
+    triggers.retain(|t| !matches!(t.trigger_type, TriggerType::Cron { .. }));
+    triggers.push(FlowTrigger {
+        id: "schedule".to_string(),
+        trigger_type: TriggerType::Cron { expression, timezone: None },
+        config: HashMap::new(),
+        enabled: true,
+        priority: 0,
+    });
+}
+
+fn flow_response(flow: &Flow) -> FlowResponse {
+    FlowResponse {
+        id: flow.id.to_string(),
+        name: flow.name.clone(),
+        description: flow.description.clone(),
+        version: flow.version.clone(),
+        status: FlowStatus::Draft,
+        nodes: flow
+            .nodes
+            .values()
+            .map(|n| FlowNodeResponse {
+                id: n.id.clone(),
+                node_type: n.node_type.clone(),
+                position: Position { x: n.position.x, y: n.position.y },
+                parameters: n.parameters.clone(),
+            })
+            .collect(),
+        edges: flow
+            .edges
+            .iter()
+            .map(|e| FlowEdgeResponse {
+                id: e.id.clone(),
+                source_node: e.source_node.clone(),
+                source_output: e.source_port.clone().unwrap_or_default(),
+                target_node: e.target_node.clone(),
+                target_input: e.target_port.clone().unwrap_or_default(),
+            })
+            .collect(),
+        triggers: flow
+            .triggers
+            .iter()
+            .map(|t| FlowTriggerResponse {
+                trigger_type: serde_json::to_value(&t.trigger_type)
+                    .ok()
+                    .and_then(|v| v["type"].as_str().map(str::to_string))
+                    .unwrap_or_default(),
+                configuration: t.config.clone(),
+            })
+            .collect(),
+        schedule: flow.triggers.iter().find_map(|t| match &t.trigger_type {
+            TriggerType::Cron { expression, .. } => Some(expression.clone()),
+            _ => None,
+        }),
+        created_at: flow.metadata.created_at,
+        updated_at: flow.metadata.updated_at,
         last_execution: None,
         execution_count: 0,
-    };
-    
-    Ok(Json(response))
+    }
 }
 
 pub async fn delete_flow(
@@ -399,45 +456,227 @@ pub async fn delete_flow(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// `POST /api/flows/:id/validate` — static checks of the saved flow. Unknown
+/// node types, cycles, dangling edges and missing required parameters are
+/// errors; unconnected nodes, unread variables and credentials that are not in
+/// the vault are warnings.
 pub async fn validate_flow(
     Path(flow_id): Path<String>,
     State(state): State<Arc<AppState>>,
 ) -> ApiResult<Json<ValidateFlowResponse>> {
-    // TODO: Implement actual flow validation
-    // Check for circular dependencies, missing connections, invalid parameters, etc.
-    
-    let response = ValidateFlowResponse {
-        valid: true,
-        errors: vec![],
-        warnings: vec![
-            FlowValidationWarning {
-                node_id: Some("node_001".to_string()),
-                warning_type: "performance".to_string(),
-                message: "This node may run slowly with large datasets".to_string(),
-            },
-        ],
-    };
-    
-    Ok(Json(response))
+    let flow_id = Uuid::parse_str(&flow_id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid flow id '{}'", flow_id)))?;
+    let flow = state
+        .flow_storage
+        .get_flow(&flow_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Flow '{}' not found", flow_id)))?;
+
+    let credentials = state
+        .credential_vault
+        .list("default")
+        .await?
+        .into_iter()
+        .flat_map(|c| [c.id, c.name])
+        .collect();
+    let report = FlowValidator::new(state.node_registry.as_ref())
+        .with_known_credentials(credentials)
+        .validate(&flow);
+
+    Ok(Json(ValidateFlowResponse {
+        valid: report.valid,
+        errors: report.errors().cloned().collect(),
+        warnings: report.warnings().cloned().collect(),
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FlowVersionSummary {
+    pub version: u32,
+    pub saved_at: DateTime<Utc>,
+    pub name: String,
+    pub node_count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RollbackFlowResponse {
+    pub flow_id: String,
+    /// New current version holding the restored flow
+    pub version: u32,
+    pub restored_from: u32,
+}
+
+/// `GET /api/flows/:id/versions` — saved versions, oldest first.
+pub async fn list_flow_versions(
+    Path(flow_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<FlowVersionSummary>>> {
+    let flow_id = Uuid::parse_str(&flow_id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid flow id '{}'", flow_id)))?;
+
+    let versions = state.flow_storage.list_versions(&flow_id).await?;
+    if versions.is_empty() {
+        return Err(ApiError::NotFound(format!("Flow '{}' not found", flow_id)));
+    }
+
+    Ok(Json(
+        versions
+            .into_iter()
+            .map(|v| FlowVersionSummary {
+                version: v.version,
+                saved_at: v.saved_at,
+                name: v.flow.name,
+                node_count: v.flow.nodes.len() as u32,
+            })
+            .collect(),
+    ))
+}
+
+/// `POST /api/flows/:id/rollback/:version` — restore an old version as a new
+/// current version. A deployed flow is redeployed so the next run uses it.
+pub async fn rollback_flow(
+    Path((flow_id, version)): Path<(String, u32)>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<RollbackFlowResponse>> {
+    let flow_id = Uuid::parse_str(&flow_id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid flow id '{}'", flow_id)))?;
+
+    let new_version = state.flow_storage.rollback_flow(&flow_id, version).await?;
+
+    if state.runtime.get_flow(&flow_id).await.is_some() {
+        if let Some(flow) = state.flow_storage.get_flow(&flow_id).await? {
+            state.runtime.deploy_flow(flow).await?;
+        }
+    }
+
+    Ok(Json(RollbackFlowResponse {
+        flow_id: flow_id.to_string(),
+        version: new_version,
+        restored_from: version,
+    }))
 }
 
+/// Header clients set so a retried request returns the original execution
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
 pub async fn execute_flow(
     Path(flow_id): Path<String>,
     State(state): State<Arc<AppState>>,
-    Json(request): Json<ExecuteFlowRequest>,
+    headers: HeaderMap,
+    LimitedJson(request): LimitedJson<ExecuteFlowRequest>,
 ) -> ApiResult<Json<ExecuteFlowResponse>> {
-    let execution_id = Uuid::new_v4().to_string();
-    let now = Utc::now();
-    
-    // TODO: Start actual flow execution
+    let flow_id = Uuid::parse_str(&flow_id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid flow id '{}'", flow_id)))?;
+    let input_data = serde_json::to_value(request.input_data.unwrap_or_default())
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    // Input is checked against the manual trigger's schema before any node
+    // runs; violations come back as 400 with one entry per field
+    let idempotency_key = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|value| value.to_str())
+        .transpose()
+        .map_err(|_| ApiError::BadRequest(format!("{} must be valid ASCII", IDEMPOTENCY_KEY_HEADER)))?
+        .filter(|key| !key.is_empty());
+
+    let execution = match idempotency_key {
+        Some(key) => {
+            state
+                .runtime
+                .execute_flow_idempotent(&flow_id, key, input_data, request.dry_run, request.environment.as_deref())
+                .await?
+        }
+        None => {
+            state
+                .runtime
+                .execute_flow_manually(&flow_id, input_data, request.dry_run, request.environment.as_deref())
+                .await?
+        }
+    };
+
     // TODO: Store execution record in database
     // TODO: Send WebSocket notification
     
     let response = ExecuteFlowResponse {
-        execution_id,
-        status: ExecutionStatus::Running,
-        started_at: now,
+        execution_id: execution.id.to_string(),
+        status: execution.status,
+        started_at: execution.started_at,
+        validation_report: request.dry_run.then_some(execution.validation_report),
     };
     
     Ok(Json(response))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use crate::test_support::app_state;
+    use ghostflow_core::BasicNodeRegistry;
+    use tower::ServiceExt;
+
+    fn state() -> Arc<AppState> {
+        Arc::new(app_state(Arc::new(BasicNodeRegistry::new())))
+    }
+
+    async fn send(state: &Arc<AppState>, method: &str, uri: &str, body: serde_json::Value) -> (StatusCode, serde_json::Value) {
+        let response = crate::create_api_router(state.clone())
+            .unwrap()
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_created_flow_is_stored_and_updates_add_versions() {
+        let state = state();
+        let (status, created) = send(
+            &state,
+            "POST",
+            "/api/flows",
+            serde_json::json!({
+                "name": "Restart VM",
+                "description": null,
+                "nodes": [{ "id": "restart", "node_type": "proxmox", "position": { "x": 0.0, "y": 0.0 }, "parameters": { "vmid": 101 } }],
+                "edges": [],
+                "triggers": [{ "trigger_type": "manual", "configuration": {} }],
+                "schedule": "0 3 * * *",
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(created["version"], "1");
+        assert_eq!(created["schedule"], "0 3 * * *");
+
+        let id = Uuid::parse_str(created["id"].as_str().unwrap()).unwrap();
+        let stored = state.flow_storage.get_flow(&id).await.unwrap().unwrap();
+        assert_eq!(stored.nodes["restart"].parameters["vmid"], 101);
+        assert_eq!(stored.triggers.len(), 2);
+
+        let (status, updated) = send(&state, "PUT", &format!("/api/flows/{}", id), serde_json::json!({ "name": "Restart VM (notify)" })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["version"], "2");
+        assert_eq!(updated["nodes"][0]["id"], "restart");
+
+        let (_, fetched) = send(&state, "GET", &format!("/api/flows/{}", id), serde_json::Value::Null).await;
+        assert_eq!(fetched["name"], "Restart VM (notify)");
+        assert_eq!(state.flow_storage.list_versions(&id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_updating_unknown_flow_is_not_found() {
+        let (status, _) = send(&state(), "PUT", &format!("/api/flows/{}", Uuid::new_v4()), serde_json::json!({ "name": "x" })).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
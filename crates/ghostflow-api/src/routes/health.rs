@@ -0,0 +1,239 @@
+use async_trait::async_trait;
+use axum::{extract::State, http::StatusCode, Json};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::AppState;
+
+/// How long one readiness check may take before it counts as down
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A dependency `/health/ready` verifies before the server takes traffic.
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Whether the server is unready while this dependency is down. Failing
+    /// non-critical checks are reported but leave the status at 200.
+    fn critical(&self) -> bool {
+        true
+    }
+
+    /// `Err` carries a short reason shown in the readiness body.
+    async fn check(&self) -> Result<(), String>;
+}
+
+/// The Postgres pool behind flow and execution storage.
+pub struct DatabaseHealthCheck {
+    pool: PgPool,
+}
+
+impl DatabaseHealthCheck {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DatabaseHealthCheck {
+    fn name(&self) -> &str {
+        "storage"
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// A TCP endpoint a flow's nodes depend on, such as a Wazuh manager or a
+/// Proxmox host. Reachable means a connection can be opened.
+pub struct TcpHealthCheck {
+    name: String,
+    address: String,
+    critical: bool,
+}
+
+impl TcpHealthCheck {
+    pub fn new(name: impl Into<String>, address: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            address: address.into(),
+            critical: true,
+        }
+    }
+
+    /// Report the dependency without failing readiness when it is down.
+    pub fn optional(mut self) -> Self {
+        self.critical = false;
+        self
+    }
+}
+
+#[async_trait]
+impl HealthCheck for TcpHealthCheck {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn critical(&self) -> bool {
+        self.critical
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        tokio::net::TcpStream::connect(&self.address)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("{}: {}", self.address, e))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyStatus {
+    pub name: String,
+    pub status: HealthStatus,
+    pub critical: bool,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    /// `ready`, `degraded` (only optional dependencies down) or `unavailable`
+    pub status: String,
+    pub dependencies: Vec<DependencyStatus>,
+}
+
+/// `GET /health` — liveness. Answers as long as the process can serve
+/// requests; dependencies are not checked.
+pub async fn health_check() -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+    }))
+}
+
+/// `GET /health/ready` — readiness. Checks storage and every configured
+/// dependency, answering 503 when a critical one is down.
+pub async fn readiness_check(State(state): State<Arc<AppState>>) -> (StatusCode, Json<ReadinessResponse>) {
+    let mut checks: Vec<Arc<dyn HealthCheck>> = vec![Arc::new(DatabaseHealthCheck::new(state.db_pool.clone()))];
+    checks.extend(state.health_checks.iter().cloned());
+
+    let (status, response) = run_health_checks(&checks).await;
+    (status, Json(response))
+}
+
+/// Run `checks` concurrently, each bounded by [`HEALTH_CHECK_TIMEOUT`].
+pub async fn run_health_checks(checks: &[Arc<dyn HealthCheck>]) -> (StatusCode, ReadinessResponse) {
+    let dependencies = futures::future::join_all(checks.iter().map(|check| async move {
+        let started = Instant::now();
+        let result = match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, check.check()).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("no answer within {}s", HEALTH_CHECK_TIMEOUT.as_secs())),
+        };
+        DependencyStatus {
+            name: check.name().to_string(),
+            status: if result.is_ok() { HealthStatus::Up } else { HealthStatus::Down },
+            critical: check.critical(),
+            latency_ms: started.elapsed().as_millis() as u64,
+            error: result.err(),
+        }
+    }))
+    .await;
+
+    let down = |critical: bool| {
+        dependencies
+            .iter()
+            .any(|d| d.status == HealthStatus::Down && d.critical == critical)
+    };
+    let (status, label) = if down(true) {
+        (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+    } else if down(false) {
+        (StatusCode::OK, "degraded")
+    } else {
+        (StatusCode::OK, "ready")
+    };
+
+    (
+        status,
+        ReadinessResponse {
+            status: label.to_string(),
+            dependencies,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use ghostflow_core::BasicNodeRegistry;
+    use tower::ServiceExt;
+
+    fn app_state() -> AppState {
+        crate::test_support::app_state(Arc::new(BasicNodeRegistry::new()))
+    }
+
+    async fn get(state: AppState, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = crate::create_api_router(Arc::new(state))
+            .unwrap()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_ready_is_unavailable_when_storage_is_down() {
+        let (status, body) = get(app_state(), "/health/ready").await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "unavailable");
+        assert_eq!(body["dependencies"][0]["name"], "storage");
+        assert_eq!(body["dependencies"][0]["status"], "down");
+        assert!(body["dependencies"][0]["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_liveness_ignores_dependencies() {
+        let (status, body) = get(app_state(), "/health").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "ok");
+    }
+
+    #[tokio::test]
+    async fn test_optional_dependency_only_degrades() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let reachable = listener.local_addr().unwrap().to_string();
+        let checks: Vec<Arc<dyn HealthCheck>> = vec![
+            Arc::new(TcpHealthCheck::new("wazuh", reachable)),
+            Arc::new(TcpHealthCheck::new("proxmox", "127.0.0.1:1").optional()),
+        ];
+
+        let (status, response) = run_health_checks(&checks).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response.status, "degraded");
+        assert_eq!(response.dependencies[0].status, HealthStatus::Up);
+        assert_eq!(response.dependencies[1].status, HealthStatus::Down);
+
+        let required: Vec<Arc<dyn HealthCheck>> = vec![Arc::new(TcpHealthCheck::new("proxmox", "127.0.0.1:1"))];
+        let (status, _) = run_health_checks(&required).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -1,11 +1,17 @@
 pub mod flows;
 pub mod executions;
 pub mod nodes;
+pub mod templates;
+pub mod webhooks;
 pub mod credentials;
+pub mod dead_letters;
 pub mod health;
 
 pub use flows::*;
 pub use executions::*;
 pub use nodes::*;
+pub use templates::*;
+pub use webhooks::*;
 pub use credentials::*;
+pub use dead_letters::*;
 pub use health::*;
\ No newline at end of file
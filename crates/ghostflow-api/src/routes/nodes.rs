@@ -3,11 +3,19 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
 
-use crate::{AppState, ApiResult};
-use ghostflow_core::{NodeDefinition, NodeParameter, ParameterType};
+use crate::routes::credentials::{credential_input, decrypted};
+use crate::{AppState, ApiError, ApiResult};
+use ghostflow_core::{export_node_definitions, NodeRegistry};
+use ghostflow_schema::{ExecutionContext, NodeDefinition, ParameterOption};
+
+/// How long a node may take to load parameter options.
+const LOAD_OPTIONS_TIMEOUT: Duration = Duration::from_secs(15);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NodeListQuery {
@@ -17,441 +25,268 @@ pub struct NodeListQuery {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NodeListResponse {
-    pub nodes: Vec<NodeCatalogEntry>,
+    pub nodes: Vec<NodeDefinition>,
     pub categories: Vec<NodeCategory>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct NodeCatalogEntry {
-    pub id: String,
-    pub name: String,
-    pub display_name: String,
-    pub description: String,
-    pub category: String,
-    pub version: String,
-    pub icon: Option<String>,
-    pub tags: Vec<String>,
-    pub input_count: u32,
-    pub output_count: u32,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NodeCategory {
     pub id: String,
-    pub name: String,
-    pub description: String,
-    pub icon: String,
     pub node_count: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct NodeDetailResponse {
-    pub id: String,
-    pub name: String,
-    pub display_name: String,
-    pub description: String,
-    pub category: String,
-    pub version: String,
-    pub icon: Option<String>,
-    pub parameters: Vec<NodeParameterInfo>,
-    pub inputs: Vec<NodePortInfo>,
-    pub outputs: Vec<NodePortInfo>,
-    pub examples: Vec<NodeExample>,
-    pub documentation: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct NodeParameterInfo {
-    pub name: String,
-    pub display_name: String,
-    pub description: String,
-    pub parameter_type: ParameterType,
-    pub required: bool,
-    pub default_value: Option<serde_json::Value>,
-    pub validation: Option<ParameterValidation>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ParameterValidation {
-    pub min_length: Option<usize>,
-    pub max_length: Option<usize>,
-    pub pattern: Option<String>,
-    pub min_value: Option<f64>,
-    pub max_value: Option<f64>,
-    pub options: Option<Vec<String>>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct NodePortInfo {
-    pub name: String,
-    pub display_name: String,
-    pub description: String,
-    pub data_type: String,
-    pub required: bool,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct NodeExample {
-    pub title: String,
-    pub description: String,
-    pub parameters: HashMap<String, serde_json::Value>,
-    pub expected_output: Option<serde_json::Value>,
-}
-
 pub async fn list_nodes(
     Query(query): Query<NodeListQuery>,
     State(state): State<Arc<AppState>>,
 ) -> ApiResult<Json<NodeListResponse>> {
-    // TODO: Get from actual node registry
-    let all_nodes = get_sample_nodes();
-    
+    let all_nodes = export_node_definitions(state.node_registry.as_ref());
+
+    let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+    for node in &all_nodes {
+        *counts.entry(category_id(node)).or_insert(0) += 1;
+    }
+    let categories = counts
+        .into_iter()
+        .map(|(id, node_count)| NodeCategory { id, node_count })
+        .collect();
+
     let filtered_nodes = if let Some(category) = query.category {
         all_nodes.into_iter()
-            .filter(|node| node.category == category)
+            .filter(|node| category_id(node) == category)
             .collect()
     } else if let Some(search) = query.search {
         let search_lower = search.to_lowercase();
         all_nodes.into_iter()
             .filter(|node| {
+                node.id.to_lowercase().contains(&search_lower) ||
                 node.name.to_lowercase().contains(&search_lower) ||
-                node.display_name.to_lowercase().contains(&search_lower) ||
-                node.description.to_lowercase().contains(&search_lower) ||
-                node.tags.iter().any(|tag| tag.to_lowercase().contains(&search_lower))
+                node.description.to_lowercase().contains(&search_lower)
             })
             .collect()
     } else {
         all_nodes
     };
-    
-    let categories = vec![
-        NodeCategory {
-            id: "basic".to_string(),
-            name: "Basic Nodes".to_string(),
-            description: "Fundamental workflow building blocks".to_string(),
-            icon: "🔧".to_string(),
-            node_count: 5,
-        },
-        NodeCategory {
-            id: "integrations".to_string(),
-            name: "Integrations".to_string(),
-            description: "Connect to external services and APIs".to_string(),
-            icon: "🔌".to_string(),
-            node_count: 25,
-        },
-        NodeCategory {
-            id: "ai".to_string(),
-            name: "AI & ML".to_string(),
-            description: "Artificial intelligence and machine learning".to_string(),
-            icon: "🤖".to_string(),
-            node_count: 8,
-        },
-        NodeCategory {
-            id: "data".to_string(),
-            name: "Data Processing".to_string(),
-            description: "Transform and manipulate data".to_string(),
-            icon: "📊".to_string(),
-            node_count: 12,
-        },
-        NodeCategory {
-            id: "infrastructure".to_string(),
-            name: "Infrastructure".to_string(),
-            description: "Manage servers, containers, and infrastructure".to_string(),
-            icon: "🖥️".to_string(),
-            node_count: 15,
-        },
-        NodeCategory {
-            id: "security".to_string(),
-            name: "Security".to_string(),
-            description: "Security monitoring and incident response".to_string(),
-            icon: "🔒".to_string(),
-            node_count: 6,
-        },
-    ];
-    
+
     let response = NodeListResponse {
         nodes: filtered_nodes,
         categories,
     };
-    
+
     Ok(Json(response))
 }
 
 pub async fn get_node(
     Path(node_id): Path<String>,
     State(state): State<Arc<AppState>>,
-) -> ApiResult<Json<NodeDetailResponse>> {
-    // TODO: Get from actual node registry
-    let node_detail = get_sample_node_detail(&node_id)?;
-    Ok(Json(node_detail))
+) -> ApiResult<Json<NodeDefinition>> {
+    let node = state
+        .node_registry
+        .get_node(&node_id)
+        .ok_or_else(|| crate::ApiError::NotFound(format!("Node type '{}' not found", node_id)))?;
+    Ok(Json(node.definition()))
 }
 
-fn get_sample_nodes() -> Vec<NodeCatalogEntry> {
-    vec![
-        // Basic Nodes
-        NodeCatalogEntry {
-            id: "http_request".to_string(),
-            name: "http_request".to_string(),
-            display_name: "HTTP Request".to_string(),
-            description: "Make HTTP requests to external APIs and services".to_string(),
-            category: "basic".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("🌐".to_string()),
-            tags: vec!["http".to_string(), "api".to_string(), "request".to_string()],
-            input_count: 1,
-            output_count: 2,
-        },
-        NodeCatalogEntry {
-            id: "webhook".to_string(),
-            name: "webhook".to_string(),
-            display_name: "Webhook".to_string(),
-            description: "Receive HTTP webhooks from external services".to_string(),
-            category: "basic".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("📨".to_string()),
-            tags: vec!["webhook".to_string(), "trigger".to_string(), "http".to_string()],
-            input_count: 0,
-            output_count: 1,
-        },
-        NodeCatalogEntry {
-            id: "if_else".to_string(),
-            name: "if_else".to_string(),
-            display_name: "If/Else".to_string(),
-            description: "Conditional branching based on input data".to_string(),
-            category: "basic".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("🔀".to_string()),
-            tags: vec!["conditional".to_string(), "logic".to_string(), "branch".to_string()],
-            input_count: 1,
-            output_count: 2,
-        },
-        
-        // Integration Nodes
-        NodeCatalogEntry {
-            id: "cloudflare_dns".to_string(),
-            name: "cloudflare_dns".to_string(),
-            display_name: "Cloudflare DNS".to_string(),
-            description: "Manage Cloudflare DNS records and settings".to_string(),
-            category: "integrations".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("☁️".to_string()),
-            tags: vec!["cloudflare".to_string(), "dns".to_string(), "cdn".to_string()],
-            input_count: 0,
-            output_count: 1,
-        },
-        NodeCatalogEntry {
-            id: "discord_webhook".to_string(),
-            name: "discord_webhook".to_string(),
-            display_name: "Discord Webhook".to_string(),
-            description: "Send messages to Discord via webhook".to_string(),
-            category: "integrations".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("💬".to_string()),
-            tags: vec!["discord".to_string(), "chat".to_string(), "notification".to_string()],
-            input_count: 1,
-            output_count: 1,
-        },
-        NodeCatalogEntry {
-            id: "slack_message".to_string(),
-            name: "slack_message".to_string(),
-            display_name: "Slack Message".to_string(),
-            description: "Send messages to Slack channels or users".to_string(),
-            category: "integrations".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("💼".to_string()),
-            tags: vec!["slack".to_string(), "chat".to_string(), "notification".to_string()],
-            input_count: 0,
-            output_count: 1,
-        },
-        NodeCatalogEntry {
-            id: "microsoft_graph_email".to_string(),
-            name: "microsoft_graph_email".to_string(),
-            display_name: "Microsoft 365 Email".to_string(),
-            description: "Send and manage emails via Microsoft Graph API".to_string(),
-            category: "integrations".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("📧".to_string()),
-            tags: vec!["microsoft".to_string(), "email".to_string(), "outlook".to_string()],
-            input_count: 0,
-            output_count: 1,
-        },
-        NodeCatalogEntry {
-            id: "google_sheets".to_string(),
-            name: "google_sheets".to_string(),
-            display_name: "Google Sheets".to_string(),
-            description: "Read from and write to Google Sheets".to_string(),
-            category: "integrations".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("📊".to_string()),
-            tags: vec!["google".to_string(), "sheets".to_string(), "spreadsheet".to_string()],
-            input_count: 0,
-            output_count: 3,
-        },
-        NodeCatalogEntry {
-            id: "gitlab_project".to_string(),
-            name: "gitlab_project".to_string(),
-            display_name: "GitLab Project".to_string(),
-            description: "Manage GitLab projects and repositories".to_string(),
-            category: "integrations".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("🦊".to_string()),
-            tags: vec!["gitlab".to_string(), "git".to_string(), "project".to_string()],
-            input_count: 0,
-            output_count: 1,
-        },
-        
-        // Infrastructure Nodes
-        NodeCatalogEntry {
-            id: "azure_vm".to_string(),
-            name: "azure_vm".to_string(),
-            display_name: "Azure Virtual Machine".to_string(),
-            description: "Manage Azure Virtual Machines".to_string(),
-            category: "infrastructure".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("☁️".to_string()),
-            tags: vec!["azure".to_string(), "vm".to_string(), "cloud".to_string()],
-            input_count: 0,
-            output_count: 2,
-        },
-        NodeCatalogEntry {
-            id: "proxmox_vm".to_string(),
-            name: "proxmox_vm".to_string(),
-            display_name: "Proxmox VM".to_string(),
-            description: "Manage Proxmox Virtual Machines".to_string(),
-            category: "infrastructure".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("🖥️".to_string()),
-            tags: vec!["proxmox".to_string(), "vm".to_string(), "virtualization".to_string()],
-            input_count: 0,
-            output_count: 2,
-        },
-        
-        // Security Nodes
-        NodeCatalogEntry {
-            id: "wazuh_api".to_string(),
-            name: "wazuh_api".to_string(),
-            display_name: "Wazuh SIEM".to_string(),
-            description: "Interact with Wazuh security monitoring platform".to_string(),
-            category: "security".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("🔒".to_string()),
-            tags: vec!["wazuh".to_string(), "siem".to_string(), "security".to_string()],
-            input_count: 0,
-            output_count: 2,
-        },
-        
-        // AI Nodes
-        NodeCatalogEntry {
-            id: "ollama_generate".to_string(),
-            name: "ollama_generate".to_string(),
-            display_name: "Ollama Generate".to_string(),
-            description: "Generate text using local Ollama models".to_string(),
-            category: "ai".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("🤖".to_string()),
-            tags: vec!["ollama".to_string(), "ai".to_string(), "llm".to_string(), "text".to_string()],
-            input_count: 0,
-            output_count: 1,
-        },
-    ]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NodeOptionsResponse {
+    pub node_type: String,
+    pub parameter: String,
+    pub options: Vec<ParameterOption>,
 }
 
-fn get_sample_node_detail(node_id: &str) -> crate::Result<NodeDetailResponse> {
-    match node_id {
-        "discord_webhook" => Ok(NodeDetailResponse {
-            id: "discord_webhook".to_string(),
-            name: "discord_webhook".to_string(),
-            display_name: "Discord Webhook".to_string(),
-            description: "Send messages to Discord via webhook with rich formatting support".to_string(),
-            category: "integrations".to_string(),
-            version: "1.0.0".to_string(),
-            icon: Some("💬".to_string()),
-            parameters: vec![
-                NodeParameterInfo {
-                    name: "webhook_url".to_string(),
-                    display_name: "Webhook URL".to_string(),
-                    description: "Discord webhook URL from channel settings".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
-                    default_value: None,
-                    validation: Some(ParameterValidation {
-                        pattern: Some(r"^https://discord\.com/api/webhooks/".to_string()),
-                        ..Default::default()
-                    }),
-                },
-                NodeParameterInfo {
-                    name: "content".to_string(),
-                    display_name: "Message Content".to_string(),
-                    description: "Text message to send".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
-                    default_value: None,
-                    validation: Some(ParameterValidation {
-                        max_length: Some(2000),
-                        ..Default::default()
-                    }),
-                },
-                NodeParameterInfo {
-                    name: "username".to_string(),
-                    display_name: "Username".to_string(),
-                    description: "Override webhook username".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
-                    default_value: Some(serde_json::Value::String("GhostFlow".to_string())),
-                    validation: Some(ParameterValidation {
-                        max_length: Some(80),
-                        ..Default::default()
-                    }),
-                },
-            ],
-            inputs: vec![
-                NodePortInfo {
-                    name: "trigger".to_string(),
-                    display_name: "Trigger".to_string(),
-                    description: "Trigger the webhook execution".to_string(),
-                    data_type: "any".to_string(),
-                    required: true,
-                },
-            ],
-            outputs: vec![
-                NodePortInfo {
-                    name: "result".to_string(),
-                    display_name: "Result".to_string(),
-                    description: "Webhook execution result".to_string(),
-                    data_type: "object".to_string(),
-                    required: false,
-                },
-            ],
-            examples: vec![
-                NodeExample {
-                    title: "Simple Message".to_string(),
-                    description: "Send a simple text message to Discord".to_string(),
-                    parameters: {
-                        let mut params = HashMap::new();
-                        params.insert("webhook_url".to_string(), serde_json::Value::String("https://discord.com/api/webhooks/123/abc".to_string()));
-                        params.insert("content".to_string(), serde_json::Value::String("Hello from GhostFlow!".to_string()));
-                        params
-                    },
-                    expected_output: Some(serde_json::json!({
-                        "success": true,
-                        "status": 200
-                    })),
-                },
-            ],
-            documentation: Some("For detailed documentation, visit: https://docs.ghostflow.dev/nodes/discord-webhook".to_string()),
-        }),
-        _ => Err(crate::ApiError::NotFound("Node not found".to_string())),
+/// `GET /api/nodes/:id/options/:param` — current choices for a parameter the
+/// node loads from the remote system, such as Slack channels. Connection
+/// details come from the stored credential named by `?credential=`; any
+/// other query parameters are passed to the node as input too, but never
+/// override a field the credential defines.
+pub async fn load_node_options(
+    Path((node_id, parameter)): Path<(String, String)>,
+    Query(mut query): Query<HashMap<String, String>>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<NodeOptionsResponse>> {
+    let credential = match query.remove("credential") {
+        Some(credential_id) => {
+            let vault = state.credential_vault.as_ref();
+            let credential = vault
+                .retrieve(&credential_id)
+                .await?
+                .ok_or_else(|| ApiError::NotFound(format!("Credential '{}' not found", credential_id)))?;
+            Some(decrypted(vault, credential).await?)
+        }
+        None => None,
+    };
+
+    // Credential fields win, so a query cannot send them to another host
+    let mut fields = query;
+    fields.extend(credential.map(|c| c.data).unwrap_or_default());
+    let options = run_load_options(state.node_registry.as_ref(), &node_id, &parameter, &fields).await?;
+
+    Ok(Json(NodeOptionsResponse {
+        node_type: node_id,
+        parameter,
+        options,
+    }))
+}
+
+/// Ask `node_type` for the options of `parameter`, with `fields` as input.
+pub async fn run_load_options(
+    registry: &dyn NodeRegistry,
+    node_type: &str,
+    parameter: &str,
+    fields: &HashMap<String, String>,
+) -> ApiResult<Vec<ParameterOption>> {
+    let node = registry
+        .get_node(node_type)
+        .ok_or_else(|| ApiError::NotFound(format!("Node type '{}' not found", node_type)))?;
+    if !node.dynamic_option_parameters().iter().any(|name| *name == parameter) {
+        return Err(ApiError::BadRequest(format!(
+            "Node type '{}' does not load options for '{}'",
+            node_type, parameter
+        )));
+    }
+
+    let context = ExecutionContext {
+        execution_id: Uuid::new_v4(),
+        flow_id: Uuid::nil(),
+        node_id: node_type.to_string(),
+        input: credential_input(&node.definition(), fields),
+        variables: HashMap::new(),
+        secrets: HashMap::new(),
+        artifacts: HashMap::new(),
+        node_outputs: HashMap::new(),
+        attempt: 1,
+    };
+
+    match tokio::time::timeout(LOAD_OPTIONS_TIMEOUT, node.load_options(parameter, &context)).await {
+        Ok(options) => Ok(options?),
+        Err(_) => Err(ApiError::InternalServerError(format!(
+            "Node type '{}' did not load options within {}s",
+            node_type,
+            LOAD_OPTIONS_TIMEOUT.as_secs()
+        ))),
     }
 }
 
-impl Default for ParameterValidation {
-    fn default() -> Self {
-        Self {
-            min_length: None,
-            max_length: None,
-            pattern: None,
-            min_value: None,
-            max_value: None,
-            options: None,
+/// Category in the same snake_case form used by the serialized definition
+fn category_id(node: &NodeDefinition) -> String {
+    serde_json::to_value(&node.category)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use ghostflow_core::{BasicNodeRegistry, Node};
+    use ghostflow_schema::node::ParameterType;
+    use ghostflow_schema::{NodeCategory, NodeParameter};
+
+    /// Lists the zones of the server at its `base_url` input
+    struct ZoneNode;
+
+    #[async_trait]
+    impl Node for ZoneNode {
+        fn definition(&self) -> NodeDefinition {
+            NodeDefinition {
+                id: "zones".to_string(),
+                name: "Zones".to_string(),
+                description: "Test node".to_string(),
+                category: NodeCategory::Integration,
+                version: "1.0.0".to_string(),
+                inputs: vec![],
+                outputs: vec![],
+                parameters: vec![NodeParameter {
+                    name: "zone".to_string(),
+                    display_name: "Zone".to_string(),
+                    description: None,
+                    param_type: ParameterType::Select,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                }],
+                icon: None,
+                color: None,
+            }
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> ghostflow_core::Result<()> {
+            Ok(())
         }
+
+        async fn execute(&self, _context: ExecutionContext) -> ghostflow_core::Result<Value> {
+            Ok(Value::Null)
+        }
+
+        fn dynamic_option_parameters(&self) -> Vec<&'static str> {
+            vec!["zone"]
+        }
+
+        async fn load_options(
+            &self,
+            _parameter: &str,
+            context: &ExecutionContext,
+        ) -> ghostflow_core::Result<Vec<ParameterOption>> {
+            let base_url = context.input["base_url"].as_str().unwrap_or("none");
+            Ok(vec![ParameterOption {
+                value: serde_json::json!(format!("{}/zone", base_url)),
+                label: format!("Zone of {}", base_url),
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_options_are_loaded_with_the_given_fields() {
+        let mut registry = BasicNodeRegistry::new();
+        registry.register_node("zones".to_string(), Arc::new(ZoneNode)).unwrap();
+        let fields = HashMap::from([("base_url".to_string(), "https://dns.lan".to_string())]);
+
+        let options = run_load_options(&registry, "zones", "zone", &fields).await.unwrap();
+        assert_eq!(options.len(), 1);
+        assert_eq!(options[0].value, "https://dns.lan/zone");
+
+        let error = run_load_options(&registry, "zones", "account", &fields).await.unwrap_err();
+        assert!(matches!(error, ApiError::BadRequest(_)));
+        let error = run_load_options(&registry, "missing", "zone", &fields).await.unwrap_err();
+        assert!(matches!(error, ApiError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_query_cannot_override_the_credentials_fields() {
+        let mut registry = BasicNodeRegistry::new();
+        registry.register_node("zones".to_string(), Arc::new(ZoneNode)).unwrap();
+        let state = Arc::new(crate::test_support::app_state(Arc::new(registry)));
+        state
+            .credential_vault
+            .store(ghostflow_core::Credential {
+                id: "dns".to_string(),
+                name: "DNS".to_string(),
+                credential_type: ghostflow_core::CredentialType::Custom("zones".to_string()),
+                data: HashMap::from([
+                    ("base_url".to_string(), "https://dns.lan".to_string()),
+                    ("password".to_string(), "hunter2".to_string()),
+                ]),
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                workspace_id: "default".to_string(),
+                encrypted: false,
+            })
+            .await
+            .unwrap();
+
+        let query = HashMap::from([
+            ("credential".to_string(), "dns".to_string()),
+            ("base_url".to_string(), "https://attacker.example".to_string()),
+        ]);
+        let Json(response) = load_node_options(
+            Path(("zones".to_string(), "zone".to_string())),
+            Query(query),
+            State(state),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.options[0].value, "https://dns.lan/zone");
     }
-}
\ No newline at end of file
+}
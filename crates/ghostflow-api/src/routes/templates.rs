@@ -0,0 +1,45 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::{AppState, ApiResult};
+use ghostflow_core::{FlowTemplate, TemplateQuery};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateListResponse {
+    pub templates: Vec<FlowTemplate>,
+    pub total: usize,
+}
+
+/// `GET /api/templates?category=security&difficulty=beginner&search=wazuh&sort=downloads`
+pub async fn list_templates(
+    Query(query): Query<TemplateQuery>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<TemplateListResponse>> {
+    let templates: Vec<FlowTemplate> = state
+        .template_registry
+        .search(&query)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    Ok(Json(TemplateListResponse {
+        total: templates.len(),
+        templates,
+    }))
+}
+
+pub async fn get_template(
+    Path(template_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<FlowTemplate>> {
+    let template = state
+        .template_registry
+        .get(&template_id)
+        .cloned()
+        .ok_or_else(|| crate::ApiError::NotFound(format!("Template '{}' not found", template_id)))?;
+    Ok(Json(template))
+}
@@ -0,0 +1,176 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use ghostflow_core::{WebhookResponse, DEFAULT_WEBHOOK_RESPONSE_TIMEOUT};
+use ghostflow_nodes::{verify_signature, SignatureConfig};
+use ghostflow_schema::Flow;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{check_json_depth, AppState, ApiError, ApiResult};
+
+/// `POST /api/webhooks/:flow_id` — run the flow with the request as input and
+/// reply with whatever its `respond_to_webhook` node sends, or 202 if it does
+/// not respond within [`DEFAULT_WEBHOOK_RESPONSE_TIMEOUT`]. When the flow's
+/// webhook trigger uses HMAC authentication, a request with a bad signature
+/// is answered with 401 and never reaches the runtime.
+pub async fn receive_webhook(
+    Path(flow_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Response> {
+    let flow_id = Uuid::parse_str(&flow_id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid flow id '{}'", flow_id)))?;
+    check_json_depth(&body, state.config.max_json_depth)?;
+    if let Some(flow) = state.runtime.get_flow(&flow_id).await {
+        check_signature(&flow, &headers, &body)?;
+    }
+
+    let raw_body = String::from_utf8_lossy(&body).into_owned();
+    let input_data = serde_json::json!({
+        "body": serde_json::from_slice::<serde_json::Value>(&body)
+            .unwrap_or_else(|_| serde_json::Value::String(raw_body.clone())),
+        "headers": headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), serde_json::Value::from(value.to_str().ok()?))))
+            .collect::<serde_json::Map<_, _>>(),
+        "raw_body": raw_body,
+    });
+
+    let response = state
+        .runtime
+        .execute_webhook(&flow_id, input_data, DEFAULT_WEBHOOK_RESPONSE_TIMEOUT)
+        .await?;
+
+    Ok(into_http_response(response))
+}
+
+fn check_signature(flow: &Flow, headers: &HeaderMap, body: &[u8]) -> ghostflow_core::Result<()> {
+    for trigger in flow.nodes.values().filter(|node| node.node_type == "webhook_trigger") {
+        let Some(config) = SignatureConfig::for_trigger(&serde_json::to_value(&trigger.parameters)?)? else {
+            continue;
+        };
+        let headers: HashMap<String, String> = headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        verify_signature(&config, &headers, body)?;
+    }
+    Ok(())
+}
+
+fn into_http_response(response: WebhookResponse) -> Response {
+    let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::OK);
+
+    let mut headers = HeaderMap::new();
+    for (name, value) in &response.headers {
+        match (HeaderName::try_from(name.as_str()), HeaderValue::from_str(value)) {
+            (Ok(name), Ok(value)) => {
+                headers.insert(name, value);
+            }
+            _ => warn!("Dropping invalid webhook response header '{}'", name),
+        }
+    }
+
+    (status, headers, Json(response.body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use crate::test_support::{app_state, flow, node};
+    use ghostflow_core::{BasicNodeRegistry, NodeRegistry};
+    use ghostflow_nodes::WebhookTriggerNode;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "It's a Secret to Everybody";
+    const BODY: &str = r#"{"action":"opened"}"#;
+
+    async fn state_with_signed_flow() -> (Arc<AppState>, Uuid) {
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("webhook_trigger".to_string(), Arc::new(WebhookTriggerNode::new()))
+            .unwrap();
+        let state = app_state(Arc::new(registry));
+
+        let flow = flow(vec![node(
+            "github",
+            "webhook_trigger",
+            serde_json::json!({
+                "path": "/github",
+                "authentication": "hmac",
+                "secret": SECRET,
+                "delivery_id_header": "X-GitHub-Delivery",
+            }),
+        )]);
+        let flow_id = flow.id;
+        state.runtime.deploy_flow(flow).await.unwrap();
+
+        (Arc::new(state), flow_id)
+    }
+
+    async fn deliver(state: Arc<AppState>, flow_id: Uuid, signature: &str, delivery_id: &str) -> (StatusCode, serde_json::Value) {
+        let response = crate::create_api_router(state)
+            .unwrap()
+            .oneshot(
+                Request::post(format!("/api/webhooks/{}", flow_id))
+                    .header("X-Hub-Signature-256", signature)
+                    .header("X-GitHub-Delivery", delivery_id)
+                    .body(Body::from(BODY))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    fn sign(secret: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(BODY.as_bytes());
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[tokio::test]
+    async fn test_bad_signature_is_unauthorized_and_not_executed() {
+        let (state, flow_id) = state_with_signed_flow().await;
+
+        let (status, _) = deliver(state.clone(), flow_id, &sign("wrong secret"), "d-1").await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(state.runtime.list_executions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_valid_signature_runs_the_flow() {
+        let (state, flow_id) = state_with_signed_flow().await;
+
+        let (status, _) = deliver(state, flow_id, &sign(SECRET), "d-1").await;
+
+        assert_eq!(status, StatusCode::ACCEPTED);
+    }
+
+    #[tokio::test]
+    async fn test_forged_delivery_does_not_claim_its_id() {
+        let (state, flow_id) = state_with_signed_flow().await;
+
+        let (forged, _) = deliver(state.clone(), flow_id, &sign("wrong secret"), "d-7").await;
+        let (genuine, body) = deliver(state, flow_id, &sign(SECRET), "d-7").await;
+
+        assert_eq!(forged, StatusCode::UNAUTHORIZED);
+        assert_eq!(genuine, StatusCode::ACCEPTED);
+        assert_ne!(body["status"], "duplicate");
+    }
+}
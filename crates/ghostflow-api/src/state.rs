@@ -1,5 +1,9 @@
-use ghostflow_core::NodeRegistry;
+use ghostflow_core::{
+    CredentialVault, FlowStorage, MemoryCredentialVault, MemoryFlowStorage, NodeRegistry, TemplateRegistry,
+};
 use ghostflow_engine::FlowRuntime;
+use crate::routes::HealthCheck;
+use crate::ApiConfig;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -8,11 +12,24 @@ use tokio::sync::RwLock;
 pub struct AppState {
     pub db_pool: PgPool,
     pub runtime: Arc<FlowRuntime>,
+    pub flow_storage: Arc<dyn FlowStorage>,
     pub node_registry: Arc<dyn NodeRegistry>,
+    pub template_registry: Arc<TemplateRegistry>,
+    pub credential_vault: Arc<dyn CredentialVault>,
     pub websocket_clients: Arc<RwLock<WebSocketClients>>,
+    /// Dependencies checked by `/health/ready` in addition to storage
+    pub health_checks: Vec<Arc<dyn HealthCheck>>,
+    pub config: ApiConfig,
 }
 
-pub type WebSocketClients = std::collections::HashMap<uuid::Uuid, tokio::sync::mpsc::UnboundedSender<String>>;
+pub type WebSocketClients = std::collections::HashMap<uuid::Uuid, WebSocketClient>;
+
+/// What the event forwarder needs to know about an open WebSocket connection
+pub struct WebSocketClient {
+    pub workspace_id: String,
+    pub subscriptions: std::collections::HashMap<String, crate::websocket::SubscribeMessage>,
+    pub sender: tokio::sync::mpsc::UnboundedSender<axum::extract::ws::Message>,
+}
 
 impl AppState {
     pub fn new(
@@ -23,15 +40,44 @@ impl AppState {
         Self {
             db_pool,
             runtime,
+            flow_storage: Arc::new(MemoryFlowStorage::new()),
             node_registry,
+            template_registry: Arc::new(TemplateRegistry::with_builtin_templates()),
+            credential_vault: Arc::new(MemoryCredentialVault::new()),
             websocket_clients: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            health_checks: Vec::new(),
+            config: ApiConfig::default(),
         }
     }
 
+    /// Keep flow versions somewhere other than process memory.
+    pub fn with_flow_storage(mut self, flow_storage: Arc<dyn FlowStorage>) -> Self {
+        self.flow_storage = flow_storage;
+        self
+    }
+
+    /// Read credentials from a persistent vault instead of process memory.
+    pub fn with_credential_vault(mut self, credential_vault: Arc<dyn CredentialVault>) -> Self {
+        self.credential_vault = credential_vault;
+        self
+    }
+
+    /// Replace the default (same-origin only) API settings.
+    pub fn with_config(mut self, config: ApiConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Make readiness depend on another service, e.g. a node's upstream API.
+    pub fn with_health_check(mut self, check: Arc<dyn HealthCheck>) -> Self {
+        self.health_checks.push(check);
+        self
+    }
+
     pub async fn broadcast_message(&self, message: &str) {
         let clients = self.websocket_clients.read().await;
-        for (_, tx) in clients.iter() {
-            let _ = tx.send(message.to_string());
+        for (_, client) in clients.iter() {
+            let _ = client.sender.send(axum::extract::ws::Message::Text(message.to_string()));
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,65 @@
+use crate::AppState;
+use ghostflow_core::NodeRegistry;
+use ghostflow_engine::FlowRuntime;
+use ghostflow_schema::{Flow, FlowMetadata, FlowNode, NodePosition, OverflowPolicy};
+use serde_json::Value;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// A pool whose server refuses connections
+pub fn unreachable_pool() -> PgPool {
+    PgPoolOptions::new()
+        .acquire_timeout(Duration::from_millis(500))
+        .connect_lazy("postgres://ghostflow@127.0.0.1:1/ghostflow")
+        .unwrap()
+}
+
+/// State with a runtime of its own over `registry` and no database
+pub fn app_state(registry: Arc<dyn NodeRegistry>) -> AppState {
+    AppState::new(unreachable_pool(), Arc::new(FlowRuntime::new(registry.clone())), registry)
+}
+
+/// Node `id` of `node_type`, configured with the fields of `parameters`
+pub fn node(id: &str, node_type: &str, parameters: Value) -> FlowNode {
+    FlowNode {
+        id: id.to_string(),
+        node_type: node_type.to_string(),
+        name: id.to_string(),
+        description: None,
+        parameters: serde_json::from_value(parameters).unwrap(),
+        position: NodePosition { x: 0.0, y: 0.0 },
+        retry_config: None,
+        timeout_ms: None,
+        cache_ttl_ms: None,
+        node_version: None,
+    }
+}
+
+/// Flow of `nodes` without edges, in the default workspace
+pub fn flow(nodes: Vec<FlowNode>) -> Flow {
+    Flow {
+        id: Uuid::new_v4(),
+        name: "Test Flow".to_string(),
+        description: None,
+        version: "1.0.0".to_string(),
+        nodes: nodes.into_iter().map(|n| (n.id.clone(), n)).collect(),
+        edges: vec![],
+        triggers: vec![],
+        parameters: HashMap::new(),
+        secrets: vec![],
+        max_duration_ms: None,
+        max_concurrent_executions: None,
+        overflow_policy: OverflowPolicy::Queue,
+        metadata: FlowMetadata {
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            created_by: "test".to_string(),
+            tags: vec![],
+            category: None,
+            workspace_id: "default".to_string(),
+        },
+    }
+}
@@ -9,13 +9,20 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-use crate::{AppState, ApiResult};
+use crate::{AppState, ApiResult, WebSocketClient};
+use ghostflow_core::{EventBus, OutputStream};
 use ghostflow_schema::ExecutionStatus;
 
+/// Log lines buffered per connection for `tail` subscriptions. When a slow
+/// client lets this fill up, further lines are dropped and the client is told
+/// how many it missed, rather than buffering without bound.
+pub const LOG_TAIL_BUFFER: usize = 256;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebSocketMessage {
     #[serde(rename = "type")]
@@ -24,7 +31,7 @@ pub struct WebSocketMessage {
     pub timestamp: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum WebSocketMessageType {
     // Client to Server
@@ -40,6 +47,12 @@ pub enum WebSocketMessageType {
     NodeStarted,
     NodeCompleted,
     NodeFailed,
+    NodeOutput,
+    NodeToken,
+    NodeLog,
+    ApprovalRequested,
+    LogDropped,
+    TailEnded,
     FlowUpdated,
     Pong,
     Error,
@@ -52,6 +65,26 @@ pub struct SubscribeMessage {
     pub event_types: Vec<WebSocketMessageType>,
 }
 
+impl SubscribeMessage {
+    /// Whether this subscription wants a `message_type` event from
+    /// `execution_id` of `flow_id`. Unset ids and empty `event_types` match
+    /// anything.
+    pub fn matches(&self, flow_id: &Uuid, execution_id: &Uuid, message_type: &WebSocketMessageType) -> bool {
+        self.flow_id.as_ref().is_none_or(|id| *id == flow_id.to_string())
+            && self.execution_id.as_ref().is_none_or(|id| *id == execution_id.to_string())
+            && (self.event_types.is_empty() || self.event_types.contains(message_type))
+    }
+}
+
+/// `{"tail":"<exec_id>","node":"<node_id>"}` — stream the log lines one
+/// node produces during one execution. `{"untail":...,"node":...}` stops it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TailRequest {
+    #[serde(alias = "untail")]
+    pub tail: Uuid,
+    pub node: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionEvent {
     pub execution_id: String,
@@ -118,6 +151,9 @@ pub struct WebSocketConnection {
     pub workspace_id: Option<String>,
     pub subscriptions: HashMap<String, SubscribeMessage>,
     pub sender: tokio::sync::mpsc::UnboundedSender<Message>,
+    /// Bounded channel shared by this connection's log tails
+    pub log_sender: mpsc::Sender<WebSocketMessage>,
+    pub tails: HashMap<TailRequest, JoinHandle<()>>,
 }
 
 pub async fn websocket_handler(
@@ -141,9 +177,11 @@ async fn websocket_connection_handler(
     user_id: Option<String>,
     workspace_id: String,
 ) {
-    let connection_id = Uuid::new_v4().to_string();
+    let client_id = Uuid::new_v4();
+    let connection_id = client_id.to_string();
     let (sender, mut receiver) = socket.split();
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let (log_tx, log_rx) = mpsc::channel(LOG_TAIL_BUFFER);
     
     // Create connection record
     let connection = WebSocketConnection {
@@ -152,13 +190,19 @@ async fn websocket_connection_handler(
         workspace_id: workspace_id.clone(),
         subscriptions: HashMap::new(),
         sender: tx.clone(),
+        log_sender: log_tx,
+        tails: HashMap::new(),
     };
     
-    // Store connection (TODO: implement proper connection management)
+    state.websocket_clients.write().await.insert(client_id, WebSocketClient {
+        workspace_id: workspace_id.clone(),
+        subscriptions: HashMap::new(),
+        sender: tx.clone(),
+    });
     log::info!("WebSocket connection established: {}", connection_id);
     
     // Spawn task to handle outgoing messages
-    let outgoing_task = tokio::spawn(handle_outgoing_messages(sender, rx));
+    let outgoing_task = tokio::spawn(handle_outgoing_messages(sender, rx, log_rx));
     
     // Handle incoming messages
     let incoming_task = tokio::spawn(handle_incoming_messages(
@@ -177,14 +221,28 @@ async fn websocket_connection_handler(
         }
     }
     
+    state.websocket_clients.write().await.remove(&client_id);
     log::info!("WebSocket connection closed: {}", connection_id);
 }
 
 async fn handle_outgoing_messages(
     mut sender: axum::extract::ws::WebSocketSender,
     mut rx: tokio::sync::mpsc::UnboundedReceiver<Message>,
+    mut log_rx: mpsc::Receiver<WebSocketMessage>,
 ) {
-    while let Some(msg) = rx.recv().await {
+    loop {
+        // Log lines are only pulled from their bounded buffer as fast as the
+        // socket accepts them, which is what lets a slow client drop lines
+        let msg = tokio::select! {
+            msg = rx.recv() => match msg {
+                Some(msg) => msg,
+                None => break,
+            },
+            Some(log) = log_rx.recv() => match serde_json::to_string(&log) {
+                Ok(text) => Message::Text(text),
+                Err(_) => continue,
+            },
+        };
         if sender.send(msg).await.is_err() {
             break;
         }
@@ -224,6 +282,10 @@ async fn handle_incoming_messages(
             }
         }
     }
+
+    for (_, tail) in connection.tails.drain() {
+        tail.abort();
+    }
 }
 
 async fn handle_text_message(
@@ -231,15 +293,25 @@ async fn handle_text_message(
     connection: &mut WebSocketConnection,
     state: &AppState,
 ) -> Result<(), String> {
-    let msg: WebSocketMessage = serde_json::from_str(text)
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    if value.get("tail").is_some() || value.get("untail").is_some() {
+        let stop = value.get("untail").is_some();
+        let request: TailRequest = serde_json::from_value(value)
+            .map_err(|e| format!("Invalid tail message: {}", e))?;
+        return handle_tail_message(request, stop, connection, state).await;
+    }
+
+    let msg: WebSocketMessage = serde_json::from_value(value)
         .map_err(|e| format!("Invalid JSON: {}", e))?;
     
     match msg.message_type {
         WebSocketMessageType::Subscribe => {
-            handle_subscribe_message(&msg.data, connection).await
+            handle_subscribe_message(&msg.data, connection, state).await
         }
         WebSocketMessageType::Unsubscribe => {
-            handle_unsubscribe_message(&msg.data, connection).await
+            handle_unsubscribe_message(&msg.data, connection, state).await
         }
         WebSocketMessageType::Ping => {
             let pong_msg = WebSocketMessage {
@@ -260,6 +332,7 @@ async fn handle_text_message(
 async fn handle_subscribe_message(
     data: &serde_json::Value,
     connection: &mut WebSocketConnection,
+    state: &AppState,
 ) -> Result<(), String> {
     let subscribe: SubscribeMessage = serde_json::from_value(data.clone())
         .map_err(|e| format!("Invalid subscribe message: {}", e))?;
@@ -271,6 +344,7 @@ async fn handle_subscribe_message(
     );
     
     connection.subscriptions.insert(subscription_key.clone(), subscribe);
+    sync_subscriptions(connection, state).await;
     
     log::info!("Client {} subscribed to {}", connection.id, subscription_key);
     
@@ -296,6 +370,7 @@ async fn handle_subscribe_message(
 async fn handle_unsubscribe_message(
     data: &serde_json::Value,
     connection: &mut WebSocketConnection,
+    state: &AppState,
 ) -> Result<(), String> {
     let subscribe: SubscribeMessage = serde_json::from_value(data.clone())
         .map_err(|e| format!("Invalid unsubscribe message: {}", e))?;
@@ -307,12 +382,136 @@ async fn handle_unsubscribe_message(
     );
     
     connection.subscriptions.remove(&subscription_key);
+    sync_subscriptions(connection, state).await;
     
     log::info!("Client {} unsubscribed from {}", connection.id, subscription_key);
     
     Ok(())
 }
 
+/// Let the event forwarder see the connection's current subscriptions.
+async fn sync_subscriptions(connection: &WebSocketConnection, state: &AppState) {
+    let Ok(client_id) = connection.id.parse::<Uuid>() else {
+        return;
+    };
+    if let Some(client) = state.websocket_clients.write().await.get_mut(&client_id) {
+        client.subscriptions = connection.subscriptions.clone();
+    }
+}
+
+async fn handle_tail_message(
+    request: TailRequest,
+    stop: bool,
+    connection: &mut WebSocketConnection,
+    state: &AppState,
+) -> Result<(), String> {
+    // Finished tails have nothing left to stop
+    connection.tails.retain(|_, tail| !tail.is_finished());
+
+    if let Some(existing) = connection.tails.remove(&request) {
+        existing.abort();
+    }
+    if stop {
+        log::info!("Client {} stopped tailing {}/{}", connection.id, request.tail, request.node);
+        return Ok(());
+    }
+
+    // Executions of other workspaces are reported as missing, like unknown ones
+    let workspace_id = execution_owner(state, &request.tail).await.map(|(_, workspace_id)| workspace_id);
+    if workspace_id.as_deref() != connection.workspace_id.as_deref() {
+        return Err(format!("Execution {} not found", request.tail));
+    }
+
+    log::info!("Client {} tailing {}/{}", connection.id, request.tail, request.node);
+    let tail = spawn_log_tail(request.clone(), &state.runtime.services().events, connection.log_sender.clone());
+    connection.tails.insert(request, tail);
+    Ok(())
+}
+
+/// Flow `execution_id` belongs to and that flow's workspace, if the
+/// execution is running or finished.
+async fn execution_owner(state: &AppState, execution_id: &Uuid) -> Option<(Uuid, String)> {
+    let flow_id = state.runtime.execution_flow_id(execution_id).await?;
+    let flow = match state.runtime.get_flow(&flow_id).await {
+        Some(flow) => flow,
+        None => state.flow_storage.get_flow(&flow_id).await.ok()??,
+    };
+    Some((flow_id, flow.metadata.workspace_id))
+}
+
+/// Forward the log lines `request.node` emits during execution
+/// `request.tail` on `events` into `buffer` until the node finishes or the receiver goes
+/// away. Lines that do not fit are counted, and a `log_dropped` message with
+/// the count is sent as soon as there is room again.
+pub fn spawn_log_tail(request: TailRequest, events: &EventBus, buffer: mpsc::Sender<WebSocketMessage>) -> JoinHandle<()> {
+    // Subscribe before spawning so lines published right after the request
+    // are not missed
+    let mut events = events.subscribe();
+
+    tokio::spawn(async move {
+        let mut dropped: u64 = 0;
+
+        loop {
+            let event = match events.recv().await {
+                Ok(event) if event.execution_id() == request.tail => event,
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    dropped += skipped;
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            let (message_type, data) = match event {
+                ghostflow_core::ExecutionEvent::NodeOutput { node_id, stream, data, .. } if node_id == request.node => {
+                    let stream = match stream {
+                        OutputStream::Stdout => "stdout",
+                        OutputStream::Stderr => "stderr",
+                    };
+                    (WebSocketMessageType::NodeLog, serde_json::json!({ "stream": stream, "line": data }))
+                }
+                ghostflow_core::ExecutionEvent::NodeCompleted { node_id, .. }
+                | ghostflow_core::ExecutionEvent::NodeFailed { node_id, .. }
+                    if node_id == request.node =>
+                {
+                    // The end notice is worth waiting for; lines are not
+                    let _ = buffer.send(tail_message(&request, WebSocketMessageType::TailEnded, serde_json::json!({ "dropped": dropped }))).await;
+                    break;
+                }
+                _ => continue,
+            };
+
+            if dropped > 0 {
+                let notice = tail_message(&request, WebSocketMessageType::LogDropped, serde_json::json!({ "dropped": dropped }));
+                match buffer.try_send(notice) {
+                    Ok(()) => dropped = 0,
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        dropped += 1;
+                        continue;
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => break,
+                }
+            }
+
+            match buffer.try_send(tail_message(&request, message_type, data)) {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Full(_)) => dropped += 1,
+                Err(mpsc::error::TrySendError::Closed(_)) => break,
+            }
+        }
+    })
+}
+
+fn tail_message(request: &TailRequest, message_type: WebSocketMessageType, mut data: serde_json::Value) -> WebSocketMessage {
+    data["execution_id"] = serde_json::json!(request.tail);
+    data["node_id"] = serde_json::json!(request.node);
+    WebSocketMessage {
+        message_type,
+        data,
+        timestamp: Utc::now(),
+    }
+}
+
 fn create_error_message(error: &str) -> String {
     let error_msg = WebSocketMessage {
         message_type: WebSocketMessageType::Error,
@@ -379,4 +578,240 @@ pub async fn broadcast_flow_update(
     
     // TODO: Implement actual broadcasting to connected clients
     log::info!("Broadcasting flow update: {:?}", message.message_type);
-}
\ No newline at end of file
+}
+
+/// Relays engine execution events from the runtime's event bus to the
+/// WebSocket clients subscribed to them. Spawn this once when the server starts.
+pub async fn forward_execution_events(state: Arc<AppState>) {
+    let mut events = state.runtime.services().events.subscribe();
+
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                log::warn!("WebSocket event forwarder lagged, skipped {} events", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+
+        route_execution_event(&state, &event).await;
+    }
+}
+
+/// Send `event` to every client of the execution's workspace with a
+/// matching subscription. Events of unknown executions go nowhere.
+async fn route_execution_event(state: &AppState, event: &ghostflow_core::ExecutionEvent) {
+    let execution_id = event.execution_id();
+    let Some((flow_id, workspace_id)) = execution_owner(state, &execution_id).await else {
+        return;
+    };
+
+    let message_type = match event {
+        ghostflow_core::ExecutionEvent::NodeStarted { .. } => WebSocketMessageType::NodeStarted,
+        ghostflow_core::ExecutionEvent::NodeCompleted { .. } => WebSocketMessageType::NodeCompleted,
+        ghostflow_core::ExecutionEvent::NodeFailed { .. } => WebSocketMessageType::NodeFailed,
+        ghostflow_core::ExecutionEvent::NodeOutput { .. } => WebSocketMessageType::NodeOutput,
+        ghostflow_core::ExecutionEvent::Token { .. } => WebSocketMessageType::NodeToken,
+        ghostflow_core::ExecutionEvent::NodeProgress { .. } => WebSocketMessageType::ExecutionProgress,
+        ghostflow_core::ExecutionEvent::ApprovalRequested { .. } => WebSocketMessageType::ApprovalRequested,
+    };
+
+    let message = WebSocketMessage {
+        message_type: message_type.clone(),
+        data: serde_json::to_value(event).unwrap_or_default(),
+        timestamp: Utc::now(),
+    };
+    let Ok(text) = serde_json::to_string(&message) else {
+        return;
+    };
+
+    let clients = state.websocket_clients.read().await;
+    for client in clients.values() {
+        let subscribed = client
+            .subscriptions
+            .values()
+            .any(|subscription| subscription.matches(&flow_id, &execution_id, &message_type));
+        if client.workspace_id == workspace_id && subscribed {
+            let _ = client.sender.send(Message::Text(text.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{app_state, flow, node};
+    use ghostflow_core::{BasicNodeRegistry, ExecutionEvent as EngineEvent, NodeRegistry};
+    use ghostflow_nodes::WebhookTriggerNode;
+
+    /// State whose runtime has finished one execution of a flow owned by `workspace_id`.
+    async fn state_with_execution(workspace_id: &str) -> (Arc<AppState>, Uuid) {
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("webhook_trigger".to_string(), Arc::new(WebhookTriggerNode::new()))
+            .unwrap();
+        let state = app_state(Arc::new(registry));
+
+        let mut flow = flow(vec![node("start", "webhook_trigger", serde_json::json!({}))]);
+        flow.metadata.workspace_id = workspace_id.to_string();
+        let flow_id = flow.id;
+        state.runtime.deploy_flow(flow).await.unwrap();
+        let execution = state
+            .runtime
+            .execute_flow_manually(&flow_id, serde_json::json!({}), false, None)
+            .await
+            .unwrap();
+
+        (Arc::new(state), execution.id)
+    }
+
+    fn connection(workspace_id: &str) -> WebSocketConnection {
+        let (sender, _) = tokio::sync::mpsc::unbounded_channel();
+        let (log_sender, _) = mpsc::channel(LOG_TAIL_BUFFER);
+        WebSocketConnection {
+            id: Uuid::new_v4().to_string(),
+            user_id: None,
+            workspace_id: Some(workspace_id.to_string()),
+            subscriptions: HashMap::new(),
+            sender,
+            log_sender,
+            tails: HashMap::new(),
+        }
+    }
+
+    fn line(execution_id: Uuid, node_id: &str, data: &str) -> EngineEvent {
+        EngineEvent::NodeOutput {
+            execution_id,
+            node_id: node_id.to_string(),
+            stream: OutputStream::Stdout,
+            data: data.to_string(),
+        }
+    }
+
+    fn completed(execution_id: Uuid, node_id: &str) -> EngineEvent {
+        EngineEvent::NodeCompleted {
+            execution_id,
+            node_id: node_id.to_string(),
+            node_type: "jarvis".to_string(),
+            duration_ms: 5,
+        }
+    }
+
+    #[test]
+    fn test_tail_request_parses_client_message() {
+        let execution_id = Uuid::new_v4();
+        let request: TailRequest =
+            serde_json::from_str(&format!(r#"{{"tail":"{}","node":"backup"}}"#, execution_id)).unwrap();
+        assert_eq!(request, TailRequest { tail: execution_id, node: "backup".to_string() });
+    }
+
+    #[tokio::test]
+    async fn test_tail_forwards_only_the_requested_nodes_lines() {
+        let execution_id = Uuid::new_v4();
+        let (tx, mut rx) = mpsc::channel(16);
+        let bus = EventBus::default();
+        let tail = spawn_log_tail(TailRequest { tail: execution_id, node: "backup".to_string() }, &bus, tx);
+
+        bus.publish(line(execution_id, "backup", "dumping database"));
+        bus.publish(line(execution_id, "notify", "not this node"));
+        bus.publish(line(Uuid::new_v4(), "backup", "not this execution"));
+        bus.publish(line(execution_id, "backup", "uploading archive"));
+        bus.publish(completed(execution_id, "backup"));
+
+        let mut lines = Vec::new();
+        while let Some(message) = rx.recv().await {
+            match message.message_type {
+                WebSocketMessageType::NodeLog => {
+                    assert_eq!(message.data["node_id"], "backup");
+                    assert_eq!(message.data["stream"], "stdout");
+                    lines.push(message.data["line"].as_str().unwrap().to_string());
+                }
+                WebSocketMessageType::TailEnded => break,
+                other => panic!("unexpected message {:?}", other),
+            }
+        }
+
+        assert_eq!(lines, vec!["dumping database", "uploading archive"]);
+        tail.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_slow_client_drops_lines_instead_of_buffering() {
+        let execution_id = Uuid::new_v4();
+        let (tx, mut rx) = mpsc::channel(2);
+        let bus = EventBus::default();
+        let tail = spawn_log_tail(TailRequest { tail: execution_id, node: "backup".to_string() }, &bus, tx);
+
+        // Nothing is read until the node has finished
+        for n in 1..=5 {
+            bus.publish(line(execution_id, "backup", &format!("line {}", n)));
+        }
+        bus.publish(completed(execution_id, "backup"));
+
+        let first = rx.recv().await.unwrap();
+        let second = rx.recv().await.unwrap();
+        assert_eq!(first.data["line"], "line 1");
+        assert_eq!(second.data["line"], "line 2");
+
+        let end = rx.recv().await.unwrap();
+        assert!(matches!(end.message_type, WebSocketMessageType::TailEnded));
+        assert_eq!(end.data["dropped"], 3);
+
+        tail.await.unwrap();
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tail_of_another_workspaces_execution_is_refused() {
+        let (state, execution_id) = state_with_execution("acme").await;
+        let request = TailRequest { tail: execution_id, node: "start".to_string() };
+
+        let mut outsider = connection("globex");
+        let refused = handle_tail_message(request.clone(), false, &mut outsider, &state).await;
+        assert_eq!(refused, Err(format!("Execution {} not found", execution_id)));
+        assert!(outsider.tails.is_empty());
+
+        let mut owner = connection("acme");
+        handle_tail_message(request, false, &mut owner, &state).await.unwrap();
+        assert_eq!(owner.tails.len(), 1);
+    }
+
+    fn register_client(state: &AppState, workspace_id: &str, subscription: SubscribeMessage) -> mpsc::UnboundedReceiver<Message> {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let client = WebSocketClient {
+            workspace_id: workspace_id.to_string(),
+            subscriptions: HashMap::from([("key".to_string(), subscription)]),
+            sender,
+        };
+        state.websocket_clients.try_write().unwrap().insert(Uuid::new_v4(), client);
+        receiver
+    }
+
+    fn subscription(execution_id: Option<Uuid>) -> SubscribeMessage {
+        SubscribeMessage {
+            flow_id: None,
+            execution_id: execution_id.map(|id| id.to_string()),
+            event_types: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_reach_only_subscribers_in_the_executions_workspace() {
+        let (state, execution_id) = state_with_execution("acme").await;
+        let mut subscriber = register_client(&state, "acme", subscription(Some(execution_id)));
+        let mut other_execution = register_client(&state, "acme", subscription(Some(Uuid::new_v4())));
+        let mut other_workspace = register_client(&state, "globex", subscription(None));
+
+        route_execution_event(&state, &line(execution_id, "start", "secret output")).await;
+
+        let Message::Text(text) = subscriber.try_recv().unwrap() else {
+            panic!("expected a text message");
+        };
+        let message: WebSocketMessage = serde_json::from_str(&text).unwrap();
+        assert_eq!(message.message_type, WebSocketMessageType::NodeOutput);
+        assert_eq!(message.data["data"], "secret output");
+        assert!(other_execution.try_recv().is_err());
+        assert!(other_workspace.try_recv().is_err());
+    }
+}
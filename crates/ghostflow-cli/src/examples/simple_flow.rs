@@ -1,6 +1,6 @@
-use ghostflow_core::{BasicNodeRegistry, NodeRegistry};
+use ghostflow_core::{BasicNodeRegistry, Services};
 use ghostflow_engine::FlowExecutor;
-use ghostflow_nodes::{HttpRequestNode};
+use ghostflow_nodes::register_builtin_nodes;
 use ghostflow_schema::*;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -11,12 +11,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging
     tracing_subscriber::fmt::init();
     
-    // Create node registry and register HTTP node
+    // Create node registry with all built-in nodes
+    let services = Services::default();
     let mut registry = BasicNodeRegistry::new();
-    registry.register_node("http_request".to_string(), Arc::new(HttpRequestNode::new()))?;
+    register_builtin_nodes(&mut registry, &services)?;
     
     // Create executor
-    let executor = FlowExecutor::new(Arc::new(registry));
+    let executor = FlowExecutor::new(Arc::new(registry)).with_services(services);
     
     // Create a simple flow that makes an HTTP request
     let flow = Flow {
@@ -41,6 +42,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 position: NodePosition { x: 100.0, y: 100.0 },
                 retry_config: None,
                 timeout_ms: Some(30000),
+                cache_ttl_ms: None,
+                node_version: None,
             });
             nodes
         },
@@ -50,15 +53,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             trigger_type: TriggerType::Manual,
             config: HashMap::new(),
             enabled: true,
+            priority: 0,
         }],
         parameters: HashMap::new(),
         secrets: vec![],
+        max_duration_ms: None,
+        max_concurrent_executions: None,
+        overflow_policy: OverflowPolicy::Queue,
         metadata: FlowMetadata {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             created_by: "example".to_string(),
             tags: vec!["example".to_string(), "http".to_string()],
             category: Some("example".to_string()),
+            workspace_id: "default".to_string(),
         },
     };
 
@@ -66,6 +74,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         trigger_type: "manual".to_string(),
         source: Some("example".to_string()),
         metadata: HashMap::new(),
+        dry_run: false,
+        priority: 0,
+        environment: None,
     };
 
     let input_data = serde_json::json!({
@@ -1,5 +1,11 @@
 use clap::{Parser, Subcommand};
 use anyhow::Result;
+use ghostflow_core::{
+    export_node_definitions, export_template, import_n8n_workflow, import_template, node_definitions_json,
+    BasicNodeRegistry, Services, TemplateFileFormat, TemplateRegistry,
+};
+use std::path::PathBuf;
+use ghostflow_nodes::register_builtin_nodes;
 
 #[derive(Parser)]
 #[command(name = "gflow")]
@@ -29,11 +35,50 @@ enum Commands {
         /// Path to flow file
         flow: String,
     },
+    /// List the available node types
+    Nodes {
+        /// Print full node definitions as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Share flow templates as files
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
+    },
+    /// Convert a workflow from another tool into a GhostFlow flow
+    Import {
+        /// Tool the workflow was exported from; only `n8n` is supported
+        #[arg(long)]
+        from: String,
+        /// Exported workflow file
+        file: PathBuf,
+        /// Write the flow JSON here instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplateCommands {
+    /// Write a template to a JSON or YAML file
+    Export {
+        /// Template id
+        id: String,
+        /// Output file; the extension picks the format. Prints JSON when omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Read and validate a template file
+    Import {
+        /// Path to a .json, .yaml or .yml template file
+        file: PathBuf,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    ghostflow_engine::init_tracing("gflow")?;
     
     let cli = Cli::parse();
     
@@ -50,7 +95,68 @@ async fn main() -> Result<()> {
         Commands::Validate { flow } => {
             println!("Validating flow: {}", flow);
         }
+        Commands::Nodes { json } => {
+            let mut registry = BasicNodeRegistry::new();
+            register_builtin_nodes(&mut registry, &Services::default())?;
+
+            if json {
+                let catalog = node_definitions_json(&registry)?;
+                println!("{}", serde_json::to_string_pretty(&catalog)?);
+            } else {
+                for definition in export_node_definitions(&registry) {
+                    println!("{:<28} {}", definition.id, definition.description);
+                }
+            }
+        }
+        Commands::Template { command: TemplateCommands::Export { id, output } } => {
+            let registry = TemplateRegistry::with_builtin_templates();
+            let template = registry
+                .get(&id)
+                .ok_or_else(|| anyhow::anyhow!("Unknown template '{}'", id))?;
+
+            match output {
+                Some(path) => {
+                    let contents = export_template(template, TemplateFileFormat::from_path(&path))?;
+                    std::fs::write(&path, contents)?;
+                    println!("Exported template '{}' to {}", id, path.display());
+                }
+                None => println!("{}", export_template(template, TemplateFileFormat::Json)?),
+            }
+        }
+        Commands::Template { command: TemplateCommands::Import { file } } => {
+            let contents = std::fs::read_to_string(&file)?;
+            let (template, warnings) = import_template(&contents, TemplateFileFormat::from_path(&file))?;
+            println!(
+                "Imported template '{}' ({} nodes, {} variables)",
+                template.id,
+                template.template_data.nodes.len(),
+                template.template_data.variables.len()
+            );
+            for warning in warnings {
+                println!("warning: {}", warning);
+            }
+        }
+        Commands::Import { from, file, output } => {
+            if !from.eq_ignore_ascii_case("n8n") {
+                anyhow::bail!("Unsupported import source '{}'; expected n8n", from);
+            }
+            let contents = std::fs::read_to_string(&file)?;
+            let (flow, warnings) = import_n8n_workflow(&contents)?;
+            let json = serde_json::to_string_pretty(&flow)?;
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, json)?;
+                    println!("Imported '{}' ({} nodes) to {}", flow.name, flow.nodes.len(), path.display());
+                }
+                None => println!("{}", json),
+            }
+            for warning in warnings {
+                eprintln!("warning: {}", warning);
+            }
+        }
     }
     
+    ghostflow_engine::shutdown_tracing();
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,178 @@
+use crate::{GhostFlowError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Approved,
+    Rejected,
+}
+
+/// A person's answer to a pending approval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Approval {
+    pub decision: ApprovalDecision,
+    #[serde(default)]
+    pub approver: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    pub decided_at: DateTime<Utc>,
+}
+
+impl Approval {
+    pub fn new(decision: ApprovalDecision, approver: Option<String>, comment: Option<String>) -> Self {
+        Self {
+            decision,
+            approver,
+            comment,
+            decided_at: Utc::now(),
+        }
+    }
+}
+
+/// An execution suspended at an approval node, waiting for a decision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingApproval {
+    pub execution_id: Uuid,
+    pub node_id: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    pub requested_at: DateTime<Utc>,
+    /// When the request is rejected automatically; never when unset
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Approval nodes waiting on a decision, keyed by execution and node id. The
+/// node registers and awaits the receiver; the API delivers the decision.
+#[derive(Default)]
+pub struct ApprovalRegistry {
+    pending: Mutex<HashMap<(Uuid, String), (PendingApproval, oneshot::Sender<Approval>)>>,
+}
+
+impl ApprovalRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a request; the receiver resolves when someone decides it.
+    pub fn request(&self, pending: PendingApproval) -> oneshot::Receiver<Approval> {
+        let (tx, rx) = oneshot::channel();
+        let key = (pending.execution_id, pending.node_id.clone());
+        self.pending.lock().unwrap().insert(key, (pending, tx));
+        rx
+    }
+
+    /// Deliver `approval` to the node waiting in `execution_id`. `node_id` may
+    /// be omitted when the execution waits on a single approval. Returns the
+    /// request that was decided.
+    pub fn decide(&self, execution_id: Uuid, node_id: Option<&str>, approval: Approval) -> Result<PendingApproval> {
+        let mut pending = self.pending.lock().unwrap();
+        let key = match node_id {
+            Some(node_id) => (execution_id, node_id.to_string()),
+            None => {
+                let mut waiting = pending.keys().filter(|(id, _)| *id == execution_id);
+                match (waiting.next(), waiting.next()) {
+                    (Some(key), None) => key.clone(),
+                    (Some(_), Some(_)) => {
+                        return Err(GhostFlowError::ValidationError {
+                            message: format!(
+                                "Execution {} is waiting on several approvals; specify a node_id",
+                                execution_id
+                            ),
+                        })
+                    }
+                    _ => return Err(not_found(execution_id)),
+                }
+            }
+        };
+
+        let (request, tx) = pending.remove(&key).ok_or_else(|| not_found(execution_id))?;
+        // The node gave up (timed out or was cancelled) after the lookup
+        tx.send(approval).map_err(|_| not_found(execution_id))?;
+        Ok(request)
+    }
+
+    /// Open requests for `execution_id`, oldest first.
+    pub fn pending(&self, execution_id: Uuid) -> Vec<PendingApproval> {
+        let mut requests: Vec<PendingApproval> = self
+            .pending
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|(request, _)| request.execution_id == execution_id)
+            .map(|(request, _)| request.clone())
+            .collect();
+        requests.sort_by_key(|r| r.requested_at);
+        requests
+    }
+
+    /// Withdraw a request, e.g. once it has timed out.
+    pub fn remove(&self, execution_id: Uuid, node_id: &str) -> Option<PendingApproval> {
+        self.pending
+            .lock()
+            .unwrap()
+            .remove(&(execution_id, node_id.to_string()))
+            .map(|(request, _)| request)
+    }
+}
+
+fn not_found(execution_id: Uuid) -> GhostFlowError {
+    GhostFlowError::NotFoundError {
+        resource_type: "pending approval".to_string(),
+        id: execution_id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(execution_id: Uuid, node_id: &str) -> PendingApproval {
+        PendingApproval {
+            execution_id,
+            node_id: node_id.to_string(),
+            message: None,
+            requested_at: Utc::now(),
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decision_reaches_the_waiting_node() {
+        let registry = ApprovalRegistry::new();
+        let execution_id = Uuid::new_v4();
+        let rx = registry.request(pending(execution_id, "gate"));
+        assert_eq!(registry.pending(execution_id).len(), 1);
+
+        let approval = Approval::new(ApprovalDecision::Approved, Some("alice".to_string()), None);
+        let decided = registry.decide(execution_id, None, approval.clone()).unwrap();
+
+        assert_eq!(decided.node_id, "gate");
+        assert_eq!(rx.await.unwrap(), approval);
+        assert!(registry.pending(execution_id).is_empty());
+    }
+
+    #[test]
+    fn test_ambiguous_or_missing_requests_are_errors() {
+        let registry = ApprovalRegistry::new();
+        let execution_id = Uuid::new_v4();
+        let reject = || Approval::new(ApprovalDecision::Rejected, None, None);
+
+        let missing = registry.decide(execution_id, None, reject()).unwrap_err();
+        assert!(matches!(missing, GhostFlowError::NotFoundError { .. }));
+
+        let _first = registry.request(pending(execution_id, "a"));
+        let _second = registry.request(pending(execution_id, "b"));
+        let ambiguous = registry.decide(execution_id, None, reject()).unwrap_err();
+        assert!(matches!(ambiguous, GhostFlowError::ValidationError { .. }));
+
+        assert!(registry.decide(execution_id, Some("b"), reject()).is_ok());
+        assert_eq!(registry.pending(execution_id)[0].node_id, "a");
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+pub use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Cancellation tokens for running executions, keyed by execution id. The
+/// executor registers a token when a flow starts and removes it when the
+/// flow ends; long-running nodes select on [`CancellationRegistry::token`]
+/// so a cancelled flow stops promptly.
+#[derive(Default)]
+pub struct CancellationRegistry {
+    tokens: Mutex<HashMap<Uuid, CancellationToken>>,
+}
+
+impl CancellationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create (or return the existing) token for `execution_id`.
+    pub fn register(&self, execution_id: Uuid) -> CancellationToken {
+        self.tokens
+            .lock()
+            .unwrap()
+            .entry(execution_id)
+            .or_default()
+            .clone()
+    }
+
+    /// Token for `execution_id`. Executions that were never registered get a
+    /// detached token that is never cancelled.
+    pub fn token(&self, execution_id: Uuid) -> CancellationToken {
+        self.tokens
+            .lock()
+            .unwrap()
+            .get(&execution_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Cancel a running execution. Returns false if it is not registered.
+    pub fn cancel(&self, execution_id: Uuid) -> bool {
+        match self.tokens.lock().unwrap().get(&execution_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_cancelled(&self, execution_id: Uuid) -> bool {
+        self.token(execution_id).is_cancelled()
+    }
+
+    pub fn remove(&self, execution_id: Uuid) {
+        self.tokens.lock().unwrap().remove(&execution_id);
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BreakerState {
+    Closed { consecutive_failures: u32 },
+    Open { until: Instant },
+    /// A single trial request is in flight after the open period elapsed
+    HalfOpen,
+}
+
+/// Circuit breakers keyed by `host:port`, shared by every node of an engine
+/// so one misbehaving service is backed off from everywhere.
+#[derive(Debug, Default)]
+pub struct CircuitBreakerRegistry {
+    breakers: Mutex<HashMap<String, BreakerState>>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a request to `host` may go out now. An open breaker whose
+    /// reset timeout has passed lets exactly one trial through.
+    pub fn try_acquire(&self, host: &str) -> bool {
+        let mut breakers = self.breakers.lock().unwrap();
+        let state = breakers
+            .entry(host.to_string())
+            .or_insert(BreakerState::Closed { consecutive_failures: 0 });
+        match *state {
+            BreakerState::Closed { .. } => true,
+            BreakerState::Open { until } if Instant::now() >= until => {
+                *state = BreakerState::HalfOpen;
+                true
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen => false,
+        }
+    }
+
+    pub fn record_success(&self, host: &str) {
+        self.breakers
+            .lock()
+            .unwrap()
+            .insert(host.to_string(), BreakerState::Closed { consecutive_failures: 0 });
+    }
+
+    /// Count a failed request to `host`. The breaker opens for
+    /// `reset_timeout` after `failure_threshold` failures in a row, or when
+    /// a trial request fails.
+    pub fn record_failure(&self, host: &str, failure_threshold: u32, reset_timeout: Duration) {
+        let mut breakers = self.breakers.lock().unwrap();
+        let state = breakers
+            .entry(host.to_string())
+            .or_insert(BreakerState::Closed { consecutive_failures: 0 });
+        let open = BreakerState::Open { until: Instant::now() + reset_timeout };
+        *state = match *state {
+            BreakerState::Closed { consecutive_failures } if consecutive_failures + 1 < failure_threshold => {
+                BreakerState::Closed { consecutive_failures: consecutive_failures + 1 }
+            }
+            BreakerState::Closed { .. } | BreakerState::HalfOpen => {
+                warn!("Circuit breaker for {} opened", host);
+                open
+            }
+            other @ BreakerState::Open { .. } => other,
+        };
+    }
+
+    pub fn is_open(&self, host: &str) -> bool {
+        matches!(
+            self.breakers.lock().unwrap().get(host),
+            Some(BreakerState::Open { .. }) | Some(BreakerState::HalfOpen)
+        )
+    }
+}
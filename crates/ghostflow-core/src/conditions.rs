@@ -0,0 +1,247 @@
+use crate::{GhostFlowError, Result};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Evaluate a condition against a JSON value: an edge's source node output,
+/// or an array element for the filter node.
+///
+/// Conditions compare dotted paths into the output with JSON literals, e.g.
+/// `status == "error"`, `result.count >= 3 && !dry_run`. Supported operators
+/// are `== != > >= < <=`, `&&`, `||`, `!` and parentheses. A bare path is
+/// tested for truthiness; a path that does not exist is null.
+pub fn evaluate_condition(condition: &str, output: &Value) -> Result<bool> {
+    let tokens = tokenize(condition)?;
+    let mut parser = Parser { tokens: &tokens, pos: 0, output };
+    let value = parser.or()?;
+    if parser.pos != tokens.len() {
+        return Err(condition_error(condition, "unexpected trailing input"));
+    }
+    Ok(truthy(&value))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(Value),
+    Path(String),
+    Op(&'static str),
+    Not,
+    LParen,
+    RParen,
+}
+
+fn condition_error(condition: &str, message: &str) -> GhostFlowError {
+    GhostFlowError::ValidationError {
+        message: format!("Invalid condition '{}': {}", condition, message),
+    }
+}
+
+fn tokenize(condition: &str) -> Result<Vec<Token>> {
+    const OPERATORS: [&str; 8] = ["==", "!=", ">=", "<=", "&&", "||", ">", "<"];
+
+    let chars: Vec<char> = condition.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let rest: String = chars[i..].iter().take(2).collect();
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if let Some(&op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push(Token::Op(op));
+            i += op.len();
+        } else if c == '!' {
+            tokens.push(Token::Not);
+            i += 1;
+        } else if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::LParen } else { Token::RParen });
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let end = chars[i + 1..]
+                .iter()
+                .position(|&ch| ch == c)
+                .ok_or_else(|| condition_error(condition, "unterminated string"))?;
+            tokens.push(Token::Literal(Value::String(chars[i + 1..i + 1 + end].iter().collect())));
+            i += end + 2;
+        } else if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            let len = chars[i + 1..]
+                .iter()
+                .take_while(|ch| ch.is_ascii_digit() || **ch == '.')
+                .count();
+            let text: String = chars[i..=i + len].iter().collect();
+            let number: f64 = text
+                .parse()
+                .map_err(|_| condition_error(condition, &format!("bad number '{}'", text)))?;
+            tokens.push(Token::Literal(serde_json::json!(number)));
+            i += len + 1;
+        } else if c.is_alphanumeric() || c == '_' {
+            let len = chars[i..]
+                .iter()
+                .take_while(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+                .count();
+            let word: String = chars[i..i + len].iter().collect();
+            tokens.push(match word.as_str() {
+                "true" => Token::Literal(Value::Bool(true)),
+                "false" => Token::Literal(Value::Bool(false)),
+                "null" => Token::Literal(Value::Null),
+                _ => Token::Path(word),
+            });
+            i += len;
+        } else {
+            return Err(condition_error(condition, &format!("unexpected character '{}'", c)));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    output: &'a Value,
+}
+
+impl Parser<'_> {
+    fn next_if(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<Value> {
+        let mut value = self.and()?;
+        while self.next_if(&Token::Op("||")) {
+            let rhs = self.and()?;
+            value = Value::Bool(truthy(&value) || truthy(&rhs));
+        }
+        Ok(value)
+    }
+
+    fn and(&mut self) -> Result<Value> {
+        let mut value = self.comparison()?;
+        while self.next_if(&Token::Op("&&")) {
+            let rhs = self.comparison()?;
+            value = Value::Bool(truthy(&value) && truthy(&rhs));
+        }
+        Ok(value)
+    }
+
+    fn comparison(&mut self) -> Result<Value> {
+        let lhs = self.unary()?;
+        let op = match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) if !matches!(*op, "&&" | "||") => *op,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        let rhs = self.unary()?;
+
+        let result = match op {
+            "==" => json_eq(&lhs, &rhs),
+            "!=" => !json_eq(&lhs, &rhs),
+            _ => match (json_cmp(&lhs, &rhs), op) {
+                (Some(ordering), ">") => ordering == Ordering::Greater,
+                (Some(ordering), ">=") => ordering != Ordering::Less,
+                (Some(ordering), "<") => ordering == Ordering::Less,
+                (Some(ordering), "<=") => ordering != Ordering::Greater,
+                _ => false,
+            },
+        };
+        Ok(Value::Bool(result))
+    }
+
+    fn unary(&mut self) -> Result<Value> {
+        if self.next_if(&Token::Not) {
+            return Ok(Value::Bool(!truthy(&self.unary()?)));
+        }
+
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Literal(value)) => Ok(value),
+            Some(Token::Path(path)) => Ok(lookup(self.output, &path)),
+            Some(Token::LParen) => {
+                let value = self.or()?;
+                if !self.next_if(&Token::RParen) {
+                    return Err(GhostFlowError::ValidationError {
+                        message: "Invalid condition: missing ')'".to_string(),
+                    });
+                }
+                Ok(value)
+            }
+            other => Err(GhostFlowError::ValidationError {
+                message: format!("Invalid condition: expected a value, found {:?}", other),
+            }),
+        }
+    }
+}
+
+fn lookup(output: &Value, path: &str) -> Value {
+    let mut current = output;
+    for segment in path.split('.') {
+        let next = match current {
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            Value::Object(map) => map.get(segment),
+            _ => None,
+        };
+        match next {
+            Some(value) => current = value,
+            None => return Value::Null,
+        }
+    }
+    current.clone()
+}
+
+fn json_eq(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs.as_f64(), rhs.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => lhs == rhs,
+    }
+}
+
+fn json_cmp(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Number(_), Value::Number(_)) => lhs.as_f64()?.partial_cmp(&rhs.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64() != Some(0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_comparisons_against_output() {
+        let output = json!({ "status": "error", "result": { "count": 3, "hosts": ["pve-01"] }, "dry_run": false });
+
+        assert!(evaluate_condition(r#"status == "error""#, &output).unwrap());
+        assert!(!evaluate_condition("status != 'error'", &output).unwrap());
+        assert!(evaluate_condition("result.count >= 3 && !dry_run", &output).unwrap());
+        assert!(evaluate_condition("result.hosts.0 == 'pve-01'", &output).unwrap());
+        assert!(evaluate_condition("(result.count < 2 || status == 'error') && result.hosts", &output).unwrap());
+        assert!(!evaluate_condition("missing.field", &output).unwrap());
+    }
+
+    #[test]
+    fn test_malformed_condition_is_an_error() {
+        assert!(evaluate_condition("status == ", &json!({})).is_err());
+        assert!(evaluate_condition("status == 'error", &json!({})).is_err());
+        assert!(evaluate_condition("(status", &json!({})).is_err());
+    }
+}
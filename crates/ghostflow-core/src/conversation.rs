@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+/// One turn of a multi-turn LLM conversation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversationMessage {
+    /// `user` or `assistant`
+    pub role: String,
+    pub content: String,
+}
+
+impl ConversationMessage {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: "user".to_string(),
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: "assistant".to_string(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Message history of chat-style flows, keyed by flow and conversation id.
+/// Unlike [`crate::FlowVariableStore`] it outlives a single execution, so
+/// each execution of the flow can continue where the previous one left off.
+#[derive(Default)]
+pub struct ConversationStore {
+    conversations: RwLock<HashMap<(Uuid, String), Vec<ConversationMessage>>>,
+}
+
+impl ConversationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every message of the conversation, oldest first.
+    pub fn messages(&self, flow_id: Uuid, conversation_id: &str) -> Vec<ConversationMessage> {
+        self.conversations
+            .read()
+            .unwrap()
+            .get(&(flow_id, conversation_id.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn append(
+        &self,
+        flow_id: Uuid,
+        conversation_id: &str,
+        messages: impl IntoIterator<Item = ConversationMessage>,
+    ) {
+        self.conversations
+            .write()
+            .unwrap()
+            .entry((flow_id, conversation_id.to_string()))
+            .or_default()
+            .extend(messages);
+    }
+
+    pub fn clear(&self, flow_id: Uuid, conversation_id: &str) {
+        self.conversations
+            .write()
+            .unwrap()
+            .remove(&(flow_id, conversation_id.to_string()));
+    }
+}
+
+/// Rough token count for budgeting, at about four characters per token.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// The most recent messages whose estimated tokens fit in `token_budget`,
+/// oldest first. Older turns are dropped whole rather than truncated.
+pub fn conversation_window(messages: &[ConversationMessage], token_budget: usize) -> &[ConversationMessage] {
+    let mut used = 0;
+    let kept = messages
+        .iter()
+        .rev()
+        .take_while(|message| {
+            used += estimate_tokens(&message.content);
+            used <= token_budget
+        })
+        .count();
+    &messages[messages.len() - kept..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversations_are_scoped_per_flow() {
+        let store = ConversationStore::new();
+        let (flow, other_flow) = (Uuid::new_v4(), Uuid::new_v4());
+
+        store.append(flow, "chat-1", [ConversationMessage::user("hi"), ConversationMessage::assistant("hello")]);
+        assert_eq!(store.messages(flow, "chat-1").len(), 2);
+        assert!(store.messages(other_flow, "chat-1").is_empty());
+        assert!(store.messages(flow, "chat-2").is_empty());
+
+        store.clear(flow, "chat-1");
+        assert!(store.messages(flow, "chat-1").is_empty());
+    }
+
+    #[test]
+    fn test_window_keeps_the_latest_turns_within_budget() {
+        let messages = vec![
+            ConversationMessage::user("a".repeat(40)),
+            ConversationMessage::assistant("b".repeat(40)),
+            ConversationMessage::user("c".repeat(8)),
+        ];
+
+        assert_eq!(conversation_window(&messages, 100).len(), 3);
+        assert_eq!(conversation_window(&messages, 12), &messages[1..]);
+        assert!(conversation_window(&messages, 1).is_empty());
+    }
+}
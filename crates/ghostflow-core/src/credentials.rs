@@ -273,6 +273,76 @@ impl CredentialVault for SecureVault {
     }
 }
 
+/// Unencrypted in-process [`CredentialVault`] for development and tests.
+#[derive(Default)]
+pub struct MemoryCredentialVault {
+    credentials: std::sync::RwLock<HashMap<String, Credential>>,
+}
+
+impl MemoryCredentialVault {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CredentialVault for MemoryCredentialVault {
+    async fn store(&self, credential: Credential) -> Result<String> {
+        let id = credential.id.clone();
+        self.credentials.write().unwrap().insert(id.clone(), credential);
+        Ok(id)
+    }
+
+    async fn retrieve(&self, id: &str) -> Result<Option<Credential>> {
+        Ok(self.credentials.read().unwrap().get(id).cloned())
+    }
+
+    async fn update(&self, id: &str, credential: Credential) -> Result<()> {
+        self.credentials.write().unwrap().insert(id.to_string(), credential);
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.credentials.write().unwrap().remove(id);
+        Ok(())
+    }
+
+    async fn list(&self, workspace_id: &str) -> Result<Vec<Credential>> {
+        Ok(self
+            .credentials
+            .read()
+            .unwrap()
+            .values()
+            .filter(|c| c.workspace_id == workspace_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn search(&self, workspace_id: &str, query: &str) -> Result<Vec<Credential>> {
+        let query = query.to_lowercase();
+        Ok(self
+            .list(workspace_id)
+            .await?
+            .into_iter()
+            .filter(|c| c.name.to_lowercase().contains(&query))
+            .collect())
+    }
+
+    async fn encrypt(&self, data: &str) -> Result<String> {
+        Ok(data.to_string())
+    }
+
+    async fn decrypt(&self, data: &str) -> Result<String> {
+        Ok(data.to_string())
+    }
+
+    async fn refresh_oauth_token(&self, credential_id: &str) -> Result<OAuth2Credential> {
+        Err(crate::GhostFlowError::ConfigurationError {
+            message: format!("Credential '{}' cannot be refreshed by the in-memory vault", credential_id),
+        })
+    }
+}
+
 pub fn get_credential_templates() -> Vec<CredentialTemplate> {
     vec![
         CredentialTemplate {
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use ghostflow_schema::DeadLetter;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{DeadLetterStorage, Result};
+
+/// In-process [`DeadLetterStorage`]. Entries are lost on restart.
+#[derive(Default)]
+pub struct MemoryDeadLetterStore {
+    dead_letters: Mutex<HashMap<Uuid, DeadLetter>>,
+}
+
+impl MemoryDeadLetterStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl DeadLetterStorage for MemoryDeadLetterStore {
+    async fn save_dead_letter(&self, dead_letter: &DeadLetter) -> Result<()> {
+        self.dead_letters
+            .lock()
+            .unwrap()
+            .insert(dead_letter.execution_id, dead_letter.clone());
+        Ok(())
+    }
+
+    async fn get_dead_letter(&self, execution_id: &Uuid) -> Result<Option<DeadLetter>> {
+        Ok(self.dead_letters.lock().unwrap().get(execution_id).cloned())
+    }
+
+    async fn list_dead_letters(&self) -> Result<Vec<DeadLetter>> {
+        let mut list: Vec<DeadLetter> = self.dead_letters.lock().unwrap().values().cloned().collect();
+        list.sort_by(|a, b| b.failed_at.cmp(&a.failed_at));
+        Ok(list)
+    }
+
+    async fn delete_dead_letter(&self, execution_id: &Uuid) -> Result<()> {
+        self.dead_letters.lock().unwrap().remove(execution_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// A named stage a flow runs in, e.g. `staging` or `prod`. Selected when an
+/// execution starts, it supplies the values of the flow's parameters and
+/// which credentials its secrets refer to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Environment {
+    pub name: String,
+    /// Values for `{{flow.params.<name>}}`, overriding the flow's defaults
+    #[serde(default)]
+    pub variables: HashMap<String, Value>,
+    /// Credential id or name to use for each of the flow's secrets
+    #[serde(default)]
+    pub credentials: HashMap<String, String>,
+}
+
+impl Environment {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            variables: HashMap::new(),
+            credentials: HashMap::new(),
+        }
+    }
+
+    pub fn with_variable(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.variables.insert(name.into(), value);
+        self
+    }
+
+    pub fn with_credential(mut self, secret: impl Into<String>, credential: impl Into<String>) -> Self {
+        self.credentials.insert(secret.into(), credential.into());
+        self
+    }
+}
+
+/// Environments executions can be started in, by name.
+#[derive(Default)]
+pub struct EnvironmentStore {
+    environments: RwLock<HashMap<String, Environment>>,
+}
+
+impl EnvironmentStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `environment`, replacing one with the same name.
+    pub fn insert(&self, environment: Environment) {
+        self.environments
+            .write()
+            .unwrap()
+            .insert(environment.name.clone(), environment);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Environment> {
+        self.environments.read().unwrap().get(name).cloned()
+    }
+
+    pub fn remove(&self, name: &str) -> Option<Environment> {
+        self.environments.write().unwrap().remove(name)
+    }
+
+    /// Every environment, sorted by name.
+    pub fn list(&self) -> Vec<Environment> {
+        let mut environments: Vec<Environment> = self.environments.read().unwrap().values().cloned().collect();
+        environments.sort_by(|a, b| a.name.cmp(&b.name));
+        environments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_replaces_environment_by_name() {
+        let store = EnvironmentStore::new();
+        store.insert(Environment::new("staging").with_variable("endpoint", Value::from("https://old")));
+        store.insert(Environment::new("staging").with_variable("endpoint", Value::from("https://stg")));
+        store.insert(Environment::new("prod").with_credential("slack", "slack-prod"));
+
+        let names: Vec<String> = store.list().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["prod", "staging"]);
+        assert_eq!(store.get("staging").unwrap().variables["endpoint"], "https://stg");
+        assert_eq!(store.get("prod").unwrap().credentials["slack"], "slack-prod");
+
+        assert!(store.remove("prod").is_some());
+        assert!(store.get("prod").is_none());
+    }
+}
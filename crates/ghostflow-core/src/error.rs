@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -35,6 +36,12 @@ pub enum GhostFlowError {
     #[error("Timeout error: operation timed out after {timeout_ms}ms")]
     TimeoutError { timeout_ms: u64 },
     
+    #[error("Execution {execution_id} was cancelled")]
+    Cancelled { execution_id: uuid::Uuid },
+    
+    #[error("Invalid input: {}", format_field_errors(.errors))]
+    InvalidInput { errors: Vec<FieldError> },
+    
     #[error("Rate limit exceeded: {message}")]
     RateLimitError { message: String },
     
@@ -45,4 +52,55 @@ pub enum GhostFlowError {
     InternalError { message: String },
 }
 
-pub type Result<T> = std::result::Result<T, GhostFlowError>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, GhostFlowError>;
+
+impl GhostFlowError {
+    /// Stable, machine-readable name of the error kind, e.g. for the error
+    /// objects routed to `on_error` edges.
+    pub fn code(&self) -> &'static str {
+        match self {
+            GhostFlowError::ValidationError { .. } => "validation_error",
+            GhostFlowError::NodeExecutionError { .. } => "node_execution_error",
+            GhostFlowError::FlowExecutionError { .. } => "flow_execution_error",
+            GhostFlowError::ConfigurationError { .. } => "configuration_error",
+            GhostFlowError::DatabaseError(_) => "database_error",
+            GhostFlowError::SerializationError(_) => "serialization_error",
+            GhostFlowError::IoError(_) => "io_error",
+            GhostFlowError::NetworkError(_) => "network_error",
+            GhostFlowError::AuthenticationError { .. } => "authentication_error",
+            GhostFlowError::AuthorizationError { .. } => "authorization_error",
+            GhostFlowError::TimeoutError { .. } => "timeout",
+            GhostFlowError::Cancelled { .. } => "cancelled",
+            GhostFlowError::InvalidInput { .. } => "invalid_input",
+            GhostFlowError::RateLimitError { .. } => "rate_limited",
+            GhostFlowError::NotFoundError { .. } => "not_found",
+            GhostFlowError::InternalError { .. } => "internal_error",
+        }
+    }
+
+    /// The error as handed to error-handling branches: `code`, `message` and
+    /// the id of the node that failed.
+    pub fn error_object(&self, node_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "code": self.code(),
+            "message": self.to_string(),
+            "node_id": node_id,
+        })
+    }
+}
+
+/// A single field that failed validation, reported alongside its siblings so
+/// callers can surface every problem at once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+fn format_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Alerts already escalated by escalation nodes, so repeats within a
+/// window are held back. Keys are scoped by the caller, e.g. by flow and
+/// tier.
+#[derive(Default)]
+pub struct EscalationStore {
+    entries: Mutex<HashMap<String, Escalated>>,
+}
+
+struct Escalated {
+    /// `None` when the window outlasts what the clock can represent
+    expires_at: Option<Instant>,
+    suppressed: u64,
+}
+
+impl EscalationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an escalation of `key`. Returns how many repeats were
+    /// suppressed so far if one is already within its window, else `None`.
+    pub fn check(&self, key: String, window: Duration) -> Option<u64> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, e| e.expires_at.is_none_or(|at| at > now));
+        match entries.get_mut(&key) {
+            Some(escalated) => {
+                escalated.suppressed += 1;
+                Some(escalated.suppressed)
+            }
+            None => {
+                entries.insert(
+                    key,
+                    Escalated {
+                        expires_at: now.checked_add(window),
+                        suppressed: 0,
+                    },
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_beyond_the_clock_does_not_overflow() {
+        let store = EscalationStore::new();
+        let window = Duration::from_secs(u64::MAX);
+
+        assert_eq!(store.check("alert".to_string(), window), None);
+        assert_eq!(store.check("alert".to_string(), window), Some(1));
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+const EVENT_BUS_CAPACITY: usize = 1024;
+
+/// Progress events emitted while a flow executes. These feed the WebSocket
+/// progress stream so clients can follow an execution as it happens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ExecutionEvent {
+    NodeStarted {
+        execution_id: Uuid,
+        node_id: String,
+        node_type: String,
+    },
+    NodeCompleted {
+        execution_id: Uuid,
+        node_id: String,
+        node_type: String,
+        duration_ms: u64,
+    },
+    NodeFailed {
+        execution_id: Uuid,
+        node_id: String,
+        node_type: String,
+        error: String,
+    },
+    NodeOutput {
+        execution_id: Uuid,
+        node_id: String,
+        stream: OutputStream,
+        data: String,
+    },
+    /// A chunk of generated text from a streaming LLM node
+    Token {
+        execution_id: Uuid,
+        node_id: String,
+        text: String,
+    },
+    /// Progress of a long-running step inside a node, such as a model download
+    NodeProgress {
+        execution_id: Uuid,
+        node_id: String,
+        message: String,
+        completed: Option<u64>,
+        total: Option<u64>,
+    },
+    /// The execution is suspended until someone approves or rejects it
+    ApprovalRequested {
+        execution_id: Uuid,
+        node_id: String,
+        message: Option<String>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    },
+}
+
+impl ExecutionEvent {
+    pub fn execution_id(&self) -> Uuid {
+        match self {
+            ExecutionEvent::NodeStarted { execution_id, .. }
+            | ExecutionEvent::NodeCompleted { execution_id, .. }
+            | ExecutionEvent::NodeFailed { execution_id, .. }
+            | ExecutionEvent::NodeOutput { execution_id, .. }
+            | ExecutionEvent::Token { execution_id, .. }
+            | ExecutionEvent::NodeProgress { execution_id, .. }
+            | ExecutionEvent::ApprovalRequested { execution_id, .. } => *execution_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Broadcast channel carrying [`ExecutionEvent`]s to any number of listeners.
+/// Clones publish to and subscribe on the same channel.
+/// Publishing never blocks; events are dropped when nobody is subscribed and
+/// slow subscribers observe a `Lagged` error rather than stalling execution.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<ExecutionEvent>,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: ExecutionEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(EVENT_BUS_CAPACITY)
+    }
+}
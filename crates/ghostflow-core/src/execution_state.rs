@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use ghostflow_schema::ExecutionState;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{ExecutionStateStorage, Result};
+
+/// In-process [`ExecutionStateStorage`]. States are kept serialised, exactly
+/// as a database-backed store would hold them, so anything that does not
+/// survive a JSON round trip shows up here first.
+#[derive(Default)]
+pub struct MemoryExecutionStateStore {
+    states: Mutex<HashMap<Uuid, String>>,
+}
+
+impl MemoryExecutionStateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ExecutionStateStorage for MemoryExecutionStateStore {
+    async fn save_state(&self, state: &ExecutionState) -> Result<()> {
+        let serialized = serde_json::to_string(state)?;
+        self.states.lock().unwrap().insert(state.execution_id, serialized);
+        Ok(())
+    }
+
+    async fn load_state(&self, execution_id: &Uuid) -> Result<Option<ExecutionState>> {
+        let states = self.states.lock().unwrap();
+        states
+            .get(execution_id)
+            .map(|serialized| serde_json::from_str(serialized).map_err(Into::into))
+            .transpose()
+    }
+
+    async fn delete_state(&self, execution_id: &Uuid) -> Result<()> {
+        self.states.lock().unwrap().remove(execution_id);
+        Ok(())
+    }
+
+    async fn list_states(&self) -> Result<Vec<ExecutionState>> {
+        let states = self.states.lock().unwrap();
+        states
+            .values()
+            .map(|serialized| serde_json::from_str(serialized).map_err(Into::into))
+            .collect()
+    }
+}
@@ -0,0 +1,172 @@
+use ghostflow_schema::{Flow, TriggerType};
+use serde_json::Value;
+
+use crate::templates::{validate_variable, TemplateVariable};
+use crate::{FieldError, GhostFlowError, Result};
+
+/// Key in a manual trigger's `config` holding the declared input fields.
+pub const INPUT_SCHEMA_KEY: &str = "input_schema";
+
+/// Input fields declared on the flow's enabled manual trigger, if any. The
+/// schema reuses [`TemplateVariable`], so types, defaults and
+/// `VariableValidation` rules mean the same thing as they do for templates.
+pub fn manual_input_schema(flow: &Flow) -> Result<Vec<TemplateVariable>> {
+    let schema = flow
+        .triggers
+        .iter()
+        .filter(|t| t.enabled && matches!(t.trigger_type, TriggerType::Manual))
+        .find_map(|t| t.config.get(INPUT_SCHEMA_KEY).filter(|s| !s.is_null()));
+
+    match schema {
+        Some(schema) => serde_json::from_value(schema.clone()).map_err(|e| {
+            GhostFlowError::ConfigurationError {
+                message: format!("Invalid input schema on flow '{}': {}", flow.id, e),
+            }
+        }),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Check `input` against the flow's manual input schema before anything
+/// runs. Returns the input with defaults filled in, or
+/// `GhostFlowError::InvalidInput` listing every failing field. Fields not in
+/// the schema are passed through untouched.
+pub fn validate_flow_input(flow: &Flow, input: &Value) -> Result<Value> {
+    let schema = manual_input_schema(flow)?;
+    if schema.is_empty() {
+        return Ok(input.clone());
+    }
+
+    let mut fields = match input {
+        Value::Object(fields) => fields.clone(),
+        Value::Null => serde_json::Map::new(),
+        _ => {
+            return Err(GhostFlowError::InvalidInput {
+                errors: vec![FieldError {
+                    field: "input".to_string(),
+                    message: "expected an object".to_string(),
+                }],
+            })
+        }
+    };
+
+    let mut errors = Vec::new();
+    for variable in &schema {
+        let value = fields
+            .get(&variable.name)
+            .filter(|v| !v.is_null())
+            .or(variable.default_value.as_ref())
+            .cloned();
+
+        match value {
+            Some(value) => match validate_variable(variable, &value) {
+                Ok(()) => {
+                    fields.insert(variable.name.clone(), value);
+                }
+                Err(message) => errors.push(FieldError { field: variable.name.clone(), message }),
+            },
+            None if variable.required => errors.push(FieldError {
+                field: variable.name.clone(),
+                message: "is required".to_string(),
+            }),
+            None => {}
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(Value::Object(fields))
+    } else {
+        Err(GhostFlowError::InvalidInput { errors })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ghostflow_schema::{FlowMetadata, FlowTrigger, OverflowPolicy};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn flow_with_schema(schema: Value) -> Flow {
+        Flow {
+            id: Uuid::new_v4(),
+            name: "Restart VM".to_string(),
+            description: None,
+            version: "1.0.0".to_string(),
+            nodes: HashMap::new(),
+            edges: vec![],
+            triggers: vec![FlowTrigger {
+                id: "manual".to_string(),
+                trigger_type: TriggerType::Manual,
+                config: HashMap::from([("input_schema".to_string(), schema)]),
+                enabled: true,
+                priority: 0,
+            }],
+            parameters: HashMap::new(),
+            secrets: vec![],
+            max_duration_ms: None,
+            max_concurrent_executions: None,
+            overflow_policy: OverflowPolicy::Queue,
+            metadata: FlowMetadata {
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                created_by: "test".to_string(),
+                tags: vec![],
+                category: None,
+                workspace_id: "default".to_string(),
+            },
+        }
+    }
+
+    fn restart_flow() -> Flow {
+        flow_with_schema(json!([
+            {
+                "name": "vmid",
+                "display_name": "VM ID",
+                "description": "Proxmox VM to restart",
+                "variable_type": "number",
+                "default_value": null,
+                "required": true,
+                "placeholder": null,
+                "validation": null
+            },
+            {
+                "name": "mode",
+                "display_name": "Mode",
+                "description": "How to restart",
+                "variable_type": "select",
+                "default_value": "reboot",
+                "required": false,
+                "placeholder": null,
+                "validation": { "min_length": null, "max_length": null, "pattern": null, "options": ["reboot", "reset"] }
+            }
+        ]))
+    }
+
+    #[test]
+    fn test_valid_input_gets_defaults() {
+        let input = validate_flow_input(&restart_flow(), &json!({ "vmid": 101, "note": "kept" })).unwrap();
+        assert_eq!(input, json!({ "vmid": 101, "mode": "reboot", "note": "kept" }));
+    }
+
+    #[test]
+    fn test_schema_violation_lists_each_field() {
+        let error = validate_flow_input(&restart_flow(), &json!({ "mode": "shutdown" })).unwrap_err();
+        match error {
+            GhostFlowError::InvalidInput { errors } => {
+                let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+                assert_eq!(fields, vec!["vmid", "mode"]);
+                assert_eq!(errors[0].message, "is required");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_flow_without_schema_accepts_anything() {
+        let mut flow = restart_flow();
+        flow.triggers.clear();
+        assert_eq!(validate_flow_input(&flow, &json!("raw")).unwrap(), json!("raw"));
+    }
+}
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use ghostflow_schema::{Flow, FlowVersion};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{FlowStorage, Result};
+
+/// In-process [`FlowStorage`] keeping every saved version of each flow.
+#[derive(Default)]
+pub struct MemoryFlowStorage {
+    versions: Mutex<HashMap<Uuid, Vec<FlowVersion>>>,
+}
+
+impl MemoryFlowStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl FlowStorage for MemoryFlowStorage {
+    async fn save_flow(&self, flow: &Flow) -> Result<u32> {
+        let mut versions = self.versions.lock().unwrap();
+        let history = versions.entry(flow.id).or_default();
+        let version = history.last().map_or(1, |v| v.version + 1);
+
+        let mut flow = flow.clone();
+        flow.version = version.to_string();
+        history.push(FlowVersion {
+            version,
+            saved_at: chrono::Utc::now(),
+            flow,
+        });
+        Ok(version)
+    }
+
+    async fn get_flow(&self, flow_id: &Uuid) -> Result<Option<Flow>> {
+        let versions = self.versions.lock().unwrap();
+        Ok(versions
+            .get(flow_id)
+            .and_then(|history| history.last())
+            .map(|v| v.flow.clone()))
+    }
+
+    async fn list_flows(&self) -> Result<Vec<Flow>> {
+        let versions = self.versions.lock().unwrap();
+        Ok(versions
+            .values()
+            .filter_map(|history| history.last())
+            .map(|v| v.flow.clone())
+            .collect())
+    }
+
+    async fn delete_flow(&self, flow_id: &Uuid) -> Result<()> {
+        self.versions.lock().unwrap().remove(flow_id);
+        Ok(())
+    }
+
+    async fn list_versions(&self, flow_id: &Uuid) -> Result<Vec<FlowVersion>> {
+        let versions = self.versions.lock().unwrap();
+        Ok(versions.get(flow_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_flow_version(&self, flow_id: &Uuid, version: u32) -> Result<Option<Flow>> {
+        let versions = self.versions.lock().unwrap();
+        Ok(versions
+            .get(flow_id)
+            .and_then(|history| history.iter().find(|v| v.version == version))
+            .map(|v| v.flow.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ghostflow_schema::{FlowMetadata, OverflowPolicy};
+
+    fn flow(id: Uuid, name: &str) -> Flow {
+        Flow {
+            id,
+            name: name.to_string(),
+            description: None,
+            version: "1.0.0".to_string(),
+            nodes: HashMap::new(),
+            edges: vec![],
+            triggers: vec![],
+            parameters: HashMap::new(),
+            secrets: vec![],
+            max_duration_ms: None,
+            max_concurrent_executions: None,
+            overflow_policy: OverflowPolicy::Queue,
+            metadata: FlowMetadata {
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                created_by: "test".to_string(),
+                tags: vec![],
+                category: None,
+                workspace_id: "default".to_string(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_three_versions_and_roll_back_to_first() {
+        let storage = MemoryFlowStorage::new();
+        let id = Uuid::new_v4();
+
+        for name in ["Restart VM", "Restart VM (notify)", "Restart VM (notify + ticket)"] {
+            storage.save_flow(&flow(id, name)).await.unwrap();
+        }
+        assert_eq!(storage.get_flow(&id).await.unwrap().unwrap().name, "Restart VM (notify + ticket)");
+
+        let restored = storage.rollback_flow(&id, 1).await.unwrap();
+        assert_eq!(restored, 4);
+
+        let current = storage.get_flow(&id).await.unwrap().unwrap();
+        assert_eq!(current.name, "Restart VM");
+        assert_eq!(current.version, "4");
+
+        // Rolling back adds a version; the history itself is untouched
+        let history = storage.list_versions(&id).await.unwrap();
+        let names: Vec<(u32, &str)> = history.iter().map(|v| (v.version, v.flow.name.as_str())).collect();
+        assert_eq!(
+            names,
+            vec![
+                (1, "Restart VM"),
+                (2, "Restart VM (notify)"),
+                (3, "Restart VM (notify + ticket)"),
+                (4, "Restart VM"),
+            ]
+        );
+        assert_eq!(storage.get_flow_version(&id, 2).await.unwrap().unwrap().version, "2");
+    }
+
+    #[tokio::test]
+    async fn test_saved_version_replaces_the_flow_version() {
+        let storage = MemoryFlowStorage::new();
+        let id = Uuid::new_v4();
+        let mut edited = flow(id, "Restart VM");
+        edited.version = "2.1.0-beta".to_string();
+
+        assert_eq!(storage.save_flow(&edited).await.unwrap(), 1);
+        assert_eq!(storage.save_flow(&edited).await.unwrap(), 2);
+
+        assert_eq!(storage.get_flow(&id).await.unwrap().unwrap().version, "2");
+        assert_eq!(storage.get_flow_version(&id, 1).await.unwrap().unwrap().version, "1");
+    }
+
+    #[tokio::test]
+    async fn test_rollback_to_unknown_version_fails() {
+        let storage = MemoryFlowStorage::new();
+        let id = Uuid::new_v4();
+        storage.save_flow(&flow(id, "Restart VM")).await.unwrap();
+
+        assert!(storage.rollback_flow(&id, 7).await.is_err());
+        assert_eq!(storage.list_versions(&id).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::{IdempotencyStorage, Result};
+
+/// How long an idempotency key is honoured unless the caller says otherwise.
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// In-process [`IdempotencyStorage`]. Expired keys are dropped lazily.
+#[derive(Default)]
+pub struct MemoryIdempotencyStore {
+    keys: Mutex<HashMap<String, (Uuid, Instant)>>,
+}
+
+impl MemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl IdempotencyStorage for MemoryIdempotencyStore {
+    async fn get_execution_for_key(&self, key: &str) -> Result<Option<Uuid>> {
+        let mut keys = self.keys.lock().unwrap();
+        match keys.get(key) {
+            Some((execution_id, expires_at)) if *expires_at > Instant::now() => Ok(Some(*execution_id)),
+            Some(_) => {
+                keys.remove(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn save_key(&self, key: &str, execution_id: Uuid, ttl: Duration) -> Result<()> {
+        let now = Instant::now();
+        let mut keys = self.keys.lock().unwrap();
+        keys.retain(|_, (_, expires_at)| *expires_at > now);
+        keys.insert(key.to_string(), (execution_id, now + ttl));
+        Ok(())
+    }
+
+    async fn reserve_key(&self, key: &str, execution_id: Uuid, ttl: Duration) -> Result<Option<Uuid>> {
+        let now = Instant::now();
+        let mut keys = self.keys.lock().unwrap();
+        keys.retain(|_, (_, expires_at)| *expires_at > now);
+        if let Some((existing, _)) = keys.get(key) {
+            return Ok(Some(*existing));
+        }
+        keys.insert(key.to_string(), (execution_id, now + ttl));
+        Ok(None)
+    }
+
+    async fn release_key(&self, key: &str) -> Result<()> {
+        self.keys.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_keys_expire_after_ttl() {
+        let store = MemoryIdempotencyStore::new();
+        let execution_id = Uuid::new_v4();
+
+        store.save_key("retry-1", execution_id, Duration::from_secs(60)).await.unwrap();
+        store.save_key("retry-2", Uuid::new_v4(), Duration::ZERO).await.unwrap();
+
+        assert_eq!(store.get_execution_for_key("retry-1").await.unwrap(), Some(execution_id));
+        assert_eq!(store.get_execution_for_key("retry-2").await.unwrap(), None);
+        assert_eq!(store.get_execution_for_key("unknown").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_only_the_first_reservation_wins() {
+        let store = MemoryIdempotencyStore::new();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert_eq!(store.reserve_key("order-7", first, Duration::from_secs(60)).await.unwrap(), None);
+        assert_eq!(store.reserve_key("order-7", second, Duration::from_secs(60)).await.unwrap(), Some(first));
+
+        store.release_key("order-7").await.unwrap();
+        assert_eq!(store.reserve_key("order-7", second, Duration::from_secs(60)).await.unwrap(), None);
+    }
+}
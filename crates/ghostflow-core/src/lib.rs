@@ -1,7 +1,59 @@
 pub mod error;
 pub mod traits;
 pub mod credentials;
+pub mod events;
+pub mod cancellation;
+pub mod circuit_breaker;
+pub mod variables;
+pub mod conversation;
+pub mod vector_index;
+pub mod templates;
+pub mod template_file;
+pub mod n8n;
+pub mod template_expression;
+pub mod flow_input;
+pub mod idempotency;
+pub mod flow_storage;
+pub mod execution_state;
+pub mod webhook_response;
+pub mod webhook_replay;
+pub mod conditions;
+pub mod approvals;
+pub mod dead_letter;
+pub mod process_limits;
+pub mod resume_token;
+pub mod warmup;
+pub mod environment;
+pub mod escalation;
+pub mod node_migration;
+pub mod services;
 
 pub use error::*;
 pub use traits::*;
-pub use credentials::*;
\ No newline at end of file
+pub use credentials::*;
+pub use events::*;
+pub use cancellation::*;
+pub use circuit_breaker::*;
+pub use variables::*;
+pub use conversation::*;
+pub use vector_index::*;
+pub use templates::*;
+pub use template_file::*;
+pub use n8n::*;
+pub use template_expression::*;
+pub use flow_input::*;
+pub use idempotency::*;
+pub use flow_storage::*;
+pub use execution_state::*;
+pub use webhook_response::*;
+pub use webhook_replay::*;
+pub use conditions::*;
+pub use approvals::*;
+pub use dead_letter::*;
+pub use process_limits::*;
+pub use resume_token::*;
+pub use warmup::*;
+pub use environment::*;
+pub use escalation::*;
+pub use node_migration::*;
+pub use services::*;
\ No newline at end of file
@@ -0,0 +1,709 @@
+use chrono::Utc;
+use ghostflow_schema::{
+    Flow, FlowEdge, FlowMetadata, FlowNode, FlowTrigger, NodePosition, OverflowPolicy, TriggerType,
+};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{GhostFlowError, Result};
+
+/// Convert an n8n workflow export into a [`Flow`] on a best-effort basis.
+///
+/// HTTP Request, IF, Set, Slack and Postgres nodes become their GhostFlow
+/// counterparts, and manual, webhook and cron-expression schedule triggers
+/// become flow triggers. Anything else is skipped along with its connections.
+/// Credentials are never carried over. Every skipped node or setting that
+/// needs attention after import is reported in the returned warnings.
+pub fn import_n8n_workflow(contents: &str) -> Result<(Flow, Vec<String>)> {
+    let workflow: Value = serde_json::from_str(contents).map_err(import_error)?;
+    let exported_nodes = workflow
+        .get("nodes")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| import_error("expected a workflow with a `nodes` array"))?;
+
+    let mut warnings = Vec::new();
+    let mut nodes = HashMap::new();
+    let mut triggers = Vec::new();
+    // n8n connects nodes by name; remember what each name became
+    let mut ids: HashMap<String, Imported> = HashMap::new();
+
+    for exported in exported_nodes {
+        let name = exported
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| import_error("every node needs a name"))?;
+        let node_type = exported.get("type").and_then(|v| v.as_str()).unwrap_or_default();
+        let params = exported.get("parameters").cloned().unwrap_or_else(|| json!({}));
+
+        if exported.get("disabled").and_then(|v| v.as_bool()).unwrap_or(false) {
+            warnings.push(format!("node '{}' is disabled in n8n and was skipped", name));
+            ids.insert(name.to_string(), Imported::Skipped);
+            continue;
+        }
+
+        let short_type = node_type.strip_prefix("n8n-nodes-base.").unwrap_or(node_type);
+        if let Some(trigger_type) = convert_trigger(name, short_type, &params, &mut warnings) {
+            triggers.push(FlowTrigger {
+                id: unique_id(name, &ids),
+                trigger_type,
+                config: HashMap::new(),
+                enabled: true,
+                priority: 0,
+            });
+            ids.insert(name.to_string(), Imported::Trigger);
+            continue;
+        }
+
+        let converted = match short_type {
+            "httpRequest" => Some(convert_http_request(name, &params, &mut warnings)),
+            "if" => Some(convert_if(name, &params, &mut warnings)),
+            "set" => Some(convert_set(name, &params, &mut warnings)),
+            "slack" => convert_slack(name, &params, &mut warnings),
+            "postgres" => convert_postgres(name, &params, &mut warnings),
+            _ => {
+                warnings.push(format!(
+                    "node '{}' ({}) has no GhostFlow equivalent and was skipped",
+                    name, node_type
+                ));
+                None
+            }
+        };
+        let Some((ghostflow_type, parameters)) = converted else {
+            ids.insert(name.to_string(), Imported::Skipped);
+            continue;
+        };
+
+        let position = exported.get("position").and_then(|v| v.as_array());
+        let coordinate = |i: usize| position.and_then(|p| p.get(i)).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let id = unique_id(name, &ids);
+        nodes.insert(
+            id.clone(),
+            FlowNode {
+                id: id.clone(),
+                node_type: ghostflow_type.to_string(),
+                name: name.to_string(),
+                description: exported.get("notes").and_then(|v| v.as_str()).map(str::to_string),
+                parameters,
+                position: NodePosition {
+                    x: coordinate(0),
+                    y: coordinate(1),
+                },
+                retry_config: None,
+                timeout_ms: None,
+                cache_ttl_ms: None,
+                node_version: None,
+            },
+        );
+        ids.insert(name.to_string(), Imported::Node { id, node_type: ghostflow_type });
+    }
+
+    let edges = convert_connections(workflow.get("connections"), &ids, &mut warnings);
+
+    let now = Utc::now();
+    let flow = Flow {
+        id: Uuid::new_v4(),
+        name: workflow
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Imported n8n workflow")
+            .to_string(),
+        description: Some("Imported from n8n".to_string()),
+        version: "1.0.0".to_string(),
+        nodes,
+        edges,
+        triggers,
+        parameters: HashMap::new(),
+        secrets: Vec::new(),
+        max_duration_ms: None,
+        max_concurrent_executions: None,
+        overflow_policy: OverflowPolicy::Queue,
+        metadata: FlowMetadata {
+            created_at: now,
+            updated_at: now,
+            created_by: "import:n8n".to_string(),
+            tags: Vec::new(),
+            category: None,
+            workspace_id: "default".to_string(),
+        },
+    };
+    Ok((flow, warnings))
+}
+
+enum Imported {
+    Node { id: String, node_type: &'static str },
+    Trigger,
+    Skipped,
+}
+
+fn import_error(message: impl std::fmt::Display) -> GhostFlowError {
+    GhostFlowError::ValidationError {
+        message: format!("Invalid n8n workflow: {}", message),
+    }
+}
+
+/// Snake-cased node name, suffixed when another node already has it.
+fn unique_id(name: &str, taken: &HashMap<String, Imported>) -> String {
+    let mut base: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect::<String>()
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    if base.is_empty() {
+        base = "node".to_string();
+    }
+
+    let in_use = |id: &str| {
+        taken.values().any(|imported| match imported {
+            Imported::Node { id: existing, .. } => existing == id,
+            _ => false,
+        })
+    };
+    let mut id = base.clone();
+    let mut suffix = 2;
+    while in_use(&id) {
+        id = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    id
+}
+
+/// n8n marks expression parameters with a leading `=`, e.g.
+/// `={{ $json.status }}`. Plain values come back unchanged.
+fn expression(value: &str) -> Option<&str> {
+    value
+        .strip_prefix('=')
+        .map(str::trim)
+        .and_then(|v| v.strip_prefix("{{"))
+        .and_then(|v| v.strip_suffix("}}"))
+        .map(str::trim)
+}
+
+/// Dotted path for an expression that only reads the current item, e.g.
+/// `$json.body.status` or `$json["status"]`.
+fn json_path(expression: &str) -> Option<String> {
+    let rest = expression.strip_prefix("$json")?;
+    let mut path = Vec::new();
+    let mut chars = rest.chars().peekable();
+    while let Some(c) = chars.next() {
+        let segment: String = match c {
+            '.' => std::iter::from_fn(|| chars.next_if(|c| c.is_alphanumeric() || *c == '_')).collect(),
+            '[' => {
+                let quote = chars.next().filter(|q| *q == '"' || *q == '\'')?;
+                let key: String = std::iter::from_fn(|| chars.next_if(|c| *c != quote)).collect();
+                chars.next();
+                chars.next().filter(|c| *c == ']')?;
+                key
+            }
+            _ => return None,
+        };
+        if segment.is_empty() {
+            return None;
+        }
+        path.push(segment);
+    }
+    (!path.is_empty()).then(|| path.join("."))
+}
+
+/// Copy a string parameter, warning when it is an n8n expression that
+/// GhostFlow cannot evaluate.
+fn text_param(node: &str, key: &str, value: Option<&Value>, warnings: &mut Vec<String>) -> Option<Value> {
+    let value = value?;
+    if let Some(text) = value.as_str() {
+        if text.starts_with('=') {
+            warnings.push(format!(
+                "node '{}': parameter '{}' uses an n8n expression and was copied as text",
+                node, key
+            ));
+            return Some(Value::String(text[1..].to_string()));
+        }
+    }
+    Some(value.clone())
+}
+
+/// `{"parameters": [{"name": .., "value": ..}]}` lists as an object
+fn name_value_pairs(list: Option<&Value>, key: &str) -> Option<Map<String, Value>> {
+    let pairs = list?.get(key)?.as_array()?;
+    let object: Map<String, Value> = pairs
+        .iter()
+        .filter_map(|pair| Some((pair.get("name")?.as_str()?.to_string(), pair.get("value")?.clone())))
+        .collect();
+    (!object.is_empty()).then_some(object)
+}
+
+fn convert_http_request(
+    name: &str,
+    params: &Value,
+    warnings: &mut Vec<String>,
+) -> (&'static str, HashMap<String, Value>) {
+    let mut parameters = HashMap::new();
+    // Version 1 calls the method `requestMethod`
+    let method = params
+        .get("method")
+        .or_else(|| params.get("requestMethod"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("GET");
+    parameters.insert("method".to_string(), json!(method));
+    if let Some(url) = text_param(name, "url", params.get("url"), warnings) {
+        parameters.insert("url".to_string(), url);
+    }
+
+    let headers = name_value_pairs(params.get("headerParameters"), "parameters")
+        .or_else(|| name_value_pairs(params.get("headerParametersUi"), "parameter"));
+    if let Some(headers) = headers {
+        parameters.insert("headers".to_string(), Value::Object(headers));
+    }
+    let query = name_value_pairs(params.get("queryParameters"), "parameters")
+        .or_else(|| name_value_pairs(params.get("queryParametersUi"), "parameter"));
+    if let Some(query) = query {
+        parameters.insert("query".to_string(), Value::Object(query));
+    }
+
+    let body = match params.get("jsonBody").or_else(|| params.get("bodyParametersJson")) {
+        Some(Value::String(text)) if !text.starts_with('=') => {
+            Some(serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.clone())))
+        }
+        Some(other) => text_param(name, "body", Some(other), warnings),
+        None => name_value_pairs(params.get("bodyParameters"), "parameters")
+            .or_else(|| name_value_pairs(params.get("bodyParametersUi"), "parameter"))
+            .map(Value::Object),
+    };
+    if let Some(body) = body {
+        parameters.insert("body".to_string(), body);
+        parameters.insert("body_type".to_string(), json!("json"));
+    }
+
+    if params.get("authentication").and_then(|v| v.as_str()).is_some_and(|auth| auth != "none") {
+        warnings.push(format!(
+            "node '{}': n8n credentials are not imported; add authentication headers by hand",
+            name
+        ));
+    }
+    ("http_request", parameters)
+}
+
+/// An IF comparison operand as a condition path or JSON literal
+fn operand(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(text) if text.starts_with('=') => json_path(expression(text)?),
+        other => serde_json::to_string(other).ok(),
+    }
+}
+
+fn comparison(operation: &str, left: Option<&Value>, right: Option<&Value>) -> Option<String> {
+    let operator = match operation {
+        "equal" | "equals" | "true" | "false" => "==",
+        "notEqual" | "notEquals" => "!=",
+        "larger" | "gt" => ">",
+        "largerEqual" | "gte" => ">=",
+        "smaller" | "lt" => "<",
+        "smallerEqual" | "lte" => "<=",
+        "exists" => return operand(left),
+        "notExists" => return Some(format!("!{}", operand(left)?)),
+        _ => return None,
+    };
+    let right = match operation {
+        "true" => "true".to_string(),
+        "false" => "false".to_string(),
+        _ => operand(right)?,
+    };
+    Some(format!("{} {} {}", operand(left)?, operator, right))
+}
+
+fn convert_if(name: &str, params: &Value, warnings: &mut Vec<String>) -> (&'static str, HashMap<String, Value>) {
+    let conditions = params.get("conditions");
+    let (comparisons, any): (Vec<Option<String>>, bool) =
+        match conditions.and_then(|c| c.get("conditions")).and_then(|v| v.as_array()) {
+            // Version 2 keeps a flat list with typed operators
+            Some(list) => (
+                list.iter()
+                    .map(|c| {
+                        let operation = c.pointer("/operator/operation")?.as_str()?;
+                        comparison(operation, c.get("leftValue"), c.get("rightValue"))
+                    })
+                    .collect(),
+                conditions.and_then(|c| c.get("combinator")).and_then(|v| v.as_str()) == Some("or"),
+            ),
+            // Version 1 groups comparisons by value type
+            None => (
+                ["string", "number", "boolean", "dateTime"]
+                    .iter()
+                    .filter_map(|kind| conditions?.get(kind)?.as_array())
+                    .flatten()
+                    .map(|c| {
+                        let operation = c.get("operation").and_then(|v| v.as_str()).unwrap_or("equal");
+                        comparison(operation, c.get("value1"), c.get("value2"))
+                    })
+                    .collect(),
+                params.get("combineOperation").and_then(|v| v.as_str()) == Some("any"),
+            ),
+        };
+
+    let condition = match comparisons.into_iter().collect::<Option<Vec<_>>>() {
+        Some(parts) if !parts.is_empty() => parts.join(if any { " || " } else { " && " }),
+        _ => {
+            warnings.push(format!(
+                "node '{}': conditions could not be converted; set its condition by hand",
+                name
+            ));
+            "true".to_string()
+        }
+    };
+    ("if", HashMap::from([("condition".to_string(), json!(condition))]))
+}
+
+/// Set becomes a transform with one JMESPath mapping per assigned field:
+/// item references as paths and constants as backtick literals.
+fn convert_set(name: &str, params: &Value, warnings: &mut Vec<String>) -> (&'static str, HashMap<String, Value>) {
+    let assignments: Vec<(String, Value)> = match params.pointer("/assignments/assignments").and_then(|v| v.as_array()) {
+        Some(list) => list
+            .iter()
+            .filter_map(|a| Some((a.get("name")?.as_str()?.to_string(), a.get("value")?.clone())))
+            .collect(),
+        None => ["string", "number", "boolean"]
+            .iter()
+            .filter_map(|kind| params.pointer(&format!("/values/{}", kind))?.as_array())
+            .flatten()
+            .filter_map(|a| Some((a.get("name")?.as_str()?.to_string(), a.get("value")?.clone())))
+            .collect(),
+    };
+
+    let mut mappings = Map::new();
+    for (field, value) in assignments {
+        let mapping = match &value {
+            Value::String(text) if text.starts_with('=') => match expression(text).and_then(json_path) {
+                Some(path) => path,
+                None => {
+                    warnings.push(format!(
+                        "node '{}': field '{}' uses an n8n expression and was skipped",
+                        name, field
+                    ));
+                    continue;
+                }
+            },
+            literal => format!("`{}`", literal),
+        };
+        mappings.insert(field, Value::String(mapping));
+    }
+    if params.get("keepOnlySet").and_then(|v| v.as_bool()) != Some(true) && !params.get("include").is_some_and(|v| v == "none") {
+        warnings.push(format!(
+            "node '{}': only the assigned fields are kept; map any other fields it should pass through",
+            name
+        ));
+    }
+    ("transform", HashMap::from([("mappings".to_string(), Value::Object(mappings))]))
+}
+
+fn convert_slack(
+    name: &str,
+    params: &Value,
+    warnings: &mut Vec<String>,
+) -> Option<(&'static str, HashMap<String, Value>)> {
+    let resource = params.get("resource").and_then(|v| v.as_str()).unwrap_or("message");
+    let operation = params.get("operation").and_then(|v| v.as_str()).unwrap_or("post");
+    if resource != "message" || operation != "post" {
+        warnings.push(format!(
+            "node '{}': Slack {} {} has no GhostFlow equivalent and was skipped",
+            name, resource, operation
+        ));
+        return None;
+    }
+
+    let mut parameters = HashMap::new();
+    // Version 2 stores the channel as a resource locator
+    let channel = params
+        .get("channelId")
+        .and_then(|locator| locator.get("value").or(Some(locator)))
+        .or_else(|| params.get("channel"));
+    if let Some(channel) = text_param(name, "channel", channel, warnings) {
+        parameters.insert("channel".to_string(), channel);
+    }
+    if let Some(text) = text_param(name, "text", params.get("text"), warnings) {
+        parameters.insert("text".to_string(), text);
+    }
+    warnings.push(format!("node '{}': set bot_token, n8n credentials are not imported", name));
+    Some(("slack_message", parameters))
+}
+
+fn convert_postgres(
+    name: &str,
+    params: &Value,
+    warnings: &mut Vec<String>,
+) -> Option<(&'static str, HashMap<String, Value>)> {
+    let mut parameters = HashMap::new();
+    match params.get("operation").and_then(|v| v.as_str()).unwrap_or("executeQuery") {
+        "executeQuery" => {
+            parameters.insert("operation".to_string(), json!("query"));
+            if let Some(query) = text_param(name, "query", params.get("query"), warnings) {
+                parameters.insert("query".to_string(), query);
+            }
+        }
+        "insert" => {
+            parameters.insert("operation".to_string(), json!("insert"));
+            let table = params.get("table").and_then(|t| t.get("value").or(Some(t)));
+            if let Some(table) = text_param(name, "table", table, warnings) {
+                parameters.insert("table_name".to_string(), table);
+            }
+        }
+        other => {
+            warnings.push(format!(
+                "node '{}': Postgres operation '{}' has no GhostFlow equivalent and was skipped",
+                name, other
+            ));
+            return None;
+        }
+    }
+    warnings.push(format!(
+        "node '{}': set the database connection, n8n credentials are not imported",
+        name
+    ));
+    Some(("postgresql", parameters))
+}
+
+/// Trigger for n8n trigger node types, `None` for everything else
+fn convert_trigger(name: &str, short_type: &str, params: &Value, warnings: &mut Vec<String>) -> Option<TriggerType> {
+    match short_type {
+        "manualTrigger" | "start" => Some(TriggerType::Manual),
+        "webhook" => Some(TriggerType::Webhook {
+            path: params.get("path").and_then(|v| v.as_str()).unwrap_or(name).to_string(),
+            method: params.get("httpMethod").and_then(|v| v.as_str()).unwrap_or("GET").to_string(),
+        }),
+        "scheduleTrigger" | "cron" => {
+            let expression = params
+                .pointer("/rule/interval")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .find(|rule| rule.get("field").and_then(|v| v.as_str()) == Some("cronExpression"))
+                .and_then(|rule| rule.get("expression")?.as_str());
+            match expression {
+                Some(expression) => Some(TriggerType::Cron {
+                    expression: expression.to_string(),
+                    timezone: None,
+                }),
+                None => {
+                    warnings.push(format!(
+                        "trigger '{}': only cron expression schedules are imported; add a cron trigger by hand",
+                        name
+                    ));
+                    Some(TriggerType::Manual)
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `{"Source": {"main": [[{"node": "Target", "index": 0}], ..]}}`, one inner
+/// list per source output. IF outputs 0 and 1 are its true and false ports.
+fn convert_connections(
+    connections: Option<&Value>,
+    ids: &HashMap<String, Imported>,
+    warnings: &mut Vec<String>,
+) -> Vec<FlowEdge> {
+    let mut edges = Vec::new();
+    let Some(connections) = connections.and_then(|v| v.as_object()) else {
+        return edges;
+    };
+
+    for (source, by_kind) in connections {
+        let outputs = by_kind.get("main").and_then(|v| v.as_array()).into_iter().flatten();
+        for (output, targets) in outputs.enumerate() {
+            for target in targets.as_array().into_iter().flatten() {
+                let Some(target) = target.get("node").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let (source_id, source_type, target_id) = match (ids.get(source), ids.get(target)) {
+                    (Some(Imported::Node { id, node_type }), Some(Imported::Node { id: target_id, .. })) => {
+                        (id, *node_type, target_id)
+                    }
+                    // Triggers start the flow at its entry nodes without an edge
+                    (Some(Imported::Trigger), _) => continue,
+                    _ => {
+                        warnings.push(format!(
+                            "connection '{}' -> '{}' was dropped because a node was skipped",
+                            source, target
+                        ));
+                        continue;
+                    }
+                };
+                let source_port = match (source_type, output) {
+                    ("if", 0) => Some("true".to_string()),
+                    ("if", _) => Some("false".to_string()),
+                    _ => None,
+                };
+                edges.push(FlowEdge {
+                    id: format!("{}_to_{}", source_id, target_id),
+                    source_node: source_id.clone(),
+                    target_node: target_id.clone(),
+                    source_port,
+                    target_port: None,
+                    condition: None,
+                    coerce: false,
+                });
+            }
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r##"{
+        "name": "Deploy status",
+        "nodes": [
+            {
+                "name": "When clicking 'Test workflow'",
+                "type": "n8n-nodes-base.manualTrigger",
+                "typeVersion": 1,
+                "position": [0, 0],
+                "parameters": {}
+            },
+            {
+                "name": "HTTP Request",
+                "type": "n8n-nodes-base.httpRequest",
+                "typeVersion": 4.2,
+                "position": [220, 0],
+                "parameters": {
+                    "method": "GET",
+                    "url": "https://ci.example.com/api/status",
+                    "sendHeaders": true,
+                    "headerParameters": { "parameters": [{ "name": "Accept", "value": "application/json" }] }
+                }
+            },
+            {
+                "name": "Failed?",
+                "type": "n8n-nodes-base.if",
+                "typeVersion": 2,
+                "position": [440, 0],
+                "parameters": {
+                    "conditions": {
+                        "combinator": "and",
+                        "conditions": [{
+                            "leftValue": "={{ $json.status }}",
+                            "rightValue": "failed",
+                            "operator": { "type": "string", "operation": "equals" }
+                        }]
+                    }
+                }
+            },
+            {
+                "name": "Slack",
+                "type": "n8n-nodes-base.slack",
+                "typeVersion": 2.2,
+                "position": [660, -100],
+                "parameters": {
+                    "select": "channel",
+                    "channelId": { "__rl": true, "value": "#deploys", "mode": "name" },
+                    "text": "Deploy failed"
+                },
+                "credentials": { "slackApi": { "id": "1", "name": "Slack account" } }
+            },
+            {
+                "name": "Code",
+                "type": "n8n-nodes-base.code",
+                "typeVersion": 2,
+                "position": [660, 100],
+                "parameters": { "jsCode": "return items;" }
+            }
+        ],
+        "connections": {
+            "When clicking 'Test workflow'": { "main": [[{ "node": "HTTP Request", "type": "main", "index": 0 }]] },
+            "HTTP Request": { "main": [[{ "node": "Failed?", "type": "main", "index": 0 }]] },
+            "Failed?": {
+                "main": [
+                    [{ "node": "Slack", "type": "main", "index": 0 }],
+                    [{ "node": "Code", "type": "main", "index": 0 }]
+                ]
+            }
+        }
+    }"##;
+
+    #[test]
+    fn test_http_if_slack_chain_is_imported() {
+        let (flow, warnings) = import_n8n_workflow(EXPORT).unwrap();
+        assert_eq!(flow.name, "Deploy status");
+        assert!(matches!(flow.triggers[0].trigger_type, TriggerType::Manual));
+
+        let http = &flow.nodes["http_request"];
+        assert_eq!(http.node_type, "http_request");
+        assert_eq!(http.parameters["url"], "https://ci.example.com/api/status");
+        assert_eq!(http.parameters["headers"], json!({ "Accept": "application/json" }));
+        assert_eq!(http.position.x, 220.0);
+
+        let branch = &flow.nodes["failed"];
+        assert_eq!(branch.node_type, "if");
+        assert_eq!(branch.parameters["condition"], r#"status == "failed""#);
+
+        let slack = &flow.nodes["slack"];
+        assert_eq!(slack.node_type, "slack_message");
+        assert_eq!(slack.parameters["channel"], "#deploys");
+        assert_eq!(slack.parameters["text"], "Deploy failed");
+
+        assert_eq!(flow.nodes.len(), 3);
+        let mut edges: Vec<_> = flow
+            .edges
+            .iter()
+            .map(|e| (e.source_node.as_str(), e.target_node.as_str(), e.source_port.as_deref()))
+            .collect();
+        edges.sort();
+        assert_eq!(
+            edges,
+            vec![("failed", "slack", Some("true")), ("http_request", "failed", None)]
+        );
+
+        assert!(warnings.iter().any(|w| w.contains("'Code' (n8n-nodes-base.code)")), "{:?}", warnings);
+        assert!(warnings.iter().any(|w| w.contains("'Failed?' -> 'Code'")), "{:?}", warnings);
+        assert!(warnings.iter().any(|w| w.contains("bot_token")), "{:?}", warnings);
+    }
+
+    #[test]
+    fn test_version_one_if_and_set_nodes() {
+        let export = json!({
+            "nodes": [
+                {
+                    "name": "Big order",
+                    "type": "n8n-nodes-base.if",
+                    "parameters": {
+                        "conditions": {
+                            "number": [{ "value1": "={{$json[\"total\"]}}", "operation": "larger", "value2": 100 }],
+                            "boolean": [{ "value1": "={{ $json.paid }}", "value2": true }]
+                        },
+                        "combineOperation": "any"
+                    }
+                },
+                {
+                    "name": "Set",
+                    "type": "n8n-nodes-base.set",
+                    "parameters": {
+                        "keepOnlySet": true,
+                        "values": { "string": [
+                            { "name": "customer", "value": "={{ $json.order.customer }}" },
+                            { "name": "tier", "value": "gold" }
+                        ] }
+                    }
+                }
+            ],
+            "connections": {}
+        });
+        let (flow, warnings) = import_n8n_workflow(&export.to_string()).unwrap();
+
+        assert_eq!(flow.nodes["big_order"].parameters["condition"], "total > 100 || paid == true");
+        assert_eq!(
+            flow.nodes["set"].parameters["mappings"],
+            json!({ "customer": "order.customer", "tier": "`\"gold\"`" })
+        );
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn test_invalid_export_is_rejected() {
+        assert!(import_n8n_workflow("not json").is_err());
+        assert!(import_n8n_workflow(r#"{"name": "no nodes"}"#).is_err());
+    }
+}
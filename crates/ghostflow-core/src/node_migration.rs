@@ -0,0 +1,195 @@
+use ghostflow_schema::{Flow, FlowNode};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::warn;
+
+use crate::{GhostFlowError, Result};
+
+/// Rewrites a node's parameters from the layout of one version of its node
+/// type to the layout of the next
+pub type ParameterMigration = Arc<dyn Fn(&mut HashMap<String, Value>) -> Result<()> + Send + Sync>;
+
+#[derive(Clone)]
+struct Migration {
+    to_version: String,
+    migrate: ParameterMigration,
+}
+
+/// Upgrades flow nodes written for an older version of their node type, so
+/// flows keep working when a node's parameters change shape. Migrations are
+/// registered per `(node_type, old_version)` and chained until no migration
+/// applies, e.g. 1.0.0 to 2.0.0 to 3.0.0. Node types can also be marked
+/// deprecated, which only warns.
+#[derive(Default)]
+pub struct NodeMigrationRegistry {
+    migrations: RwLock<HashMap<(String, String), Migration>>,
+    deprecations: RwLock<HashMap<String, String>>,
+}
+
+impl NodeMigrationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Upgrade `node_type` nodes at `from_version` to `to_version` with
+    /// `migrate`, replacing any migration registered for the same version.
+    pub fn register(
+        &self,
+        node_type: impl Into<String>,
+        from_version: impl Into<String>,
+        to_version: impl Into<String>,
+        migrate: impl Fn(&mut HashMap<String, Value>) -> Result<()> + Send + Sync + 'static,
+    ) {
+        self.migrations.write().unwrap().insert(
+            (node_type.into(), from_version.into()),
+            Migration {
+                to_version: to_version.into(),
+                migrate: Arc::new(migrate),
+            },
+        );
+    }
+
+    /// Warn whenever a flow using `node_type` is migrated, with `note`
+    /// saying what to use instead.
+    pub fn deprecate(&self, node_type: impl Into<String>, note: impl Into<String>) {
+        self.deprecations.write().unwrap().insert(node_type.into(), note.into());
+    }
+
+    /// Upgrade `node` to the newest version its migrations lead to. Returns
+    /// whether anything changed. A failing migration leaves the node as it
+    /// was.
+    pub fn migrate_node(&self, node: &mut FlowNode) -> Result<bool> {
+        if let Some(note) = self.deprecations.read().unwrap().get(&node.node_type) {
+            warn!("Node '{}' uses deprecated node type '{}': {}", node.id, node.node_type, note);
+        }
+        let Some(original) = node.node_version.clone() else {
+            return Ok(false);
+        };
+
+        let migrations = self.migrations.read().unwrap();
+        let mut version = original.clone();
+        let mut parameters = node.parameters.clone();
+        let mut seen = vec![version.clone()];
+        while let Some(migration) = migrations.get(&(node.node_type.clone(), version.clone())) {
+            (migration.migrate)(&mut parameters).map_err(|e| GhostFlowError::ValidationError {
+                message: format!(
+                    "Could not migrate node '{}' ({}) from version {} to {}: {}",
+                    node.id, node.node_type, version, migration.to_version, e
+                ),
+            })?;
+            version = migration.to_version.clone();
+            if seen.contains(&version) {
+                return Err(GhostFlowError::ConfigurationError {
+                    message: format!("Migrations of node type '{}' loop back to version {}", node.node_type, version),
+                });
+            }
+            seen.push(version.clone());
+        }
+
+        if version == original {
+            return Ok(false);
+        }
+        warn!(
+            "Migrated node '{}' ({}) from version {} to {}; save the flow to keep the upgrade",
+            node.id, node.node_type, original, version
+        );
+        node.parameters = parameters;
+        node.node_version = Some(version);
+        Ok(true)
+    }
+
+    /// Upgrade every node of `flow`, returning how many were migrated.
+    pub fn migrate_flow(&self, flow: &mut Flow) -> Result<usize> {
+        let mut migrated = 0;
+        for node in flow.nodes.values_mut() {
+            if self.migrate_node(node)? {
+                migrated += 1;
+            }
+        }
+        Ok(migrated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ghostflow_schema::NodePosition;
+    use serde_json::json;
+
+    fn command_node(version: Option<&str>, args: Value) -> FlowNode {
+        FlowNode {
+            id: "run".to_string(),
+            node_type: "shell_command".to_string(),
+            name: "Run".to_string(),
+            description: None,
+            parameters: HashMap::from([("command".to_string(), json!("ls")), ("args".to_string(), args)]),
+            position: NodePosition { x: 0.0, y: 0.0 },
+            retry_config: None,
+            timeout_ms: None,
+            cache_ttl_ms: None,
+            node_version: version.map(str::to_string),
+        }
+    }
+
+    /// v1 took `args` as one comma-separated string, v2 takes an array
+    fn registry() -> NodeMigrationRegistry {
+        let registry = NodeMigrationRegistry::new();
+        registry.register("shell_command", "1.0.0", "2.0.0", |params| {
+            let args = match params.get("args") {
+                Some(Value::String(args)) => args
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|arg| !arg.is_empty())
+                    .map(|arg| Value::String(arg.to_string()))
+                    .collect(),
+                Some(other) => {
+                    return Err(GhostFlowError::ValidationError {
+                        message: format!("args must be a string, got {}", other),
+                    })
+                }
+                None => Vec::new(),
+            };
+            params.insert("args".to_string(), Value::Array(args));
+            Ok(())
+        });
+        registry
+    }
+
+    #[test]
+    fn test_v1_comma_separated_args_become_an_array() {
+        let mut node = command_node(Some("1.0.0"), json!("-l, -a,/tmp"));
+
+        assert!(registry().migrate_node(&mut node).unwrap());
+        assert_eq!(node.parameters["args"], json!(["-l", "-a", "/tmp"]));
+        assert_eq!(node.parameters["command"], "ls");
+        assert_eq!(node.node_version.as_deref(), Some("2.0.0"));
+
+        // Already current, or written without a version: left alone
+        assert!(!registry().migrate_node(&mut node).unwrap());
+        let mut unversioned = command_node(None, json!("-l"));
+        assert!(!registry().migrate_node(&mut unversioned).unwrap());
+        assert_eq!(unversioned.parameters["args"], "-l");
+    }
+
+    #[test]
+    fn test_migrations_chain_and_failures_leave_the_node_unchanged() {
+        let registry = registry();
+        registry.register("shell_command", "2.0.0", "3.0.0", |params| {
+            let args = params.remove("args").unwrap_or(Value::Null);
+            params.insert("argv".to_string(), args);
+            Ok(())
+        });
+
+        let mut node = command_node(Some("1.0.0"), json!("-l"));
+        assert!(registry.migrate_node(&mut node).unwrap());
+        assert_eq!(node.parameters["argv"], json!(["-l"]));
+        assert_eq!(node.node_version.as_deref(), Some("3.0.0"));
+
+        let mut broken = command_node(Some("1.0.0"), json!(42));
+        let error = registry.migrate_node(&mut broken).unwrap_err();
+        assert!(error.to_string().contains("from version 1.0.0 to 2.0.0"), "{}", error);
+        assert_eq!(broken.parameters["args"], 42);
+        assert_eq!(broken.node_version.as_deref(), Some("1.0.0"));
+    }
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::process::Command;
+
+/// Default for [`ProcessLimits::max_output_bytes`]: 1 MiB per stream
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// Resource guard for nodes that run external processes. The wall-clock
+/// timeout stays with each node; these cap what a command may consume while
+/// it runs. Memory and CPU limits are applied with `setrlimit` and only take
+/// effect on Linux.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessLimits {
+    /// Bytes of stdout, and separately of stderr, kept in the node output.
+    /// Anything beyond is read and discarded so the child never blocks.
+    pub max_output_bytes: usize,
+    /// Address space the child may map (`RLIMIT_AS`)
+    pub max_memory_bytes: Option<u64>,
+    /// CPU time after which the kernel kills the child (`RLIMIT_CPU`)
+    pub max_cpu_seconds: Option<u64>,
+}
+
+impl Default for ProcessLimits {
+    fn default() -> Self {
+        Self {
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            max_memory_bytes: None,
+            max_cpu_seconds: None,
+        }
+    }
+}
+
+impl ProcessLimits {
+    /// Tighten these limits with a node's `max_output_bytes`,
+    /// `max_memory_mb` and `max_cpu_seconds` parameters. Parameters can only
+    /// lower a deployment limit, never raise it.
+    pub fn narrowed_by(&self, params: &Value) -> Self {
+        let param = |name: &str| params.get(name).and_then(|v| v.as_u64());
+        let lower = |configured: Option<u64>, requested: Option<u64>| match (configured, requested) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        Self {
+            max_output_bytes: param("max_output_bytes")
+                .map_or(self.max_output_bytes, |n| self.max_output_bytes.min(n as usize)),
+            max_memory_bytes: lower(
+                self.max_memory_bytes,
+                param("max_memory_mb").map(|mb| mb.saturating_mul(1024 * 1024)),
+            ),
+            max_cpu_seconds: lower(self.max_cpu_seconds, param("max_cpu_seconds")),
+        }
+    }
+
+    /// Arrange for the memory and CPU limits to be set in the child before it
+    /// executes. A no-op off Linux or when neither limit is set.
+    pub fn apply(&self, command: &mut Command) {
+        #[cfg(target_os = "linux")]
+        {
+            let (memory, cpu) = (self.max_memory_bytes, self.max_cpu_seconds);
+            if memory.is_none() && cpu.is_none() {
+                return;
+            }
+            // SAFETY: the hook runs between fork and exec and only calls
+            // setrlimit, which is async-signal-safe
+            unsafe {
+                command.pre_exec(move || {
+                    if let Some(bytes) = memory {
+                        set_rlimit(libc::RLIMIT_AS, bytes)?;
+                    }
+                    if let Some(seconds) = cpu {
+                        set_rlimit(libc::RLIMIT_CPU, seconds)?;
+                    }
+                    Ok(())
+                });
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = command;
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type Resource = libc::__rlimit_resource_t;
+#[cfg(all(target_os = "linux", not(target_env = "gnu")))]
+type Resource = libc::c_int;
+
+#[cfg(target_os = "linux")]
+fn set_rlimit(resource: Resource, value: u64) -> std::io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    };
+    // SAFETY: `limit` is a valid rlimit for the duration of the call
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Output collected from one of a child's pipes, cut off at a byte limit.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CappedOutput {
+    pub bytes: Vec<u8>,
+    /// Bytes the child wrote in total, including any that were dropped
+    pub total_bytes: usize,
+    pub truncated: bool,
+    limit: usize,
+}
+
+impl CappedOutput {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            ..Self::default()
+        }
+    }
+
+    /// Keep as much of `chunk` as still fits under the limit.
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.total_bytes += chunk.len();
+        let room = self.limit.saturating_sub(self.bytes.len());
+        if chunk.len() > room {
+            self.truncated = true;
+        }
+        self.bytes.extend_from_slice(&chunk[..chunk.len().min(room)]);
+    }
+}
+
+/// Drain `pipe` to EOF, keeping at most `limit` bytes.
+pub async fn read_capped<R: AsyncRead + Unpin>(pipe: Option<R>, limit: usize) -> CappedOutput {
+    let mut output = CappedOutput::new(limit);
+    let Some(mut pipe) = pipe else {
+        return output;
+    };
+
+    let mut chunk = [0u8; 8192];
+    loop {
+        match pipe.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => output.push(&chunk[..n]),
+        }
+    }
+    output
+}
+
+/// Write `payload` to `pipe` and close it. Run this alongside the output
+/// readers: a child blocked on a full stdout never drains a large stdin.
+/// A child that exits without reading its input is not an error.
+pub async fn write_input<W: AsyncWrite + Unpin>(pipe: Option<W>, payload: Option<Vec<u8>>) -> std::io::Result<()> {
+    let (Some(mut pipe), Some(payload)) = (pipe, payload) else {
+        return Ok(());
+    };
+    match pipe.write_all(&payload).await {
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_read_capped_keeps_draining_past_the_limit() {
+        let data = vec![b'x'; 100_000];
+        let output = read_capped(Some(&data[..]), 1000).await;
+
+        assert_eq!(output.bytes.len(), 1000);
+        assert_eq!(output.total_bytes, 100_000);
+        assert!(output.truncated);
+
+        let output = read_capped(Some(&b"short"[..]), 1000).await;
+        assert_eq!(output.bytes, b"short");
+        assert!(!output.truncated);
+    }
+
+    #[test]
+    fn test_params_only_lower_limits() {
+        let limits = ProcessLimits {
+            max_output_bytes: 4096,
+            max_memory_bytes: Some(256 * 1024 * 1024),
+            max_cpu_seconds: None,
+        };
+
+        let narrowed = limits.narrowed_by(&json!({ "max_output_bytes": 1_000_000, "max_memory_mb": 64, "max_cpu_seconds": 5 }));
+        assert_eq!(narrowed.max_output_bytes, 4096);
+        assert_eq!(narrowed.max_memory_bytes, Some(64 * 1024 * 1024));
+        assert_eq!(narrowed.max_cpu_seconds, Some(5));
+
+        let widened = limits.narrowed_by(&json!({ "max_memory_mb": 1024 }));
+        assert_eq!(widened.max_memory_bytes, Some(256 * 1024 * 1024));
+    }
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::{ResumeTokenStorage, Result};
+
+/// In-process [`ResumeTokenStorage`]. Tokens are lost on restart, so a
+/// trigger using it starts from the current end of its stream.
+#[derive(Default)]
+pub struct MemoryResumeTokenStore {
+    tokens: Mutex<HashMap<String, Value>>,
+}
+
+impl MemoryResumeTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ResumeTokenStorage for MemoryResumeTokenStore {
+    async fn save_resume_token(&self, key: &str, token: &Value) -> Result<()> {
+        self.tokens.lock().unwrap().insert(key.to_string(), token.clone());
+        Ok(())
+    }
+
+    async fn get_resume_token(&self, key: &str) -> Result<Option<Value>> {
+        Ok(self.tokens.lock().unwrap().get(key).cloned())
+    }
+
+    async fn delete_resume_token(&self, key: &str) -> Result<()> {
+        self.tokens.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
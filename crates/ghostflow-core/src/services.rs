@@ -0,0 +1,52 @@
+use crate::{
+    ApprovalRegistry, CancellationRegistry, CircuitBreakerRegistry, ConversationStore, EnvironmentStore, EscalationStore, EventBus, FlowVariableStore,
+    NodeMigrationRegistry, NodeRunner, VectorIndexStore, WebhookResponseRegistry,
+};
+use std::sync::{Arc, RwLock, Weak};
+
+/// State that one engine shares with the nodes it runs and the API in front
+/// of it. Build one per server and hand it to the runtime and to the nodes;
+/// clones refer to the same state.
+#[derive(Clone, Default)]
+pub struct Services {
+    /// Progress of every execution, for WebSocket clients and log tails
+    pub events: EventBus,
+    /// Tokens that stop running executions and the nodes waiting in them
+    pub cancellations: Arc<CancellationRegistry>,
+    /// Flow-scoped variables of running executions
+    pub variables: Arc<FlowVariableStore>,
+    /// Webhook callers waiting for their flow to respond
+    pub webhook_responses: Arc<WebhookResponseRegistry>,
+    /// Approval requests that executions are waiting on
+    pub approvals: Arc<ApprovalRegistry>,
+    /// Earlier turns of each flow's LLM conversations
+    pub conversations: Arc<ConversationStore>,
+    /// Named embedding indexes for vector search
+    pub vector_indexes: Arc<VectorIndexStore>,
+    /// Stages such as `staging` or `prod` that executions can run in
+    pub environments: Arc<EnvironmentStore>,
+    /// Upgrades applied to outdated nodes when flows are deployed
+    pub node_migrations: Arc<NodeMigrationRegistry>,
+    /// Alerts escalated recently, so repeats can be held back
+    pub escalations: Arc<EscalationStore>,
+    /// Per-host circuit breakers of the nodes' outgoing HTTP requests
+    pub circuit_breakers: Arc<CircuitBreakerRegistry>,
+    /// The engine's nodes, for nodes that run other nodes
+    pub nodes: NodeRunnerSlot,
+}
+
+/// The [`NodeRunner`] of the executor the services were handed to. Held
+/// weakly, since the runner's registry holds the nodes that hold the slot.
+#[derive(Clone, Default)]
+pub struct NodeRunnerSlot(Arc<RwLock<Option<Weak<dyn NodeRunner>>>>);
+
+impl NodeRunnerSlot {
+    pub fn set(&self, runner: Weak<dyn NodeRunner>) {
+        *self.0.write().unwrap() = Some(runner);
+    }
+
+    /// `None` outside an engine, or once its executor is gone
+    pub fn get(&self) -> Option<Arc<dyn NodeRunner>> {
+        self.0.read().unwrap().as_ref().and_then(Weak::upgrade)
+    }
+}
@@ -0,0 +1,418 @@
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{GhostFlowError, Result};
+
+/// Functions an install-time expression may call
+pub const TEMPLATE_FUNCTIONS: [&str; 3] = ["env", "uuid", "now"];
+
+/// An expression evaluated once, when a template is installed: numbers and
+/// quoted strings, `{{variable}}` references to template variables, the
+/// functions in [`TEMPLATE_FUNCTIONS`], parentheses, `+` (which
+/// concatenates when either side is a string), `-`, `*`, `/` and `%`.
+///
+/// ```text
+/// {{cluster}} + "-" + uuid()
+/// {{base_port}} + 1
+/// env("GHOSTFLOW_REGION", "us-east-1")
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateExpression {
+    root: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Variable(String),
+    Call(String, Vec<Expr>),
+    Negate(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Variable(String),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl TemplateExpression {
+    /// Parse `source`. Text that is not written as an expression at all, such
+    /// as `Disk on {{host}} is full` or a runtime condition like
+    /// `cpu_usage > 80`, gives `Ok(None)`; callers treat it as plain text
+    /// with `{{variable}}` placeholders. Malformed expressions (an unknown
+    /// function, a dangling operator, unbalanced parentheses) are errors.
+    pub fn parse(source: &str) -> Result<Option<Self>> {
+        let Some(tokens) = tokenize(source) else {
+            return Ok(None);
+        };
+        if tokens.is_empty() {
+            return Ok(None);
+        }
+
+        let mut parser = Parser { tokens: &tokens, position: 0, source };
+        let root = parser.expression()?;
+        if parser.position < tokens.len() {
+            return Err(parser.error("unexpected input after the end of the expression"));
+        }
+        Ok(Some(Self { root }))
+    }
+
+    /// Template variables the expression refers to, in order of appearance.
+    pub fn references(&self) -> Vec<String> {
+        fn walk(expr: &Expr, names: &mut Vec<String>) {
+            match expr {
+                Expr::Variable(name) => names.push(name.clone()),
+                Expr::Call(_, args) => args.iter().for_each(|arg| walk(arg, names)),
+                Expr::Negate(inner) => walk(inner, names),
+                Expr::Binary(_, left, right) => {
+                    walk(left, names);
+                    walk(right, names);
+                }
+                Expr::Literal(_) => {}
+            }
+        }
+        let mut names = Vec::new();
+        walk(&self.root, &mut names);
+        names
+    }
+
+    /// Evaluate against the installed template's variables. A reference to a
+    /// variable that has no value is an error.
+    pub fn evaluate(&self, variables: &HashMap<String, Value>) -> Result<Value> {
+        evaluate(&self.root, variables)
+    }
+}
+
+fn expression_error(message: impl Into<String>) -> GhostFlowError {
+    GhostFlowError::ValidationError {
+        message: message.into(),
+    }
+}
+
+/// Split `source` into tokens, or `None` when it contains something no
+/// expression can: a character outside the grammar or a bare word that is
+/// neither a function call nor `true`/`false`/`null`.
+fn tokenize(source: &str) -> Option<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '{' if chars.get(i + 1) == Some(&'{') => {
+                let rest: String = chars[i + 2..].iter().collect();
+                let end = rest.find("}}")?;
+                tokens.push(Token::Variable(rest[..end].trim().to_string()));
+                i += 2 + rest[..end].chars().count() + 2;
+            }
+            '"' | '\'' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i)? {
+                        '\\' => {
+                            text.push(*chars.get(i + 1)?);
+                            i += 2;
+                        }
+                        ch if *ch == c => break,
+                        ch => {
+                            text.push(*ch);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                tokens.push(Token::Str(text));
+            }
+            _ if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit)) => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                tokens.push(Token::Number(text.parse().ok()?));
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                let is_call = chars[i..].iter().find(|ch| !ch.is_whitespace()) == Some(&'(');
+                if !is_call && !matches!(word.as_str(), "true" | "false" | "null") {
+                    return None;
+                }
+                tokens.push(Token::Ident(word));
+            }
+            '+' | '-' | '*' | '/' | '%' => {
+                tokens.push(Token::Op(c));
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    source: &'a str,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> GhostFlowError {
+        expression_error(format!("Invalid expression '{}': {}", self.source, message))
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            _ => Err(self.error(&format!("expected {}", what))),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expr> {
+        let mut left = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek().cloned() {
+            self.position += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr> {
+        let mut left = self.unary()?;
+        while let Some(Token::Op(op @ ('*' | '/' | '%'))) = self.peek().cloned() {
+            self.position += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.peek() == Some(&Token::Op('-')) {
+            self.position += 1;
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Literal(number(n))),
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::String(s))),
+            Some(Token::Variable(name)) if name.is_empty() => Err(self.error("empty variable reference")),
+            Some(Token::Variable(name)) => Ok(Expr::Variable(name)),
+            Some(Token::LParen) => {
+                let inner = self.expression()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(inner)
+            }
+            Some(Token::Ident(word)) => match word.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ if !TEMPLATE_FUNCTIONS.contains(&word.as_str()) => Err(self.error(&format!(
+                    "unknown function '{}'; available: {}",
+                    word,
+                    TEMPLATE_FUNCTIONS.join(", ")
+                ))),
+                _ => {
+                    self.expect(Token::LParen, "'('")?;
+                    let mut args = Vec::new();
+                    if self.peek() == Some(&Token::RParen) {
+                        self.position += 1;
+                    } else {
+                        loop {
+                            args.push(self.expression()?);
+                            match self.next() {
+                                Some(Token::Comma) => {}
+                                Some(Token::RParen) => break,
+                                _ => return Err(self.error(&format!("expected ',' or ')' in {}()", word))),
+                            }
+                        }
+                    }
+                    Ok(Expr::Call(word, args))
+                }
+            },
+            _ => Err(self.error("expected a value")),
+        }
+    }
+}
+
+/// Whole numbers stay integers so `{{port}} + 1` gives `8081`, not `8081.0`
+fn number(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+        json!(n as i64)
+    } else {
+        json!(n)
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn evaluate(expr: &Expr, variables: &HashMap<String, Value>) -> Result<Value> {
+    match expr {
+        Expr::Literal(value) => Ok(value.clone()),
+        Expr::Variable(name) => variables
+            .get(name)
+            .cloned()
+            .ok_or_else(|| expression_error(format!("Expression references variable '{}', which has no value", name))),
+        Expr::Negate(inner) => match evaluate(inner, variables)?.as_f64() {
+            Some(n) => Ok(number(-n)),
+            None => Err(expression_error("Only numbers can be negated")),
+        },
+        Expr::Binary(op, left, right) => {
+            let left = evaluate(left, variables)?;
+            let right = evaluate(right, variables)?;
+            if *op == '+' && (left.is_string() || right.is_string()) {
+                return Ok(Value::String(text(&left) + &text(&right)));
+            }
+            let (Some(a), Some(b)) = (left.as_f64(), right.as_f64()) else {
+                return Err(expression_error(format!(
+                    "Cannot apply '{}' to {} and {}",
+                    op, left, right
+                )));
+            };
+            match op {
+                '+' => Ok(number(a + b)),
+                '-' => Ok(number(a - b)),
+                '*' => Ok(number(a * b)),
+                '/' | '%' if b == 0.0 => Err(expression_error("Division by zero")),
+                '/' => Ok(number(a / b)),
+                _ => Ok(number(a % b)),
+            }
+        }
+        Expr::Call(name, args) => {
+            let args = args
+                .iter()
+                .map(|arg| evaluate(arg, variables))
+                .collect::<Result<Vec<_>>>()?;
+            call(name, &args)
+        }
+    }
+}
+
+fn call(name: &str, args: &[Value]) -> Result<Value> {
+    match (name, args) {
+        ("uuid", []) => Ok(Value::String(Uuid::new_v4().to_string())),
+        ("now", []) => Ok(Value::String(Utc::now().to_rfc3339())),
+        ("now", [Value::String(format)]) => Ok(Value::String(Utc::now().format(format).to_string())),
+        ("env", [Value::String(var)]) => std::env::var(var)
+            .map(Value::String)
+            .map_err(|_| expression_error(format!("Environment variable '{}' is not set", var))),
+        ("env", [Value::String(var), fallback]) => {
+            Ok(std::env::var(var).map(Value::String).unwrap_or_else(|_| fallback.clone()))
+        }
+        ("uuid", _) => Err(expression_error("uuid() takes no arguments")),
+        ("now", _) => Err(expression_error("now() takes an optional format string")),
+        ("env", _) => Err(expression_error("env() takes a variable name and an optional default")),
+        _ => Err(expression_error(format!("Unknown function '{}'", name))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(source: &str, variables: Value) -> Result<Value> {
+        let variables: HashMap<String, Value> = serde_json::from_value(variables).unwrap();
+        TemplateExpression::parse(source)?.expect("not an expression").evaluate(&variables)
+    }
+
+    #[test]
+    fn test_uuid_gives_distinct_values() {
+        let expression = TemplateExpression::parse("uuid()").unwrap().unwrap();
+        let first = expression.evaluate(&HashMap::new()).unwrap();
+        let second = expression.evaluate(&HashMap::new()).unwrap();
+        assert_ne!(first, second);
+        assert!(Uuid::parse_str(first.as_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_concatenates_variables_and_literals() {
+        let variables = json!({ "cluster": "prod", "node": 3 });
+        assert_eq!(
+            eval(r#"{{cluster}} + "-node-" + {{ node }}"#, variables).unwrap(),
+            json!("prod-node-3")
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_follows_precedence() {
+        let variables = json!({ "base_port": 8080, "replicas": 3 });
+        assert_eq!(eval("{{base_port}} + 1", variables.clone()).unwrap(), json!(8081));
+        assert_eq!(eval("(1 + {{replicas}}) * 2 - -1", variables.clone()).unwrap(), json!(9));
+        assert_eq!(eval("7 / 2", variables.clone()).unwrap(), json!(3.5));
+        assert_eq!(eval("7 % 4", variables.clone()).unwrap(), json!(3));
+        assert!(eval("1 / 0", variables).is_err());
+    }
+
+    #[test]
+    fn test_env_and_now() {
+        std::env::set_var("GHOSTFLOW_TEMPLATE_TEST_REGION", "eu-west-1");
+        assert_eq!(eval(r#"env("GHOSTFLOW_TEMPLATE_TEST_REGION")"#, json!({})).unwrap(), json!("eu-west-1"));
+        assert_eq!(eval(r#"env("GHOSTFLOW_TEMPLATE_TEST_UNSET", 'fallback')"#, json!({})).unwrap(), json!("fallback"));
+        assert!(eval(r#"env("GHOSTFLOW_TEMPLATE_TEST_UNSET")"#, json!({})).is_err());
+
+        let year = eval(r#"now("%Y")"#, json!({})).unwrap();
+        assert_eq!(year, json!(Utc::now().format("%Y").to_string()));
+    }
+
+    #[test]
+    fn test_variable_without_value_is_an_error() {
+        let error = eval("{{missing}} + 1", json!({})).unwrap_err();
+        assert!(error.to_string().contains("variable 'missing', which has no value"), "{}", error);
+    }
+
+    #[test]
+    fn test_plain_text_and_malformed_expressions() {
+        for text in ["cpu_usage > 80 OR memory_usage > 90", "Disk on {{host}} is full", "{{host}}:{{port}}"] {
+            assert_eq!(TemplateExpression::parse(text).unwrap(), None, "{}", text);
+        }
+        for malformed in ["{{a}} +", "uuidd()", "(1 + 2", "env(\"A\" \"B\")"] {
+            assert!(TemplateExpression::parse(malformed).is_err(), "{}", malformed);
+        }
+        let expression = TemplateExpression::parse("{{a}} * 2 + env({{b}})").unwrap().unwrap();
+        assert_eq!(expression.references(), vec!["a", "b"]);
+    }
+}
@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::{validate_template, FlowTemplate, GhostFlowError, Result, TemplateParameter};
+
+/// Version of the template file layout written by [`export_template`].
+/// Files from older versions are migrated on import.
+pub const TEMPLATE_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateFileFormat {
+    Json,
+    Yaml,
+}
+
+impl TemplateFileFormat {
+    /// YAML for `.yaml` and `.yml` files, JSON otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => Self::Yaml,
+            _ => Self::Json,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TemplateFile {
+    schema_version: u32,
+    template: FlowTemplate,
+}
+
+/// [`TemplateParameter`] is untagged, so a variable reference and a static
+/// string look the same once serialized. Files spell out the kind instead.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TaggedParameter {
+    Static(Value),
+    Variable(String),
+    Expression(String),
+}
+
+impl From<TemplateParameter> for TaggedParameter {
+    fn from(parameter: TemplateParameter) -> Self {
+        match parameter {
+            TemplateParameter::Static(value) => Self::Static(value),
+            TemplateParameter::Variable(name) => Self::Variable(name),
+            TemplateParameter::Expression(expression) => Self::Expression(expression),
+        }
+    }
+}
+
+impl From<TaggedParameter> for TemplateParameter {
+    fn from(parameter: TaggedParameter) -> Self {
+        match parameter {
+            TaggedParameter::Static(value) => Self::Static(value),
+            TaggedParameter::Variable(name) => Self::Variable(name),
+            TaggedParameter::Expression(expression) => Self::Expression(expression),
+        }
+    }
+}
+
+fn parameters_mut(template: &mut FlowTemplate) -> impl Iterator<Item = &mut TemplateParameter> {
+    let data = &mut template.template_data;
+    data.nodes
+        .iter_mut()
+        .flat_map(|node| node.parameters.values_mut())
+        .chain(data.triggers.iter_mut().flat_map(|trigger| trigger.configuration.values_mut()))
+}
+
+fn file_error(message: impl std::fmt::Display) -> GhostFlowError {
+    GhostFlowError::ValidationError {
+        message: format!("Invalid template file: {}", message),
+    }
+}
+
+/// Serialize `template` into a versioned template file.
+pub fn export_template(template: &FlowTemplate, format: TemplateFileFormat) -> Result<String> {
+    let mut template = template.clone();
+    for parameter in parameters_mut(&mut template) {
+        let tagged = TaggedParameter::from(parameter.clone());
+        *parameter = TemplateParameter::Static(serde_json::to_value(tagged)?);
+    }
+
+    let file = TemplateFile {
+        schema_version: TEMPLATE_FILE_VERSION,
+        template,
+    };
+    match format {
+        TemplateFileFormat::Json => Ok(serde_json::to_string_pretty(&file)?),
+        TemplateFileFormat::Yaml => serde_yaml::to_string(&file).map_err(file_error),
+    }
+}
+
+/// Read a template file written by [`export_template`], migrating older
+/// layouts, and check the template with [`validate_template`]. Returns the
+/// template along with any validation warnings.
+pub fn import_template(contents: &str, format: TemplateFileFormat) -> Result<(FlowTemplate, Vec<String>)> {
+    let document: Value = match format {
+        TemplateFileFormat::Json => serde_json::from_str(contents).map_err(file_error)?,
+        TemplateFileFormat::Yaml => serde_yaml::from_str(contents).map_err(file_error)?,
+    };
+
+    let file: TemplateFile = serde_json::from_value(migrate(document)?).map_err(file_error)?;
+    let mut template = file.template;
+    for parameter in parameters_mut(&mut template) {
+        let TemplateParameter::Static(tagged) = parameter else {
+            continue;
+        };
+        let tagged: TaggedParameter = serde_json::from_value(tagged.clone())
+            .map_err(|e| file_error(format!("parameter {}: {}", tagged, e)))?;
+        *parameter = tagged.into();
+    }
+
+    let warnings = validate_template(&template)?;
+    Ok((template, warnings))
+}
+
+/// Bring a parsed file up to [`TEMPLATE_FILE_VERSION`] one version at a time.
+fn migrate(mut document: Value) -> Result<Value> {
+    loop {
+        let version = match document.get("schema_version") {
+            // Version 0 is a bare template as served by the API
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .ok_or_else(|| file_error("schema_version must be a number"))? as u32,
+        };
+        document = match version {
+            0 => migrate_v0(document)?,
+            TEMPLATE_FILE_VERSION => return Ok(document),
+            newer => {
+                return Err(file_error(format!(
+                    "schema version {} is newer than the supported version {}",
+                    newer, TEMPLATE_FILE_VERSION
+                )))
+            }
+        };
+    }
+}
+
+/// Wrap a bare template and tag its parameters. Untagged strings are read as
+/// a variable when they name a declared variable, as an expression when they
+/// contain a `{{` placeholder, and as static values otherwise.
+fn migrate_v0(mut template: Value) -> Result<Value> {
+    let declared: HashSet<String> = template
+        .pointer("/template_data/variables")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|variable| variable.get("name")?.as_str().map(str::to_string))
+        .collect();
+
+    let tag = |value: &mut Value| {
+        let tagged = match value.take() {
+            Value::String(s) if declared.contains(&s) => TaggedParameter::Variable(s),
+            Value::String(s) if s.contains("{{") => TaggedParameter::Expression(s),
+            other => TaggedParameter::Static(other),
+        };
+        *value = serde_json::to_value(tagged).expect("tagged parameters serialize");
+    };
+    for (list, field) in [("nodes", "parameters"), ("triggers", "configuration")] {
+        let Some(items) = template
+            .pointer_mut(&format!("/template_data/{}", list))
+            .and_then(|v| v.as_array_mut())
+        else {
+            continue;
+        };
+        for item in items {
+            if let Some(parameters) = item.get_mut(field).and_then(|v| v.as_object_mut()) {
+                parameters.values_mut().for_each(tag);
+            }
+        }
+    }
+
+    Ok(serde_json::json!({ "schema_version": 1, "template": template }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_builtin_templates;
+
+    fn assert_equivalent(left: &FlowTemplate, right: &FlowTemplate) {
+        assert_eq!(serde_json::to_value(left).unwrap(), serde_json::to_value(right).unwrap());
+        // Serialized parameters alone cannot tell a variable from a string
+        let kinds = |template: &FlowTemplate| {
+            let data = &template.template_data;
+            let nodes = data.nodes.iter().map(|node| (node.id.clone(), &node.parameters));
+            let triggers = data.triggers.iter().map(|trigger| (trigger.trigger_type.clone(), &trigger.configuration));
+            nodes
+                .chain(triggers)
+                .flat_map(|(owner, parameters)| {
+                    parameters.iter().map(move |(name, p)| {
+                        let tagged = serde_json::to_value(TaggedParameter::from(p.clone())).unwrap();
+                        (format!("{}.{}", owner, name), tagged)
+                    })
+                })
+                .collect::<std::collections::BTreeMap<_, _>>()
+        };
+        assert_eq!(kinds(left), kinds(right));
+    }
+
+    #[test]
+    fn test_builtin_template_round_trips_through_json_and_yaml() {
+        for template in get_builtin_templates() {
+            for format in [TemplateFileFormat::Json, TemplateFileFormat::Yaml] {
+                let exported = export_template(&template, format).unwrap();
+                let (imported, warnings) = import_template(&exported, format).unwrap();
+                assert_equivalent(&imported, &template);
+                assert!(warnings.is_empty(), "{:?}", warnings);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bare_template_is_migrated() {
+        let template = get_builtin_templates().remove(0);
+        let bare = serde_json::to_string(&template).unwrap();
+
+        let (imported, _) = import_template(&bare, TemplateFileFormat::Json).unwrap();
+        assert_equivalent(&imported, &template);
+    }
+
+    #[test]
+    fn test_newer_and_invalid_files_are_rejected() {
+        let newer = r#"{"schema_version": 99, "template": {}}"#;
+        let message = import_template(newer, TemplateFileFormat::Json).unwrap_err().to_string();
+        assert!(message.contains("newer than the supported version"), "{}", message);
+
+        let mut template = get_builtin_templates().remove(0);
+        template.template_data.edges[0].target_node = "missing".to_string();
+        let exported = export_template(&template, TemplateFileFormat::Yaml).unwrap();
+        assert!(import_template(&exported, TemplateFileFormat::Yaml).is_err());
+    }
+}
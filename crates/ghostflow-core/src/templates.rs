@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use chrono::{DateTime, Utc};
+use ghostflow_schema::{
+    Flow, FlowEdge, FlowMetadata, FlowNode, FlowTrigger, NodePosition, OverflowPolicy, TriggerType,
+};
+use regex::Regex;
+use uuid::Uuid;
+
+use crate::{GhostFlowError, Result, TemplateExpression};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlowTemplate {
@@ -105,7 +112,7 @@ pub enum TemplateParameter {
     Expression(String), // Expression to evaluate
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TemplateCategory {
     Alerts,
@@ -120,7 +127,7 @@ pub enum TemplateCategory {
     Development,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TemplateDifficulty {
     Beginner,
@@ -137,6 +144,21 @@ pub struct TemplateInstallation {
     pub description: Option<String>,
 }
 
+/// A node parameter to turn into a template variable with [`extract_template`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposedParameter {
+    pub node_id: String,
+    pub parameter: String,
+    /// Variable name; defaults to `<node_id>_<parameter>`. Parameters exposed
+    /// under the same name share one variable.
+    #[serde(default)]
+    pub variable: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
 pub fn get_builtin_templates() -> Vec<FlowTemplate> {
     vec![
         FlowTemplate {
@@ -638,4 +660,1080 @@ impl Default for VariableValidation {
             options: None,
         }
     }
-}
\ No newline at end of file
+}
+/// Sort order for [`TemplateRegistry::search`]; both orders are descending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateSort {
+    Downloads,
+    Rating,
+}
+
+/// Filters for [`TemplateRegistry::search`]. Unset fields match everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateQuery {
+    pub category: Option<TemplateCategory>,
+    pub difficulty: Option<TemplateDifficulty>,
+    /// Whitespace-separated terms; each must appear in the name, display
+    /// name, description or tags (case-insensitive)
+    pub search: Option<String>,
+    pub sort: Option<TemplateSort>,
+}
+
+/// In-memory catalog of flow templates, keyed by template id.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRegistry {
+    templates: Vec<FlowTemplate>,
+}
+
+impl TemplateRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry pre-populated with [`get_builtin_templates`].
+    pub fn with_builtin_templates() -> Self {
+        Self {
+            templates: get_builtin_templates(),
+        }
+    }
+
+    /// Add a template after checking it with [`validate_template`].
+    pub fn register(&mut self, template: FlowTemplate) -> Result<()> {
+        validate_template(&template)?;
+        if self.get(&template.id).is_some() {
+            return Err(GhostFlowError::ConfigurationError {
+                message: format!("Template '{}' is already registered", template.id),
+            });
+        }
+        self.templates.push(template);
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&FlowTemplate> {
+        self.templates.iter().find(|t| t.id == id)
+    }
+
+    pub fn list(&self) -> &[FlowTemplate] {
+        &self.templates
+    }
+
+    pub fn search(&self, query: &TemplateQuery) -> Vec<&FlowTemplate> {
+        let terms: Vec<String> = query
+            .search
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+
+        let mut results: Vec<&FlowTemplate> = self
+            .templates
+            .iter()
+            .filter(|t| query.category.map_or(true, |c| t.category == c))
+            .filter(|t| query.difficulty.map_or(true, |d| t.difficulty == d))
+            .filter(|t| terms.iter().all(|term| matches_term(t, term)))
+            .collect();
+
+        match query.sort {
+            Some(TemplateSort::Downloads) => results.sort_by(|a, b| b.downloads.cmp(&a.downloads)),
+            Some(TemplateSort::Rating) => results.sort_by(|a, b| {
+                b.rating
+                    .unwrap_or(f32::MIN)
+                    .total_cmp(&a.rating.unwrap_or(f32::MIN))
+            }),
+            None => {}
+        }
+
+        results
+    }
+}
+
+fn matches_term(template: &FlowTemplate, term: &str) -> bool {
+    template.name.to_lowercase().contains(term)
+        || template.display_name.to_lowercase().contains(term)
+        || template.description.to_lowercase().contains(term)
+        || template.tags.iter().any(|tag| tag.to_lowercase().contains(term))
+}
+
+/// Check a template's internal consistency before it is offered for install:
+/// every `TemplateParameter::Variable` used by nodes and triggers must be
+/// declared, edges must connect known nodes and validation patterns must
+/// compile. Problems are returned as a single `ValidationError`; variables
+/// that are declared but never referenced come back as warnings.
+pub fn validate_template(template: &FlowTemplate) -> Result<Vec<String>> {
+    let data = &template.template_data;
+    let mut errors = Vec::new();
+
+    let mut declared = HashSet::new();
+    for variable in &data.variables {
+        if !declared.insert(variable.name.as_str()) {
+            errors.push(format!("variable '{}' is declared more than once", variable.name));
+        }
+        if let Some(pattern) = variable.validation.as_ref().and_then(|v| v.pattern.as_ref()) {
+            if let Err(e) = Regex::new(pattern) {
+                errors.push(format!("variable '{}' has an invalid pattern: {}", variable.name, e));
+            }
+        }
+    }
+
+    let mut node_ids = HashSet::new();
+    for node in &data.nodes {
+        if !node_ids.insert(node.id.as_str()) {
+            errors.push(format!("node id '{}' is used more than once", node.id));
+        }
+    }
+    for edge in &data.edges {
+        for endpoint in [&edge.source_node, &edge.target_node] {
+            if !node_ids.contains(endpoint.as_str()) {
+                errors.push(format!("edge '{}' references unknown node '{}'", edge.id, endpoint));
+            }
+        }
+    }
+
+    let parameter_sets = data
+        .nodes
+        .iter()
+        .map(|node| (format!("node '{}'", node.id), &node.parameters))
+        .chain(
+            data.triggers
+                .iter()
+                .map(|trigger| (format!("{} trigger", trigger.trigger_type), &trigger.configuration)),
+        );
+
+    let mut used = HashSet::new();
+    for (location, parameters) in parameter_sets {
+        for (name, parameter) in parameters {
+            match parameter {
+                TemplateParameter::Variable(variable) => {
+                    if declared.contains(variable.as_str()) {
+                        used.insert(variable.clone());
+                    } else {
+                        errors.push(format!(
+                            "{} parameter '{}' references undeclared variable '{}'",
+                            location, name, variable
+                        ));
+                    }
+                }
+                // Expressions are evaluated at install time, so every
+                // reference must be a template variable
+                TemplateParameter::Expression(expression) => {
+                    let references = match TemplateExpression::parse(expression) {
+                        Ok(Some(parsed)) => parsed.references(),
+                        Ok(None) => expression_references(expression),
+                        Err(GhostFlowError::ValidationError { message }) => {
+                            errors.push(format!("{} parameter '{}': {}", location, name, message));
+                            continue;
+                        }
+                        Err(e) => {
+                            errors.push(format!("{} parameter '{}': {}", location, name, e));
+                            continue;
+                        }
+                    };
+                    for variable in references {
+                        if declared.contains(variable.as_str()) {
+                            used.insert(variable);
+                        } else {
+                            errors.push(format!(
+                                "{} parameter '{}' expression references undeclared variable '{}'",
+                                location, name, variable
+                            ));
+                        }
+                    }
+                }
+                TemplateParameter::Static(_) => {}
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(GhostFlowError::ValidationError {
+            message: format!("Template '{}' is invalid: {}", template.id, errors.join("; ")),
+        });
+    }
+
+    Ok(data
+        .variables
+        .iter()
+        .filter(|variable| !used.contains(&variable.name))
+        .map(|variable| format!("variable '{}' is declared but never used", variable.name))
+        .collect())
+}
+
+/// Names referenced as `{{name}}` inside an expression
+fn expression_references(expression: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = expression;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        names.push(rest[start + 2..start + end].trim().to_string());
+        rest = &rest[start + end + 2..];
+    }
+    names
+}
+
+/// Turn a template plus the user's answers into a runnable [`Flow`] with a
+/// fresh id. Variables are checked against their type and
+/// [`VariableValidation`]; every problem is reported in one `ValidationError`.
+pub fn install_template(template: &FlowTemplate, installation: &TemplateInstallation) -> Result<Flow> {
+    if installation.template_id != template.id {
+        return Err(GhostFlowError::ValidationError {
+            message: format!(
+                "Installation targets template '{}' but '{}' was supplied",
+                installation.template_id, template.id
+            ),
+        });
+    }
+
+    validate_template(template)?;
+    let variables = resolve_variables(&template.template_data.variables, &installation.user_variables)?;
+    let data = &template.template_data;
+
+    let mut nodes = HashMap::new();
+    for node in &data.nodes {
+        nodes.insert(
+            node.id.clone(),
+            FlowNode {
+                id: node.id.clone(),
+                node_type: node.node_type.clone(),
+                name: node.id.clone(),
+                description: node.description.clone(),
+                parameters: resolve_parameters(&node.parameters, &variables)?,
+                position: NodePosition { x: node.position.x, y: node.position.y },
+                retry_config: None,
+                timeout_ms: None,
+                cache_ttl_ms: None,
+                node_version: None,
+            },
+        );
+    }
+
+    let edges = data
+        .edges
+        .iter()
+        .map(|edge| {
+            if !nodes.contains_key(&edge.source_node) || !nodes.contains_key(&edge.target_node) {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!(
+                        "Template edge '{}' connects unknown nodes {} -> {}",
+                        edge.id, edge.source_node, edge.target_node
+                    ),
+                });
+            }
+            Ok(FlowEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node: edge.source_node.clone(),
+                target_node: edge.target_node.clone(),
+                source_port: Some(edge.source_output.clone()),
+                target_port: Some(edge.target_input.clone()),
+                condition: None,
+                coerce: false,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let triggers = data
+        .triggers
+        .iter()
+        .map(|trigger| {
+            let config = resolve_parameters(&trigger.configuration, &variables)?;
+            Ok(FlowTrigger {
+                id: Uuid::new_v4().to_string(),
+                trigger_type: trigger_type(&trigger.trigger_type, &config, data.schedule.as_deref())?,
+                config,
+                enabled: true,
+                priority: 0,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let secrets = data
+        .variables
+        .iter()
+        .filter(|v| matches!(v.variable_type, VariableType::Secret))
+        .map(|v| v.name.clone())
+        .collect();
+
+    let now = Utc::now();
+    Ok(Flow {
+        id: Uuid::new_v4(),
+        name: installation.flow_name.clone(),
+        description: installation
+            .description
+            .clone()
+            .or_else(|| Some(template.description.clone())),
+        version: "1.0.0".to_string(),
+        nodes,
+        edges,
+        triggers,
+        parameters: HashMap::new(),
+        secrets,
+        max_duration_ms: None,
+        max_concurrent_executions: None,
+        overflow_policy: OverflowPolicy::Queue,
+        metadata: FlowMetadata {
+            created_at: now,
+            updated_at: now,
+            created_by: format!("template:{}", template.id),
+            tags: template.tags.clone(),
+            category: serde_json::to_value(&template.category)
+                .ok()
+                .and_then(|v| v.as_str().map(String::from)),
+            workspace_id: "default".to_string(),
+        },
+    })
+}
+
+/// Turn a flow into a reusable template, the inverse of [`install_template`].
+/// Each exposed parameter becomes a [`TemplateParameter::Variable`] with a
+/// declaration whose type is inferred from the current value; every other
+/// parameter is kept as a static value. Secrets (parameters listed in
+/// `flow.secrets` or named like a password, token or key) are declared
+/// without a default so their values never end up in the template, while
+/// other variables default to the flow's current value. Edge conditions and
+/// node retry settings have no template equivalent and are dropped.
+pub fn extract_template(flow: &Flow, params_to_expose: &[ExposedParameter]) -> Result<FlowTemplate> {
+    let mut exposed: HashMap<(&str, &str), String> = HashMap::new();
+    let mut variables: Vec<TemplateVariable> = Vec::new();
+    for param in params_to_expose {
+        let value = flow
+            .nodes
+            .get(&param.node_id)
+            .ok_or_else(|| GhostFlowError::NotFoundError {
+                resource_type: "node".to_string(),
+                id: param.node_id.clone(),
+            })?
+            .parameters
+            .get(&param.parameter)
+            .ok_or_else(|| GhostFlowError::ValidationError {
+                message: format!("Node '{}' has no parameter '{}' to expose", param.node_id, param.parameter),
+            })?;
+        let name = param
+            .variable
+            .clone()
+            .unwrap_or_else(|| format!("{}_{}", param.node_id, param.parameter));
+
+        let secret = flow.secrets.contains(&param.parameter) || flow.secrets.contains(&name) || is_secret_name(&param.parameter);
+        match variables.iter().find(|v| v.name == name) {
+            Some(existing) if !secret && existing.default_value.as_ref() != Some(value) => {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Variable '{}' is exposed for parameters with different values", name),
+                });
+            }
+            Some(_) => {}
+            None => variables.push(TemplateVariable {
+                name: name.clone(),
+                display_name: param.display_name.clone().unwrap_or_else(|| display_name(&param.parameter)),
+                description: param
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| format!("{} of node {}", display_name(&param.parameter), param.node_id)),
+                variable_type: if secret { VariableType::Secret } else { infer_variable_type(value) },
+                default_value: (!secret).then(|| value.clone()),
+                required: secret,
+                placeholder: None,
+                validation: None,
+            }),
+        }
+        exposed.insert((param.node_id.as_str(), param.parameter.as_str()), name);
+    }
+
+    // Sorted so the same flow always yields the same template
+    let mut node_ids: Vec<&String> = flow.nodes.keys().collect();
+    node_ids.sort();
+    let nodes = node_ids
+        .into_iter()
+        .map(|id| {
+            let node = &flow.nodes[id];
+            let parameters = node
+                .parameters
+                .iter()
+                .map(|(name, value)| {
+                    let parameter = match exposed.get(&(node.id.as_str(), name.as_str())) {
+                        Some(variable) => TemplateParameter::Variable(variable.clone()),
+                        None => TemplateParameter::Static(value.clone()),
+                    };
+                    (name.clone(), parameter)
+                })
+                .collect();
+            TemplateNode {
+                id: node.id.clone(),
+                node_type: node.node_type.clone(),
+                position: Position { x: node.position.x, y: node.position.y },
+                parameters,
+                description: node.description.clone(),
+            }
+        })
+        .collect();
+
+    let edges = flow
+        .edges
+        .iter()
+        .map(|edge| TemplateEdge {
+            id: edge.id.clone(),
+            source_node: edge.source_node.clone(),
+            source_output: edge.source_port.clone().unwrap_or_default(),
+            target_node: edge.target_node.clone(),
+            target_input: edge.target_port.clone().unwrap_or_default(),
+        })
+        .collect();
+
+    let triggers = flow.triggers.iter().map(template_trigger).collect::<Result<Vec<_>>>()?;
+
+    let slug: String = flow
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    let category = flow
+        .metadata
+        .category
+        .as_ref()
+        .and_then(|c| serde_json::from_value(serde_json::Value::String(c.clone())).ok())
+        .unwrap_or(TemplateCategory::Automation);
+
+    let now = Utc::now();
+    let template = FlowTemplate {
+        id: slug.clone(),
+        name: slug,
+        display_name: flow.name.clone(),
+        description: flow.description.clone().unwrap_or_default(),
+        category,
+        tags: flow.metadata.tags.clone(),
+        version: "1.0.0".to_string(),
+        author: flow.metadata.created_by.clone(),
+        icon: None,
+        screenshot: None,
+        difficulty: TemplateDifficulty::Beginner,
+        estimated_time: String::new(),
+        use_cases: Vec::new(),
+        prerequisites: Vec::new(),
+        template_data: TemplateData {
+            nodes,
+            edges,
+            triggers,
+            variables,
+            schedule: None,
+        },
+        created_at: now,
+        updated_at: now,
+        downloads: 0,
+        rating: None,
+    };
+    validate_template(&template)?;
+    Ok(template)
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    ["password", "secret", "token", "api_key", "apikey", "private_key", "credential"]
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+/// `api_key` -> `Api Key`
+fn display_name(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn infer_variable_type(value: &serde_json::Value) -> VariableType {
+    match value {
+        serde_json::Value::Bool(_) => VariableType::Boolean,
+        serde_json::Value::Number(_) => VariableType::Number,
+        serde_json::Value::String(s) if s.starts_with("http://") || s.starts_with("https://") => VariableType::Url,
+        serde_json::Value::String(s)
+            if s.contains('@') && !s.starts_with('@') && !s.contains(char::is_whitespace) =>
+        {
+            VariableType::Email
+        }
+        serde_json::Value::String(_) => VariableType::String,
+        _ => VariableType::Json,
+    }
+}
+
+/// The template form of a trigger [`install_template`] can recreate
+fn template_trigger(trigger: &FlowTrigger) -> Result<TemplateTrigger> {
+    let mut configuration: HashMap<String, TemplateParameter> = trigger
+        .config
+        .iter()
+        .map(|(name, value)| (name.clone(), TemplateParameter::Static(value.clone())))
+        .collect();
+    let mut set = |name: &str, value: &str| {
+        configuration.insert(name.to_string(), TemplateParameter::Static(serde_json::Value::String(value.to_string())));
+    };
+    let kind = match &trigger.trigger_type {
+        TriggerType::Cron { expression, timezone } => {
+            set("cron", expression);
+            if let Some(timezone) = timezone {
+                set("timezone", timezone);
+            }
+            "schedule"
+        }
+        TriggerType::Webhook { path, method } => {
+            set("path", path);
+            set("method", method);
+            "webhook"
+        }
+        TriggerType::Manual => "manual",
+        other => {
+            let kind = serde_json::to_value(other)?["type"].as_str().unwrap_or_default().to_string();
+            return Err(GhostFlowError::ValidationError {
+                message: format!(
+                    "Trigger '{}' cannot be expressed in a template: {} triggers are not supported",
+                    trigger.id, kind
+                ),
+            });
+        }
+    };
+    Ok(TemplateTrigger {
+        trigger_type: kind.to_string(),
+        configuration,
+    })
+}
+
+/// Merge user values with defaults and validate each variable. Optional
+/// variables without a value are left out.
+fn resolve_variables(
+    definitions: &[TemplateVariable],
+    user_variables: &HashMap<String, serde_json::Value>,
+) -> Result<HashMap<String, serde_json::Value>> {
+    let mut resolved = HashMap::new();
+    let mut failures = Vec::new();
+
+    for variable in definitions {
+        let value = user_variables
+            .get(&variable.name)
+            .filter(|v| !v.is_null())
+            .or(variable.default_value.as_ref());
+
+        match value {
+            Some(value) => match validate_variable(variable, value) {
+                Ok(()) => {
+                    resolved.insert(variable.name.clone(), value.clone());
+                }
+                Err(message) => failures.push(format!("{}: {}", variable.name, message)),
+            },
+            None if variable.required => failures.push(format!("{}: is required", variable.name)),
+            None => {}
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(resolved)
+    } else {
+        Err(GhostFlowError::ValidationError {
+            message: format!("Invalid template variables: {}", failures.join("; ")),
+        })
+    }
+}
+
+pub(crate) fn validate_variable(variable: &TemplateVariable, value: &serde_json::Value) -> std::result::Result<(), String> {
+    let text = value.as_str();
+    match variable.variable_type {
+        VariableType::Number if !value.is_number() => return Err("expected a number".to_string()),
+        VariableType::Boolean if !value.is_boolean() => return Err("expected a boolean".to_string()),
+        VariableType::Email if !text.map_or(false, |t| t.contains('@') && !t.starts_with('@')) => {
+            return Err("expected an email address".to_string())
+        }
+        VariableType::Url
+            if !text.map_or(false, |t| t.starts_with("http://") || t.starts_with("https://")) =>
+        {
+            return Err("expected an http(s) URL".to_string())
+        }
+        VariableType::String | VariableType::Secret | VariableType::Select if text.is_none() => {
+            return Err("expected a string".to_string())
+        }
+        _ => {}
+    }
+
+    let Some(validation) = &variable.validation else {
+        return Ok(());
+    };
+
+    if let Some(text) = text {
+        let length = text.chars().count();
+        if let Some(min) = validation.min_length {
+            if length < min {
+                return Err(format!("length {} is below the minimum of {}", length, min));
+            }
+        }
+        if let Some(max) = validation.max_length {
+            if length > max {
+                return Err(format!("length {} exceeds the maximum of {}", length, max));
+            }
+        }
+        if let Some(pattern) = &validation.pattern {
+            let regex = Regex::new(pattern).map_err(|e| format!("has an invalid pattern: {}", e))?;
+            if !regex.is_match(text) {
+                return Err(format!("does not match pattern {}", pattern));
+            }
+        }
+    }
+
+    if let Some(options) = &validation.options {
+        let choice = text.map(String::from).unwrap_or_else(|| value.to_string());
+        if !options.contains(&choice) {
+            return Err(format!("'{}' is not one of {}", choice, options.join(", ")));
+        }
+    }
+
+    Ok(())
+}
+
+fn resolve_parameters(
+    parameters: &HashMap<String, TemplateParameter>,
+    variables: &HashMap<String, serde_json::Value>,
+) -> Result<HashMap<String, serde_json::Value>> {
+    let mut resolved = HashMap::new();
+    for (name, parameter) in parameters {
+        let value = match parameter {
+            TemplateParameter::Static(value) => Some(value.clone()),
+            TemplateParameter::Variable(variable) => variables.get(variable).cloned(),
+            TemplateParameter::Expression(expression) => Some(evaluate_expression(expression, variables)?),
+        };
+        if let Some(value) = value {
+            resolved.insert(name.clone(), value);
+        }
+    }
+    Ok(resolved)
+}
+
+/// Evaluate a [`TemplateExpression`], or for text that is not written as an
+/// expression, substitute its `{{variable}}` placeholders. Either way every
+/// referenced variable must have a value.
+fn evaluate_expression(
+    expression: &str,
+    variables: &HashMap<String, serde_json::Value>,
+) -> Result<serde_json::Value> {
+    if let Some(parsed) = TemplateExpression::parse(expression)? {
+        return parsed.evaluate(variables);
+    }
+
+    let mut output = String::with_capacity(expression.len());
+    let mut rest = expression;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + end].trim();
+        output.push_str(&rest[..start]);
+        match variables.get(name) {
+            Some(serde_json::Value::String(s)) => output.push_str(s),
+            Some(other) => output.push_str(&other.to_string()),
+            None => {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Expression '{}' references variable '{}', which has no value", expression, name),
+                })
+            }
+        }
+        rest = &rest[start + end + 2..];
+    }
+    output.push_str(rest);
+    Ok(serde_json::Value::String(output))
+}
+
+fn trigger_type(
+    kind: &str,
+    config: &HashMap<String, serde_json::Value>,
+    schedule: Option<&str>,
+) -> Result<TriggerType> {
+    let text = |key: &str| config.get(key).and_then(|v| v.as_str()).map(String::from);
+    match kind {
+        "schedule" | "cron" => {
+            let expression = text("cron").or_else(|| schedule.map(String::from)).ok_or_else(|| {
+                GhostFlowError::ValidationError {
+                    message: "Schedule trigger needs a cron expression".to_string(),
+                }
+            })?;
+            Ok(TriggerType::Cron {
+                expression,
+                timezone: text("timezone"),
+            })
+        }
+        "webhook" => Ok(TriggerType::Webhook {
+            path: text("path").unwrap_or_else(|| format!("/webhooks/{}", Uuid::new_v4())),
+            method: text("method").unwrap_or_else(|| "POST".to_string()),
+        }),
+        "manual" => Ok(TriggerType::Manual),
+        other => Err(GhostFlowError::ValidationError {
+            message: format!("Unsupported template trigger type '{}'", other),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discord_template() -> FlowTemplate {
+        get_builtin_templates()
+            .into_iter()
+            .find(|t| t.id == "discord_security_alerts")
+            .unwrap()
+    }
+
+    fn installation(variables: serde_json::Value) -> TemplateInstallation {
+        TemplateInstallation {
+            template_id: "discord_security_alerts".to_string(),
+            user_variables: serde_json::from_value(variables).unwrap(),
+            flow_name: "SOC alerts".to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_install_discord_security_template() {
+        let template = discord_template();
+        let flow = install_template(
+            &template,
+            &installation(serde_json::json!({
+                "wazuh_username": "wazuh-api",
+                "wazuh_password": "s3cret",
+                "discord_webhook": "https://discord.com/api/webhooks/123/abc",
+                "alert_level": "10",
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(flow.name, "SOC alerts");
+        assert_eq!(flow.nodes.len(), 3);
+        assert_eq!(flow.secrets, vec!["wazuh_password".to_string()]);
+
+        let wazuh = &flow.nodes["wazuh_monitor"].parameters;
+        assert_eq!(wazuh["base_url"], "https://wazuh-manager:55000");
+        assert_eq!(wazuh["username"], "wazuh-api");
+        assert_eq!(wazuh["operation"], "get_alerts");
+        assert_eq!(wazuh["level"], "10");
+
+        let filter = &flow.nodes["alert_filter"].parameters;
+        assert_eq!(filter["filter_level"], "medium");
+        assert_eq!(filter["enable_correlation"], true);
+
+        let discord = &flow.nodes["discord_alert"].parameters;
+        assert_eq!(discord["webhook_url"], "https://discord.com/api/webhooks/123/abc");
+        assert!(!discord.contains_key("mention_role"));
+
+        assert_eq!(flow.edges.len(), 2);
+        assert_eq!(flow.edges[0].source_port.as_deref(), Some("alerts"));
+        assert_ne!(flow.edges[0].id, "edge_1");
+        match &flow.triggers[0].trigger_type {
+            TriggerType::Cron { expression, .. } => assert_eq!(expression, "0 */5 * * * *"),
+            other => panic!("unexpected trigger {:?}", other),
+        }
+
+        let again = install_template(&template, &installation(serde_json::json!({
+            "wazuh_username": "a",
+            "wazuh_password": "b",
+            "discord_webhook": "https://discord.com/api/webhooks/1/x",
+        })))
+        .unwrap();
+        assert_ne!(again.id, flow.id);
+    }
+
+    #[test]
+    fn test_install_reports_every_invalid_variable() {
+        let error = install_template(
+            &discord_template(),
+            &installation(serde_json::json!({
+                "wazuh_username": "wazuh-api",
+                "discord_webhook": "https://example.com/hook",
+                "alert_level": "5",
+            })),
+        )
+        .unwrap_err();
+
+        let message = error.to_string();
+        assert!(message.contains("wazuh_password: is required"), "{}", message);
+        assert!(message.contains("discord_webhook: does not match pattern"), "{}", message);
+        assert!(message.contains("alert_level: '5' is not one of"), "{}", message);
+    }
+
+    #[test]
+    fn test_expression_substitutes_template_variables() {
+        let mut variables = HashMap::new();
+        variables.insert("cpu_threshold".to_string(), serde_json::json!(80));
+
+        assert_eq!(
+            evaluate_expression("cpu_usage > {{cpu_threshold}} OR load > {{ cpu_threshold }}", &variables).unwrap(),
+            serde_json::json!("cpu_usage > 80 OR load > 80")
+        );
+        assert_eq!(evaluate_expression("{{cpu_threshold}}", &variables).unwrap(), serde_json::json!(80));
+        assert_eq!(evaluate_expression("{{cpu_threshold}} - 5", &variables).unwrap(), serde_json::json!(75));
+        assert!(evaluate_expression("load > {{ load }}", &variables).is_err());
+    }
+
+    #[test]
+    fn test_install_evaluates_expressions() {
+        let mut template = discord_template();
+        let set_label = |template: &mut FlowTemplate, expression: &str| {
+            let node = template.template_data.nodes.iter_mut().find(|n| n.id == "wazuh_monitor").unwrap();
+            node.parameters
+                .insert("label".to_string(), TemplateParameter::Expression(expression.to_string()));
+        };
+        let install = |template: &FlowTemplate| {
+            install_template(
+                template,
+                &installation(serde_json::json!({
+                    "wazuh_username": "wazuh-api",
+                    "wazuh_password": "s3cret",
+                    "discord_webhook": "https://discord.com/api/webhooks/123/abc",
+                })),
+            )
+        };
+
+        set_label(&mut template, r#""soc-" + {{wazuh_username}} + "-" + uuid()"#);
+        let first = install(&template).unwrap();
+        let second = install(&template).unwrap();
+        let label = |flow: &Flow| flow.nodes["wazuh_monitor"].parameters["label"].as_str().unwrap().to_string();
+        assert!(label(&first).starts_with("soc-wazuh-api-"));
+        assert_ne!(label(&first), label(&second));
+
+        set_label(&mut template, "{{undeclared}} + 1");
+        let message = install(&template).unwrap_err().to_string();
+        assert!(message.contains("references undeclared variable 'undeclared'"), "{}", message);
+    }
+
+    #[test]
+    fn test_registry_filters_by_category() {
+        let registry = TemplateRegistry::with_builtin_templates();
+        let query = TemplateQuery {
+            category: Some(TemplateCategory::Infrastructure),
+            ..Default::default()
+        };
+        let ids: Vec<&str> = registry.search(&query).iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["proxmox_vm_monitoring"]);
+
+        let query = TemplateQuery {
+            difficulty: Some(TemplateDifficulty::Beginner),
+            sort: Some(TemplateSort::Downloads),
+            ..Default::default()
+        };
+        let ids: Vec<&str> = registry.search(&query).iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["microsoft_teams_daily_report", "discord_security_alerts"]);
+    }
+
+    #[test]
+    fn test_registry_tag_search_finds_wazuh_template() {
+        let registry = TemplateRegistry::with_builtin_templates();
+        let query = TemplateQuery {
+            search: Some("WAZUH".to_string()),
+            ..Default::default()
+        };
+        let results = registry.search(&query);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "discord_security_alerts");
+        assert!(registry.get("discord_security_alerts").is_some());
+    }
+
+    #[test]
+    fn test_registry_rejects_duplicate_ids() {
+        let mut registry = TemplateRegistry::with_builtin_templates();
+        assert!(registry.register(discord_template()).is_err());
+    }
+
+    #[test]
+    fn test_builtin_templates_are_valid() {
+        for template in get_builtin_templates() {
+            let warnings = validate_template(&template)
+                .unwrap_or_else(|e| panic!("{}", e));
+            assert!(warnings.is_empty(), "{}: {:?}", template.id, warnings);
+        }
+    }
+
+    #[test]
+    fn test_validate_template_catches_broken_references() {
+        let mut template = discord_template();
+        template.id = "broken".to_string();
+        let data = &mut template.template_data;
+        data.nodes[0]
+            .parameters
+            .insert("extra".to_string(), TemplateParameter::Variable("not_declared".to_string()));
+        data.variables[0].validation = Some(VariableValidation {
+            pattern: Some("([unclosed".to_string()),
+            ..Default::default()
+        });
+        data.edges[0].target_node = "missing".to_string();
+
+        let message = validate_template(&template).unwrap_err().to_string();
+        assert!(message.contains("undeclared variable 'not_declared'"), "{}", message);
+        assert!(message.contains("invalid pattern"), "{}", message);
+        assert!(message.contains("unknown node 'missing'"), "{}", message);
+
+        let mut registry = TemplateRegistry::new();
+        assert!(registry.register(template).is_err());
+    }
+
+    #[test]
+    fn test_validate_template_warns_on_unused_variable() {
+        let mut template = discord_template();
+        template.template_data.variables.push(TemplateVariable {
+            name: "unused".to_string(),
+            display_name: "Unused".to_string(),
+            description: "Never referenced".to_string(),
+            variable_type: VariableType::String,
+            default_value: None,
+            required: false,
+            placeholder: None,
+            validation: None,
+        });
+        assert_eq!(
+            validate_template(&template).unwrap(),
+            vec!["variable 'unused' is declared but never used".to_string()]
+        );
+    }
+
+    fn uptime_flow() -> Flow {
+        let node = |id: &str, node_type: &str, parameters: serde_json::Value| FlowNode {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            name: id.to_string(),
+            description: None,
+            parameters: serde_json::from_value(parameters).unwrap(),
+            position: NodePosition { x: 100.0, y: 200.0 },
+            retry_config: None,
+            timeout_ms: None,
+            cache_ttl_ms: None,
+            node_version: None,
+        };
+        let nodes = vec![
+            node("check", "http_request", serde_json::json!({
+                "url": "https://status.example.com/health",
+                "method": "GET",
+                "timeout": 30,
+            })),
+            node("notify", "slack_message", serde_json::json!({
+                "bot_token": "xoxb-secret",
+                "channel": "#ops",
+                "text": "Health check finished",
+            })),
+        ];
+        Flow {
+            id: Uuid::new_v4(),
+            name: "Uptime Check".to_string(),
+            description: Some("Ping the status page".to_string()),
+            version: "1.0.0".to_string(),
+            nodes: nodes.into_iter().map(|n| (n.id.clone(), n)).collect(),
+            edges: vec![FlowEdge {
+                id: "e1".to_string(),
+                source_node: "check".to_string(),
+                target_node: "notify".to_string(),
+                source_port: Some("body".to_string()),
+                target_port: Some("text".to_string()),
+                condition: None,
+                coerce: false,
+            }],
+            triggers: vec![FlowTrigger {
+                id: "t1".to_string(),
+                trigger_type: TriggerType::Cron {
+                    expression: "*/5 * * * *".to_string(),
+                    timezone: Some("UTC".to_string()),
+                },
+                config: HashMap::new(),
+                enabled: true,
+                priority: 0,
+            }],
+            parameters: HashMap::new(),
+            secrets: vec![],
+            max_duration_ms: None,
+            max_concurrent_executions: None,
+            overflow_policy: OverflowPolicy::Queue,
+            metadata: FlowMetadata {
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                created_by: "ops".to_string(),
+                tags: vec!["monitoring".to_string()],
+                category: Some("monitoring".to_string()),
+                workspace_id: "default".to_string(),
+            },
+        }
+    }
+
+    fn expose(node_id: &str, parameter: &str) -> ExposedParameter {
+        ExposedParameter {
+            node_id: node_id.to_string(),
+            parameter: parameter.to_string(),
+            variable: None,
+            display_name: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_extract_template_declares_typed_variables() {
+        let template = extract_template(
+            &uptime_flow(),
+            &[expose("check", "url"), expose("check", "timeout"), expose("notify", "bot_token")],
+        )
+        .unwrap();
+
+        assert_eq!(template.id, "uptime_check");
+        assert_eq!(template.category, TemplateCategory::Monitoring);
+        let variable = |name: &str| template.template_data.variables.iter().find(|v| v.name == name).unwrap();
+        assert!(matches!(variable("check_url").variable_type, VariableType::Url));
+        assert!(matches!(variable("check_timeout").variable_type, VariableType::Number));
+        let token = variable("notify_bot_token");
+        assert!(matches!(token.variable_type, VariableType::Secret));
+        assert!(token.required && token.default_value.is_none());
+
+        let check = template.template_data.nodes.iter().find(|n| n.id == "check").unwrap();
+        assert!(matches!(&check.parameters["url"], TemplateParameter::Variable(v) if v == "check_url"));
+        assert!(matches!(&check.parameters["method"], TemplateParameter::Static(v) if v == "GET"));
+        assert!(!serde_json::to_string(&template).unwrap().contains("xoxb-secret"));
+
+        let err = extract_template(&uptime_flow(), &[expose("check", "headers")]).unwrap_err();
+        assert!(err.to_string().contains("no parameter 'headers'"), "{}", err);
+    }
+
+    #[test]
+    fn test_extracted_template_reinstalls_to_the_original_flow() {
+        let original = uptime_flow();
+        let template =
+            extract_template(&original, &[expose("check", "url"), expose("notify", "bot_token")]).unwrap();
+
+        let installed = install_template(
+            &template,
+            &TemplateInstallation {
+                template_id: template.id.clone(),
+                user_variables: serde_json::from_value(serde_json::json!({ "notify_bot_token": "xoxb-secret" }))
+                    .unwrap(),
+                flow_name: original.name.clone(),
+                description: None,
+            },
+        )
+        .unwrap();
+
+        for (id, node) in &original.nodes {
+            let copy = &installed.nodes[id];
+            assert_eq!(copy.node_type, node.node_type);
+            assert_eq!(copy.parameters, node.parameters);
+            assert_eq!((copy.position.x, copy.position.y), (node.position.x, node.position.y));
+        }
+        assert_eq!(installed.edges.len(), 1);
+        let edge = &installed.edges[0];
+        assert_eq!((edge.source_node.as_str(), edge.target_node.as_str()), ("check", "notify"));
+        assert_eq!((edge.source_port.as_deref(), edge.target_port.as_deref()), (Some("body"), Some("text")));
+        assert!(matches!(
+            &installed.triggers[0].trigger_type,
+            TriggerType::Cron { expression, timezone: Some(tz) } if expression == "*/5 * * * *" && tz == "UTC"
+        ));
+        assert_eq!(installed.description, original.description);
+        assert_eq!(installed.secrets, vec!["notify_bot_token".to_string()]);
+    }
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use ghostflow_schema::{ExecutionContext, NodeDefinition};
+use ghostflow_schema::{ExecutionContext, NodeDefinition, ParameterOption};
 use crate::Result;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -16,20 +16,104 @@ pub trait Node: Send + Sync {
         true
     }
     
+    /// Whether the same input always gives the same output without side
+    /// effects, so the engine may serve the output from its cache and
+    /// re-run the node on replay. Pure nodes opt in; anything that talks to
+    /// the outside world must not.
     fn is_deterministic(&self) -> bool {
-        true
+        false
+    }
+
+    /// [`Node::is_deterministic`] for one resolved input, for nodes whose
+    /// parameters decide whether they have side effects, like an HTTP
+    /// method. The engine asks this before caching or stubbing a node.
+    fn is_deterministic_for(&self, _input: &serde_json::Value) -> bool {
+        self.is_deterministic()
+    }
+
+    /// Whether the node is activated once per completed upstream node
+    /// rather than once with the first value per port. Such nodes receive
+    /// every activation on [`ghostflow_schema::ACTIVATIONS_PORT`].
+    fn accepts_multiple_activations(&self) -> bool {
+        false
+    }
+
+    /// Whether [`Node::test_connection`] is implemented, i.e. whether the UI
+    /// can offer to test credentials for this node.
+    fn supports_connection_test(&self) -> bool {
+        false
+    }
+
+    /// Check that the connection details in `context.input` (usually a
+    /// stored credential) are accepted by the remote system, without doing
+    /// any real work. Returns a short diagnostic on success.
+    async fn test_connection(&self, _context: &ExecutionContext) -> Result<String> {
+        Err(crate::GhostFlowError::ConfigurationError {
+            message: format!("Node '{}' has no connection test", self.definition().id),
+        })
+    }
+
+    /// Parameters whose choices come from the remote system (Slack channels,
+    /// Proxmox nodes) and are fetched with [`Node::load_options`] rather than
+    /// listed in the definition.
+    fn dynamic_option_parameters(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Current choices for `parameter`, using the connection details in
+    /// `context.input` (usually a stored credential).
+    async fn load_options(&self, parameter: &str, _context: &ExecutionContext) -> Result<Vec<ParameterOption>> {
+        Err(crate::GhostFlowError::ConfigurationError {
+            message: format!("Node '{}' cannot load options for '{}'", self.definition().id, parameter),
+        })
+    }
+
+    /// Get ready for the first execution, e.g. by loading a model, so it is
+    /// not slowed down by a cold start. Called by [`crate::warm_up_nodes`].
+    async fn warmup(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// How often to repeat [`Node::warmup`] so whatever it loaded stays
+    /// resident between executions. `None` warms up only once.
+    fn keep_alive_interval(&self) -> Option<std::time::Duration> {
+        None
     }
 }
 
 #[async_trait]
 pub trait FlowStorage: Send + Sync {
-    async fn save_flow(&self, flow: &ghostflow_schema::Flow) -> Result<()>;
+    /// Store `flow` as a new version, leaving earlier versions untouched.
+    /// Returns the version number assigned. The stored copy's `version` is
+    /// replaced with that number, so it always names the snapshot that ran;
+    /// whatever version string the caller set is not kept.
+    async fn save_flow(&self, flow: &ghostflow_schema::Flow) -> Result<u32>;
     
+    /// Current (latest) version of the flow.
     async fn get_flow(&self, flow_id: &uuid::Uuid) -> Result<Option<ghostflow_schema::Flow>>;
     
     async fn list_flows(&self) -> Result<Vec<ghostflow_schema::Flow>>;
     
+    /// Delete the flow along with every stored version.
     async fn delete_flow(&self, flow_id: &uuid::Uuid) -> Result<()>;
+
+    /// Every saved version of the flow, oldest first.
+    async fn list_versions(&self, flow_id: &uuid::Uuid) -> Result<Vec<ghostflow_schema::FlowVersion>>;
+
+    async fn get_flow_version(&self, flow_id: &uuid::Uuid, version: u32) -> Result<Option<ghostflow_schema::Flow>>;
+
+    /// Restore an old version by saving a copy of it as the new current
+    /// version. Returns the new version number.
+    async fn rollback_flow(&self, flow_id: &uuid::Uuid, version: u32) -> Result<u32> {
+        let flow = self
+            .get_flow_version(flow_id, version)
+            .await?
+            .ok_or_else(|| crate::GhostFlowError::NotFoundError {
+                resource_type: "flow version".to_string(),
+                id: format!("{}@{}", flow_id, version),
+            })?;
+        self.save_flow(&flow).await
+    }
 }
 
 #[async_trait]
@@ -47,6 +131,68 @@ pub trait ExecutionStorage: Send + Sync {
     async fn list_executions(&self, flow_id: &uuid::Uuid) -> Result<Vec<ghostflow_schema::FlowExecution>>;
 }
 
+/// Durable home for the progress of unfinished executions, so those
+/// suspended at an approval or interrupted by a restart can resume.
+#[async_trait]
+pub trait ExecutionStateStorage: Send + Sync {
+    /// Insert or replace the state of `state.execution_id`.
+    async fn save_state(&self, state: &ghostflow_schema::ExecutionState) -> Result<()>;
+
+    async fn load_state(&self, execution_id: &uuid::Uuid) -> Result<Option<ghostflow_schema::ExecutionState>>;
+
+    async fn delete_state(&self, execution_id: &uuid::Uuid) -> Result<()>;
+
+    /// Every execution with saved state, i.e. those that have not finished.
+    async fn list_states(&self) -> Result<Vec<ghostflow_schema::ExecutionState>>;
+}
+
+/// Remembers which execution an idempotency key started, so a retried
+/// request can be answered with the original run.
+#[async_trait]
+pub trait IdempotencyStorage: Send + Sync {
+    /// Execution recorded for `key`, unless its TTL has passed.
+    async fn get_execution_for_key(&self, key: &str) -> Result<Option<uuid::Uuid>>;
+    
+    async fn save_key(&self, key: &str, execution_id: uuid::Uuid, ttl: std::time::Duration) -> Result<()>;
+
+    /// Atomically record `execution_id` for `key` before it runs, unless
+    /// the key is already live; then the execution holding it is returned
+    /// and nothing is written.
+    async fn reserve_key(
+        &self,
+        key: &str,
+        execution_id: uuid::Uuid,
+        ttl: std::time::Duration,
+    ) -> Result<Option<uuid::Uuid>>;
+
+    /// Forget `key`, e.g. when the execution reserved for it never started.
+    async fn release_key(&self, key: &str) -> Result<()>;
+}
+
+/// Failed executions awaiting inspection or retry, keyed by execution id.
+#[async_trait]
+pub trait DeadLetterStorage: Send + Sync {
+    async fn save_dead_letter(&self, dead_letter: &ghostflow_schema::DeadLetter) -> Result<()>;
+
+    async fn get_dead_letter(&self, execution_id: &uuid::Uuid) -> Result<Option<ghostflow_schema::DeadLetter>>;
+
+    /// Every dead letter, most recent failure first.
+    async fn list_dead_letters(&self) -> Result<Vec<ghostflow_schema::DeadLetter>>;
+
+    async fn delete_dead_letter(&self, execution_id: &uuid::Uuid) -> Result<()>;
+}
+
+/// Where event-stream triggers remember their position, so a restarted
+/// runtime continues after the last event it handled. Keyed per trigger.
+#[async_trait]
+pub trait ResumeTokenStorage: Send + Sync {
+    async fn save_resume_token(&self, key: &str, token: &serde_json::Value) -> Result<()>;
+
+    async fn get_resume_token(&self, key: &str) -> Result<Option<serde_json::Value>>;
+
+    async fn delete_resume_token(&self, key: &str) -> Result<()>;
+}
+
 #[async_trait]
 pub trait SecretsManager: Send + Sync {
     async fn get_secret(&self, key: &str) -> Result<Option<String>>;
@@ -68,6 +214,34 @@ pub trait NodeRegistry: Send + Sync {
     fn validate_node_type(&self, node_type: &str) -> bool;
 }
 
+/// Runs nodes the way the engine runs the nodes of a flow, for nodes that
+/// run other nodes such as try/catch or LLM tools.
+#[async_trait]
+pub trait NodeRunner: Send + Sync {
+    /// The node of `node_type` in the engine's registry
+    fn get_node(&self, node_type: &str) -> Option<Arc<dyn Node>>;
+
+    /// Check the declared parameters, validate and execute `node` under its
+    /// node type's concurrency limit.
+    async fn run_node(&self, node: &dyn Node, context: ExecutionContext) -> Result<serde_json::Value>;
+}
+
+/// Definitions of every node in `registry`, sorted by id so exports are
+/// stable between runs.
+pub fn export_node_definitions(registry: &dyn NodeRegistry) -> Vec<NodeDefinition> {
+    let mut definitions = registry.list_node_definitions();
+    definitions.sort_by(|a, b| a.id.cmp(&b.id));
+    definitions
+}
+
+/// Machine-readable node catalog used by the UI and `gflow nodes --json`.
+/// Includes parameter options and validation so clients can render forms.
+pub fn node_definitions_json(registry: &dyn NodeRegistry) -> Result<serde_json::Value> {
+    Ok(serde_json::json!({
+        "nodes": serde_json::to_value(export_node_definitions(registry))?,
+    }))
+}
+
 pub struct BasicNodeRegistry {
     nodes: HashMap<String, Arc<dyn Node>>,
 }
@@ -0,0 +1,69 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+/// Flow-scoped key-value state, namespaced by execution id. Values written by
+/// one node are visible to every node that runs later in the same execution,
+/// whether or not an edge connects them. The executor clears an execution's
+/// scope when it finishes.
+#[derive(Default)]
+pub struct FlowVariableStore {
+    scopes: RwLock<HashMap<Uuid, HashMap<String, Value>>>,
+}
+
+impl FlowVariableStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, execution_id: Uuid, name: impl Into<String>, value: Value) {
+        self.scopes
+            .write()
+            .unwrap()
+            .entry(execution_id)
+            .or_default()
+            .insert(name.into(), value);
+    }
+
+    pub fn get(&self, execution_id: Uuid, name: &str) -> Option<Value> {
+        self.scopes
+            .read()
+            .unwrap()
+            .get(&execution_id)
+            .and_then(|scope| scope.get(name))
+            .cloned()
+    }
+
+    /// All variables set so far in `execution_id`.
+    pub fn snapshot(&self, execution_id: Uuid) -> HashMap<String, Value> {
+        self.scopes
+            .read()
+            .unwrap()
+            .get(&execution_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&self, execution_id: Uuid) {
+        self.scopes.write().unwrap().remove(&execution_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variables_are_namespaced_per_execution() {
+        let store = FlowVariableStore::new();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        store.set(first, "vmid", Value::from(101));
+        assert_eq!(store.get(first, "vmid"), Some(Value::from(101)));
+        assert_eq!(store.get(second, "vmid"), None);
+
+        store.clear(first);
+        assert!(store.snapshot(first).is_empty());
+    }
+}
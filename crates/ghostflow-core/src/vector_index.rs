@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::{GhostFlowError, Result};
+
+/// A stored embedding and what it was computed from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VectorEntry {
+    pub id: String,
+    pub embedding: Vec<f32>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub metadata: Value,
+}
+
+/// An entry returned by [`VectorIndexStore::query`], most similar first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VectorMatch {
+    pub id: String,
+    /// Cosine similarity to the query, from -1 to 1
+    pub score: f32,
+    pub text: Option<String>,
+    pub metadata: Value,
+}
+
+/// Named in-memory vector indexes for retrieval in RAG flows. Indexes are
+/// shared between flows, so one flow can ingest documents that others query.
+/// Queries scan every entry, which is fine for thousands of vectors.
+#[derive(Default)]
+pub struct VectorIndexStore {
+    indexes: RwLock<HashMap<String, Vec<VectorEntry>>>,
+}
+
+impl VectorIndexStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `entries` to `index`, replacing entries with the same id. All
+    /// vectors in an index must have the same dimension. Returns the size
+    /// of the index afterwards.
+    pub fn upsert(&self, index: &str, entries: Vec<VectorEntry>) -> Result<usize> {
+        let mut indexes = self.indexes.write().unwrap();
+        let stored = indexes.entry(index.to_string()).or_default();
+
+        let dimension = stored.first().or(entries.first()).map(|e| e.embedding.len());
+        if let Some(entry) = entries.iter().find(|e| Some(e.embedding.len()) != dimension || e.embedding.is_empty()) {
+            return Err(GhostFlowError::ValidationError {
+                message: format!(
+                    "Vector '{}' has {} dimensions but index '{}' uses {}",
+                    entry.id,
+                    entry.embedding.len(),
+                    index,
+                    dimension.unwrap_or_default()
+                ),
+            });
+        }
+
+        for entry in entries {
+            match stored.iter_mut().find(|existing| existing.id == entry.id) {
+                Some(existing) => *existing = entry,
+                None => stored.push(entry),
+            }
+        }
+        Ok(stored.len())
+    }
+
+    /// The `top_k` entries of `index` most similar to `vector`.
+    pub fn query(&self, index: &str, vector: &[f32], top_k: usize) -> Result<Vec<VectorMatch>> {
+        let indexes = self.indexes.read().unwrap();
+        let Some(stored) = indexes.get(index) else {
+            return Ok(Vec::new());
+        };
+        if let Some(entry) = stored.first().filter(|e| e.embedding.len() != vector.len()) {
+            return Err(GhostFlowError::ValidationError {
+                message: format!(
+                    "Query vector has {} dimensions but index '{}' uses {}",
+                    vector.len(),
+                    index,
+                    entry.embedding.len()
+                ),
+            });
+        }
+
+        let mut matches: Vec<VectorMatch> = stored
+            .iter()
+            .map(|entry| VectorMatch {
+                id: entry.id.clone(),
+                score: cosine_similarity(&entry.embedding, vector),
+                text: entry.text.clone(),
+                metadata: entry.metadata.clone(),
+            })
+            .collect();
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(top_k);
+        Ok(matches)
+    }
+
+    /// Remove entries by id, returning how many were removed.
+    pub fn remove(&self, index: &str, ids: &[String]) -> usize {
+        let mut indexes = self.indexes.write().unwrap();
+        let Some(stored) = indexes.get_mut(index) else {
+            return 0;
+        };
+        let before = stored.len();
+        stored.retain(|entry| !ids.contains(&entry.id));
+        before - stored.len()
+    }
+
+    pub fn len(&self, index: &str) -> usize {
+        self.indexes.read().unwrap().get(index).map_or(0, Vec::len)
+    }
+}
+
+/// Cosine of the angle between two vectors; 0 when either has no length.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let (norm_a, norm_b) = (norm(a), norm(b));
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, embedding: Vec<f32>) -> VectorEntry {
+        VectorEntry {
+            id: id.to_string(),
+            embedding,
+            text: Some(id.to_string()),
+            metadata: Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_query_ranks_by_cosine_similarity() {
+        let store = VectorIndexStore::new();
+        store
+            .upsert("docs", vec![entry("east", vec![1.0, 0.0]), entry("north", vec![0.0, 1.0])])
+            .unwrap();
+        store.upsert("docs", vec![entry("northeast", vec![1.0, 1.0])]).unwrap();
+
+        let matches = store.query("docs", &[0.1, 0.9], 2).unwrap();
+        let ids: Vec<&str> = matches.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["north", "northeast"]);
+        assert!((matches[0].score - cosine_similarity(&[0.0, 1.0], &[0.1, 0.9])).abs() < 1e-6);
+        assert!(store.query("missing", &[1.0, 0.0], 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_upsert_replaces_by_id_and_checks_dimensions() {
+        let store = VectorIndexStore::new();
+        assert_eq!(store.upsert("docs", vec![entry("a", vec![1.0, 0.0])]).unwrap(), 1);
+        assert_eq!(store.upsert("docs", vec![entry("a", vec![0.0, 1.0])]).unwrap(), 1);
+        assert_eq!(store.query("docs", &[0.0, 1.0], 1).unwrap()[0].score, 1.0);
+
+        assert!(store.upsert("docs", vec![entry("b", vec![1.0, 0.0, 0.0])]).is_err());
+        assert!(store.query("docs", &[1.0], 1).is_err());
+        assert_eq!(store.remove("docs", &["a".to_string()]), 1);
+        assert_eq!(store.len("docs"), 0);
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::{Node, NodeRegistry};
+
+/// Background keep-alive tasks started by [`warm_up_nodes`]. They stop when
+/// this is dropped.
+#[derive(Default)]
+pub struct KeepAlive {
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl KeepAlive {
+    /// Number of nodes being kept warm
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+impl Drop for KeepAlive {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Call [`Node::warmup`] once on every node in `registry`, then keep calling
+/// it in the background for nodes with a [`Node::keep_alive_interval`].
+/// Failures are logged rather than returned: a model server that is not up
+/// yet should not stop startup. Call once, after registering the nodes.
+pub async fn warm_up_nodes(registry: &dyn NodeRegistry) -> KeepAlive {
+    let mut seen = HashSet::new();
+    let mut keep_alive = KeepAlive::default();
+
+    for definition in registry.list_node_definitions() {
+        let Some(node) = registry.get_node(&definition.id) else {
+            continue;
+        };
+        // The same node may be registered under several ids
+        if !seen.insert(Arc::as_ptr(&node) as *const () as usize) {
+            continue;
+        }
+
+        match node.warmup().await {
+            Ok(()) => info!("Warmed up node '{}'", definition.id),
+            Err(e) => warn!("Warmup of node '{}' failed: {}", definition.id, e),
+        }
+        if let Some(interval) = node.keep_alive_interval() {
+            keep_alive.tasks.push(tokio::spawn(keep_warm(definition.id, node, interval)));
+        }
+    }
+    keep_alive
+}
+
+async fn keep_warm(node_type: String, node: Arc<dyn Node>, interval: std::time::Duration) {
+    let mut ticks = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticks.tick().await;
+        if let Err(e) = node.warmup().await {
+            warn!("Keep-alive of node '{}' failed: {}", node_type, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNodeRegistry, Result};
+    use async_trait::async_trait;
+    use ghostflow_schema::{ExecutionContext, NodeCategory, NodeDefinition};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    struct ModelNode {
+        id: &'static str,
+        warmups: Arc<AtomicUsize>,
+        keep_alive: Option<Duration>,
+    }
+
+    #[async_trait]
+    impl Node for ModelNode {
+        fn definition(&self) -> NodeDefinition {
+            NodeDefinition {
+                id: self.id.to_string(),
+                name: self.id.to_string(),
+                description: String::new(),
+                category: NodeCategory::Ai,
+                version: "1.0.0".to_string(),
+                inputs: vec![],
+                outputs: vec![],
+                parameters: vec![],
+                icon: None,
+                color: None,
+            }
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, _context: ExecutionContext) -> Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+
+        async fn warmup(&self) -> Result<()> {
+            self.warmups.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn keep_alive_interval(&self) -> Option<Duration> {
+            self.keep_alive
+        }
+    }
+
+    fn registry(nodes: Vec<ModelNode>) -> BasicNodeRegistry {
+        let mut registry = BasicNodeRegistry::new();
+        for node in nodes {
+            registry.register_node(node.id.to_string(), Arc::new(node)).unwrap();
+        }
+        registry
+    }
+
+    #[tokio::test]
+    async fn test_every_node_is_warmed_up_once() {
+        let (first, second) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let registry = registry(vec![
+            ModelNode { id: "first", warmups: first.clone(), keep_alive: None },
+            ModelNode { id: "second", warmups: second.clone(), keep_alive: None },
+        ]);
+
+        let keep_alive = warm_up_nodes(&registry).await;
+
+        assert!(keep_alive.is_empty());
+        assert_eq!(first.load(Ordering::SeqCst), 1);
+        assert_eq!(second.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keep_alive_ticks_on_schedule() {
+        let warmups = Arc::new(AtomicUsize::new(0));
+        let registry = registry(vec![ModelNode {
+            id: "model",
+            warmups: warmups.clone(),
+            keep_alive: Some(Duration::from_secs(60)),
+        }]);
+
+        let keep_alive = warm_up_nodes(&registry).await;
+        assert_eq!(keep_alive.len(), 1);
+        assert_eq!(warmups.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_secs(59)).await;
+        assert_eq!(warmups.load(Ordering::SeqCst), 1);
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(warmups.load(Ordering::SeqCst), 2);
+        tokio::time::sleep(Duration::from_secs(120)).await;
+        assert_eq!(warmups.load(Ordering::SeqCst), 4);
+
+        drop(keep_alive);
+        tokio::time::sleep(Duration::from_secs(600)).await;
+        assert_eq!(warmups.load(Ordering::SeqCst), 4);
+    }
+}
@@ -0,0 +1,119 @@
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::{GhostFlowError, Result};
+
+/// How long a delivery id is remembered unless the webhook node says otherwise.
+pub const DEFAULT_REPLAY_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Replay protection settings of a webhook trigger node. Providers that
+/// redeliver a webhook resend the same delivery id, taken either from a
+/// header such as `X-GitHub-Delivery` or from a field of the JSON body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayProtection {
+    pub header: Option<String>,
+    /// Dotted path into the body, e.g. `event.id`
+    pub body_field: Option<String>,
+    pub window: Duration,
+}
+
+impl ReplayProtection {
+    /// Read from the `delivery_id_header`, `delivery_id_field` and
+    /// `replay_window_secs` node parameters. `None` when neither a header nor
+    /// a field is set.
+    pub fn from_params(params: &Value) -> Result<Option<Self>> {
+        let text = |key: &str| {
+            params
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let (header, body_field) = (text("delivery_id_header"), text("delivery_id_field"));
+        if header.is_none() && body_field.is_none() {
+            return Ok(None);
+        }
+
+        let window = match params.get("replay_window_secs") {
+            None | Some(Value::Null) => DEFAULT_REPLAY_WINDOW,
+            Some(value) => value
+                .as_u64()
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs)
+                .ok_or_else(|| GhostFlowError::ValidationError {
+                    message: format!("Replay window must be a positive number of seconds, got {}", value),
+                })?,
+        };
+
+        Ok(Some(Self {
+            header,
+            body_field,
+            window,
+        }))
+    }
+
+    /// Delivery id of a webhook request, given the `{"headers": .., "body": ..}`
+    /// input the API builds for it. The header wins when both are set.
+    pub fn delivery_id(&self, input: &Value) -> Option<String> {
+        let from_header = self.header.as_ref().and_then(|name| {
+            input
+                .get("headers")?
+                .as_object()?
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .and_then(|(_, value)| value.as_str())
+                .map(str::to_string)
+        });
+        let from_body = || {
+            let field = self.body_field.as_ref()?;
+            let value = field
+                .split('.')
+                .try_fold(input.get("body")?, |value, key| value.get(key))?;
+            match value {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            }
+        };
+
+        from_header.or_else(from_body).filter(|id| !id.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_delivery_id_from_header_or_body_field() {
+        let input = json!({
+            "headers": { "x-github-delivery": "72d3162e-cc78-11e3-81ab-4c9367dc0958" },
+            "body": { "event": { "id": 4471 } },
+        });
+
+        let by_header = ReplayProtection::from_params(&json!({ "delivery_id_header": "X-GitHub-Delivery" }))
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_header.window, DEFAULT_REPLAY_WINDOW);
+        assert_eq!(
+            by_header.delivery_id(&input).as_deref(),
+            Some("72d3162e-cc78-11e3-81ab-4c9367dc0958")
+        );
+
+        let by_field =
+            ReplayProtection::from_params(&json!({ "delivery_id_field": "event.id", "replay_window_secs": 600 }))
+                .unwrap()
+                .unwrap();
+        assert_eq!(by_field.window, Duration::from_secs(600));
+        assert_eq!(by_field.delivery_id(&input).as_deref(), Some("4471"));
+        assert_eq!(by_field.delivery_id(&json!({ "body": {} })), None);
+    }
+
+    #[test]
+    fn test_replay_protection_is_off_without_a_source() {
+        assert_eq!(ReplayProtection::from_params(&json!({ "replay_window_secs": 60 })).unwrap(), None);
+        assert!(ReplayProtection::from_params(&json!({ "delivery_id_header": "X-Id", "replay_window_secs": 0 })).is_err());
+    }
+}
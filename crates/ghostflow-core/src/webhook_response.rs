@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+/// How long the webhook handler waits for a flow to respond before falling
+/// back to 202 Accepted.
+pub const DEFAULT_WEBHOOK_RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// HTTP response a webhook-triggered flow sends back to its caller.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: Value,
+}
+
+impl WebhookResponse {
+    /// What the caller gets when the flow does not respond in time: 202 with
+    /// the execution id so it can poll for the result.
+    pub fn accepted(execution_id: Uuid) -> Self {
+        Self {
+            status: 202,
+            headers: HashMap::new(),
+            body: serde_json::json!({
+                "execution_id": execution_id,
+                "status": "accepted"
+            }),
+        }
+    }
+
+    /// What the caller gets for a redelivered webhook: 200 so the provider
+    /// stops retrying, with the execution the first delivery started.
+    pub fn duplicate(execution_id: Uuid) -> Self {
+        Self {
+            status: 200,
+            headers: HashMap::new(),
+            body: serde_json::json!({
+                "execution_id": execution_id,
+                "status": "duplicate"
+            }),
+        }
+    }
+}
+
+/// Webhook callers waiting on a response, keyed by execution id. The webhook
+/// handler registers before the flow starts and awaits the receiver; the
+/// respond-to-webhook node completes it. Only the first response counts.
+#[derive(Default)]
+pub struct WebhookResponseRegistry {
+    pending: Mutex<HashMap<Uuid, oneshot::Sender<WebhookResponse>>>,
+}
+
+impl WebhookResponseRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, execution_id: Uuid) -> oneshot::Receiver<WebhookResponse> {
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(execution_id, tx);
+        rx
+    }
+
+    /// Deliver `response` to the caller waiting on `execution_id`. Returns
+    /// false if nobody is waiting: the execution was not webhook-triggered,
+    /// already responded, or the caller gave up.
+    pub fn respond(&self, execution_id: Uuid, response: WebhookResponse) -> bool {
+        match self.pending.lock().unwrap().remove(&execution_id) {
+            Some(tx) => tx.send(response).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Drop the waiting caller, if any; its receiver then resolves to an error.
+    pub fn remove(&self, execution_id: Uuid) {
+        self.pending.lock().unwrap().remove(&execution_id);
+    }
+}
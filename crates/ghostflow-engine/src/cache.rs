@@ -0,0 +1,116 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Outputs of deterministic nodes, keyed by [`NodeOutputCache::key`] and
+/// kept for the node's `cache_ttl_ms`. Expired entries are dropped lazily.
+#[derive(Default)]
+pub struct NodeOutputCache {
+    entries: Mutex<HashMap<String, (serde_json::Value, Instant)>>,
+}
+
+impl NodeOutputCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash of the node type, its definition version and its resolved
+    /// input. Object keys are hashed in sorted order, since `serde_json`
+    /// keeps insertion order when another crate enables `preserve_order`.
+    /// Bumping a node's version invalidates what older code produced.
+    pub fn key(node_type: &str, version: &str, input: &serde_json::Value) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(node_type.as_bytes());
+        hasher.update([0]);
+        hasher.update(version.as_bytes());
+        hasher.update([0]);
+        hash_canonical(&mut hasher, input);
+        hex::encode(hasher.finalize())
+    }
+
+    pub fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((output, expires_at)) if *expires_at > Instant::now() => Some(output.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&self, key: String, output: serde_json::Value, ttl: Duration) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (_, expires_at)| *expires_at > now);
+        entries.insert(key, (output, now + ttl));
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn hash_canonical(hasher: &mut Sha256, value: &serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            hasher.update(b"{");
+            for key in keys {
+                hasher.update(serde_json::Value::from(key.as_str()).to_string().as_bytes());
+                hasher.update(b":");
+                hash_canonical(hasher, &map[key]);
+                hasher.update(b",");
+            }
+            hasher.update(b"}");
+        }
+        serde_json::Value::Array(items) => {
+            hasher.update(b"[");
+            for item in items {
+                hash_canonical(hasher, item);
+                hasher.update(b",");
+            }
+            hasher.update(b"]");
+        }
+        other => hasher.update(other.to_string().as_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_key_depends_on_type_version_and_input() {
+        let input = json!({ "a": 1, "b": [true, null] });
+        let key = NodeOutputCache::key("transform", "1.0.0", &input);
+
+        assert_eq!(key, NodeOutputCache::key("transform", "1.0.0", &json!({ "b": [true, null], "a": 1 })));
+        assert_ne!(key, NodeOutputCache::key("transform", "1.1.0", &input));
+        assert_ne!(key, NodeOutputCache::key("filter", "1.0.0", &input));
+        assert_ne!(key, NodeOutputCache::key("transform", "1.0.0", &json!({ "a": 2, "b": [true, null] })));
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = NodeOutputCache::new();
+        cache.insert("fresh".to_string(), json!(1), Duration::from_secs(60));
+        cache.insert("stale".to_string(), json!(2), Duration::ZERO);
+
+        assert_eq!(cache.get("fresh"), Some(json!(1)));
+        assert_eq!(cache.get("stale"), None);
+        assert_eq!(cache.len(), 1);
+    }
+}
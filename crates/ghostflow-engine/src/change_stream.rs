@@ -0,0 +1,371 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use ghostflow_core::{GhostFlowError, ResumeTokenStorage, Result};
+use ghostflow_schema::TriggerType;
+use mongodb::bson::{self, Bson, Document};
+use mongodb::change_stream::event::{ChangeStreamEvent, ResumeToken};
+use serde_json::Value;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Longest wait between attempts to reopen a failed change stream
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Where a `TriggerType::MongoChangeStream` trigger listens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeStreamSpec {
+    pub connection_string: String,
+    pub database: String,
+    pub collection: String,
+    pub pipeline: Vec<Value>,
+}
+
+impl ChangeStreamSpec {
+    /// `None` for triggers that are not change streams.
+    pub fn from_trigger(trigger: &TriggerType) -> Result<Option<Self>> {
+        let TriggerType::MongoChangeStream { connection_string, database, collection, pipeline } = trigger else {
+            return Ok(None);
+        };
+        for (name, value) in [("connection_string", connection_string), ("database", database), ("collection", collection)] {
+            if value.is_empty() {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("MongoDB change stream trigger needs a {}", name),
+                });
+            }
+        }
+        if let Some(stage) = pipeline.iter().find(|stage| !stage.is_object()) {
+            return Err(GhostFlowError::ValidationError {
+                message: format!("Change stream pipeline stages must be objects, got {}", stage),
+            });
+        }
+
+        Ok(Some(Self {
+            connection_string: connection_string.clone(),
+            database: database.clone(),
+            collection: collection.clone(),
+            pipeline: pipeline.clone(),
+        }))
+    }
+}
+
+/// An open change stream.
+#[async_trait]
+pub trait ChangeStream: Send {
+    /// The next change event as JSON, its resume token under `_id`. `None`
+    /// when the server closed the stream.
+    async fn next_change(&mut self) -> Result<Option<Value>>;
+}
+
+/// Opens change streams; swapped out in tests.
+#[async_trait]
+pub trait ChangeStreamConnector: Send + Sync {
+    /// Open a stream for `spec`, continuing after `resume_after` when given.
+    async fn open(&self, spec: &ChangeStreamSpec, resume_after: Option<Value>) -> Result<Box<dyn ChangeStream>>;
+}
+
+/// [`ChangeStreamConnector`] backed by the MongoDB driver.
+#[derive(Debug, Default)]
+pub struct MongoChangeStreamConnector;
+
+struct MongoChangeStream {
+    stream: mongodb::change_stream::ChangeStream<ChangeStreamEvent<Document>>,
+}
+
+#[async_trait]
+impl ChangeStreamConnector for MongoChangeStreamConnector {
+    async fn open(&self, spec: &ChangeStreamSpec, resume_after: Option<Value>) -> Result<Box<dyn ChangeStream>> {
+        let client = mongodb::Client::with_uri_str(&spec.connection_string)
+            .await
+            .map_err(mongo_error)?;
+        let collection = client.database(&spec.database).collection::<Document>(&spec.collection);
+
+        let pipeline = spec
+            .pipeline
+            .iter()
+            .map(|stage| {
+                bson::to_document(stage).map_err(|e| GhostFlowError::ValidationError {
+                    message: format!("Invalid change stream pipeline stage: {}", e),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let resume_after = resume_after.map(resume_token).transpose()?;
+
+        let stream = collection
+            .watch()
+            .pipeline(pipeline)
+            .resume_after(resume_after)
+            .await
+            .map_err(mongo_error)?;
+        Ok(Box::new(MongoChangeStream { stream }))
+    }
+}
+
+#[async_trait]
+impl ChangeStream for MongoChangeStream {
+    async fn next_change(&mut self) -> Result<Option<Value>> {
+        match self.stream.next().await {
+            Some(Ok(event)) => {
+                let event = bson::to_bson(&event).map_err(|e| GhostFlowError::InternalError {
+                    message: format!("Failed to convert change event: {}", e),
+                })?;
+                Ok(Some(event.into_relaxed_extjson()))
+            }
+            Some(Err(e)) => Err(mongo_error(e)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// The driver's resume token from the `_id` of a stored change event.
+fn resume_token(token: Value) -> Result<ResumeToken> {
+    let token = Bson::try_from(token).map_err(|e| GhostFlowError::ValidationError {
+        message: format!("Stored resume token is not valid extended JSON: {}", e),
+    })?;
+    bson::from_bson(token).map_err(|e| GhostFlowError::ValidationError {
+        message: format!("Stored resume token is not a change stream token: {}", e),
+    })
+}
+
+fn mongo_error(error: mongodb::error::Error) -> GhostFlowError {
+    GhostFlowError::NetworkError(format!("MongoDB change stream: {}", error))
+}
+
+/// Keeps one change stream trigger listening: opens the stream, hands every
+/// event to the flow and stores its resume token, and reopens the stream
+/// from the last stored token when it closes or fails.
+pub struct ChangeStreamWatcher {
+    spec: ChangeStreamSpec,
+    /// Key of this trigger's token in `tokens`
+    key: String,
+    connector: Arc<dyn ChangeStreamConnector>,
+    tokens: Arc<dyn ResumeTokenStorage>,
+    initial_backoff: Duration,
+}
+
+impl ChangeStreamWatcher {
+    pub fn new(
+        spec: ChangeStreamSpec,
+        key: impl Into<String>,
+        connector: Arc<dyn ChangeStreamConnector>,
+        tokens: Arc<dyn ResumeTokenStorage>,
+    ) -> Self {
+        Self {
+            spec,
+            key: key.into(),
+            connector,
+            tokens,
+            initial_backoff: Duration::from_secs(1),
+        }
+    }
+
+    /// First wait before reopening a stream that failed; doubles on each
+    /// consecutive failure.
+    pub fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Run until the task is aborted, awaiting `on_change` for every event.
+    /// An event's token is stored only after `on_change` returns, so a crash
+    /// in between replays the event rather than losing it.
+    pub async fn run<F, Fut>(self, mut on_change: F)
+    where
+        F: FnMut(Value) -> Fut + Send,
+        Fut: Future<Output = ()> + Send,
+    {
+        let mut backoff = self.initial_backoff;
+        loop {
+            let resume_after = match self.tokens.get_resume_token(&self.key).await {
+                Ok(token) => token,
+                Err(e) => {
+                    warn!("Failed to load resume token for {}: {}", self.key, e);
+                    None
+                }
+            };
+
+            let failed = match self.connector.open(&self.spec, resume_after.clone()).await {
+                Ok(mut stream) => {
+                    info!(
+                        "Watching {}.{} for trigger {}{}",
+                        self.spec.database,
+                        self.spec.collection,
+                        self.key,
+                        if resume_after.is_some() { " (resumed)" } else { "" }
+                    );
+                    loop {
+                        match stream.next_change().await {
+                            Ok(Some(change)) => {
+                                backoff = self.initial_backoff;
+                                let token = change.get("_id").cloned();
+                                on_change(change).await;
+                                if let Some(token) = token {
+                                    if let Err(e) = self.tokens.save_resume_token(&self.key, &token).await {
+                                        warn!("Failed to store resume token for {}: {}", self.key, e);
+                                    }
+                                }
+                            }
+                            Ok(None) => {
+                                info!("Change stream for {} closed; reopening", self.key);
+                                break false;
+                            }
+                            Err(e) => {
+                                warn!("Change stream for {} failed: {}", self.key, e);
+                                break true;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to open change stream for {}: {}", self.key, e);
+                    true
+                }
+            };
+
+            if failed {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ghostflow_core::MemoryResumeTokenStore;
+    use serde_json::json;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+    use tokio::sync::mpsc;
+
+    /// Serves scripted events from a shared queue, one stream per `open`,
+    /// and records the resume token each `open` was given.
+    #[derive(Default)]
+    struct MockConnector {
+        streams: Mutex<VecDeque<Vec<Value>>>,
+        opened_with: Mutex<Vec<Option<Value>>>,
+    }
+
+    struct MockStream {
+        events: VecDeque<Value>,
+    }
+
+    #[async_trait]
+    impl ChangeStream for MockStream {
+        async fn next_change(&mut self) -> Result<Option<Value>> {
+            match self.events.pop_front() {
+                Some(event) => Ok(Some(event)),
+                // Stay open once the script runs out, like an idle stream
+                None => std::future::pending().await,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ChangeStreamConnector for MockConnector {
+        async fn open(&self, _spec: &ChangeStreamSpec, resume_after: Option<Value>) -> Result<Box<dyn ChangeStream>> {
+            self.opened_with.lock().unwrap().push(resume_after);
+            let events = self.streams.lock().unwrap().pop_front().unwrap_or_default();
+            Ok(Box::new(MockStream { events: events.into() }))
+        }
+    }
+
+    fn change(token: &str, operation: &str, document: Value) -> Value {
+        json!({
+            "_id": { "_data": token },
+            "operationType": operation,
+            "ns": { "db": "soc", "coll": "alerts" },
+            "documentKey": { "_id": document["_id"] },
+            "fullDocument": document,
+        })
+    }
+
+    fn spec() -> ChangeStreamSpec {
+        ChangeStreamSpec::from_trigger(&TriggerType::MongoChangeStream {
+            connection_string: "mongodb://localhost:27017".to_string(),
+            database: "soc".to_string(),
+            collection: "alerts".to_string(),
+            pipeline: vec![json!({ "$match": { "operationType": { "$in": ["insert", "update"] } } })],
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    fn watch(
+        connector: Arc<MockConnector>,
+        tokens: Arc<MemoryResumeTokenStore>,
+    ) -> (tokio::task::JoinHandle<()>, mpsc::UnboundedReceiver<Value>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let watcher = ChangeStreamWatcher::new(spec(), "flow-1/alerts", connector, tokens)
+            .with_initial_backoff(Duration::from_millis(10));
+        let handle = tokio::spawn(watcher.run(move |change| {
+            let tx = tx.clone();
+            async move {
+                tx.send(change).unwrap();
+            }
+        }));
+        (handle, rx)
+    }
+
+    #[tokio::test]
+    async fn test_each_change_event_is_delivered_with_its_document() {
+        let connector = Arc::new(MockConnector::default());
+        connector.streams.lock().unwrap().push_back(vec![
+            change("t1", "insert", json!({ "_id": 1, "rule": "ssh brute force", "level": 10 })),
+            change("t2", "update", json!({ "_id": 1, "rule": "ssh brute force", "level": 14 })),
+        ]);
+        let tokens = Arc::new(MemoryResumeTokenStore::new());
+        let (handle, mut rx) = watch(connector.clone(), tokens.clone());
+
+        let insert = rx.recv().await.unwrap();
+        assert_eq!(insert["operationType"], "insert");
+        assert_eq!(insert["fullDocument"]["level"], 10);
+        let update = rx.recv().await.unwrap();
+        assert_eq!(update["operationType"], "update");
+        assert_eq!(update["fullDocument"]["level"], 14);
+        handle.abort();
+
+        assert_eq!(tokens.get_resume_token("flow-1/alerts").await.unwrap(), Some(json!({ "_data": "t2" })));
+        assert_eq!(*connector.opened_with.lock().unwrap(), vec![None]);
+    }
+
+    #[tokio::test]
+    async fn test_restarted_watcher_resumes_after_last_stored_token() {
+        let connector = Arc::new(MockConnector::default());
+        connector.streams.lock().unwrap().extend([
+            vec![change("t1", "insert", json!({ "_id": 1 })), change("t2", "insert", json!({ "_id": 2 }))],
+            vec![change("t3", "update", json!({ "_id": 2 }))],
+        ]);
+        let tokens = Arc::new(MemoryResumeTokenStore::new());
+
+        let (first, mut rx) = watch(connector.clone(), tokens.clone());
+        rx.recv().await.unwrap();
+        rx.recv().await.unwrap();
+        first.abort();
+        let _ = first.await;
+
+        // A new watcher, as after a runtime restart, picks up where the old one stopped
+        let (second, mut rx) = watch(connector.clone(), tokens.clone());
+        let next = rx.recv().await.unwrap();
+        assert_eq!(next["_id"]["_data"], "t3");
+        second.abort();
+
+        let opened_with = connector.opened_with.lock().unwrap().clone();
+        assert_eq!(opened_with, vec![None, Some(json!({ "_data": "t2" }))]);
+    }
+
+    #[test]
+    fn test_spec_requires_collection_and_object_stages() {
+        let trigger = |collection: &str, pipeline: Vec<Value>| TriggerType::MongoChangeStream {
+            connection_string: "mongodb://localhost:27017".to_string(),
+            database: "soc".to_string(),
+            collection: collection.to_string(),
+            pipeline,
+        };
+        assert!(ChangeStreamSpec::from_trigger(&trigger("", vec![])).is_err());
+        assert!(ChangeStreamSpec::from_trigger(&trigger("alerts", vec![json!("$match")])).is_err());
+        assert!(ChangeStreamSpec::from_trigger(&TriggerType::Manual).unwrap().is_none());
+    }
+}
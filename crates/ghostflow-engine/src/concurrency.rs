@@ -0,0 +1,286 @@
+use ghostflow_core::{CancellationRegistry, GhostFlowError, Result};
+use ghostflow_schema::{Flow, OverflowPolicy};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+use tracing::info;
+use uuid::Uuid;
+
+/// Enforces each flow's `max_concurrent_executions`. Clones share state, so
+/// every entry point of a runtime counts against the same limit.
+#[derive(Clone, Default)]
+pub struct FlowConcurrencyLimiter {
+    flows: Arc<Mutex<HashMap<Uuid, Arc<FlowSlots>>>>,
+    cancellations: Arc<CancellationRegistry>,
+}
+
+struct FlowSlots {
+    limit: u32,
+    semaphore: Arc<Semaphore>,
+    /// Executions holding a slot, oldest first
+    running: Mutex<VecDeque<Uuid>>,
+}
+
+/// A running execution's place under its flow's limit, given up on drop.
+pub struct ExecutionSlot {
+    _permit: OwnedSemaphorePermit,
+    slots: Arc<FlowSlots>,
+    execution_id: Uuid,
+}
+
+impl Drop for ExecutionSlot {
+    fn drop(&mut self) {
+        self.slots.running.lock().unwrap().retain(|id| *id != self.execution_id);
+    }
+}
+
+impl FlowConcurrencyLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel executions replaced under the `replace` policy through
+    /// `cancellations`, the registry their executor watches.
+    pub fn with_cancellations(mut self, cancellations: Arc<CancellationRegistry>) -> Self {
+        self.cancellations = cancellations;
+        self
+    }
+
+    /// Take a slot for `execution_id`, applying the flow's overflow policy
+    /// when all are in use: `queue` waits its turn, `drop` fails with
+    /// [`GhostFlowError::RateLimitError`], and `replace` cancels the oldest
+    /// running execution and takes its slot. `None` when the flow has no limit.
+    pub async fn acquire(&self, flow: &Flow, execution_id: Uuid) -> Result<Option<ExecutionSlot>> {
+        let Some(limit) = flow.max_concurrent_executions else {
+            return Ok(None);
+        };
+        let slots = self.slots(flow.id, limit.max(1));
+
+        let permit = match slots.semaphore.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                match flow.overflow_policy {
+                    OverflowPolicy::Drop => {
+                        return Err(GhostFlowError::RateLimitError {
+                            message: format!(
+                                "Flow {} is already running {} execution(s)",
+                                flow.id, slots.limit
+                            ),
+                        });
+                    }
+                    OverflowPolicy::Queue => {
+                        info!("Execution {} of flow {} is queued", execution_id, flow.id);
+                    }
+                    OverflowPolicy::Replace => {
+                        // Taken off the list so concurrent replacements pick different victims
+                        let oldest = slots.running.lock().unwrap().pop_front();
+                        if let Some(oldest) = oldest {
+                            info!("Execution {} of flow {} replaces {}", execution_id, flow.id, oldest);
+                            self.cancellations.cancel(oldest);
+                        }
+                    }
+                }
+                slots
+                    .semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .map_err(|e| GhostFlowError::InternalError { message: e.to_string() })?
+            }
+        };
+
+        slots.running.lock().unwrap().push_back(execution_id);
+        Ok(Some(ExecutionSlot {
+            _permit: permit,
+            slots,
+            execution_id,
+        }))
+    }
+
+    /// Slots for `flow_id`, recreated when a redeploy changed the limit.
+    /// Executions already running keep their old slots until they finish.
+    fn slots(&self, flow_id: Uuid, limit: u32) -> Arc<FlowSlots> {
+        let mut flows = self.flows.lock().unwrap();
+        match flows.get(&flow_id) {
+            Some(slots) if slots.limit == limit => slots.clone(),
+            _ => {
+                let slots = Arc::new(FlowSlots {
+                    limit,
+                    semaphore: Arc::new(Semaphore::new(limit as usize)),
+                    running: Mutex::new(VecDeque::new()),
+                });
+                flows.insert(flow_id, slots.clone());
+                slots
+            }
+        }
+    }
+}
+
+/// Bounds how many executions a runtime runs at once across all flows.
+/// Executions over the limit wait in a priority queue: the highest priority
+/// is dispatched first when a slot frees up, and equal priorities keep
+/// arrival order. Running executions are never preempted. Clones share state.
+#[derive(Clone)]
+pub struct ExecutionDispatcher {
+    state: Arc<Mutex<DispatchState>>,
+}
+
+struct DispatchState {
+    max_concurrency: Option<usize>,
+    running: usize,
+    queue: BinaryHeap<Waiter>,
+    next_sequence: u64,
+}
+
+struct Waiter {
+    priority: i32,
+    sequence: u64,
+    slot: oneshot::Sender<DispatchSlot>,
+}
+
+impl Waiter {
+    fn key(&self) -> (i32, Reverse<u64>) {
+        (self.priority, Reverse(self.sequence))
+    }
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// A running execution's place under the runtime-wide limit. Dropping it
+/// hands the place to the next queued execution.
+pub struct DispatchSlot {
+    /// Taken when a slot offered to a waiter that gave up is discarded
+    state: Option<Arc<Mutex<DispatchState>>>,
+}
+
+impl Drop for DispatchSlot {
+    fn drop(&mut self) {
+        let Some(shared) = self.state.take() else {
+            return;
+        };
+        let mut state = shared.lock().unwrap();
+        while let Some(waiter) = state.queue.pop() {
+            let slot = DispatchSlot {
+                state: Some(shared.clone()),
+            };
+            match waiter.slot.send(slot) {
+                Ok(()) => return,
+                Err(mut unclaimed) => {
+                    unclaimed.state = None;
+                }
+            }
+        }
+        state.running -= 1;
+    }
+}
+
+impl ExecutionDispatcher {
+    /// `None` runs every execution as soon as it is admitted.
+    pub fn new(max_concurrency: Option<usize>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(DispatchState {
+                max_concurrency: max_concurrency.map(|limit| limit.max(1)),
+                running: 0,
+                queue: BinaryHeap::new(),
+                next_sequence: 0,
+            })),
+        }
+    }
+
+    /// Wait for a slot, queued by `priority` behind any executions already
+    /// waiting. `None` when there is no limit.
+    pub async fn acquire(&self, execution_id: Uuid, priority: i32) -> Result<Option<DispatchSlot>> {
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            let Some(limit) = state.max_concurrency else {
+                return Ok(None);
+            };
+            if state.running < limit && state.queue.is_empty() {
+                state.running += 1;
+                return Ok(Some(DispatchSlot {
+                    state: Some(self.state.clone()),
+                }));
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let sequence = state.next_sequence;
+            state.next_sequence += 1;
+            state.queue.push(Waiter {
+                priority,
+                sequence,
+                slot: sender,
+            });
+            info!(
+                "Execution {} queued with priority {} ({} waiting)",
+                execution_id,
+                priority,
+                state.queue.len()
+            );
+            receiver
+        };
+
+        receiver
+            .await
+            .map(Some)
+            .map_err(|e| GhostFlowError::InternalError { message: e.to_string() })
+    }
+
+    /// Executions waiting for a slot
+    pub fn queued(&self) -> usize {
+        self.state.lock().unwrap().queue.len()
+    }
+}
+
+impl Default for ExecutionDispatcher {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_abandoned_waiter_does_not_keep_its_slot() {
+        let dispatcher = ExecutionDispatcher::new(Some(1));
+        let running = dispatcher.acquire(Uuid::new_v4(), 0).await.unwrap();
+
+        // Gives up waiting before a slot frees up
+        let abandoned = tokio::time::timeout(Duration::from_millis(10), dispatcher.acquire(Uuid::new_v4(), 5)).await;
+        assert!(abandoned.is_err());
+
+        let waiting = {
+            let dispatcher = dispatcher.clone();
+            tokio::spawn(async move { dispatcher.acquire(Uuid::new_v4(), 0).await.unwrap().is_some() })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(running);
+        assert!(waiting.await.unwrap());
+        assert_eq!(dispatcher.queued(), 0);
+
+        // Both slots handed out above are gone again, so the next one is immediate
+        let next = tokio::time::timeout(Duration::from_millis(100), dispatcher.acquire(Uuid::new_v4(), 0)).await;
+        assert!(next.unwrap().unwrap().is_some());
+    }
+}
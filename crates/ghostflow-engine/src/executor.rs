@@ -1,29 +1,157 @@
 use async_trait::async_trait;
-use futures::future::join_all;
-use ghostflow_core::{GhostFlowError, Node, NodeRegistry, Result};
+use futures::future::{join_all, Either};
+use ghostflow_core::{
+    evaluate_condition, CredentialVault, ExecutionEvent, ExecutionStateStorage,
+    GhostFlowError, Node, NodeRegistry, NodeRunner, Result, Services,
+};
 use ghostflow_schema::{
-    ExecutionContext, ExecutionStatus, Flow, FlowExecution, NodeExecution, ExecutionTrigger,
-    ExecutionMetadata, ExecutionError, ErrorType,
+    ExecutionContext, ExecutionState, ExecutionStatus, Flow, FlowEdge, FlowExecution, FlowNode, NodeExecution,
+    NodeExecutionRecord, NodeValidationReport, ExecutionTrigger, ACTIVATIONS_PORT, ON_ERROR_PORT, ExecutionMetadata, ExecutionError, ErrorType,
 };
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use std::time::Instant;
-use tokio::sync::RwLock;
-use tracing::{error, info, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, Semaphore};
+use tracing::{error, info, info_span, warn, Instrument};
 use uuid::Uuid;
 
+use crate::cache::NodeOutputCache;
+use crate::flow_values::FlowValues;
+use crate::references::resolve_node_references;
+use crate::validation::{coerce_value, port_data_type, validate_input_ports, validate_parameters};
+
+/// The executor's registry and node type limits, which nodes that run other
+/// nodes reach through [`Services::nodes`].
+struct RegisteredNodes {
+    registry: Arc<dyn NodeRegistry>,
+    /// Caps on how many nodes of a type run at once, shared by every
+    /// execution of this executor and its clones.
+    limits: HashMap<String, Arc<Semaphore>>,
+}
+
+impl RegisteredNodes {
+    /// Hold a slot of `node_type` while one is free, when it is limited
+    async fn permit(&self, node_type: &str) -> Option<tokio::sync::SemaphorePermit<'_>> {
+        match self.limits.get(node_type) {
+            Some(slots) => slots.acquire().await.ok(),
+            None => None,
+        }
+    }
+}
+
+#[async_trait]
+impl NodeRunner for RegisteredNodes {
+    fn get_node(&self, node_type: &str) -> Option<Arc<dyn Node>> {
+        self.registry.get_node(node_type)
+    }
+
+    async fn run_node(&self, node: &dyn Node, context: ExecutionContext) -> Result<serde_json::Value> {
+        let definition = node.definition();
+        validate_parameters(&definition, &context.input)?;
+        validate_input_ports(&definition, &context.input)?;
+        node.validate(&context).await?;
+        let _permit = self.permit(&definition.id).await;
+        node.execute(context).await
+    }
+}
+
 #[derive(Clone)]
 pub struct FlowExecutor {
-    node_registry: Arc<dyn NodeRegistry>,
+    nodes: Arc<RegisteredNodes>,
     max_concurrent_nodes: usize,
+    output_cache: Arc<NodeOutputCache>,
+    state_storage: Option<Arc<dyn ExecutionStateStorage>>,
+    credential_vault: Option<Arc<dyn CredentialVault>>,
+    result_envelope: bool,
+    /// Flow of each execution currently running, shared by clones
+    running: Arc<std::sync::Mutex<HashMap<Uuid, Uuid>>>,
+    services: Services,
 }
 
 impl FlowExecutor {
     pub fn new(node_registry: Arc<dyn NodeRegistry>) -> Self {
         Self {
-            node_registry,
+            nodes: Arc::new(RegisteredNodes {
+                registry: node_registry,
+                limits: HashMap::new(),
+            }),
             max_concurrent_nodes: 10,
+            output_cache: Arc::new(NodeOutputCache::new()),
+            state_storage: None,
+            credential_vault: None,
+            result_envelope: false,
+            running: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            services: Services::default(),
         }
+        .with_nodes_in_services()
+    }
+
+    /// Let nodes that run other nodes, such as try/catch, run them through
+    /// this executor's registry and limits.
+    fn with_nodes_in_services(self) -> Self {
+        let nodes = Arc::downgrade(&self.nodes);
+        self.services.nodes.set(nodes);
+        self
+    }
+
+    /// Run at most `max_concurrent` nodes of `node_type` at a time across all
+    /// executions, e.g. to stay under an API's rate limit. Further nodes of
+    /// that type wait for a free slot; the wait counts towards their timeout.
+    pub fn with_node_type_limit(mut self, node_type: impl Into<String>, max_concurrent: usize) -> Self {
+        let mut limits = self.nodes.limits.clone();
+        limits.insert(node_type.into(), Arc::new(Semaphore::new(max_concurrent.max(1))));
+        self.nodes = Arc::new(RegisteredNodes {
+            registry: self.nodes.registry.clone(),
+            limits,
+        });
+        self.with_nodes_in_services()
+    }
+
+    /// Save the progress of every execution to `storage` before each batch
+    /// of nodes, so it can be resumed with [`Self::resume_execution`].
+    pub fn with_state_storage(mut self, storage: Arc<dyn ExecutionStateStorage>) -> Self {
+        self.state_storage = Some(storage);
+        self
+    }
+
+    /// Share `services` with the nodes and the API, e.g. so the API sees the
+    /// events of every execution. Give the nodes the same [`Services`].
+    pub fn with_services(mut self, services: Services) -> Self {
+        self.services = services;
+        self.with_nodes_in_services()
+    }
+
+    pub fn services(&self) -> &Services {
+        &self.services
+    }
+
+    /// Look up the credentials a flow lists in `secrets` in `vault`, so node
+    /// parameters can reference them as `{{flow.secrets.<name>}}`.
+    pub fn with_credential_vault(mut self, vault: Arc<dyn CredentialVault>) -> Self {
+        self.credential_vault = Some(vault);
+        self
+    }
+
+    /// Wrap every node's output in a uniform envelope, so downstream nodes
+    /// can branch on the outcome the same way for any node type:
+    /// `{ok, data, error: {code, message}, meta: {status, duration_ms}}`,
+    /// with the node's own output under `data`. A failed node without error
+    /// edges no longer fails the flow; its envelope with `ok: false` is
+    /// passed downstream instead.
+    pub fn with_result_envelope(mut self) -> Self {
+        self.result_envelope = true;
+        self
+    }
+
+    /// Outputs kept for nodes with a `cache_ttl_ms`, shared by clones of
+    /// this executor.
+    pub fn output_cache(&self) -> &NodeOutputCache {
+        &self.output_cache
+    }
+
+    /// Id of the flow `execution_id` belongs to, while it is running.
+    pub fn running_flow(&self, execution_id: &Uuid) -> Option<Uuid> {
+        self.running.lock().unwrap().get(execution_id).copied()
     }
 
     pub async fn execute_flow(
@@ -32,11 +160,121 @@ impl FlowExecutor {
         input_data: serde_json::Value,
         trigger: ExecutionTrigger,
     ) -> Result<FlowExecution> {
-        let execution_id = Uuid::new_v4();
+        self.execute_flow_with_id(Uuid::new_v4(), flow, input_data, trigger).await
+    }
+
+    /// Like [`Self::execute_flow`], with an execution id chosen by the caller
+    /// so it can register per-execution state (e.g. a waiting webhook
+    /// response) before any node runs.
+    pub async fn execute_flow_with_id(
+        &self,
+        execution_id: Uuid,
+        flow: &Flow,
+        input_data: serde_json::Value,
+        trigger: ExecutionTrigger,
+    ) -> Result<FlowExecution> {
+        self.run(execution_id, flow, input_data, trigger, None, None).await
+    }
+
+    /// Run `flow` again with the inputs each node recorded in `original`,
+    /// instead of resolving them from upstream outputs. With
+    /// `stub_non_deterministic`, nodes that are not deterministic for their
+    /// recorded input are not executed; their recorded output is used, so
+    /// the replay reproduces the original run exactly as long as the rest of
+    /// the flow is pure.
+    pub async fn replay_execution(
+        &self,
+        flow: &Flow,
+        original: &FlowExecution,
+        stub_non_deterministic: bool,
+    ) -> Result<FlowExecution> {
+        let mut replay = Replay::default();
+        for record in &original.node_records {
+            if let Some(input) = &record.input {
+                replay.inputs.insert(record.node_id.clone(), input.clone());
+            }
+            let recorded_input = record.input.as_ref().unwrap_or(&serde_json::Value::Null);
+            let stub = stub_non_deterministic
+                && flow
+                    .nodes
+                    .get(&record.node_id)
+                    .and_then(|n| self.nodes.registry.get_node(&n.node_type))
+                    .is_some_and(|node| !node.is_deterministic_for(recorded_input));
+            if let (true, Some(output)) = (stub, &record.output) {
+                replay.outputs.insert(record.node_id.clone(), output.clone());
+            }
+        }
+
+        if original.flow_version != flow.version {
+            warn!(
+                "Replaying execution {} (flow version {}) against flow version {}",
+                original.id, original.flow_version, flow.version
+            );
+        }
+
+        let trigger = ExecutionTrigger {
+            trigger_type: "replay".to_string(),
+            source: Some(original.id.to_string()),
+            metadata: HashMap::from([("replay_of".to_string(), serde_json::json!(original.id))]),
+            dry_run: false,
+            priority: original.trigger.priority,
+            environment: original.trigger.environment.clone(),
+        };
+        self.run(Uuid::new_v4(), flow, original.input_data.clone(), trigger, Some(&replay), None)
+            .await
+    }
+
+    /// Continue an execution from saved state, e.g. after a restart. Nodes
+    /// that finished before the state was saved are not run again, so
+    /// non-deterministic ones keep the output they produced; the rest run as
+    /// usual, starting with the batch that was in progress.
+    pub async fn resume_execution(&self, flow: &Flow, state: ExecutionState) -> Result<FlowExecution> {
+        if state.flow_id != flow.id {
+            return Err(GhostFlowError::ValidationError {
+                message: format!("Execution {} belongs to flow {}, not {}", state.execution_id, state.flow_id, flow.id),
+            });
+        }
+        if state.flow_version != flow.version {
+            warn!(
+                "Resuming execution {} (flow version {}) against flow version {}",
+                state.execution_id, state.flow_version, flow.version
+            );
+        }
+
+        info!(
+            "Resuming execution {} with {} node(s) left",
+            state.execution_id,
+            state.pending_nodes.len()
+        );
+        let trigger = state.trigger.clone();
+        let input_data = state.input_data.clone();
+        self.run(state.execution_id, flow, input_data, trigger, None, Some(state))
+            .await
+    }
+
+    async fn run(
+        &self,
+        execution_id: Uuid,
+        flow: &Flow,
+        input_data: serde_json::Value,
+        trigger: ExecutionTrigger,
+        replay: Option<&Replay>,
+        resume: Option<ExecutionState>,
+    ) -> Result<FlowExecution> {
         let start_time = Instant::now();
-        
-        info!("Starting flow execution {} for flow {}", execution_id, flow.id);
 
+        // Root span for the run; every node span is a child of it, so each
+        // log line written during the run can be traced to the execution
+        let span = info_span!(
+            "flow_execution",
+            execution.id = %execution_id,
+            flow.id = %flow.id,
+            flow.version = %flow.version,
+            execution.status = tracing::field::Empty,
+        );
+        span.in_scope(|| info!("Starting flow execution {} for flow {}", execution_id, flow.id));
+
+        let environment = trigger.environment.clone().unwrap_or_else(|| "local".to_string());
         let mut execution = FlowExecution {
             id: execution_id,
             flow_id: flow.id,
@@ -47,148 +285,791 @@ impl FlowExecutor {
             output_data: None,
             error: None,
             node_executions: HashMap::new(),
+            node_records: Vec::new(),
+            validation_report: Vec::new(),
             started_at: chrono::Utc::now(),
             completed_at: None,
             execution_time_ms: None,
             metadata: ExecutionMetadata {
                 executor_id: "default".to_string(),
-                environment: "local".to_string(),
+                environment,
                 correlation_id: None,
                 trace_id: Some(execution_id.to_string()),
                 span_id: None,
             },
         };
 
-        match self.execute_flow_internal(flow, &input_data, &execution_id).await {
+        if execution.trigger.dry_run {
+            return self
+                .dry_run(flow, &input_data, execution, start_time)
+                .instrument(span)
+                .await;
+        }
+
+        let cancellations = &self.services.cancellations;
+        cancellations.register(execution_id);
+        self.running.lock().unwrap().insert(execution_id, flow.id);
+        let deadline = flow.max_duration_ms.map(|ms| Deadline {
+            at: start_time + Duration::from_millis(ms),
+            timeout_ms: ms,
+        });
+        let mut state = match resume {
+            Some(state) => {
+                execution.started_at = state.started_at;
+                let variables = &self.services.variables;
+                for (name, value) in &state.variables {
+                    variables.set(execution_id, name.clone(), value.clone());
+                }
+                state
+            }
+            None => ExecutionState::new(&execution),
+        };
+        let mut values = FlowValues::default();
+        let outcome = match self.flow_values(flow, &execution.trigger).await {
+            Ok(loaded) => {
+                values = loaded;
+                self.execute_flow_internal(flow, &input_data, &execution_id, &values, deadline, replay, &mut state)
+                    .instrument(span.clone())
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        cancellations.remove(execution_id);
+        self.running.lock().unwrap().remove(&execution_id);
+        self.services.variables.clear(execution_id);
+        if let Some(storage) = &self.state_storage {
+            if let Err(e) = storage.delete_state(&execution_id).await {
+                warn!("Could not remove state of finished execution {}: {}", execution_id, e);
+            }
+        }
+        execution.node_records = state.node_records;
+
+        let _entered = span.enter();
+        match outcome {
             Ok(result) => {
                 execution.status = ExecutionStatus::Completed;
-                execution.output_data = Some(result);
+                execution.output_data = Some(values.redact(result));
                 execution.completed_at = Some(chrono::Utc::now());
                 execution.execution_time_ms = Some(start_time.elapsed().as_millis() as u64);
                 
                 info!("Flow execution {} completed successfully", execution_id);
             }
+            Err(error @ GhostFlowError::Cancelled { .. }) => {
+                execution.status = ExecutionStatus::Cancelled;
+                execution.error = Some(ExecutionError {
+                    error_type: ErrorType::UserError,
+                    message: error.to_string(),
+                    details: None,
+                    retryable: false,
+                });
+                execution.completed_at = Some(chrono::Utc::now());
+                execution.execution_time_ms = Some(start_time.elapsed().as_millis() as u64);
+
+                warn!("Flow execution {} was cancelled", execution_id);
+            }
             Err(error) => {
                 execution.status = ExecutionStatus::Failed;
                 execution.error = Some(ExecutionError {
-                    error_type: ErrorType::InternalError,
-                    message: error.to_string(),
+                    error_type: match error {
+                        GhostFlowError::TimeoutError { .. } => ErrorType::TimeoutError,
+                        _ => ErrorType::InternalError,
+                    },
+                    message: values.redact_text(&error.to_string()),
                     details: None,
                     retryable: true,
                 });
                 execution.completed_at = Some(chrono::Utc::now());
                 execution.execution_time_ms = Some(start_time.elapsed().as_millis() as u64);
                 
-                error!("Flow execution {} failed: {}", execution_id, error);
+                error!("Flow execution {} failed: {}", execution_id, execution.error.as_ref().unwrap().message);
             }
         }
 
+        span.record("execution.status", status_label(&execution.status));
         Ok(execution)
     }
 
-    async fn execute_flow_internal(
+    /// Flow parameters and secrets for an execution started by `trigger`,
+    /// in the environment it names.
+    async fn flow_values(&self, flow: &Flow, trigger: &ExecutionTrigger) -> Result<FlowValues> {
+        let environment = match &trigger.environment {
+            Some(name) => Some(self.services.environments.get(name).ok_or_else(|| {
+                GhostFlowError::NotFoundError {
+                    resource_type: "environment".to_string(),
+                    id: name.clone(),
+                }
+            })?),
+            None => None,
+        };
+        FlowValues::load(flow, self.credential_vault.as_deref(), environment.as_ref()).await
+    }
+
+    /// Signal a running execution to stop. Nodes that are waiting observe the
+    /// cancellation immediately; no further nodes are started. Returns false
+    /// if the execution is not running.
+    pub fn cancel_execution(&self, execution_id: Uuid) -> bool {
+        self.services.cancellations.cancel(execution_id)
+    }
+
+    async fn dry_run(
+        &self,
+        flow: &Flow,
+        input_data: &serde_json::Value,
+        mut execution: FlowExecution,
+        start_time: Instant,
+    ) -> Result<FlowExecution> {
+        info!("Dry run {} for flow {}", execution.id, flow.id);
+
+        let report = match self.flow_values(flow, &execution.trigger).await {
+            Ok(values) => self.validate_nodes(flow, input_data, &execution.id, &values).await,
+            Err(e) => Err(e),
+        };
+        match report {
+            Ok(report) => {
+                let invalid = report.iter().filter(|r| !r.valid).count();
+                if invalid == 0 {
+                    execution.status = ExecutionStatus::Completed;
+                } else {
+                    execution.status = ExecutionStatus::Failed;
+                    execution.error = Some(ExecutionError {
+                        error_type: ErrorType::ValidationError,
+                        message: format!("Dry run found problems in {} node(s)", invalid),
+                        details: None,
+                        retryable: false,
+                    });
+                }
+                execution.validation_report = report;
+            }
+            Err(error) => {
+                execution.status = ExecutionStatus::Failed;
+                execution.error = Some(ExecutionError {
+                    error_type: ErrorType::ValidationError,
+                    message: error.to_string(),
+                    details: None,
+                    retryable: false,
+                });
+            }
+        }
+
+        execution.completed_at = Some(chrono::Utc::now());
+        execution.execution_time_ms = Some(start_time.elapsed().as_millis() as u64);
+        Ok(execution)
+    }
+
+    /// Walk the DAG in execution order, resolving and validating each node's
+    /// parameters exactly as a real run would, without calling `execute`.
+    /// Every node gets a report; a failing node does not stop the walk.
+    async fn validate_nodes(
         &self,
         flow: &Flow,
         input_data: &serde_json::Value,
         execution_id: &Uuid,
-    ) -> Result<serde_json::Value> {
-        // Build execution graph
+        values: &FlowValues,
+    ) -> Result<Vec<NodeValidationReport>> {
         let execution_order = self.build_execution_order(flow)?;
         let mut node_results: HashMap<String, serde_json::Value> = HashMap::new();
         let mut variables = HashMap::new();
-        
-        // Add input data to variables
         variables.insert("input".to_string(), input_data.clone());
 
-        // Execute nodes in topological order
-        for node_batch in execution_order {
-            let node_ids: Vec<String> = node_batch.clone();
-            let futures: Vec<_> = node_batch
-                .into_iter()
-                .map(|node_id| {
-                    let flow_node = flow.nodes.get(&node_id).unwrap();
+        let mut report = Vec::new();
+        for node_id in execution_order.into_iter().flatten() {
+            let flow_node = flow.nodes.get(&node_id).unwrap();
+            let mut errors = Vec::new();
+
+            // Validated with secrets revealed, reported with them as written
+            let input = self
+                .resolve_node_input(flow, flow_node, &node_results, &variables, values, true)
+                .unwrap_or_else(|e| {
+                    errors.push(values.redact_text(&e.to_string()));
+                    serde_json::Value::Null
+                });
+            let parameters = self
+                .resolve_node_input(flow, flow_node, &node_results, &variables, values, false)
+                .map(|params| values.redact(params))
+                .unwrap_or(serde_json::Value::Null);
+
+            match self.nodes.registry.get_node(&flow_node.node_type) {
+                None => errors.push(format!("Unknown node type: {}", flow_node.node_type)),
+                Some(node) if errors.is_empty() => {
+                    let definition = node.definition();
                     let context = ExecutionContext {
                         execution_id: *execution_id,
                         flow_id: flow.id,
                         node_id: node_id.clone(),
-                        input: self.resolve_node_input(flow_node, &node_results, &variables),
+                        input: input.clone(),
                         variables: variables.clone(),
-                        secrets: HashMap::new(), // TODO: integrate with secrets manager
+                        secrets: values.context_secrets(),
                         artifacts: HashMap::new(),
+                        node_outputs: node_results.clone(),
+                        attempt: 1,
                     };
-                    
-                    self.execute_node(flow_node.node_type.clone(), context)
-                })
+                    let checked = validate_parameters(&definition, &input)
+                        .and_then(|()| validate_input_ports(&definition, &input));
+                    let checked = match checked {
+                        Ok(()) => node.validate(&context).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = checked {
+                        errors.push(values.redact_text(&e.to_string()));
+                    }
+                }
+                Some(_) => {}
+            }
+
+            // Nothing ran, so downstream references resolve to null
+            node_results.insert(node_id.clone(), serde_json::Value::Null);
+            report.push(NodeValidationReport {
+                node_id,
+                node_type: flow_node.node_type.clone(),
+                valid: errors.is_empty(),
+                parameters,
+                errors,
+            });
+        }
+
+        Ok(report)
+    }
+
+    async fn execute_flow_internal(
+        &self,
+        flow: &Flow,
+        input_data: &serde_json::Value,
+        execution_id: &Uuid,
+        values: &FlowValues,
+        deadline: Option<Deadline>,
+        replay: Option<&Replay>,
+        state: &mut ExecutionState,
+    ) -> Result<serde_json::Value> {
+        // Build execution graph
+        let execution_order = self.build_execution_order(flow)?;
+        let mut variables = HashMap::new();
+        
+        // Add input data to variables
+        variables.insert("input".to_string(), input_data.clone());
+        variables.extend(self.services.variables.snapshot(*execution_id));
+
+        // Nodes that finished before a resume are not run again
+        let finished: HashSet<String> = state.node_records.iter().map(|r| r.node_id.clone()).collect();
+
+        // The flow's output is that of the last node to run
+        let mut final_output = state
+            .node_records
+            .iter()
+            .rev()
+            .find_map(|r| r.output.clone())
+            .unwrap_or(serde_json::Value::Null);
+
+        // Execute nodes in topological order
+        for (batch_index, node_batch) in execution_order.iter().enumerate() {
+            state.pending_nodes = execution_order[batch_index..]
+                .iter()
+                .flatten()
+                .filter(|id| !finished.contains(*id))
+                .cloned()
                 .collect();
+            self.checkpoint(state, *execution_id).await;
+
+            if self.services.cancellations.is_cancelled(*execution_id) {
+                return Err(GhostFlowError::Cancelled { execution_id: *execution_id });
+            }
+            if let Some(deadline) = deadline.filter(Deadline::has_passed) {
+                warn!("Flow execution {} exceeded its {}ms deadline", execution_id, deadline.timeout_ms);
+                return Err(deadline.error());
+            }
+
+            let mut node_ids = Vec::with_capacity(node_batch.len());
+            let mut futures = Vec::with_capacity(node_batch.len());
+            for node_id in node_batch.iter().filter(|id| !finished.contains(*id)).cloned() {
+                let flow_node = flow.nodes.get(&node_id).unwrap();
+                if !self.is_activated(flow, &node_id, &state.node_outputs, &state.failed_nodes)? {
+                    info!("Skipping node {}: no incoming edge is active", node_id);
+                    state.node_records.push(skipped_record(&node_id));
+                    continue;
+                }
+                // The node gets secrets revealed; its record keeps the
+                // references as written, so secrets never reach storage
+                let recorded_input = replay.and_then(|r| r.inputs.get(&node_id));
+                let (input, logged_input) = match recorded_input {
+                    Some(input) => (values.resolve(&node_id, input.clone(), true)?, input.clone()),
+                    None => {
+                        let resolve = |reveal_secrets| {
+                            self.resolve_node_input(flow, flow_node, &state.node_outputs, &variables, values, reveal_secrets)
+                        };
+                        let (mut input, mut logged_input) = (resolve(true)?, resolve(false)?);
+                        if self.accepts_multiple_activations(flow_node) {
+                            let activations = self.activations(flow, &node_id, state)?;
+                            for params in [&mut input, &mut logged_input] {
+                                if let Some(params) = params.as_object_mut() {
+                                    params.insert(ACTIVATIONS_PORT.to_string(), activations.clone());
+                                }
+                            }
+                        }
+                        (input, values.redact(logged_input))
+                    }
+                };
+
+                if let Some(output) = replay.and_then(|r| r.outputs.get(&node_id)) {
+                    info!("Replaying recorded output for non-deterministic node {}", node_id);
+                    let record = replayed_record(&node_id, logged_input, values.redact(output.clone()));
+                    futures.push(Either::Right(std::future::ready((Ok(output.clone()), record))));
+                    node_ids.push(node_id);
+                    continue;
+                }
+
+                let context = ExecutionContext {
+                    execution_id: *execution_id,
+                    flow_id: flow.id,
+                    node_id: node_id.clone(),
+                    input,
+                    variables: variables.clone(),
+                    secrets: values.context_secrets(),
+                    artifacts: HashMap::new(),
+                    node_outputs: state.node_outputs.clone(),
+                    attempt: 1,
+                };
+
+                futures.push(Either::Left(self.execute_node(flow_node, context, logged_input, values, deadline)));
+                node_ids.push(node_id);
+            }
 
             // Execute nodes in parallel within the batch
             let batch_results = join_all(futures).await;
             
-            for (i, result) in batch_results.into_iter().enumerate() {
+            for (i, (result, mut record)) in batch_results.into_iter().enumerate() {
                 let node_id = &node_ids[i];
+                let result = match result {
+                    Err(error)
+                        if self.result_envelope
+                            && !matches!(error, GhostFlowError::Cancelled { .. })
+                            && !has_error_edges(flow, node_id) =>
+                    {
+                        warn!(
+                            "Node {} failed, passing its error downstream: {}",
+                            node_id,
+                            values.redact_text(&error.to_string())
+                        );
+                        let envelope = result_envelope(serde_json::Value::Null, Some(&error), &record, values);
+                        record.output = Some(envelope.clone());
+                        Ok(envelope)
+                    }
+                    other => other,
+                };
+                state.node_records.push(record);
                 match result {
                     Ok(output) => {
-                        node_results.insert(node_id.clone(), output);
+                        final_output = output.clone();
+                        state.node_outputs.insert(node_id.clone(), output);
+                    }
+                    Err(error) if !matches!(error, GhostFlowError::Cancelled { .. }) && has_error_edges(flow, node_id) => {
+                        warn!(
+                            "Node {} failed, continuing on its error edges: {}",
+                            node_id,
+                            values.redact_text(&error.to_string())
+                        );
+                        let caught = serde_json::json!({ ON_ERROR_PORT: values.redact(error.error_object(node_id)) });
+                        state.node_outputs.insert(node_id.clone(), caught);
+                        state.failed_nodes.push(node_id.clone());
                     }
                     Err(error) => {
-                        error!("Node {} failed: {}", node_id, error);
+                        error!("Node {} failed: {}", node_id, values.redact_text(&error.to_string()));
                         return Err(error);
                     }
                 }
             }
-        }
 
-        // Determine final output
-        let final_output = if let Some(last_node_id) = flow.nodes.keys().last() {
-            node_results.get(last_node_id).cloned().unwrap_or(serde_json::Value::Null)
-        } else {
-            serde_json::Value::Null
-        };
+            // Make flow-scoped state set by this batch visible to later nodes
+            variables.extend(self.services.variables.snapshot(*execution_id));
+        }
 
         Ok(final_output)
     }
 
+    /// Save `state` to the state storage, if one is configured. A failed
+    /// save only costs the ability to resume, so it does not fail the run.
+    async fn checkpoint(&self, state: &mut ExecutionState, execution_id: Uuid) {
+        let Some(storage) = &self.state_storage else {
+            return;
+        };
+        state.variables = self.services.variables.snapshot(execution_id);
+        state.updated_at = chrono::Utc::now();
+        if let Err(e) = storage.save_state(state).await {
+            warn!("Could not save state of execution {}: {}", execution_id, e);
+        }
+    }
+
+    /// Run one node and record its timing and outcome alongside the result.
+    /// The record holds `logged_input` and has secret values redacted.
     async fn execute_node(
         &self,
-        node_type: String,
+        flow_node: &FlowNode,
         context: ExecutionContext,
-    ) -> Result<serde_json::Value> {
-        let node = self.node_registry
-            .get_node(&node_type)
-            .ok_or_else(|| GhostFlowError::NodeExecutionError {
+        logged_input: serde_json::Value,
+        values: &FlowValues,
+        deadline: Option<Deadline>,
+    ) -> (Result<serde_json::Value>, NodeExecutionRecord) {
+        let started_at = chrono::Utc::now();
+        let started = Instant::now();
+
+        let span = info_span!(
+            "node",
+            execution.id = %context.execution_id,
+            node.id = %flow_node.id,
+            node.type = %flow_node.node_type,
+            node.attempt = tracing::field::Empty,
+            node.status = tracing::field::Empty,
+        );
+        let (result, attempts) = self
+            .run_node(flow_node, context, values, deadline)
+            .instrument(span.clone())
+            .await;
+
+        let elapsed = started.elapsed();
+        let mut record = NodeExecutionRecord {
+            node_id: flow_node.id.clone(),
+            status: match &result {
+                Ok(_) => ExecutionStatus::Completed,
+                Err(GhostFlowError::Cancelled { .. }) => ExecutionStatus::Cancelled,
+                Err(_) => ExecutionStatus::Failed,
+            },
+            started_at,
+            finished_at: Some(started_at + chrono::Duration::from_std(elapsed).unwrap_or_default()),
+            duration_ms: elapsed.as_millis() as u64,
+            attempts,
+            error: result.as_ref().err().map(|e| values.redact_text(&e.to_string())),
+            input: Some(logged_input),
+            output: None,
+        };
+        let result = match result {
+            Ok(output) if self.result_envelope => Ok(result_envelope(output, None, &record, values)),
+            other => other,
+        };
+        record.output = result.as_ref().ok().map(|output| values.redact(output.clone()));
+        span.record("node.attempt", attempts);
+        span.record("node.status", status_label(&record.status));
+
+        (result, record)
+    }
+
+    /// Validate and execute a node, retrying failed executions according to
+    /// the node's `retry_config` when the node supports it. Each attempt is
+    /// bounded by the node's `timeout_ms` and the flow deadline, whichever
+    /// comes first. Returns the result with the number of attempts made,
+    /// zero when the output came from the cache.
+    async fn run_node(
+        &self,
+        flow_node: &FlowNode,
+        context: ExecutionContext,
+        values: &FlowValues,
+        deadline: Option<Deadline>,
+    ) -> (Result<serde_json::Value>, u32) {
+        let node_type = flow_node.node_type.clone();
+        let Some(node) = self.nodes.registry.get_node(&node_type) else {
+            let error = GhostFlowError::NodeExecutionError {
                 node_id: context.node_id.clone(),
                 message: format!("Unknown node type: {}", node_type),
-            })?;
+            };
+            return (Err(error), 0);
+        };
 
-        // Validate node inputs
-        node.validate(&context).await?;
+        let events = &self.services.events;
+        let execution_id = context.execution_id;
+        let node_id = context.node_id.clone();
+        let started = Instant::now();
 
-        // Execute the node
-        let result = node.execute(context).await?;
-        
-        Ok(result)
+        events.publish(ExecutionEvent::NodeStarted {
+            execution_id,
+            node_id: node_id.clone(),
+            node_type: node_type.clone(),
+        });
+
+        let definition = node.definition();
+
+        // Deterministic nodes with a TTL reuse the output of an earlier run
+        // on identical input; it already passed validation then
+        let cache = match flow_node.cache_ttl_ms {
+            Some(_) if !node.is_deterministic_for(&context.input) => {
+                warn!("Ignoring cache_ttl_ms on non-deterministic node {}", node_id);
+                None
+            }
+            Some(ttl) => Some((
+                NodeOutputCache::key(&node_type, &definition.version, &context.input),
+                Duration::from_millis(ttl),
+            )),
+            None => None,
+        };
+        if let Some(output) = cache.as_ref().and_then(|(key, _)| self.output_cache.get(key)) {
+            info!("Node {} served from cache", node_id);
+            events.publish(ExecutionEvent::NodeCompleted {
+                execution_id,
+                node_id,
+                node_type,
+                duration_ms: started.elapsed().as_millis() as u64,
+            });
+            return (Ok(output), 0);
+        }
+
+        // Check declared parameters, run node-specific validation, then execute
+        let checked = validate_parameters(&definition, &context.input)
+            .and_then(|()| validate_input_ports(&definition, &context.input));
+        let mut attempts = 0;
+        let result = match checked {
+            Ok(()) => match node.validate(&context).await {
+                Ok(()) => {
+                    let retry = flow_node.retry_config.as_ref().filter(|_| node.supports_retry());
+                    let max_attempts = retry.map_or(1, |r| r.max_attempts.max(1));
+                    let mut delay_ms = retry.map_or(0, |r| r.delay_ms);
+                    loop {
+                        attempts += 1;
+                        let node_deadline = flow_node.timeout_ms.map(|ms| Deadline {
+                            at: Instant::now() + Duration::from_millis(ms),
+                            timeout_ms: ms,
+                        });
+                        let limit = match (node_deadline, deadline) {
+                            (Some(own), Some(flow)) => Some(if own.at <= flow.at { own } else { flow }),
+                            (own, flow) => own.or(flow),
+                        };
+                        let run = async {
+                            // Held for this attempt only, not across retry delays
+                            let _permit = self.nodes.permit(&node_type).await;
+                            let context = ExecutionContext {
+                                attempt: attempts,
+                                ..context.clone()
+                            };
+                            node.execute(context).await
+                        };
+                        let attempt = match limit {
+                            Some(limit) => tokio::time::timeout_at(limit.at.into(), run)
+                                .await
+                                .unwrap_or_else(|_| Err(limit.error())),
+                            None => run.await,
+                        };
+                        let out_of_time = deadline.as_ref().is_some_and(Deadline::has_passed);
+                        match attempt {
+                            Err(e)
+                                if attempts < max_attempts
+                                    && !out_of_time
+                                    && !matches!(e, GhostFlowError::Cancelled { .. }) =>
+                            {
+                                warn!(
+                                    "Node {} attempt {} failed, retrying: {}",
+                                    node_id,
+                                    attempts,
+                                    values.redact_text(&e.to_string())
+                                );
+                                // Never wait past the flow deadline for an attempt that
+                                // could not run anyway
+                                let mut pause = Duration::from_millis(delay_ms);
+                                if let Some(flow) = deadline {
+                                    pause = pause.min(flow.at.saturating_duration_since(Instant::now()));
+                                }
+                                tokio::time::sleep(pause).await;
+                                if deadline.as_ref().is_some_and(Deadline::has_passed) {
+                                    break Err(e);
+                                }
+                                if let Some(retry) = retry {
+                                    delay_ms = ((delay_ms as f64 * retry.backoff_multiplier) as u64)
+                                        .min(retry.max_delay_ms);
+                                }
+                            }
+                            other => break other,
+                        }
+                    }
+                }
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+
+        if let (Ok(output), Some((key, ttl))) = (&result, cache) {
+            self.output_cache.insert(key, output.clone(), ttl);
+        }
+
+        match &result {
+            Ok(_) => events.publish(ExecutionEvent::NodeCompleted {
+                execution_id,
+                node_id,
+                node_type,
+                duration_ms: started.elapsed().as_millis() as u64,
+            }),
+            Err(e) => events.publish(ExecutionEvent::NodeFailed {
+                execution_id,
+                node_id,
+                node_type,
+                error: values.redact_text(&e.to_string()),
+            }),
+        }
+
+        (result, attempts)
+    }
+
+    /// Whether `node_id` should run. Entry nodes always run; any other node
+    /// runs when at least one incoming edge comes from a node that ran and
+    /// either has no condition or a condition that holds for that node's
+    /// output. Skipped nodes never run, so everything only reachable through
+    /// them is skipped as well. Error edges are only active when their
+    /// source failed, and their conditions see the error object; a failed
+    /// node's other edges stay inactive.
+    fn is_activated(
+        &self,
+        flow: &Flow,
+        node_id: &str,
+        node_results: &HashMap<String, serde_json::Value>,
+        failed_nodes: &[String],
+    ) -> Result<bool> {
+        let mut incoming = flow.edges.iter().filter(|e| e.target_node == node_id).peekable();
+        if incoming.peek().is_none() {
+            return Ok(true);
+        }
+
+        for edge in incoming {
+            if self.is_edge_active(edge, node_id, node_results, failed_nodes)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Whether `edge` into `node_id` carries a value; see [`Self::is_activated`].
+    fn is_edge_active(
+        &self,
+        edge: &FlowEdge,
+        node_id: &str,
+        node_results: &HashMap<String, serde_json::Value>,
+        failed_nodes: &[String],
+    ) -> Result<bool> {
+        let Some(output) = node_results.get(&edge.source_node) else {
+            return Ok(false);
+        };
+        if edge.is_error_edge() != failed_nodes.contains(&edge.source_node) {
+            return Ok(false);
+        }
+        let output = if edge.is_error_edge() {
+            output.get(ON_ERROR_PORT).unwrap_or(output)
+        } else {
+            output
+        };
+        match edge.condition.as_deref().map(str::trim) {
+            None | Some("") => Ok(true),
+            Some(condition) => evaluate_condition(condition, output).map_err(|e| {
+                GhostFlowError::NodeExecutionError {
+                    node_id: node_id.to_string(),
+                    message: format!("Edge '{}': {}", edge.id, e),
+                }
+            }),
+        }
+    }
+
+    fn accepts_multiple_activations(&self, flow_node: &FlowNode) -> bool {
+        self.nodes.registry
+            .get_node(&flow_node.node_type)
+            .is_some_and(|node| node.accepts_multiple_activations())
+    }
+
+    /// One entry per upstream node that finished and feeds `node_id` over an
+    /// active edge, ordered by when it finished. See [`ACTIVATIONS_PORT`].
+    fn activations(&self, flow: &Flow, node_id: &str, state: &ExecutionState) -> Result<serde_json::Value> {
+        let mut arrived = Vec::new();
+        for edge in flow.edges.iter().filter(|e| e.target_node == node_id) {
+            if !self.is_edge_active(edge, node_id, &state.node_outputs, &state.failed_nodes)? {
+                continue;
+            }
+            let output = &state.node_outputs[&edge.source_node];
+            let value = edge
+                .source_port
+                .as_ref()
+                .and_then(|port| output.get(port))
+                .unwrap_or(output)
+                .clone();
+            let finished_at = state
+                .node_records
+                .iter()
+                .rev()
+                .find(|r| r.node_id == edge.source_node)
+                .and_then(|r| r.finished_at)
+                .unwrap_or_else(chrono::Utc::now);
+            arrived.push((finished_at, edge.source_node.clone(), value));
+        }
+        arrived.sort_by_key(|(finished_at, _, _)| *finished_at);
+
+        let Some(first) = arrived.first().map(|(finished_at, _, _)| *finished_at) else {
+            return Ok(serde_json::Value::Array(Vec::new()));
+        };
+        Ok(arrived
+            .into_iter()
+            .map(|(finished_at, source, value)| {
+                serde_json::json!({
+                    "node_id": source,
+                    "value": value,
+                    "after_ms": (finished_at - first).num_milliseconds(),
+                })
+            })
+            .collect())
     }
 
     fn resolve_node_input(
         &self,
+        flow: &Flow,
         flow_node: &ghostflow_schema::FlowNode,
         node_results: &HashMap<String, serde_json::Value>,
-        variables: &HashMap<String, serde_json::Value>,
-    ) -> serde_json::Value {
-        // Simple parameter resolution - in a real implementation, this would be more sophisticated
-        let mut resolved_params = flow_node.parameters.clone();
-        
-        // TODO: Implement proper parameter interpolation
-        // - Support for {{$node.output}} syntax
-        // - Variable substitution
-        // - Expression evaluation
-        
-        serde_json::Value::Object(
-            resolved_params
-                .into_iter()
-                .map(|(k, v)| (k, v))
-                .collect()
-        )
+        _variables: &HashMap<String, serde_json::Value>,
+        values: &FlowValues,
+        reveal_secrets: bool,
+    ) -> Result<serde_json::Value> {
+        // Flow values are substituted first, into the node's own parameters
+        // only, so upstream data cannot smuggle in a secret reference
+        let params = serde_json::Value::Object(flow_node.parameters.clone().into_iter().collect());
+        let mut resolved_params = match values.resolve(&flow_node.id, params, reveal_secrets)? {
+            serde_json::Value::Object(params) => params,
+            _ => serde_json::Map::new(),
+        };
+
+        // Values arriving over edges fill the target port unless the node
+        // already sets a parameter of that name. A source port selects one
+        // field of the upstream output when it exists.
+        for edge in flow.edges.iter().filter(|e| e.target_node == flow_node.id) {
+            let (Some(target_port), Some(output)) =
+                (edge.target_port.as_ref(), node_results.get(&edge.source_node))
+            else {
+                continue;
+            };
+            let value = edge
+                .source_port
+                .as_ref()
+                .and_then(|port| output.get(port))
+                .unwrap_or(output)
+                .clone();
+            let value = if edge.coerce {
+                self.coerce_edge_value(edge, flow_node, target_port, value)?
+            } else {
+                value
+            };
+            resolved_params.entry(target_port.clone()).or_insert(value);
+        }
+
+        resolve_node_references(&flow_node.id, serde_json::Value::Object(resolved_params), node_results)
+    }
+
+    /// Convert a value arriving over a `coerce` edge to the data type of the
+    /// port it feeds. Ports the node does not declare are left alone.
+    fn coerce_edge_value(
+        &self,
+        edge: &ghostflow_schema::FlowEdge,
+        flow_node: &FlowNode,
+        target_port: &str,
+        value: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let Some(node) = self.nodes.registry.get_node(&flow_node.node_type) else {
+            return Ok(value);
+        };
+        let Some(data_type) = port_data_type(&node.definition(), target_port) else {
+            return Ok(value);
+        };
+        coerce_value(value, &data_type).map_err(|e| match e {
+            GhostFlowError::ValidationError { message } => GhostFlowError::ValidationError {
+                message: format!("Edge '{}' into {}.{}: {}", edge.id, flow_node.id, target_port, message),
+            },
+            other => other,
+        })
     }
 
     fn build_execution_order(&self, flow: &Flow) -> Result<Vec<Vec<String>>> {
@@ -260,4 +1141,100 @@ impl FlowExecutor {
         
         Ok(result)
     }
-}
\ No newline at end of file
+}
+
+/// Per-node inputs, and outputs for stubbed nodes, recorded by an earlier
+/// execution. See [`FlowExecutor::replay_execution`].
+#[derive(Debug, Default)]
+struct Replay {
+    inputs: HashMap<String, serde_json::Value>,
+    outputs: HashMap<String, serde_json::Value>,
+}
+
+/// A point in time a node attempt or a whole execution must finish by,
+/// with the configured limit it came from for error reporting.
+#[derive(Debug, Clone, Copy)]
+struct Deadline {
+    at: Instant,
+    timeout_ms: u64,
+}
+
+impl Deadline {
+    fn has_passed(&self) -> bool {
+        Instant::now() >= self.at
+    }
+
+    fn error(&self) -> GhostFlowError {
+        GhostFlowError::TimeoutError {
+            timeout_ms: self.timeout_ms,
+        }
+    }
+}
+
+/// The status as it appears in JSON, e.g. `completed`, for span attributes.
+fn status_label(status: &ExecutionStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// The envelope around a node's result; see
+/// [`FlowExecutor::with_result_envelope`].
+fn result_envelope(
+    data: serde_json::Value,
+    error: Option<&GhostFlowError>,
+    record: &NodeExecutionRecord,
+    values: &FlowValues,
+) -> serde_json::Value {
+    serde_json::json!({
+        "ok": error.is_none(),
+        "data": data,
+        "error": error.map(|e| serde_json::json!({
+            "code": e.code(),
+            "message": values.redact_text(&e.to_string()),
+        })),
+        "meta": {
+            "status": status_label(&record.status),
+            "duration_ms": record.duration_ms,
+        },
+    })
+}
+
+/// Whether a failure of `node_id` is handled by the flow rather than
+/// failing it.
+fn has_error_edges(flow: &Flow, node_id: &str) -> bool {
+    flow.edges.iter().any(|e| e.source_node == node_id && e.is_error_edge())
+}
+
+fn skipped_record(node_id: &str) -> NodeExecutionRecord {
+    let now = chrono::Utc::now();
+    NodeExecutionRecord {
+        node_id: node_id.to_string(),
+        status: ExecutionStatus::Skipped,
+        started_at: now,
+        finished_at: Some(now),
+        duration_ms: 0,
+        attempts: 0,
+        error: None,
+        input: None,
+        output: None,
+    }
+}
+
+/// Record for a node whose output was taken from a recording instead of
+/// executing it; zero attempts marks that it did not run.
+fn replayed_record(node_id: &str, input: serde_json::Value, output: serde_json::Value) -> NodeExecutionRecord {
+    let now = chrono::Utc::now();
+    NodeExecutionRecord {
+        node_id: node_id.to_string(),
+        status: ExecutionStatus::Completed,
+        started_at: now,
+        finished_at: Some(now),
+        duration_ms: 0,
+        attempts: 0,
+        error: None,
+        input: Some(input),
+        output: Some(output),
+    }
+}
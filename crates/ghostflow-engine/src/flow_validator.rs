@@ -0,0 +1,464 @@
+use ghostflow_core::NodeRegistry;
+use ghostflow_schema::{Flow, FlowNode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The flow cannot run as written
+    Error,
+    /// The flow runs, but probably not as intended
+    Warning,
+}
+
+/// One finding about a flow. `code` is stable and meant for machines, e.g.
+/// `unknown_node_type`; `message` is meant for people.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: String,
+    pub message: String,
+    #[serde(default)]
+    pub node_id: Option<String>,
+    #[serde(default)]
+    pub edge_id: Option<String>,
+}
+
+impl Diagnostic {
+    fn error(code: &str, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            code: code.to_string(),
+            message,
+            node_id: None,
+            edge_id: None,
+        }
+    }
+
+    fn warning(code: &str, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(code, message)
+        }
+    }
+
+    fn on_node(mut self, node_id: &str) -> Self {
+        self.node_id = Some(node_id.to_string());
+        self
+    }
+
+    fn on_edge(mut self, edge_id: &str) -> Self {
+        self.edge_id = Some(edge_id.to_string());
+        self
+    }
+}
+
+/// Everything [`FlowValidator`] found. The flow is valid when there are no
+/// errors; warnings alone do not stop it from being deployed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowValidationReport {
+    pub valid: bool,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl FlowValidationReport {
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Warning)
+    }
+}
+
+/// Static checks of a flow against the node types in a registry, without
+/// running anything.
+///
+/// Errors: `dangling_edge`, `cycle`, `unknown_node_type` and
+/// `missing_required_parameter`. Warnings: `unreachable_node`,
+/// `unused_flow_variable` and, when the known credentials are supplied,
+/// `missing_credential`.
+pub struct FlowValidator<'a> {
+    registry: &'a dyn NodeRegistry,
+    credentials: Option<HashSet<String>>,
+}
+
+impl<'a> FlowValidator<'a> {
+    pub fn new(registry: &'a dyn NodeRegistry) -> Self {
+        Self {
+            registry,
+            credentials: None,
+        }
+    }
+
+    /// Ids or names of the credentials the flow can use. Without them,
+    /// credential references are not checked.
+    pub fn with_known_credentials(mut self, credentials: HashSet<String>) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    pub fn validate(&self, flow: &Flow) -> FlowValidationReport {
+        let mut diagnostics = Vec::new();
+        // Iterate nodes in a stable order so reports do not shuffle
+        let mut nodes: Vec<&FlowNode> = flow.nodes.values().collect();
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+        self.check_edges(flow, &mut diagnostics);
+        self.check_cycles(flow, &mut diagnostics);
+        self.check_nodes(flow, &nodes, &mut diagnostics);
+        self.check_reachability(flow, &nodes, &mut diagnostics);
+        self.check_variables(&nodes, &mut diagnostics);
+        self.check_credentials(flow, &nodes, &mut diagnostics);
+
+        FlowValidationReport {
+            valid: !diagnostics.iter().any(|d| d.severity == Severity::Error),
+            diagnostics,
+        }
+    }
+
+    fn check_edges(&self, flow: &Flow, diagnostics: &mut Vec<Diagnostic>) {
+        for edge in &flow.edges {
+            for (end, node_id) in [("source", &edge.source_node), ("target", &edge.target_node)] {
+                if !flow.nodes.contains_key(node_id) {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            "dangling_edge",
+                            format!("Edge '{}' {} node '{}' does not exist", edge.id, end, node_id),
+                        )
+                        .on_edge(&edge.id),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Nodes left over after a topological sort sit on, or behind, a cycle.
+    fn check_cycles(&self, flow: &Flow, diagnostics: &mut Vec<Diagnostic>) {
+        let edges: Vec<_> = flow
+            .edges
+            .iter()
+            .filter(|e| flow.nodes.contains_key(&e.source_node) && flow.nodes.contains_key(&e.target_node))
+            .collect();
+        let mut in_degree: HashMap<&str, usize> = flow.nodes.keys().map(|id| (id.as_str(), 0)).collect();
+        for edge in &edges {
+            *in_degree.get_mut(edge.target_node.as_str()).unwrap() += 1;
+        }
+
+        let mut queue: VecDeque<&str> = in_degree.iter().filter(|(_, d)| **d == 0).map(|(id, _)| *id).collect();
+        while let Some(node_id) = queue.pop_front() {
+            for edge in edges.iter().filter(|e| e.source_node == node_id) {
+                let degree = in_degree.get_mut(edge.target_node.as_str()).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(&edge.target_node);
+                }
+            }
+        }
+
+        let mut remaining: Vec<&str> = in_degree.into_iter().filter(|(_, d)| *d > 0).map(|(id, _)| id).collect();
+        remaining.sort_unstable();
+        for node_id in remaining {
+            diagnostics.push(
+                Diagnostic::error("cycle", format!("Node '{}' is part of or depends on a cycle", node_id))
+                    .on_node(node_id),
+            );
+        }
+    }
+
+    fn check_nodes(&self, flow: &Flow, nodes: &[&FlowNode], diagnostics: &mut Vec<Diagnostic>) {
+        for flow_node in nodes {
+            let Some(node) = self.registry.get_node(&flow_node.node_type) else {
+                diagnostics.push(
+                    Diagnostic::error(
+                        "unknown_node_type",
+                        format!("Node '{}' has unknown type '{}'", flow_node.id, flow_node.node_type),
+                    )
+                    .on_node(&flow_node.id),
+                );
+                continue;
+            };
+
+            // Edges fill parameters named by their target port at run time
+            let fed: HashSet<&str> = flow
+                .edges
+                .iter()
+                .filter(|e| e.target_node == flow_node.id)
+                .filter_map(|e| e.target_port.as_deref())
+                .collect();
+            for param in node.definition().parameters {
+                let set = flow_node.parameters.get(&param.name).is_some_and(|v| !v.is_null());
+                if param.required && param.default_value.is_none() && !set && !fed.contains(param.name.as_str()) {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            "missing_required_parameter",
+                            format!("Node '{}' is missing required parameter '{}'", flow_node.id, param.name),
+                        )
+                        .on_node(&flow_node.id),
+                    );
+                }
+            }
+        }
+    }
+
+    /// A node with no edges at all never receives data and nothing depends
+    /// on it; in a flow of one node that is expected.
+    fn check_reachability(&self, flow: &Flow, nodes: &[&FlowNode], diagnostics: &mut Vec<Diagnostic>) {
+        if nodes.len() < 2 {
+            return;
+        }
+        for flow_node in nodes {
+            let connected = flow
+                .edges
+                .iter()
+                .any(|e| e.source_node == flow_node.id || e.target_node == flow_node.id);
+            if !connected {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "unreachable_node",
+                        format!("Node '{}' is not connected to the rest of the flow", flow_node.id),
+                    )
+                    .on_node(&flow_node.id),
+                );
+            }
+        }
+    }
+
+    /// Variables stored with `set_variable` that no `get_variable` reads.
+    fn check_variables(&self, nodes: &[&FlowNode], diagnostics: &mut Vec<Diagnostic>) {
+        let name = |node: &FlowNode| node.parameters.get("name").and_then(Value::as_str).map(str::to_string);
+        let read: HashSet<String> = nodes
+            .iter()
+            .filter(|n| n.node_type == "get_variable")
+            .filter_map(|n| name(n))
+            .collect();
+
+        for flow_node in nodes.iter().filter(|n| n.node_type == "set_variable") {
+            let Some(variable) = name(flow_node) else { continue };
+            if !read.contains(&variable) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "unused_flow_variable",
+                        format!("Variable '{}' is set by '{}' but never read", variable, flow_node.id),
+                    )
+                    .on_node(&flow_node.id),
+                );
+            }
+        }
+    }
+
+    fn check_credentials(&self, flow: &Flow, nodes: &[&FlowNode], diagnostics: &mut Vec<Diagnostic>) {
+        let Some(known) = &self.credentials else { return };
+
+        for secret in flow.secrets.iter().filter(|s| !known.contains(*s)) {
+            diagnostics.push(Diagnostic::warning(
+                "missing_credential",
+                format!("Flow secret '{}' does not match any stored credential", secret),
+            ));
+        }
+        for flow_node in nodes {
+            let reference = flow_node
+                .parameters
+                .get("credential")
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty());
+            if let Some(reference) = reference.filter(|r| !known.contains(*r)) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "missing_credential",
+                        format!("Node '{}' references credential '{}', which does not exist", flow_node.id, reference),
+                    )
+                    .on_node(&flow_node.id),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use ghostflow_core::{BasicNodeRegistry, Node, Result};
+    use ghostflow_schema::node::ParameterType;
+    use ghostflow_schema::*;
+    use serde_json::json;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    /// Node type with one required `url` parameter.
+    struct FetchNode;
+
+    #[async_trait]
+    impl Node for FetchNode {
+        fn definition(&self) -> NodeDefinition {
+            NodeDefinition {
+                id: "fetch".to_string(),
+                name: "Fetch".to_string(),
+                description: "Test node".to_string(),
+                category: NodeCategory::Action,
+                version: "1.0.0".to_string(),
+                inputs: vec![],
+                outputs: vec![],
+                parameters: vec![NodeParameter {
+                    name: "url".to_string(),
+                    display_name: "URL".to_string(),
+                    description: None,
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                }],
+                icon: None,
+                color: None,
+            }
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+            Ok(context.input)
+        }
+    }
+
+    fn registry() -> BasicNodeRegistry {
+        let mut registry = BasicNodeRegistry::new();
+        for node_type in ["fetch", "set_variable", "get_variable"] {
+            registry.register_node(node_type.to_string(), Arc::new(FetchNode)).unwrap();
+        }
+        registry
+    }
+
+    fn node(id: &str, node_type: &str, parameters: Value) -> FlowNode {
+        FlowNode {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            name: id.to_string(),
+            description: None,
+            parameters: serde_json::from_value(parameters).unwrap(),
+            position: NodePosition { x: 0.0, y: 0.0 },
+            retry_config: None,
+            timeout_ms: None,
+            cache_ttl_ms: None,
+            node_version: None,
+        }
+    }
+
+    fn edge(source: &str, target: &str) -> FlowEdge {
+        FlowEdge {
+            id: format!("{}-{}", source, target),
+            source_node: source.to_string(),
+            target_node: target.to_string(),
+            source_port: None,
+            target_port: None,
+            condition: None,
+            coerce: false,
+        }
+    }
+
+    fn flow(nodes: Vec<FlowNode>, edges: Vec<FlowEdge>) -> Flow {
+        Flow {
+            id: Uuid::new_v4(),
+            name: "Validated".to_string(),
+            description: None,
+            version: "1".to_string(),
+            nodes: nodes.into_iter().map(|n| (n.id.clone(), n)).collect(),
+            edges,
+            triggers: vec![],
+            parameters: HashMap::new(),
+            secrets: vec![],
+            max_duration_ms: None,
+            max_concurrent_executions: None,
+            overflow_policy: OverflowPolicy::Queue,
+            metadata: FlowMetadata {
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                created_by: "test".to_string(),
+                tags: vec![],
+                category: None,
+                workspace_id: "default".to_string(),
+            },
+        }
+    }
+
+    fn codes(report: &FlowValidationReport) -> Vec<&str> {
+        report.diagnostics.iter().map(|d| d.code.as_str()).collect()
+    }
+
+    #[test]
+    fn test_unreachable_node_is_a_warning_only() {
+        let url = json!({ "url": "https://example.com" });
+        let flow = flow(
+            vec![node("a", "fetch", url.clone()), node("b", "fetch", url.clone()), node("orphan", "fetch", url)],
+            vec![edge("a", "b")],
+        );
+
+        let report = FlowValidator::new(&registry()).validate(&flow);
+
+        assert!(report.valid);
+        assert_eq!(report.errors().count(), 0);
+        let warning = report.warnings().next().unwrap();
+        assert_eq!(warning.code, "unreachable_node");
+        assert_eq!(warning.node_id.as_deref(), Some("orphan"));
+    }
+
+    #[test]
+    fn test_structural_problems_are_errors() {
+        let url = json!({ "url": "https://example.com" });
+        let mut fed = edge("a", "c");
+        fed.target_port = Some("url".to_string());
+        let flow = flow(
+            vec![
+                node("a", "fetch", url.clone()),
+                node("b", "fetch", json!({})),
+                node("c", "fetch", json!({})),
+                node("d", "teleport", json!({})),
+                node("x", "fetch", url.clone()),
+                node("y", "fetch", url),
+            ],
+            vec![edge("a", "b"), fed, edge("c", "d"), edge("x", "y"), edge("y", "x"), edge("a", "ghost")],
+        );
+
+        let report = FlowValidator::new(&registry()).validate(&flow);
+
+        assert!(!report.valid);
+        assert_eq!(
+            codes(&report),
+            vec!["dangling_edge", "cycle", "cycle", "missing_required_parameter", "unknown_node_type"]
+        );
+        assert_eq!(report.diagnostics[0].edge_id.as_deref(), Some("a-ghost"));
+        // `c` gets its url over the edge, so only `b` is missing it
+        assert_eq!(report.diagnostics[3].node_id.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn test_unused_variables_and_missing_credentials_are_warnings() {
+        let flow = {
+            let mut flow = flow(
+                vec![
+                    node("store", "set_variable", json!({ "name": "count", "url": "-" })),
+                    node("stash", "set_variable", json!({ "name": "unused", "url": "-" })),
+                    node("load", "get_variable", json!({ "name": "count", "url": "-" })),
+                    node("upload", "fetch", json!({ "url": "s3://bucket", "credential": "aws-prod" })),
+                ],
+                vec![edge("store", "load"), edge("load", "upload"), edge("stash", "upload")],
+            );
+            flow.secrets = vec!["slack-token".to_string()];
+            flow
+        };
+
+        let unchecked = FlowValidator::new(&registry()).validate(&flow);
+        assert_eq!(codes(&unchecked), vec!["unused_flow_variable"]);
+
+        let known = HashSet::from(["slack-token".to_string()]);
+        let report = FlowValidator::new(&registry()).with_known_credentials(known).validate(&flow);
+        assert!(report.valid);
+        assert_eq!(codes(&report), vec!["unused_flow_variable", "missing_credential"]);
+        assert_eq!(report.diagnostics[1].node_id.as_deref(), Some("upload"));
+    }
+}
@@ -0,0 +1,304 @@
+use ghostflow_core::{CredentialVault, Environment, GhostFlowError, Result};
+use ghostflow_schema::Flow;
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Written in place of secret values in records, logs and events
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Workspace searched when a flow names a secret by credential name
+const SECRETS_WORKSPACE: &str = "default";
+
+fn flow_value_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"\{\{\s*flow\.(params|secrets)\.([A-Za-z0-9_-]+)((?:\.[^\s.{}]+)*)\s*\}\}").unwrap()
+    })
+}
+
+/// The values a flow's node parameters can reference: `{{flow.params.<name>}}`
+/// from the flow's declared parameters and `{{flow.secrets.<name>}}` from the
+/// credentials listed in its `secrets`. A secret is an object of the
+/// credential's fields, e.g. `{{flow.secrets.github.token}}`; a credential
+/// with a single field can be referenced without naming it.
+#[derive(Debug, Clone, Default)]
+pub struct FlowValues {
+    params: HashMap<String, Value>,
+    secrets: HashMap<String, HashMap<String, String>>,
+    /// Every secret field value, longest first so overlapping values are
+    /// redacted whole
+    secret_values: Vec<String>,
+}
+
+impl FlowValues {
+    /// Parameters take their default values, overlaid with the variables
+    /// of `environment`; a required parameter left without a value is an
+    /// error. Secrets are looked up in `vault` by credential id, then by
+    /// name, using the credential `environment` maps the secret to if any.
+    /// Without a vault, referencing a secret fails.
+    pub async fn load(
+        flow: &Flow,
+        vault: Option<&dyn CredentialVault>,
+        environment: Option<&Environment>,
+    ) -> Result<Self> {
+        let mut values = Self::default();
+        for (name, parameter) in &flow.parameters {
+            if let Some(value) = &parameter.default_value {
+                values.params.insert(name.clone(), value.clone());
+            }
+        }
+        if let Some(environment) = environment {
+            values.params.extend(environment.variables.clone());
+        }
+        if let Some((name, _)) = flow
+            .parameters
+            .iter()
+            .find(|(name, parameter)| parameter.required && !values.params.contains_key(*name))
+        {
+            return Err(GhostFlowError::ValidationError {
+                message: format!("Flow parameter '{}' is required but has no value", name),
+            });
+        }
+
+        let Some(vault) = vault else {
+            return Ok(values);
+        };
+        for name in &flow.secrets {
+            let credential_name = environment
+                .and_then(|e| e.credentials.get(name))
+                .unwrap_or(name);
+            let credential = match vault.retrieve(credential_name).await? {
+                Some(credential) => Some(credential),
+                None => vault
+                    .list(SECRETS_WORKSPACE)
+                    .await?
+                    .into_iter()
+                    .find(|c| &c.name == credential_name),
+            };
+            let Some(mut credential) = credential else {
+                return Err(GhostFlowError::ConfigurationError {
+                    message: format!(
+                        "Credential '{}' for flow secret '{}' was not found in the credential store",
+                        credential_name, name
+                    ),
+                });
+            };
+            if credential.encrypted {
+                for value in credential.data.values_mut() {
+                    *value = vault.decrypt(value).await?;
+                }
+            }
+            values.add_secret(name.clone(), credential.data);
+        }
+        Ok(values)
+    }
+
+    pub fn with_param(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.params.insert(name.into(), value);
+        self
+    }
+
+    pub fn with_secret(mut self, name: impl Into<String>, fields: HashMap<String, String>) -> Self {
+        self.add_secret(name.into(), fields);
+        self
+    }
+
+    fn add_secret(&mut self, name: String, fields: HashMap<String, String>) {
+        self.secret_values
+            .extend(fields.values().filter(|v| !v.is_empty()).cloned());
+        self.secret_values.sort_by_key(|v| std::cmp::Reverse(v.len()));
+        self.secret_values.dedup();
+        self.secrets.insert(name, fields);
+    }
+
+    /// Secret fields for [`ghostflow_schema::ExecutionContext::secrets`],
+    /// keyed `<secret>.<field>`.
+    pub fn context_secrets(&self) -> HashMap<String, String> {
+        self.secrets
+            .iter()
+            .flat_map(|(name, fields)| {
+                fields
+                    .iter()
+                    .map(move |(field, value)| (format!("{}.{}", name, field), value.clone()))
+            })
+            .collect()
+    }
+
+    /// Replace `{{flow.params...}}` references in `params`, and with
+    /// `reveal_secrets` also `{{flow.secrets...}}`; otherwise secret
+    /// references are left as written, which is what gets recorded. Like
+    /// node references, a string that is exactly one reference keeps the
+    /// value's JSON type and embedded references render as text.
+    pub fn resolve(&self, node_id: &str, params: Value, reveal_secrets: bool) -> Result<Value> {
+        match params {
+            Value::String(text) => self.resolve_string(node_id, text, reveal_secrets),
+            Value::Array(items) => items
+                .into_iter()
+                .map(|item| self.resolve(node_id, item, reveal_secrets))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            Value::Object(map) => map
+                .into_iter()
+                .map(|(key, value)| Ok((key, self.resolve(node_id, value, reveal_secrets)?)))
+                .collect::<Result<serde_json::Map<_, _>>>()
+                .map(Value::Object),
+            other => Ok(other),
+        }
+    }
+
+    fn resolve_string(&self, node_id: &str, text: String, reveal_secrets: bool) -> Result<Value> {
+        let pattern = flow_value_pattern();
+        let Some(captures) = pattern.captures(&text) else {
+            return Ok(Value::String(text));
+        };
+        if captures.get(0).unwrap().as_str() == text.trim() {
+            return self
+                .lookup(node_id, &captures, reveal_secrets)
+                .map(|value| value.unwrap_or(Value::String(text.clone())));
+        }
+
+        let mut failure = None;
+        let rendered = pattern.replace_all(&text, |captures: &Captures| {
+            match self.lookup(node_id, captures, reveal_secrets) {
+                Ok(Some(Value::String(s))) => s,
+                Ok(Some(value)) => value.to_string(),
+                Ok(None) => captures[0].to_string(),
+                Err(e) => {
+                    failure.get_or_insert(e);
+                    String::new()
+                }
+            }
+        });
+
+        match failure {
+            Some(error) => Err(error),
+            None => Ok(Value::String(rendered.into_owned())),
+        }
+    }
+
+    /// The referenced value, or `None` for a secret that is not revealed.
+    fn lookup(&self, node_id: &str, captures: &Captures, reveal_secrets: bool) -> Result<Option<Value>> {
+        let (kind, name) = (&captures[1], &captures[2]);
+        let missing = |what: String| GhostFlowError::NodeExecutionError {
+            node_id: node_id.to_string(),
+            message: format!("Parameter references {}", what),
+        };
+
+        let value = if kind == "params" {
+            self.params
+                .get(name)
+                .cloned()
+                .ok_or_else(|| missing(format!("flow parameter '{}', which has no value", name)))?
+        } else {
+            let fields = self.secrets.get(name).ok_or_else(|| {
+                missing(format!("secret '{}', which is not one of the flow's secrets", name))
+            })?;
+            if !reveal_secrets {
+                return Ok(None);
+            }
+            if captures[3].is_empty() && fields.len() == 1 {
+                return Ok(fields.values().next().cloned().map(Value::String));
+            }
+            serde_json::to_value(fields)?
+        };
+
+        let mut current = &value;
+        for segment in captures[3].split('.').filter(|s| !s.is_empty()) {
+            let next = match current {
+                Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+                Value::Object(map) => map.get(segment),
+                _ => None,
+            };
+            current = next.ok_or_else(|| {
+                missing(format!("flow.{}.{}{}, which does not exist", kind, name, &captures[3]))
+            })?;
+        }
+        Ok(Some(current.clone()))
+    }
+
+    /// `value` with every secret value in its strings replaced by [`REDACTED`].
+    pub fn redact(&self, value: Value) -> Value {
+        if self.secret_values.is_empty() {
+            return value;
+        }
+        match value {
+            Value::String(text) => Value::String(self.redact_text(&text)),
+            Value::Array(items) => Value::Array(items.into_iter().map(|item| self.redact(item)).collect()),
+            Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key, self.redact(value)))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    pub fn redact_text(&self, text: &str) -> String {
+        let mut redacted = text.to_string();
+        for secret in &self.secret_values {
+            if redacted.contains(secret.as_str()) {
+                redacted = redacted.replace(secret.as_str(), REDACTED);
+            }
+        }
+        redacted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn values() -> FlowValues {
+        FlowValues::default()
+            .with_param("region", json!("eu-west"))
+            .with_param("limits", json!({ "retries": 3 }))
+            .with_secret("slack", HashMap::from([("token".to_string(), "xoxb-123".to_string())]))
+            .with_secret(
+                "db",
+                HashMap::from([
+                    ("user".to_string(), "ghost".to_string()),
+                    ("password".to_string(), "hunter22".to_string()),
+                ]),
+            )
+    }
+
+    #[test]
+    fn test_resolves_params_and_reveals_secrets_on_request() {
+        let params = json!({
+            "retries": "{{ flow.params.limits.retries }}",
+            "url": "https://{{flow.params.region}}.example.com",
+            "token": "{{flow.secrets.slack}}",
+            "dsn": "postgres://{{flow.secrets.db.user}}:{{flow.secrets.db.password}}@db",
+        });
+
+        let revealed = values().resolve("node", params.clone(), true).unwrap();
+        assert_eq!(revealed["retries"], 3);
+        assert_eq!(revealed["url"], "https://eu-west.example.com");
+        assert_eq!(revealed["token"], "xoxb-123");
+        assert_eq!(revealed["dsn"], "postgres://ghost:hunter22@db");
+
+        let recorded = values().resolve("node", params, false).unwrap();
+        assert_eq!(recorded["url"], "https://eu-west.example.com");
+        assert_eq!(recorded["token"], "{{flow.secrets.slack}}");
+        assert_eq!(recorded["dsn"], "postgres://{{flow.secrets.db.user}}:{{flow.secrets.db.password}}@db");
+    }
+
+    #[test]
+    fn test_unknown_references_fail() {
+        for reference in ["{{flow.params.nope}}", "{{flow.secrets.nope}}", "{{flow.secrets.db.port}}"] {
+            assert!(values().resolve("node", json!(reference), true).is_err(), "{}", reference);
+        }
+    }
+
+    #[test]
+    fn test_redacts_secret_values() {
+        let redacted = values().redact(json!({
+            "error": "auth failed for token xoxb-123",
+            "nested": ["hunter22", 5],
+        }));
+        assert_eq!(redacted, json!({ "error": "auth failed for token [REDACTED]", "nested": ["[REDACTED]", 5] }));
+    }
+}
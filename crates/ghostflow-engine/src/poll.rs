@@ -0,0 +1,286 @@
+use chrono::{DateTime, Utc};
+use ghostflow_core::{GhostFlowError, Result};
+use ghostflow_schema::TriggerType;
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Shortest interval a poll trigger may use. The runtime checks for due
+/// triggers this often, so anything shorter would not poll faster anyway.
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Longest back-off honoured from a `Retry-After` header
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(3600);
+
+/// State of one `TriggerType::Poll` trigger: requests the endpoint and
+/// reports what changed since the previous poll. The first poll only records
+/// a baseline, so data that existed before the flow was deployed does not
+/// start it.
+#[derive(Debug)]
+pub struct PollTrigger {
+    client: Client,
+    url: String,
+    headers: HashMap<String, String>,
+    interval: Duration,
+    items: Option<String>,
+    cursor: Option<String>,
+    /// Keys of the records in the last response, or the hash of the whole
+    /// body when no `items` expression is set
+    seen: Option<HashSet<String>>,
+    last_body: Value,
+    /// Set by a `429 Too Many Requests`; no poll goes out before it
+    not_before: Option<DateTime<Utc>>,
+}
+
+impl PollTrigger {
+    /// `None` for triggers that are not polls.
+    pub fn from_trigger(trigger: &TriggerType) -> Result<Option<Self>> {
+        let TriggerType::Poll { url, interval_secs, items, cursor, headers } = trigger else {
+            return Ok(None);
+        };
+        if url.is_empty() {
+            return Err(GhostFlowError::ValidationError {
+                message: "Poll trigger needs a url".to_string(),
+            });
+        }
+        for expression in [items, cursor].into_iter().flatten() {
+            compile(expression)?;
+        }
+
+        Ok(Some(Self {
+            client: Client::new(),
+            url: url.clone(),
+            headers: headers.clone(),
+            interval: Duration::from_secs(*interval_secs).max(MIN_POLL_INTERVAL),
+            items: items.clone(),
+            cursor: cursor.clone(),
+            seen: None,
+            last_body: Value::Null,
+            not_before: None,
+        }))
+    }
+
+    /// When the poll after one made at `now` is due.
+    pub fn next_poll(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let next = now + chrono::Duration::from_std(self.interval).unwrap_or_else(|_| chrono::Duration::zero());
+        self.not_before.map_or(next, |not_before| next.max(not_before))
+    }
+
+    /// Request the endpoint once. Returns the flow input when something
+    /// changed: `{items, response}` for new records, or `{previous,
+    /// response}` when the whole body is compared.
+    pub async fn poll(&mut self) -> Result<Option<Value>> {
+        if self.not_before.is_some_and(|t| Utc::now() < t) {
+            return Ok(None);
+        }
+
+        let mut request = self.client.get(&self.url).timeout(self.interval);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await.map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let wait = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(self.interval)
+                .min(MAX_RETRY_AFTER);
+            warn!("Poll of {} was rate limited; backing off for {:?}", self.url, wait);
+            self.not_before = Some(Utc::now() + chrono::Duration::from_std(wait).unwrap_or_else(|_| chrono::Duration::zero()));
+            return Ok(None);
+        }
+        self.not_before = None;
+        if !response.status().is_success() {
+            return Err(GhostFlowError::NetworkError(format!(
+                "Poll of {} returned HTTP {}",
+                self.url,
+                response.status().as_u16()
+            )));
+        }
+
+        let text = response.text().await.map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+        let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+        self.observe(body)
+    }
+
+    /// Compare `body` with the previous response and remember it.
+    fn observe(&mut self, body: Value) -> Result<Option<Value>> {
+        let delta = match &self.items {
+            Some(items) => {
+                let records = match search(items, &body)? {
+                    Value::Array(records) => records,
+                    Value::Null => Vec::new(),
+                    other => vec![other],
+                };
+                let keyed = records
+                    .into_iter()
+                    .map(|record| Ok((self.record_key(&record)?, record)))
+                    .collect::<Result<Vec<_>>>()?;
+                let keys: HashSet<String> = keyed.iter().map(|(key, _)| key.clone()).collect();
+
+                let delta = self.seen.as_ref().and_then(|seen| {
+                    let new: Vec<Value> = keyed
+                        .into_iter()
+                        .filter(|(key, _)| !seen.contains(key))
+                        .map(|(_, record)| record)
+                        .collect();
+                    (!new.is_empty()).then(|| json!({ "items": new, "response": body }))
+                });
+                self.seen = Some(keys);
+                delta
+            }
+            None => {
+                let hash = HashSet::from([hash(&body)]);
+                let changed = self.seen.as_ref().is_some_and(|seen| *seen != hash);
+                let previous = std::mem::replace(&mut self.last_body, body.clone());
+                self.seen = Some(hash);
+                changed.then(|| json!({ "previous": previous, "response": body }))
+            }
+        };
+
+        if delta.is_some() {
+            info!("Poll of {} found new data", self.url);
+        }
+        Ok(delta)
+    }
+
+    fn record_key(&self, record: &Value) -> Result<String> {
+        match &self.cursor {
+            Some(cursor) => match search(cursor, record)? {
+                Value::String(key) => Ok(key),
+                other => Ok(other.to_string()),
+            },
+            None => Ok(hash(record)),
+        }
+    }
+}
+
+fn compile(expression: &str) -> Result<jmespath::Expression<'static>> {
+    jmespath::compile(expression).map_err(|e| GhostFlowError::ValidationError {
+        message: format!("Invalid JMESPath expression '{}': {}", expression, e),
+    })
+}
+
+fn search(expression: &str, data: &Value) -> Result<Value> {
+    let result = compile(expression)?
+        .search(data.clone())
+        .map_err(|e| GhostFlowError::ValidationError {
+            message: format!("Failed to evaluate '{}': {}", expression, e),
+        })?;
+    Ok(serde_json::to_value(&*result)?)
+}
+
+fn hash(value: &Value) -> String {
+    hex::encode(Sha256::digest(value.to_string().as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn poll_trigger(url: String, items: Option<&str>, cursor: Option<&str>) -> PollTrigger {
+        PollTrigger::from_trigger(&TriggerType::Poll {
+            url,
+            interval_secs: 30,
+            items: items.map(String::from),
+            cursor: cursor.map(String::from),
+            headers: HashMap::from([("Authorization".to_string(), "Bearer t0ken".to_string())]),
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    async fn respond_once(server: &MockServer, body: Value) {
+        Mock::given(method("GET"))
+            .and(path("/alerts"))
+            .and(header("authorization", "Bearer t0ken"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .up_to_n_times(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_only_new_records_start_the_flow() {
+        let server = MockServer::start().await;
+        let first = json!({ "data": [{ "id": 1, "level": 12 }, { "id": 2, "level": 7 }] });
+        let second = json!({ "data": [{ "id": 2, "level": 7 }, { "id": 3, "level": 15 }] });
+        respond_once(&server, first.clone()).await;
+        respond_once(&server, first).await;
+        respond_once(&server, second).await;
+        let mut trigger = poll_trigger(format!("{}/alerts", server.uri()), Some("data"), Some("id"));
+
+        assert_eq!(trigger.poll().await.unwrap(), None, "first poll is the baseline");
+        assert_eq!(trigger.poll().await.unwrap(), None, "unchanged response");
+
+        let delta = trigger.poll().await.unwrap().unwrap();
+        assert_eq!(delta["items"], json!([{ "id": 3, "level": 15 }]));
+        assert_eq!(delta["response"]["data"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_whole_body_hash_detects_change() {
+        let server = MockServer::start().await;
+        respond_once(&server, json!({ "status": "green" })).await;
+        respond_once(&server, json!({ "status": "green" })).await;
+        respond_once(&server, json!({ "status": "red" })).await;
+        let mut trigger = poll_trigger(format!("{}/alerts", server.uri()), None, None);
+
+        assert_eq!(trigger.poll().await.unwrap(), None);
+        assert_eq!(trigger.poll().await.unwrap(), None);
+        let delta = trigger.poll().await.unwrap().unwrap();
+        assert_eq!(delta, json!({ "previous": { "status": "green" }, "response": { "status": "red" } }));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_defers_next_poll() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "120"))
+            .mount(&server)
+            .await;
+        let mut trigger = poll_trigger(format!("{}/alerts", server.uri()), None, None);
+
+        let now = Utc::now();
+        assert_eq!(trigger.poll().await.unwrap(), None);
+        assert!(trigger.next_poll(now) >= now + chrono::Duration::seconds(119));
+
+        // Polls before the back-off ends do not reach the server
+        assert_eq!(trigger.poll().await.unwrap(), None);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_interval_is_clamped_and_expressions_checked() {
+        let trigger = PollTrigger::from_trigger(&TriggerType::Poll {
+            url: "http://localhost/feed".to_string(),
+            interval_secs: 1,
+            items: None,
+            cursor: None,
+            headers: HashMap::new(),
+        })
+        .unwrap()
+        .unwrap();
+        let now = Utc::now();
+        assert_eq!(trigger.next_poll(now), now + chrono::Duration::seconds(10));
+
+        let invalid = TriggerType::Poll {
+            url: "http://localhost/feed".to_string(),
+            interval_secs: 60,
+            items: Some("data[".to_string()),
+            cursor: None,
+            headers: HashMap::new(),
+        };
+        assert!(PollTrigger::from_trigger(&invalid).is_err());
+        assert!(PollTrigger::from_trigger(&TriggerType::Manual).unwrap().is_none());
+    }
+}
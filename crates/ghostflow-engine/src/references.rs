@@ -0,0 +1,146 @@
+use ghostflow_core::{GhostFlowError, Result};
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+fn reference_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"\{\{\s*nodes\.([A-Za-z0-9_-]+)((?:\.[^\s.{}]+)*)\s*\}\}").unwrap()
+    })
+}
+
+/// Replace `{{nodes.<id>.<path>}}` references in `params` with values from
+/// the outputs of nodes that have already run. A string that is exactly one
+/// reference takes the referenced value with its JSON type; references
+/// embedded in longer text are rendered as text. Numeric path segments index
+/// into arrays, and a path that does not exist resolves to null. Referencing
+/// a node with no output yet fails, naming `node_id` as the culprit.
+pub fn resolve_node_references(
+    node_id: &str,
+    params: Value,
+    node_outputs: &HashMap<String, Value>,
+) -> Result<Value> {
+    match params {
+        Value::String(text) => resolve_string(node_id, text, node_outputs),
+        Value::Array(items) => items
+            .into_iter()
+            .map(|item| resolve_node_references(node_id, item, node_outputs))
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+        Value::Object(map) => map
+            .into_iter()
+            .map(|(key, value)| Ok((key, resolve_node_references(node_id, value, node_outputs)?)))
+            .collect::<Result<serde_json::Map<_, _>>>()
+            .map(Value::Object),
+        other => Ok(other),
+    }
+}
+
+fn resolve_string(node_id: &str, text: String, node_outputs: &HashMap<String, Value>) -> Result<Value> {
+    let pattern = reference_pattern();
+
+    if let Some(captures) = pattern.captures(&text) {
+        if captures.get(0).unwrap().as_str() == text.trim() {
+            return lookup(node_id, &captures, node_outputs);
+        }
+    } else {
+        return Ok(Value::String(text));
+    }
+
+    let mut failure = None;
+    let rendered = pattern.replace_all(&text, |captures: &Captures| {
+        match lookup(node_id, captures, node_outputs) {
+            Ok(Value::String(s)) => s,
+            Ok(value) => value.to_string(),
+            Err(e) => {
+                failure.get_or_insert(e);
+                String::new()
+            }
+        }
+    });
+
+    match failure {
+        Some(error) => Err(error),
+        None => Ok(Value::String(rendered.into_owned())),
+    }
+}
+
+fn lookup(node_id: &str, captures: &Captures, node_outputs: &HashMap<String, Value>) -> Result<Value> {
+    let source = &captures[1];
+    let output = node_outputs.get(source).ok_or_else(|| GhostFlowError::NodeExecutionError {
+        node_id: node_id.to_string(),
+        message: format!(
+            "Parameter references output of node '{}', which has not run yet",
+            source
+        ),
+    })?;
+
+    let mut current = output;
+    for segment in captures[2].split('.').filter(|s| !s.is_empty()) {
+        let next = match current {
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            Value::Object(map) => map.get(segment),
+            _ => None,
+        };
+        match next {
+            Some(value) => current = value,
+            None => return Ok(Value::Null),
+        }
+    }
+    Ok(current.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn outputs() -> HashMap<String, Value> {
+        let mut outputs = HashMap::new();
+        outputs.insert(
+            "node1".to_string(),
+            json!({ "result": { "rows": [{ "id": 7, "name": "pve-01" }], "count": 1 } }),
+        );
+        outputs
+    }
+
+    #[test]
+    fn test_resolves_deep_path_from_prior_node() {
+        let params = json!({
+            "rows": "{{nodes.node1.result.rows}}",
+            "first": "{{ nodes.node1.result.rows.0.name }}",
+            "summary": "Found {{nodes.node1.result.count}} host(s)",
+            "nested": ["{{nodes.node1.result.rows.0.id}}"],
+            "missing": "{{nodes.node1.result.nope}}",
+            "untouched": "{{input.name}}",
+        });
+
+        let resolved = resolve_node_references("node2", params, &outputs()).unwrap();
+        assert_eq!(resolved["rows"], json!([{ "id": 7, "name": "pve-01" }]));
+        assert_eq!(resolved["first"], "pve-01");
+        assert_eq!(resolved["summary"], "Found 1 host(s)");
+        assert_eq!(resolved["nested"], json!([7]));
+        assert_eq!(resolved["missing"], Value::Null);
+        assert_eq!(resolved["untouched"], "{{input.name}}");
+    }
+
+    #[test]
+    fn test_reference_to_node_that_has_not_run() {
+        let error = resolve_node_references(
+            "node2",
+            json!({ "host": "host-{{nodes.node3.result}}" }),
+            &outputs(),
+        )
+        .unwrap_err();
+
+        match error {
+            GhostFlowError::NodeExecutionError { node_id, message } => {
+                assert_eq!(node_id, "node2");
+                assert!(message.contains("'node3'"), "{}", message);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
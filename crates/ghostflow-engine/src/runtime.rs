@@ -1,10 +1,18 @@
-use crate::{FlowExecutor, FlowScheduler};
-use ghostflow_core::{GhostFlowError, NodeRegistry, Result};
-use ghostflow_schema::{ExecutionTrigger, Flow, FlowExecution};
+use crate::{
+    ChangeStreamConnector, ChangeStreamSpec, ChangeStreamWatcher, ExecutionDispatcher, FlowConcurrencyLimiter,
+    FlowExecutor, FlowScheduler, MongoChangeStreamConnector,
+};
+use ghostflow_core::{
+    validate_flow_input, DeadLetterStorage, ExecutionStateStorage, GhostFlowError, IdempotencyStorage,
+    MemoryDeadLetterStore, MemoryIdempotencyStore, MemoryResumeTokenStore, NodeRegistry, ReplayProtection, Result,
+    ResumeTokenStorage, Services, WebhookResponse, DEFAULT_IDEMPOTENCY_TTL,
+};
+use ghostflow_schema::{DeadLetter, ExecutionTrigger, Flow, FlowExecution, TriggerType};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tokio::time::interval;
 use tracing::{error, info, warn};
 use uuid::Uuid;
@@ -12,8 +20,20 @@ use uuid::Uuid;
 pub struct FlowRuntime {
     executor: FlowExecutor,
     scheduler: FlowScheduler,
+    concurrency: FlowConcurrencyLimiter,
+    dispatcher: ExecutionDispatcher,
     flows: Arc<RwLock<HashMap<Uuid, Flow>>>,
+    executions: Arc<RwLock<HashMap<Uuid, FlowExecution>>>,
+    idempotency: Arc<dyn IdempotencyStorage>,
+    dead_letters: Arc<dyn DeadLetterStorage>,
+    state_storage: Option<Arc<dyn ExecutionStateStorage>>,
+    change_stream_connector: Arc<dyn ChangeStreamConnector>,
+    resume_tokens: Arc<dyn ResumeTokenStorage>,
+    /// Watcher tasks of the change stream triggers of each deployed flow
+    change_streams: Arc<RwLock<HashMap<Uuid, Vec<JoinHandle<()>>>>>,
     node_registry: Arc<dyn NodeRegistry>,
+    /// Idempotent runs that hold a reserved key, published once finished
+    in_flight: Arc<RwLock<HashMap<Uuid, tokio::sync::watch::Receiver<Option<FlowExecution>>>>>,
     running: Arc<RwLock<bool>>,
 }
 
@@ -21,16 +41,96 @@ impl FlowRuntime {
     pub fn new(node_registry: Arc<dyn NodeRegistry>) -> Self {
         let executor = FlowExecutor::new(node_registry.clone());
         let scheduler = FlowScheduler::new();
+        let concurrency = FlowConcurrencyLimiter::new().with_cancellations(executor.services().cancellations.clone());
         
         Self {
             executor,
             scheduler,
+            concurrency,
+            dispatcher: ExecutionDispatcher::default(),
             flows: Arc::new(RwLock::new(HashMap::new())),
+            executions: Arc::new(RwLock::new(HashMap::new())),
+            idempotency: Arc::new(MemoryIdempotencyStore::new()),
+            dead_letters: Arc::new(MemoryDeadLetterStore::new()),
+            state_storage: None,
+            change_stream_connector: Arc::new(MongoChangeStreamConnector),
+            resume_tokens: Arc::new(MemoryResumeTokenStore::new()),
+            change_streams: Arc::new(RwLock::new(HashMap::new())),
             node_registry,
+            in_flight: Arc::new(RwLock::new(HashMap::new())),
             running: Arc::new(RwLock::new(false)),
         }
     }
 
+    /// Keep idempotency keys somewhere other than process memory, e.g. so
+    /// they survive restarts or are shared between API replicas.
+    pub fn with_idempotency_storage(mut self, storage: Arc<dyn IdempotencyStorage>) -> Self {
+        self.idempotency = storage;
+        self
+    }
+
+    /// Keep dead letters somewhere other than process memory.
+    pub fn with_dead_letter_storage(mut self, storage: Arc<dyn DeadLetterStorage>) -> Self {
+        self.dead_letters = storage;
+        self
+    }
+
+    /// Share `services` with the nodes and the API. See
+    /// [`FlowExecutor::with_services`].
+    pub fn with_services(mut self, services: Services) -> Self {
+        self.concurrency = self.concurrency.with_cancellations(services.cancellations.clone());
+        self.executor = self.executor.with_services(services);
+        self
+    }
+
+    /// State shared with the nodes and the API, such as the event bus.
+    pub fn services(&self) -> &Services {
+        self.executor.services()
+    }
+
+    /// Run at most `max_concurrent` nodes of `node_type` at a time across
+    /// every execution of this runtime. See [`FlowExecutor::with_node_type_limit`].
+    pub fn with_node_type_limit(mut self, node_type: impl Into<String>, max_concurrent: usize) -> Self {
+        self.executor = self.executor.with_node_type_limit(node_type, max_concurrent);
+        self
+    }
+
+    /// Wrap node outputs in a uniform result envelope. See
+    /// [`FlowExecutor::with_result_envelope`].
+    pub fn with_result_envelope(mut self) -> Self {
+        self.executor = self.executor.with_result_envelope();
+        self
+    }
+
+    /// Run at most `max_concurrency` executions at a time across all flows.
+    /// Further executions queue by the priority of the trigger that started
+    /// them; see [`ExecutionDispatcher`].
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.dispatcher = ExecutionDispatcher::new(Some(max_concurrency));
+        self
+    }
+
+    /// Save the progress of running executions to `storage` so they can be
+    /// picked up with [`Self::resume_execution`] after a restart.
+    pub fn with_state_storage(mut self, storage: Arc<dyn ExecutionStateStorage>) -> Self {
+        self.executor = self.executor.with_state_storage(storage.clone());
+        self.state_storage = Some(storage);
+        self
+    }
+
+    /// Open change streams through `connector` instead of the MongoDB driver.
+    pub fn with_change_stream_connector(mut self, connector: Arc<dyn ChangeStreamConnector>) -> Self {
+        self.change_stream_connector = connector;
+        self
+    }
+
+    /// Keep change stream resume tokens somewhere other than process memory,
+    /// so change stream triggers pick up where they stopped after a restart.
+    pub fn with_resume_token_storage(mut self, storage: Arc<dyn ResumeTokenStorage>) -> Self {
+        self.resume_tokens = storage;
+        self
+    }
+
     pub async fn start(&self) -> Result<()> {
         let mut running = self.running.write().await;
         if *running {
@@ -48,6 +148,10 @@ impl FlowRuntime {
         let scheduler = self.scheduler.clone();
         let executor = self.executor.clone();
         let running_clone = self.running.clone();
+        let executions = self.executions.clone();
+        let concurrency = self.concurrency.clone();
+        let dispatcher = self.dispatcher.clone();
+        let dead_letters = self.dead_letters.clone();
         
         tokio::spawn(async move {
             let mut ticker = interval(Duration::from_secs(10)); // Check every 10 seconds
@@ -68,32 +172,85 @@ impl FlowRuntime {
                 let ready_flows = scheduler.get_ready_flows().await;
                 
                 for (flow, trigger) in ready_flows {
+                    // Poll triggers only start the flow when the endpoint has new data
+                    let input = if let ghostflow_schema::TriggerType::Poll { .. } = trigger.trigger_type {
+                        let Some(poller) = scheduler.poller(&flow.id, &trigger.id).await else {
+                            continue;
+                        };
+                        let polled = poller.lock().await.poll().await;
+                        if let Err(e) = scheduler.update_trigger_next_run(&flow.id, &trigger.id).await {
+                            error!("Failed to update trigger next run: {}", e);
+                        }
+                        match polled {
+                            Ok(Some(delta)) => delta,
+                            Ok(None) => continue,
+                            Err(e) => {
+                                warn!("Poll for flow {} trigger {} failed: {}", flow.id, trigger.id, e);
+                                continue;
+                            }
+                        }
+                    } else {
+                        // Set before running so a slow execution is not started again on the next tick
+                        if let Err(e) = scheduler.update_trigger_next_run(&flow.id, &trigger.id).await {
+                            error!("Failed to update trigger next run: {}", e);
+                        }
+                        serde_json::Value::Null
+                    };
                     info!("Executing scheduled flow {} triggered by {}", flow.id, trigger.id);
-                    
+
                     let execution_trigger = ExecutionTrigger {
                         trigger_type: match trigger.trigger_type {
                             ghostflow_schema::TriggerType::Cron { .. } => "cron".to_string(),
                             ghostflow_schema::TriggerType::Webhook { .. } => "webhook".to_string(),
                             ghostflow_schema::TriggerType::Manual => "manual".to_string(),
+                            ghostflow_schema::TriggerType::Poll { .. } => "poll".to_string(),
+                            ghostflow_schema::TriggerType::MongoChangeStream { .. } => {
+                                "mongodb_change_stream".to_string()
+                            }
                         },
                         source: Some(trigger.id.clone()),
                         metadata: HashMap::new(),
+                        dry_run: false,
+                        priority: trigger.priority,
+                        environment: None,
                     };
                     
-                    // Execute the flow
-                    match executor.execute_flow(&flow, serde_json::Value::Null, execution_trigger).await {
-                        Ok(execution) => {
-                            info!("Flow execution {} completed with status {:?}", execution.id, execution.status);
-                            
-                            // Update trigger next run time for cron triggers
-                            if let Err(e) = scheduler.update_trigger_next_run(&flow.id, &trigger.id).await {
-                                error!("Failed to update trigger next run: {}", e);
+                    // Run in the background so one slow flow does not hold up the
+                    // rest; overlapping runs of a flow are bounded by its limit
+                    let executor = executor.clone();
+                    let executions = executions.clone();
+                    let concurrency = concurrency.clone();
+                    let dispatcher = dispatcher.clone();
+                    let dead_letters = dead_letters.clone();
+                    tokio::spawn(async move {
+                        let execution_id = Uuid::new_v4();
+                        let _slot = match concurrency.acquire(&flow, execution_id).await {
+                            Ok(slot) => slot,
+                            Err(e) => {
+                                warn!("Skipping scheduled run of flow {}: {}", flow.id, e);
+                                return;
+                            }
+                        };
+                        let _dispatch = match dispatcher.acquire(execution_id, execution_trigger.priority).await {
+                            Ok(slot) => slot,
+                            Err(e) => {
+                                warn!("Skipping scheduled run of flow {}: {}", flow.id, e);
+                                return;
+                            }
+                        };
+                        match executor
+                            .execute_flow_with_id(execution_id, &flow, input, execution_trigger)
+                            .await
+                        {
+                            Ok(execution) => {
+                                info!("Flow execution {} completed with status {:?}", execution.id, execution.status);
+                                record_execution(&executions, dead_letters.as_ref(), execution).await;
+                            }
+                            Err(e) => {
+                                error!("Flow execution failed: {}", e);
                             }
                         }
-                        Err(e) => {
-                            error!("Flow execution failed: {}", e);
-                        }
-                    }
+                    });
                 }
             }
         });
@@ -108,11 +265,24 @@ impl FlowRuntime {
         Ok(())
     }
 
-    pub async fn deploy_flow(&self, flow: Flow) -> Result<()> {
+    pub async fn deploy_flow(&self, mut flow: Flow) -> Result<()> {
         info!("Deploying flow {}: {}", flow.id, flow.name);
-        
+
+        // Upgrade nodes written for older versions of their node type
+        self.services().node_migrations.migrate_flow(&mut flow)?;
+
         // Validate the flow
         self.validate_flow(&flow).await?;
+        let change_streams = flow
+            .triggers
+            .iter()
+            .filter(|trigger| trigger.enabled)
+            .filter_map(|trigger| {
+                ChangeStreamSpec::from_trigger(&trigger.trigger_type)
+                    .transpose()
+                    .map(|spec| spec.map(|spec| (trigger.id.clone(), spec)))
+            })
+            .collect::<Result<Vec<_>>>()?;
         
         // Store the flow
         {
@@ -121,14 +291,98 @@ impl FlowRuntime {
         }
         
         // Schedule the flow
-        self.scheduler.schedule_flow(flow).await?;
+        self.scheduler.schedule_flow(flow.clone()).await?;
+
+        // Change streams push events rather than being scheduled
+        self.stop_change_streams(&flow.id).await;
+        let flow = Arc::new(flow);
+        let watchers = change_streams
+            .into_iter()
+            .map(|(trigger_id, spec)| self.watch_change_stream(flow.clone(), trigger_id, spec))
+            .collect();
+        self.change_streams.write().await.insert(flow.id, watchers);
         
         Ok(())
     }
 
+    /// Spawn a watcher that runs `flow` once per event of a change stream
+    /// trigger, with the change event as input. Events are handled one at a
+    /// time and a resume token is stored only once its run has finished.
+    fn watch_change_stream(&self, flow: Arc<Flow>, trigger_id: String, spec: ChangeStreamSpec) -> JoinHandle<()> {
+        let key = format!("{}/{}", flow.id, trigger_id);
+        let watcher = ChangeStreamWatcher::new(spec, key, self.change_stream_connector.clone(), self.resume_tokens.clone());
+        let executor = self.executor.clone();
+        let executions = self.executions.clone();
+        let concurrency = self.concurrency.clone();
+        let dispatcher = self.dispatcher.clone();
+        let dead_letters = self.dead_letters.clone();
+        let priority = flow
+            .triggers
+            .iter()
+            .find(|trigger| trigger.id == trigger_id)
+            .map_or(0, |trigger| trigger.priority);
+
+        tokio::spawn(watcher.run(move |change| {
+            let flow = flow.clone();
+            let trigger_id = trigger_id.clone();
+            let executor = executor.clone();
+            let executions = executions.clone();
+            let concurrency = concurrency.clone();
+            let dispatcher = dispatcher.clone();
+            let dead_letters = dead_letters.clone();
+            async move {
+                let mut metadata = HashMap::new();
+                if let Some(operation) = change.get("operationType") {
+                    metadata.insert("operation_type".to_string(), operation.clone());
+                }
+                let execution_trigger = ExecutionTrigger {
+                    trigger_type: "mongodb_change_stream".to_string(),
+                    source: Some(trigger_id),
+                    metadata,
+                    dry_run: false,
+                    priority,
+                    environment: None,
+                };
+
+                let execution_id = Uuid::new_v4();
+                let _slot = match concurrency.acquire(&flow, execution_id).await {
+                    Ok(slot) => slot,
+                    Err(e) => {
+                        warn!("Skipping change event for flow {}: {}", flow.id, e);
+                        return;
+                    }
+                };
+                let _dispatch = match dispatcher.acquire(execution_id, priority).await {
+                    Ok(slot) => slot,
+                    Err(e) => {
+                        warn!("Skipping change event for flow {}: {}", flow.id, e);
+                        return;
+                    }
+                };
+                match executor
+                    .execute_flow_with_id(execution_id, &flow, change, execution_trigger)
+                    .await
+                {
+                    Ok(execution) => record_execution(&executions, dead_letters.as_ref(), execution).await,
+                    Err(e) => error!("Change stream execution {} failed: {}", execution_id, e),
+                }
+            }
+        }))
+    }
+
+    async fn stop_change_streams(&self, flow_id: &Uuid) {
+        if let Some(watchers) = self.change_streams.write().await.remove(flow_id) {
+            for watcher in watchers {
+                watcher.abort();
+            }
+        }
+    }
+
     pub async fn undeploy_flow(&self, flow_id: &Uuid) -> Result<()> {
         info!("Undeploying flow {}", flow_id);
         
+        self.stop_change_streams(flow_id).await;
+
         // Remove from scheduler
         self.scheduler.unschedule_flow(flow_id).await?;
         
@@ -141,10 +395,28 @@ impl FlowRuntime {
         Ok(())
     }
 
+    /// Run a deployed flow on demand. With `dry_run` set, every node is
+    /// resolved and validated but none is executed; see
+    /// `FlowExecution::validation_report`. `environment` names an
+    /// [`ghostflow_core::Environment`] in the services' environments to run in.
     pub async fn execute_flow_manually(
         &self,
         flow_id: &Uuid,
         input_data: serde_json::Value,
+        dry_run: bool,
+        environment: Option<&str>,
+    ) -> Result<FlowExecution> {
+        self.run_manually(Uuid::new_v4(), flow_id, input_data, dry_run, environment)
+            .await
+    }
+
+    async fn run_manually(
+        &self,
+        execution_id: Uuid,
+        flow_id: &Uuid,
+        input_data: serde_json::Value,
+        dry_run: bool,
+        environment: Option<&str>,
     ) -> Result<FlowExecution> {
         let flow = {
             let flows = self.flows.read().await;
@@ -154,13 +426,323 @@ impl FlowRuntime {
             })?
         };
         
+        // Reject bad input up front rather than deep inside a node
+        let input_data = validate_flow_input(&flow, &input_data)?;
+        if let Some(name) = environment.filter(|name| self.services().environments.get(name).is_none()) {
+            return Err(GhostFlowError::NotFoundError {
+                resource_type: "environment".to_string(),
+                id: name.to_string(),
+            });
+        }
+
         let execution_trigger = ExecutionTrigger {
             trigger_type: "manual".to_string(),
             source: None,
             metadata: HashMap::new(),
+            dry_run,
+            priority: trigger_priority(&flow, |t| matches!(t, TriggerType::Manual)),
+            environment: environment.map(str::to_string),
         };
-        
-        self.executor.execute_flow(&flow, input_data, execution_trigger).await
+
+        // Dry runs execute nothing, so they do not count against the limits
+        let (_slot, _dispatch) = if dry_run {
+            (None, None)
+        } else {
+            let slot = self.concurrency.acquire(&flow, execution_id).await?;
+            let dispatch = self.dispatcher.acquire(execution_id, execution_trigger.priority).await?;
+            (slot, dispatch)
+        };
+        let execution = self
+            .executor
+            .execute_flow_with_id(execution_id, &flow, input_data, execution_trigger)
+            .await?;
+        record_execution(&self.executions, self.dead_letters.as_ref(), execution.clone()).await;
+        Ok(execution)
+    }
+
+    /// Like [`Self::execute_flow_manually`], but a key already seen for this
+    /// flow within [`DEFAULT_IDEMPOTENCY_TTL`] returns the original execution
+    /// instead of starting another run. The key is reserved before the run
+    /// starts, so a concurrent request with the same key waits for the
+    /// original to finish and gets its execution.
+    pub async fn execute_flow_idempotent(
+        &self,
+        flow_id: &Uuid,
+        idempotency_key: &str,
+        input_data: serde_json::Value,
+        dry_run: bool,
+        environment: Option<&str>,
+    ) -> Result<FlowExecution> {
+        let key = format!("{}:{}", flow_id, idempotency_key);
+        let execution_id = Uuid::new_v4();
+        let (finished, watch) = tokio::sync::watch::channel(None);
+        self.in_flight.write().await.insert(execution_id, watch);
+
+        let result = loop {
+            let original = match self.idempotency.reserve_key(&key, execution_id, DEFAULT_IDEMPOTENCY_TTL).await {
+                Ok(None) => break self.run_manually(execution_id, flow_id, input_data, dry_run, environment).await,
+                Ok(Some(original)) => original,
+                Err(e) => break Err(e),
+            };
+            match self.wait_for_execution(original).await {
+                Some(execution) => {
+                    info!("Idempotency key {} matched execution {}", idempotency_key, original);
+                    self.in_flight.write().await.remove(&execution_id);
+                    return Ok(execution);
+                }
+                // The original never started, or is gone; take the key over
+                None => {
+                    let held = self.idempotency.get_execution_for_key(&key).await;
+                    if matches!(held, Ok(Some(id)) if id == original) {
+                        if let Err(e) = self.idempotency.release_key(&key).await {
+                            break Err(e);
+                        }
+                    }
+                }
+            }
+        };
+
+        self.in_flight.write().await.remove(&execution_id);
+        match &result {
+            Ok(execution) => {
+                finished.send_replace(Some(execution.clone()));
+            }
+            Err(_) => self.idempotency.release_key(&key).await?,
+        }
+        result
+    }
+
+    /// The execution `execution_id` once it has finished, waiting if it is
+    /// an idempotent run still in progress on this runtime.
+    async fn wait_for_execution(&self, execution_id: Uuid) -> Option<FlowExecution> {
+        let watch = self.in_flight.read().await.get(&execution_id).cloned();
+        match watch {
+            Some(mut watch) => watch.wait_for(Option::is_some).await.ok().and_then(|e| e.clone()),
+            None => self.get_execution(&execution_id).await,
+        }
+    }
+
+    /// Run a deployed flow for an inbound webhook and wait up to `timeout`
+    /// for a `respond_to_webhook` node to produce the HTTP response. The flow
+    /// keeps running in the background after responding; one that finishes
+    /// or times out without responding yields [`WebhookResponse::accepted`].
+    /// When the flow's webhook trigger node sets up [`ReplayProtection`], a
+    /// delivery id already seen within its window is answered with
+    /// [`WebhookResponse::duplicate`] and does not run the flow again.
+    /// The delivery id is recorded here, so callers must verify the request's
+    /// signature first; otherwise a forged request could claim a real id.
+    /// Flows without a webhook trigger are reported as not found, and the
+    /// request body is checked against the flow's input schema.
+    pub async fn execute_webhook(
+        &self,
+        flow_id: &Uuid,
+        mut input_data: serde_json::Value,
+        timeout: Duration,
+    ) -> Result<WebhookResponse> {
+        let flow = self
+            .get_flow(flow_id)
+            .await
+            .filter(accepts_webhooks)
+            .ok_or_else(|| GhostFlowError::NotFoundError {
+                resource_type: "webhook".to_string(),
+                id: flow_id.to_string(),
+            })?;
+
+        // The request body carries what a manual run takes as its input
+        let body = input_data.get("body").cloned().unwrap_or(serde_json::Value::Null);
+        let body = validate_flow_input(&flow, &body)?;
+        if let Some(envelope) = input_data.as_object_mut() {
+            if !body.is_null() {
+                envelope.insert("body".to_string(), body);
+            }
+        }
+
+        let execution_id = Uuid::new_v4();
+        if let Some(original) = self.check_webhook_replay(&flow, &input_data, execution_id).await? {
+            info!("Webhook for flow {} is a redelivery of execution {}", flow.id, original);
+            return Ok(WebhookResponse::duplicate(original));
+        }
+
+        // A queued webhook waits here; its response timeout starts once it runs
+        let priority = trigger_priority(&flow, |t| matches!(t, TriggerType::Webhook { .. }));
+        let slot = self.concurrency.acquire(&flow, execution_id).await?;
+        let dispatch = self.dispatcher.acquire(execution_id, priority).await?;
+        let responses = self.services().webhook_responses.clone();
+        let response = responses.register(execution_id);
+
+        let execution_trigger = ExecutionTrigger {
+            trigger_type: "webhook".to_string(),
+            source: None,
+            metadata: HashMap::new(),
+            dry_run: false,
+            priority,
+            environment: None,
+        };
+        let executor = self.executor.clone();
+        let executions = self.executions.clone();
+        let dead_letters = self.dead_letters.clone();
+        let pending = responses.clone();
+        tokio::spawn(async move {
+            let _slot = slot;
+            let _dispatch = dispatch;
+            match executor
+                .execute_flow_with_id(execution_id, &flow, input_data, execution_trigger)
+                .await
+            {
+                Ok(execution) => {
+                    record_execution(&executions, dead_letters.as_ref(), execution).await;
+                }
+                Err(e) => error!("Webhook execution {} failed: {}", execution_id, e),
+            }
+            // Wakes the caller if the flow never responded
+            pending.remove(execution_id);
+        });
+
+        match tokio::time::timeout(timeout, response).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Ok(WebhookResponse::accepted(execution_id)),
+            Err(_) => {
+                warn!("Webhook execution {} did not respond within {:?}", execution_id, timeout);
+                responses.remove(execution_id);
+                Ok(WebhookResponse::accepted(execution_id))
+            }
+        }
+    }
+
+    /// Execution started by an earlier delivery of this webhook, if any.
+    /// Otherwise records `execution_id` against the delivery id, so later
+    /// redeliveries within the window find it. The record expires with the
+    /// window. Only call this for authenticated requests.
+    async fn check_webhook_replay(
+        &self,
+        flow: &Flow,
+        input_data: &serde_json::Value,
+        execution_id: Uuid,
+    ) -> Result<Option<Uuid>> {
+        let Some(trigger_node) = flow.nodes.values().find(|node| node.node_type == "webhook_trigger") else {
+            return Ok(None);
+        };
+        let Some(protection) = ReplayProtection::from_params(&serde_json::to_value(&trigger_node.parameters)?)? else {
+            return Ok(None);
+        };
+        let Some(delivery_id) = protection.delivery_id(input_data) else {
+            warn!("Webhook for flow {} has no delivery id; replay protection skipped", flow.id);
+            return Ok(None);
+        };
+
+        let key = format!("webhook:{}:{}", flow.id, delivery_id);
+        self.idempotency.reserve_key(&key, execution_id, protection.window).await
+    }
+
+    /// Re-run a stored execution with the inputs its nodes recorded, against
+    /// the currently deployed version of its flow. See
+    /// [`FlowExecutor::replay_execution`].
+    pub async fn replay_execution(
+        &self,
+        execution_id: &Uuid,
+        stub_non_deterministic: bool,
+    ) -> Result<FlowExecution> {
+        let original = self.get_execution(execution_id).await.ok_or_else(|| GhostFlowError::NotFoundError {
+            resource_type: "execution".to_string(),
+            id: execution_id.to_string(),
+        })?;
+        let flow = self.get_flow(&original.flow_id).await.ok_or_else(|| GhostFlowError::NotFoundError {
+            resource_type: "flow".to_string(),
+            id: original.flow_id.to_string(),
+        })?;
+
+        let execution = self
+            .executor
+            .replay_execution(&flow, &original, stub_non_deterministic)
+            .await?;
+        record_execution(&self.executions, self.dead_letters.as_ref(), execution.clone()).await;
+        Ok(execution)
+    }
+
+    /// Continue an execution that did not finish, e.g. because the process
+    /// running it stopped, from the state it last saved. Its flow must be
+    /// deployed on this runtime.
+    pub async fn resume_execution(&self, execution_id: &Uuid) -> Result<FlowExecution> {
+        let storage = self.state_storage.as_ref().ok_or_else(|| GhostFlowError::ConfigurationError {
+            message: "No execution state storage is configured".to_string(),
+        })?;
+        let state = storage.load_state(execution_id).await?.ok_or_else(|| GhostFlowError::NotFoundError {
+            resource_type: "execution state".to_string(),
+            id: execution_id.to_string(),
+        })?;
+        let flow = self.get_flow(&state.flow_id).await.ok_or_else(|| GhostFlowError::NotFoundError {
+            resource_type: "flow".to_string(),
+            id: state.flow_id.to_string(),
+        })?;
+
+        let execution = self.executor.resume_execution(&flow, state).await?;
+        record_execution(&self.executions, self.dead_letters.as_ref(), execution.clone()).await;
+        Ok(execution)
+    }
+
+    /// Failed executions kept for retry, most recent first.
+    pub async fn list_dead_letters(&self) -> Result<Vec<DeadLetter>> {
+        self.dead_letters.list_dead_letters().await
+    }
+
+    /// Run the flow of a dead-lettered execution again with its original
+    /// input, against the currently deployed version. The dead letter is
+    /// removed; if the new run fails too it is dead-lettered in its own right.
+    pub async fn retry_dead_letter(&self, execution_id: &Uuid) -> Result<FlowExecution> {
+        let dead_letter = self
+            .dead_letters
+            .get_dead_letter(execution_id)
+            .await?
+            .ok_or_else(|| GhostFlowError::NotFoundError {
+                resource_type: "dead letter".to_string(),
+                id: execution_id.to_string(),
+            })?;
+        let flow = self.get_flow(&dead_letter.flow_id).await.ok_or_else(|| GhostFlowError::NotFoundError {
+            resource_type: "flow".to_string(),
+            id: dead_letter.flow_id.to_string(),
+        })?;
+
+        let mut trigger = dead_letter.trigger.clone();
+        trigger
+            .metadata
+            .insert("retry_of".to_string(), serde_json::json!(dead_letter.execution_id));
+        self.dead_letters.delete_dead_letter(execution_id).await?;
+        info!("Retrying dead-lettered execution {}", execution_id);
+
+        let new_id = Uuid::new_v4();
+        let _slot = self.concurrency.acquire(&flow, new_id).await?;
+        let _dispatch = self.dispatcher.acquire(new_id, trigger.priority).await?;
+        let execution = self
+            .executor
+            .execute_flow_with_id(new_id, &flow, dead_letter.input_data, trigger)
+            .await?;
+        record_execution(&self.executions, self.dead_letters.as_ref(), execution.clone()).await;
+        Ok(execution)
+    }
+
+    pub async fn get_execution(&self, execution_id: &Uuid) -> Option<FlowExecution> {
+        let executions = self.executions.read().await;
+        executions.get(execution_id).cloned()
+    }
+
+    /// Flow that a running or finished execution belongs to.
+    pub async fn execution_flow_id(&self, execution_id: &Uuid) -> Option<Uuid> {
+        match self.executor.running_flow(execution_id) {
+            Some(flow_id) => Some(flow_id),
+            None => self.get_execution(execution_id).await.map(|execution| execution.flow_id),
+        }
+    }
+
+    /// Executions run by this runtime, most recent first.
+    pub async fn list_executions(&self) -> Vec<FlowExecution> {
+        let executions = self.executions.read().await;
+        let mut list: Vec<FlowExecution> = executions.values().cloned().collect();
+        list.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        list
+    }
+
+    pub fn cancel_execution(&self, execution_id: Uuid) -> bool {
+        self.executor.cancel_execution(execution_id)
     }
 
     pub async fn list_flows(&self) -> Vec<Flow> {
@@ -207,4 +789,923 @@ impl FlowRuntime {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Whether `flow` has a `webhook_trigger` node or an enabled webhook trigger
+fn accepts_webhooks(flow: &Flow) -> bool {
+    flow.nodes.values().any(|node| node.node_type == "webhook_trigger")
+        || flow
+            .triggers
+            .iter()
+            .any(|t| t.enabled && matches!(t.trigger_type, TriggerType::Webhook { .. }))
+}
+
+/// Priority of the first enabled trigger of `flow` that `matches`, 0 when
+/// none does.
+fn trigger_priority(flow: &Flow, matches: impl Fn(&TriggerType) -> bool) -> i32 {
+    flow.triggers
+        .iter()
+        .find(|trigger| trigger.enabled && matches(&trigger.trigger_type))
+        .map_or(0, |trigger| trigger.priority)
+}
+
+/// Keep a finished execution, and dead-letter it if it failed.
+async fn record_execution(
+    executions: &RwLock<HashMap<Uuid, FlowExecution>>,
+    dead_letters: &dyn DeadLetterStorage,
+    execution: FlowExecution,
+) {
+    if let Some(dead_letter) = DeadLetter::from_execution(&execution) {
+        warn!(
+            "Execution {} failed at {:?}; saved as a dead letter",
+            execution.id, dead_letter.failed_node
+        );
+        if let Err(e) = dead_letters.save_dead_letter(&dead_letter).await {
+            error!("Could not save dead letter for execution {}: {}", execution.id, e);
+        }
+    }
+    executions.write().await.insert(execution.id, execution);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use ghostflow_core::{BasicNodeRegistry, Node};
+    use ghostflow_schema::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingNode {
+        executed: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Node for CountingNode {
+        fn definition(&self) -> NodeDefinition {
+            NodeDefinition {
+                id: "counting".to_string(),
+                name: "Counting".to_string(),
+                description: "Counts executions".to_string(),
+                category: NodeCategory::Utility,
+                version: "1.0.0".to_string(),
+                inputs: vec![],
+                outputs: vec![],
+                parameters: vec![],
+                icon: None,
+                color: None,
+            }
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, _context: ExecutionContext) -> Result<serde_json::Value> {
+            self.executed.fetch_add(1, Ordering::SeqCst);
+            Ok(serde_json::json!({}))
+        }
+    }
+
+    fn flow_node(id: &str, node_type: &str, parameters: HashMap<String, serde_json::Value>) -> FlowNode {
+        FlowNode {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            name: id.to_string(),
+            description: None,
+            parameters,
+            position: NodePosition { x: 0.0, y: 0.0 },
+            retry_config: None,
+            timeout_ms: None,
+            cache_ttl_ms: None,
+            node_version: None,
+        }
+    }
+
+    async fn runtime_with_flow(executed: Arc<AtomicUsize>) -> (FlowRuntime, Uuid) {
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("counting".to_string(), Arc::new(CountingNode { executed }))
+            .unwrap();
+        let runtime = FlowRuntime::new(Arc::new(registry));
+        let flow_id = deploy(&runtime, vec![flow_node("count", "counting", HashMap::new())]).await;
+        (runtime, flow_id)
+    }
+
+    async fn deploy(runtime: &FlowRuntime, nodes: Vec<FlowNode>) -> Uuid {
+        let flow = test_flow(nodes);
+        let flow_id = flow.id;
+        runtime.deploy_flow(flow).await.unwrap();
+        flow_id
+    }
+
+    fn webhook_trigger(enabled: bool) -> FlowTrigger {
+        FlowTrigger {
+            id: "hook".to_string(),
+            trigger_type: TriggerType::Webhook {
+                path: "/hook".to_string(),
+                method: "POST".to_string(),
+            },
+            config: HashMap::new(),
+            enabled,
+            priority: 0,
+        }
+    }
+
+    async fn deploy_webhook(runtime: &FlowRuntime, nodes: Vec<FlowNode>) -> Uuid {
+        let mut flow = test_flow(nodes);
+        flow.triggers.push(webhook_trigger(true));
+        let flow_id = flow.id;
+        runtime.deploy_flow(flow).await.unwrap();
+        flow_id
+    }
+
+    fn test_flow(nodes: Vec<FlowNode>) -> Flow {
+        Flow {
+            id: Uuid::new_v4(),
+            name: "Restart VM".to_string(),
+            description: None,
+            version: "1.0.0".to_string(),
+            nodes: nodes.into_iter().map(|n| (n.id.clone(), n)).collect(),
+            edges: vec![],
+            triggers: vec![],
+            parameters: HashMap::new(),
+            secrets: vec![],
+            max_duration_ms: None,
+            max_concurrent_executions: None,
+            overflow_policy: OverflowPolicy::Queue,
+            metadata: FlowMetadata {
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                created_by: "test".to_string(),
+                tags: vec![],
+                category: None,
+                workspace_id: "default".to_string(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deploy_migrates_outdated_nodes() {
+        let services = Services::default();
+        services.node_migrations.register("legacy_command", "1.0.0", "2.0.0", |params| {
+            let args = params.get("args").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let args: Vec<&str> = args.split(',').map(str::trim).filter(|a| !a.is_empty()).collect();
+            params.insert("args".to_string(), serde_json::json!(args));
+            Ok(())
+        });
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node(
+                "legacy_command".to_string(),
+                Arc::new(CountingNode {
+                    executed: Arc::new(AtomicUsize::new(0)),
+                }),
+            )
+            .unwrap();
+        let runtime = FlowRuntime::new(Arc::new(registry)).with_services(services);
+
+        let mut node = flow_node(
+            "run",
+            "legacy_command",
+            HashMap::from([("args".to_string(), serde_json::json!("status, --short"))]),
+        );
+        node.node_version = Some("1.0.0".to_string());
+        let flow_id = deploy(&runtime, vec![node]).await;
+
+        let deployed = runtime.get_flow(&flow_id).await.unwrap();
+        assert_eq!(deployed.nodes["run"].parameters["args"], serde_json::json!(["status", "--short"]));
+        assert_eq!(deployed.nodes["run"].node_version.as_deref(), Some("2.0.0"));
+    }
+
+    #[tokio::test]
+    async fn test_repeated_idempotency_key_returns_original_execution() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let (runtime, flow_id) = runtime_with_flow(executed.clone()).await;
+
+        let first = runtime
+            .execute_flow_idempotent(&flow_id, "webhook-42", serde_json::json!({}), false, None)
+            .await
+            .unwrap();
+        let repeat = runtime
+            .execute_flow_idempotent(&flow_id, "webhook-42", serde_json::json!({}), false, None)
+            .await
+            .unwrap();
+
+        assert_eq!(repeat.id, first.id);
+        assert_eq!(executed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_with_one_key_run_once() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node(
+                "slow".to_string(),
+                Arc::new(SlowNode {
+                    running: Arc::new(AtomicUsize::new(0)),
+                    peak: Arc::new(AtomicUsize::new(0)),
+                    executed: executed.clone(),
+                }),
+            )
+            .unwrap();
+        let runtime = Arc::new(FlowRuntime::new(Arc::new(registry)));
+        let flow_id = deploy(&runtime, vec![flow_node("work", "slow", HashMap::new())]).await;
+
+        let requests = (0..8).map(|_| {
+            let runtime = runtime.clone();
+            tokio::spawn(async move {
+                runtime
+                    .execute_flow_idempotent(&flow_id, "order-7", serde_json::json!({}), false, None)
+                    .await
+                    .unwrap()
+            })
+        });
+        let executions = futures::future::join_all(requests).await;
+
+        let first = executions[0].as_ref().unwrap().id;
+        assert!(executions.iter().all(|e| e.as_ref().unwrap().id == first));
+        assert_eq!(executed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_distinct_idempotency_key_starts_new_execution() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let (runtime, flow_id) = runtime_with_flow(executed.clone()).await;
+
+        let first = runtime
+            .execute_flow_idempotent(&flow_id, "webhook-42", serde_json::json!({}), false, None)
+            .await
+            .unwrap();
+        let second = runtime
+            .execute_flow_idempotent(&flow_id, "webhook-43", serde_json::json!({}), false, None)
+            .await
+            .unwrap();
+
+        assert_ne!(second.id, first.id);
+        assert_eq!(executed.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_flow_returns_custom_response() {
+        let services = Services::default();
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node(
+                "respond_to_webhook".to_string(),
+                Arc::new(
+                    ghostflow_nodes::RespondToWebhookNode::new()
+                        .with_webhook_responses(services.webhook_responses.clone()),
+                ),
+            )
+            .unwrap();
+        let runtime = FlowRuntime::new(Arc::new(registry)).with_services(services);
+
+        let respond = flow_node(
+            "respond",
+            "respond_to_webhook",
+            HashMap::from([
+                ("status_code".to_string(), serde_json::json!(201)),
+                ("headers".to_string(), serde_json::json!({ "Location": "/vms/101" })),
+                ("body".to_string(), serde_json::json!({ "vmid": 101, "created": true })),
+            ]),
+        );
+        let flow_id = deploy_webhook(&runtime, vec![respond]).await;
+
+        let response = runtime
+            .execute_webhook(&flow_id, serde_json::json!({ "body": {} }), Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, 201);
+        assert_eq!(response.headers["Location"], "/vms/101");
+        assert_eq!(response.body, serde_json::json!({ "vmid": 101, "created": true }));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_flow_without_response_is_accepted() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("counting".to_string(), Arc::new(CountingNode { executed: executed.clone() }))
+            .unwrap();
+        let runtime = FlowRuntime::new(Arc::new(registry));
+        let flow_id = deploy_webhook(&runtime, vec![flow_node("count", "counting", HashMap::new())]).await;
+
+        let response = runtime
+            .execute_webhook(&flow_id, serde_json::json!({}), Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, 202);
+        assert!(response.body["execution_id"].is_string());
+        assert_eq!(executed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_flow_without_enabled_webhook_trigger_is_not_found() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let (runtime, flow_id) = runtime_with_flow(executed.clone()).await;
+
+        let mut disabled = test_flow(vec![flow_node("count", "counting", HashMap::new())]);
+        disabled.triggers.push(webhook_trigger(false));
+        let disabled_id = disabled.id;
+        runtime.deploy_flow(disabled).await.unwrap();
+
+        for id in [flow_id, disabled_id] {
+            let result = runtime.execute_webhook(&id, serde_json::json!({}), Duration::from_secs(5)).await;
+            assert!(matches!(result, Err(GhostFlowError::NotFoundError { .. })));
+        }
+        assert_eq!(executed.load(Ordering::SeqCst), 0);
+        assert!(runtime.list_executions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_body_is_checked_against_the_input_schema() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("counting".to_string(), Arc::new(CountingNode { executed: executed.clone() }))
+            .unwrap();
+        let runtime = FlowRuntime::new(Arc::new(registry));
+
+        let mut flow = test_flow(vec![flow_node("count", "counting", HashMap::new())]);
+        flow.triggers.push(webhook_trigger(true));
+        flow.triggers.push(FlowTrigger {
+            id: "manual".to_string(),
+            trigger_type: TriggerType::Manual,
+            config: HashMap::from([(
+                "input_schema".to_string(),
+                serde_json::json!([{
+                    "name": "vmid",
+                    "display_name": "VM ID",
+                    "description": "Proxmox VM to restart",
+                    "variable_type": "number",
+                    "default_value": null,
+                    "required": true,
+                    "placeholder": null,
+                    "validation": null,
+                }]),
+            )]),
+            enabled: true,
+            priority: 0,
+        });
+        let flow_id = flow.id;
+        runtime.deploy_flow(flow).await.unwrap();
+
+        let rejected = runtime
+            .execute_webhook(&flow_id, serde_json::json!({ "body": { "vmid": "abc" } }), Duration::from_secs(5))
+            .await;
+        assert!(matches!(rejected, Err(GhostFlowError::InvalidInput { .. })));
+        assert_eq!(executed.load(Ordering::SeqCst), 0);
+
+        let accepted = runtime
+            .execute_webhook(&flow_id, serde_json::json!({ "body": { "vmid": 101 } }), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(accepted.status, 202);
+    }
+
+    #[tokio::test]
+    async fn test_redelivered_webhook_is_acknowledged_without_running() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("counting".to_string(), Arc::new(CountingNode { executed: executed.clone() }))
+            .unwrap();
+        registry
+            .register_node("webhook_trigger".to_string(), Arc::new(ghostflow_nodes::WebhookTriggerNode::new()))
+            .unwrap();
+        let runtime = FlowRuntime::new(Arc::new(registry));
+
+        let trigger = flow_node(
+            "github",
+            "webhook_trigger",
+            HashMap::from([
+                ("path".to_string(), serde_json::json!("/github")),
+                ("delivery_id_header".to_string(), serde_json::json!("X-GitHub-Delivery")),
+                ("replay_window_secs".to_string(), serde_json::json!(300)),
+            ]),
+        );
+        let flow_id = deploy(&runtime, vec![trigger, flow_node("count", "counting", HashMap::new())]).await;
+        let delivery = |id: &str| {
+            serde_json::json!({
+                "headers": { "x-github-delivery": id },
+                "body": { "action": "opened" },
+            })
+        };
+
+        let first = runtime
+            .execute_webhook(&flow_id, delivery("d-1"), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(first.status, 202);
+        let redelivered = runtime
+            .execute_webhook(&flow_id, delivery("d-1"), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(redelivered.status, 200);
+        assert_eq!(redelivered.body["status"], "duplicate");
+        assert_eq!(redelivered.body["execution_id"], first.body["execution_id"]);
+        assert_eq!(executed.load(Ordering::SeqCst), 1);
+
+        runtime
+            .execute_webhook(&flow_id, delivery("d-2"), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(executed.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_delivery_id_is_forgotten_after_the_window() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("counting".to_string(), Arc::new(CountingNode { executed: executed.clone() }))
+            .unwrap();
+        registry
+            .register_node("webhook_trigger".to_string(), Arc::new(ghostflow_nodes::WebhookTriggerNode::new()))
+            .unwrap();
+        let runtime = FlowRuntime::new(Arc::new(registry));
+
+        let trigger = flow_node(
+            "github",
+            "webhook_trigger",
+            HashMap::from([
+                ("path".to_string(), serde_json::json!("/github")),
+                ("delivery_id_header".to_string(), serde_json::json!("X-GitHub-Delivery")),
+                ("replay_window_secs".to_string(), serde_json::json!(1)),
+            ]),
+        );
+        let flow_id = deploy(&runtime, vec![trigger, flow_node("count", "counting", HashMap::new())]).await;
+        let delivery = serde_json::json!({ "headers": { "x-github-delivery": "d-1" }, "body": {} });
+
+        runtime.execute_webhook(&flow_id, delivery.clone(), Duration::from_secs(5)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        let late = runtime.execute_webhook(&flow_id, delivery, Duration::from_secs(5)).await.unwrap();
+
+        assert_eq!(late.status, 202);
+        assert_eq!(executed.load(Ordering::SeqCst), 2);
+    }
+
+    /// Sleeps briefly, recording the most runs seen in progress at once
+    struct SlowNode {
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+        executed: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Node for SlowNode {
+        fn definition(&self) -> NodeDefinition {
+            let mut definition = CountingNode { executed: self.executed.clone() }.definition();
+            definition.id = "slow".to_string();
+            definition
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, _context: ExecutionContext) -> Result<serde_json::Value> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            self.executed.fetch_add(1, Ordering::SeqCst);
+            Ok(serde_json::json!({}))
+        }
+    }
+
+    async fn limited_runtime(policy: OverflowPolicy) -> (Arc<FlowRuntime>, Uuid, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+        let peak = Arc::new(AtomicUsize::new(0));
+        let executed = Arc::new(AtomicUsize::new(0));
+        let mut registry = BasicNodeRegistry::new();
+        let node = SlowNode {
+            running: Arc::new(AtomicUsize::new(0)),
+            peak: peak.clone(),
+            executed: executed.clone(),
+        };
+        registry.register_node("slow".to_string(), Arc::new(node)).unwrap();
+        let runtime = FlowRuntime::new(Arc::new(registry));
+
+        let flow_id = deploy(&runtime, vec![flow_node("slow", "slow", HashMap::new())]).await;
+        let mut flow = runtime.get_flow(&flow_id).await.unwrap();
+        flow.max_concurrent_executions = Some(1);
+        flow.overflow_policy = policy;
+        runtime.deploy_flow(flow).await.unwrap();
+        (Arc::new(runtime), flow_id, peak, executed)
+    }
+
+    #[tokio::test]
+    async fn test_queue_policy_runs_overlapping_triggers_one_at_a_time() {
+        let (runtime, flow_id, peak, executed) = limited_runtime(OverflowPolicy::Queue).await;
+
+        let run = || {
+            let runtime = runtime.clone();
+            tokio::spawn(async move { runtime.execute_flow_manually(&flow_id, serde_json::json!({}), false, None).await })
+        };
+        let (first, second) = tokio::join!(run(), run());
+        let (first, second) = (first.unwrap().unwrap(), second.unwrap().unwrap());
+
+        assert_eq!(first.status, ExecutionStatus::Completed);
+        assert_eq!(second.status, ExecutionStatus::Completed);
+        assert_eq!(executed.load(Ordering::SeqCst), 2);
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_drop_policy_discards_trigger_at_limit() {
+        let (runtime, flow_id, _peak, executed) = limited_runtime(OverflowPolicy::Drop).await;
+
+        let first = {
+            let runtime = runtime.clone();
+            tokio::spawn(async move { runtime.execute_flow_manually(&flow_id, serde_json::json!({}), false, None).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let second = runtime.execute_flow_manually(&flow_id, serde_json::json!({}), false, None).await;
+
+        assert!(matches!(second, Err(GhostFlowError::RateLimitError { .. })));
+        assert_eq!(first.await.unwrap().unwrap().status, ExecutionStatus::Completed);
+        assert_eq!(executed.load(Ordering::SeqCst), 1);
+
+        // The slot is free again once the first run finishes
+        runtime.execute_flow_manually(&flow_id, serde_json::json!({}), false, None).await.unwrap();
+        assert_eq!(executed.load(Ordering::SeqCst), 2);
+    }
+
+    /// Records the flow of each run as it starts, then sleeps briefly
+    struct OrderNode {
+        started: Arc<std::sync::Mutex<Vec<Uuid>>>,
+    }
+
+    #[async_trait]
+    impl Node for OrderNode {
+        fn definition(&self) -> NodeDefinition {
+            let mut definition = CountingNode { executed: Arc::new(AtomicUsize::new(0)) }.definition();
+            definition.id = "order".to_string();
+            definition
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, context: ExecutionContext) -> Result<serde_json::Value> {
+            self.started.lock().unwrap().push(context.flow_id);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(serde_json::json!({}))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queued_executions_are_dispatched_by_priority() {
+        let started = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("order".to_string(), Arc::new(OrderNode { started: started.clone() }))
+            .unwrap();
+        let runtime = Arc::new(FlowRuntime::new(Arc::new(registry)).with_max_concurrency(1));
+
+        let flow_with_priority = |priority| {
+            let mut flow = test_flow(vec![flow_node("order", "order", HashMap::new())]);
+            flow.triggers.push(FlowTrigger {
+                id: "manual".to_string(),
+                trigger_type: TriggerType::Manual,
+                config: HashMap::new(),
+                enabled: true,
+                priority,
+            });
+            flow
+        };
+        let (routine, critical) = (flow_with_priority(0), flow_with_priority(10));
+        let (routine_id, critical_id) = (routine.id, critical.id);
+        runtime.deploy_flow(routine).await.unwrap();
+        runtime.deploy_flow(critical).await.unwrap();
+
+        let run = |flow_id: Uuid| {
+            let runtime = runtime.clone();
+            tokio::spawn(async move { runtime.execute_flow_manually(&flow_id, serde_json::json!({}), false, None).await })
+        };
+        let wait_for_queue = |len: usize| {
+            let runtime = runtime.clone();
+            async move {
+                for _ in 0..100 {
+                    if runtime.dispatcher.queued() == len {
+                        return;
+                    }
+                    tokio::time::sleep(Duration::from_millis(2)).await;
+                }
+                panic!("queue never reached {} executions", len);
+            }
+        };
+
+        // The first run takes the only slot; the rest queue behind it
+        let running = run(routine_id);
+        for _ in 0..100 {
+            if !started.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        let queued_routine = run(routine_id);
+        wait_for_queue(1).await;
+        let queued_critical = run(critical_id);
+        wait_for_queue(2).await;
+
+        let critical = queued_critical.await.unwrap().unwrap();
+        for task in [running, queued_routine] {
+            assert_eq!(task.await.unwrap().unwrap().status, ExecutionStatus::Completed);
+        }
+
+        assert_eq!(*started.lock().unwrap(), vec![routine_id, critical_id, routine_id]);
+        assert_eq!(critical.trigger.priority, 10);
+        assert_eq!(runtime.dispatcher.queued(), 0);
+    }
+
+    /// Fails every attempt until `broken` is cleared
+    struct FlakyNode {
+        broken: Arc<std::sync::atomic::AtomicBool>,
+        attempts: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Node for FlakyNode {
+        fn definition(&self) -> NodeDefinition {
+            let mut definition = CountingNode { executed: self.attempts.clone() }.definition();
+            definition.id = "flaky".to_string();
+            definition
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, _context: ExecutionContext) -> Result<serde_json::Value> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            if self.broken.load(Ordering::SeqCst) {
+                return Err(GhostFlowError::NetworkError("connection refused".to_string()));
+            }
+            Ok(serde_json::json!({ "delivered": true }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_execution_is_dead_lettered_and_retried() {
+        let broken = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let mut registry = BasicNodeRegistry::new();
+        let node = FlakyNode { broken: broken.clone(), attempts: attempts.clone() };
+        registry.register_node("flaky".to_string(), Arc::new(node)).unwrap();
+        let runtime = FlowRuntime::new(Arc::new(registry));
+
+        let mut notify = flow_node("notify", "flaky", HashMap::new());
+        notify.retry_config = Some(RetryConfig {
+            max_attempts: 3,
+            delay_ms: 1,
+            backoff_multiplier: 1.0,
+            max_delay_ms: 1,
+        });
+        let flow_id = deploy(&runtime, vec![notify]).await;
+
+        let input = serde_json::json!({ "alert": "disk full" });
+        let failed = runtime.execute_flow_manually(&flow_id, input.clone(), false, None).await.unwrap();
+        assert_eq!(failed.status, ExecutionStatus::Failed);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        let dead_letters = runtime.list_dead_letters().await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        let dead_letter = &dead_letters[0];
+        assert_eq!(dead_letter.execution_id, failed.id);
+        assert_eq!(dead_letter.flow_id, flow_id);
+        assert_eq!(dead_letter.failed_node.as_deref(), Some("notify"));
+        assert_eq!(dead_letter.attempts, 3);
+        assert!(dead_letter.error.contains("connection refused"), "{}", dead_letter.error);
+        assert_eq!(dead_letter.input_data, input);
+        assert_eq!(dead_letter.trigger.trigger_type, "manual");
+
+        broken.store(false, Ordering::SeqCst);
+        let retried = runtime.retry_dead_letter(&failed.id).await.unwrap();
+        assert_ne!(retried.id, failed.id);
+        assert_eq!(retried.status, ExecutionStatus::Completed);
+        assert_eq!(retried.input_data, input);
+        assert_eq!(retried.trigger.metadata["retry_of"], serde_json::json!(failed.id));
+        assert!(runtime.list_dead_letters().await.unwrap().is_empty());
+        assert!(matches!(
+            runtime.retry_dead_letter(&failed.id).await,
+            Err(GhostFlowError::NotFoundError { .. })
+        ));
+    }
+
+    /// Hands out a new ticket number on every run
+    struct TicketNode {
+        issued: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Node for TicketNode {
+        fn definition(&self) -> NodeDefinition {
+            let mut definition = CountingNode { executed: self.issued.clone() }.definition();
+            definition.id = "ticket".to_string();
+            definition
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, _context: ExecutionContext) -> Result<serde_json::Value> {
+            Ok(serde_json::json!({ "ticket": self.issued.fetch_add(1, Ordering::SeqCst) + 1 }))
+        }
+
+        fn is_deterministic(&self) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_execution_resumes_in_new_runtime() {
+        let issued = Arc::new(AtomicUsize::new(0));
+        let closed = Arc::new(AtomicUsize::new(0));
+        let storage = Arc::new(ghostflow_core::MemoryExecutionStateStore::new());
+        let runtime = {
+            let issued = issued.clone();
+            let closed = closed.clone();
+            let storage = storage.clone();
+            move || {
+                // Each runtime stands for a process with its own waiting approvals
+                let services = Services::default();
+                let mut registry = BasicNodeRegistry::new();
+                registry.register_node("ticket".to_string(), Arc::new(TicketNode { issued: issued.clone() })).unwrap();
+                registry
+                    .register_node(
+                        "wait_for_approval".to_string(),
+                        Arc::new(ghostflow_nodes::WaitForApprovalNode::new().with_approvals(services.approvals.clone())),
+                    )
+                    .unwrap();
+                registry.register_node("counting".to_string(), Arc::new(CountingNode { executed: closed.clone() })).unwrap();
+                FlowRuntime::new(Arc::new(registry)).with_state_storage(storage.clone()).with_services(services)
+            }
+        };
+        let edge = |source: &str, source_port: &str, target: &str, target_port: &str| FlowEdge {
+            id: format!("{}-{}", source, target),
+            source_node: source.to_string(),
+            target_node: target.to_string(),
+            source_port: Some(source_port.to_string()),
+            target_port: Some(target_port.to_string()),
+            condition: None,
+            coerce: false,
+        };
+
+        let first = runtime();
+        let flow_id = deploy(
+            &first,
+            vec![
+                flow_node("ticket", "ticket", HashMap::new()),
+                flow_node("gate", "wait_for_approval", HashMap::new()),
+                flow_node("close", "counting", HashMap::new()),
+            ],
+        )
+        .await;
+        let mut flow = first.get_flow(&flow_id).await.unwrap();
+        flow.edges = vec![edge("ticket", "ticket", "gate", "input"), edge("gate", "input", "close", "ticket")];
+        first.deploy_flow(flow.clone()).await.unwrap();
+
+        // Run until the flow waits for approval, then drop the runtime
+        let approvals = first.services().approvals.clone();
+        let task = tokio::spawn(async move { first.execute_flow_manually(&flow_id, serde_json::json!({}), false, None).await });
+        let mut saved = None;
+        for _ in 0..100 {
+            if let Some(state) = storage.list_states().await.unwrap().into_iter().find(|s| s.flow_id == flow_id) {
+                if !approvals.pending(state.execution_id).is_empty() {
+                    saved = Some(state);
+                    break;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        task.abort();
+        assert!(task.await.unwrap_err().is_cancelled());
+        let saved = saved.expect("execution never paused for approval");
+        let execution_id = saved.execution_id;
+        assert_eq!(saved.node_outputs["ticket"], serde_json::json!({ "ticket": 1 }));
+        assert_eq!(saved.pending_nodes, vec!["gate", "close"]);
+
+        let second = Arc::new(runtime());
+        second.deploy_flow(flow).await.unwrap();
+        let approvals = second.services().approvals.clone();
+        let resumed = {
+            let second = second.clone();
+            tokio::spawn(async move { second.resume_execution(&execution_id).await })
+        };
+        let mut decided = false;
+        for _ in 0..100 {
+            let approval = ghostflow_core::Approval::new(ghostflow_core::ApprovalDecision::Approved, None, None);
+            if approvals.decide(execution_id, Some("gate"), approval).is_ok() {
+                decided = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(decided, "resumed execution never asked for approval");
+
+        let execution = resumed.await.unwrap().unwrap();
+        assert_eq!(execution.id, execution_id);
+        assert_eq!(execution.status, ExecutionStatus::Completed);
+        let ran: Vec<&str> = execution.node_records.iter().map(|r| r.node_id.as_str()).collect();
+        assert_eq!(ran, vec!["ticket", "gate", "close"]);
+        assert_eq!(execution.node_records[1].output.as_ref().unwrap()["input"], 1);
+        // The non-deterministic ticket node ran once, before the restart
+        assert_eq!(issued.load(Ordering::SeqCst), 1);
+        assert_eq!(closed.load(Ordering::SeqCst), 1);
+        assert!(storage.load_state(&execution_id).await.unwrap().is_none());
+        assert!(second.get_execution(&execution_id).await.is_some());
+    }
+    /// Emits its events once, then stays open
+    struct ScriptedConnector {
+        events: std::sync::Mutex<Vec<serde_json::Value>>,
+    }
+
+    struct ScriptedStream {
+        events: Vec<serde_json::Value>,
+    }
+
+    #[async_trait]
+    impl crate::ChangeStream for ScriptedStream {
+        async fn next_change(&mut self) -> Result<Option<serde_json::Value>> {
+            if self.events.is_empty() {
+                std::future::pending::<()>().await;
+            }
+            Ok(Some(self.events.remove(0)))
+        }
+    }
+
+    #[async_trait]
+    impl ChangeStreamConnector for ScriptedConnector {
+        async fn open(
+            &self,
+            _spec: &ChangeStreamSpec,
+            _resume_after: Option<serde_json::Value>,
+        ) -> Result<Box<dyn crate::ChangeStream>> {
+            let events = std::mem::take(&mut *self.events.lock().unwrap());
+            Ok(Box::new(ScriptedStream { events }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_change_stream_trigger_runs_flow_per_event() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("counting".to_string(), Arc::new(CountingNode { executed: executed.clone() }))
+            .unwrap();
+        let connector = ScriptedConnector {
+            events: std::sync::Mutex::new(vec![
+                serde_json::json!({ "_id": { "_data": "t1" }, "operationType": "insert", "fullDocument": { "_id": 1 } }),
+                serde_json::json!({ "_id": { "_data": "t2" }, "operationType": "update", "documentKey": { "_id": 1 } }),
+            ]),
+        };
+        let tokens = Arc::new(MemoryResumeTokenStore::new());
+        let runtime = FlowRuntime::new(Arc::new(registry))
+            .with_change_stream_connector(Arc::new(connector))
+            .with_resume_token_storage(tokens.clone());
+
+        let mut flow = test_flow(vec![flow_node("count", "counting", HashMap::new())]);
+        flow.triggers.push(FlowTrigger {
+            id: "alerts".to_string(),
+            trigger_type: TriggerType::MongoChangeStream {
+                connection_string: "mongodb://localhost:27017".to_string(),
+                database: "soc".to_string(),
+                collection: "alerts".to_string(),
+                pipeline: vec![],
+            },
+            config: HashMap::new(),
+            enabled: true,
+            priority: 0,
+        });
+        let flow_id = flow.id;
+        runtime.deploy_flow(flow).await.unwrap();
+
+        let key = format!("{}/alerts", flow_id);
+        for _ in 0..100 {
+            if tokens.get_resume_token(&key).await.unwrap() == Some(serde_json::json!({ "_data": "t2" })) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        runtime.undeploy_flow(&flow_id).await.unwrap();
+
+        assert_eq!(executed.load(Ordering::SeqCst), 2);
+        let mut operations: Vec<_> = runtime
+            .list_executions()
+            .await
+            .into_iter()
+            .map(|execution| {
+                assert_eq!(execution.trigger.trigger_type, "mongodb_change_stream");
+                execution.input_data["operationType"].as_str().unwrap().to_string()
+            })
+            .collect();
+        operations.sort();
+        assert_eq!(operations, ["insert", "update"]);
+    }
+}
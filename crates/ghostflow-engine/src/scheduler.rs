@@ -1,8 +1,13 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+use crate::poll::PollTrigger;
 use ghostflow_core::{GhostFlowError, Result};
 use ghostflow_schema::{Flow, FlowTrigger, TriggerType};
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
@@ -21,6 +26,7 @@ struct ScheduledFlow {
 struct ScheduledTrigger {
     trigger: FlowTrigger,
     next_run: Option<chrono::DateTime<chrono::Utc>>,
+    poller: Option<Arc<Mutex<PollTrigger>>>,
 }
 
 impl FlowScheduler {
@@ -46,6 +52,7 @@ impl FlowScheduler {
                     ScheduledTrigger {
                         trigger: trigger.clone(),
                         next_run: Some(next_run),
+                        poller: None,
                     }
                 }
                 TriggerType::Webhook { .. } => {
@@ -53,6 +60,7 @@ impl FlowScheduler {
                     ScheduledTrigger {
                         trigger: trigger.clone(),
                         next_run: None,
+                        poller: None,
                     }
                 }
                 TriggerType::Manual => {
@@ -60,6 +68,24 @@ impl FlowScheduler {
                     ScheduledTrigger {
                         trigger: trigger.clone(),
                         next_run: None,
+                        poller: None,
+                    }
+                }
+                TriggerType::MongoChangeStream { .. } => {
+                    // Change events start the flow as they arrive; see `ChangeStreamWatcher`
+                    ScheduledTrigger {
+                        trigger: trigger.clone(),
+                        next_run: None,
+                        poller: None,
+                    }
+                }
+                TriggerType::Poll { .. } => {
+                    // First poll runs on the next tick and records the baseline
+                    let poller = PollTrigger::from_trigger(&trigger.trigger_type)?;
+                    ScheduledTrigger {
+                        trigger: trigger.clone(),
+                        next_run: Some(chrono::Utc::now()),
+                        poller: poller.map(|p| Arc::new(Mutex::new(p))),
                     }
                 }
             };
@@ -93,6 +119,7 @@ impl FlowScheduler {
         }
     }
 
+    /// Flows with a trigger that is due, highest trigger priority first
     pub async fn get_ready_flows(&self) -> Vec<(Flow, FlowTrigger)> {
         let now = chrono::Utc::now();
         let mut ready_flows = Vec::new();
@@ -112,6 +139,7 @@ impl FlowScheduler {
             }
         }
         
+        ready_flows.sort_by_key(|(_, trigger)| std::cmp::Reverse(trigger.priority));
         ready_flows
     }
 
@@ -127,6 +155,11 @@ impl FlowScheduler {
                             scheduled_trigger.next_run = Some(next_run);
                             info!("Updated next run for trigger {} to {}", trigger_id, next_run);
                         }
+                        TriggerType::Poll { .. } => {
+                            if let Some(poller) = &scheduled_trigger.poller {
+                                scheduled_trigger.next_run = Some(poller.lock().await.next_poll(chrono::Utc::now()));
+                            }
+                        }
                         _ => {
                             // Non-cron triggers don't need next run updates
                         }
@@ -139,22 +172,23 @@ impl FlowScheduler {
         Ok(())
     }
 
+    /// Polling state for a `TriggerType::Poll` trigger of a scheduled flow
+    pub async fn poller(&self, flow_id: &Uuid, trigger_id: &str) -> Option<Arc<Mutex<PollTrigger>>> {
+        let scheduled_flows = self.scheduled_flows.read().await;
+        scheduled_flows
+            .get(flow_id)?
+            .triggers
+            .iter()
+            .find(|t| t.trigger.id == trigger_id)
+            .and_then(|t| t.poller.clone())
+    }
+
     fn calculate_next_cron_run(
         &self,
         expression: &str,
-        _timezone: Option<&str>,
+        timezone: Option<&str>,
     ) -> Result<chrono::DateTime<chrono::Utc>> {
-        // Simple implementation - in a real system, use a proper cron library like `cron`
-        // For now, we'll just add 1 minute to simulate a basic schedule
-        
-        // TODO: Implement proper cron parsing
-        // - Support standard cron expressions (minute hour day month weekday)
-        // - Handle timezone conversions
-        // - Validate expressions
-        
-        let next_run = chrono::Utc::now() + chrono::Duration::minutes(1);
-        
-        Ok(next_run)
+        next_cron_run(expression, timezone, chrono::Utc::now())
     }
 
     pub async fn list_scheduled_flows(&self) -> Vec<Flow> {
@@ -170,4 +204,82 @@ impl Default for FlowScheduler {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// The first time after `after` that `expression` fires, with the schedule
+/// read as wall-clock time in `timezone` (an IANA name such as
+/// `America/New_York`; UTC when unset). `0 0 9 * * MON-FRI` therefore fires
+/// at 9am local on both sides of a DST change. Expressions take a leading
+/// seconds field; classic five-field expressions are accepted and fire at
+/// second 0.
+pub fn next_cron_run(expression: &str, timezone: Option<&str>, after: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let expression = expression.trim();
+    let schedule = if expression.split_whitespace().count() == 5 {
+        Schedule::from_str(&format!("0 {}", expression))
+    } else {
+        Schedule::from_str(expression)
+    }
+    .map_err(|e| GhostFlowError::ValidationError {
+        message: format!("Invalid cron expression '{}': {}", expression, e),
+    })?;
+
+    let timezone = match timezone.map(str::trim).filter(|tz| !tz.is_empty()) {
+        Some(name) => name.parse::<Tz>().map_err(|_| GhostFlowError::ValidationError {
+            message: format!("Unknown timezone '{}'", name),
+        })?,
+        None => Tz::UTC,
+    };
+
+    schedule
+        .after(&after.with_timezone(&timezone))
+        .next()
+        .map(|next| next.with_timezone(&Utc))
+        .ok_or_else(|| GhostFlowError::ValidationError {
+            message: format!("Cron expression '{}' never fires again", expression),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_weekday_schedule_keeps_local_time_across_spring_forward() {
+        let tz = Some("America/New_York");
+
+        // Friday 7 March 2025, 9am EST (UTC-5)
+        let friday = next_cron_run("0 0 9 * * MON-FRI", tz, utc(2025, 3, 7, 12, 0)).unwrap();
+        assert_eq!(friday, utc(2025, 3, 7, 14, 0));
+
+        // Clocks go forward on Sunday 9 March; Monday's 9am is EDT (UTC-4)
+        let monday = next_cron_run("0 0 9 * * MON-FRI", tz, friday).unwrap();
+        assert_eq!(monday, utc(2025, 3, 10, 13, 0));
+        assert_eq!(monday.with_timezone(&Tz::America__New_York).format("%H:%M").to_string(), "09:00");
+    }
+
+    #[test]
+    fn test_hourly_schedule_skips_the_missing_hour() {
+        // 02:00 local does not exist on 9 March 2025 in New York; the hour
+        // after 01:00 EST is 03:00 EDT, one real hour later
+        let next = next_cron_run("0 0 * * * *", Some("America/New_York"), utc(2025, 3, 9, 6, 30)).unwrap();
+        assert_eq!(next, utc(2025, 3, 9, 7, 0));
+        assert_eq!(next.with_timezone(&Tz::America__New_York).format("%H:%M").to_string(), "03:00");
+    }
+
+    #[test]
+    fn test_schedule_defaults_to_utc_and_accepts_five_fields() {
+        let next = next_cron_run("30 9 * * *", None, utc(2025, 3, 7, 12, 0)).unwrap();
+        assert_eq!(next, utc(2025, 3, 8, 9, 30));
+    }
+
+    #[test]
+    fn test_invalid_timezone_and_expression_are_rejected() {
+        assert!(next_cron_run("0 0 9 * * *", Some("Mars/Olympus_Mons"), Utc::now()).is_err());
+        assert!(next_cron_run("every morning", None, Utc::now()).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use ghostflow_core::{GhostFlowError, Result};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::{runtime, trace, Resource};
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Setting this to an OTLP/gRPC endpoint (e.g. `http://localhost:4317`)
+/// turns on OpenTelemetry export. Unset, only the usual log output is
+/// produced and no trace context is sent on outbound requests.
+pub const OTEL_ENDPOINT_ENV: &str = "GHOSTFLOW_OTEL_ENDPOINT";
+
+/// Set to `json` to write one JSON object per log line instead of text.
+pub const LOG_FORMAT_ENV: &str = "GHOSTFLOW_LOG_FORMAT";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One object per line, with the fields of every enclosing span under
+    /// `spans`, so lines logged while a node runs carry `execution.id` and
+    /// `node.id`.
+    Json,
+}
+
+impl LogFormat {
+    /// The format selected by [`LOG_FORMAT_ENV`].
+    pub fn from_env() -> Self {
+        match std::env::var(LOG_FORMAT_ENV) {
+            Ok(format) if format.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// Log output in `format` written to `writer`. Text lines are prefixed with
+/// the enclosing spans, JSON lines list them.
+pub fn log_layer<S, W>(format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer().with_writer(writer);
+    match format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Json => layer.json().with_current_span(true).with_span_list(true).boxed(),
+    }
+}
+
+/// Install the global tracing subscriber: logs filtered by `RUST_LOG` in the
+/// [`LOG_FORMAT_ENV`] format, plus an OpenTelemetry layer when
+/// [`OTEL_ENDPOINT_ENV`] is set.
+/// Executions then export a root span per flow run with a child span per
+/// node, and integration nodes send W3C `traceparent` headers.
+pub fn init_tracing(service_name: &str) -> Result<()> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(log_layer(LogFormat::from_env(), std::io::stdout));
+
+    let Some(endpoint) = std::env::var(OTEL_ENDPOINT_ENV).ok().filter(|e| !e.is_empty()) else {
+        return registry.try_init().map_err(init_error);
+    };
+
+    let provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&endpoint))
+        .with_trace_config(
+            trace::Config::default()
+                .with_resource(Resource::new(vec![KeyValue::new("service.name", service_name.to_string())])),
+        )
+        .install_batch(runtime::Tokio)
+        .map_err(init_error)?;
+
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let tracer = provider.tracer("ghostflow");
+    opentelemetry::global::set_tracer_provider(provider);
+
+    registry
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()
+        .map_err(init_error)?;
+
+    tracing::info!("Exporting traces to {}", endpoint);
+    Ok(())
+}
+
+/// Flush spans that have not been exported yet. Call before exiting.
+pub fn shutdown_tracing() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+fn init_error(error: impl std::fmt::Display) -> GhostFlowError {
+    GhostFlowError::ConfigurationError {
+        message: format!("Failed to initialise tracing: {}", error),
+    }
+}
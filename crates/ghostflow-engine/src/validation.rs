@@ -0,0 +1,317 @@
+use ghostflow_core::{GhostFlowError, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{DataType, NodeDefinition, NodeParameter};
+use regex::Regex;
+use serde_json::Value;
+
+/// Check `input` against the parameters declared in `definition`: required
+/// presence, value type, select-option membership and any
+/// `ParameterValidation` bounds. Every failing field is reported in a single
+/// `ValidationError`.
+pub fn validate_parameters(definition: &NodeDefinition, input: &Value) -> Result<()> {
+    let mut failures = Vec::new();
+
+    for param in &definition.parameters {
+        match input.get(&param.name) {
+            None | Some(Value::Null) => {
+                if param.required && param.default_value.is_none() {
+                    failures.push(format!("{}: is required", param.name));
+                }
+            }
+            Some(value) => {
+                if let Err(message) = check_parameter(param, value) {
+                    failures.push(format!("{}: {}", param.name, message));
+                }
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(GhostFlowError::ValidationError {
+            message: format!(
+                "Invalid parameters for node '{}': {}",
+                definition.id,
+                failures.join("; ")
+            ),
+        })
+    }
+}
+
+/// Check values present on the node's input ports against the port data
+/// types. Missing ports are not an error; nodes decide what they need.
+pub fn validate_input_ports(definition: &NodeDefinition, input: &Value) -> Result<()> {
+    let failures: Vec<String> = definition
+        .inputs
+        .iter()
+        .filter_map(|port| {
+            let value = input.get(&port.name).filter(|v| !v.is_null())?;
+            (!port.data_type.accepts(value)).then(|| {
+                format!("{}: expected {:?} data, got {}", port.name, port.data_type, describe(value))
+            })
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(GhostFlowError::ValidationError {
+            message: format!(
+                "Invalid input for node '{}': {}",
+                definition.id,
+                failures.join("; ")
+            ),
+        })
+    }
+}
+
+/// Convert `value` to `target` for an edge declared with `coerce: true`.
+/// Values the target already accepts, and nulls, pass through unchanged.
+/// Strings and numbers convert both ways, as do numbers and booleans (0 and
+/// 1 only) and scalars and single-element arrays. Anything else, e.g. an
+/// object into a number port, is a `ValidationError`.
+pub fn coerce_value(value: Value, target: &DataType) -> Result<Value> {
+    if value.is_null() || target.accepts(&value) {
+        return Ok(value);
+    }
+
+    let coerced = match (target, &value) {
+        (DataType::Array, Value::String(_) | Value::Number(_) | Value::Bool(_)) => {
+            Some(Value::Array(vec![value.clone()]))
+        }
+        (DataType::String | DataType::Number | DataType::Boolean, Value::Array(items)) if items.len() == 1 => {
+            return coerce_value(items[0].clone(), target).map_err(|_| cannot_coerce(&value, target));
+        }
+        (DataType::Number, Value::String(text)) => {
+            let text = text.trim();
+            text.parse::<i64>()
+                .map(Value::from)
+                .ok()
+                .or_else(|| text.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number))
+        }
+        (DataType::Number, Value::Bool(flag)) => Some(Value::from(*flag as i64)),
+        (DataType::String, Value::Number(number)) => Some(Value::String(number.to_string())),
+        (DataType::String, Value::Bool(flag)) => Some(Value::String(flag.to_string())),
+        (DataType::Boolean, Value::Number(number)) => match number.as_f64() {
+            Some(n) if n == 0.0 => Some(Value::Bool(false)),
+            Some(n) if n == 1.0 => Some(Value::Bool(true)),
+            _ => None,
+        },
+        _ => None,
+    };
+    coerced.ok_or_else(|| cannot_coerce(&value, target))
+}
+
+/// Data type expected on `port` of `definition`: the input port of that
+/// name, else the parameter of that name. Free-form parameters take anything.
+pub fn port_data_type(definition: &NodeDefinition, port: &str) -> Option<DataType> {
+    if let Some(input) = definition.inputs.iter().find(|p| p.name == port) {
+        return Some(input.data_type.clone());
+    }
+    let param = definition.parameters.iter().find(|p| p.name == port)?;
+    Some(match param.param_type {
+        ParameterType::String | ParameterType::Secret | ParameterType::Code | ParameterType::File => {
+            DataType::String
+        }
+        ParameterType::Number => DataType::Number,
+        ParameterType::Boolean => DataType::Boolean,
+        ParameterType::Array | ParameterType::MultiSelect => DataType::Array,
+        ParameterType::Object | ParameterType::Select => DataType::Any,
+    })
+}
+
+fn cannot_coerce(value: &Value, target: &DataType) -> GhostFlowError {
+    GhostFlowError::ValidationError {
+        message: match value {
+            Value::Object(_) | Value::Array(_) => format!("Cannot coerce {} to {:?}", describe(value), target),
+            scalar => format!("Cannot coerce {} {} to {:?}", describe(scalar), display(scalar), target),
+        },
+    }
+}
+
+fn check_parameter(param: &NodeParameter, value: &Value) -> std::result::Result<(), String> {
+    let type_ok = match param.param_type {
+        ParameterType::String | ParameterType::Secret | ParameterType::Code | ParameterType::File => {
+            value.is_string()
+        }
+        ParameterType::Number => value.is_number(),
+        ParameterType::Boolean => value.is_boolean(),
+        // Object parameters double as free-form JSON payloads (e.g. raw HTTP bodies)
+        ParameterType::Object => true,
+        ParameterType::Array | ParameterType::MultiSelect => value.is_array(),
+        ParameterType::Select => !value.is_object() && !value.is_array(),
+    };
+    if !type_ok {
+        return Err(format!("expected {}, got {}", type_name(&param.param_type), describe(value)));
+    }
+
+    if let Some(options) = param.options.as_ref().filter(|o| !o.is_empty()) {
+        let selected: Vec<&Value> = match value {
+            Value::Array(items) => items.iter().collect(),
+            other => vec![other],
+        };
+        for item in selected {
+            if !options.iter().any(|option| &option.value == item) {
+                let allowed: Vec<String> = options.iter().map(|o| display(&o.value)).collect();
+                return Err(format!(
+                    "{} is not one of {}",
+                    display(item),
+                    allowed.join(", ")
+                ));
+            }
+        }
+    }
+
+    let Some(validation) = &param.validation else {
+        return Ok(());
+    };
+
+    let length = match value {
+        Value::String(s) => Some(s.chars().count()),
+        Value::Array(items) => Some(items.len()),
+        _ => None,
+    };
+    if let Some(length) = length {
+        if let Some(min) = validation.min_length {
+            if length < min {
+                return Err(format!("length {} is below the minimum of {}", length, min));
+            }
+        }
+        if let Some(max) = validation.max_length {
+            if length > max {
+                return Err(format!("length {} exceeds the maximum of {}", length, max));
+            }
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(min) = validation.min_value {
+            if number < min {
+                return Err(format!("{} is below the minimum of {}", number, min));
+            }
+        }
+        if let Some(max) = validation.max_value {
+            if number > max {
+                return Err(format!("{} exceeds the maximum of {}", number, max));
+            }
+        }
+    }
+
+    if let (Some(pattern), Some(text)) = (&validation.pattern, value.as_str()) {
+        let regex = Regex::new(pattern)
+            .map_err(|e| format!("has an invalid validation pattern: {}", e))?;
+        if !regex.is_match(text) {
+            return Err(format!("does not match pattern {}", pattern));
+        }
+    }
+
+    Ok(())
+}
+
+fn type_name(param_type: &ParameterType) -> &'static str {
+    match param_type {
+        ParameterType::String | ParameterType::Secret | ParameterType::Code | ParameterType::File => {
+            "a string"
+        }
+        ParameterType::Number => "a number",
+        ParameterType::Boolean => "a boolean",
+        ParameterType::Object => "an object",
+        ParameterType::Array | ParameterType::MultiSelect => "an array",
+        ParameterType::Select => "a single value",
+    }
+}
+
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("'{}'", s),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ghostflow_core::Node;
+    use ghostflow_nodes::{DiscordWebhookNode, HttpRequestNode};
+    use serde_json::json;
+
+    fn message(result: Result<()>) -> String {
+        match result {
+            Err(GhostFlowError::ValidationError { message }) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        let definition = DiscordWebhookNode.definition();
+        let error = message(validate_parameters(&definition, &json!({ "content": "hi" })));
+        assert!(error.contains("webhook_url: is required"), "{}", error);
+    }
+
+    #[test]
+    fn test_pattern_mismatch() {
+        let definition = DiscordWebhookNode.definition();
+        assert!(validate_parameters(
+            &definition,
+            &json!({ "webhook_url": "https://discord.com/api/webhooks/123/abc" })
+        )
+        .is_ok());
+
+        let error = message(validate_parameters(
+            &definition,
+            &json!({ "webhook_url": "https://example.com/hook", "content": "x".repeat(2001) }),
+        ));
+        assert!(error.contains("webhook_url: does not match pattern"), "{}", error);
+        assert!(error.contains("content: length 2001 exceeds the maximum of 2000"), "{}", error);
+    }
+
+    #[test]
+    fn test_select_value_outside_options() {
+        let definition = HttpRequestNode::new().definition();
+        let error = message(validate_parameters(
+            &definition,
+            &json!({ "url": "https://example.com", "method": "FETCH" }),
+        ));
+        assert!(error.contains("method: 'FETCH' is not one of 'GET'"), "{}", error);
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let definition = HttpRequestNode::new().definition();
+        let error = message(validate_parameters(&definition, &json!({ "url": 42 })));
+        assert!(error.contains("url: expected a string, got a number"), "{}", error);
+    }
+
+    #[test]
+    fn test_coerce_numeric_string_to_number() {
+        assert_eq!(coerce_value(json!("42"), &DataType::Number).unwrap(), json!(42));
+        assert_eq!(coerce_value(json!(" 2.5 "), &DataType::Number).unwrap(), json!(2.5));
+        assert_eq!(coerce_value(json!(["7"]), &DataType::Number).unwrap(), json!(7));
+        assert_eq!(coerce_value(json!(1), &DataType::Boolean).unwrap(), json!(true));
+        assert_eq!(coerce_value(json!("x"), &DataType::Array).unwrap(), json!(["x"]));
+        assert_eq!(coerce_value(json!(3), &DataType::String).unwrap(), json!("3"));
+    }
+
+    #[test]
+    fn test_coerce_object_to_number_fails() {
+        let error = message(coerce_value(json!({ "count": 3 }), &DataType::Number).map(|_| ()));
+        assert!(error.contains("Cannot coerce an object"), "{}", error);
+        assert!(coerce_value(json!("forty-two"), &DataType::Number).is_err());
+        assert!(coerce_value(json!(2), &DataType::Boolean).is_err());
+        assert!(coerce_value(json!([1, 2]), &DataType::Number).is_err());
+    }
+
+}
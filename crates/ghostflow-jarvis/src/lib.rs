@@ -1,19 +1,43 @@
 use async_trait::async_trait;
-use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_core::{
+    read_capped, write_input, CappedOutput, EventBus, ExecutionEvent, GhostFlowError, Node, OutputStream, ProcessLimits, Result,
+};
 use ghostflow_schema::{
     DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::process::Stdio;
-use tokio::process::Command;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
 use tracing::{error, info};
+use uuid::Uuid;
 
-pub struct JarvisNode;
+pub struct JarvisNode {
+    limits: ProcessLimits,
+    events: EventBus,
+}
 
 impl JarvisNode {
     pub fn new() -> Self {
-        Self
+        Self::with_limits(ProcessLimits::default())
+    }
+
+    /// Cap output, memory and CPU time of every command this node runs.
+    /// Flow parameters may lower these limits but not raise them.
+    pub fn with_limits(limits: ProcessLimits) -> Self {
+        Self {
+            limits,
+            events: EventBus::default(),
+        }
+    }
+
+    /// Publish streamed stdout lines on `events`
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
     }
 }
 
@@ -21,7 +45,7 @@ impl JarvisNode {
 pub struct JarvisCommand {
     pub command: String,
     pub args: Vec<String>,
-    pub env: Option<std::collections::HashMap<String, String>>,
+    pub env: Option<HashMap<String, String>>,
     pub working_dir: Option<String>,
 }
 
@@ -70,13 +94,24 @@ impl Node for JarvisNode {
                 NodeParameter {
                     name: "args".to_string(),
                     display_name: "Arguments".to_string(),
-                    description: Some("Command arguments (comma-separated)".to_string()),
-                    param_type: ghostflow_schema::node::ParameterType::String,
+                    description: Some("Command arguments as a JSON array, or a shell-quoted string".to_string()),
+                    // Object is free-form JSON, so the string form passes engine validation
+                    param_type: ghostflow_schema::node::ParameterType::Object,
                     default_value: None,
                     required: false,
                     options: None,
                     validation: None,
                 },
+                NodeParameter {
+                    name: "comma_separated_args".to_string(),
+                    display_name: "Comma-Separated Arguments".to_string(),
+                    description: Some("Legacy mode: split a string `args` on commas instead of shell quoting".to_string()),
+                    param_type: ghostflow_schema::node::ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
                 NodeParameter {
                     name: "working_dir".to_string(),
                     display_name: "Working Directory".to_string(),
@@ -87,6 +122,26 @@ impl Node for JarvisNode {
                     options: None,
                     validation: None,
                 },
+                NodeParameter {
+                    name: "env".to_string(),
+                    display_name: "Environment Variables".to_string(),
+                    description: Some("Extra environment variables as a JSON object".to_string()),
+                    param_type: ghostflow_schema::node::ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "stream_output".to_string(),
+                    display_name: "Stream Output".to_string(),
+                    description: Some("Publish stdout line-by-line as execution events while the command runs".to_string()),
+                    param_type: ghostflow_schema::node::ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
                 NodeParameter {
                     name: "timeout_seconds".to_string(),
                     display_name: "Timeout (seconds)".to_string(),
@@ -97,6 +152,36 @@ impl Node for JarvisNode {
                     options: None,
                     validation: None,
                 },
+                NodeParameter {
+                    name: "max_output_bytes".to_string(),
+                    display_name: "Max Output (bytes)".to_string(),
+                    description: Some("Truncate stdout and stderr beyond this many bytes each".to_string()),
+                    param_type: ghostflow_schema::node::ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(self.limits.max_output_bytes))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_memory_mb".to_string(),
+                    display_name: "Memory Limit (MB)".to_string(),
+                    description: Some("Address space the command may use (Linux only)".to_string()),
+                    param_type: ghostflow_schema::node::ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_cpu_seconds".to_string(),
+                    display_name: "CPU Time Limit (seconds)".to_string(),
+                    description: Some("Kill the command once it has used this much CPU time (Linux only)".to_string()),
+                    param_type: ghostflow_schema::node::ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
             ],
             icon: Some("terminal".to_string()),
             color: Some("#ef4444".to_string()), // Red for Rust
@@ -118,6 +203,14 @@ impl Node for JarvisNode {
             });
         }
 
+        parse_env(params.get("env"))?;
+
+        let comma_separated = params
+            .get("comma_separated_args")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        parse_args(params.get("args"), comma_separated)?;
+
         Ok(())
     }
 
@@ -130,16 +223,12 @@ impl Node for JarvisNode {
             .and_then(|v| v.as_str())
             .unwrap_or("jarvis");
 
-        let args_str = params
-            .get("args")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        
-        let args: Vec<String> = if args_str.is_empty() {
-            vec![]
-        } else {
-            args_str.split(',').map(|s| s.trim().to_string()).collect()
-        };
+        let comma_separated = params
+            .get("comma_separated_args")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let args = parse_args(params.get("args"), comma_separated)?;
 
         let working_dir = params
             .get("working_dir")
@@ -150,14 +239,25 @@ impl Node for JarvisNode {
             .and_then(|v| v.as_u64())
             .unwrap_or(60);
 
+        let env = parse_env(params.get("env"))?;
+
+        let stream_output = params
+            .get("stream_output")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let limits = self.limits.narrowed_by(params);
+
         info!("Executing Jarvis command: {} {:?}", command, args);
 
         // Build the command
-        let mut cmd = Command::new(command);
+        let mut cmd = Command::new(resolve_executable(command, working_dir));
+        cmd.envs(&env).kill_on_drop(true);
         cmd.args(&args)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
+        limits.apply(&mut cmd);
 
         if let Some(dir) = working_dir {
             cmd.current_dir(dir);
@@ -165,36 +265,61 @@ impl Node for JarvisNode {
 
         // Pass input data as JSON to stdin if available
         let input_json = params.get("input").cloned().unwrap_or(Value::Null);
-        
-        // Execute with timeout
-        let output = tokio::time::timeout(
-            std::time::Duration::from_secs(timeout_seconds),
-            cmd.output()
-        ).await
-        .map_err(|_| GhostFlowError::TimeoutError {
-            timeout_ms: timeout_seconds * 1000,
-        })?
-        .map_err(|e| {
-            error!("Failed to execute Jarvis command: {}", e);
+
+        let child = cmd.spawn().map_err(|e| {
+            error!("Failed to spawn Jarvis command: {}", e);
             GhostFlowError::NodeExecutionError {
                 node_id: context.node_id.clone(),
                 message: format!("Command execution failed: {}", e),
             }
         })?;
 
+        let input = if input_json.is_null() {
+            None
+        } else {
+            Some(serde_json::to_vec(&input_json)?)
+        };
+
+        // Execute with timeout, killing the child if it overruns
+        let stream_target = stream_output.then(|| (self.events.clone(), context.execution_id, context.node_id.clone()));
+        let output = wait_or_kill(
+            child,
+            input,
+            std::time::Duration::from_secs(timeout_seconds),
+            limits.max_output_bytes,
+            stream_target,
+        )
+            .await
+            .map_err(|e| {
+                error!("Failed to execute Jarvis command: {}", e);
+                GhostFlowError::NodeExecutionError {
+                    node_id: context.node_id.clone(),
+                    message: format!("Command execution failed: {}", e),
+                }
+            })?
+            .ok_or_else(|| {
+                error!("Jarvis command timed out after {}s and was killed", timeout_seconds);
+                GhostFlowError::TimeoutError {
+                    timeout_ms: timeout_seconds * 1000,
+                }
+            })?;
+
         let execution_time_ms = start_time.elapsed().as_millis() as u64;
 
         let response = JarvisResponse {
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            stdout: String::from_utf8_lossy(&output.stdout.bytes).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr.bytes).to_string(),
             exit_code: output.status.code().unwrap_or(-1),
             execution_time_ms,
         };
 
         info!("Jarvis command completed with exit code: {}", response.exit_code);
 
-        // Parse stdout as JSON if possible, otherwise return as string
-        let result_data = if !response.stdout.is_empty() {
+        // Parse stdout as JSON if possible, otherwise return as string.
+        // Truncated output is never valid JSON, so it stays a string.
+        let result_data = if output.stdout.truncated {
+            Value::String(response.stdout.clone())
+        } else if !response.stdout.is_empty() {
             serde_json::from_str::<Value>(&response.stdout)
                 .unwrap_or_else(|_| Value::String(response.stdout.clone()))
         } else {
@@ -206,6 +331,8 @@ impl Node for JarvisNode {
             "data": result_data,
             "stdout": response.stdout,
             "stderr": response.stderr,
+            "stdout_truncated": output.stdout.truncated,
+            "stderr_truncated": output.stderr.truncated,
             "exit_code": response.exit_code,
             "execution_time_ms": response.execution_time_ms,
             "command": {
@@ -225,6 +352,167 @@ impl Node for JarvisNode {
     }
 }
 
+struct CommandOutput {
+    status: ExitStatus,
+    stdout: CappedOutput,
+    stderr: CappedOutput,
+}
+
+/// Feeds `input` to the child's stdin and waits for it to exit while
+/// draining stdout/stderr, keeping at most
+/// `max_output_bytes` of each. Returns `None` if the timeout elapses, in
+/// which case the child has been killed and reaped so it does not linger as
+/// a zombie.
+///
+/// When `stream_target` is set, each stdout line is also published to its
+/// event bus as it arrives, including lines past the output cap.
+async fn wait_or_kill(
+    mut child: Child,
+    input: Option<Vec<u8>>,
+    timeout: std::time::Duration,
+    max_output_bytes: usize,
+    stream_target: Option<(EventBus, Uuid, String)>,
+) -> std::io::Result<Option<CommandOutput>> {
+    let stdin_task = tokio::spawn(write_input(child.stdin.take(), input));
+    let stdout = child.stdout.take();
+    let stdout_task = match stream_target {
+        Some((events, execution_id, node_id)) => {
+            tokio::spawn(stream_lines(stdout, max_output_bytes, events, execution_id, node_id))
+        }
+        None => tokio::spawn(read_capped(stdout, max_output_bytes)),
+    };
+    let stderr_task = tokio::spawn(read_capped(child.stderr.take(), max_output_bytes));
+
+    let stdin_abort = stdin_task.abort_handle();
+    let finished = tokio::time::timeout(timeout, async { tokio::join!(child.wait(), stdin_task) }).await;
+
+    match finished {
+        Ok((status, written)) => {
+            let status = status?;
+            if let Ok(Err(e)) = written {
+                return Err(std::io::Error::new(e.kind(), format!("failed to write command input: {}", e)));
+            }
+            let stdout = stdout_task.await.unwrap_or_default();
+            let stderr = stderr_task.await.unwrap_or_default();
+            Ok(Some(CommandOutput { status, stdout, stderr }))
+        }
+        Err(_) => {
+            // `kill` sends SIGKILL and then waits on the child
+            child.kill().await?;
+            stdin_abort.abort();
+            stdout_task.abort();
+            stderr_task.abort();
+            Ok(None)
+        }
+    }
+}
+
+async fn stream_lines<R: AsyncRead + Unpin>(
+    pipe: Option<R>,
+    max_output_bytes: usize,
+    events: EventBus,
+    execution_id: Uuid,
+    node_id: String,
+) -> CappedOutput {
+    let mut buf = CappedOutput::new(max_output_bytes);
+    let Some(pipe) = pipe else {
+        return buf;
+    };
+
+    let mut reader = BufReader::new(pipe);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                events.publish(ExecutionEvent::NodeOutput {
+                    execution_id,
+                    node_id: node_id.clone(),
+                    stream: OutputStream::Stdout,
+                    data: String::from_utf8_lossy(&line).trim_end_matches(['\r', '\n']).to_string(),
+                });
+                buf.push(&line);
+            }
+        }
+    }
+    buf
+}
+
+/// Parses the `args` parameter. A JSON array is passed through element by
+/// element; a string is tokenized with shell quoting rules so quoted values
+/// containing spaces or commas stay intact. `comma_separated` restores the
+/// old split-on-comma behaviour for flows that still rely on it.
+fn parse_args(value: Option<&Value>, comma_separated: bool) -> Result<Vec<String>> {
+    match value {
+        None | Some(Value::Null) => Ok(vec![]),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| match item {
+                Value::String(s) => Ok(s.clone()),
+                Value::Number(_) | Value::Bool(_) => Ok(item.to_string()),
+                _ => Err(GhostFlowError::ValidationError {
+                    message: "Arguments must be strings, numbers or booleans".to_string(),
+                }),
+            })
+            .collect(),
+        Some(Value::String(s)) if s.trim().is_empty() => Ok(vec![]),
+        Some(Value::String(s)) if comma_separated => {
+            Ok(s.split(',').map(|arg| arg.trim().to_string()).collect())
+        }
+        Some(Value::String(s)) => shlex::split(s).ok_or_else(|| GhostFlowError::ValidationError {
+            message: "Arguments contain unbalanced quotes".to_string(),
+        }),
+        Some(_) => Err(GhostFlowError::ValidationError {
+            message: "Arguments must be a JSON array or a string".to_string(),
+        }),
+    }
+}
+
+/// Parses the `env` parameter into variables for the child process.
+/// Non-string scalar values are stringified; keys must be non-empty.
+fn parse_env(value: Option<&Value>) -> Result<HashMap<String, String>> {
+    let Some(value) = value.filter(|v| !v.is_null()) else {
+        return Ok(HashMap::new());
+    };
+
+    let obj = value.as_object().ok_or_else(|| GhostFlowError::ValidationError {
+        message: "Env must be a JSON object".to_string(),
+    })?;
+
+    obj.iter()
+        .map(|(key, value)| {
+            if key.trim().is_empty() {
+                return Err(GhostFlowError::ValidationError {
+                    message: "Env variable names cannot be empty".to_string(),
+                });
+            }
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(_) | Value::Bool(_) => value.to_string(),
+                _ => {
+                    return Err(GhostFlowError::ValidationError {
+                        message: format!("Env variable '{}' must be a string, number or boolean", key),
+                    });
+                }
+            };
+            Ok((key.clone(), value))
+        })
+        .collect()
+}
+
+/// Resolves the executable against `working_dir` when it is a relative path
+/// such as `./bin/jarvis`. How `Command` treats relative programs combined with
+/// `current_dir` is platform specific, so the join is done explicitly here.
+/// Bare names like `jarvis` are left alone and looked up on `PATH`.
+fn resolve_executable(command: &str, working_dir: Option<&str>) -> PathBuf {
+    let path = Path::new(command);
+    match working_dir {
+        Some(dir) if path.is_relative() && path.components().count() > 1 => Path::new(dir).join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
 impl Default for JarvisNode {
     fn default() -> Self {
         Self::new()
@@ -288,4 +576,292 @@ impl JarvisBridge {
         
         Ok(tasks)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(input: Value) -> ExecutionContext {
+        ExecutionContext {
+            execution_id: Uuid::new_v4(),
+            flow_id: Uuid::new_v4(),
+            node_id: "jarvis".to_string(),
+            input,
+            variables: HashMap::new(),
+            secrets: HashMap::new(),
+            artifacts: HashMap::new(),
+            node_outputs: HashMap::new(),
+            attempt: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_input_is_piped_to_stdin() {
+        let node = JarvisNode::new();
+        let payload = serde_json::json!({ "task": "deploy", "targets": ["a", "b"] });
+
+        let result = node.execute(context(serde_json::json!({
+            "command": "cat",
+            "input": payload,
+        }))).await.unwrap();
+
+        assert_eq!(result["success"], true);
+        assert_eq!(result["data"], payload);
+    }
+
+    #[tokio::test]
+    async fn test_input_larger_than_pipe_buffer_does_not_block() {
+        let node = JarvisNode::new();
+        let payload = Value::String("x".repeat(256 * 1024));
+
+        let result = node.execute(context(serde_json::json!({
+            "command": "cat",
+            "input": payload,
+            "timeout_seconds": 10,
+        }))).await.unwrap();
+
+        assert_eq!(result["success"], true);
+        assert_eq!(result["data"], payload);
+    }
+
+    #[tokio::test]
+    async fn test_env_is_passed_to_child() {
+        let node = JarvisNode::new();
+
+        let result = node.execute(context(serde_json::json!({
+            "command": "sh",
+            "args": ["-c", "printf %s \"$GREETING\""],
+            "env": { "GREETING": "hello from jarvis" },
+        }))).await.unwrap();
+
+        assert_eq!(result["stdout"], "hello from jarvis");
+    }
+
+    #[tokio::test]
+    async fn test_empty_env_key_is_rejected() {
+        let node = JarvisNode::new();
+
+        let result = node.validate(&context(serde_json::json!({
+            "command": "jarvis",
+            "env": { "": "value" },
+        }))).await;
+
+        assert!(matches!(result, Err(GhostFlowError::ValidationError { .. })));
+    }
+
+    #[test]
+    fn test_relative_executable_resolves_against_working_dir() {
+        assert_eq!(resolve_executable("./bin/jarvis", Some("/opt/app")), PathBuf::from("/opt/app/./bin/jarvis"));
+        assert_eq!(resolve_executable("jarvis", Some("/opt/app")), PathBuf::from("jarvis"));
+        assert_eq!(resolve_executable("/usr/bin/jarvis", Some("/opt/app")), PathBuf::from("/usr/bin/jarvis"));
+    }
+
+    #[tokio::test]
+    async fn test_megabytes_of_output_are_truncated() {
+        let node = JarvisNode::with_limits(ProcessLimits {
+            max_output_bytes: 64 * 1024,
+            ..ProcessLimits::default()
+        });
+        let result = node
+            .execute(context(serde_json::json!({
+                "command": "sh",
+                "args": ["-c", "yes '{\"alert\": 1}' | head -c 8000000"],
+                "max_output_bytes": 10_000_000,
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["exit_code"], 0);
+        assert_eq!(result["stdout"].as_str().unwrap().len(), 64 * 1024);
+        assert_eq!(result["stdout_truncated"], true);
+        assert!(result["data"].is_string());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_timeout_kills_child() {
+        let node = JarvisNode::new();
+        let pid_file = std::env::temp_dir().join(format!("jarvis-timeout-{}.pid", Uuid::new_v4()));
+
+        let started = std::time::Instant::now();
+        let result = node.execute(context(serde_json::json!({
+            "command": "sh",
+            "args": ["-c", format!("echo $$ > {}; exec sleep 10", pid_file.display())],
+            "timeout_seconds": 1,
+        }))).await;
+
+        assert!(matches!(result, Err(GhostFlowError::TimeoutError { timeout_ms: 1000 })));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        let _ = std::fs::remove_file(&pid_file);
+        assert!(!Path::new(&format!("/proc/{}", pid.trim())).exists());
+    }
+
+    #[tokio::test]
+    async fn test_streamed_stdout_produces_incremental_events() {
+        let bus = EventBus::default();
+        let node = JarvisNode::new().with_event_bus(bus.clone());
+        let ctx = context(serde_json::json!({
+            "command": "sh",
+            "args": ["-c", "echo one; sleep 0.3; echo two; sleep 0.3; echo three"],
+            "stream_output": true,
+        }));
+        let execution_id = ctx.execution_id;
+        let mut events = bus.subscribe();
+
+        let handle = tokio::spawn(async move { node.execute(ctx).await });
+
+        let mut lines = Vec::new();
+        while lines.len() < 3 {
+            let event = events.recv().await.unwrap();
+            if event.execution_id() != execution_id {
+                continue;
+            }
+            if let ExecutionEvent::NodeOutput { data, .. } = event {
+                if lines.is_empty() {
+                    // The first line must arrive while the command is still running
+                    assert!(!handle.is_finished());
+                }
+                lines.push(data);
+            }
+        }
+
+        let result = handle.await.unwrap().unwrap();
+        assert_eq!(lines, vec!["one", "two", "three"]);
+        assert_eq!(result["stdout"], "one\ntwo\nthree\n");
+    }
+
+    #[test]
+    fn test_array_args_keep_commas() {
+        let args = parse_args(Some(&serde_json::json!(["--data", "a,b,c"])), false).unwrap();
+        assert_eq!(args, vec!["--data", "a,b,c"]);
+    }
+
+    #[test]
+    fn test_string_args_use_shell_quoting() {
+        let args = parse_args(Some(&serde_json::json!(r#"--data "a,b,c" --name 'two words'"#)), false).unwrap();
+        assert_eq!(args, vec!["--data", "a,b,c", "--name", "two words"]);
+
+        assert!(parse_args(Some(&serde_json::json!(r#"--data "unterminated"#)), false).is_err());
+    }
+
+    #[test]
+    fn test_legacy_comma_split_behind_flag() {
+        let args = parse_args(Some(&serde_json::json!("task, deploy, --json")), true).unwrap();
+        assert_eq!(args, vec!["task", "deploy", "--json"]);
+    }
+
+    #[tokio::test]
+    async fn test_comma_argument_reaches_child_intact() {
+        let node = JarvisNode::new();
+
+        let result = node.execute(context(serde_json::json!({
+            "command": "printf",
+            "args": ["%s|", "--data", "a,b,c"],
+        }))).await.unwrap();
+
+        assert_eq!(result["stdout"], "--data|a,b,c|");
+    }
+
+    #[tokio::test]
+    async fn test_string_args_pass_engine_validation() {
+        use ghostflow_core::{BasicNodeRegistry, NodeRegistry};
+        use ghostflow_engine::FlowExecutor;
+        use ghostflow_schema::*;
+        use std::sync::Arc;
+
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("jarvis_command".to_string(), Arc::new(JarvisNode::new()))
+            .unwrap();
+        let executor = FlowExecutor::new(Arc::new(registry));
+
+        let node = FlowNode {
+            id: "jarvis".to_string(),
+            node_type: "jarvis_command".to_string(),
+            name: "jarvis".to_string(),
+            description: None,
+            parameters: HashMap::from([
+                ("command".to_string(), serde_json::json!("printf")),
+                ("args".to_string(), serde_json::json!(r#"'%s|' --data "a,b,c""#)),
+            ]),
+            position: NodePosition { x: 0.0, y: 0.0 },
+            retry_config: None,
+            timeout_ms: None,
+            cache_ttl_ms: None,
+            node_version: None,
+        };
+        let flow = Flow {
+            id: Uuid::new_v4(),
+            name: "Jarvis".to_string(),
+            description: None,
+            version: "1.0.0".to_string(),
+            nodes: HashMap::from([(node.id.clone(), node)]),
+            edges: vec![],
+            triggers: vec![],
+            parameters: HashMap::new(),
+            secrets: vec![],
+            max_duration_ms: None,
+            max_concurrent_executions: None,
+            overflow_policy: OverflowPolicy::Queue,
+            metadata: FlowMetadata {
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                created_by: "test".to_string(),
+                tags: vec![],
+                category: None,
+                workspace_id: "default".to_string(),
+            },
+        };
+        let trigger = ExecutionTrigger {
+            trigger_type: "manual".to_string(),
+            source: None,
+            metadata: HashMap::new(),
+            dry_run: false,
+            priority: 0,
+            environment: None,
+        };
+
+        let execution = executor.execute_flow(&flow, serde_json::json!({}), trigger).await.unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::Completed, "{:?}", execution.error);
+        let record = execution.node_records.iter().find(|r| r.node_id == "jarvis").unwrap();
+        assert_eq!(record.output.as_ref().unwrap()["stdout"], "--data|a,b,c|");
+    }
+
+    #[tokio::test]
+    async fn test_jarvis_and_slack_share_node_registry() {
+        use ghostflow_core::{BasicNodeRegistry, NodeRegistry};
+        use ghostflow_nodes::SlackMessageNode;
+        use std::sync::Arc;
+
+        let mut registry = BasicNodeRegistry::new();
+        registry
+            .register_node("jarvis_command".to_string(), Arc::new(JarvisNode::new()))
+            .unwrap();
+        registry
+            .register_node("slack_message".to_string(), Arc::new(SlackMessageNode::new()))
+            .unwrap();
+
+        assert!(registry.validate_node_type("jarvis_command"));
+        assert!(registry.validate_node_type("slack_message"));
+
+        let definitions = registry.list_node_definitions();
+        assert_eq!(definitions.len(), 2);
+        let slack = definitions.iter().find(|d| d.id == "slack_message").unwrap();
+        assert!(slack.parameters.iter().any(|p| p.name == "bot_token" && p.required));
+
+        // Both node styles validate against the same ExecutionContext
+        let slack_node = registry.get_node("slack_message").unwrap();
+        assert!(slack_node.validate(&context(serde_json::json!({}))).await.is_err());
+        assert!(slack_node
+            .validate(&context(serde_json::json!({
+                "bot_token": "xoxb-test",
+                "channel": "#ops",
+            })))
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,344 @@
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::{json, Value};
+
+const OPERATIONS: [&str; 5] = ["sum", "avg", "min", "max", "count"];
+
+/// Summarise a numeric field across an array with sum/avg/min/max/count,
+/// optionally per group.
+pub struct AggregateNode;
+
+impl AggregateNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AggregateNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Value at a dotted path such as `usage.cpu`; the element itself for an
+/// empty path.
+fn field<'a>(item: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(item);
+    }
+    path.split('.').try_fold(item, |current, segment| match current {
+        Value::Object(fields) => fields.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+/// Numbers and numeric strings (as sheets and CSV often produce) count;
+/// anything else is skipped.
+fn numeric(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok().filter(|n: &f64| n.is_finite()),
+        _ => None,
+    }
+}
+
+/// Running totals for one group (or the whole input).
+#[derive(Default)]
+struct Accumulator {
+    items: usize,
+    values: Vec<f64>,
+    skipped: usize,
+}
+
+impl Accumulator {
+    fn add(&mut self, item: &Value, path: &str) {
+        self.items += 1;
+        match field(item, path).and_then(numeric) {
+            Some(n) => self.values.push(n),
+            None => self.skipped += 1,
+        }
+    }
+
+    fn result(&self, operation: &str) -> Value {
+        let values = &self.values;
+        match operation {
+            "count" => json!(self.items),
+            "sum" => json!(values.iter().sum::<f64>()),
+            "avg" if !values.is_empty() => json!(values.iter().sum::<f64>() / values.len() as f64),
+            "min" => values.iter().copied().reduce(f64::min).map_or(Value::Null, |n| json!(n)),
+            "max" => values.iter().copied().reduce(f64::max).map_or(Value::Null, |n| json!(n)),
+            // The average of nothing is undefined rather than zero
+            _ => Value::Null,
+        }
+    }
+
+    fn summary(&self, operation: &str) -> Value {
+        json!({
+            "result": self.result(operation),
+            "count": if operation == "count" { self.items } else { self.values.len() },
+            "skipped": if operation == "count" { 0 } else { self.skipped },
+        })
+    }
+}
+
+struct Params<'a> {
+    items: &'a [Value],
+    operation: &'a str,
+    field: &'a str,
+    group_by: Option<&'a str>,
+}
+
+impl<'a> Params<'a> {
+    fn from_input(input: &'a Value) -> Result<Self> {
+        let items = match input.get("items") {
+            None | Some(Value::Null) => &[][..],
+            Some(Value::Array(items)) => items.as_slice(),
+            Some(_) => {
+                return Err(GhostFlowError::ValidationError {
+                    message: "Aggregate items must be an array".to_string(),
+                })
+            }
+        };
+
+        let operation = input.get("operation").and_then(|v| v.as_str()).unwrap_or("sum");
+        if !OPERATIONS.contains(&operation) {
+            return Err(GhostFlowError::ValidationError {
+                message: format!("Unsupported aggregate operation: {}", operation),
+            });
+        }
+
+        let field = input.get("field").and_then(|v| v.as_str()).unwrap_or("");
+        if field.is_empty() && operation != "count" {
+            return Err(GhostFlowError::ValidationError {
+                message: format!("A field is required for {}", operation),
+            });
+        }
+
+        Ok(Self {
+            items,
+            operation,
+            field,
+            group_by: input.get("group_by").and_then(|v| v.as_str()).filter(|g| !g.is_empty()),
+        })
+    }
+}
+
+#[async_trait]
+impl Node for AggregateNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "aggregate".to_string(),
+            name: "Aggregate".to_string(),
+            description: "Sum, average, min, max or count a field, optionally grouped by a key".to_string(),
+            category: NodeCategory::Transform,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "items".to_string(),
+                display_name: "Items".to_string(),
+                description: Some("Array of rows to summarise".to_string()),
+                data_type: DataType::Array,
+                required: true,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: Some("Aggregate over all items; null when there is nothing to aggregate".to_string()),
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "groups".to_string(),
+                    display_name: "Groups".to_string(),
+                    description: Some("Per-group results in first-seen order, when grouping".to_string()),
+                    data_type: DataType::Array,
+                    required: false,
+                },
+            ],
+            parameters: vec![
+                NodeParameter {
+                    name: "operation".to_string(),
+                    display_name: "Operation".to_string(),
+                    description: Some("How to combine the values".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("sum".to_string())),
+                    required: true,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "sum", "label": "Sum"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "avg", "label": "Average"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "min", "label": "Minimum"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "max", "label": "Maximum"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "count", "label": "Count"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "field".to_string(),
+                    display_name: "Field".to_string(),
+                    description: Some("Dotted path to the numeric field, e.g. usage.cpu; not needed for count".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "group_by".to_string(),
+                    display_name: "Group By".to_string(),
+                    description: Some("Dotted path to the key to group rows by".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("sigma".to_string()),
+            color: Some("#10b981".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        Params::from_input(&context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = Params::from_input(&context.input)?;
+
+        let mut total = Accumulator::default();
+        // (key, totals) in first-seen order; groups are few, so a linear
+        // search keeps keys as their original JSON values
+        let mut groups: Vec<(Value, Accumulator)> = Vec::new();
+
+        for item in params.items {
+            total.add(item, params.field);
+
+            if let Some(group_by) = params.group_by {
+                let key = field(item, group_by).cloned().unwrap_or(Value::Null);
+                let index = match groups.iter().position(|(k, _)| *k == key) {
+                    Some(index) => index,
+                    None => {
+                        groups.push((key, Accumulator::default()));
+                        groups.len() - 1
+                    }
+                };
+                groups[index].1.add(item, params.field);
+            }
+        }
+
+        let mut output = total.summary(params.operation);
+        output["operation"] = json!(params.operation);
+        output["field"] = json!(params.field);
+        if let Some(group_by) = params.group_by {
+            output["group_by"] = json!(group_by);
+            output["groups"] = groups
+                .iter()
+                .map(|(key, totals)| {
+                    let mut group = totals.summary(params.operation);
+                    group["key"] = key.clone();
+                    group
+                })
+                .collect();
+        }
+        Ok(output)
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+
+    fn invoices() -> Value {
+        json!([
+            { "customer": "acme", "amount": 120.0 },
+            { "customer": "globex", "amount": 80 },
+            { "customer": "acme", "amount": "30.5" },
+            { "customer": "initech", "amount": "n/a" },
+            { "customer": "globex", "amount": 20 }
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_grouped_sum() {
+        let output = AggregateNode::new()
+            .execute(context(json!({
+                "items": invoices(),
+                "operation": "sum",
+                "field": "amount",
+                "group_by": "customer",
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["result"], 250.5);
+        assert_eq!(output["count"], 4);
+        assert_eq!(output["skipped"], 1);
+        assert_eq!(
+            output["groups"],
+            json!([
+                { "key": "acme", "result": 150.5, "count": 2, "skipped": 0 },
+                { "key": "globex", "result": 100.0, "count": 2, "skipped": 0 },
+                { "key": "initech", "result": 0.0, "count": 0, "skipped": 1 }
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_avg_min_max_and_count() {
+        let node = AggregateNode::new();
+        let run = |operation: &str| {
+            context(json!({ "items": invoices(), "operation": operation, "field": "amount" }))
+        };
+
+        assert_eq!(node.execute(run("avg")).await.unwrap()["result"], 62.625);
+        assert_eq!(node.execute(run("min")).await.unwrap()["result"], 20.0);
+        assert_eq!(node.execute(run("max")).await.unwrap()["result"], 120.0);
+        assert_eq!(node.execute(run("count")).await.unwrap()["result"], 5);
+    }
+
+    #[tokio::test]
+    async fn test_empty_input() {
+        let node = AggregateNode::new();
+        for (operation, expected) in [
+            ("sum", json!(0.0)),
+            ("avg", Value::Null),
+            ("min", Value::Null),
+            ("max", Value::Null),
+            ("count", json!(0)),
+        ] {
+            let output = node
+                .execute(context(json!({
+                    "items": [],
+                    "operation": operation,
+                    "field": "amount",
+                    "group_by": "customer",
+                })))
+                .await
+                .unwrap();
+            assert_eq!(output["result"], expected, "{}", operation);
+            assert_eq!(output["groups"], json!([]));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_field_fails_validation() {
+        let node = AggregateNode::new();
+        assert!(node.validate(&context(json!({ "items": [], "operation": "sum" }))).await.is_err());
+        assert!(node.validate(&context(json!({ "items": [], "operation": "median", "field": "x" }))).await.is_err());
+        assert!(node.validate(&context(json!({ "items": [], "operation": "count" }))).await.is_ok());
+    }
+}
@@ -0,0 +1,291 @@
+use async_trait::async_trait;
+use ghostflow_core::{
+    ApprovalDecision, ApprovalRegistry, CancellationRegistry, EventBus, ExecutionEvent, GhostFlowError, Node,
+    PendingApproval, Result,
+};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+/// Timeout applied when the flow does not set one, in seconds (a day)
+const DEFAULT_APPROVAL_TIMEOUT_SECS: f64 = 86400.0;
+
+/// Longest approval timeout accepted, in seconds (30 days)
+const MAX_APPROVAL_TIMEOUT_SECS: f64 = 30.0 * 24.0 * 3600.0;
+
+/// Suspend the execution until someone approves or rejects it through
+/// `POST /api/executions/:id/approve` or `/reject`. Approval passes the input
+/// on to the next node; rejection, or no decision before the timeout, fails
+/// the node.
+pub struct WaitForApprovalNode {
+    events: EventBus,
+    cancellations: Arc<CancellationRegistry>,
+    approvals: Arc<ApprovalRegistry>,
+}
+
+impl WaitForApprovalNode {
+    pub fn new() -> Self {
+        Self {
+            events: EventBus::default(),
+            cancellations: Arc::new(CancellationRegistry::new()),
+            approvals: Arc::new(ApprovalRegistry::new()),
+        }
+    }
+
+    /// Announce approval requests on `events`
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Stop waiting when the execution is cancelled in `cancellations`
+    pub fn with_cancellations(mut self, cancellations: Arc<CancellationRegistry>) -> Self {
+        self.cancellations = cancellations;
+        self
+    }
+
+    /// Open approval requests in `approvals`, where the API decides them
+    pub fn with_approvals(mut self, approvals: Arc<ApprovalRegistry>) -> Self {
+        self.approvals = approvals;
+        self
+    }
+
+    /// `None` waits indefinitely.
+    fn timeout(params: &Value) -> Result<Option<Duration>> {
+        let seconds = match params.get("timeout_seconds") {
+            None | Some(Value::Null) => DEFAULT_APPROVAL_TIMEOUT_SECS,
+            Some(value) => value.as_f64().ok_or_else(|| GhostFlowError::ValidationError {
+                message: "timeout_seconds must be a number".to_string(),
+            })?,
+        };
+        if !(0.0..=MAX_APPROVAL_TIMEOUT_SECS).contains(&seconds) {
+            return Err(GhostFlowError::ValidationError {
+                message: format!("timeout_seconds must be between 0 and {}", MAX_APPROVAL_TIMEOUT_SECS),
+            });
+        }
+        Ok((seconds > 0.0).then(|| Duration::from_secs_f64(seconds)))
+    }
+}
+
+impl Default for WaitForApprovalNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for WaitForApprovalNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "wait_for_approval".to_string(),
+            name: "Wait for Approval".to_string(),
+            description: "Pause the flow until someone approves or rejects it".to_string(),
+            category: NodeCategory::ControlFlow,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "input".to_string(),
+                display_name: "Input".to_string(),
+                description: Some("Data to pass on once approved".to_string()),
+                data_type: DataType::Any,
+                required: false,
+            }],
+            outputs: vec![NodePort {
+                name: "output".to_string(),
+                display_name: "Output".to_string(),
+                description: Some("The decision, approver and comment alongside the input".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            parameters: vec![
+                NodeParameter {
+                    name: "message".to_string(),
+                    display_name: "Message".to_string(),
+                    description: Some("What the approver is asked to decide".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "timeout_seconds".to_string(),
+                    display_name: "Timeout (seconds)".to_string(),
+                    description: Some("Reject automatically when nobody decides in time; 0 waits indefinitely".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(json!(DEFAULT_APPROVAL_TIMEOUT_SECS)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("user-check".to_string()),
+            color: Some("#f59e0b".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        Self::timeout(&context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let timeout = Self::timeout(params)?;
+        let message = params.get("message").and_then(|v| v.as_str()).map(str::to_string);
+        let requested_at = chrono::Utc::now();
+        let expires_at = timeout.and_then(|t| chrono::Duration::from_std(t).ok()).map(|t| requested_at + t);
+
+        let decision = self.approvals.request(PendingApproval {
+            execution_id: context.execution_id,
+            node_id: context.node_id.clone(),
+            message: message.clone(),
+            requested_at,
+            expires_at,
+        });
+        self.events.publish(ExecutionEvent::ApprovalRequested {
+            execution_id: context.execution_id,
+            node_id: context.node_id.clone(),
+            message,
+            expires_at,
+        });
+        info!("Execution {} is waiting for approval at {}", context.execution_id, context.node_id);
+
+        let cancellation = self.cancellations.token(context.execution_id);
+        let expired = async {
+            match timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => std::future::pending().await,
+            }
+        };
+        let approval = tokio::select! {
+            approval = decision => approval.map_err(|_| GhostFlowError::InternalError {
+                message: format!("Approval request for node {} was dropped", context.node_id),
+            })?,
+            _ = expired => {
+                self.approvals.remove(context.execution_id, &context.node_id);
+                return Err(GhostFlowError::NodeExecutionError {
+                    node_id: context.node_id.clone(),
+                    message: format!(
+                        "No decision within {}s; approval was rejected automatically",
+                        timeout.unwrap_or_default().as_secs_f64()
+                    ),
+                });
+            }
+            _ = cancellation.cancelled() => {
+                self.approvals.remove(context.execution_id, &context.node_id);
+                return Err(GhostFlowError::Cancelled {
+                    execution_id: context.execution_id,
+                });
+            }
+        };
+
+        if approval.decision == ApprovalDecision::Rejected {
+            let by = approval.approver.as_deref().map(|a| format!(" by {}", a)).unwrap_or_default();
+            let comment = approval.comment.as_deref().map(|c| format!(": {}", c)).unwrap_or_default();
+            return Err(GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: format!("Rejected{}{}", by, comment),
+            });
+        }
+
+        Ok(json!({
+            "approved": true,
+            "approver": approval.approver,
+            "comment": approval.comment,
+            "decided_at": approval.decided_at,
+            "input": params.get("input").cloned().unwrap_or(Value::Null),
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use ghostflow_core::Approval;
+    use uuid::Uuid;
+
+    /// Wait until the node has registered its request.
+    async fn wait_for_request(approvals: &ApprovalRegistry, execution_id: Uuid) -> PendingApproval {
+        for _ in 0..100 {
+            if let Some(pending) = approvals.pending(execution_id).pop() {
+                return pending;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("approval was never requested");
+    }
+
+    #[tokio::test]
+    async fn test_approval_resumes_with_input() {
+        let ctx = context(json!({ "message": "Reboot prod?", "input": { "vm": 101 } }));
+        let execution_id = ctx.execution_id;
+        let approvals = Arc::new(ApprovalRegistry::new());
+        let node = WaitForApprovalNode::new().with_approvals(approvals.clone());
+        let task = tokio::spawn(async move { node.execute(ctx).await });
+
+        let pending = wait_for_request(&approvals, execution_id).await;
+        assert_eq!(pending.message.as_deref(), Some("Reboot prod?"));
+        assert!(pending.expires_at.is_some());
+
+        let approval = Approval::new(ApprovalDecision::Approved, Some("alice".to_string()), Some("go".to_string()));
+        approvals.decide(execution_id, None, approval).unwrap();
+
+        let output = task.await.unwrap().unwrap();
+        assert_eq!(output["approved"], true);
+        assert_eq!(output["approver"], "alice");
+        assert_eq!(output["comment"], "go");
+        assert_eq!(output["input"], json!({ "vm": 101 }));
+    }
+
+    #[tokio::test]
+    async fn test_rejection_fails_the_node() {
+        let ctx = context(json!({ "timeout_seconds": 0 }));
+        let execution_id = ctx.execution_id;
+        let approvals = Arc::new(ApprovalRegistry::new());
+        let node = WaitForApprovalNode::new().with_approvals(approvals.clone());
+        let task = tokio::spawn(async move { node.execute(ctx).await });
+
+        assert_eq!(wait_for_request(&approvals, execution_id).await.expires_at, None);
+        let rejection = Approval::new(ApprovalDecision::Rejected, Some("bob".to_string()), Some("not today".to_string()));
+        approvals.decide(execution_id, Some("node"), rejection).unwrap();
+
+        let error = task.await.unwrap().unwrap_err().to_string();
+        assert!(error.contains("Rejected by bob: not today"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_rejects_automatically() {
+        let ctx = context(json!({ "timeout_seconds": 0.1 }));
+        let execution_id = ctx.execution_id;
+        let approvals = Arc::new(ApprovalRegistry::new());
+        let node = WaitForApprovalNode::new().with_approvals(approvals.clone());
+
+        let error = node.execute(ctx).await.unwrap_err().to_string();
+
+        assert!(error.contains("rejected automatically"), "{}", error);
+        assert!(approvals.pending(execution_id).is_empty());
+        let late = Approval::new(ApprovalDecision::Approved, None, None);
+        assert!(approvals.decide(execution_id, None, late).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_timeout_fails_validation() {
+        let node = WaitForApprovalNode::new();
+        assert!(node.validate(&context(json!({ "timeout_seconds": -1 }))).await.is_err());
+        assert!(node.validate(&context(json!({ "timeout_seconds": "soon" }))).await.is_err());
+        assert!(node.validate(&context(json!({}))).await.is_ok());
+    }
+}
@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort, ACTIVATIONS_PORT,
+};
+use serde_json::{json, Value};
+use tracing::info;
+
+/// Fan-in barrier for scatter-gather flows. Buffers the value of each
+/// upstream node as it completes and emits them as one array once
+/// `expected_count` have arrived. With a `timeout_ms`, values arriving that
+/// long after the first one are dropped and whatever was gathered by then
+/// is emitted with `partial` set.
+pub struct CollectNode;
+
+impl CollectNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CollectNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn expected_count(params: &Value) -> Result<usize> {
+    params
+        .get("expected_count")
+        .and_then(|v| v.as_u64())
+        .filter(|count| *count > 0)
+        .map(|count| count as usize)
+        .ok_or_else(|| GhostFlowError::ValidationError {
+            message: "Expected count must be a positive integer".to_string(),
+        })
+}
+
+#[async_trait]
+impl Node for CollectNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "collect".to_string(),
+            name: "Collect".to_string(),
+            description: "Wait for a number of upstream nodes to finish and emit their outputs as one array".to_string(),
+            category: NodeCategory::ControlFlow,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: ACTIVATIONS_PORT.to_string(),
+                display_name: "Items".to_string(),
+                description: Some("One entry per finished upstream node, supplied by the executor".to_string()),
+                data_type: DataType::Array,
+                required: false,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "items".to_string(),
+                    display_name: "Items".to_string(),
+                    description: Some("Collected values in the order they arrived".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+                NodePort {
+                    name: "partial".to_string(),
+                    display_name: "Partial".to_string(),
+                    description: Some("True when fewer than the expected count arrived".to_string()),
+                    data_type: DataType::Boolean,
+                    required: true,
+                },
+            ],
+            parameters: vec![
+                NodeParameter {
+                    name: "expected_count".to_string(),
+                    display_name: "Expected Count".to_string(),
+                    description: Some("Number of upstream completions to wait for".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "timeout_ms".to_string(),
+                    display_name: "Timeout (ms)".to_string(),
+                    description: Some("Emit what has arrived this long after the first item".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("layers".to_string()),
+            color: Some("#8b5cf6".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        expected_count(&context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let expected = expected_count(params)?;
+        let timeout_ms = params.get("timeout_ms").and_then(|v| v.as_i64());
+        let activations = params
+            .get(ACTIVATIONS_PORT)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut items = Vec::with_capacity(expected);
+        let mut timed_out = false;
+        for activation in activations {
+            if items.len() == expected {
+                break;
+            }
+            let after_ms = activation.get("after_ms").and_then(|v| v.as_i64()).unwrap_or(0);
+            if timeout_ms.is_some_and(|timeout| after_ms > timeout) {
+                timed_out = true;
+                break;
+            }
+            items.push(activation.get("value").cloned().unwrap_or(Value::Null));
+        }
+
+        info!("Collected {} of {} items for node {}", items.len(), expected, context.node_id);
+        Ok(json!({
+            "count": items.len(),
+            "partial": items.len() < expected,
+            "timed_out": timed_out,
+            "items": items,
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn accepts_multiple_activations(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+
+    fn activation(value: i64, after_ms: i64) -> Value {
+        json!({ "node_id": format!("n{}", value), "value": value, "after_ms": after_ms })
+    }
+
+    #[tokio::test]
+    async fn test_extra_items_after_the_count_are_ignored() {
+        let output = CollectNode::new()
+            .execute(context(json!({
+                "expected_count": 2,
+                "activations": [activation(1, 0), activation(2, 3), activation(3, 8)],
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["items"], json!([1, 2]));
+        assert_eq!(output["partial"], false);
+    }
+
+    #[tokio::test]
+    async fn test_items_after_the_timeout_are_dropped() {
+        let output = CollectNode::new()
+            .execute(context(json!({
+                "expected_count": 3,
+                "timeout_ms": 50,
+                "activations": [activation(1, 0), activation(2, 40), activation(3, 400)],
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["items"], json!([1, 2]));
+        assert_eq!(output["partial"], true);
+        assert_eq!(output["timed_out"], true);
+    }
+
+    #[tokio::test]
+    async fn test_expected_count_is_required() {
+        let err = CollectNode::new().validate(&context(json!({ "expected_count": 0 }))).await.unwrap_err();
+        assert!(err.to_string().contains("positive integer"), "{}", err);
+    }
+}
@@ -1,10 +1,11 @@
 use async_trait::async_trait;
-use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_core::{CancellationRegistry, GhostFlowError, Node, Result};
 use ghostflow_schema::{
     DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
 };
 use ghostflow_schema::node::ParameterType;
 use serde_json::Value;
+use std::sync::Arc;
 use tracing::info;
 
 pub struct IfNode;
@@ -153,11 +154,63 @@ impl IfNode {
     }
 }
 
-pub struct DelayNode;
+pub struct DelayNode {
+    cancellations: Arc<CancellationRegistry>,
+}
+
+/// Longest fixed delay accepted, in seconds
+const MAX_DELAY_SECS: f64 = 3600.0;
 
 impl DelayNode {
     pub fn new() -> Self {
-        Self
+        Self {
+            cancellations: Arc::new(CancellationRegistry::new()),
+        }
+    }
+
+    /// Stop waiting when the execution is cancelled in `cancellations`
+    pub fn with_cancellations(mut self, cancellations: Arc<CancellationRegistry>) -> Self {
+        self.cancellations = cancellations;
+        self
+    }
+
+    fn wait_duration(params: &Value) -> Result<std::time::Duration> {
+        match params.get("mode").and_then(|v| v.as_str()).unwrap_or("fixed") {
+            "fixed" => {
+                let duration = params
+                    .get("duration")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| GhostFlowError::ValidationError {
+                        message: "Duration must be a number".to_string(),
+                    })?;
+                if !(0.0..=MAX_DELAY_SECS).contains(&duration) {
+                    return Err(GhostFlowError::ValidationError {
+                        message: format!("Duration must be between 0 and {} seconds", MAX_DELAY_SECS),
+                    });
+                }
+                Ok(std::time::Duration::from_secs_f64(duration))
+            }
+            "until" => {
+                let until = params
+                    .get("until")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| GhostFlowError::ValidationError {
+                        message: "'until' timestamp is required when mode is until".to_string(),
+                    })?;
+                let until = chrono::DateTime::parse_from_rfc3339(until).map_err(|e| {
+                    GhostFlowError::ValidationError {
+                        message: format!("Invalid 'until' timestamp '{}': {}", until, e),
+                    }
+                })?;
+                // A timestamp in the past means no wait at all
+                Ok((until.with_timezone(&chrono::Utc) - chrono::Utc::now())
+                    .to_std()
+                    .unwrap_or_default())
+            }
+            other => Err(GhostFlowError::ValidationError {
+                message: format!("Unknown delay mode: {}", other),
+            }),
+        }
     }
 }
 
@@ -173,7 +226,7 @@ impl Node for DelayNode {
         NodeDefinition {
             id: "delay".to_string(),
             name: "Delay".to_string(),
-            description: "Wait for a specified amount of time".to_string(),
+            description: "Wait for a fixed time or until a timestamp, then pass the input through".to_string(),
             category: NodeCategory::ControlFlow,
             version: "1.0.0".to_string(),
             inputs: vec![NodePort {
@@ -191,6 +244,19 @@ impl Node for DelayNode {
                 required: true,
             }],
             parameters: vec![
+                NodeParameter {
+                    name: "mode".to_string(),
+                    display_name: "Mode".to_string(),
+                    description: Some("Wait a fixed duration or until a point in time".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("fixed".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "fixed", "label": "Fixed Duration"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "until", "label": "Until Timestamp"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
                 NodeParameter {
                     name: "duration".to_string(),
                     display_name: "Duration (seconds)".to_string(),
@@ -201,6 +267,16 @@ impl Node for DelayNode {
                     options: None,
                     validation: None,
                 },
+                NodeParameter {
+                    name: "until".to_string(),
+                    display_name: "Until".to_string(),
+                    description: Some("RFC 3339 timestamp to wait for, e.g. 2024-05-01T09:00:00Z".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
             ],
             icon: Some("clock".to_string()),
             color: Some("#f59e0b".to_string()),
@@ -208,44 +284,25 @@ impl Node for DelayNode {
     }
 
     async fn validate(&self, context: &ExecutionContext) -> Result<()> {
-        let params = &context.input;
-        
-        if let Some(duration_value) = params.get("duration") {
-            if let Some(duration) = duration_value.as_f64() {
-                if duration < 0.0 || duration > 3600.0 {
-                    return Err(GhostFlowError::ValidationError {
-                        message: "Duration must be between 0 and 3600 seconds".to_string(),
-                    });
-                }
-            } else {
-                return Err(GhostFlowError::ValidationError {
-                    message: "Duration must be a number".to_string(),
-                });
-            }
-        } else {
-            return Err(GhostFlowError::ValidationError {
-                message: "Duration parameter is required".to_string(),
-            });
-        }
-
-        Ok(())
+        Self::wait_duration(&context.input).map(|_| ())
     }
 
     async fn execute(&self, context: ExecutionContext) -> Result<serde_json::Value> {
         let params = &context.input;
-        
-        let duration = params
-            .get("duration")
-            .and_then(|v| v.as_f64())
-            .ok_or_else(|| GhostFlowError::NodeExecutionError {
-                node_id: context.node_id.clone(),
-                message: "Missing or invalid duration parameter".to_string(),
-            })?;
+        let wait = Self::wait_duration(params)?;
 
-        info!("Delaying execution for {} seconds", duration);
+        info!("Delaying execution for {:?}", wait);
 
-        // Sleep for the specified duration
-        tokio::time::sleep(tokio::time::Duration::from_secs_f64(duration)).await;
+        // Stop waiting as soon as the execution is cancelled
+        let cancellation = self.cancellations.token(context.execution_id);
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = cancellation.cancelled() => {
+                return Err(GhostFlowError::Cancelled {
+                    execution_id: context.execution_id,
+                });
+            }
+        }
 
         // Pass through the original input data
         let input_data = params.get("input").cloned().unwrap_or(Value::Null);
@@ -260,4 +317,56 @@ impl Node for DelayNode {
     fn is_deterministic(&self) -> bool {
         false // Time-based, so not deterministic
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use std::time::{Duration, Instant};
+
+    #[tokio::test]
+    async fn test_fixed_delay_passes_input_through() {
+        let started = Instant::now();
+        let output = DelayNode::new()
+            .execute(context(serde_json::json!({
+                "duration": 0.1,
+                "input": { "vm": 101 },
+            })))
+            .await
+            .unwrap();
+
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert_eq!(output, serde_json::json!({ "vm": 101 }));
+    }
+
+    #[tokio::test]
+    async fn test_until_in_the_past_does_not_wait() {
+        let node = DelayNode::new();
+        let ctx = context(serde_json::json!({ "mode": "until", "until": "2000-01-01T00:00:00Z", "input": 1 }));
+        node.validate(&ctx).await.unwrap();
+        assert_eq!(node.execute(ctx).await.unwrap(), 1);
+
+        let bad = context(serde_json::json!({ "mode": "until", "until": "tomorrow" }));
+        assert!(node.validate(&bad).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_interrupts_wait() {
+        let ctx = context(serde_json::json!({ "duration": 3600 }));
+        let execution_id = ctx.execution_id;
+        let registry = Arc::new(CancellationRegistry::new());
+        registry.register(execution_id);
+
+        let node = DelayNode::new().with_cancellations(registry.clone());
+        let task = tokio::spawn(async move { node.execute(ctx).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(registry.cancel(execution_id));
+
+        let result = tokio::time::timeout(Duration::from_secs(2), task)
+            .await
+            .expect("delay did not observe cancellation")
+            .unwrap();
+        assert!(matches!(result, Err(GhostFlowError::Cancelled { .. })));
+    }
+}
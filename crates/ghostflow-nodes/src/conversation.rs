@@ -0,0 +1,331 @@
+use crate::LlmNode;
+use async_trait::async_trait;
+use ghostflow_core::{
+    conversation_window, estimate_tokens, ConversationMessage, ConversationStore, GhostFlowError, Node, Result,
+};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::info;
+
+const DEFAULT_TOKEN_BUDGET: u64 = 2048;
+
+/// Parameters passed through unchanged to the LLM node
+const LLM_PARAMETERS: [&str; 5] = ["provider", "model", "system", "temperature", "max_tokens"];
+
+/// Multi-turn chat on top of [`LlmNode`]. Prior turns are kept in the
+/// [`ConversationStore`] under the flow and `conversation_id`, so each
+/// execution sees the conversation so far. As many recent turns as fit the
+/// token budget are sent along with the new message, then the message and
+/// the reply are appended.
+pub struct ConversationNode {
+    llm: Arc<dyn Node>,
+    conversations: Arc<ConversationStore>,
+}
+
+impl ConversationNode {
+    pub fn new() -> Self {
+        Self::with_llm(Arc::new(LlmNode::new()))
+    }
+
+    /// Generate replies with `llm`, which must return a `text` field like
+    /// [`LlmNode`]
+    pub fn with_llm(llm: Arc<dyn Node>) -> Self {
+        Self {
+            llm,
+            conversations: Arc::new(ConversationStore::new()),
+        }
+    }
+
+    /// Keep turns in `conversations`
+    pub fn with_conversations(mut self, conversations: Arc<ConversationStore>) -> Self {
+        self.conversations = conversations;
+        self
+    }
+
+    fn required_text<'a>(params: &'a Value, key: &str) -> Result<&'a str> {
+        params
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| GhostFlowError::ValidationError {
+                message: format!("{} is required", key),
+            })
+    }
+
+    /// Earlier turns followed by the new message, ending where the model
+    /// should continue as the assistant.
+    fn transcript(history: &[ConversationMessage], message: &str) -> String {
+        let mut transcript = String::new();
+        for turn in history {
+            let speaker = if turn.role == "assistant" { "Assistant" } else { "User" };
+            transcript.push_str(&format!("{}: {}\n", speaker, turn.content));
+        }
+        transcript.push_str(&format!("User: {}\nAssistant:", message));
+        transcript
+    }
+}
+
+impl Default for ConversationNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for ConversationNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "llm_conversation".to_string(),
+            name: "LLM Conversation".to_string(),
+            description: "Chat with an LLM, remembering earlier turns of the conversation".to_string(),
+            category: NodeCategory::Ai,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "message".to_string(),
+                display_name: "Message".to_string(),
+                description: Some("The user's next message".to_string()),
+                data_type: DataType::String,
+                required: true,
+            }],
+            outputs: vec![NodePort {
+                name: "response".to_string(),
+                display_name: "Response".to_string(),
+                description: Some("LLM response with the conversation id and turn number".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            parameters: vec![
+                NodeParameter {
+                    name: "conversation_id".to_string(),
+                    display_name: "Conversation ID".to_string(),
+                    description: Some("Identifies the conversation, e.g. a chat thread or user id".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "message".to_string(),
+                    display_name: "Message".to_string(),
+                    description: Some("The user's next message".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "token_budget".to_string(),
+                    display_name: "History Token Budget".to_string(),
+                    description: Some("Approximate tokens of history and message sent to the model; older turns are left out".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(DEFAULT_TOKEN_BUDGET))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "provider".to_string(),
+                    display_name: "Provider".to_string(),
+                    description: Some("Backend that runs the model".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("ollama".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "ollama", "label": "Ollama"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "ghostllm", "label": "GhostLLM"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "model".to_string(),
+                    display_name: "Model".to_string(),
+                    description: Some("Ollama model name or GhostLLM model path".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "system".to_string(),
+                    display_name: "System Prompt".to_string(),
+                    description: Some("System prompt to set model behavior".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("message-circle".to_string()),
+            color: Some("#8b5cf6".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        Self::required_text(&context.input, "conversation_id")?;
+        Self::required_text(&context.input, "message")?;
+        if context.input.get("token_budget").is_some_and(|v| v.as_u64().is_none()) {
+            return Err(GhostFlowError::ValidationError {
+                message: "token_budget must be a positive number".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let conversation_id = Self::required_text(params, "conversation_id")?.to_string();
+        let message = Self::required_text(params, "message")?.to_string();
+        let budget = params
+            .get("token_budget")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_TOKEN_BUDGET) as usize;
+
+        let history = self.conversations.messages(context.flow_id, &conversation_id);
+        let window = conversation_window(&history, budget.saturating_sub(estimate_tokens(&message)));
+        info!(
+            "Continuing conversation {} with {} of {} earlier messages",
+            conversation_id,
+            window.len(),
+            history.len()
+        );
+
+        let mut input = serde_json::Map::new();
+        for key in LLM_PARAMETERS {
+            if let Some(value) = params.get(key) {
+                input.insert(key.to_string(), value.clone());
+            }
+        }
+        input.insert("prompt".to_string(), Value::String(Self::transcript(window, &message)));
+        let mut llm_context = context.clone();
+        llm_context.input = Value::Object(input);
+
+        let mut output = self.llm.execute(llm_context).await?;
+        let reply = output.get("text").and_then(|v| v.as_str()).unwrap_or_default().trim().to_string();
+        self.conversations.append(
+            context.flow_id,
+            &conversation_id,
+            [ConversationMessage::user(message), ConversationMessage::assistant(reply)],
+        );
+
+        output["conversation_id"] = Value::String(conversation_id);
+        output["turn"] = Value::from(history.len() / 2 + 1);
+        output["history_messages"] = Value::from(window.len());
+        Ok(output)
+    }
+
+    fn supports_retry(&self) -> bool {
+        true
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// Stands in for the LLM node: records each prompt and numbers its replies
+    #[derive(Default)]
+    struct RecordingLlm {
+        prompts: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Node for RecordingLlm {
+        fn definition(&self) -> NodeDefinition {
+            LlmNode::new().definition()
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+            let mut prompts = self.prompts.lock().unwrap();
+            prompts.push(context.input["prompt"].as_str().unwrap().to_string());
+            let reply = match prompts.len() {
+                1 => "Nice to meet you, Ada.",
+                _ => "Your name is Ada.",
+            };
+            Ok(serde_json::json!({"text": reply, "provider": context.input["provider"]}))
+        }
+    }
+
+    fn context(flow_id: Uuid, input: Value) -> ExecutionContext {
+        ExecutionContext {
+            flow_id,
+            ..crate::test_support::context(input)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_second_turn_sees_the_first() {
+        let llm = Arc::new(RecordingLlm::default());
+        let conversations = Arc::new(ConversationStore::new());
+        let node = ConversationNode::with_llm(llm.clone()).with_conversations(conversations.clone());
+        let flow_id = Uuid::new_v4();
+        let turn = |message: &str| {
+            context(
+                flow_id,
+                serde_json::json!({"conversation_id": "thread-7", "message": message, "provider": "ghostllm"}),
+            )
+        };
+
+        let first = node.execute(turn("Hi, I'm Ada")).await.unwrap();
+        assert_eq!(first["turn"], 1);
+        assert_eq!(first["history_messages"], 0);
+
+        let second = node.execute(turn("What's my name?")).await.unwrap();
+        assert_eq!(second["turn"], 2);
+        assert_eq!(second["history_messages"], 2);
+        assert_eq!(second["text"], "Your name is Ada.");
+        assert_eq!(second["provider"], "ghostllm");
+
+        let prompts = llm.prompts.lock().unwrap();
+        assert_eq!(prompts[0], "User: Hi, I'm Ada\nAssistant:");
+        assert_eq!(
+            prompts[1],
+            "User: Hi, I'm Ada\nAssistant: Nice to meet you, Ada.\nUser: What's my name?\nAssistant:"
+        );
+        assert_eq!(conversations.messages(flow_id, "thread-7").len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_history_beyond_the_budget_is_left_out() {
+        let llm = Arc::new(RecordingLlm::default());
+        let conversations = Arc::new(ConversationStore::new());
+        let node = ConversationNode::with_llm(llm.clone()).with_conversations(conversations.clone());
+        let flow_id = Uuid::new_v4();
+        conversations.append(
+            flow_id,
+            "thread-8",
+            [
+                ConversationMessage::user("x".repeat(4000)),
+                ConversationMessage::assistant("ok"),
+            ],
+        );
+
+        let output = node
+            .execute(context(
+                flow_id,
+                serde_json::json!({"conversation_id": "thread-8", "message": "Still there?", "token_budget": 100}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(output["history_messages"], 1);
+        assert_eq!(llm.prompts.lock().unwrap()[0], "Assistant: ok\nUser: Still there?\nAssistant:");
+    }
+}
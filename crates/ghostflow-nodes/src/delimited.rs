@@ -0,0 +1,473 @@
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    BinaryData, DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::{json, Value};
+
+/// Parse CSV text into an array of objects keyed by the header row.
+pub struct CsvParseNode;
+
+/// Write an array of objects as CSV text with a stable column order.
+pub struct CsvWriteNode;
+
+fn csv_error(error: impl std::fmt::Display) -> GhostFlowError {
+    GhostFlowError::ValidationError {
+        message: format!("CSV error: {}", error),
+    }
+}
+
+/// A one-byte setting such as the delimiter. `\t` and `tab` both mean a tab.
+fn single_byte(params: &Value, key: &str, default: u8) -> Result<u8> {
+    match params.get(key).and_then(|v| v.as_str()) {
+        None | Some("") => Ok(default),
+        Some("\\t") | Some("tab") => Ok(b'\t'),
+        Some(text) if text.len() == 1 => Ok(text.as_bytes()[0]),
+        Some(text) => Err(GhostFlowError::ValidationError {
+            message: format!("CSV {} must be a single character, got '{}'", key, text),
+        }),
+    }
+}
+
+fn dialect_parameters() -> Vec<NodeParameter> {
+    vec![
+        NodeParameter {
+            name: "delimiter".to_string(),
+            display_name: "Delimiter".to_string(),
+            description: Some("Field separator; use \\t for tab-separated data".to_string()),
+            param_type: ParameterType::String,
+            default_value: Some(Value::String(",".to_string())),
+            required: false,
+            options: None,
+            validation: None,
+        },
+        NodeParameter {
+            name: "quote".to_string(),
+            display_name: "Quote Character".to_string(),
+            description: Some("Character that wraps fields containing delimiters, quotes or newlines".to_string()),
+            param_type: ParameterType::String,
+            default_value: Some(Value::String("\"".to_string())),
+            required: false,
+            options: None,
+            validation: None,
+        },
+    ]
+}
+
+/// CSV text from the `text` input, or from a binary `file` input such as an
+/// email attachment.
+fn input_text(params: &Value) -> Result<String> {
+    if let Some(file) = params.get("file").filter(|v| !v.is_null()) {
+        let file = BinaryData::from_value(file).ok_or_else(|| GhostFlowError::ValidationError {
+            message: "CSV 'file' input must be binary data".to_string(),
+        })?;
+        return String::from_utf8(file.data).map_err(|_| GhostFlowError::ValidationError {
+            message: "CSV file is not valid UTF-8".to_string(),
+        });
+    }
+    match params.get("text") {
+        Some(Value::String(text)) => Ok(text.clone()),
+        None | Some(Value::Null) => Err(GhostFlowError::ValidationError {
+            message: "CSV text or a file input is required".to_string(),
+        }),
+        Some(_) => Err(GhostFlowError::ValidationError {
+            message: "CSV text must be a string".to_string(),
+        }),
+    }
+}
+
+fn parse(text: &str, params: &Value) -> Result<(Vec<String>, Vec<Value>)> {
+    let has_headers = params.get("has_headers").and_then(|v| v.as_bool()).unwrap_or(true);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(single_byte(params, "delimiter", b',')?)
+        .quote(single_byte(params, "quote", b'"')?)
+        .has_headers(has_headers)
+        // Short rows get nulls for their missing cells instead of failing
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let mut columns: Vec<String> = if has_headers {
+        reader.headers().map_err(csv_error)?.iter().map(str::to_string).collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        // Without a header row, columns are numbered as they appear
+        while columns.len() < record.len() && !has_headers {
+            columns.push(format!("column_{}", columns.len() + 1));
+        }
+        let row: serde_json::Map<String, Value> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| (column.clone(), record.get(i).map_or(Value::Null, |cell| json!(cell))))
+            .collect();
+        rows.push(Value::Object(row));
+    }
+
+    Ok((columns, rows))
+}
+
+/// Column order for writing: the `columns` parameter when given, otherwise
+/// each key in the order it is first seen across the rows.
+fn columns(params: &Value, rows: &[Value]) -> Result<Vec<String>> {
+    if let Some(columns) = params.get("columns").and_then(|v| v.as_array()).filter(|c| !c.is_empty()) {
+        return columns
+            .iter()
+            .map(|c| {
+                c.as_str().map(str::to_string).ok_or_else(|| GhostFlowError::ValidationError {
+                    message: "CSV columns must be strings".to_string(),
+                })
+            })
+            .collect();
+    }
+
+    let mut columns: Vec<String> = Vec::new();
+    for row in rows {
+        for key in row.as_object().into_iter().flat_map(|fields| fields.keys()) {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    Ok(columns)
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        // Numbers and booleans as written; nested values as JSON text
+        Some(other) => other.to_string(),
+    }
+}
+
+fn write(rows: &[Value], params: &Value) -> Result<(String, usize)> {
+    if let Some(bad) = rows.iter().position(|row| !row.is_object()) {
+        return Err(GhostFlowError::ValidationError {
+            message: format!("CSV rows must be objects; item {} is not", bad),
+        });
+    }
+
+    let columns = columns(params, rows)?;
+    let terminator = match params.get("line_ending").and_then(|v| v.as_str()).unwrap_or("lf") {
+        "crlf" => csv::Terminator::CRLF,
+        _ => csv::Terminator::Any(b'\n'),
+    };
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(single_byte(params, "delimiter", b',')?)
+        .quote(single_byte(params, "quote", b'"')?)
+        .terminator(terminator)
+        .from_writer(Vec::new());
+
+    if params.get("include_header").and_then(|v| v.as_bool()).unwrap_or(true) {
+        writer.write_record(&columns).map_err(csv_error)?;
+    }
+    for row in rows {
+        writer
+            .write_record(columns.iter().map(|column| cell(row.get(column))))
+            .map_err(csv_error)?;
+    }
+
+    let bytes = writer.into_inner().map_err(csv_error)?;
+    let text = String::from_utf8(bytes).map_err(csv_error)?;
+    Ok((text, rows.len()))
+}
+
+fn rows(params: &Value) -> Result<&[Value]> {
+    match params.get("items") {
+        None | Some(Value::Null) => Ok(&[]),
+        Some(Value::Array(items)) => Ok(items),
+        Some(_) => Err(GhostFlowError::ValidationError {
+            message: "CSV items must be an array of objects".to_string(),
+        }),
+    }
+}
+
+#[async_trait]
+impl Node for CsvParseNode {
+    fn definition(&self) -> NodeDefinition {
+        let mut parameters = dialect_parameters();
+        parameters.push(NodeParameter {
+            name: "has_headers".to_string(),
+            display_name: "Header Row".to_string(),
+            description: Some("First row names the columns; otherwise columns are column_1, column_2, ...".to_string()),
+            param_type: ParameterType::Boolean,
+            default_value: Some(Value::Bool(true)),
+            required: false,
+            options: None,
+            validation: None,
+        });
+
+        NodeDefinition {
+            id: "csv_parse".to_string(),
+            name: "Parse CSV".to_string(),
+            description: "Turn CSV text into an array of objects".to_string(),
+            category: NodeCategory::Transform,
+            version: "1.0.0".to_string(),
+            inputs: vec![
+                NodePort {
+                    name: "text".to_string(),
+                    display_name: "Text".to_string(),
+                    description: Some("CSV text".to_string()),
+                    data_type: DataType::String,
+                    required: false,
+                },
+                NodePort {
+                    name: "file".to_string(),
+                    display_name: "File".to_string(),
+                    description: Some("CSV file, e.g. an email attachment".to_string()),
+                    data_type: DataType::Binary,
+                    required: false,
+                },
+            ],
+            outputs: vec![
+                NodePort {
+                    name: "rows".to_string(),
+                    display_name: "Rows".to_string(),
+                    description: Some("One object per record, keyed by column".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+                NodePort {
+                    name: "columns".to_string(),
+                    display_name: "Columns".to_string(),
+                    description: Some("Column names in file order".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+            ],
+            parameters,
+            icon: Some("table".to_string()),
+            color: Some("#10b981".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        input_text(&context.input)?;
+        single_byte(&context.input, "delimiter", b',')?;
+        single_byte(&context.input, "quote", b'"').map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let text = input_text(&context.input)?;
+        let (columns, rows) = parse(&text, &context.input)?;
+        Ok(json!({
+            "count": rows.len(),
+            "columns": columns,
+            "rows": rows,
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl Node for CsvWriteNode {
+    fn definition(&self) -> NodeDefinition {
+        let mut parameters = dialect_parameters();
+        parameters.extend([
+            NodeParameter {
+                name: "columns".to_string(),
+                display_name: "Columns".to_string(),
+                description: Some("Columns to write, in order; defaults to every key in first-seen order".to_string()),
+                param_type: ParameterType::Array,
+                default_value: None,
+                required: false,
+                options: None,
+                validation: None,
+            },
+            NodeParameter {
+                name: "include_header".to_string(),
+                display_name: "Header Row".to_string(),
+                description: Some("Write the column names as the first row".to_string()),
+                param_type: ParameterType::Boolean,
+                default_value: Some(Value::Bool(true)),
+                required: false,
+                options: None,
+                validation: None,
+            },
+            NodeParameter {
+                name: "line_ending".to_string(),
+                display_name: "Line Ending".to_string(),
+                description: Some("Record terminator".to_string()),
+                param_type: ParameterType::Select,
+                default_value: Some(Value::String("lf".to_string())),
+                required: false,
+                options: Some(vec![
+                    serde_json::from_str(r#"{"value": "lf", "label": "LF (\\n)"}"#).unwrap(),
+                    serde_json::from_str(r#"{"value": "crlf", "label": "CRLF (\\r\\n)"}"#).unwrap(),
+                ]),
+                validation: None,
+            },
+            NodeParameter {
+                name: "filename".to_string(),
+                display_name: "File Name".to_string(),
+                description: Some("Name given to the file output".to_string()),
+                param_type: ParameterType::String,
+                default_value: Some(Value::String("data.csv".to_string())),
+                required: false,
+                options: None,
+                validation: None,
+            },
+        ]);
+
+        NodeDefinition {
+            id: "csv_write".to_string(),
+            name: "Write CSV".to_string(),
+            description: "Turn an array of objects into CSV text".to_string(),
+            category: NodeCategory::Transform,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "items".to_string(),
+                display_name: "Items".to_string(),
+                description: Some("Array of objects, one per row".to_string()),
+                data_type: DataType::Array,
+                required: true,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "text".to_string(),
+                    display_name: "Text".to_string(),
+                    description: Some("CSV text".to_string()),
+                    data_type: DataType::String,
+                    required: true,
+                },
+                NodePort {
+                    name: "file".to_string(),
+                    display_name: "File".to_string(),
+                    description: Some("The same CSV as a file, e.g. for an email attachment".to_string()),
+                    data_type: DataType::Binary,
+                    required: true,
+                },
+            ],
+            parameters,
+            icon: Some("table".to_string()),
+            color: Some("#10b981".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        let rows = rows(&context.input)?;
+        columns(&context.input, rows)?;
+        single_byte(&context.input, "delimiter", b',')?;
+        single_byte(&context.input, "quote", b'"').map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let (text, count) = write(rows(&context.input)?, &context.input)?;
+        let filename = context.input.get("filename").and_then(|v| v.as_str()).unwrap_or("data.csv");
+        let file = BinaryData::new(text.as_bytes().to_vec())
+            .with_content_type("text/csv")
+            .with_filename(filename);
+
+        Ok(json!({
+            "count": count,
+            "text": text,
+            "file": file.to_value(),
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+
+    #[tokio::test]
+    async fn test_round_trip_with_comma_newline_and_quotes() {
+        let items = json!([
+            { "host": "pve-01", "note": "rebooted, then\nrestored from backup", "count": 2 },
+            { "host": "pve-02", "note": "said \"all good\"", "count": 0 }
+        ]);
+
+        let written = CsvWriteNode
+            .execute(context(json!({ "items": items, "columns": ["host", "note", "count"] })))
+            .await
+            .unwrap();
+        let text = written["text"].as_str().unwrap();
+        assert_eq!(
+            text,
+            "host,note,count\npve-01,\"rebooted, then\nrestored from backup\",2\npve-02,\"said \"\"all good\"\"\",0\n"
+        );
+
+        let parsed = CsvParseNode.execute(context(json!({ "text": text }))).await.unwrap();
+        assert_eq!(parsed["columns"], json!(["host", "note", "count"]));
+        assert_eq!(
+            parsed["rows"],
+            json!([
+                { "host": "pve-01", "note": "rebooted, then\nrestored from backup", "count": "2" },
+                { "host": "pve-02", "note": "said \"all good\"", "count": "0" }
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_column_order_is_stable_across_ragged_rows() {
+        let written = CsvWriteNode
+            .execute(context(json!({
+                "items": [{ "b": 1, "a": 2 }, { "c": true }, { "a": null, "d": { "x": 1 } }]
+            })))
+            .await
+            .unwrap();
+
+        let text = written["text"].as_str().unwrap();
+        let header = text.lines().next().unwrap();
+        assert_eq!(header.split(',').count(), 4);
+        assert!(text.ends_with(",,,\"{\"\"x\"\":1}\"\n"), "{}", text);
+
+        let again = CsvWriteNode
+            .execute(context(json!({
+                "items": [{ "b": 1, "a": 2 }, { "c": true }, { "a": null, "d": { "x": 1 } }]
+            })))
+            .await
+            .unwrap();
+        assert_eq!(again["text"], written["text"]);
+        assert_eq!(BinaryData::from_value(&written["file"]).unwrap().data, text.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_parse_semicolons_without_header() {
+        let parsed = CsvParseNode
+            .execute(context(json!({
+                "text": "pve-01;'up; healthy'\npve-02\n",
+                "delimiter": ";",
+                "quote": "'",
+                "has_headers": false,
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            parsed["rows"],
+            json!([
+                { "column_1": "pve-01", "column_2": "up; healthy" },
+                { "column_1": "pve-02", "column_2": null }
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_settings_fail_validation() {
+        assert!(CsvParseNode.validate(&context(json!({ "text": "a", "delimiter": "||" }))).await.is_err());
+        assert!(CsvParseNode.validate(&context(json!({}))).await.is_err());
+        assert!(CsvWriteNode.execute(context(json!({ "items": [1, 2] }))).await.is_err());
+    }
+}
@@ -0,0 +1,354 @@
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::info;
+
+/// Compare two JSON values and report the paths that were added, removed
+/// or changed. Either diffs `previous` against `current`, or in `stored`
+/// mode diffs `current` against the value this node saw on its last run.
+/// Arrays are compared by position unless `array_key` names a field that
+/// identifies their elements.
+pub struct DiffNode {
+    /// Last value per state key for `stored` mode, kept in process memory
+    previous: Mutex<HashMap<String, Value>>,
+}
+
+impl DiffNode {
+    pub fn new() -> Self {
+        Self {
+            previous: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for DiffNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Path of a child in dotted notation; array elements matched by key are
+/// written `hosts[id=web-1]`.
+fn child_path(parent: &str, segment: &str) -> String {
+    if parent.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{}.{}", parent, segment)
+    }
+}
+
+fn key_label(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[derive(Default)]
+struct Changes {
+    added: Vec<Value>,
+    removed: Vec<Value>,
+    changed: Vec<Value>,
+}
+
+impl Changes {
+    fn diff(&mut self, path: &str, old: &Value, new: &Value, array_key: Option<&str>) {
+        match (old, new) {
+            (Value::Object(old_fields), Value::Object(new_fields)) => {
+                for (name, old_value) in old_fields {
+                    let path = child_path(path, name);
+                    match new_fields.get(name) {
+                        Some(new_value) => self.diff(&path, old_value, new_value, array_key),
+                        None => self.removed.push(json!({ "path": path, "value": old_value })),
+                    }
+                }
+                for (name, new_value) in new_fields {
+                    if !old_fields.contains_key(name) {
+                        self.added.push(json!({ "path": child_path(path, name), "value": new_value }));
+                    }
+                }
+            }
+            (Value::Array(old_items), Value::Array(new_items)) => match array_key {
+                Some(key) if keyed(old_items, key) && keyed(new_items, key) => {
+                    self.diff_keyed(path, old_items, new_items, key)
+                }
+                _ => self.diff_positional(path, old_items, new_items, array_key),
+            },
+            _ if old != new => {
+                self.changed.push(json!({ "path": path, "old": old, "new": new }));
+            }
+            _ => {}
+        }
+    }
+
+    fn diff_positional(&mut self, path: &str, old_items: &[Value], new_items: &[Value], array_key: Option<&str>) {
+        for (index, old_value) in old_items.iter().enumerate() {
+            let path = child_path(path, &index.to_string());
+            match new_items.get(index) {
+                Some(new_value) => self.diff(&path, old_value, new_value, array_key),
+                None => self.removed.push(json!({ "path": path, "value": old_value })),
+            }
+        }
+        for (index, new_value) in new_items.iter().enumerate().skip(old_items.len()) {
+            self.added.push(json!({ "path": child_path(path, &index.to_string()), "value": new_value }));
+        }
+    }
+
+    fn diff_keyed(&mut self, path: &str, old_items: &[Value], new_items: &[Value], key: &str) {
+        let element_path = |item: &Value| format!("{}[{}={}]", path, key, key_label(&item[key]));
+        for old_value in old_items {
+            match new_items.iter().find(|item| item[key] == old_value[key]) {
+                Some(new_value) => self.diff(&element_path(old_value), old_value, new_value, Some(key)),
+                None => self.removed.push(json!({ "path": element_path(old_value), "value": old_value })),
+            }
+        }
+        for new_value in new_items {
+            if !old_items.iter().any(|item| item[key] == new_value[key]) {
+                self.added.push(json!({ "path": element_path(new_value), "value": new_value }));
+            }
+        }
+    }
+
+    fn into_output(self) -> Value {
+        json!({
+            "has_changes": !(self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()),
+            "added": self.added,
+            "removed": self.removed,
+            "changed": self.changed,
+        })
+    }
+}
+
+/// Whether every element is an object carrying `key`
+fn keyed(items: &[Value], key: &str) -> bool {
+    items.iter().all(|item| item.get(key).is_some_and(|v| !v.is_null()))
+}
+
+/// Stand-in for a value never seen before, so a first run reports the
+/// contents of `current` as added.
+fn empty_like(value: &Value) -> Value {
+    match value {
+        Value::Object(_) => Value::Object(Map::new()),
+        Value::Array(_) => Value::Array(Vec::new()),
+        _ => Value::Null,
+    }
+}
+
+fn mode(params: &Value) -> Result<&str> {
+    match params.get("mode").and_then(|v| v.as_str()).unwrap_or("inputs") {
+        mode @ ("inputs" | "stored") => Ok(mode),
+        other => Err(GhostFlowError::ValidationError {
+            message: format!("Unknown diff mode: {}", other),
+        }),
+    }
+}
+
+#[async_trait]
+impl Node for DiffNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "diff".to_string(),
+            name: "Diff".to_string(),
+            description: "Report paths added, removed or changed between two JSON values".to_string(),
+            category: NodeCategory::Transform,
+            version: "1.0.0".to_string(),
+            inputs: vec![
+                NodePort {
+                    name: "current".to_string(),
+                    display_name: "Current".to_string(),
+                    description: Some("The new value".to_string()),
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "previous".to_string(),
+                    display_name: "Previous".to_string(),
+                    description: Some("The value to compare against, in inputs mode".to_string()),
+                    data_type: DataType::Any,
+                    required: false,
+                },
+            ],
+            outputs: vec![
+                NodePort {
+                    name: "has_changes".to_string(),
+                    display_name: "Has Changes".to_string(),
+                    description: Some("True when anything was added, removed or changed".to_string()),
+                    data_type: DataType::Boolean,
+                    required: true,
+                },
+                NodePort {
+                    name: "added".to_string(),
+                    display_name: "Added".to_string(),
+                    description: Some("New paths with their values".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+                NodePort {
+                    name: "removed".to_string(),
+                    display_name: "Removed".to_string(),
+                    description: Some("Paths no longer present, with their old values".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+                NodePort {
+                    name: "changed".to_string(),
+                    display_name: "Changed".to_string(),
+                    description: Some("Paths whose value differs, with old and new values".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+            ],
+            parameters: vec![
+                NodeParameter {
+                    name: "mode".to_string(),
+                    display_name: "Mode".to_string(),
+                    description: Some("Compare two inputs, or the current input with the last one seen".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("inputs".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "inputs", "label": "Previous vs Current"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "stored", "label": "Last Run vs Current"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "array_key".to_string(),
+                    display_name: "Array Key".to_string(),
+                    description: Some("Field that identifies array elements, e.g. id; arrays are compared by position without it".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "state_key".to_string(),
+                    display_name: "State Key".to_string(),
+                    description: Some("Name the last value is stored under in stored mode; defaults to the flow and node".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("git-compare".to_string()),
+            color: Some("#0ea5e9".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        mode(&context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let current = params.get("current").cloned().unwrap_or(Value::Null);
+        let array_key = params.get("array_key").and_then(|v| v.as_str()).filter(|k| !k.is_empty());
+
+        let previous = match mode(params)? {
+            "stored" => {
+                let key = params
+                    .get("state_key")
+                    .and_then(|v| v.as_str())
+                    .filter(|k| !k.is_empty())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("{}:{}", context.flow_id, context.node_id));
+                self.previous
+                    .lock()
+                    .unwrap()
+                    .insert(key, current.clone())
+                    .unwrap_or_else(|| empty_like(&current))
+            }
+            _ => params.get("previous").cloned().unwrap_or_else(|| empty_like(&current)),
+        };
+
+        let mut changes = Changes::default();
+        changes.diff("", &previous, &current, array_key);
+        info!(
+            "Diff found {} added, {} removed and {} changed paths",
+            changes.added.len(),
+            changes.removed.len(),
+            changes.changed.len()
+        );
+        Ok(changes.into_output())
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        // Stored mode depends on earlier runs
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+
+    #[tokio::test]
+    async fn test_nested_object_changes() {
+        let output = DiffNode::new()
+            .execute(context(json!({
+                "previous": { "host": { "cpu": 40, "disk": "ok", "tags": ["a"] }, "owner": "ops" },
+                "current": { "host": { "cpu": 95, "tags": ["a"], "memory": 70 } },
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["has_changes"], true);
+        assert_eq!(output["changed"], json!([{ "path": "host.cpu", "old": 40, "new": 95 }]));
+        assert_eq!(output["added"], json!([{ "path": "host.memory", "value": 70 }]));
+        assert_eq!(
+            output["removed"],
+            json!([{ "path": "host.disk", "value": "ok" }, { "path": "owner", "value": "ops" }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_array_elements_added_by_position_and_by_key() {
+        let node = DiffNode::new();
+        let previous = json!({ "hosts": [{ "id": "web-1", "up": true }, { "id": "web-2", "up": true }] });
+        let current = json!({ "hosts": [{ "id": "web-2", "up": false }, { "id": "web-1", "up": true }, { "id": "web-3", "up": true }] });
+
+        let by_key = node
+            .execute(context(json!({ "previous": previous, "current": current, "array_key": "id" })))
+            .await
+            .unwrap();
+        assert_eq!(by_key["added"], json!([{ "path": "hosts[id=web-3]", "value": { "id": "web-3", "up": true } }]));
+        assert_eq!(by_key["changed"], json!([{ "path": "hosts[id=web-2].up", "old": true, "new": false }]));
+        assert_eq!(by_key["removed"], json!([]));
+
+        let by_position = node
+            .execute(context(json!({ "previous": previous, "current": current })))
+            .await
+            .unwrap();
+        assert_eq!(by_position["added"][0]["path"], "hosts.2");
+        assert_eq!(by_position["changed"].as_array().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stored_mode_compares_with_last_run() {
+        let node = DiffNode::new();
+        let input = |current: Value| json!({ "mode": "stored", "state_key": "inventory", "current": current });
+
+        let first = node.execute(context(input(json!({ "vms": 3 })))).await.unwrap();
+        assert_eq!(first["added"], json!([{ "path": "vms", "value": 3 }]));
+
+        let unchanged = node.execute(context(input(json!({ "vms": 3 })))).await.unwrap();
+        assert_eq!(unchanged["has_changes"], false);
+
+        let changed = node.execute(context(input(json!({ "vms": 4 })))).await.unwrap();
+        assert_eq!(changed["changed"], json!([{ "path": "vms", "old": 3, "new": 4 }]));
+    }
+}
@@ -0,0 +1,434 @@
+use async_trait::async_trait;
+use ghostflow_core::{EscalationStore, GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// One band of an escalation policy: alerts whose level falls within
+/// `min_level..=max_level` go to `targets`.
+#[derive(Debug, Clone, Deserialize)]
+struct Tier {
+    name: String,
+    min_level: f64,
+    #[serde(default)]
+    max_level: Option<f64>,
+    #[serde(default)]
+    targets: Vec<Value>,
+    #[serde(default)]
+    mentions: Vec<String>,
+}
+
+impl Tier {
+    fn matches(&self, level: f64) -> bool {
+        level >= self.min_level && self.max_level.map_or(true, |max| level <= max)
+    }
+}
+
+/// Wazuh-style levels: 13+ pages on-call, 7-12 goes to the alerts channel.
+/// Overlapping tiers resolve to the one with the highest `min_level`.
+fn default_policy() -> Vec<Tier> {
+    vec![
+        Tier {
+            name: "critical".to_string(),
+            min_level: 13.0,
+            max_level: None,
+            targets: vec![json!({ "type": "pager", "target": "on-call" }), json!({ "type": "chat", "target": "#security-alerts" })],
+            mentions: vec!["@channel".to_string()],
+        },
+        Tier {
+            name: "high".to_string(),
+            min_level: 7.0,
+            max_level: None,
+            targets: vec![json!({ "type": "chat", "target": "#security-alerts" })],
+            mentions: Vec::new(),
+        },
+    ]
+}
+
+/// Value at a dotted path such as `rule.level`
+fn field<'a>(item: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(item, |current, segment| match current {
+        Value::Object(fields) => fields.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+struct Params {
+    alert: Value,
+    level: f64,
+    policy: Vec<Tier>,
+    window: Duration,
+    dedup_fields: Vec<String>,
+}
+
+impl Params {
+    fn from_input(input: &Value) -> Result<Self> {
+        let alert = input.get("alert").cloned().unwrap_or(Value::Null);
+        if !alert.is_object() {
+            return Err(GhostFlowError::ValidationError {
+                message: "Escalation needs an alert object".to_string(),
+            });
+        }
+
+        let level_field = input.get("level_field").and_then(|v| v.as_str()).unwrap_or("rule.level");
+        let level = field(&alert, level_field)
+            .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.trim().parse().ok())))
+            .ok_or_else(|| GhostFlowError::ValidationError {
+                message: format!("Alert has no numeric level at '{}'", level_field),
+            })?;
+
+        let policy = match input.get("policy") {
+            None | Some(Value::Null) => default_policy(),
+            Some(policy) => serde_json::from_value::<Vec<Tier>>(policy.clone()).map_err(|e| {
+                GhostFlowError::ValidationError {
+                    message: format!("Invalid escalation policy: {}", e),
+                }
+            })?,
+        };
+
+        let window = input.get("dedup_window_secs").and_then(|v| v.as_f64()).unwrap_or(300.0);
+        if !window.is_finite() || window < 0.0 {
+            return Err(GhostFlowError::ValidationError {
+                message: "dedup_window_secs must not be negative".to_string(),
+            });
+        }
+        let window = Duration::try_from_secs_f64(window).map_err(|_| GhostFlowError::ValidationError {
+            message: format!("dedup_window_secs {} is too large", window),
+        })?;
+
+        let dedup_fields = input
+            .get("dedup_fields")
+            .and_then(|v| v.as_str())
+            .unwrap_or("rule.id,agent.id")
+            .split(',')
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty())
+            .collect();
+
+        Ok(Self {
+            alert,
+            level,
+            policy,
+            window,
+            dedup_fields,
+        })
+    }
+
+    /// The most severe tier the alert's level falls in
+    fn tier(&self) -> Option<&Tier> {
+        self.policy
+            .iter()
+            .filter(|tier| tier.matches(self.level))
+            .max_by(|a, b| a.min_level.total_cmp(&b.min_level))
+    }
+
+    fn dedup_key(&self) -> String {
+        self.dedup_fields
+            .iter()
+            .map(|path| match field(&self.alert, path) {
+                Some(Value::String(s)) => s.clone(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+}
+
+/// Decide who to notify about an alert from a tiered severity policy, and
+/// hold back repeats of the same alert within a time window.
+pub struct EscalationNode {
+    escalations: Arc<EscalationStore>,
+}
+
+impl EscalationNode {
+    pub fn new() -> Self {
+        Self {
+            escalations: Arc::new(EscalationStore::new()),
+        }
+    }
+
+    /// Remember escalated alerts in `escalations`
+    pub fn with_escalations(mut self, escalations: Arc<EscalationStore>) -> Self {
+        self.escalations = escalations;
+        self
+    }
+}
+
+impl Default for EscalationNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for EscalationNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "escalation".to_string(),
+            name: "Escalation Policy".to_string(),
+            description: "Route an alert to notification targets by severity tier, suppressing repeats".to_string(),
+            category: NodeCategory::ControlFlow,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "alert".to_string(),
+                display_name: "Alert".to_string(),
+                description: Some("Alert to route, e.g. a Wazuh alert".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "escalate".to_string(),
+                    display_name: "Escalate".to_string(),
+                    description: Some("Whether any target should be notified".to_string()),
+                    data_type: DataType::Boolean,
+                    required: true,
+                },
+                NodePort {
+                    name: "tier".to_string(),
+                    display_name: "Tier".to_string(),
+                    description: Some("Name of the matching tier; null when none matches".to_string()),
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "targets".to_string(),
+                    display_name: "Targets".to_string(),
+                    description: Some("Channels or roles to notify; empty when suppressed".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+                NodePort {
+                    name: "deduplicated".to_string(),
+                    display_name: "Deduplicated".to_string(),
+                    description: Some("The same alert was escalated within the window".to_string()),
+                    data_type: DataType::Boolean,
+                    required: true,
+                },
+            ],
+            parameters: vec![
+                NodeParameter {
+                    name: "policy".to_string(),
+                    display_name: "Policy".to_string(),
+                    description: Some(
+                        "Tiers as [{name, min_level, max_level, targets, mentions}]; defaults to 13+ paging on-call and 7-12 to the alerts channel".to_string(),
+                    ),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "level_field".to_string(),
+                    display_name: "Level Field".to_string(),
+                    description: Some("Dotted path to the alert's severity level".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("rule.level".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "dedup_window_secs".to_string(),
+                    display_name: "Dedup Window (seconds)".to_string(),
+                    description: Some("Suppress repeats of an escalated alert for this long; 0 disables".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(json!(300)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "dedup_fields".to_string(),
+                    display_name: "Dedup Fields".to_string(),
+                    description: Some("Comma-separated dotted paths that identify the same alert".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("rule.id,agent.id".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("siren".to_string()),
+            color: Some("#dc2626".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        Params::from_input(&context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = Params::from_input(&context.input)?;
+        let dedup_key = params.dedup_key();
+
+        let Some(tier) = params.tier() else {
+            return Ok(json!({
+                "escalate": false,
+                "tier": Value::Null,
+                "level": params.level,
+                "targets": [],
+                "mentions": [],
+                "deduplicated": false,
+                "suppressed": 0,
+                "dedup_key": dedup_key,
+            }));
+        };
+
+        // Keyed by tier too, so an alert that worsens is escalated again
+        let suppressed = if params.window.is_zero() {
+            None
+        } else {
+            self.escalations.check(scoped_key(context.flow_id, &tier.name, &dedup_key), params.window)
+        };
+        let deduplicated = suppressed.is_some();
+
+        Ok(json!({
+            "escalate": !deduplicated,
+            "tier": tier.name,
+            "level": params.level,
+            "targets": if deduplicated { Vec::new() } else { tier.targets.clone() },
+            "mentions": if deduplicated { Vec::new() } else { tier.mentions.clone() },
+            "deduplicated": deduplicated,
+            "suppressed": suppressed.unwrap_or(0),
+            "dedup_key": dedup_key,
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+fn scoped_key(flow_id: Uuid, tier: &str, dedup_key: &str) -> String {
+    format!("{}|{}|{}", flow_id, tier, dedup_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+
+    fn alert(level: u64, rule: &str, agent: &str) -> Value {
+        json!({ "rule": { "id": rule, "level": level }, "agent": { "id": agent } })
+    }
+
+    #[tokio::test]
+    async fn test_default_policy_selects_tier_by_level() {
+        let route = |level| async move {
+            EscalationNode::new()
+                .execute(context(json!({ "alert": alert(level, "5710", "001"), "dedup_window_secs": 0 })))
+                .await
+                .unwrap()
+        };
+
+        let critical = route(14).await;
+        assert_eq!(critical["escalate"], true);
+        assert_eq!(critical["tier"], "critical");
+        assert_eq!(critical["targets"][0], json!({ "type": "pager", "target": "on-call" }));
+        assert_eq!(critical["mentions"], json!(["@channel"]));
+
+        let high = route(9).await;
+        assert_eq!(high["tier"], "high");
+        assert_eq!(high["targets"], json!([{ "type": "chat", "target": "#security-alerts" }]));
+        assert_eq!(high["mentions"], json!([]));
+
+        let low = route(3).await;
+        assert_eq!(low["escalate"], false);
+        assert_eq!(low["tier"], Value::Null);
+        assert_eq!(low["targets"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_custom_policy_prefers_most_severe_overlapping_tier() {
+        let output = EscalationNode::new()
+            .execute(context(json!({
+                "alert": { "severity": "8", "id": "disk" },
+                "level_field": "severity",
+                "dedup_window_secs": 0,
+                "policy": [
+                    { "name": "ops", "min_level": 1, "targets": ["#ops"] },
+                    { "name": "sre", "min_level": 5, "max_level": 9, "targets": ["#sre"], "mentions": ["@sre-oncall"] },
+                    { "name": "exec", "min_level": 10, "targets": ["cto"] }
+                ]
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["tier"], "sre");
+        assert_eq!(output["level"], 8.0);
+        assert_eq!(output["targets"], json!(["#sre"]));
+        assert_eq!(output["mentions"], json!(["@sre-oncall"]));
+    }
+
+    #[tokio::test]
+    async fn test_repeats_within_window_are_suppressed() {
+        let node = EscalationNode::new();
+        let flow_id = Uuid::new_v4();
+        let run = |alert: Value| {
+            let mut context = context(json!({ "alert": alert, "dedup_window_secs": 0.2 }));
+            context.flow_id = flow_id;
+            node.execute(context)
+        };
+
+        let first = run(alert(13, "5710", "001")).await.unwrap();
+        assert_eq!(first["escalate"], true);
+        assert_eq!(first["deduplicated"], false);
+
+        let repeat = run(alert(13, "5710", "001")).await.unwrap();
+        assert_eq!(repeat["escalate"], false);
+        assert_eq!(repeat["deduplicated"], true);
+        assert_eq!(repeat["suppressed"], 1);
+        assert_eq!(repeat["targets"], json!([]));
+        assert_eq!(run(alert(14, "5710", "001")).await.unwrap()["suppressed"], 2);
+
+        // A different agent, or the same alert in another tier, still escalates
+        assert_eq!(run(alert(13, "5710", "002")).await.unwrap()["escalate"], true);
+        assert_eq!(run(alert(10, "5710", "001")).await.unwrap()["escalate"], true);
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(run(alert(13, "5710", "001")).await.unwrap()["escalate"], true);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_input_fails_validation() {
+        let node = EscalationNode::new();
+        assert!(node.validate(&context(json!({ "alert": { "rule": {} } }))).await.is_err());
+        assert!(node.validate(&context(json!({ "alert": "disk full" }))).await.is_err());
+        assert!(node
+            .validate(&context(json!({ "alert": alert(5, "1", "1"), "policy": [{ "name": "x" }] })))
+            .await
+            .is_err());
+        assert!(node
+            .validate(&context(json!({ "alert": alert(5, "1", "1"), "dedup_window_secs": -1 })))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_huge_dedup_window_is_a_parameter_error() {
+        let node = EscalationNode::new();
+
+        let result = node
+            .execute(context(json!({ "alert": alert(13, "5710", "001"), "dedup_window_secs": 1e20 })))
+            .await;
+        assert!(matches!(result, Err(GhostFlowError::ValidationError { .. })));
+
+        // Longer than the clock can count, but still a valid window
+        let run = || node.execute(context(json!({ "alert": alert(13, "5710", "001"), "dedup_window_secs": 1e19 })));
+        assert_eq!(run().await.unwrap()["escalate"], true);
+    }
+}
@@ -0,0 +1,482 @@
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    BinaryData, DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Component, Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+use tracing::info;
+
+/// Deployment-level guard for the file nodes: every path a flow names is
+/// resolved inside `root` and may not leave it, through `..` or a symlink.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileNodeConfig {
+    pub root: PathBuf,
+}
+
+impl Default for FileNodeConfig {
+    fn default() -> Self {
+        Self {
+            root: std::env::var("GHOSTFLOW_FILE_ROOT")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("./files")),
+        }
+    }
+}
+
+impl FileNodeConfig {
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Map a flow-supplied path onto the filesystem. Relative paths are
+    /// taken from the root; absolute ones must already lie under it.
+    pub async fn resolve(&self, path: &str) -> Result<PathBuf> {
+        if path.is_empty() {
+            return Err(GhostFlowError::ValidationError {
+                message: "File path is required".to_string(),
+            });
+        }
+        let root = tokio::fs::canonicalize(&self.root)
+            .await
+            .map_err(|e| GhostFlowError::ConfigurationError {
+                message: format!("File root {} is not usable: {}", self.root.display(), e),
+            })?;
+
+        let requested = Path::new(path);
+        let relative = if requested.is_absolute() {
+            requested
+                .strip_prefix(&root)
+                .or_else(|_| requested.strip_prefix(&self.root))
+                .map_err(|_| outside_root(path))?
+        } else {
+            requested
+        };
+
+        // Resolve `.` and `..` without touching the disk, refusing to climb above the root
+        let mut normalized = PathBuf::new();
+        for component in relative.components() {
+            match component {
+                Component::Normal(part) => normalized.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !normalized.pop() {
+                        return Err(outside_root(path));
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => return Err(outside_root(path)),
+            }
+        }
+        if normalized.as_os_str().is_empty() {
+            return Err(GhostFlowError::ValidationError {
+                message: format!("File path '{}' names the root directory", path),
+            });
+        }
+
+        // A symlink inside the root could still point outside it, so check
+        // where the deepest existing ancestor really is
+        let target = root.join(&normalized);
+        let mut existing = target.as_path();
+        let mut missing = Vec::new();
+        let real = loop {
+            match tokio::fs::canonicalize(existing).await {
+                Ok(real) => break real,
+                Err(_) => {
+                    // Present but unresolvable means a dangling symlink, which a
+                    // write would follow wherever it points
+                    if tokio::fs::symlink_metadata(existing).await.is_ok() {
+                        return Err(outside_root(path));
+                    }
+                    missing.push(existing.file_name().unwrap_or_default().to_os_string());
+                    existing = existing.parent().unwrap_or(&root);
+                }
+            }
+        };
+        if !real.starts_with(&root) {
+            return Err(outside_root(path));
+        }
+        Ok(missing.into_iter().rev().fold(real, |resolved, part| resolved.join(part)))
+    }
+}
+
+fn outside_root(path: &str) -> GhostFlowError {
+    GhostFlowError::ValidationError {
+        message: format!("File path '{}' is outside the allowed root directory", path),
+    }
+}
+
+fn path_param(params: &Value) -> Result<&str> {
+    params
+        .get("path")
+        .and_then(|v| v.as_str())
+        .filter(|path| !path.is_empty())
+        .ok_or_else(|| GhostFlowError::ValidationError {
+            message: "File path is required and must be a string".to_string(),
+        })
+}
+
+fn encoding(params: &Value) -> Result<&str> {
+    match params.get("encoding").and_then(|v| v.as_str()).unwrap_or("text") {
+        encoding @ ("text" | "binary") => Ok(encoding),
+        other => Err(GhostFlowError::ValidationError {
+            message: format!("Unknown file encoding '{}'; expected text or binary", other),
+        }),
+    }
+}
+
+fn io_error(node_id: &str, action: &str, path: &Path, error: std::io::Error) -> GhostFlowError {
+    GhostFlowError::NodeExecutionError {
+        node_id: node_id.to_string(),
+        message: format!("Failed to {} {}: {}", action, path.display(), error),
+    }
+}
+
+fn path_parameter(description: &str) -> NodeParameter {
+    NodeParameter {
+        name: "path".to_string(),
+        display_name: "Path".to_string(),
+        description: Some(description.to_string()),
+        param_type: ParameterType::String,
+        default_value: None,
+        required: true,
+        options: None,
+        validation: None,
+    }
+}
+
+/// Reads a file under the configured root as text or binary data
+pub struct ReadFileNode {
+    config: FileNodeConfig,
+}
+
+impl ReadFileNode {
+    pub fn new() -> Self {
+        Self {
+            config: FileNodeConfig::default(),
+        }
+    }
+
+    pub fn with_config(config: FileNodeConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Default for ReadFileNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes text, binary data or JSON to a file under the configured root
+pub struct WriteFileNode {
+    config: FileNodeConfig,
+}
+
+impl WriteFileNode {
+    pub fn new() -> Self {
+        Self {
+            config: FileNodeConfig::default(),
+        }
+    }
+
+    pub fn with_config(config: FileNodeConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Default for WriteFileNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for ReadFileNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "read_file".to_string(),
+            name: "Read File".to_string(),
+            description: "Read a local file as text or binary data".to_string(),
+            category: NodeCategory::Data,
+            version: "1.0.0".to_string(),
+            inputs: vec![],
+            outputs: vec![
+                NodePort {
+                    name: "content".to_string(),
+                    display_name: "Content".to_string(),
+                    description: Some("File contents, with text encoding".to_string()),
+                    data_type: DataType::String,
+                    required: false,
+                },
+                NodePort {
+                    name: "file".to_string(),
+                    display_name: "File".to_string(),
+                    description: Some("File contents, with binary encoding".to_string()),
+                    data_type: DataType::Binary,
+                    required: false,
+                },
+            ],
+            parameters: vec![
+                path_parameter("File to read, relative to the file root"),
+                NodeParameter {
+                    name: "encoding".to_string(),
+                    display_name: "Encoding".to_string(),
+                    description: Some("Read UTF-8 text or raw bytes".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("text".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "text", "label": "Text"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "binary", "label": "Binary"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+            ],
+            icon: Some("file".to_string()),
+            color: Some("#0ea5e9".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        path_param(&context.input)?;
+        encoding(&context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let requested = path_param(params)?;
+        let path = self.config.resolve(requested).await?;
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|e| io_error(&context.node_id, "read", &path, e))?;
+        info!("Read {} bytes from {}", bytes.len(), path.display());
+
+        let size = bytes.len();
+        if encoding(params)? == "binary" {
+            let mut file = BinaryData::new(bytes);
+            if let Some(name) = path.file_name() {
+                file = file.with_filename(name.to_string_lossy());
+            }
+            return Ok(json!({ "path": requested, "size": size, "file": file.to_value() }));
+        }
+
+        let content = String::from_utf8(bytes).map_err(|_| GhostFlowError::NodeExecutionError {
+            node_id: context.node_id.clone(),
+            message: format!("{} is not valid UTF-8; read it with binary encoding", requested),
+        })?;
+        Ok(json!({ "path": requested, "size": size, "content": content }))
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl Node for WriteFileNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "write_file".to_string(),
+            name: "Write File".to_string(),
+            description: "Write text or binary data to a local file".to_string(),
+            category: NodeCategory::Data,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "content".to_string(),
+                display_name: "Content".to_string(),
+                description: Some("Text, binary data, or any other value to write as JSON".to_string()),
+                data_type: DataType::Any,
+                required: true,
+            }],
+            outputs: vec![NodePort {
+                name: "result".to_string(),
+                display_name: "Result".to_string(),
+                description: Some("Path written and the number of bytes".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            parameters: vec![
+                path_parameter("File to write, relative to the file root"),
+                NodeParameter {
+                    name: "mode".to_string(),
+                    display_name: "Mode".to_string(),
+                    description: Some("Replace the file or add to its end".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("overwrite".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "overwrite", "label": "Overwrite"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "append", "label": "Append"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "create_dirs".to_string(),
+                    display_name: "Create Directories".to_string(),
+                    description: Some("Create missing parent directories".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("file".to_string()),
+            color: Some("#0ea5e9".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        path_param(&context.input)?;
+        write_mode(&context.input)?;
+        if context.input.get("content").is_none() {
+            return Err(GhostFlowError::ValidationError {
+                message: "File content is required".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let requested = path_param(params)?;
+        let append = write_mode(params)? == "append";
+        let path = self.config.resolve(requested).await?;
+
+        let bytes = match params.get("content") {
+            Some(Value::String(text)) => text.clone().into_bytes(),
+            Some(value) if BinaryData::is_binary(value) => {
+                BinaryData::from_value(value)
+                    .ok_or_else(|| GhostFlowError::ValidationError {
+                        message: "File content is not valid base64 binary data".to_string(),
+                    })?
+                    .data
+            }
+            Some(Value::Null) | None => Vec::new(),
+            Some(value) => serde_json::to_vec_pretty(value)?,
+        };
+
+        if params.get("create_dirs").and_then(|v| v.as_bool()).unwrap_or(false) {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| io_error(&context.node_id, "create", parent, e))?;
+            }
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(&path)
+            .await
+            .map_err(|e| io_error(&context.node_id, "open", &path, e))?;
+        file.write_all(&bytes)
+            .await
+            .map_err(|e| io_error(&context.node_id, "write", &path, e))?;
+        file.flush().await.map_err(|e| io_error(&context.node_id, "write", &path, e))?;
+        info!("Wrote {} bytes to {}", bytes.len(), path.display());
+
+        Ok(json!({
+            "path": requested,
+            "bytes_written": bytes.len(),
+            "appended": append,
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+fn write_mode(params: &Value) -> Result<&str> {
+    match params.get("mode").and_then(|v| v.as_str()).unwrap_or("overwrite") {
+        mode @ ("overwrite" | "append") => Ok(mode),
+        other => Err(GhostFlowError::ValidationError {
+            message: format!("Unknown write mode '{}'; expected overwrite or append", other),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use uuid::Uuid;
+
+    fn root() -> PathBuf {
+        let root = std::env::temp_dir().join(format!("ghostflow-files-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[tokio::test]
+    async fn test_write_then_read_round_trip() {
+        let config = FileNodeConfig::with_root(root());
+        let writer = WriteFileNode::with_config(config.clone());
+        let reader = ReadFileNode::with_config(config.clone());
+
+        let written = writer
+            .execute(context(json!({ "path": "reports/today.log", "content": "first\n", "create_dirs": true })))
+            .await
+            .unwrap();
+        assert_eq!(written["bytes_written"], 6);
+        writer
+            .execute(context(json!({ "path": "reports/today.log", "content": "second\n", "mode": "append" })))
+            .await
+            .unwrap();
+        let read = reader.execute(context(json!({ "path": "reports/today.log" }))).await.unwrap();
+        assert_eq!(read["content"], "first\nsecond\n");
+
+        let blob = BinaryData::new(vec![0u8, 159, 146, 150, 255]);
+        writer
+            .execute(context(json!({ "path": "reports/blob.bin", "content": blob.to_value() })))
+            .await
+            .unwrap();
+        let read = reader
+            .execute(context(json!({ "path": "reports/blob.bin", "encoding": "binary" })))
+            .await
+            .unwrap();
+        let file = BinaryData::from_value(&read["file"]).unwrap();
+        assert_eq!(file.data, blob.data);
+        assert_eq!(file.filename.as_deref(), Some("blob.bin"));
+
+        std::fs::remove_dir_all(&config.root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_traversal_outside_root_is_rejected() {
+        let config = FileNodeConfig::with_root(root());
+        let reader = ReadFileNode::with_config(config.clone());
+        let writer = WriteFileNode::with_config(config.clone());
+
+        for path in ["../../etc/passwd", "/etc/passwd", "logs/../../secret"] {
+            let err = reader.execute(context(json!({ "path": path }))).await.unwrap_err();
+            assert!(err.to_string().contains("outside the allowed root"), "{}: {}", path, err);
+            let err = writer
+                .execute(context(json!({ "path": path, "content": "x", "create_dirs": true })))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("outside the allowed root"), "{}: {}", path, err);
+        }
+
+        // Climbing out and back in is fine as long as it stays inside
+        assert!(config.resolve("logs/../today.log").await.unwrap().starts_with(config.root.canonicalize().unwrap()));
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("/etc", config.root.join("etc")).unwrap();
+            assert!(config.resolve("etc/passwd").await.is_err());
+        }
+
+        std::fs::remove_dir_all(&config.root).unwrap();
+    }
+}
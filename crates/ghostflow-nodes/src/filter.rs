@@ -0,0 +1,217 @@
+use async_trait::async_trait;
+use ghostflow_core::{evaluate_condition, GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::{json, Value};
+
+/// Keep the elements of an array for which a condition holds, e.g.
+/// `cpu > 0.9 && status == "running"`.
+pub struct FilterNode;
+
+impl FilterNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for FilterNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn condition(params: &Value) -> Result<&str> {
+    params
+        .get("condition")
+        .and_then(|v| v.as_str())
+        .filter(|c| !c.trim().is_empty())
+        .ok_or_else(|| GhostFlowError::ValidationError {
+            message: "Filter condition is required".to_string(),
+        })
+}
+
+fn items(params: &Value) -> Result<&[Value]> {
+    match params.get("items") {
+        None | Some(Value::Null) => Ok(&[]),
+        Some(Value::Array(items)) => Ok(items),
+        Some(_) => Err(GhostFlowError::ValidationError {
+            message: "Filter items must be an array".to_string(),
+        }),
+    }
+}
+
+/// What the condition sees for one element: the element as `item`, its
+/// position as `index`, and an object element's fields at the top level so
+/// `price > 10` and `item.price > 10` both work.
+fn scope(item: &Value, index: usize) -> Value {
+    let mut scope = match item {
+        Value::Object(fields) => fields.clone(),
+        _ => serde_json::Map::new(),
+    };
+    scope.insert("item".to_string(), item.clone());
+    scope.insert("index".to_string(), json!(index));
+    Value::Object(scope)
+}
+
+#[async_trait]
+impl Node for FilterNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "filter".to_string(),
+            name: "Filter".to_string(),
+            description: "Keep only the array elements that match a condition".to_string(),
+            category: NodeCategory::Transform,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "items".to_string(),
+                display_name: "Items".to_string(),
+                description: Some("Array to filter".to_string()),
+                data_type: DataType::Array,
+                required: true,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "items".to_string(),
+                    display_name: "Items".to_string(),
+                    description: Some("Elements that matched, in their original order".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+                NodePort {
+                    name: "count".to_string(),
+                    display_name: "Count".to_string(),
+                    description: Some("How many elements matched".to_string()),
+                    data_type: DataType::Number,
+                    required: true,
+                },
+            ],
+            parameters: vec![NodeParameter {
+                name: "condition".to_string(),
+                display_name: "Condition".to_string(),
+                description: Some(
+                    "Expression per element; fields are available directly or via item, e.g. item.size_gb > 100"
+                        .to_string(),
+                ),
+                param_type: ParameterType::Code,
+                default_value: None,
+                required: true,
+                options: None,
+                validation: None,
+            }],
+            icon: Some("filter".to_string()),
+            color: Some("#10b981".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        items(&context.input)?;
+        // Evaluating against null surfaces syntax errors without any data
+        evaluate_condition(condition(&context.input)?, &Value::Null).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let condition = condition(&context.input)?;
+        let items = items(&context.input)?;
+
+        let mut matched = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            if evaluate_condition(condition, &scope(item, index))? {
+                matched.push(item.clone());
+            }
+        }
+
+        Ok(json!({
+            "count": matched.len(),
+            "total": items.len(),
+            "items": matched,
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+
+    fn vms() -> Value {
+        json!([
+            { "name": "web-01", "cpu": 0.35, "tags": { "env": "prod" } },
+            { "name": "db-01", "cpu": 0.92, "tags": { "env": "prod" } },
+            { "name": "ci-runner", "cpu": 0.97, "tags": { "env": "dev" } },
+            { "name": "cache-01", "cpu": 0.9, "tags": { "env": "prod" } }
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_keeps_elements_above_threshold() {
+        let output = FilterNode::new()
+            .execute(context(json!({ "items": vms(), "condition": "cpu > 0.9" })))
+            .await
+            .unwrap();
+
+        let names: Vec<&str> = output["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|vm| vm["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["db-01", "ci-runner"]);
+        assert_eq!(output["count"], 2);
+        assert_eq!(output["total"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_condition_reads_nested_fields_through_item() {
+        let output = FilterNode::new()
+            .execute(context(json!({
+                "items": vms(),
+                "condition": "item.cpu >= 0.9 && item.tags.env == \"prod\"",
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["count"], 2);
+        assert_eq!(output["items"][0]["name"], "db-01");
+        assert_eq!(output["items"][1]["name"], "cache-01");
+    }
+
+    #[tokio::test]
+    async fn test_scalar_elements_and_empty_input() {
+        let node = FilterNode::new();
+        let output = node
+            .execute(context(json!({ "items": [3, 12, 7, 40], "condition": "item > 10" })))
+            .await
+            .unwrap();
+        assert_eq!(output["items"], json!([12, 40]));
+
+        let output = node
+            .execute(context(json!({ "items": [], "condition": "item > 10" })))
+            .await
+            .unwrap();
+        assert_eq!(output["count"], 0);
+        assert_eq!(output["items"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_condition_or_items_fail_validation() {
+        let node = FilterNode::new();
+        assert!(node
+            .validate(&context(json!({ "items": vms(), "condition": "cpu >" })))
+            .await
+            .is_err());
+        assert!(node
+            .validate(&context(json!({ "items": { "cpu": 1 }, "condition": "cpu > 0.5" })))
+            .await
+            .is_err());
+    }
+}
@@ -1,22 +1,34 @@
 use async_trait::async_trait;
-use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_core::{EventBus, ExecutionEvent, GhostFlowError, Node, Result};
 use ghostflow_schema::{
     DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
 };
 use ghostflow_schema::node::ParameterType;
-use ghostllm_sys::{GhostLLM, GhostConfig, GhostLLMError};
+use crate::json_mode::{generate_json, json_mode_enabled, json_mode_parameters, json_output_port};
+use crate::prompt_template::{apply_prompt_templates, prompt_template_parameters, render_prompts};
+use ghostllm_sys::{GhostLLM, GhostConfig, GhostGenerationResponse, GhostLLMError};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use tracing::{error, info};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tracing::{error, info, warn};
 
 /// Configuration for the GhostLLM node
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GhostLLMNodeConfig {
     pub model_path: String,
     pub default_temperature: f32,
     pub default_max_tokens: u32,
+    /// A generation still running after this long fails the node
+    pub generation_timeout_ms: u64,
+    /// Generations allowed to run at once; further ones wait for a slot
+    pub max_concurrent_generations: usize,
+    /// Load the model with a one-token generation at startup
+    pub warmup: bool,
+    /// Repeat the warmup generation this often to keep the model resident
+    pub keep_alive_secs: Option<u64>,
 }
 
 impl Default for GhostLLMNodeConfig {
@@ -26,61 +38,236 @@ impl Default for GhostLLMNodeConfig {
                 .unwrap_or_else(|_| "/models/default.gguf".to_string()),
             default_temperature: 0.7,
             default_max_tokens: 2048,
+            generation_timeout_ms: 300_000,
+            max_concurrent_generations: 1,
+            warmup: false,
+            keep_alive_secs: None,
+        }
+    }
+}
+
+/// One generation as handed to a [`GhostLLMBackend`]
+#[derive(Debug, Clone)]
+pub struct GenerationRequest {
+    pub model_path: String,
+    pub prompt: String,
+    pub config: GhostConfig,
+}
+
+/// Receives each generated token while a streaming generation runs
+pub type TokenSink = Box<dyn FnMut(&str) + Send>;
+
+/// Runs generations for the GhostLLM node. Calls block until the text is
+/// complete, so the node runs them on the blocking thread pool. With
+/// `on_token`, tokens are passed to it as they are produced.
+pub trait GhostLLMBackend: Send + Sync {
+    fn generate(
+        &self,
+        request: &GenerationRequest,
+        on_token: Option<TokenSink>,
+    ) -> std::result::Result<GhostGenerationResponse, GhostLLMError>;
+}
+
+/// Backend calling into the GhostLLM library
+pub struct NativeGhostLLM;
+
+impl GhostLLMBackend for NativeGhostLLM {
+    fn generate(
+        &self,
+        request: &GenerationRequest,
+        on_token: Option<TokenSink>,
+    ) -> std::result::Result<GhostGenerationResponse, GhostLLMError> {
+        let llm = GhostLLM::with_config(&request.model_path, request.config.clone())?;
+        match on_token {
+            Some(on_token) => llm.generate_stream(&request.prompt, on_token),
+            None => llm.generate(&request.prompt),
         }
     }
 }
 
 /// GhostLLM node for GPU-accelerated AI inference
 pub struct GhostLLMNode {
-    llm: Arc<Mutex<Option<GhostLLM>>>,
+    backend: Arc<dyn GhostLLMBackend>,
+    generations: Arc<Semaphore>,
     config: GhostLLMNodeConfig,
+    events: EventBus,
 }
 
 impl GhostLLMNode {
     pub fn new() -> Self {
-        Self {
-            llm: Arc::new(Mutex::new(None)),
-            config: GhostLLMNodeConfig::default(),
-        }
+        Self::with_config(GhostLLMNodeConfig::default())
     }
 
     pub fn with_config(config: GhostLLMNodeConfig) -> Self {
         Self {
-            llm: Arc::new(Mutex::new(None)),
+            backend: Arc::new(NativeGhostLLM),
+            generations: Arc::new(Semaphore::new(config.max_concurrent_generations.max(1))),
             config,
+            events: EventBus::default(),
         }
     }
 
-    /// Initialize the GhostLLM instance if not already done
-    async fn ensure_initialized(&self, model_path: &str) -> Result<()> {
-        let mut llm_guard = self.llm.lock().await;
+    /// Publish streamed tokens on `events`
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Generate with `backend` instead of the GhostLLM library
+    pub fn with_backend(mut self, backend: Arc<dyn GhostLLMBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Run `request` on the blocking pool once a generation slot is free.
+    /// Waiting for the slot counts towards the timeout. A generation that
+    /// times out keeps its slot until the backend call actually returns, so
+    /// hung calls cannot pile up beyond the limit.
+    async fn run_generation(
+        &self,
+        request: GenerationRequest,
+        on_token: Option<TokenSink>,
+    ) -> std::result::Result<GhostGenerationResponse, GhostLLMError> {
+        let timeout = Duration::from_millis(self.config.generation_timeout_ms);
+        let backend = self.backend.clone();
+        let generations = self.generations.clone();
+
+        let generation = async move {
+            let permit = generations
+                .acquire_owned()
+                .await
+                .map_err(|_| GhostLLMError::GenerationFailed)?;
+            tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                backend.generate(&request, on_token)
+            })
+            .await
+            .map_err(|e| {
+                error!("GhostLLM generation task failed: {}", e);
+                GhostLLMError::GenerationFailed
+            })?
+        };
+
+        tokio::time::timeout(timeout, generation).await.unwrap_or_else(|_| {
+            warn!("GhostLLM generation timed out after {:?}", timeout);
+            Err(GhostLLMError::GenerationFailed)
+        })
+    }
+
+    /// Run a single generation with the parameters in `context.input`
+    async fn generate(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
         
-        if llm_guard.is_none() {
-            info!("Initializing GhostLLM with model: {}", model_path);
-            
-            match GhostLLM::new(model_path) {
-                Ok(llm) => {
-                    *llm_guard = Some(llm);
-                    info!("GhostLLM initialized successfully");
-                }
-                Err(GhostLLMError::InitializationFailed) => {
-                    error!("Failed to initialize GhostLLM - check model path and dependencies");
-                    return Err(GhostFlowError::NodeExecutionError {
-                        node_id: "ghostllm".to_string(),
-                        message: "Failed to initialize GhostLLM. Check model path and ensure Zig/GhostLLM dependencies are properly installed.".to_string(),
-                    });
-                }
-                Err(e) => {
-                    error!("GhostLLM initialization error: {}", e);
-                    return Err(GhostFlowError::NodeExecutionError {
-                        node_id: "ghostllm".to_string(),
-                        message: format!("GhostLLM initialization failed: {}", e),
-                    });
-                }
+        let prompt = params
+            .get("prompt")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: "Missing prompt parameter".to_string(),
+            })?;
+
+        let model_path = params
+            .get("model_path")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&self.config.model_path);
+
+        let temperature = params
+            .get("temperature")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(self.config.default_temperature as f64) as f32;
+
+        let max_tokens = params
+            .get("max_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(self.config.default_max_tokens as u64) as u32;
+
+        let enable_streaming = params
+            .get("streaming")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        info!(
+            "Generating text with GhostLLM - temperature: {}, max_tokens: {}, streaming: {}",
+            temperature, max_tokens, enable_streaming
+        );
+
+        let request = GenerationRequest {
+            model_path: model_path.to_string(),
+            prompt: prompt.to_string(),
+            config: GhostConfig {
+                max_tokens,
+                temperature,
+            },
+        };
+
+        // Streamed tokens go to the event bus as they arrive and are kept
+        // in case the backend's final response carries no text
+        let streamed = Arc::new(std::sync::Mutex::new((String::new(), 0u32)));
+        let on_token = enable_streaming.then(|| {
+            let streamed = streamed.clone();
+            let events = self.events.clone();
+            let (execution_id, node_id) = (context.execution_id, context.node_id.clone());
+            Box::new(move |token: &str| {
+                let mut streamed = streamed.lock().unwrap();
+                streamed.0.push_str(token);
+                streamed.1 += 1;
+                events.publish(ExecutionEvent::Token {
+                    execution_id,
+                    node_id: node_id.clone(),
+                    text: token.to_string(),
+                });
+            }) as TokenSink
+        });
+
+        let start_time = std::time::Instant::now();
+
+        let mut response = self.run_generation(request, on_token).await.map_err(|e| {
+            error!("GhostLLM generation failed: {}", e);
+            let message = match e {
+                GhostLLMError::InitializationFailed => "Failed to initialize GhostLLM. Check model path and ensure Zig/GhostLLM dependencies are properly installed.".to_string(),
+                GhostLLMError::InvalidConfiguration => format!("Failed to configure GhostLLM: {}", e),
+                e => format!("Text generation failed: {}", e),
+            };
+            GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message,
             }
+        })?;
+
+        let generation_time = start_time.elapsed();
+        let (streamed_text, streamed_tokens) = std::mem::take(&mut *streamed.lock().unwrap());
+        if response.text.is_empty() {
+            response.text = streamed_text;
         }
-        
-        Ok(())
+        if response.tokens_used == 0 {
+            response.tokens_used = streamed_tokens;
+        }
+
+        info!(
+            "GhostLLM generation completed in {:.2}s - {} tokens",
+            generation_time.as_secs_f64(),
+            response.tokens_used
+        );
+
+        Ok(serde_json::json!({
+            "text": response.text,
+            "tokens_used": response.tokens_used,
+            "prompt": prompt,
+            "metadata": {
+                "model_path": model_path,
+                "temperature": temperature,
+                "max_tokens": max_tokens,
+                "streaming_enabled": enable_streaming,
+                "streamed_tokens": streamed_tokens,
+                "generation_time_ms": generation_time.as_millis(),
+                "tokens_per_second": if generation_time.as_secs_f64() > 0.0 {
+                    response.tokens_used as f64 / generation_time.as_secs_f64()
+                } else {
+                    0.0
+                },
+                "engine": "GhostLLM"
+            }
+        }))
     }
 }
 
@@ -106,13 +293,16 @@ impl Node for GhostLLMNode {
                 data_type: DataType::String,
                 required: true,
             }],
-            outputs: vec![NodePort {
-                name: "response".to_string(),
-                display_name: "Response".to_string(),
-                description: Some("AI generated response".to_string()),
-                data_type: DataType::Object,
-                required: true,
-            }],
+            outputs: vec![
+                NodePort {
+                    name: "response".to_string(),
+                    display_name: "Response".to_string(),
+                    description: Some("AI generated response".to_string()),
+                    data_type: DataType::Object,
+                    required: true,
+                },
+                json_output_port(),
+            ],
             parameters: vec![
                 NodeParameter {
                     name: "model_path".to_string(),
@@ -156,7 +346,11 @@ impl Node for GhostLLMNode {
                     options: None,
                     validation: None,
                 },
-            ],
+            ]
+            .into_iter()
+            .chain(prompt_template_parameters())
+            .chain(json_mode_parameters())
+            .collect(),
             icon: Some("zap".to_string()), // Lightning bolt for speed
             color: Some("#10b981".to_string()), // Green for GhostLLM
         }
@@ -166,7 +360,9 @@ impl Node for GhostLLMNode {
         let params = &context.input;
         
         // Validate prompt
-        if params.get("prompt").and_then(|v| v.as_str()).map(|s| s.is_empty()).unwrap_or(true) {
+        let rendered = render_prompts(params)?;
+        let prompt = rendered.prompt.as_deref().or_else(|| params.get("prompt").and_then(|v| v.as_str()));
+        if prompt.map(|s| s.is_empty()).unwrap_or(true) {
             return Err(GhostFlowError::ValidationError {
                 message: "Prompt parameter is required and cannot be empty".to_string(),
             });
@@ -202,126 +398,222 @@ impl Node for GhostLLMNode {
         Ok(())
     }
 
-    async fn execute(&self, context: ExecutionContext) -> Result<serde_json::Value> {
-        let params = &context.input;
-        
-        let prompt = params
-            .get("prompt")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| GhostFlowError::NodeExecutionError {
-                node_id: context.node_id.clone(),
-                message: "Missing prompt parameter".to_string(),
-            })?;
+    async fn execute(&self, mut context: ExecutionContext) -> Result<serde_json::Value> {
+        // GhostLLM has no separate system prompt, so it goes in front
+        apply_prompt_templates(&mut context, None)?;
 
-        let model_path = params
-            .get("model_path")
-            .and_then(|v| v.as_str())
-            .unwrap_or(&self.config.model_path);
+        if json_mode_enabled(&context.input) {
+            return generate_json(context, "text", |ctx| self.generate(ctx)).await;
+        }
 
-        let temperature = params
-            .get("temperature")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(self.config.default_temperature as f64) as f32;
+        self.generate(context).await
+    }
 
-        let max_tokens = params
-            .get("max_tokens")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(self.config.default_max_tokens as u64) as u32;
+    fn supports_retry(&self) -> bool {
+        true
+    }
 
-        let enable_streaming = params
-            .get("streaming")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+    fn is_deterministic(&self) -> bool {
+        false // LLM outputs are non-deterministic
+    }
 
-        // Ensure GhostLLM is initialized
-        self.ensure_initialized(model_path).await?;
+    async fn warmup(&self) -> Result<()> {
+        if !self.config.warmup && self.config.keep_alive_secs.is_none() {
+            return Ok(());
+        }
 
-        info!(
-            "Generating text with GhostLLM - temperature: {}, max_tokens: {}, streaming: {}",
-            temperature, max_tokens, enable_streaming
-        );
+        let request = GenerationRequest {
+            model_path: self.config.model_path.clone(),
+            prompt: " ".to_string(),
+            config: GhostConfig {
+                max_tokens: 1,
+                temperature: self.config.default_temperature,
+            },
+        };
+        self.run_generation(request, None)
+            .await
+            .map(|_| ())
+            .map_err(|e| GhostFlowError::NodeExecutionError {
+                node_id: "ghostllm".to_string(),
+                message: format!("GhostLLM warmup failed: {}", e),
+            })
+    }
 
-        let llm_guard = self.llm.lock().await;
-        let _llm = llm_guard.as_ref().ok_or_else(|| GhostFlowError::NodeExecutionError {
-            node_id: context.node_id.clone(),
-            message: "GhostLLM not initialized".to_string(),
-        })?;
+    fn keep_alive_interval(&self) -> Option<Duration> {
+        self.config.keep_alive_secs.map(Duration::from_secs)
+    }
+}
 
-        // Update configuration
-        let config = GhostConfig {
-            max_tokens,
-            temperature,
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
-        // Create a new LLM instance with updated config for this request
-        // This is a workaround since we can't easily modify the existing instance
-        let request_llm = GhostLLM::with_config(model_path, config)
-            .map_err(|e| GhostFlowError::NodeExecutionError {
-                node_id: context.node_id.clone(),
-                message: format!("Failed to configure GhostLLM: {}", e),
-            })?;
+    /// Backend that takes `delay` per generation and records how many ran at once
+    struct SlowBackend {
+        delay: Duration,
+        running: AtomicUsize,
+        peak: AtomicUsize,
+    }
 
-        let start_time = std::time::Instant::now();
+    impl SlowBackend {
+        fn new(delay: Duration) -> Arc<Self> {
+            Arc::new(Self {
+                delay,
+                running: AtomicUsize::new(0),
+                peak: AtomicUsize::new(0),
+            })
+        }
+    }
 
-        let response = if enable_streaming {
-            // Use streaming generation
-            let mut tokens = Vec::new();
-            
-            request_llm.generate_stream(prompt, move |token| {
-                tokens.push(token.to_string());
-                // In a real implementation, you might want to send these tokens
-                // to a WebSocket or other streaming endpoint
-            }).map_err(|e| {
-                error!("GhostLLM generation failed: {}", e);
-                GhostFlowError::NodeExecutionError {
-                    node_id: context.node_id.clone(),
-                    message: format!("Text generation failed: {}", e),
-                }
-            })?
-        } else {
-            // Standard generation
-            request_llm.generate(prompt).map_err(|e| {
-                error!("GhostLLM generation failed: {}", e);
-                GhostFlowError::NodeExecutionError {
-                    node_id: context.node_id.clone(),
-                    message: format!("Text generation failed: {}", e),
-                }
-            })?
-        };
+    impl GhostLLMBackend for SlowBackend {
+        fn generate(
+            &self,
+            request: &GenerationRequest,
+            _on_token: Option<TokenSink>,
+        ) -> std::result::Result<GhostGenerationResponse, GhostLLMError> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            std::thread::sleep(self.delay);
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(GhostGenerationResponse {
+                text: format!("echo: {}", request.prompt),
+                tokens_used: 3,
+            })
+        }
+    }
 
-        let generation_time = start_time.elapsed();
+    /// Backend that streams `tokens` and reports no final text, like a
+    /// runtime that only returns usage at the end
+    struct StreamingBackend {
+        tokens: Vec<&'static str>,
+    }
 
-        info!(
-            "GhostLLM generation completed in {:.2}s - {} tokens",
-            generation_time.as_secs_f64(),
-            response.tokens_used
+    impl GhostLLMBackend for StreamingBackend {
+        fn generate(
+            &self,
+            _request: &GenerationRequest,
+            on_token: Option<TokenSink>,
+        ) -> std::result::Result<GhostGenerationResponse, GhostLLMError> {
+            let mut on_token = on_token.expect("streaming was requested");
+            for token in &self.tokens {
+                on_token(token);
+            }
+            Ok(GhostGenerationResponse {
+                text: String::new(),
+                tokens_used: 0,
+            })
+        }
+    }
+
+    fn node(backend: Arc<SlowBackend>, timeout_ms: u64, max_concurrent: usize) -> GhostLLMNode {
+        GhostLLMNode::with_config(GhostLLMNodeConfig {
+            generation_timeout_ms: timeout_ms,
+            max_concurrent_generations: max_concurrent,
+            ..GhostLLMNodeConfig::default()
+        })
+        .with_backend(backend)
+    }
+
+    #[tokio::test]
+    async fn test_slow_generation_times_out() {
+        let backend = SlowBackend::new(Duration::from_millis(400));
+        let node = node(backend.clone(), 50, 1);
+
+        let started = std::time::Instant::now();
+        let (first, second) = tokio::join!(
+            node.execute(context(serde_json::json!({"prompt": "hi"}))),
+            node.execute(context(serde_json::json!({"prompt": "there"}))),
         );
+        assert!(started.elapsed() < Duration::from_millis(300));
 
-        Ok(serde_json::json!({
-            "text": response.text,
-            "tokens_used": response.tokens_used,
-            "prompt": prompt,
-            "metadata": {
-                "model_path": model_path,
-                "temperature": temperature,
-                "max_tokens": max_tokens,
-                "streaming_enabled": enable_streaming,
-                "generation_time_ms": generation_time.as_millis(),
-                "tokens_per_second": if generation_time.as_secs_f64() > 0.0 {
-                    response.tokens_used as f64 / generation_time.as_secs_f64()
-                } else {
-                    0.0
-                },
-                "engine": "GhostLLM"
-            }
-        }))
+        for result in [first, second] {
+            let message = result.unwrap_err().to_string();
+            assert!(message.contains("Text generation failed"), "{}", message);
+        }
+        // The timed-out call keeps its slot, so the second never started
+        assert_eq!(backend.peak.load(Ordering::SeqCst), 1);
     }
 
-    fn supports_retry(&self) -> bool {
-        true
+    #[tokio::test]
+    async fn test_concurrent_generations_are_limited() {
+        let backend = SlowBackend::new(Duration::from_millis(30));
+        let node = Arc::new(node(backend.clone(), 5_000, 2));
+
+        let handles: Vec<_> = (0..5)
+            .map(|i| {
+                let node = node.clone();
+                tokio::spawn(async move { node.execute(context(serde_json::json!({"prompt": format!("p{}", i)}))).await })
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.await.unwrap().unwrap()["text"], format!("echo: p{}", i));
+        }
+        assert_eq!(backend.peak.load(Ordering::SeqCst), 2);
     }
 
-    fn is_deterministic(&self) -> bool {
-        false // LLM outputs are non-deterministic
+    #[tokio::test]
+    async fn test_warmup_runs_a_tiny_generation_when_configured() {
+        let backend = SlowBackend::new(Duration::ZERO);
+        let idle = node(backend.clone(), 1_000, 1);
+        idle.warmup().await.unwrap();
+        assert_eq!(backend.peak.load(Ordering::SeqCst), 0);
+        assert_eq!(idle.keep_alive_interval(), None);
+
+        let kept_warm = GhostLLMNode::with_config(GhostLLMNodeConfig {
+            keep_alive_secs: Some(600),
+            ..GhostLLMNodeConfig::default()
+        })
+        .with_backend(backend.clone());
+        kept_warm.warmup().await.unwrap();
+        assert_eq!(backend.peak.load(Ordering::SeqCst), 1);
+        assert_eq!(kept_warm.keep_alive_interval(), Some(Duration::from_secs(600)));
+    }
+
+    #[tokio::test]
+    async fn test_prompt_template_is_rendered_before_generation() {
+        let node = node(SlowBackend::new(Duration::ZERO), 1_000, 1);
+        let ctx = context(serde_json::json!({
+            "alert": {"host": "db-1", "labels": {"severity": "critical"}},
+            "system_prompt": "You are an SRE assistant.",
+            "prompt_template": "Explain the {{input.alert.labels.severity}} alert on {{input.alert.host}}",
+        }));
+
+        node.validate(&ctx).await.unwrap();
+        let result = node.execute(ctx).await.unwrap();
+        assert_eq!(
+            result["text"],
+            "echo: You are an SRE assistant.\n\nExplain the critical alert on db-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_streamed_tokens_are_published_before_the_output() {
+        let bus = EventBus::default();
+        let node = GhostLLMNode::new()
+            .with_backend(Arc::new(StreamingBackend {
+                tokens: vec!["Ghost", "Flow", " rocks"],
+            }))
+            .with_event_bus(bus.clone());
+        let ctx = context(serde_json::json!({"prompt": "hi", "streaming": true}));
+        let execution_id = ctx.execution_id;
+        let mut events = bus.subscribe();
+
+        let result = node.execute(ctx).await.unwrap();
+
+        let mut tokens = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let ExecutionEvent::Token { execution_id: id, node_id, text } = event {
+                if id == execution_id {
+                    assert_eq!(node_id, "node");
+                    tokens.push(text);
+                }
+            }
+        }
+        assert_eq!(tokens, vec!["Ghost", "Flow", " rocks"]);
+        assert_eq!(result["text"], "GhostFlow rocks");
+        assert_eq!(result["tokens_used"], 3);
+        assert_eq!(result["metadata"]["streamed_tokens"], 3);
     }
 }
\ No newline at end of file
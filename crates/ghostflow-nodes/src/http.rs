@@ -1,14 +1,18 @@
 use async_trait::async_trait;
 use ghostflow_core::{GhostFlowError, Node, Result};
 use ghostflow_schema::{
-    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+    BinaryData, DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
     ParameterValidation,
 };
 use ghostflow_schema::node::ParameterType;
-use reqwest::{Client, Method};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
 use serde_json::Value;
 use std::collections::HashMap;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+use crate::http_util::shared_client;
+use crate::pagination::{NextPage, Pagination};
 
 pub struct HttpRequestNode {
     client: Client,
@@ -17,9 +21,334 @@ pub struct HttpRequestNode {
 impl HttpRequestNode {
     pub fn new() -> Self {
         Self {
-            client: Client::new(),
+            client: shared_client().clone(),
+        }
+    }
+}
+
+/// Method and retry settings shared by every page of a request
+struct RequestSettings {
+    method: Method,
+    timeout: u64,
+    max_retries: u32,
+    retry_delay_ms: u64,
+    /// Header name and value carrying the node's idempotency key
+    idempotency: Option<(String, String)>,
+}
+
+struct FetchedResponse {
+    status: StatusCode,
+    headers: HashMap<String, String>,
+    body: Value,
+    attempts: u32,
+}
+
+impl HttpRequestNode {
+    /// Builds a fresh request from the node parameters. Called once per
+    /// attempt because multipart bodies cannot be cloned for a retry.
+    fn build_request(&self, method: &Method, url: &str, params: &Value) -> Result<RequestBuilder> {
+        let mut request = self.client.request(method.clone(), url);
+
+        if let Some(headers_obj) = params.get("headers").and_then(|v| v.as_object()) {
+            for (key, value) in headers_obj {
+                if let Some(value_str) = value.as_str() {
+                    request = request.header(key, value_str);
+                }
+            }
+        }
+
+        if let Some(query_obj) = params.get("query").and_then(|v| v.as_object()) {
+            let pairs: Vec<(String, String)> = query_obj
+                .iter()
+                .map(|(key, value)| (key.clone(), value_to_string(value)))
+                .collect();
+            request = request.query(&pairs);
+        }
+
+        match params.get("auth_type").and_then(|v| v.as_str()).unwrap_or("none") {
+            "bearer" => {
+                if let Some(token) = params.get("token").and_then(|v| v.as_str()) {
+                    request = request.bearer_auth(token);
+                }
+            }
+            "basic" => {
+                let username = params.get("username").and_then(|v| v.as_str()).unwrap_or("");
+                let password = params.get("password").and_then(|v| v.as_str());
+                request = request.basic_auth(username, password);
+            }
+            _ => {}
+        }
+
+        let body = match params.get("body") {
+            Some(body) if !body.is_null() => body,
+            _ => return Ok(request),
+        };
+
+        if matches!(*method, Method::GET | Method::HEAD) {
+            return Ok(request);
+        }
+
+        let request = match params.get("body_type").and_then(|v| v.as_str()).unwrap_or("json") {
+            "form" => {
+                let fields: Vec<(String, String)> = body
+                    .as_object()
+                    .map(|obj| obj.iter().map(|(k, v)| (k.clone(), value_to_string(v))).collect())
+                    .unwrap_or_default();
+                request.form(&fields)
+            }
+            "multipart" => request.multipart(build_multipart(body)?),
+            "raw" => request.body(value_to_string(body)),
+            _ => request.json(body),
+        };
+
+        Ok(request)
+    }
+}
+
+/// Each top-level field becomes a part. Binary values and objects with
+/// `filename` and `content` are sent as file parts; everything else is sent
+/// as a text field.
+fn build_multipart(body: &Value) -> Result<Form> {
+    let fields = body.as_object().ok_or_else(|| GhostFlowError::ValidationError {
+        message: "Multipart body must be a JSON object".to_string(),
+    })?;
+
+    let mut form = Form::new();
+    for (name, value) in fields {
+        if let Some(binary) = BinaryData::from_value(value) {
+            let filename = binary.filename.clone().unwrap_or_else(|| name.clone());
+            let mut part = Part::bytes(binary.data).file_name(filename);
+            if let Some(content_type) = &binary.content_type {
+                part = part.mime_str(content_type).map_err(|e| GhostFlowError::ValidationError {
+                    message: format!("Invalid content type for '{}': {}", name, e),
+                })?;
+            }
+            form = form.part(name.clone(), part);
+            continue;
+        }
+
+        let file = value
+            .as_object()
+            .and_then(|obj| Some((obj.get("filename")?.as_str()?, obj.get("content")?, obj)));
+
+        form = match file {
+            Some((filename, content, obj)) => {
+                let mut part = Part::bytes(value_to_string(content).into_bytes()).file_name(filename.to_string());
+                if let Some(content_type) = obj.get("content_type").and_then(|v| v.as_str()) {
+                    part = part.mime_str(content_type).map_err(|e| GhostFlowError::ValidationError {
+                        message: format!("Invalid content type for '{}': {}", name, e),
+                    })?;
+                }
+                form.part(name.clone(), part)
+            }
+            None => form.text(name.clone(), value_to_string(value)),
+        };
+    }
+
+    Ok(form)
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Methods a server applies the same way however often they arrive, so a
+/// retry after a timeout cannot repeat a side effect.
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE | Method::TRACE
+    )
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Honors a `Retry-After` header given in seconds, capped at one minute.
+fn retry_after(response: &reqwest::Response) -> Option<std::time::Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse::<u64>()
+        .ok()
+        .map(|secs| std::time::Duration::from_secs(secs.min(60)))
+}
+
+impl HttpRequestNode {
+    /// Send one request, retrying 5xx, 429 and connection failures.
+    async fn fetch(&self, settings: &RequestSettings, url: &str, params: &Value) -> Result<FetchedResponse> {
+        let RequestSettings { method, timeout, max_retries, retry_delay_ms, idempotency } = settings;
+        info!("Making {} request to {}", method, url);
+
+        let mut attempt = 0;
+        let response = loop {
+            let mut request = self
+                .build_request(method, url, params)?
+                .headers(crate::http_util::trace_context_headers())
+                .timeout(std::time::Duration::from_secs(*timeout));
+            if let Some((name, key)) = idempotency {
+                request = request.header(name.as_str(), key.as_str());
+            }
+
+            let delay = std::time::Duration::from_millis(retry_delay_ms.saturating_mul(1 << attempt.min(16)));
+
+            match request.send().await {
+                Ok(response) if is_retryable_status(response.status()) && attempt < *max_retries => {
+                    let wait = retry_after(&response).unwrap_or(delay);
+                    warn!(
+                        "HTTP {} from {} (attempt {}), retrying in {:?}",
+                        response.status(), url, attempt + 1, wait
+                    );
+                    tokio::time::sleep(wait).await;
+                }
+                Ok(response) => break response,
+                Err(e) if (e.is_connect() || e.is_timeout()) && attempt < *max_retries => {
+                    warn!("HTTP request to {} failed (attempt {}): {}, retrying", url, attempt + 1, e);
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    error!("HTTP request failed: {}", e);
+                    return Err(GhostFlowError::NetworkError(e.to_string()));
+                }
+            }
+
+            attempt += 1;
+        };
+
+        let status = response.status();
+        let headers: HashMap<String, String> = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    value.to_str().unwrap_or("").to_string(),
+                )
+            })
+            .collect();
+
+        let is_json = headers
+            .get("content-type")
+            .map(|ct| ct.contains("json"))
+            .unwrap_or(false);
+
+        // Get response bytes first, then parse according to the content type
+        let body_bytes = response.bytes().await.map_err(|e| {
+            error!("Failed to read response body: {}", e);
+            GhostFlowError::NetworkError(e.to_string())
+        })?;
+
+        let body = if is_json {
+            // Fall back to text if the server mislabels its payload
+            serde_json::from_slice::<serde_json::Value>(&body_bytes)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body_bytes).to_string()))
+        } else {
+            match String::from_utf8(body_bytes.to_vec()) {
+                Ok(text) => Value::String(text),
+                Err(e) => {
+                    let mut binary = BinaryData::new(e.into_bytes());
+                    if let Some(content_type) = headers.get("content-type") {
+                        binary = binary.with_content_type(content_type.clone());
+                    }
+                    binary.to_value()
+                }
+            }
+        };
+
+        Ok(FetchedResponse {
+            status,
+            headers,
+            body,
+            attempts: attempt + 1,
+        })
+    }
+
+    /// Follow `pagination` from `url`, collecting each page's records until
+    /// the last page or `max_pages`/`max_items` is reached.
+    async fn execute_paginated(
+        &self,
+        settings: &RequestSettings,
+        url: &str,
+        params: &Value,
+        pagination: &Pagination,
+    ) -> Result<Value> {
+        let mut items = Vec::new();
+        let mut pages = 0;
+        let mut attempts = 0;
+        let mut next = pagination.first_page();
+        let mut last = None;
+
+        while pages < pagination.max_pages && items.len() < pagination.max_items {
+            let (page_url, page_params) = match &next {
+                NextPage::Query(query) => (url.to_string(), with_query(params, query)),
+                // Next-page URLs already carry every query parameter
+                NextPage::Url(next_url) => (next_url.clone(), without_query(params)),
+                NextPage::Done => break,
+            };
+
+            let response = self.fetch(settings, &page_url, &page_params).await?;
+            pages += 1;
+            attempts += response.attempts;
+            if !response.status.is_success() {
+                return Err(GhostFlowError::NetworkError(format!(
+                    "Page {} of {} returned HTTP {}",
+                    pages,
+                    url,
+                    response.status.as_u16()
+                )));
+            }
+
+            let page_items = pagination.items(&response.body, pages)?;
+            next = pagination.next_page(&next, pages, &response.headers, &response.body, page_items.len());
+            items.extend(page_items);
+            last = Some(response);
         }
+
+        let has_more = next != NextPage::Done || items.len() > pagination.max_items;
+        items.truncate(pagination.max_items);
+        info!("Fetched {} item(s) from {} page(s) of {}", items.len(), pages, url);
+
+        let (status, headers) = last.map(|r| (r.status, r.headers)).unwrap_or_default();
+        Ok(serde_json::json!({
+            "status": status.as_u16(),
+            "statusText": status.canonical_reason().unwrap_or("Unknown"),
+            "headers": headers,
+            "items": items,
+            "item_count": items.len(),
+            "pages": pages,
+            "has_more": has_more,
+            "attempts": attempts
+        }))
+    }
+}
+
+/// `params` with the given query parameters set, replacing any of the same name.
+fn with_query(params: &Value, query: &[(String, String)]) -> Value {
+    let mut params = params.clone();
+    if query.is_empty() {
+        return params;
+    }
+    if !params.get("query").is_some_and(|q| q.is_object()) {
+        params["query"] = Value::Object(Default::default());
+    }
+    for (name, value) in query {
+        params["query"][name] = Value::String(value.clone());
     }
+    params
+}
+
+fn without_query(params: &Value) -> Value {
+    let mut params = params.clone();
+    if let Some(fields) = params.as_object_mut() {
+        fields.remove("query");
+    }
+    params
 }
 
 impl Default for HttpRequestNode {
@@ -44,13 +373,29 @@ impl Node for HttpRequestNode {
                 data_type: DataType::Any,
                 required: false,
             }],
-            outputs: vec![NodePort {
-                name: "response".to_string(),
-                display_name: "Response".to_string(),
-                description: Some("HTTP response data".to_string()),
-                data_type: DataType::Object,
-                required: true,
-            }],
+            outputs: vec![
+                NodePort {
+                    name: "response".to_string(),
+                    display_name: "Response".to_string(),
+                    description: Some("HTTP response data".to_string()),
+                    data_type: DataType::Object,
+                    required: true,
+                },
+                NodePort {
+                    name: "status".to_string(),
+                    display_name: "Status".to_string(),
+                    description: Some("HTTP status code".to_string()),
+                    data_type: DataType::Number,
+                    required: false,
+                },
+                NodePort {
+                    name: "headers".to_string(),
+                    display_name: "Headers".to_string(),
+                    description: Some("Response headers".to_string()),
+                    data_type: DataType::Object,
+                    required: false,
+                },
+            ],
             parameters: vec![
                 NodeParameter {
                     name: "method".to_string(),
@@ -65,6 +410,8 @@ impl Node for HttpRequestNode {
                         serde_json::from_str(r#"{"value": "PUT", "label": "PUT"}"#).unwrap(),
                         serde_json::from_str(r#"{"value": "PATCH", "label": "PATCH"}"#).unwrap(),
                         serde_json::from_str(r#"{"value": "DELETE", "label": "DELETE"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "HEAD", "label": "HEAD"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "OPTIONS", "label": "OPTIONS"}"#).unwrap(),
                     ]),
                     validation: None,
                 },
@@ -94,6 +441,28 @@ impl Node for HttpRequestNode {
                     options: None,
                     validation: None,
                 },
+                NodeParameter {
+                    name: "idempotency_header".to_string(),
+                    display_name: "Idempotency Header".to_string(),
+                    description: Some(
+                        "Send a key that stays the same across retries in this header, e.g. Idempotency-Key".to_string(),
+                    ),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "query".to_string(),
+                    display_name: "Query Parameters".to_string(),
+                    description: Some("Query string parameters as JSON object".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
                 NodeParameter {
                     name: "body".to_string(),
                     display_name: "Request Body".to_string(),
@@ -104,6 +473,113 @@ impl Node for HttpRequestNode {
                     options: None,
                     validation: None,
                 },
+                NodeParameter {
+                    name: "body_type".to_string(),
+                    display_name: "Body Type".to_string(),
+                    description: Some("How the request body is encoded".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("json".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "json", "label": "JSON"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "form", "label": "Form URL-Encoded"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "multipart", "label": "Multipart Form Data"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "raw", "label": "Raw Text"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "auth_type".to_string(),
+                    display_name: "Authentication".to_string(),
+                    description: Some("Authentication scheme to apply".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("none".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "none", "label": "None"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "bearer", "label": "Bearer Token"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "basic", "label": "Basic Auth"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "token".to_string(),
+                    display_name: "Bearer Token".to_string(),
+                    description: Some("Token for bearer authentication".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "username".to_string(),
+                    display_name: "Username".to_string(),
+                    description: Some("Username for basic authentication".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "password".to_string(),
+                    display_name: "Password".to_string(),
+                    description: Some("Password for basic authentication".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_retries".to_string(),
+                    display_name: "Max Retries".to_string(),
+                    description: Some("Retries on 5xx, 429 and connection errors; POST and PATCH only with Retry Non-Idempotent".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(3))),
+                    required: false,
+                    options: None,
+                    validation: Some(ParameterValidation {
+                        min_length: None,
+                        max_length: None,
+                        min_value: Some(0.0),
+                        max_value: Some(10.0),
+                        pattern: None,
+                    }),
+                },
+                NodeParameter {
+                    name: "retry_non_idempotent".to_string(),
+                    display_name: "Retry Non-Idempotent".to_string(),
+                    description: Some("Also retry POST and PATCH, which can apply the request twice unless the server honours the idempotency header".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "retry_delay_ms".to_string(),
+                    display_name: "Retry Delay (ms)".to_string(),
+                    description: Some("Initial retry delay, doubled after each attempt".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(500))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "paginate".to_string(),
+                    display_name: "Pagination".to_string(),
+                    description: Some(
+                        "Follow pages and return their records as `items`: {\"strategy\": \"link_header\" | \"cursor\" | \"offset\" | \"page\", \"items_path\", \"cursor_path\", \"page_size\", \"max_pages\", \"max_items\"}".to_string(),
+                    ),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
                 NodeParameter {
                     name: "timeout".to_string(),
                     display_name: "Timeout (seconds)".to_string(),
@@ -173,6 +649,39 @@ impl Node for HttpRequestNode {
             }
         }
 
+        match params.get("auth_type").and_then(|v| v.as_str()).unwrap_or("none") {
+            "none" => {}
+            "bearer" => {
+                if params.get("token").and_then(|v| v.as_str()).is_none() {
+                    return Err(GhostFlowError::ValidationError {
+                        message: "Bearer authentication requires a token".to_string(),
+                    });
+                }
+            }
+            "basic" => {
+                if params.get("username").and_then(|v| v.as_str()).is_none() {
+                    return Err(GhostFlowError::ValidationError {
+                        message: "Basic authentication requires a username".to_string(),
+                    });
+                }
+            }
+            other => {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Unsupported authentication type: {}", other),
+                });
+            }
+        }
+
+        Pagination::from_params(params.get("paginate"))?;
+
+        if let Some(body_type) = params.get("body_type").and_then(|v| v.as_str()) {
+            if !matches!(body_type, "json" | "form" | "multipart" | "raw") {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Unsupported body type: {}", body_type),
+                });
+            }
+        }
+
         Ok(())
     }
 
@@ -204,76 +713,48 @@ impl Node for HttpRequestNode {
             .and_then(|v| v.as_u64())
             .unwrap_or(30);
 
-        info!("Making {} request to {}", method, url);
+        let retry_non_idempotent = params
+            .get("retry_non_idempotent")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let max_retries = if is_idempotent(&method) || retry_non_idempotent {
+            params.get("max_retries").and_then(|v| v.as_u64()).unwrap_or(3).min(10) as u32
+        } else {
+            0
+        };
 
-        // Build request
-        let mut request = self.client.request(method.clone(), url);
+        let retry_delay_ms = params
+            .get("retry_delay_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(500);
 
-        // Add timeout
-        request = request.timeout(std::time::Duration::from_secs(timeout));
+        // Same key on every retry, so servers that honour it apply the request once
+        let idempotency = params
+            .get("idempotency_header")
+            .and_then(|v| v.as_str())
+            .filter(|name| !name.is_empty())
+            .map(|name| (name.to_string(), context.idempotency_key()));
 
-        // Add headers
-        if let Some(headers_value) = params.get("headers") {
-            if let Some(headers_obj) = headers_value.as_object() {
-                for (key, value) in headers_obj {
-                    if let Some(value_str) = value.as_str() {
-                        request = request.header(key, value_str);
-                    }
-                }
-            }
-        }
+        let settings = RequestSettings {
+            method,
+            timeout,
+            max_retries,
+            retry_delay_ms,
+            idempotency,
+        };
 
-        // Add body for applicable methods
-        if matches!(method, Method::POST | Method::PUT | Method::PATCH) {
-            if let Some(body_value) = params.get("body") {
-                request = request.json(body_value);
-            }
+        if let Some(pagination) = Pagination::from_params(params.get("paginate"))? {
+            return self.execute_paginated(&settings, url, params, &pagination).await;
         }
 
-        // Execute request
-        let response = request.send().await.map_err(|e| {
-            error!("HTTP request failed: {}", e);
-            GhostFlowError::NetworkError(e.to_string())
-        })?;
-
-        let status = response.status();
-        let headers: HashMap<String, String> = response
-            .headers()
-            .iter()
-            .map(|(name, value)| {
-                (
-                    name.to_string(),
-                    value.to_str().unwrap_or("").to_string(),
-                )
-            })
-            .collect();
-
-        // Get response bytes first, then try to parse
-        let body_bytes = response.bytes().await.map_err(|e| {
-            error!("Failed to read response body: {}", e);
-            GhostFlowError::NetworkError(e.to_string())
-        })?;
-
-        // Try to parse response body as JSON, fallback to text
-        let body = match serde_json::from_slice::<serde_json::Value>(&body_bytes) {
-            Ok(json) => json,
-            Err(_) => {
-                // Fallback to text
-                match String::from_utf8(body_bytes.to_vec()) {
-                    Ok(text) => Value::String(text),
-                    Err(_) => {
-                        // If it's not valid UTF-8, return error info
-                        Value::String("<binary data>".to_string())
-                    }
-                }
-            }
-        };
+        let response = self.fetch(&settings, url, params).await?;
 
         let result = serde_json::json!({
-            "status": status.as_u16(),
-            "statusText": status.canonical_reason().unwrap_or("Unknown"),
-            "headers": headers,
-            "body": body
+            "status": response.status.as_u16(),
+            "statusText": response.status.canonical_reason().unwrap_or("Unknown"),
+            "headers": response.headers,
+            "body": response.body,
+            "attempts": response.attempts
         });
 
         Ok(result)
@@ -286,4 +767,326 @@ impl Node for HttpRequestNode {
     fn is_deterministic(&self) -> bool {
         false // HTTP requests can have different responses
     }
-}
\ No newline at end of file
+
+    /// Only reads may be cached or re-run on replay
+    fn is_deterministic_for(&self, input: &Value) -> bool {
+        let method = input.get("method").and_then(|v| v.as_str()).unwrap_or("GET");
+        method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_retries_server_error_then_succeeds() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "ok": true })))
+            .mount(&server)
+            .await;
+
+        let result = HttpRequestNode::new()
+            .execute(context(serde_json::json!({
+                "url": format!("{}/flaky", server.uri()),
+                "method": "GET",
+                "retry_delay_ms": 10,
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["status"], 200);
+        assert_eq!(result["attempts"], 2);
+        assert_eq!(result["body"]["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn test_post_is_not_retried_unless_opted_in() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/orders"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let result = HttpRequestNode::new()
+            .execute(context(serde_json::json!({
+                "url": format!("{}/orders", server.uri()),
+                "method": "POST",
+                "body": { "sku": "A-1" },
+                "retry_delay_ms": 10,
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["status"], 503);
+        assert_eq!(result["attempts"], 1);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_only_reads_are_deterministic() {
+        let node = HttpRequestNode::new();
+        assert!(node.is_deterministic_for(&serde_json::json!({ "url": "https://example.com" })));
+        assert!(node.is_deterministic_for(&serde_json::json!({ "method": "HEAD" })));
+        for method in ["POST", "PUT", "PATCH", "DELETE"] {
+            assert!(!node.is_deterministic_for(&serde_json::json!({ "method": method })), "{}", method);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_is_the_same_across_node_retries() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/charges"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/charges"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&server)
+            .await;
+
+        let node = HttpRequestNode::new();
+        let first = context(serde_json::json!({
+            "url": format!("{}/charges", server.uri()),
+            "method": "POST",
+            "body": { "amount": 500 },
+            "idempotency_header": "Idempotency-Key",
+            "retry_non_idempotent": true,
+            "retry_delay_ms": 10,
+        }));
+        // The engine retrying the whole node
+        let retry = ExecutionContext {
+            attempt: 2,
+            ..first.clone()
+        };
+        node.execute(first.clone()).await.unwrap();
+        node.execute(retry).await.unwrap();
+
+        let keys: Vec<String> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| request.headers["idempotency-key"].to_str().unwrap().to_string())
+            .collect();
+        assert_eq!(keys.len(), 3);
+        assert!(keys.iter().all(|key| *key == first.idempotency_key()), "{:?}", keys);
+
+        // Another execution of the same node gets its own key
+        let other = context(first.input.clone());
+        assert_ne!(other.idempotency_key(), first.idempotency_key());
+    }
+
+    #[tokio::test]
+    async fn test_parses_json_and_text_bodies_by_content_type() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/json"))
+            .and(query_param("page", "2"))
+            .and(header("authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "items": [1, 2] })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/text"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"looks": "like json"}"#))
+            .mount(&server)
+            .await;
+
+        let node = HttpRequestNode::new();
+
+        let json = node
+            .execute(context(serde_json::json!({
+                "url": format!("{}/json", server.uri()),
+                "query": { "page": 2 },
+                "auth_type": "bearer",
+                "token": "secret",
+            })))
+            .await
+            .unwrap();
+        assert_eq!(json["body"]["items"], serde_json::json!([1, 2]));
+
+        let text = node
+            .execute(context(serde_json::json!({
+                "url": format!("{}/text", server.uri()),
+            })))
+            .await
+            .unwrap();
+        assert_eq!(text["body"], r#"{"looks": "like json"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_binary_body_is_returned_as_binary_value() {
+        let server = MockServer::start().await;
+        let bytes = vec![0x89u8, b'P', b'N', b'G', 0xff, 0x00];
+
+        Mock::given(method("GET"))
+            .and(path("/logo.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(bytes.clone(), "image/png"))
+            .mount(&server)
+            .await;
+
+        let result = HttpRequestNode::new()
+            .execute(context(serde_json::json!({
+                "url": format!("{}/logo.png", server.uri()),
+            })))
+            .await
+            .unwrap();
+
+        let binary = BinaryData::from_value(&result["body"]).unwrap();
+        assert_eq!(binary.data, bytes);
+        assert_eq!(binary.content_type.as_deref(), Some("image/png"));
+    }
+
+    #[tokio::test]
+    async fn test_paginates_by_link_header() {
+        let server = MockServer::start().await;
+        let page = |n: u32, next: Option<u32>| {
+            let mut response = ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "id": n * 10 + 1 },
+                { "id": n * 10 + 2 },
+            ]));
+            if let Some(next) = next {
+                response = response.insert_header(
+                    "Link",
+                    format!(r#"<{}/alerts?page={}&per_page=2>; rel="next", <{}/alerts?page=3>; rel="last""#, server.uri(), next, server.uri()),
+                );
+            }
+            response
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/alerts"))
+            .and(query_param("page", "2"))
+            .respond_with(page(2, Some(3)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/alerts"))
+            .and(query_param("page", "3"))
+            .respond_with(page(3, None))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/alerts"))
+            .and(query_param("per_page", "2"))
+            .respond_with(page(1, Some(2)))
+            .with_priority(10)
+            .mount(&server)
+            .await;
+
+        let result = HttpRequestNode::new()
+            .execute(context(serde_json::json!({
+                "url": format!("{}/alerts", server.uri()),
+                "query": { "per_page": 2 },
+                "paginate": "link_header",
+            })))
+            .await
+            .unwrap();
+
+        let ids: Vec<u64> = result["items"].as_array().unwrap().iter().map(|i| i["id"].as_u64().unwrap()).collect();
+        assert_eq!(ids, vec![11, 12, 21, 22, 31, 32]);
+        assert_eq!(result["pages"], 3);
+        assert_eq!(result["has_more"], false);
+
+        // max_items stops early and reports that more data exists
+        let limited = HttpRequestNode::new()
+            .execute(context(serde_json::json!({
+                "url": format!("{}/alerts", server.uri()),
+                "query": { "per_page": 2 },
+                "paginate": { "strategy": "link_header", "max_items": 3 },
+            })))
+            .await
+            .unwrap();
+        assert_eq!(limited["item_count"], 3);
+        assert_eq!(limited["pages"], 2);
+        assert_eq!(limited["has_more"], true);
+    }
+
+    #[tokio::test]
+    async fn test_paginates_by_body_cursor() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .and(query_param("after", "c1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "b" }, { "id": "c" }],
+                "meta": { "next_cursor": "c2" },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .and(query_param("after", "c2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "d" }],
+                "meta": { "next_cursor": null },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "a" }],
+                "meta": { "next_cursor": "c1" },
+            })))
+            .with_priority(10)
+            .mount(&server)
+            .await;
+
+        let result = HttpRequestNode::new()
+            .execute(context(serde_json::json!({
+                "url": format!("{}/events", server.uri()),
+                "paginate": {
+                    "strategy": "cursor",
+                    "items_path": "data",
+                    "cursor_path": "meta.next_cursor",
+                    "cursor_param": "after",
+                },
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["items"], serde_json::json!([{ "id": "a" }, { "id": "b" }, { "id": "c" }, { "id": "d" }]));
+        assert_eq!(result["pages"], 3);
+        assert_eq!(result["status"], 200);
+
+        // max_pages caps the walk
+        let capped = HttpRequestNode::new()
+            .execute(context(serde_json::json!({
+                "url": format!("{}/events", server.uri()),
+                "paginate": {
+                    "strategy": "cursor",
+                    "items_path": "data",
+                    "cursor_path": "meta.next_cursor",
+                    "cursor_param": "after",
+                    "max_pages": 2,
+                },
+            })))
+            .await
+            .unwrap();
+        assert_eq!(capped["item_count"], 3);
+        assert_eq!(capped["has_more"], true);
+    }
+}
@@ -0,0 +1,366 @@
+use ghostflow_core::{CircuitBreakerRegistry, GhostFlowError, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::NodeParameter;
+use opentelemetry::propagation::Injector;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tracing::warn;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Retry, timeout and circuit-breaker settings for [`request_with_policy`].
+#[derive(Debug, Clone)]
+pub struct RequestPolicy {
+    /// Retries after the first attempt; 0 disables retrying
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub backoff_multiplier: f64,
+    pub max_backoff: Duration,
+    /// Per-attempt timeout
+    pub timeout: Duration,
+    /// Consecutive failures against one host before its breaker opens
+    pub failure_threshold: u32,
+    /// How long an open breaker rejects calls before letting a trial through
+    pub reset_timeout: Duration,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            backoff_multiplier: 2.0,
+            max_backoff: Duration::from_secs(10),
+            timeout: Duration::from_secs(30),
+            failure_threshold: 5,
+            reset_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// W3C `traceparent`/`tracestate` headers for the current span, so a
+/// service we call can join the execution's trace. Empty unless
+/// OpenTelemetry export is enabled.
+pub fn trace_context_headers() -> HeaderMap {
+    struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+    impl Injector for HeaderInjector<'_> {
+        fn set(&mut self, key: &str, value: String) {
+            if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(key.as_bytes()), HeaderValue::from_str(&value)) {
+                self.0.insert(name, value);
+            }
+        }
+    }
+
+    let mut headers = HeaderMap::new();
+    let context = tracing::Span::current().context();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(&mut headers))
+    });
+    headers
+}
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const POOL_MAX_IDLE_PER_HOST: usize = 16;
+
+/// Connection pool and timeout settings of every client built here. Request
+/// timeouts are left to each request, e.g. via [`RequestPolicy::timeout`].
+fn client_builder() -> reqwest::ClientBuilder {
+    Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .tcp_keepalive(Duration::from_secs(60))
+}
+
+/// Process-wide client for nodes without special TLS needs. Sharing it keeps
+/// connections alive between executions instead of reconnecting every time.
+pub fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| client_builder().build().expect("failed to build the shared HTTP client"))
+}
+
+/// TLS settings from a node's `tls` parameter, for self-hosted services.
+/// Certificates are always verified unless `insecure_skip_verify` is set;
+/// `ca_cert` adds a PEM CA certificate to trust alongside the system roots.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsConfig {
+    pub ca_cert: Option<String>,
+    pub insecure_skip_verify: bool,
+}
+
+impl TlsConfig {
+    /// The `tls` object of a node's input; the defaults when it is absent.
+    pub fn from_params(params: &Value) -> Result<Self> {
+        match params.get("tls") {
+            None | Some(Value::Null) => Ok(Self::default()),
+            Some(tls) => serde_json::from_value(tls.clone()).map_err(|e| GhostFlowError::ValidationError {
+                message: format!("Invalid tls settings: {}", e),
+            }),
+        }
+    }
+
+    /// A client for these settings: the shared client for the defaults,
+    /// otherwise one cached per distinct configuration.
+    pub fn client(&self) -> Result<Client> {
+        static CLIENTS: OnceLock<Mutex<HashMap<TlsConfig, Client>>> = OnceLock::new();
+
+        if *self == Self::default() {
+            return Ok(shared_client().clone());
+        }
+        let mut clients = CLIENTS.get_or_init(Default::default).lock().unwrap();
+        if let Some(client) = clients.get(self) {
+            return Ok(client.clone());
+        }
+        let client = self.build_client()?;
+        clients.insert(self.clone(), client.clone());
+        Ok(client)
+    }
+
+    /// A new client for these settings; prefer [`TlsConfig::client`].
+    pub fn build_client(&self) -> Result<Client> {
+        let mut builder = client_builder();
+        if let Some(pem) = &self.ca_cert {
+            let certificate = Certificate::from_pem(pem.as_bytes()).map_err(|e| GhostFlowError::ValidationError {
+                message: format!("Invalid CA certificate: {}", e),
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        if self.insecure_skip_verify {
+            warn!("TLS certificate verification is disabled for this node");
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        builder.build().map_err(|e| GhostFlowError::NetworkError(e.to_string()))
+    }
+}
+
+/// The `tls` parameter read by [`TlsConfig::from_params`]
+pub fn tls_parameter() -> NodeParameter {
+    NodeParameter {
+        name: "tls".to_string(),
+        display_name: "TLS".to_string(),
+        description: Some(
+            "Certificate checks: {ca_cert: PEM of a CA to trust, insecure_skip_verify: true to skip verification}"
+                .to_string(),
+        ),
+        param_type: ParameterType::Object,
+        default_value: None,
+        required: false,
+        options: None,
+        validation: None,
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Send `request` with the retries, backoff, per-attempt timeout and per-host
+/// circuit breaker in `breakers` described by `policy`. Connection errors, timeouts, 5xx
+/// and 429 responses are retried; any other response is returned as-is for
+/// the caller to interpret. When retries run out on a retryable status, the
+/// last response is returned. Requests whose body cannot be cloned (streams)
+/// are sent once.
+pub async fn request_with_policy(
+    request: RequestBuilder,
+    policy: &RequestPolicy,
+    breakers: &CircuitBreakerRegistry,
+) -> Result<Response> {
+    let (client, request) = request.build_split();
+    let mut request = request.map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+    *request.timeout_mut() = Some(policy.timeout);
+    request.headers_mut().extend(trace_context_headers());
+    let host = format!(
+        "{}:{}",
+        request.url().host_str().unwrap_or_default(),
+        request.url().port_or_known_default().unwrap_or_default()
+    );
+
+    let mut current = request;
+    let mut backoff = policy.initial_backoff;
+    let mut attempt = 0;
+
+    loop {
+        if !breakers.try_acquire(&host) {
+            return Err(GhostFlowError::NetworkError(format!(
+                "Circuit breaker open for {}; not sending request",
+                host
+            )));
+        }
+
+        let retry = current.try_clone();
+        let outcome = client.execute(current).await;
+
+        let failure = match &outcome {
+            Ok(response) if is_retryable(response.status()) => Some(format!("HTTP {}", response.status())),
+            Ok(_) => None,
+            Err(e) => Some(e.to_string()),
+        };
+        let Some(failure) = failure else {
+            breakers.record_success(&host);
+            return outcome.map_err(|e| GhostFlowError::NetworkError(e.to_string()));
+        };
+        breakers.record_failure(&host, policy.failure_threshold, policy.reset_timeout);
+
+        match retry {
+            Some(retry) if attempt < policy.max_retries && !breakers.is_open(&host) => {
+                attempt += 1;
+                warn!("Request to {} failed ({}), retry {} of {}", host, failure, attempt, policy.max_retries);
+
+                // Honour Retry-After on 429 when the server sends one
+                let wait = outcome
+                    .as_ref()
+                    .ok()
+                    .and_then(|r| r.headers().get(reqwest::header::RETRY_AFTER))
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .map(Duration::from_secs)
+                    .unwrap_or(backoff)
+                    .min(policy.max_backoff);
+                tokio::time::sleep(wait).await;
+
+                backoff = backoff.mul_f64(policy.backoff_multiplier).min(policy.max_backoff);
+                current = retry;
+            }
+            _ => return outcome.map_err(|e| GhostFlowError::NetworkError(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn fast_policy() -> RequestPolicy {
+        RequestPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(5),
+            backoff_multiplier: 2.0,
+            max_backoff: Duration::from_millis(20),
+            timeout: Duration::from_secs(5),
+            failure_threshold: 3,
+            reset_timeout: Duration::from_millis(100),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_then_succeeds() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat.postMessage"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat.postMessage"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "ok": true })))
+            .mount(&server)
+            .await;
+
+        let breakers = CircuitBreakerRegistry::new();
+        let request = reqwest::Client::new()
+            .post(format!("{}/api/chat.postMessage", server.uri()))
+            .json(&serde_json::json!({ "text": "hi" }));
+        let response = request_with_policy(request, &fast_policy(), &breakers).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let breakers = CircuitBreakerRegistry::new();
+        let response = request_with_policy(reqwest::Client::new().get(server.uri()), &fast_policy(), &breakers)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_then_half_opens() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(3)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let breakers = CircuitBreakerRegistry::new();
+        let client = reqwest::Client::new();
+        let policy = fast_policy();
+
+        // Three consecutive failures (one attempt plus two retries) open it
+        let response = request_with_policy(client.get(server.uri()), &policy, &breakers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let rejected = request_with_policy(client.get(server.uri()), &policy, &breakers).await;
+        assert!(rejected.unwrap_err().to_string().contains("Circuit breaker open"));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+        // After the reset timeout a trial request goes through and closes it
+        tokio::time::sleep(policy.reset_timeout).await;
+        let response = request_with_policy(client.get(server.uri()), &policy, &breakers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let host = format!("127.0.0.1:{}", server.address().port());
+        assert!(!breakers.is_open(&host));
+    }
+
+    const TEST_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBkDCCATWgAwIBAgIUX34ncbMbEvNFh0+8KRKgdXR7h5swCgYIKoZIzj0EAwIw
+HDEaMBgGA1UEAwwRR2hvc3RGbG93IFRlc3QgQ0EwIBcNMjYxMDE2MTYxMDI3WhgP
+MjEyNjA5MjIxNjEwMjdaMBwxGjAYBgNVBAMMEUdob3N0RmxvdyBUZXN0IENBMFkw
+EwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAECw7BHC9n+L9g0VucoM9rScHLNMxkU681
+OyGRZLoXd7cFKjpraYcr1dC5W8x0WA2v9/ocjOTTL0q7sl1PWQ4SUaNTMFEwHQYD
+VR0OBBYEFBuaL5a0mdQPKVx2pSKRoFBhSFdkMB8GA1UdIwQYMBaAFBuaL5a0mdQP
+KVx2pSKRoFBhSFdkMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSQAwRgIh
+AMudXxfPEKS+CoAliY7RwFmtyQTlFYRzGHw0G/W39LerAiEA82NNPQLr3RSm3ue6
+ZUxIxNiIm6MFzmLGmHS+2EWdlps=
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn test_tls_verifies_certificates_by_default() {
+        let tls = TlsConfig::from_params(&serde_json::json!({"host": "pve.lan"})).unwrap();
+        assert_eq!(tls, TlsConfig::default());
+        assert!(!tls.insecure_skip_verify);
+        assert!(tls.ca_cert.is_none());
+
+        let tls = TlsConfig::from_params(&serde_json::json!({"tls": {}})).unwrap();
+        assert!(!tls.insecure_skip_verify);
+        assert!(TlsConfig::from_params(&serde_json::json!({"tls": {"verify": false}})).is_err());
+    }
+
+    #[test]
+    fn test_client_trusts_a_custom_ca() {
+        let tls = TlsConfig::from_params(&serde_json::json!({"tls": {"ca_cert": TEST_CA}})).unwrap();
+        assert_eq!(tls.ca_cert.as_deref(), Some(TEST_CA));
+        assert!(tls.build_client().is_ok());
+
+        let invalid = TlsConfig {
+            ca_cert: Some("not a certificate".to_string()),
+            ..TlsConfig::default()
+        };
+        assert!(matches!(invalid.build_client(), Err(GhostFlowError::ValidationError { .. })));
+    }
+}
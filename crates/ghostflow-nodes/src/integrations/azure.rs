@@ -1,8 +1,13 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use crate::http_util::shared_client;
+use ghostflow_core::{Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AzureVMNode;
@@ -11,96 +16,121 @@ pub struct AzureVMNode;
 impl Node for AzureVMNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "azure_vm".to_string(),
-            display_name: "Azure Virtual Machine".to_string(),
+            id: "azure_vm".to_string(),
+            name: "Azure Virtual Machine".to_string(),
             description: "Manage Azure Virtual Machines".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "access_token".to_string(),
                     display_name: "Access Token".to_string(),
-                    description: "Azure OAuth2 access token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Azure OAuth2 access token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "subscription_id".to_string(),
                     display_name: "Subscription ID".to_string(),
-                    description: "Azure subscription ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Azure subscription ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "resource_group".to_string(),
                     display_name: "Resource Group".to_string(),
-                    description: "Azure resource group name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Azure resource group name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "VM operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("VM operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("list".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "vm_name".to_string(),
                     display_name: "VM Name".to_string(),
-                    description: "Virtual machine name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Virtual machine name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "vm_size".to_string(),
                     display_name: "VM Size".to_string(),
-                    description: "Azure VM size (Standard_B1s, Standard_D2s_v3, etc.)".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("Azure VM size (Standard_B1s, Standard_D2s_v3, etc.)".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("Standard_B1s".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "location".to_string(),
                     display_name: "Location".to_string(),
-                    description: "Azure region".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("Azure region".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("eastus".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let access_token = context.get_parameter("access_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Access token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let access_token = context.input.get("access_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Access token is required"))?;
         
-        let subscription_id = context.get_parameter("subscription_id")
-            .and_then(|v| v.as_string())
-            .ok_or("Subscription ID is required")?;
+        let subscription_id = context.input.get("subscription_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Subscription ID is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("list".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = format!("https://management.azure.com/subscriptions/{}", subscription_id);
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "list" => {
-                let url = if let Some(rg) = context.get_parameter("resource_group").and_then(|v| v.as_string()) {
+                let url = if let Some(rg) = context.input.get("resource_group").and_then(|v| v.as_str()) {
                     format!("{}/resourceGroups/{}/providers/Microsoft.Compute/virtualMachines", base_url, rg)
                 } else {
                     format!("{}/providers/Microsoft.Compute/virtualMachines", base_url)
@@ -111,19 +141,20 @@ impl Node for AzureVMNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("api-version", "2023-03-01")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "get" => {
-                let resource_group = context.get_parameter("resource_group")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Resource group is required for get operation")?;
+                let resource_group = context.input.get("resource_group")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Resource group is required for get operation"))?;
                 
-                let vm_name = context.get_parameter("vm_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("VM name is required for get operation")?;
+                let vm_name = context.input.get("vm_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("VM name is required for get operation"))?;
 
                 let response = client
                     .get(&format!("{}/resourceGroups/{}/providers/Microsoft.Compute/virtualMachines/{}", 
@@ -131,19 +162,20 @@ impl Node for AzureVMNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("api-version", "2023-03-01")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "start" => {
-                let resource_group = context.get_parameter("resource_group")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Resource group is required for start operation")?;
+                let resource_group = context.input.get("resource_group")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Resource group is required for start operation"))?;
                 
-                let vm_name = context.get_parameter("vm_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("VM name is required for start operation")?;
+                let vm_name = context.input.get("vm_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("VM name is required for start operation"))?;
 
                 let response = client
                     .post(&format!("{}/resourceGroups/{}/providers/Microsoft.Compute/virtualMachines/{}/start", 
@@ -151,7 +183,8 @@ impl Node for AzureVMNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("api-version", "2023-03-01")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
                 json!({
                     "success": response.status().is_success(),
@@ -160,13 +193,13 @@ impl Node for AzureVMNode {
                 })
             },
             "stop" => {
-                let resource_group = context.get_parameter("resource_group")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Resource group is required for stop operation")?;
+                let resource_group = context.input.get("resource_group")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Resource group is required for stop operation"))?;
                 
-                let vm_name = context.get_parameter("vm_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("VM name is required for stop operation")?;
+                let vm_name = context.input.get("vm_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("VM name is required for stop operation"))?;
 
                 let response = client
                     .post(&format!("{}/resourceGroups/{}/providers/Microsoft.Compute/virtualMachines/{}/powerOff", 
@@ -174,7 +207,8 @@ impl Node for AzureVMNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("api-version", "2023-03-01")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
                 json!({
                     "success": response.status().is_success(),
@@ -183,13 +217,13 @@ impl Node for AzureVMNode {
                 })
             },
             "restart" => {
-                let resource_group = context.get_parameter("resource_group")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Resource group is required for restart operation")?;
+                let resource_group = context.input.get("resource_group")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Resource group is required for restart operation"))?;
                 
-                let vm_name = context.get_parameter("vm_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("VM name is required for restart operation")?;
+                let vm_name = context.input.get("vm_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("VM name is required for restart operation"))?;
 
                 let response = client
                     .post(&format!("{}/resourceGroups/{}/providers/Microsoft.Compute/virtualMachines/{}/restart", 
@@ -197,7 +231,8 @@ impl Node for AzureVMNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("api-version", "2023-03-01")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
                 json!({
                     "success": response.status().is_success(),
@@ -206,13 +241,13 @@ impl Node for AzureVMNode {
                 })
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -223,88 +258,111 @@ pub struct AzureStorageNode;
 impl Node for AzureStorageNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "azure_storage".to_string(),
-            display_name: "Azure Blob Storage".to_string(),
+            id: "azure_storage".to_string(),
+            name: "Azure Blob Storage".to_string(),
             description: "Manage Azure Blob Storage containers and files".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "account_name".to_string(),
                     display_name: "Storage Account Name".to_string(),
-                    description: "Azure storage account name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Azure storage account name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "account_key".to_string(),
                     display_name: "Account Key".to_string(),
-                    description: "Azure storage account key".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Azure storage account key".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Storage operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Storage operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("list_containers".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "container_name".to_string(),
                     display_name: "Container Name".to_string(),
-                    description: "Blob container name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Blob container name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "blob_name".to_string(),
                     display_name: "Blob Name".to_string(),
-                    description: "Blob file name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Blob file name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "content".to_string(),
                     display_name: "Content".to_string(),
-                    description: "File content to upload".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("File content to upload".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let account_name = context.get_parameter("account_name")
-            .and_then(|v| v.as_string())
-            .ok_or("Storage account name is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let account_name = context.input.get("account_name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Storage account name is required"))?;
         
-        let account_key = context.get_parameter("account_key")
-            .and_then(|v| v.as_string())
-            .ok_or("Account key is required")?;
+        let account_key = context.input.get("account_key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Account key is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("list_containers".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list_containers");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = format!("https://{}.blob.core.windows.net", account_name);
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "list_containers" => {
-                let auth_header = self.generate_auth_header(&account_name, &account_key, "GET", "/", "", "")?;
+                let auth_header = self.generate_auth_header(account_name, account_key, "GET", "/", "", "")?;
                 
                 let response = client
                     .get(&format!("{}/?comp=list", base_url))
@@ -312,17 +370,18 @@ impl Node for AzureStorageNode {
                     .header("x-ms-date", chrono::Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string())
                     .header("x-ms-version", "2021-04-10")
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let text = response.text().await?;
+                let text = response.text().await.map_err(network_error)?;
                 json!({ "containers": text })
             },
             "list_blobs" => {
-                let container_name = context.get_parameter("container_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Container name is required for list blobs operation")?;
+                let container_name = context.input.get("container_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Container name is required for list blobs operation"))?;
 
-                let auth_header = self.generate_auth_header(&account_name, &account_key, "GET", &format!("/{}", container_name), "restype=container&comp=list", "")?;
+                let auth_header = self.generate_auth_header(account_name, account_key, "GET", &format!("/{}", container_name), "restype=container&comp=list", "")?;
                 
                 let response = client
                     .get(&format!("{}/{}?restype=container&comp=list", base_url, container_name))
@@ -330,25 +389,26 @@ impl Node for AzureStorageNode {
                     .header("x-ms-date", chrono::Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string())
                     .header("x-ms-version", "2021-04-10")
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let text = response.text().await?;
+                let text = response.text().await.map_err(network_error)?;
                 json!({ "blobs": text })
             },
             "upload_blob" => {
-                let container_name = context.get_parameter("container_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Container name is required for upload operation")?;
+                let container_name = context.input.get("container_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Container name is required for upload operation"))?;
                 
-                let blob_name = context.get_parameter("blob_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Blob name is required for upload operation")?;
+                let blob_name = context.input.get("blob_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Blob name is required for upload operation"))?;
                 
-                let content = context.get_parameter("content")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Content is required for upload operation")?;
+                let content = context.input.get("content")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Content is required for upload operation"))?;
 
-                let auth_header = self.generate_auth_header(&account_name, &account_key, "PUT", &format!("/{}/{}", container_name, blob_name), "", &content)?;
+                let auth_header = self.generate_auth_header(account_name, account_key, "PUT", &format!("/{}/{}", container_name, blob_name), "", content)?;
                 
                 let response = client
                     .put(&format!("{}/{}/{}", base_url, container_name, blob_name))
@@ -357,9 +417,10 @@ impl Node for AzureStorageNode {
                     .header("x-ms-version", "2021-04-10")
                     .header("x-ms-blob-type", "BlockBlob")
                     .header("Content-Length", content.len().to_string())
-                    .body(content)
+                    .body(content.to_string())
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
                 json!({
                     "success": response.status().is_success(),
@@ -367,13 +428,13 @@ impl Node for AzureStorageNode {
                 })
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -383,7 +444,7 @@ impl AzureStorageNode {
         use sha2::Sha256;
         
         let date = chrono::Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
-        let content_length = if body.is_empty() { "0" } else { &body.len().to_string() };
+        let content_length = body.len().to_string();
         
         let string_to_sign = format!(
             "{}\n\n\n{}\n\n\n\n\n\n\n\n\nx-ms-date:{}\nx-ms-version:2021-04-10\n{}{}",
@@ -395,10 +456,10 @@ impl AzureStorageNode {
         );
 
         let decoded_key = base64::decode(account_key)
-            .map_err(|e| format!("Failed to decode account key: {}", e))?;
+            .map_err(|e| param_error(format!("Failed to decode account key: {}", e)))?;
         
         let mut mac = Hmac::<Sha256>::new_from_slice(&decoded_key)
-            .map_err(|e| format!("Failed to create HMAC: {}", e))?;
+            .map_err(|e| param_error(format!("Failed to create HMAC: {}", e)))?;
         
         mac.update(string_to_sign.as_bytes());
         let signature = base64::encode(mac.finalize().into_bytes());
@@ -1,8 +1,13 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use crate::http_util::shared_client;
+use ghostflow_core::{Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CloudflareDNSNode;
@@ -11,132 +16,160 @@ pub struct CloudflareDNSNode;
 impl Node for CloudflareDNSNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "cloudflare_dns".to_string(),
-            display_name: "Cloudflare DNS".to_string(),
+            id: "cloudflare_dns".to_string(),
+            name: "Cloudflare DNS".to_string(),
             description: "Manage Cloudflare DNS records".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "api_token".to_string(),
                     display_name: "API Token".to_string(),
-                    description: "Cloudflare API token with DNS edit permissions".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Cloudflare API token with DNS edit permissions".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "zone_id".to_string(),
                     display_name: "Zone ID".to_string(),
-                    description: "Cloudflare Zone ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Cloudflare Zone ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "DNS operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("DNS operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("list".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "record_type".to_string(),
                     display_name: "Record Type".to_string(),
-                    description: "DNS record type (A, AAAA, CNAME, MX, TXT, etc.)".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("DNS record type (A, AAAA, CNAME, MX, TXT, etc.)".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("A".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "name".to_string(),
                     display_name: "Record Name".to_string(),
-                    description: "DNS record name (e.g., subdomain)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("DNS record name (e.g., subdomain)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "content".to_string(),
                     display_name: "Content".to_string(),
-                    description: "Record content (IP address, domain, etc.)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Record content (IP address, domain, etc.)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "proxied".to_string(),
                     display_name: "Proxied".to_string(),
-                    description: "Enable Cloudflare proxy".to_string(),
-                    parameter_type: ParameterType::Boolean,
-                    required: false,
+                    description: Some("Enable Cloudflare proxy".to_string()),
+                    param_type: ParameterType::Boolean,
                     default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "ttl".to_string(),
                     display_name: "TTL".to_string(),
-                    description: "Time to live in seconds (1 = auto)".to_string(),
-                    parameter_type: ParameterType::Number,
+                    description: Some("Time to live in seconds (1 = auto)".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(1))),
                     required: false,
-                    default_value: Some(Value::Number(1.0)),
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let api_token = context.get_parameter("api_token")
-            .and_then(|v| v.as_string())
-            .ok_or("API token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let api_token = context.input.get("api_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("API token is required"))?;
         
-        let zone_id = context.get_parameter("zone_id")
-            .and_then(|v| v.as_string())
-            .ok_or("Zone ID is required")?;
+        let zone_id = context.input.get("zone_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Zone ID is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("list".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = format!("https://api.cloudflare.com/client/v4/zones/{}/dns_records", zone_id);
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "list" => {
                 let response = client
                     .get(&base_url)
                     .header("Authorization", format!("Bearer {}", api_token))
                     .header("Content-Type", "application/json")
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "create" => {
-                let record_type = context.get_parameter("record_type")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("A".to_string());
+                let record_type = context.input.get("record_type")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("A");
                 
-                let name = context.get_parameter("name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Record name is required for create operation")?;
+                let name = context.input.get("name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Record name is required for create operation"))?;
                 
-                let content = context.get_parameter("content")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Content is required for create operation")?;
+                let content = context.input.get("content")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Content is required for create operation"))?;
                 
-                let proxied = context.get_parameter("proxied")
+                let proxied = context.input.get("proxied")
                     .and_then(|v| v.as_bool())
                     .unwrap_or(false);
                 
-                let ttl = context.get_parameter("ttl")
-                    .and_then(|v| v.as_number())
+                let ttl = context.input.get("ttl")
+                    .and_then(|v| v.as_f64())
                     .unwrap_or(1.0) as i64;
 
                 let body = json!({
@@ -153,28 +186,29 @@ impl Node for CloudflareDNSNode {
                     .header("Content-Type", "application/json")
                     .json(&body)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "update" => {
-                let record_id = context.get_parameter("record_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Record ID is required for update operation")?;
+                let record_id = context.input.get("record_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Record ID is required for update operation"))?;
                 
                 let mut body = json!({});
                 
-                if let Some(name) = context.get_parameter("name").and_then(|v| v.as_string()) {
+                if let Some(name) = context.input.get("name").and_then(|v| v.as_str()) {
                     body["name"] = json!(name);
                 }
-                if let Some(content) = context.get_parameter("content").and_then(|v| v.as_string()) {
+                if let Some(content) = context.input.get("content").and_then(|v| v.as_str()) {
                     body["content"] = json!(content);
                 }
-                if let Some(proxied) = context.get_parameter("proxied").and_then(|v| v.as_bool()) {
+                if let Some(proxied) = context.input.get("proxied").and_then(|v| v.as_bool()) {
                     body["proxied"] = json!(proxied);
                 }
-                if let Some(ttl) = context.get_parameter("ttl").and_then(|v| v.as_number()) {
+                if let Some(ttl) = context.input.get("ttl").and_then(|v| v.as_f64()) {
                     body["ttl"] = json!(ttl as i64);
                 }
 
@@ -184,34 +218,36 @@ impl Node for CloudflareDNSNode {
                     .header("Content-Type", "application/json")
                     .json(&body)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "delete" => {
-                let record_id = context.get_parameter("record_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Record ID is required for delete operation")?;
+                let record_id = context.input.get("record_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Record ID is required for delete operation"))?;
 
                 let response = client
                     .delete(&format!("{}/{}", base_url, record_id))
                     .header("Authorization", format!("Bearer {}", api_token))
                     .header("Content-Type", "application/json")
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -222,109 +258,133 @@ pub struct CloudflareWAFNode;
 impl Node for CloudflareWAFNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "cloudflare_waf".to_string(),
-            display_name: "Cloudflare WAF".to_string(),
+            id: "cloudflare_waf".to_string(),
+            name: "Cloudflare WAF".to_string(),
             description: "Manage Cloudflare WAF rules and firewall settings".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "api_token".to_string(),
                     display_name: "API Token".to_string(),
-                    description: "Cloudflare API token with WAF permissions".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Cloudflare API token with WAF permissions".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "zone_id".to_string(),
                     display_name: "Zone ID".to_string(),
-                    description: "Cloudflare Zone ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Cloudflare Zone ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "WAF operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("WAF operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("list_rules".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "action".to_string(),
                     display_name: "Action".to_string(),
-                    description: "Rule action (block, challenge, js_challenge, allow)".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("Rule action (block, challenge, js_challenge, allow)".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("block".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "expression".to_string(),
                     display_name: "Expression".to_string(),
-                    description: "WAF rule expression".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("WAF rule expression".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "description".to_string(),
                     display_name: "Description".to_string(),
-                    description: "Rule description".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Rule description".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let api_token = context.get_parameter("api_token")
-            .and_then(|v| v.as_string())
-            .ok_or("API token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let api_token = context.input.get("api_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("API token is required"))?;
         
-        let zone_id = context.get_parameter("zone_id")
-            .and_then(|v| v.as_string())
-            .ok_or("Zone ID is required")?;
+        let zone_id = context.input.get("zone_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Zone ID is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("list_rules".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list_rules");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = format!("https://api.cloudflare.com/client/v4/zones/{}/firewall/rules", zone_id);
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "list_rules" => {
                 let response = client
                     .get(&base_url)
                     .header("Authorization", format!("Bearer {}", api_token))
                     .header("Content-Type", "application/json")
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "create_rule" => {
-                let action = context.get_parameter("action")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("block".to_string());
+                let action = context.input.get("action")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("block");
                 
-                let expression = context.get_parameter("expression")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Expression is required for create operation")?;
+                let expression = context.input.get("expression")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Expression is required for create operation"))?;
                 
-                let description = context.get_parameter("description")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("Created by GhostFlow".to_string());
+                let description = context.input.get("description")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("Created by GhostFlow");
 
                 let filter_body = json!({
                     "expression": expression,
@@ -337,11 +397,12 @@ impl Node for CloudflareWAFNode {
                     .header("Content-Type", "application/json")
                     .json(&vec![filter_body])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let filter_data: serde_json::Value = filter_response.json().await?;
+                let filter_data: serde_json::Value = filter_response.json().await.map_err(network_error)?;
                 let filter_id = filter_data["result"][0]["id"].as_str()
-                    .ok_or("Failed to create filter")?;
+                    .ok_or_else(|| param_error("Failed to create filter"))?;
 
                 let rule_body = json!({
                     "filter": {
@@ -357,18 +418,19 @@ impl Node for CloudflareWAFNode {
                     .header("Content-Type", "application/json")
                     .json(&vec![rule_body])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
     }
 }
\ No newline at end of file
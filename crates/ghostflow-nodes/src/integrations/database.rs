@@ -1,144 +1,326 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PostgreSQLNode;
 
+/// `connection_string` when given, otherwise one built from the individual
+/// connection fields.
+fn postgres_connection_string(input: &Value) -> Result<String> {
+    if let Some(conn_str) = input.get("connection_string").and_then(|v| v.as_str()) {
+        return Ok(conn_str.to_string());
+    }
+    let host = input.get("host").and_then(|v| v.as_str()).unwrap_or("localhost");
+    let port = input.get("port").and_then(|v| v.as_f64()).unwrap_or(5432.0) as u16;
+    let database = input.get("database").and_then(|v| v.as_str()).ok_or_else(|| param_error("Database name is required"))?;
+    let username = input.get("username").and_then(|v| v.as_str()).ok_or_else(|| param_error("Username is required"))?;
+    let password = input.get("password").and_then(|v| v.as_str()).ok_or_else(|| param_error("Password is required"))?;
+
+    Ok(format!(
+        "postgresql://{}:{}@{}:{}/{}",
+        urlencoding::encode(username),
+        urlencoding::encode(password),
+        host,
+        port,
+        database
+    ))
+}
+
+/// One statement of a `transaction` operation
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct TransactionStatement {
+    query: String,
+    /// Values for `$1`, `$2`, ... in the query
+    #[serde(default)]
+    parameters: Vec<Value>,
+}
+
+fn transaction_statements(input: &Value) -> Result<Vec<TransactionStatement>> {
+    let statements = input
+        .get("statements")
+        .ok_or_else(|| param_error("Statements are required for transaction operation"))?;
+    let statements: Vec<TransactionStatement> = serde_json::from_value(statements.clone())
+        .map_err(|e| param_error(format!("Statements must be an array of {{query, parameters}}: {}", e)))?;
+    if statements.is_empty() {
+        return Err(param_error("Transaction operation needs at least one statement"));
+    }
+    Ok(statements)
+}
+
+type PgQuery<'q> = sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>;
+
+fn bind_parameter<'q>(query: PgQuery<'q>, value: &'q Value) -> PgQuery<'q> {
+    match value {
+        Value::Null => query.bind(Option::<String>::None),
+        Value::Bool(b) => query.bind(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => query.bind(i),
+            None => query.bind(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => query.bind(s.as_str()),
+        other => query.bind(sqlx::types::Json(other)),
+    }
+}
+
+/// Run `statements` in order inside one transaction. If any statement fails
+/// the transaction is rolled back and the error names that statement;
+/// otherwise it is committed and each statement's affected row count is
+/// returned.
+async fn run_transaction(connection_string: &str, node_id: &str, statements: &[TransactionStatement]) -> Result<Value> {
+    use sqlx::Connection;
+
+    let mut connection = sqlx::PgConnection::connect(connection_string)
+        .await
+        .map_err(|e| network_error(format!("Could not connect to PostgreSQL: {}", e)))?;
+    let mut transaction = connection.begin().await?;
+
+    let mut results = Vec::with_capacity(statements.len());
+    for (index, statement) in statements.iter().enumerate() {
+        let query = statement
+            .parameters
+            .iter()
+            .fold(sqlx::query(&statement.query), bind_parameter);
+        match query.execute(&mut *transaction).await {
+            Ok(done) => results.push(json!({ "index": index, "rows_affected": done.rows_affected() })),
+            Err(e) => {
+                transaction.rollback().await?;
+                return Err(GhostFlowError::NodeExecutionError {
+                    node_id: node_id.to_string(),
+                    message: format!("Transaction rolled back: statement {} failed: {}", index, e),
+                });
+            }
+        }
+    }
+    transaction.commit().await?;
+
+    let affected_rows: u64 = results.iter().filter_map(|r| r["rows_affected"].as_u64()).sum();
+    Ok(json!({
+        "result": {
+            "success": true,
+            "operation": "transaction",
+            "committed": true,
+            "statements": results,
+        },
+        "rows": [],
+        "affected_rows": affected_rows,
+    }))
+}
+
 #[async_trait]
 impl Node for PostgreSQLNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "postgresql".to_string(),
-            display_name: "PostgreSQL".to_string(),
+            id: "postgresql".to_string(),
+            name: "PostgreSQL".to_string(),
             description: "Execute queries against PostgreSQL database".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "connection_string".to_string(),
                     display_name: "Connection String".to_string(),
-                    description: "PostgreSQL connection string".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("PostgreSQL connection string".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "host".to_string(),
                     display_name: "Host".to_string(),
-                    description: "Database host".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Database host".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("localhost".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "port".to_string(),
                     display_name: "Port".to_string(),
-                    description: "Database port".to_string(),
-                    parameter_type: ParameterType::Number,
+                    description: Some("Database port".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(5432))),
                     required: false,
-                    default_value: Some(Value::Number(5432.0)),
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "database".to_string(),
                     display_name: "Database".to_string(),
-                    description: "Database name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Database name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "username".to_string(),
                     display_name: "Username".to_string(),
-                    description: "Database username".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Database username".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "password".to_string(),
                     display_name: "Password".to_string(),
-                    description: "Database password".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Database password".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Database operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Database operation to perform: query, insert, update, delete or transaction".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("query".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "statements".to_string(),
+                    display_name: "Statements".to_string(),
+                    description: Some("Array of {query, parameters} run in one transaction by the transaction operation; any failure rolls back all of them".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "query".to_string(),
                     display_name: "SQL Query".to_string(),
-                    description: "SQL query to execute".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("SQL query to execute".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "parameters".to_string(),
                     display_name: "Parameters".to_string(),
-                    description: "Query parameters (JSON array)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Query parameters (JSON array)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "table_name".to_string(),
                     display_name: "Table Name".to_string(),
-                    description: "Table name for insert/update operations".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Table name for insert/update operations".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "data".to_string(),
                     display_name: "Data".to_string(),
-                    description: "Data to insert/update (JSON object)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Data to insert/update (JSON object)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "rows".to_string(), "affected_rows".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "rows".to_string(),
+                    display_name: "Rows".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "affected_rows".to_string(),
+                    display_name: "Affected Rows".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let connection_string = if let Some(conn_str) = context.get_parameter("connection_string").and_then(|v| v.as_string()) {
-            conn_str
-        } else {
-            let host = context.get_parameter("host").and_then(|v| v.as_string()).unwrap_or("localhost".to_string());
-            let port = context.get_parameter("port").and_then(|v| v.as_number()).unwrap_or(5432.0) as u16;
-            let database = context.get_parameter("database").and_then(|v| v.as_string()).ok_or("Database name is required")?;
-            let username = context.get_parameter("username").and_then(|v| v.as_string()).ok_or("Username is required")?;
-            let password = context.get_parameter("password").and_then(|v| v.as_string()).ok_or("Password is required")?;
-            
-            format!("postgresql://{}:{}@{}:{}/{}", username, password, host, port, database)
-        };
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)?;
+        if context.input.get("operation").and_then(|v| v.as_str()) == Some("transaction") {
+            transaction_statements(&context.input)?;
+        }
+        Ok(())
+    }
+
+    fn supports_connection_test(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self, context: &ExecutionContext) -> Result<String> {
+        use sqlx::Connection;
+
+        let mut connection = sqlx::PgConnection::connect(&postgres_connection_string(&context.input)?)
+            .await
+            .map_err(|e| GhostFlowError::AuthenticationError {
+                message: format!("Could not connect to PostgreSQL: {}", e),
+            })?;
+        let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut connection).await?;
+        let _ = connection.close().await;
+        if one != 1 {
+            return Err(network_error("PostgreSQL answered SELECT 1 unexpectedly"));
+        }
+        Ok("Connected to PostgreSQL; SELECT 1 succeeded".to_string())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let connection_string = postgres_connection_string(&context.input)?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("query".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("query");
+
+        if operation == "transaction" {
+            let statements = transaction_statements(&context.input)?;
+            return run_transaction(&connection_string, &context.node_id, &statements).await;
+        }
 
         // TODO: Implement actual PostgreSQL connection using sqlx or tokio-postgres
         // For now, simulate the operations
         
-        let result = match operation.as_str() {
+        let result = match operation {
             "query" => {
-                let query = context.get_parameter("query")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Query is required for query operation")?;
+                let query = context.input.get("query")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Query is required for query operation"))?;
                 
                 // Simulate query execution
                 json!({
@@ -149,12 +331,12 @@ impl Node for PostgreSQLNode {
                 })
             },
             "insert" => {
-                let table_name = context.get_parameter("table_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Table name is required for insert operation")?;
+                let table_name = context.input.get("table_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Table name is required for insert operation"))?;
                 
-                let data = context.get_parameter("data")
-                    .ok_or("Data is required for insert operation")?;
+                let data = context.input.get("data")
+                    .ok_or_else(|| param_error("Data is required for insert operation"))?;
                 
                 json!({
                     "success": true,
@@ -165,12 +347,12 @@ impl Node for PostgreSQLNode {
                 })
             },
             "update" => {
-                let table_name = context.get_parameter("table_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Table name is required for update operation")?;
+                let table_name = context.input.get("table_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Table name is required for update operation"))?;
                 
-                let data = context.get_parameter("data")
-                    .ok_or("Data is required for update operation")?;
+                let data = context.input.get("data")
+                    .ok_or_else(|| param_error("Data is required for update operation"))?;
                 
                 json!({
                     "success": true,
@@ -180,9 +362,9 @@ impl Node for PostgreSQLNode {
                 })
             },
             "delete" => {
-                let table_name = context.get_parameter("table_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Table name is required for delete operation")?;
+                let table_name = context.input.get("table_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Table name is required for delete operation"))?;
                 
                 json!({
                     "success": true,
@@ -192,7 +374,7 @@ impl Node for PostgreSQLNode {
                 })
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
@@ -202,12 +384,12 @@ impl Node for PostgreSQLNode {
             json!({"id": 3, "name": "Carol", "email": "carol@example.com"}),
         ];
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
-        outputs.insert("rows".to_string(), Value::Array(sample_rows.into_iter().map(Value::Object).collect()));
-        outputs.insert("affected_rows".to_string(), Value::Number(result.get("affected_rows").and_then(|v| v.as_u64()).unwrap_or(0) as f64));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
+        outputs.insert("rows".to_string(), Value::Array(sample_rows));
+        outputs.insert("affected_rows".to_string(), json!(result.get("affected_rows").and_then(|v| v.as_u64()).unwrap_or(0)));
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -218,102 +400,138 @@ pub struct MySQLNode;
 impl Node for MySQLNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "mysql".to_string(),
-            display_name: "MySQL".to_string(),
+            id: "mysql".to_string(),
+            name: "MySQL".to_string(),
             description: "Execute queries against MySQL database".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "connection_string".to_string(),
                     display_name: "Connection String".to_string(),
-                    description: "MySQL connection string".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("MySQL connection string".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "host".to_string(),
                     display_name: "Host".to_string(),
-                    description: "Database host".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Database host".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("localhost".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "port".to_string(),
                     display_name: "Port".to_string(),
-                    description: "Database port".to_string(),
-                    parameter_type: ParameterType::Number,
+                    description: Some("Database port".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(3306))),
                     required: false,
-                    default_value: Some(Value::Number(3306.0)),
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "database".to_string(),
                     display_name: "Database".to_string(),
-                    description: "Database name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Database name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "username".to_string(),
                     display_name: "Username".to_string(),
-                    description: "Database username".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Database username".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "password".to_string(),
                     display_name: "Password".to_string(),
-                    description: "Database password".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Database password".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Database operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Database operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("query".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "query".to_string(),
                     display_name: "SQL Query".to_string(),
-                    description: "SQL query to execute".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("SQL query to execute".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "parameters".to_string(),
                     display_name: "Parameters".to_string(),
-                    description: "Query parameters (JSON array)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Query parameters (JSON array)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "rows".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "rows".to_string(),
+                    display_name: "Rows".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
         // Similar implementation to PostgreSQL but for MySQL
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("query".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("query");
 
-        let query = context.get_parameter("query")
-            .and_then(|v| v.as_string())
-            .ok_or("Query is required")?;
+        let query = context.input.get("query")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Query is required"))?;
 
         // TODO: Implement actual MySQL connection using sqlx or mysql_async
         let result = json!({
@@ -329,11 +547,11 @@ impl Node for MySQLNode {
             json!({"product_id": 2, "name": "Widget B", "price": 29.99}),
         ];
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        outputs.insert("rows".to_string(), Value::Array(sample_rows.into_iter().map(Value::Object).collect()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        outputs.insert("rows".to_string(), Value::Array(sample_rows));
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -344,139 +562,190 @@ pub struct MongoDBNode;
 impl Node for MongoDBNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "mongodb".to_string(),
-            display_name: "MongoDB".to_string(),
+            id: "mongodb".to_string(),
+            name: "MongoDB".to_string(),
             description: "Execute operations against MongoDB database".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "connection_string".to_string(),
                     display_name: "Connection String".to_string(),
-                    description: "MongoDB connection string".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("MongoDB connection string".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "host".to_string(),
                     display_name: "Host".to_string(),
-                    description: "Database host".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Database host".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("localhost".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "port".to_string(),
                     display_name: "Port".to_string(),
-                    description: "Database port".to_string(),
-                    parameter_type: ParameterType::Number,
+                    description: Some("Database port".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(27017))),
                     required: false,
-                    default_value: Some(Value::Number(27017.0)),
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "database".to_string(),
                     display_name: "Database".to_string(),
-                    description: "Database name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Database name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "username".to_string(),
                     display_name: "Username".to_string(),
-                    description: "Database username".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Database username".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "password".to_string(),
                     display_name: "Password".to_string(),
-                    description: "Database password".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Database password".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "MongoDB operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("MongoDB operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("find".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "collection".to_string(),
                     display_name: "Collection".to_string(),
-                    description: "MongoDB collection name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("MongoDB collection name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "filter".to_string(),
                     display_name: "Filter".to_string(),
-                    description: "MongoDB filter query (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("MongoDB filter query (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "document".to_string(),
                     display_name: "Document".to_string(),
-                    description: "Document to insert/update (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Document to insert/update (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "projection".to_string(),
                     display_name: "Projection".to_string(),
-                    description: "Fields to include/exclude (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Fields to include/exclude (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "limit".to_string(),
                     display_name: "Limit".to_string(),
-                    description: "Maximum number of documents to return".to_string(),
-                    parameter_type: ParameterType::Number,
-                    required: false,
+                    description: Some("Maximum number of documents to return".to_string()),
+                    param_type: ParameterType::Number,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "sort".to_string(),
                     display_name: "Sort".to_string(),
-                    description: "Sort criteria (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Sort criteria (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "documents".to_string(), "count".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "documents".to_string(),
+                    display_name: "Documents".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "count".to_string(),
+                    display_name: "Count".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("find".to_string());
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("find");
         
-        let collection = context.get_parameter("collection")
-            .and_then(|v| v.as_string())
-            .ok_or("Collection name is required")?;
+        let collection = context.input.get("collection")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Collection name is required"))?;
 
         // TODO: Implement actual MongoDB connection using mongodb crate
-        let result = match operation.as_str() {
+        let result = match operation {
             "find" => {
-                let filter = context.get_parameter("filter").cloned().unwrap_or(Value::Object(serde_json::Map::new()));
-                let limit = context.get_parameter("limit").and_then(|v| v.as_number());
+                let filter = context.input.get("filter").cloned().unwrap_or(Value::Object(serde_json::Map::new()));
+                let limit = context.input.get("limit").and_then(|v| v.as_f64());
                 
                 json!({
                     "success": true,
@@ -488,8 +757,8 @@ impl Node for MongoDBNode {
                 })
             },
             "insert" => {
-                let document = context.get_parameter("document")
-                    .ok_or("Document is required for insert operation")?;
+                let document = context.input.get("document")
+                    .ok_or_else(|| param_error("Document is required for insert operation"))?;
                 
                 json!({
                     "success": true,
@@ -500,10 +769,10 @@ impl Node for MongoDBNode {
                 })
             },
             "update" => {
-                let filter = context.get_parameter("filter")
-                    .ok_or("Filter is required for update operation")?;
-                let document = context.get_parameter("document")
-                    .ok_or("Document is required for update operation")?;
+                let filter = context.input.get("filter")
+                    .ok_or_else(|| param_error("Filter is required for update operation"))?;
+                let document = context.input.get("document")
+                    .ok_or_else(|| param_error("Document is required for update operation"))?;
                 
                 json!({
                     "success": true,
@@ -515,8 +784,8 @@ impl Node for MongoDBNode {
                 })
             },
             "delete" => {
-                let filter = context.get_parameter("filter")
-                    .ok_or("Filter is required for delete operation")?;
+                let filter = context.input.get("filter")
+                    .ok_or_else(|| param_error("Filter is required for delete operation"))?;
                 
                 json!({
                     "success": true,
@@ -527,8 +796,8 @@ impl Node for MongoDBNode {
                 })
             },
             "aggregate" => {
-                let pipeline = context.get_parameter("pipeline")
-                    .ok_or("Pipeline is required for aggregate operation")?;
+                let pipeline = context.input.get("pipeline")
+                    .ok_or_else(|| param_error("Pipeline is required for aggregate operation"))?;
                 
                 json!({
                     "success": true,
@@ -539,7 +808,7 @@ impl Node for MongoDBNode {
                 })
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
@@ -549,12 +818,12 @@ impl Node for MongoDBNode {
             json!({"_id": "64f1234567890abcdef12347", "name": "Product C", "category": "electronics", "price": 199.99}),
         ];
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        outputs.insert("documents".to_string(), Value::Array(sample_documents.iter().cloned().map(Value::Object).collect()));
-        outputs.insert("count".to_string(), Value::Number(sample_documents.len() as f64));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        outputs.insert("documents".to_string(), Value::Array(sample_documents.clone()));
+        outputs.insert("count".to_string(), json!(sample_documents.len()));
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -565,112 +834,150 @@ pub struct RedisNode;
 impl Node for RedisNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "redis".to_string(),
-            display_name: "Redis".to_string(),
+            id: "redis".to_string(),
+            name: "Redis".to_string(),
             description: "Interact with Redis key-value store".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "connection_string".to_string(),
                     display_name: "Connection String".to_string(),
-                    description: "Redis connection string (redis://...)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Redis connection string (redis://...)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "host".to_string(),
                     display_name: "Host".to_string(),
-                    description: "Redis host".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Redis host".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("localhost".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "port".to_string(),
                     display_name: "Port".to_string(),
-                    description: "Redis port".to_string(),
-                    parameter_type: ParameterType::Number,
+                    description: Some("Redis port".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(6379))),
                     required: false,
-                    default_value: Some(Value::Number(6379.0)),
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "password".to_string(),
                     display_name: "Password".to_string(),
-                    description: "Redis password (optional)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Redis password (optional)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "database".to_string(),
                     display_name: "Database".to_string(),
-                    description: "Redis database number".to_string(),
-                    parameter_type: ParameterType::Number,
+                    description: Some("Redis database number".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(0))),
                     required: false,
-                    default_value: Some(Value::Number(0.0)),
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Redis operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Redis operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("get".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "key".to_string(),
                     display_name: "Key".to_string(),
-                    description: "Redis key".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Redis key".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "value".to_string(),
                     display_name: "Value".to_string(),
-                    description: "Value to store".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Value to store".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "ttl".to_string(),
                     display_name: "TTL (seconds)".to_string(),
-                    description: "Time to live in seconds".to_string(),
-                    parameter_type: ParameterType::Number,
-                    required: false,
+                    description: Some("Time to live in seconds".to_string()),
+                    param_type: ParameterType::Number,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "pattern".to_string(),
                     display_name: "Pattern".to_string(),
-                    description: "Pattern for keys operation".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Pattern for keys operation".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "value".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "value".to_string(),
+                    display_name: "Value".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("get".to_string());
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("get");
 
         // TODO: Implement actual Redis connection using redis crate
-        let result = match operation.as_str() {
+        let result = match operation {
             "get" => {
-                let key = context.get_parameter("key")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Key is required for get operation")?;
+                let key = context.input.get("key")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Key is required for get operation"))?;
                 
                 json!({
                     "success": true,
@@ -681,13 +988,13 @@ impl Node for RedisNode {
                 })
             },
             "set" => {
-                let key = context.get_parameter("key")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Key is required for set operation")?;
-                let value = context.get_parameter("value")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Value is required for set operation")?;
-                let ttl = context.get_parameter("ttl").and_then(|v| v.as_number());
+                let key = context.input.get("key")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Key is required for set operation"))?;
+                let value = context.input.get("value")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Value is required for set operation"))?;
+                let ttl = context.input.get("ttl").and_then(|v| v.as_f64());
                 
                 json!({
                     "success": true,
@@ -699,9 +1006,9 @@ impl Node for RedisNode {
                 })
             },
             "del" => {
-                let key = context.get_parameter("key")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Key is required for del operation")?;
+                let key = context.input.get("key")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Key is required for del operation"))?;
                 
                 json!({
                     "success": true,
@@ -712,9 +1019,9 @@ impl Node for RedisNode {
                 })
             },
             "keys" => {
-                let pattern = context.get_parameter("pattern")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("*".to_string());
+                let pattern = context.input.get("pattern")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("*");
                 
                 json!({
                     "success": true,
@@ -725,9 +1032,9 @@ impl Node for RedisNode {
                 })
             },
             "exists" => {
-                let key = context.get_parameter("key")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Key is required for exists operation")?;
+                let key = context.input.get("key")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Key is required for exists operation"))?;
                 
                 json!({
                     "success": true,
@@ -738,17 +1045,134 @@ impl Node for RedisNode {
                 })
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
         
         if let Some(value) = result.get("value") {
             outputs.insert("value".to_string(), value.clone().into());
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use sqlx::Connection;
+    use uuid::Uuid;
+
+    /// Connection string for a throwaway PostgreSQL server. Tests that need
+    /// one are ignored by default; run them with
+    /// `GHOSTFLOW_TEST_POSTGRES=postgres://... cargo test -- --ignored`.
+    const TEST_SERVER_ENV: &str = "GHOSTFLOW_TEST_POSTGRES";
+
+    /// A new, empty `(id, name)` table with a unique name
+    async fn create_table(connection_string: &str) -> String {
+        let table = format!("ghostflow_tx_{}", Uuid::new_v4().simple());
+        let mut connection = sqlx::PgConnection::connect(connection_string).await.unwrap();
+        sqlx::query(&format!("CREATE TABLE {} (id INT8 PRIMARY KEY, name TEXT NOT NULL)", table))
+            .execute(&mut connection)
+            .await
+            .unwrap();
+        table
+    }
+
+    /// Number of rows in `table`, which is dropped afterwards
+    async fn count_and_drop(connection_string: &str, table: &str) -> i64 {
+        let mut connection = sqlx::PgConnection::connect(connection_string).await.unwrap();
+        let count = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+            .fetch_one(&mut connection)
+            .await
+            .unwrap();
+        sqlx::query(&format!("DROP TABLE {}", table))
+            .execute(&mut connection)
+            .await
+            .unwrap();
+        count
+    }
+
+    #[tokio::test]
+    async fn test_transaction_statements_are_validated() {
+        let input = |statements: Value| {
+            context(json!({
+                "database": "app",
+                "username": "app",
+                "password": "secret",
+                "operation": "transaction",
+                "statements": statements,
+            }))
+        };
+
+        assert!(PostgreSQLNode
+            .validate(&input(json!([
+                { "query": "UPDATE accounts SET balance = balance - $1 WHERE id = $2", "parameters": [50, 1] },
+                { "query": "UPDATE accounts SET balance = balance + $1 WHERE id = $2", "parameters": [50, 2] },
+            ])))
+            .await
+            .is_ok());
+        assert!(PostgreSQLNode.validate(&input(json!([]))).await.is_err());
+        assert!(PostgreSQLNode.validate(&input(json!([{ "parameters": [1] }]))).await.is_err());
+        assert!(PostgreSQLNode.validate(&input(json!("BEGIN; COMMIT;"))).await.is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL server"]
+    async fn test_transaction_commits_every_statement() {
+        let connection_string = std::env::var(TEST_SERVER_ENV).expect("GHOSTFLOW_TEST_POSTGRES must be set");
+        let table = create_table(&connection_string).await;
+
+        let output = PostgreSQLNode
+            .execute(context(json!({
+                "connection_string": connection_string,
+                "operation": "transaction",
+                "statements": [
+                    {
+                        "query": format!("INSERT INTO {} (id, name) VALUES ($1, $2), ($3, $4)", table),
+                        "parameters": [1, "ada", 2, "grace"],
+                    },
+                    {
+                        "query": format!("UPDATE {} SET name = $1 WHERE id = $2", table),
+                        "parameters": ["linus", 2],
+                    },
+                ],
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["result"]["committed"], true);
+        assert_eq!(output["result"]["statements"][0]["rows_affected"], 2);
+        assert_eq!(output["result"]["statements"][1]["rows_affected"], 1);
+        assert_eq!(output["affected_rows"], 3);
+        assert_eq!(count_and_drop(&connection_string, &table).await, 2);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL server"]
+    async fn test_failed_statement_rolls_back_the_transaction() {
+        let connection_string = std::env::var(TEST_SERVER_ENV).expect("GHOSTFLOW_TEST_POSTGRES must be set");
+        let table = create_table(&connection_string).await;
+        let insert = format!("INSERT INTO {} (id, name) VALUES ($1, $2)", table);
+
+        let error = PostgreSQLNode
+            .execute(context(json!({
+                "connection_string": connection_string,
+                "operation": "transaction",
+                "statements": [
+                    { "query": insert, "parameters": [1, "ada"] },
+                    { "query": insert, "parameters": [1, "duplicate key"] },
+                    { "query": insert, "parameters": [3, "never runs"] },
+                ],
+            })))
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("rolled back: statement 1 failed"), "{}", error);
+        assert_eq!(count_and_drop(&connection_string, &table).await, 0);
+    }
+}
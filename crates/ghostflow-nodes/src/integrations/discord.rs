@@ -1,8 +1,14 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use crate::http_util::shared_client;
+use ghostflow_core::{Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+    ParameterValidation,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscordWebhookNode;
@@ -11,101 +17,149 @@ pub struct DiscordWebhookNode;
 impl Node for DiscordWebhookNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "discord_webhook".to_string(),
-            display_name: "Discord Webhook".to_string(),
+            id: "discord_webhook".to_string(),
+            name: "Discord Webhook".to_string(),
             description: "Send messages to Discord via webhook".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "webhook_url".to_string(),
                     display_name: "Webhook URL".to_string(),
-                    description: "Discord webhook URL".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Discord webhook URL".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: Some(ParameterValidation {
+                        min_length: None,
+                        max_length: None,
+                        min_value: None,
+                        max_value: None,
+                        pattern: Some(r"^https://(discord\.com|discordapp\.com)/api/webhooks/".to_string()),
+                    }),
                 },
                 NodeParameter {
                     name: "content".to_string(),
                     display_name: "Message Content".to_string(),
-                    description: "Text message to send".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Text message to send".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: Some(ParameterValidation {
+                        min_length: None,
+                        max_length: Some(2000),
+                        min_value: None,
+                        max_value: None,
+                        pattern: None,
+                    }),
                 },
                 NodeParameter {
                     name: "username".to_string(),
                     display_name: "Username".to_string(),
-                    description: "Override webhook username".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Override webhook username".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("GhostFlow".to_string())),
+                    required: false,
+                    options: None,
+                    validation: Some(ParameterValidation {
+                        min_length: None,
+                        max_length: Some(80),
+                        min_value: None,
+                        max_value: None,
+                        pattern: None,
+                    }),
                 },
                 NodeParameter {
                     name: "avatar_url".to_string(),
                     display_name: "Avatar URL".to_string(),
-                    description: "Override webhook avatar".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Override webhook avatar".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "embed".to_string(),
                     display_name: "Embed".to_string(),
-                    description: "Rich embed object (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Rich embed object (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            inputs: vec![
+                NodePort {
+                    name: "trigger".to_string(),
+                    display_name: "Trigger".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
                 },
             ],
-            inputs: vec!["trigger".to_string()],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let webhook_url = context.get_parameter("webhook_url")
-            .and_then(|v| v.as_string())
-            .ok_or("Webhook URL is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let webhook_url = context.input.get("webhook_url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Webhook URL is required"))?;
         
         let mut body = json!({});
         
-        if let Some(content) = context.get_parameter("content").and_then(|v| v.as_string()) {
+        if let Some(content) = context.input.get("content").and_then(|v| v.as_str()) {
             body["content"] = json!(content);
         }
         
-        if let Some(username) = context.get_parameter("username").and_then(|v| v.as_string()) {
+        if let Some(username) = context.input.get("username").and_then(|v| v.as_str()) {
             body["username"] = json!(username);
         }
         
-        if let Some(avatar_url) = context.get_parameter("avatar_url").and_then(|v| v.as_string()) {
+        if let Some(avatar_url) = context.input.get("avatar_url").and_then(|v| v.as_str()) {
             body["avatar_url"] = json!(avatar_url);
         }
         
-        if let Some(embed) = context.get_parameter("embed") {
+        if let Some(embed) = context.input.get("embed") {
             body["embeds"] = json!([embed]);
         }
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let response = client
-            .post(&webhook_url)
+            .post(webhook_url)
             .json(&body)
             .send()
-            .await?;
+            .await
+            .map_err(network_error)?;
 
         let status = response.status();
         let success = status.is_success();
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(json!({
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), json!({
             "success": success,
             "status": status.as_u16()
-        })));
+        }));
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -116,99 +170,132 @@ pub struct DiscordAlertBotNode;
 impl Node for DiscordAlertBotNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "discord_alert_bot".to_string(),
-            display_name: "Discord Alert Bot".to_string(),
+            id: "discord_alert_bot".to_string(),
+            name: "Discord Alert Bot".to_string(),
             description: "Advanced Discord bot for alerts with severity levels and formatting".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "webhook_url".to_string(),
                     display_name: "Webhook URL".to_string(),
-                    description: "Discord webhook URL".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Discord webhook URL".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "alert_type".to_string(),
                     display_name: "Alert Type".to_string(),
-                    description: "Type of alert".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Type of alert".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("info".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "title".to_string(),
                     display_name: "Alert Title".to_string(),
-                    description: "Title of the alert".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Title of the alert".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "message".to_string(),
                     display_name: "Alert Message".to_string(),
-                    description: "Detailed alert message".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Detailed alert message".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "source".to_string(),
                     display_name: "Alert Source".to_string(),
-                    description: "System or service that triggered the alert".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("System or service that triggered the alert".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("GhostFlow".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "metadata".to_string(),
                     display_name: "Metadata".to_string(),
-                    description: "Additional metadata (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Additional metadata (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "mention_role".to_string(),
                     display_name: "Mention Role ID".to_string(),
-                    description: "Role ID to mention for critical alerts".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Role ID to mention for critical alerts".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            inputs: vec![
+                NodePort {
+                    name: "trigger".to_string(),
+                    display_name: "Trigger".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
                 },
             ],
-            inputs: vec!["trigger".to_string()],
-            outputs: vec!["result".to_string()],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let webhook_url = context.get_parameter("webhook_url")
-            .and_then(|v| v.as_string())
-            .ok_or("Webhook URL is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let webhook_url = context.input.get("webhook_url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Webhook URL is required"))?;
         
-        let alert_type = context.get_parameter("alert_type")
-            .and_then(|v| v.as_string())
-            .unwrap_or("info".to_string());
+        let alert_type = context.input.get("alert_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("info");
         
-        let title = context.get_parameter("title")
-            .and_then(|v| v.as_string())
-            .ok_or("Alert title is required")?;
+        let title = context.input.get("title")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Alert title is required"))?;
         
-        let message = context.get_parameter("message")
-            .and_then(|v| v.as_string())
-            .ok_or("Alert message is required")?;
+        let message = context.input.get("message")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Alert message is required"))?;
         
-        let source = context.get_parameter("source")
-            .and_then(|v| v.as_string())
-            .unwrap_or("GhostFlow".to_string());
+        let source = context.input.get("source")
+            .and_then(|v| v.as_str())
+            .unwrap_or("GhostFlow");
 
-        let (color, emoji) = match alert_type.as_str() {
+        let (color, emoji) = match alert_type {
             "critical" => (0xFF0000, "🚨"),
             "error" => (0xFF6B6B, "❌"),
             "warning" => (0xFFA500, "⚠️"),
@@ -235,9 +322,9 @@ impl Node for DiscordAlertBotNode {
             })
         ];
 
-        if let Some(metadata) = context.get_parameter("metadata") {
-            if let Value::Object(obj) = metadata {
-                for (key, value) in obj.as_object().unwrap().iter() {
+        if let Some(metadata) = context.input.get("metadata") {
+            if let Some(obj) = metadata.as_object() {
+                for (key, value) in obj.iter() {
                     fields.push(json!({
                         "name": key,
                         "value": value.to_string(),
@@ -260,7 +347,7 @@ impl Node for DiscordAlertBotNode {
 
         let mut content = String::new();
         if alert_type == "critical" {
-            if let Some(role_id) = context.get_parameter("mention_role").and_then(|v| v.as_string()) {
+            if let Some(role_id) = context.input.get("mention_role").and_then(|v| v.as_str()) {
                 content = format!("<@&{}>", role_id);
             }
         }
@@ -278,24 +365,25 @@ impl Node for DiscordAlertBotNode {
             })
         };
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let response = client
-            .post(&webhook_url)
+            .post(webhook_url)
             .json(&body)
             .send()
-            .await?;
+            .await
+            .map_err(network_error)?;
 
         let status = response.status();
         let success = status.is_success();
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(json!({
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), json!({
             "success": success,
             "status": status.as_u16(),
             "alert_sent": success
-        })));
+        }));
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -306,104 +394,151 @@ pub struct DiscordChatBotNode;
 impl Node for DiscordChatBotNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "discord_chat_bot".to_string(),
-            display_name: "Discord Chat Bot".to_string(),
+            id: "discord_chat_bot".to_string(),
+            name: "Discord Chat Bot".to_string(),
             description: "Interactive Discord bot with conversation context and AI integration".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "bot_token".to_string(),
                     display_name: "Bot Token".to_string(),
-                    description: "Discord bot token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Discord bot token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "channel_id".to_string(),
                     display_name: "Channel ID".to_string(),
-                    description: "Discord channel ID to send message to".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Discord channel ID to send message to".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "message".to_string(),
                     display_name: "Message".to_string(),
-                    description: "Message to send or process".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Message to send or process".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Bot operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Bot operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("send_message".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "reply_to".to_string(),
                     display_name: "Reply To Message ID".to_string(),
-                    description: "Message ID to reply to".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Message ID to reply to".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "ai_enabled".to_string(),
                     display_name: "Enable AI Responses".to_string(),
-                    description: "Process messages with AI for intelligent responses".to_string(),
-                    parameter_type: ParameterType::Boolean,
-                    required: false,
+                    description: Some("Process messages with AI for intelligent responses".to_string()),
+                    param_type: ParameterType::Boolean,
                     default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "context".to_string(),
                     display_name: "Conversation Context".to_string(),
-                    description: "Previous conversation context for AI".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Previous conversation context for AI".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            inputs: vec![
+                NodePort {
+                    name: "trigger".to_string(),
+                    display_name: "Trigger".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "ai_response".to_string(),
+                    display_name: "Ai Response".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "message_id".to_string(),
+                    display_name: "Message Id".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
                 },
             ],
-            inputs: vec!["trigger".to_string(), "ai_response".to_string()],
-            outputs: vec!["result".to_string(), "message_id".to_string()],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let bot_token = context.get_parameter("bot_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Bot token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let bot_token = context.input.get("bot_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Bot token is required"))?;
         
-        let channel_id = context.get_parameter("channel_id")
-            .and_then(|v| v.as_string())
-            .ok_or("Channel ID is required")?;
+        let channel_id = context.input.get("channel_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Channel ID is required"))?;
         
-        let message = context.get_parameter("message")
-            .and_then(|v| v.as_string())
-            .ok_or("Message is required")?;
+        let message = context.input.get("message")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Message is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("send_message".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("send_message");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = "https://discord.com/api/v10";
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "send_message" => {
                 let mut body = json!({
                     "content": message
                 });
 
-                if let Some(reply_to) = context.get_parameter("reply_to").and_then(|v| v.as_string()) {
+                if let Some(reply_to) = context.input.get("reply_to").and_then(|v| v.as_str()) {
                     body["message_reference"] = json!({
                         "message_id": reply_to
                     });
@@ -415,9 +550,10 @@ impl Node for DiscordChatBotNode {
                     .header("Content-Type", "application/json")
                     .json(&body)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "get_messages" => {
@@ -426,9 +562,10 @@ impl Node for DiscordChatBotNode {
                     .header("Authorization", format!("Bot {}", bot_token))
                     .query(&[("limit", "50")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "create_thread" => {
@@ -444,23 +581,24 @@ impl Node for DiscordChatBotNode {
                     .header("Content-Type", "application/json")
                     .json(&body)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
         
         if let Some(message_id) = result.get("id").and_then(|v| v.as_str()) {
             outputs.insert("message_id".to_string(), Value::String(message_id.to_string()));
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
 }
\ No newline at end of file
@@ -1,8 +1,16 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use crate::http_util::{request_with_policy, shared_client, RequestPolicy};
+use ghostflow_core::{CircuitBreakerRegistry, GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    BinaryData, DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, MultiPart, SinglePart};
+use serde_json::{json, Value};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SMTPEmailNode;
@@ -11,168 +19,224 @@ pub struct SMTPEmailNode;
 impl Node for SMTPEmailNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "smtp_email".to_string(),
-            display_name: "SMTP Email".to_string(),
+            id: "smtp_email".to_string(),
+            name: "SMTP Email".to_string(),
             description: "Send emails via SMTP server".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "smtp_host".to_string(),
                     display_name: "SMTP Host".to_string(),
-                    description: "SMTP server hostname".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("SMTP server hostname".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "smtp_port".to_string(),
                     display_name: "SMTP Port".to_string(),
-                    description: "SMTP server port".to_string(),
-                    parameter_type: ParameterType::Number,
+                    description: Some("SMTP server port".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(587))),
                     required: false,
-                    default_value: Some(Value::Number(587.0)),
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "username".to_string(),
                     display_name: "Username".to_string(),
-                    description: "SMTP authentication username".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("SMTP authentication username".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "password".to_string(),
                     display_name: "Password".to_string(),
-                    description: "SMTP authentication password or app password".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("SMTP authentication password or app password".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "use_tls".to_string(),
                     display_name: "Use TLS".to_string(),
-                    description: "Enable TLS encryption".to_string(),
-                    parameter_type: ParameterType::Boolean,
-                    required: false,
+                    description: Some("Enable TLS encryption".to_string()),
+                    param_type: ParameterType::Boolean,
                     default_value: Some(Value::Bool(true)),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "from".to_string(),
                     display_name: "From".to_string(),
-                    description: "Sender email address".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Sender email address".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "from_name".to_string(),
                     display_name: "From Name".to_string(),
-                    description: "Sender display name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Sender display name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "to".to_string(),
                     display_name: "To".to_string(),
-                    description: "Recipient email addresses (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Recipient email addresses (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "cc".to_string(),
                     display_name: "CC".to_string(),
-                    description: "CC recipients (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("CC recipients (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "bcc".to_string(),
                     display_name: "BCC".to_string(),
-                    description: "BCC recipients (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("BCC recipients (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "subject".to_string(),
                     display_name: "Subject".to_string(),
-                    description: "Email subject line".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Email subject line".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "body".to_string(),
                     display_name: "Body".to_string(),
-                    description: "Email body content".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Email body content".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "body_type".to_string(),
                     display_name: "Body Type".to_string(),
-                    description: "Email body format".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("Email body format".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("html".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "attachments".to_string(),
+                    display_name: "Attachments".to_string(),
+                    description: Some("Binary values to attach; filename and content_type are taken from each value".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
-            inputs: vec![],
-            outputs: vec!["result".to_string()],
+            inputs: vec![
+                NodePort {
+                    name: "attachments".to_string(),
+                    display_name: "Attachments".to_string(),
+                    description: Some("Files to attach".to_string()),
+                    data_type: DataType::Any,
+                    required: false,
+                },
+            ],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let smtp_host = context.get_parameter("smtp_host")
-            .and_then(|v| v.as_string())
-            .ok_or("SMTP host is required")?;
-        
-        let smtp_port = context.get_parameter("smtp_port")
-            .and_then(|v| v.as_number())
-            .unwrap_or(587.0) as u16;
-        
-        let username = context.get_parameter("username")
-            .and_then(|v| v.as_string())
-            .ok_or("Username is required")?;
-        
-        let password = context.get_parameter("password")
-            .and_then(|v| v.as_string())
-            .ok_or("Password is required")?;
-        
-        let use_tls = context.get_parameter("use_tls")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(true);
-        
-        let from = context.get_parameter("from")
-            .and_then(|v| v.as_string())
-            .ok_or("From address is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    fn supports_connection_test(&self) -> bool {
+        true
+    }
+
+    /// Connect, say EHLO and log in, without sending anything.
+    async fn test_connection(&self, context: &ExecutionContext) -> Result<String> {
+        let mailer = smtp_transport(&context.input)?;
+        let host = context.input.get("smtp_host").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let connected = tokio::task::spawn_blocking(move || mailer.test_connection())
+            .await
+            .map_err(|e| GhostFlowError::InternalError { message: e.to_string() })?;
+        match connected {
+            Ok(true) => Ok(format!("Connected to SMTP server {}", host)),
+            Ok(false) => Err(network_error(format!("SMTP server {} did not respond", host))),
+            Err(e) if e.is_permanent() => Err(GhostFlowError::AuthenticationError {
+                message: format!("SMTP server {} refused the login: {}", host, e),
+            }),
+            Err(e) => Err(network_error(e)),
+        }
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let from = context.input.get("from")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("From address is required"))?;
         
-        let from_name = context.get_parameter("from_name")
-            .and_then(|v| v.as_string());
+        let from_name = context.input.get("from_name")
+            .and_then(|v| v.as_str());
         
-        let to = context.get_parameter("to")
-            .and_then(|v| v.as_string())
-            .ok_or("To address is required")?;
+        let to = context.input.get("to")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("To address is required"))?;
         
-        let subject = context.get_parameter("subject")
-            .and_then(|v| v.as_string())
-            .ok_or("Subject is required")?;
+        let subject = context.input.get("subject")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Subject is required"))?;
         
-        let body = context.get_parameter("body")
-            .and_then(|v| v.as_string())
-            .ok_or("Body is required")?;
+        let body = context.input.get("body")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Body is required"))?;
         
-        let body_type = context.get_parameter("body_type")
-            .and_then(|v| v.as_string())
-            .unwrap_or("html".to_string());
+        let body_type = context.input.get("body_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("html");
 
         // Build email message
         let mut email_builder = lettre::Message::builder()
@@ -187,40 +251,45 @@ impl Node for SMTPEmailNode {
             email_builder = email_builder.to(recipient.trim().parse().unwrap());
         }
 
-        if let Some(cc) = context.get_parameter("cc").and_then(|v| v.as_string()) {
+        if let Some(cc) = context.input.get("cc").and_then(|v| v.as_str()) {
             for recipient in cc.split(',') {
                 email_builder = email_builder.cc(recipient.trim().parse().unwrap());
             }
         }
 
-        if let Some(bcc) = context.get_parameter("bcc").and_then(|v| v.as_string()) {
+        if let Some(bcc) = context.input.get("bcc").and_then(|v| v.as_str()) {
             for recipient in bcc.split(',') {
                 email_builder = email_builder.bcc(recipient.trim().parse().unwrap());
             }
         }
 
-        let email = email_builder
-            .subject(subject)
-            .body(body)
-            .unwrap();
-
-        // Create SMTP transport
-        use lettre::{SmtpTransport, Transport, transport::smtp::authentication::Credentials};
-
-        let creds = Credentials::new(username, password);
-        
-        let mailer = if use_tls {
-            SmtpTransport::relay(&smtp_host)
-                .unwrap()
-                .port(smtp_port)
-                .credentials(creds)
-                .build()
+        let attachments = attachments(&context.input)?;
+        let email_builder = email_builder.subject(subject);
+        let email = if attachments.is_empty() {
+            email_builder.body(body.to_string())
         } else {
-            SmtpTransport::builder_dangerous(&smtp_host)
-                .port(smtp_port)
-                .credentials(creds)
-                .build()
-        };
+            let text = if body_type == "html" {
+                SinglePart::html(body.to_string())
+            } else {
+                SinglePart::plain(body.to_string())
+            };
+            let mut multipart = MultiPart::mixed().singlepart(text);
+            for (index, attachment) in attachments.into_iter().enumerate() {
+                let filename = attachment
+                    .filename
+                    .unwrap_or_else(|| format!("attachment-{}", index + 1));
+                let content_type = ContentType::parse(
+                    attachment.content_type.as_deref().unwrap_or("application/octet-stream"),
+                )
+                .map_err(|e| param_error(format!("Invalid content type for attachment '{}': {}", filename, e)))?;
+                multipart = multipart.singlepart(Attachment::new(filename).body(attachment.data, content_type));
+            }
+            email_builder.multipart(multipart)
+        }
+        .map_err(|e| param_error(format!("Failed to build email: {}", e)))?;
+
+        use lettre::Transport;
+        let mailer = smtp_transport(&context.input)?;
 
         // Send email
         let send_result = mailer.send(&email);
@@ -238,184 +307,245 @@ impl Node for SMTPEmailNode {
             }),
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SendGridNode;
+/// SMTP transport from the node's server and account settings.
+fn smtp_transport(input: &Value) -> Result<lettre::SmtpTransport> {
+    use lettre::{SmtpTransport, transport::smtp::authentication::Credentials};
+
+    let smtp_host = input.get("smtp_host")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("SMTP host is required"))?;
+
+    let smtp_port = input.get("smtp_port")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(587.0) as u16;
+
+    let username = input.get("username")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Username is required"))?;
+
+    let password = input.get("password")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Password is required"))?;
+
+    let use_tls = input.get("use_tls")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    let creds = Credentials::new(username.to_string(), password.to_string());
+
+    let mailer = if use_tls {
+        SmtpTransport::relay(smtp_host)
+            .map_err(|e| param_error(format!("Invalid SMTP host '{}': {}", smtp_host, e)))?
+            .port(smtp_port)
+            .credentials(creds)
+            .build()
+    } else {
+        SmtpTransport::builder_dangerous(smtp_host)
+            .port(smtp_port)
+            .credentials(creds)
+            .build()
+    };
+    Ok(mailer)
+}
+
+/// Decode the `attachments` input: a single binary value or an array of them.
+fn attachments(input: &Value) -> Result<Vec<BinaryData>> {
+    let items = match input.get("attachments") {
+        None | Some(Value::Null) => return Ok(Vec::new()),
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(item) => vec![item],
+    };
+    items
+        .into_iter()
+        .map(|item| {
+            BinaryData::from_value(item)
+                .ok_or_else(|| param_error("Attachments must be binary values ({\"__binary__\": \"<base64>\", ...})"))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct SendGridNode {
+    breakers: Arc<CircuitBreakerRegistry>,
+}
+
+impl SendGridNode {
+    pub fn new() -> Self {
+        Self {
+            breakers: Arc::new(CircuitBreakerRegistry::new()),
+        }
+    }
+
+    /// Back off from failing hosts using `breakers`
+    pub fn with_circuit_breakers(mut self, breakers: Arc<CircuitBreakerRegistry>) -> Self {
+        self.breakers = breakers;
+        self
+    }
+}
+
+impl Default for SendGridNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[async_trait]
 impl Node for SendGridNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "sendgrid_email".to_string(),
-            display_name: "SendGrid Email".to_string(),
+            id: "sendgrid_email".to_string(),
+            name: "SendGrid Email".to_string(),
             description: "Send emails via SendGrid API".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "api_key".to_string(),
                     display_name: "API Key".to_string(),
-                    description: "SendGrid API key".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("SendGrid API key".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "from".to_string(),
                     display_name: "From Email".to_string(),
-                    description: "Sender email address".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Sender email address".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "from_name".to_string(),
                     display_name: "From Name".to_string(),
-                    description: "Sender display name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Sender display name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "to".to_string(),
                     display_name: "To".to_string(),
-                    description: "Recipient email addresses (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Recipient email addresses (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "subject".to_string(),
                     display_name: "Subject".to_string(),
-                    description: "Email subject line".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Email subject line".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "content".to_string(),
                     display_name: "Content".to_string(),
-                    description: "Email content".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Email content".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "content_type".to_string(),
                     display_name: "Content Type".to_string(),
-                    description: "Email content type".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("Email content type".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("text/html".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "template_id".to_string(),
                     display_name: "Template ID".to_string(),
-                    description: "SendGrid template ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("SendGrid template ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "dynamic_template_data".to_string(),
                     display_name: "Template Data".to_string(),
-                    description: "Dynamic template data (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Dynamic template data (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "message_id".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "message_id".to_string(),
+                    display_name: "Message Id".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let api_key = context.get_parameter("api_key")
-            .and_then(|v| v.as_string())
-            .ok_or("API key is required")?;
-        
-        let from_email = context.get_parameter("from")
-            .and_then(|v| v.as_string())
-            .ok_or("From email is required")?;
-        
-        let from_name = context.get_parameter("from_name")
-            .and_then(|v| v.as_string());
-        
-        let to = context.get_parameter("to")
-            .and_then(|v| v.as_string())
-            .ok_or("To email is required")?;
-        
-        let subject = context.get_parameter("subject")
-            .and_then(|v| v.as_string())
-            .ok_or("Subject is required")?;
-        
-        let content = context.get_parameter("content")
-            .and_then(|v| v.as_string())
-            .ok_or("Content is required")?;
-        
-        let content_type = context.get_parameter("content_type")
-            .and_then(|v| v.as_string())
-            .unwrap_or("text/html".to_string());
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
 
-        let client = reqwest::Client::new();
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let api_key = context.input.get("api_key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("API key is required"))?;
         
-        // Build email payload
-        let mut email_payload = json!({
-            "personalizations": [{
-                "to": to.split(',').map(|email| json!({
-                    "email": email.trim()
-                })).collect::<Vec<_>>(),
-                "subject": subject
-            }],
-            "from": {
-                "email": from_email,
-                "name": from_name.unwrap_or(from_email.clone())
-            },
-            "content": [{
-                "type": content_type,
-                "value": content
-            }]
-        });
+        let email_payload = sendgrid_payload(&context)?;
+        let client = shared_client();
 
-        // Handle dynamic templates
-        if let Some(template_id) = context.get_parameter("template_id").and_then(|v| v.as_string()) {
-            email_payload["template_id"] = json!(template_id);
-            
-            if let Some(template_data) = context.get_parameter("dynamic_template_data") {
-                email_payload["personalizations"][0]["dynamic_template_data"] = template_data.clone();
-            }
-            
-            // Remove content when using templates
-            email_payload.as_object_mut().unwrap().remove("content");
-        }
-
-        let response = client
+        let request = client
             .post("https://api.sendgrid.com/v3/mail/send")
             .header("Authorization", format!("Bearer {}", api_key))
             .header("Content-Type", "application/json")
-            .json(&email_payload)
-            .send()
-            .await?;
+            .json(&email_payload);
+        let response = request_with_policy(request, &RequestPolicy::default(), &self.breakers).await?;
 
         let status = response.status();
         let success = status.is_success();
-        let response_text = response.text().await?;
-
-        let message_id = if success {
-            // Extract message ID from headers if available
-            Some("sg_message_id_placeholder".to_string())
-        } else {
-            None
-        };
+        let message_id = response
+            .headers()
+            .get("X-Message-Id")
+            .and_then(|v| v.to_str().ok())
+            .filter(|_| success)
+            .map(str::to_string);
+        let response_text = response.text().await.map_err(network_error)?;
 
         let result = json!({
             "success": success,
@@ -424,17 +554,79 @@ impl Node for SendGridNode {
             "message_id": message_id
         });
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
         
         if let Some(msg_id) = message_id {
             outputs.insert("message_id".to_string(), Value::String(msg_id));
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
 }
 
+/// The SendGrid v3 request body. The node's idempotency key rides along as a
+/// custom arg, so deliveries from a retried node can be matched up in the
+/// event webhook.
+fn sendgrid_payload(context: &ExecutionContext) -> Result<Value> {
+    let input = &context.input;
+    let from_email = input.get("from")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("From email is required"))?;
+    
+    let from_name = input.get("from_name")
+        .and_then(|v| v.as_str());
+    
+    let to = input.get("to")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("To email is required"))?;
+    
+    let subject = input.get("subject")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Subject is required"))?;
+    
+    let content = input.get("content")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Content is required"))?;
+    
+    let content_type = input.get("content_type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("text/html");
+
+    // Build email payload
+    let mut email_payload = json!({
+        "personalizations": [{
+            "to": to.split(',').map(|email| json!({
+                "email": email.trim()
+            })).collect::<Vec<_>>(),
+            "subject": subject
+        }],
+        "from": {
+            "email": from_email,
+            "name": from_name.unwrap_or(from_email)
+        },
+        "content": [{
+            "type": content_type,
+            "value": content
+        }]
+    });
+
+    // Handle dynamic templates
+    if let Some(template_id) = input.get("template_id").and_then(|v| v.as_str()) {
+        email_payload["template_id"] = json!(template_id);
+        
+        if let Some(template_data) = input.get("dynamic_template_data") {
+            email_payload["personalizations"][0]["dynamic_template_data"] = template_data.clone();
+        }
+        
+        // Remove content when using templates
+        email_payload.as_object_mut().unwrap().remove("content");
+    }
+
+    email_payload["custom_args"] = json!({ "ghostflow_idempotency_key": context.idempotency_key() });
+    Ok(email_payload)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MailgunNode;
 
@@ -442,178 +634,222 @@ pub struct MailgunNode;
 impl Node for MailgunNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "mailgun_email".to_string(),
-            display_name: "Mailgun Email".to_string(),
+            id: "mailgun_email".to_string(),
+            name: "Mailgun Email".to_string(),
             description: "Send emails via Mailgun API".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "api_key".to_string(),
                     display_name: "API Key".to_string(),
-                    description: "Mailgun API key".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Mailgun API key".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "domain".to_string(),
                     display_name: "Domain".to_string(),
-                    description: "Mailgun sending domain".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Mailgun sending domain".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "region".to_string(),
                     display_name: "Region".to_string(),
-                    description: "Mailgun region (us, eu)".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("Mailgun region (us, eu)".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("us".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "us", "label": "US"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "eu", "label": "EU"}"#).unwrap(),
+                    ]),
+                    validation: None,
                 },
                 NodeParameter {
                     name: "from".to_string(),
                     display_name: "From".to_string(),
-                    description: "Sender email address".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Sender email address".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "to".to_string(),
                     display_name: "To".to_string(),
-                    description: "Recipient email addresses (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Recipient email addresses (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "cc".to_string(),
                     display_name: "CC".to_string(),
-                    description: "CC recipients (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("CC recipients (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "bcc".to_string(),
                     display_name: "BCC".to_string(),
-                    description: "BCC recipients (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("BCC recipients (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "subject".to_string(),
                     display_name: "Subject".to_string(),
-                    description: "Email subject line".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Email subject line".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "text".to_string(),
                     display_name: "Text Content".to_string(),
-                    description: "Plain text email content".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Plain text email content".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "html".to_string(),
                     display_name: "HTML Content".to_string(),
-                    description: "HTML email content".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("HTML email content".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "tags".to_string(),
                     display_name: "Tags".to_string(),
-                    description: "Email tags for tracking (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Email tags for tracking (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "message_id".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "message_id".to_string(),
+                    display_name: "Message Id".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let api_key = context.get_parameter("api_key")
-            .and_then(|v| v.as_string())
-            .ok_or("API key is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let api_key = context.input.get("api_key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("API key is required"))?;
         
-        let domain = context.get_parameter("domain")
-            .and_then(|v| v.as_string())
-            .ok_or("Domain is required")?;
+        let domain = context.input.get("domain")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Domain is required"))?;
         
-        let region = context.get_parameter("region")
-            .and_then(|v| v.as_string())
-            .unwrap_or("us".to_string());
+        let region = context.input.get("region")
+            .and_then(|v| v.as_str())
+            .unwrap_or("us");
         
-        let from = context.get_parameter("from")
-            .and_then(|v| v.as_string())
-            .ok_or("From address is required")?;
+        let from = context.input.get("from")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("From address is required"))?;
         
-        let to = context.get_parameter("to")
-            .and_then(|v| v.as_string())
-            .ok_or("To address is required")?;
+        let to = context.input.get("to")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("To address is required"))?;
         
-        let subject = context.get_parameter("subject")
-            .and_then(|v| v.as_string())
-            .ok_or("Subject is required")?;
+        let subject = context.input.get("subject")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Subject is required"))?;
 
-        let base_url = match region.as_str() {
+        let base_url = match region {
             "eu" => "https://api.eu.mailgun.net/v3",
             _ => "https://api.mailgun.net/v3",
         };
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let mut form = vec![
             ("from", from),
             ("to", to),
             ("subject", subject),
         ];
 
-        if let Some(cc) = context.get_parameter("cc").and_then(|v| v.as_string()) {
+        if let Some(cc) = context.input.get("cc").and_then(|v| v.as_str()) {
             form.push(("cc", cc));
         }
         
-        if let Some(bcc) = context.get_parameter("bcc").and_then(|v| v.as_string()) {
+        if let Some(bcc) = context.input.get("bcc").and_then(|v| v.as_str()) {
             form.push(("bcc", bcc));
         }
         
-        if let Some(text) = context.get_parameter("text").and_then(|v| v.as_string()) {
+        if let Some(text) = context.input.get("text").and_then(|v| v.as_str()) {
             form.push(("text", text));
         }
         
-        if let Some(html) = context.get_parameter("html").and_then(|v| v.as_string()) {
+        if let Some(html) = context.input.get("html").and_then(|v| v.as_str()) {
             form.push(("html", html));
         }
         
-        if let Some(tags) = context.get_parameter("tags").and_then(|v| v.as_string()) {
+        if let Some(tags) = context.input.get("tags").and_then(|v| v.as_str()) {
             for tag in tags.split(',') {
-                form.push(("o:tag", tag.trim().to_string()));
+                form.push(("o:tag", tag.trim()));
             }
         }
 
         let response = client
             .post(&format!("{}/{}/messages", base_url, domain))
-            .basic_auth("api", Some(&api_key))
+            .basic_auth("api", Some(api_key))
             .form(&form)
             .send()
-            .await?;
+            .await
+            .map_err(network_error)?;
 
         let status = response.status();
         let success = status.is_success();
-        let response_data: serde_json::Value = response.json().await?;
+        let response_data: serde_json::Value = response.json().await.map_err(network_error)?;
 
         let message_id = response_data.get("id")
             .and_then(|id| id.as_str())
@@ -626,13 +862,317 @@ impl Node for MailgunNode {
             "message_id": message_id
         });
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
         
         if let Some(msg_id) = message_id {
             outputs.insert("message_id".to_string(), Value::String(msg_id));
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
+    }
+}
+
+/// How old a Mailgun webhook signature may be before it is treated as a replay
+const MAILGUN_DEFAULT_MAX_AGE_SECS: i64 = 900;
+
+/// Check the `signature` block Mailgun sends with every webhook: an
+/// HMAC-SHA256 of `timestamp` + `token` keyed with the webhook signing key.
+/// Signatures older than `max_age_secs` are rejected so a captured request
+/// cannot be replayed later.
+pub fn verify_mailgun_signature(
+    signing_key: &str,
+    signature: &Value,
+    max_age_secs: i64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    use hmac::{Hmac, Mac};
+
+    let invalid = |message: &str| GhostFlowError::AuthenticationError {
+        message: format!("Invalid Mailgun webhook signature: {}", message),
+    };
+    let field = |name: &str| {
+        signature
+            .get(name)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| invalid(&format!("missing {}", name)))
+    };
+    let timestamp = field("timestamp")?;
+    let token = field("token")?;
+    let digest = hex::decode(field("signature")?).map_err(|_| invalid("signature is not hex"))?;
+
+    let signed_at = timestamp.parse::<i64>().map_err(|_| invalid("timestamp is not a number"))?;
+    if (now.timestamp() - signed_at).abs() > max_age_secs {
+        return Err(invalid("timestamp is too old"));
+    }
+
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(signing_key.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.as_bytes());
+    mac.update(token.as_bytes());
+    mac.verify_slice(&digest).map_err(|_| invalid("digest does not match"))
+}
+
+/// Flatten Mailgun's `event-data` into the fields flows usually branch on.
+/// Permanent failures are reported as `bounced` and temporary ones as
+/// `failed`; other event names pass through unchanged.
+pub fn parse_mailgun_event(event_data: &Value) -> Result<Value> {
+    let event = event_data
+        .get("event")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Mailgun event data has no event name"))?;
+    let severity = event_data.get("severity").and_then(|v| v.as_str());
+    let normalized = match (event, severity) {
+        ("failed", Some("permanent")) => "bounced",
+        (other, _) => other,
+    };
+
+    let timestamp = event_data
+        .get("timestamp")
+        .and_then(|v| v.as_f64())
+        .and_then(|secs| chrono::DateTime::from_timestamp_millis((secs * 1000.0) as i64))
+        .map(|at| at.to_rfc3339());
+    let status = event_data.get("delivery-status");
+    let reason = status
+        .and_then(|s| s.get("description").or_else(|| s.get("message")))
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.is_empty())
+        .or_else(|| event_data.get("reason").and_then(|v| v.as_str()));
+
+    Ok(json!({
+        "event": normalized,
+        "mailgun_event": event,
+        "event_id": event_data.get("id"),
+        "timestamp": timestamp,
+        "recipient": event_data.get("recipient"),
+        "message_id": event_data.pointer("/message/headers/message-id"),
+        "subject": event_data.pointer("/message/headers/subject"),
+        "severity": severity,
+        "reason": reason,
+        "status_code": status.and_then(|s| s.get("code")),
+        "url": event_data.get("url"),
+        "tags": event_data.get("tags").cloned().unwrap_or_else(|| json!([])),
+        "user_variables": event_data.get("user-variables").cloned().unwrap_or_else(|| json!({})),
+    }))
+}
+
+/// Trigger for Mailgun event webhooks (delivered, opened, clicked, failed
+/// and so on). Verifies the request signature and emits the event in the
+/// shape produced by [`parse_mailgun_event`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MailgunWebhookNode;
+
+#[async_trait]
+impl Node for MailgunWebhookNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "mailgun_webhook".to_string(),
+            name: "Mailgun Webhook".to_string(),
+            description: "Start a flow from Mailgun delivery, open, click and bounce events".to_string(),
+            category: NodeCategory::Trigger,
+            version: "1.0.0".to_string(),
+            inputs: vec![],
+            outputs: vec![NodePort {
+                name: "event".to_string(),
+                display_name: "Event".to_string(),
+                description: Some("Normalized event: event, recipient, message_id, timestamp, reason, url".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            parameters: vec![
+                NodeParameter {
+                    name: "signing_key".to_string(),
+                    display_name: "Webhook Signing Key".to_string(),
+                    description: Some("HTTP webhook signing key from the Mailgun dashboard".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_age_secs".to_string(),
+                    display_name: "Max Signature Age (seconds)".to_string(),
+                    description: Some("Reject requests signed longer ago than this".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(json!(MAILGUN_DEFAULT_MAX_AGE_SECS)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("mail".to_string()),
+            color: Some("#f06b66".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let signing_key = context
+            .input
+            .get("signing_key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Webhook signing key is required"))?;
+        let max_age_secs = context
+            .input
+            .get("max_age_secs")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(MAILGUN_DEFAULT_MAX_AGE_SECS);
+        // Webhook requests arrive as { body, headers, raw_body }
+        let body = context.input.get("body").unwrap_or(&context.input);
+
+        let signature = body
+            .get("signature")
+            .ok_or_else(|| GhostFlowError::AuthenticationError {
+                message: "Mailgun webhook has no signature".to_string(),
+            })?;
+        verify_mailgun_signature(signing_key, signature, max_age_secs, chrono::Utc::now())?;
+
+        let event_data = body
+            .get("event-data")
+            .ok_or_else(|| param_error("Mailgun webhook has no event-data"))?;
+        let event = parse_mailgun_event(event_data)?;
+        Ok(json!({ "event": event }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
     }
-}
\ No newline at end of file
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+
+    #[test]
+    fn test_attachments_accept_single_or_list_of_binary_values() {
+        let pdf = BinaryData::new(b"%PDF-1.7".to_vec())
+            .with_content_type("application/pdf")
+            .with_filename("report.pdf");
+
+        let single = attachments(&json!({ "attachments": pdf.to_value() })).unwrap();
+        assert_eq!(single, vec![pdf.clone()]);
+
+        let list = attachments(&json!({ "attachments": [pdf.to_value(), pdf.to_value()] })).unwrap();
+        assert_eq!(list.len(), 2);
+
+        assert!(attachments(&json!({})).unwrap().is_empty());
+        assert!(attachments(&json!({ "attachments": ["JVBERi0xLjc="] })).is_err());
+    }
+
+    #[test]
+    fn test_sendgrid_payload_carries_the_same_key_across_retries() {
+        let first = context(json!({
+            "from": "soc@example.com",
+            "to": "oncall@example.com",
+            "subject": "Daily report",
+            "content": "<p>All quiet</p>",
+        }));
+        let retry = ExecutionContext {
+            attempt: 3,
+            ..first.clone()
+        };
+
+        let key = &sendgrid_payload(&first).unwrap()["custom_args"]["ghostflow_idempotency_key"];
+        assert_eq!(*key, json!(first.idempotency_key()));
+        assert_eq!(sendgrid_payload(&retry).unwrap()["custom_args"]["ghostflow_idempotency_key"], *key);
+
+        let other_node = ExecutionContext {
+            node_id: "send_summary".to_string(),
+            ..first.clone()
+        };
+        assert_ne!(sendgrid_payload(&other_node).unwrap()["custom_args"]["ghostflow_idempotency_key"], *key);
+    }
+
+    fn mailgun_signature(key: &str, timestamp: i64, token: &str) -> Value {
+        use hmac::{Hmac, Mac};
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(key.as_bytes()).unwrap();
+        mac.update(format!("{}{}", timestamp, token).as_bytes());
+        json!({
+            "timestamp": timestamp.to_string(),
+            "token": token,
+            "signature": hex::encode(mac.finalize().into_bytes()),
+        })
+    }
+
+    #[test]
+    fn test_mailgun_signature_verification() {
+        let now = chrono::Utc::now();
+        let signature = mailgun_signature("key-123", now.timestamp(), "a8ce0edb2dd8301dee6c2405235584e45aa91d1e9f979f3de0");
+
+        assert!(verify_mailgun_signature("key-123", &signature, 900, now).is_ok());
+        assert!(verify_mailgun_signature("key-456", &signature, 900, now).is_err());
+
+        let mut tampered = signature.clone();
+        tampered["token"] = json!("another-token");
+        assert!(verify_mailgun_signature("key-123", &tampered, 900, now).is_err());
+
+        let later = now + chrono::Duration::hours(1);
+        let err = verify_mailgun_signature("key-123", &signature, 900, later).unwrap_err();
+        assert!(err.to_string().contains("too old"), "{}", err);
+    }
+
+    #[test]
+    fn test_mailgun_events_are_normalized() {
+        let bounce = parse_mailgun_event(&json!({
+            "event": "failed",
+            "severity": "permanent",
+            "id": "G9Bn5sl1TC6nu79C8C0bwg",
+            "timestamp": 1521233195.375624,
+            "recipient": "alice@example.com",
+            "message": { "headers": { "message-id": "20130503182626.18666.16540@example.com", "subject": "Report" } },
+            "delivery-status": { "code": 550, "message": "", "description": "No such mailbox" },
+            "user-variables": { "ticket": "INC-42" },
+        }))
+        .unwrap();
+        assert_eq!(bounce["event"], "bounced");
+        assert_eq!(bounce["mailgun_event"], "failed");
+        assert_eq!(bounce["recipient"], "alice@example.com");
+        assert_eq!(bounce["message_id"], "20130503182626.18666.16540@example.com");
+        assert_eq!(bounce["reason"], "No such mailbox");
+        assert_eq!(bounce["status_code"], 550);
+        assert_eq!(bounce["user_variables"]["ticket"], "INC-42");
+        assert!(bounce["timestamp"].as_str().unwrap().starts_with("2018-03-16T"));
+
+        let click = parse_mailgun_event(&json!({
+            "event": "clicked",
+            "timestamp": 1521233195.0,
+            "recipient": "bob@example.com",
+            "url": "https://status.example.com",
+            "tags": ["weekly"],
+        }))
+        .unwrap();
+        assert_eq!(click["event"], "clicked");
+        assert_eq!(click["url"], "https://status.example.com");
+        assert_eq!(click["tags"], json!(["weekly"]));
+
+        let deferred = parse_mailgun_event(&json!({ "event": "failed", "severity": "temporary" })).unwrap();
+        assert_eq!(deferred["event"], "failed");
+        assert!(parse_mailgun_event(&json!({ "recipient": "x@example.com" })).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mailgun_webhook_rejects_unsigned_requests() {
+        let now = chrono::Utc::now();
+        let body = json!({
+            "signature": mailgun_signature("key-123", now.timestamp(), "token-1"),
+            "event-data": { "event": "opened", "recipient": "carol@example.com" },
+        });
+        let context = |signing_key: &str| context(json!({ "signing_key": signing_key, "body": body }));
+
+        let output = MailgunWebhookNode.execute(context("key-123")).await.unwrap();
+        assert_eq!(output["event"]["event"], "opened");
+
+        let err = MailgunWebhookNode.execute(context("wrong-key")).await.unwrap_err();
+        assert!(matches!(err, GhostFlowError::AuthenticationError { .. }), "{}", err);
+    }
+}
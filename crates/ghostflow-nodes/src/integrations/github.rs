@@ -0,0 +1,665 @@
+use super::{network_error, param_error, validate_required};
+use crate::http_util::shared_client;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::warn;
+
+const OPERATIONS: [&str; 6] = ["create_issue", "comment", "create_pr", "get_pr", "list_commits", "dispatch_workflow"];
+
+/// Rate-limit waits longer than this fail the node instead of stalling the flow
+const DEFAULT_MAX_RATE_LIMIT_WAIT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubNode;
+
+/// Rate-limit headers from the last GitHub response.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RateLimitState {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    /// Unix time at which the window resets
+    pub reset: Option<i64>,
+    pub used: Option<u64>,
+    pub resource: Option<String>,
+}
+
+impl RateLimitState {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let text = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        Self {
+            limit: text("x-ratelimit-limit").and_then(|v| v.parse().ok()),
+            remaining: text("x-ratelimit-remaining").and_then(|v| v.parse().ok()),
+            reset: text("x-ratelimit-reset").and_then(|v| v.parse().ok()),
+            used: text("x-ratelimit-used").and_then(|v| v.parse().ok()),
+            resource: text("x-ratelimit-resource").map(str::to_string),
+        }
+    }
+}
+
+/// How long to wait before retrying, if `status`/`headers` describe a
+/// primary or secondary rate limit. A 403 without rate-limit headers is a
+/// permissions problem and is not retried.
+fn rate_limit_wait(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+
+    if let Some(secs) = headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let state = RateLimitState::from_headers(headers);
+    if state.remaining == Some(0) {
+        let until_reset = state.reset.map_or(60, |reset| (reset - chrono::Utc::now().timestamp()).max(1));
+        return Some(Duration::from_secs(until_reset as u64));
+    }
+
+    // GitHub asks clients to back off at least a minute when it gives no hint
+    (status == StatusCode::TOO_MANY_REQUESTS).then(|| Duration::from_secs(60))
+}
+
+/// Send a GitHub API request, waiting out rate limits up to `max_retries`
+/// times as long as each wait is within `max_wait`.
+async fn send_github(
+    request: RequestBuilder,
+    max_retries: u32,
+    max_wait: Duration,
+) -> Result<(StatusCode, Value, RateLimitState)> {
+    let mut attempt = 0;
+    let mut current = request;
+
+    loop {
+        let retry = current.try_clone();
+        let response = current.send().await.map_err(network_error)?;
+        let status = response.status();
+        let headers = response.headers().clone();
+
+        match (rate_limit_wait(status, &headers), retry) {
+            (Some(wait), Some(retry)) if attempt < max_retries && wait <= max_wait => {
+                attempt += 1;
+                warn!(
+                    "GitHub rate limit hit (HTTP {}), retry {} of {} in {:?}",
+                    status, attempt, max_retries, wait
+                );
+                tokio::time::sleep(wait).await;
+                current = retry;
+            }
+            _ => {
+                let text = response.text().await.map_err(network_error)?;
+                let body = if text.is_empty() {
+                    Value::Null
+                } else {
+                    serde_json::from_str(&text).unwrap_or(Value::String(text))
+                };
+                return Ok((status, body, RateLimitState::from_headers(&headers)));
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct AppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+/// Bearer token for the request: a personal access token (or an already
+/// minted installation token) as given, or for `app` auth a fresh
+/// installation token exchanged for a JWT signed with the app's private key.
+async fn access_token(client: &reqwest::Client, api_base: &str, params: &Value) -> Result<String> {
+    let text = |key: &str| params.get(key).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+
+    match params.get("auth_type").and_then(|v| v.as_str()).unwrap_or("token") {
+        "token" => text("token")
+            .map(str::to_string)
+            .ok_or_else(|| param_error("GitHub token is required")),
+        "app" => {
+            let app_id = params
+                .get("app_id")
+                .map(|v| v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string()))
+                .filter(|s| !s.is_empty() && s != "null")
+                .ok_or_else(|| param_error("GitHub App ID is required for app authentication"))?;
+            let private_key = text("private_key")
+                .ok_or_else(|| param_error("GitHub App private key is required for app authentication"))?;
+            let installation_id = params
+                .get("installation_id")
+                .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()))
+                .ok_or_else(|| param_error("GitHub App installation ID is required for app authentication"))?;
+
+            // Backdate iat to allow for clock drift; GitHub caps exp at 10 minutes
+            let now = chrono::Utc::now().timestamp();
+            let claims = AppClaims { iat: now - 60, exp: now + 540, iss: app_id };
+            let key = jsonwebtoken::EncodingKey::from_rsa_pem(private_key.as_bytes())
+                .map_err(|e| param_error(format!("Invalid GitHub App private key: {}", e)))?;
+            let jwt = jsonwebtoken::encode(&jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256), &claims, &key)
+                .map_err(|e| param_error(format!("Failed to sign GitHub App JWT: {}", e)))?;
+
+            let request = github_request(
+                client.post(format!("{}/app/installations/{}/access_tokens", api_base, installation_id)),
+                &jwt,
+            );
+            let (status, body, _) = send_github(request, 0, Duration::ZERO).await?;
+            if !status.is_success() {
+                return Err(GhostFlowError::AuthenticationError {
+                    message: format!("GitHub App token exchange failed (HTTP {}): {}", status, body),
+                });
+            }
+            body.get("token")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .ok_or_else(|| GhostFlowError::AuthenticationError {
+                    message: "GitHub App token exchange returned no token".to_string(),
+                })
+        }
+        other => Err(param_error(format!("Unsupported GitHub auth type: {}", other))),
+    }
+}
+
+fn github_request(request: RequestBuilder, token: &str) -> RequestBuilder {
+    request
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .header("User-Agent", "ghostflow")
+}
+
+fn required_str<'a>(params: &'a Value, key: &str, operation: &str) -> Result<&'a str> {
+    params
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| param_error(format!("'{}' is required for the {} operation", key, operation)))
+}
+
+fn required_number(params: &Value, key: &str, operation: &str) -> Result<u64> {
+    params
+        .get(key)
+        .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()))
+        .ok_or_else(|| param_error(format!("'{}' is required for the {} operation", key, operation)))
+}
+
+#[async_trait]
+impl Node for GitHubNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "github".to_string(),
+            name: "GitHub".to_string(),
+            description: "Work with GitHub issues, pull requests, commits and Actions workflows".to_string(),
+            category: NodeCategory::Integration,
+            version: "1.0.0".to_string(),
+            parameters: vec![
+                NodeParameter {
+                    name: "operation".to_string(),
+                    display_name: "Operation".to_string(),
+                    description: Some("GitHub operation to perform".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("create_issue".to_string())),
+                    required: true,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "create_issue", "label": "Create Issue"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "comment", "label": "Comment on Issue/PR"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "create_pr", "label": "Create Pull Request"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "get_pr", "label": "Get Pull Request"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "list_commits", "label": "List Commits"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "dispatch_workflow", "label": "Dispatch Workflow"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "auth_type".to_string(),
+                    display_name: "Authentication".to_string(),
+                    description: Some("Personal access token or GitHub App installation".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("token".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "token", "label": "Access Token"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "app", "label": "GitHub App"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "token".to_string(),
+                    display_name: "Token".to_string(),
+                    description: Some("Personal access token or installation token".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "app_id".to_string(),
+                    display_name: "App ID".to_string(),
+                    description: Some("GitHub App ID".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "installation_id".to_string(),
+                    display_name: "Installation ID".to_string(),
+                    description: Some("GitHub App installation ID".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "private_key".to_string(),
+                    display_name: "Private Key".to_string(),
+                    description: Some("GitHub App private key (PEM)".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "base_url".to_string(),
+                    display_name: "API URL".to_string(),
+                    description: Some("API root; change for GitHub Enterprise Server".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("https://api.github.com".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "owner".to_string(),
+                    display_name: "Owner".to_string(),
+                    description: Some("Repository owner (user or organisation)".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "repo".to_string(),
+                    display_name: "Repository".to_string(),
+                    description: Some("Repository name".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "number".to_string(),
+                    display_name: "Issue/PR Number".to_string(),
+                    description: Some("Issue or pull request number for comment and get_pr".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "title".to_string(),
+                    display_name: "Title".to_string(),
+                    description: Some("Issue or pull request title".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "body".to_string(),
+                    display_name: "Body".to_string(),
+                    description: Some("Issue, pull request or comment text (Markdown)".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "labels".to_string(),
+                    display_name: "Labels".to_string(),
+                    description: Some("Labels for a new issue".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "assignees".to_string(),
+                    display_name: "Assignees".to_string(),
+                    description: Some("Logins to assign to a new issue".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "head".to_string(),
+                    display_name: "Head Branch".to_string(),
+                    description: Some("Branch with the changes for create_pr".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "base".to_string(),
+                    display_name: "Base Branch".to_string(),
+                    description: Some("Branch to merge into for create_pr".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("main".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "draft".to_string(),
+                    display_name: "Draft".to_string(),
+                    description: Some("Open the pull request as a draft".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "ref".to_string(),
+                    display_name: "Ref".to_string(),
+                    description: Some("Branch or SHA for list_commits and dispatch_workflow".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "per_page".to_string(),
+                    display_name: "Per Page".to_string(),
+                    description: Some("Commits to return (max 100)".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(30)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "workflow_id".to_string(),
+                    display_name: "Workflow".to_string(),
+                    description: Some("Workflow file name (e.g. deploy.yml) or ID".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "inputs".to_string(),
+                    display_name: "Workflow Inputs".to_string(),
+                    description: Some("Inputs for workflow_dispatch".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_retries".to_string(),
+                    display_name: "Rate Limit Retries".to_string(),
+                    description: Some("Times to wait out a rate limit before failing".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(2)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_wait".to_string(),
+                    display_name: "Max Rate Limit Wait (seconds)".to_string(),
+                    description: Some("Fail instead of waiting longer than this for a rate limit".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(DEFAULT_MAX_RATE_LIMIT_WAIT_SECS)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            inputs: vec![],
+            outputs: vec![NodePort {
+                name: "result".to_string(),
+                display_name: "Result".to_string(),
+                description: Some("Parsed response plus rate-limit state".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            icon: Some("github".to_string()),
+            color: Some("#24292f".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)?;
+
+        let operation = context.input.get("operation").and_then(|v| v.as_str()).unwrap_or("create_issue");
+        if !OPERATIONS.contains(&operation) {
+            return Err(param_error(format!("Unsupported GitHub operation: {}", operation)));
+        }
+        Ok(())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let operation = params.get("operation").and_then(|v| v.as_str()).unwrap_or("create_issue");
+        let api_base = params
+            .get("base_url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("https://api.github.com")
+            .trim_end_matches('/');
+        let owner = required_str(params, "owner", operation)?;
+        let repo = required_str(params, "repo", operation)?;
+        let max_retries = params.get("max_retries").and_then(|v| v.as_u64()).unwrap_or(2) as u32;
+        let max_wait = Duration::from_secs(
+            params
+                .get("max_wait")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_MAX_RATE_LIMIT_WAIT_SECS),
+        );
+
+        let client = shared_client();
+        let token = access_token(client, api_base, params).await?;
+        let repo_url = format!("{}/repos/{}/{}", api_base, owner, repo);
+
+        let request = match operation {
+            "create_issue" => {
+                let mut issue = json!({ "title": required_str(params, "title", operation)? });
+                for key in ["body", "labels", "assignees"] {
+                    if let Some(value) = params.get(key).filter(|v| !v.is_null()) {
+                        issue[key] = value.clone();
+                    }
+                }
+                client.post(format!("{}/issues", repo_url)).json(&issue)
+            }
+            "comment" => {
+                let number = required_number(params, "number", operation)?;
+                let body = required_str(params, "body", operation)?;
+                client
+                    .post(format!("{}/issues/{}/comments", repo_url, number))
+                    .json(&json!({ "body": body }))
+            }
+            "create_pr" => {
+                let pr = json!({
+                    "title": required_str(params, "title", operation)?,
+                    "head": required_str(params, "head", operation)?,
+                    "base": params.get("base").and_then(|v| v.as_str()).unwrap_or("main"),
+                    "body": params.get("body").and_then(|v| v.as_str()),
+                    "draft": params.get("draft").and_then(|v| v.as_bool()).unwrap_or(false),
+                });
+                client.post(format!("{}/pulls", repo_url)).json(&pr)
+            }
+            "get_pr" => {
+                let number = required_number(params, "number", operation)?;
+                client.get(format!("{}/pulls/{}", repo_url, number))
+            }
+            "list_commits" => {
+                let per_page = params.get("per_page").and_then(|v| v.as_u64()).unwrap_or(30).min(100);
+                let mut request = client
+                    .get(format!("{}/commits", repo_url))
+                    .query(&[("per_page", per_page.to_string())]);
+                if let Some(git_ref) = params.get("ref").and_then(|v| v.as_str()) {
+                    request = request.query(&[("sha", git_ref)]);
+                }
+                request
+            }
+            "dispatch_workflow" => {
+                let workflow = required_str(params, "workflow_id", operation)?;
+                let dispatch = json!({
+                    "ref": required_str(params, "ref", operation)?,
+                    "inputs": params.get("inputs").filter(|v| !v.is_null()).cloned().unwrap_or_else(|| json!({})),
+                });
+                client
+                    .post(format!("{}/actions/workflows/{}/dispatches", repo_url, urlencoding::encode(workflow)))
+                    .json(&dispatch)
+            }
+            other => return Err(param_error(format!("Unsupported GitHub operation: {}", other))),
+        };
+
+        let (status, data, rate_limit) = send_github(github_request(request, &token), max_retries, max_wait).await?;
+
+        if !status.is_success() {
+            let message = data
+                .get("message")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| data.to_string());
+            return Err(GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: format!("GitHub {} failed (HTTP {}): {}", operation, status.as_u16(), message),
+            });
+        }
+
+        Ok(json!({
+            "operation": operation,
+            "status": status.as_u16(),
+            "data": data,
+            "rate_limit": rate_limit,
+        }))
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn issue_params(server: &MockServer) -> Value {
+        json!({
+            "operation": "create_issue",
+            "token": "ghp_test",
+            "base_url": server.uri(),
+            "owner": "ghostkellz",
+            "repo": "ghostflow",
+            "title": "Nightly backup failed",
+            "labels": ["ops"],
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_issue_returns_data_and_rate_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/ghostkellz/ghostflow/issues"))
+            .and(header("authorization", "Bearer ghp_test"))
+            .and(header("x-github-api-version", "2022-11-28"))
+            .and(body_partial_json(json!({ "title": "Nightly backup failed", "labels": ["ops"] })))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .insert_header("x-ratelimit-limit", "5000")
+                    .insert_header("x-ratelimit-remaining", "4999")
+                    .insert_header("x-ratelimit-reset", "1760000000")
+                    .insert_header("x-ratelimit-used", "1")
+                    .insert_header("x-ratelimit-resource", "core")
+                    .set_body_json(json!({ "number": 42, "html_url": "https://github.com/ghostkellz/ghostflow/issues/42" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = GitHubNode.execute(context(issue_params(&server))).await.unwrap();
+        assert_eq!(result["status"], 201);
+        assert_eq!(result["data"]["number"], 42);
+        assert_eq!(result["rate_limit"]["remaining"], 4999);
+        assert_eq!(result["rate_limit"]["resource"], "core");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_is_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/ghostkellz/ghostflow/issues"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("x-ratelimit-remaining", "0")
+                    .insert_header("retry-after", "0")
+                    .set_body_json(json!({ "message": "API rate limit exceeded" })),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/ghostkellz/ghostflow/issues"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .insert_header("x-ratelimit-remaining", "4999")
+                    .set_body_json(json!({ "number": 43 })),
+            )
+            .mount(&server)
+            .await;
+
+        let result = GitHubNode.execute(context(issue_params(&server))).await.unwrap();
+        assert_eq!(result["data"]["number"], 43);
+        assert_eq!(result["rate_limit"]["remaining"], 4999);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_forbidden_without_rate_limit_is_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("x-ratelimit-remaining", "4990")
+                    .set_body_json(json!({ "message": "Resource not accessible by integration" })),
+            )
+            .mount(&server)
+            .await;
+
+        let error = GitHubNode.execute(context(issue_params(&server))).await.unwrap_err();
+        assert!(error.to_string().contains("Resource not accessible"), "{}", error);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+}
@@ -1,8 +1,13 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use crate::http_util::shared_client;
+use ghostflow_core::{Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitLabProjectNode;
@@ -11,155 +16,182 @@ pub struct GitLabProjectNode;
 impl Node for GitLabProjectNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "gitlab_project".to_string(),
-            display_name: "GitLab Project".to_string(),
+            id: "gitlab_project".to_string(),
+            name: "GitLab Project".to_string(),
             description: "Manage GitLab projects and repositories".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "base_url".to_string(),
                     display_name: "GitLab URL".to_string(),
-                    description: "GitLab instance URL".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("GitLab instance URL".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("https://gitlab.com".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "access_token".to_string(),
                     display_name: "Access Token".to_string(),
-                    description: "GitLab personal access token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("GitLab personal access token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "GitLab operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("GitLab operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("list_projects".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "project_id".to_string(),
                     display_name: "Project ID".to_string(),
-                    description: "GitLab project ID or path".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("GitLab project ID or path".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "branch".to_string(),
                     display_name: "Branch".to_string(),
-                    description: "Git branch name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Git branch name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("main".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "commit_message".to_string(),
                     display_name: "Commit Message".to_string(),
-                    description: "Commit message for file operations".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Commit message for file operations".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let base_url = context.get_parameter("base_url")
-            .and_then(|v| v.as_string())
-            .unwrap_or("https://gitlab.com".to_string());
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let base_url = context.input.get("base_url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("https://gitlab.com");
         
-        let access_token = context.get_parameter("access_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Access token is required")?;
+        let access_token = context.input.get("access_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Access token is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("list_projects".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list_projects");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let api_base = format!("{}/api/v4", base_url);
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "list_projects" => {
                 let response = client
                     .get(&format!("{}/projects", api_base))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("membership", "true"), ("per_page", "50")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 json!({ "projects": data })
             },
             "get_project" => {
-                let project_id = context.get_parameter("project_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Project ID is required for get project operation")?;
+                let project_id = context.input.get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Project ID is required for get project operation"))?;
 
                 let encoded_project_id = urlencoding::encode(&project_id);
                 let response = client
                     .get(&format!("{}/projects/{}", api_base, encoded_project_id))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "list_branches" => {
-                let project_id = context.get_parameter("project_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Project ID is required for list branches operation")?;
+                let project_id = context.input.get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Project ID is required for list branches operation"))?;
 
                 let encoded_project_id = urlencoding::encode(&project_id);
                 let response = client
                     .get(&format!("{}/projects/{}/repository/branches", api_base, encoded_project_id))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 json!({ "branches": data })
             },
             "list_commits" => {
-                let project_id = context.get_parameter("project_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Project ID is required for list commits operation")?;
+                let project_id = context.input.get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Project ID is required for list commits operation"))?;
 
-                let branch = context.get_parameter("branch")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("main".to_string());
+                let branch = context.input.get("branch")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("main");
 
                 let encoded_project_id = urlencoding::encode(&project_id);
                 let response = client
                     .get(&format!("{}/projects/{}/repository/commits", api_base, encoded_project_id))
                     .header("Authorization", format!("Bearer {}", access_token))
-                    .query(&[("ref_name", &branch), ("per_page", "20")])
+                    .query(&[("ref_name", branch), ("per_page", "20")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 json!({ "commits": data })
             },
             "trigger_pipeline" => {
-                let project_id = context.get_parameter("project_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Project ID is required for trigger pipeline operation")?;
+                let project_id = context.input.get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Project ID is required for trigger pipeline operation"))?;
 
-                let branch = context.get_parameter("branch")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("main".to_string());
+                let branch = context.input.get("branch")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("main");
 
                 let encoded_project_id = urlencoding::encode(&project_id);
                 let response = client
@@ -169,19 +201,20 @@ impl Node for GitLabProjectNode {
                         "ref": branch
                     }))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -192,125 +225,153 @@ pub struct GitLabIssueNode;
 impl Node for GitLabIssueNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "gitlab_issue".to_string(),
-            display_name: "GitLab Issues".to_string(),
+            id: "gitlab_issue".to_string(),
+            name: "GitLab Issues".to_string(),
             description: "Manage GitLab issues and merge requests".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "base_url".to_string(),
                     display_name: "GitLab URL".to_string(),
-                    description: "GitLab instance URL".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("GitLab instance URL".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("https://gitlab.com".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "access_token".to_string(),
                     display_name: "Access Token".to_string(),
-                    description: "GitLab personal access token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("GitLab personal access token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Issue operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Issue operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("list_issues".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "project_id".to_string(),
                     display_name: "Project ID".to_string(),
-                    description: "GitLab project ID or path".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("GitLab project ID or path".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "issue_id".to_string(),
                     display_name: "Issue ID".to_string(),
-                    description: "Issue internal ID".to_string(),
-                    parameter_type: ParameterType::Number,
-                    required: false,
+                    description: Some("Issue internal ID".to_string()),
+                    param_type: ParameterType::Number,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "title".to_string(),
                     display_name: "Title".to_string(),
-                    description: "Issue or MR title".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Issue or MR title".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "description".to_string(),
                     display_name: "Description".to_string(),
-                    description: "Issue or MR description".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Issue or MR description".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "labels".to_string(),
                     display_name: "Labels".to_string(),
-                    description: "Comma-separated list of labels".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Comma-separated list of labels".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let base_url = context.get_parameter("base_url")
-            .and_then(|v| v.as_string())
-            .unwrap_or("https://gitlab.com".to_string());
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let base_url = context.input.get("base_url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("https://gitlab.com");
         
-        let access_token = context.get_parameter("access_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Access token is required")?;
+        let access_token = context.input.get("access_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Access token is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("list_issues".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list_issues");
         
-        let project_id = context.get_parameter("project_id")
-            .and_then(|v| v.as_string())
-            .ok_or("Project ID is required")?;
+        let project_id = context.input.get("project_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Project ID is required"))?;
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let api_base = format!("{}/api/v4", base_url);
         let encoded_project_id = urlencoding::encode(&project_id);
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "list_issues" => {
                 let response = client
                     .get(&format!("{}/projects/{}/issues", api_base, encoded_project_id))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("state", "opened"), ("per_page", "50")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 json!({ "issues": data })
             },
             "create_issue" => {
-                let title = context.get_parameter("title")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Title is required for create issue operation")?;
+                let title = context.input.get("title")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Title is required for create issue operation"))?;
                 
-                let description = context.get_parameter("description")
-                    .and_then(|v| v.as_string())
+                let description = context.input.get("description")
+                    .and_then(|v| v.as_str())
                     .unwrap_or_default();
 
                 let mut body = json!({
@@ -318,7 +379,7 @@ impl Node for GitLabIssueNode {
                     "description": description
                 });
 
-                if let Some(labels) = context.get_parameter("labels").and_then(|v| v.as_string()) {
+                if let Some(labels) = context.input.get("labels").and_then(|v| v.as_str()) {
                     let label_list: Vec<&str> = labels.split(',').map(|l| l.trim()).collect();
                     body["labels"] = json!(label_list);
                 }
@@ -328,25 +389,26 @@ impl Node for GitLabIssueNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .json(&body)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "update_issue" => {
-                let issue_id = context.get_parameter("issue_id")
-                    .and_then(|v| v.as_number())
-                    .ok_or("Issue ID is required for update operation")? as u32;
+                let issue_id = context.input.get("issue_id")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("Issue ID is required for update operation"))? as u32;
 
                 let mut body = json!({});
 
-                if let Some(title) = context.get_parameter("title").and_then(|v| v.as_string()) {
+                if let Some(title) = context.input.get("title").and_then(|v| v.as_str()) {
                     body["title"] = json!(title);
                 }
-                if let Some(description) = context.get_parameter("description").and_then(|v| v.as_string()) {
+                if let Some(description) = context.input.get("description").and_then(|v| v.as_str()) {
                     body["description"] = json!(description);
                 }
-                if let Some(labels) = context.get_parameter("labels").and_then(|v| v.as_string()) {
+                if let Some(labels) = context.input.get("labels").and_then(|v| v.as_str()) {
                     let label_list: Vec<&str> = labels.split(',').map(|l| l.trim()).collect();
                     body["labels"] = json!(label_list);
                 }
@@ -356,15 +418,16 @@ impl Node for GitLabIssueNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .json(&body)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "close_issue" => {
-                let issue_id = context.get_parameter("issue_id")
-                    .and_then(|v| v.as_number())
-                    .ok_or("Issue ID is required for close operation")? as u32;
+                let issue_id = context.input.get("issue_id")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("Issue ID is required for close operation"))? as u32;
 
                 let response = client
                     .put(&format!("{}/projects/{}/issues/{}", api_base, encoded_project_id, issue_id))
@@ -373,18 +436,19 @@ impl Node for GitLabIssueNode {
                         "state_event": "close"
                     }))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
     }
 }
\ No newline at end of file
@@ -1,8 +1,13 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use crate::http_util::shared_client;
+use ghostflow_core::{Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GoogleSheetsNode;
@@ -11,131 +16,194 @@ pub struct GoogleSheetsNode;
 impl Node for GoogleSheetsNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "google_sheets".to_string(),
-            display_name: "Google Sheets".to_string(),
+            id: "google_sheets".to_string(),
+            name: "Google Sheets".to_string(),
             description: "Read from and write to Google Sheets".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "access_token".to_string(),
                     display_name: "Access Token".to_string(),
-                    description: "Google OAuth2 access token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Google OAuth2 access token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "spreadsheet_id".to_string(),
                     display_name: "Spreadsheet ID".to_string(),
-                    description: "Google Sheets spreadsheet ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Google Sheets spreadsheet ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Sheets operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Sheets operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("read".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "range".to_string(),
                     display_name: "Range".to_string(),
-                    description: "Cell range (e.g., A1:D10, Sheet1!A:Z)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Cell range (e.g., A1:D10, Sheet1!A:Z)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("A:Z".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "sheet_name".to_string(),
                     display_name: "Sheet Name".to_string(),
-                    description: "Name of the sheet/tab".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Name of the sheet/tab".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("Sheet1".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "values".to_string(),
                     display_name: "Values".to_string(),
-                    description: "Data to write (JSON array of arrays)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Data to write (JSON array of arrays)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "value_input_option".to_string(),
                     display_name: "Value Input Option".to_string(),
-                    description: "How input data should be interpreted".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("How input data should be interpreted".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("USER_ENTERED".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "include_headers".to_string(),
                     display_name: "Include Headers".to_string(),
-                    description: "Include first row as headers in output".to_string(),
-                    parameter_type: ParameterType::Boolean,
-                    required: false,
+                    description: Some("Include first row as headers in output".to_string()),
+                    param_type: ParameterType::Boolean,
                     default_value: Some(Value::Bool(true)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "key_column".to_string(),
+                    display_name: "Key Column".to_string(),
+                    description: Some("Header of the column used to match rows for upsert".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "data".to_string(),
+                    display_name: "Row Data".to_string(),
+                    description: Some("Object mapping column headers to values for upsert".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "data".to_string(), "headers".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "data".to_string(),
+                    display_name: "Data".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "headers".to_string(),
+                    display_name: "Headers".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let access_token = context.get_parameter("access_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Access token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let access_token = context.input.get("access_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Access token is required"))?;
         
-        let spreadsheet_id = context.get_parameter("spreadsheet_id")
-            .and_then(|v| v.as_string())
-            .ok_or("Spreadsheet ID is required")?;
+        let spreadsheet_id = context.input.get("spreadsheet_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Spreadsheet ID is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("read".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("read");
         
-        let sheet_name = context.get_parameter("sheet_name")
-            .and_then(|v| v.as_string())
-            .unwrap_or("Sheet1".to_string());
+        let sheet_name = context.input.get("sheet_name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Sheet1");
         
-        let range = context.get_parameter("range")
-            .and_then(|v| v.as_string())
-            .unwrap_or("A:Z".to_string());
+        let range = context.input.get("range")
+            .and_then(|v| v.as_str())
+            .unwrap_or("A:Z");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = "https://sheets.googleapis.com/v4/spreadsheets";
 
         let full_range = if range.contains('!') {
-            range
+            range.to_string()
         } else {
             format!("{}!{}", sheet_name, range)
         };
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "read" => {
                 let encoded_range = urlencoding::encode(&full_range);
                 let response = client
                     .get(&format!("{}/{}/values/{}", base_url, spreadsheet_id, encoded_range))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 
-                let include_headers = context.get_parameter("include_headers")
+                let include_headers = context.input.get("include_headers")
                     .and_then(|v| v.as_bool())
                     .unwrap_or(true);
 
-                let values = data["values"].as_array().unwrap_or(&vec![]);
+                let empty = Vec::new();
+                let values = data["values"].as_array().unwrap_or(&empty);
                 
                 if include_headers && !values.is_empty() {
                     let headers = &values[0];
@@ -158,50 +226,52 @@ impl Node for GoogleSheetsNode {
                 }
             },
             "write" => {
-                let values = context.get_parameter("values")
-                    .ok_or("Values are required for write operation")?;
+                let values = context.input.get("values")
+                    .ok_or_else(|| param_error("Values are required for write operation"))?;
                 
-                let value_input_option = context.get_parameter("value_input_option")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("USER_ENTERED".to_string());
+                let value_input_option = context.input.get("value_input_option")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("USER_ENTERED");
 
                 let encoded_range = urlencoding::encode(&full_range);
                 let response = client
                     .put(&format!("{}/{}/values/{}", base_url, spreadsheet_id, encoded_range))
                     .header("Authorization", format!("Bearer {}", access_token))
-                    .query(&[("valueInputOption", &value_input_option)])
+                    .query(&[("valueInputOption", value_input_option)])
                     .json(&json!({
                         "values": values
                     }))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "append" => {
-                let values = context.get_parameter("values")
-                    .ok_or("Values are required for append operation")?;
+                let values = context.input.get("values")
+                    .ok_or_else(|| param_error("Values are required for append operation"))?;
                 
-                let value_input_option = context.get_parameter("value_input_option")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("USER_ENTERED".to_string());
+                let value_input_option = context.input.get("value_input_option")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("USER_ENTERED");
 
                 let encoded_range = urlencoding::encode(&full_range);
                 let response = client
                     .post(&format!("{}/{}/values/{}:append", base_url, spreadsheet_id, encoded_range))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[
-                        ("valueInputOption", value_input_option.as_str()),
+                        ("valueInputOption", value_input_option),
                         ("insertDataOption", "INSERT_ROWS")
                     ])
                     .json(&json!({
                         "values": values
                     }))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "clear" => {
@@ -210,29 +280,55 @@ impl Node for GoogleSheetsNode {
                     .post(&format!("{}/{}/values/{}:clear", base_url, spreadsheet_id, encoded_range))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
+            "upsert" => {
+                let key_column = context.input.get("key_column")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Key column is required for upsert operation"))?;
+
+                let data = context.input.get("data")
+                    .and_then(|v| v.as_object())
+                    .ok_or_else(|| param_error("Data object is required for upsert operation"))?;
+
+                let value_input_option = context.input.get("value_input_option")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("USER_ENTERED");
+
+                upsert_row(
+                    client,
+                    base_url,
+                    access_token,
+                    spreadsheet_id,
+                    sheet_name,
+                    key_column,
+                    data,
+                    value_input_option,
+                ).await?
+            },
             "get_info" => {
                 let response = client
                     .get(&format!("{}/{}", base_url, spreadsheet_id))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "batch_get" => {
-                let ranges = context.get_parameter("ranges")
+                let ranges = context.input.get("ranges")
                     .and_then(|v| v.as_array())
-                    .ok_or("Ranges array is required for batch_get operation")?;
+                    .ok_or_else(|| param_error("Ranges array is required for batch_get operation"))?;
 
                 let range_strings: Vec<String> = ranges.iter()
-                    .filter_map(|r| r.as_string())
-                    .map(|r| if r.contains('!') { r } else { format!("{}!{}", sheet_name, r) })
+                    .filter_map(|r| r.as_str())
+                    .map(|r| if r.contains('!') { r.to_string() } else { format!("{}!{}", sheet_name, r) })
                     .collect();
 
                 let response = client
@@ -240,18 +336,19 @@ impl Node for GoogleSheetsNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&range_strings.iter().map(|r| ("ranges", r.as_str())).collect::<Vec<_>>())
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
         
         // Extract specific data for convenience
         if let Some(values) = result.get("values").or(result.get("data")) {
@@ -262,7 +359,125 @@ impl Node for GoogleSheetsNode {
             outputs.insert("headers".to_string(), headers.clone().into());
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
+    }
+}
+
+/// Updates the row whose `key_column` cell matches `data[key_column]`, or
+/// appends a new row when no match exists. Columns are resolved against the
+/// sheet's header row; columns missing from `data` keep their current value.
+async fn upsert_row(
+    client: &reqwest::Client,
+    base_url: &str,
+    access_token: &str,
+    spreadsheet_id: &str,
+    sheet_name: &str,
+    key_column: &str,
+    data: &serde_json::Map<String, serde_json::Value>,
+    value_input_option: &str,
+) -> Result<serde_json::Value> {
+    let key_value = data.get(key_column)
+        .map(cell_to_string)
+        .ok_or_else(|| param_error(format!("Data is missing a value for key column '{}'", key_column)))?;
+
+    let response = client
+        .get(&format!("{}/{}/values/{}", base_url, spreadsheet_id, urlencoding::encode(sheet_name)))
+        .header("Authorization", format!("Bearer {}", access_token))
+        .send()
+        .await
+        .map_err(network_error)?;
+
+    let sheet: serde_json::Value = response.json().await.map_err(network_error)?;
+    let rows = sheet["values"].as_array().cloned().unwrap_or_default();
+
+    let headers: Vec<String> = rows.first()
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().map(cell_to_string).collect())
+        .ok_or_else(|| param_error(format!("Sheet '{}' has no header row", sheet_name)))?;
+
+    let key_index = headers.iter()
+        .position(|h| h == key_column)
+        .ok_or_else(|| param_error(format!("Key column '{}' not found in header row", key_column)))?;
+
+    if let Some(unknown) = data.keys().find(|k| !headers.contains(k)) {
+        return Err(param_error(format!("Column '{}' not found in header row", unknown)));
+    }
+
+    let existing = rows.iter()
+        .enumerate()
+        .skip(1)
+        .find(|(_, row)| {
+            row.get(key_index).map(cell_to_string).as_deref() == Some(key_value.as_str())
+        });
+
+    let mut row_values: Vec<serde_json::Value> = match existing {
+        Some((_, row)) => row.as_array().cloned().unwrap_or_default(),
+        None => Vec::new(),
+    };
+    row_values.resize(headers.len(), json!(""));
+
+    for (index, header) in headers.iter().enumerate() {
+        if let Some(value) = data.get(header) {
+            row_values[index] = value.clone();
+        }
+    }
+
+    match existing {
+        Some((index, _)) => {
+            // Sheet rows are 1-based and the header occupies row 1
+            let row_number = index + 1;
+            let range = format!("{}!A{}", sheet_name, row_number);
+            let response = client
+                .put(&format!("{}/{}/values/{}", base_url, spreadsheet_id, urlencoding::encode(&range)))
+                .header("Authorization", format!("Bearer {}", access_token))
+                .query(&[("valueInputOption", value_input_option)])
+                .json(&json!({
+                    "values": [row_values]
+                }))
+                .send()
+                .await
+                .map_err(network_error)?;
+
+            let data: serde_json::Value = response.json().await.map_err(network_error)?;
+            Ok(json!({
+                "action": "updated",
+                "row": row_number,
+                "values": [row_values],
+                "response": data
+            }))
+        }
+        None => {
+            let range = format!("{}!A1", sheet_name);
+            let response = client
+                .post(&format!("{}/{}/values/{}:append", base_url, spreadsheet_id, urlencoding::encode(&range)))
+                .header("Authorization", format!("Bearer {}", access_token))
+                .query(&[
+                    ("valueInputOption", value_input_option),
+                    ("insertDataOption", "INSERT_ROWS")
+                ])
+                .json(&json!({
+                    "values": [row_values]
+                }))
+                .send()
+                .await
+                .map_err(network_error)?;
+
+            let data: serde_json::Value = response.json().await.map_err(network_error)?;
+            Ok(json!({
+                "action": "appended",
+                "row": rows.len() + 1,
+                "values": [row_values],
+                "response": data
+            }))
+        }
+    }
+}
+
+fn cell_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
     }
 }
 
@@ -273,104 +488,134 @@ pub struct GoogleSheetsFormulaNode;
 impl Node for GoogleSheetsFormulaNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "google_sheets_formula".to_string(),
-            display_name: "Google Sheets Formula".to_string(),
+            id: "google_sheets_formula".to_string(),
+            name: "Google Sheets Formula".to_string(),
             description: "Execute formulas and advanced operations in Google Sheets".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "access_token".to_string(),
                     display_name: "Access Token".to_string(),
-                    description: "Google OAuth2 access token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Google OAuth2 access token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "spreadsheet_id".to_string(),
                     display_name: "Spreadsheet ID".to_string(),
-                    description: "Google Sheets spreadsheet ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Google Sheets spreadsheet ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Formula operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Formula operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("write_formula".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "range".to_string(),
                     display_name: "Range".to_string(),
-                    description: "Cell range for formula".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Cell range for formula".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "formula".to_string(),
                     display_name: "Formula".to_string(),
-                    description: "Excel/Google Sheets formula (e.g., =SUM(A1:A10))".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Excel/Google Sheets formula (e.g., =SUM(A1:A10))".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "sheet_name".to_string(),
                     display_name: "Sheet Name".to_string(),
-                    description: "Name of the sheet/tab".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Name of the sheet/tab".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("Sheet1".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "calculated_value".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "calculated_value".to_string(),
+                    display_name: "Calculated Value".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let access_token = context.get_parameter("access_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Access token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let access_token = context.input.get("access_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Access token is required"))?;
         
-        let spreadsheet_id = context.get_parameter("spreadsheet_id")
-            .and_then(|v| v.as_string())
-            .ok_or("Spreadsheet ID is required")?;
+        let spreadsheet_id = context.input.get("spreadsheet_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Spreadsheet ID is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("write_formula".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("write_formula");
         
-        let sheet_name = context.get_parameter("sheet_name")
-            .and_then(|v| v.as_string())
-            .unwrap_or("Sheet1".to_string());
+        let sheet_name = context.input.get("sheet_name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Sheet1");
         
-        let range = context.get_parameter("range")
-            .and_then(|v| v.as_string())
-            .ok_or("Range is required")?;
+        let range = context.input.get("range")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Range is required"))?;
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = "https://sheets.googleapis.com/v4/spreadsheets";
 
         let full_range = if range.contains('!') {
-            range
+            range.to_string()
         } else {
             format!("{}!{}", sheet_name, range)
         };
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "write_formula" => {
-                let formula = context.get_parameter("formula")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Formula is required for write_formula operation")?;
+                let formula = context.input.get("formula")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Formula is required for write_formula operation"))?;
 
                 let encoded_range = urlencoding::encode(&full_range);
                 let response = client
@@ -381,9 +626,10 @@ impl Node for GoogleSheetsFormulaNode {
                         "values": [[formula]]
                     }))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let write_result: serde_json::Value = response.json().await?;
+                let write_result: serde_json::Value = response.json().await.map_err(network_error)?;
 
                 // Read back the calculated value
                 let read_response = client
@@ -391,9 +637,10 @@ impl Node for GoogleSheetsFormulaNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("valueRenderOption", "FORMATTED_VALUE")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let read_result: serde_json::Value = read_response.json().await?;
+                let read_result: serde_json::Value = read_response.json().await.map_err(network_error)?;
                 
                 json!({
                     "write_result": write_result,
@@ -403,9 +650,9 @@ impl Node for GoogleSheetsFormulaNode {
                 })
             },
             "batch_formula" => {
-                let formulas = context.get_parameter("formulas")
+                let formulas = context.input.get("formulas")
                     .and_then(|v| v.as_object())
-                    .ok_or("Formulas object is required for batch_formula operation")?;
+                    .ok_or_else(|| param_error("Formulas object is required for batch_formula operation"))?;
 
                 let mut batch_data = Vec::new();
                 
@@ -430,18 +677,19 @@ impl Node for GoogleSheetsFormulaNode {
                         "data": batch_data
                     }))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
         
         // Extract calculated value if available
         if let Some(calc_result) = result.get("calculated_result") {
@@ -454,6 +702,87 @@ impl Node for GoogleSheetsFormulaNode {
             }
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_sheet(server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/sheet123/values/Sheet1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "range": "Sheet1!A1:C3",
+                "majorDimension": "ROWS",
+                "values": [
+                    ["id", "name", "status"],
+                    ["1", "alpha", "open"],
+                    ["2", "beta", "open"]
+                ]
+            })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_upsert_updates_existing_row() {
+        let server = MockServer::start().await;
+        mock_sheet(&server).await;
+
+        Mock::given(method("PUT"))
+            .and(path("/sheet123/values/Sheet1%21A3"))
+            .and(body_json(json!({ "values": [["2", "beta", "closed"]] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "updatedRows": 1 })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let data = json!({ "id": "2", "status": "closed" });
+        let result = upsert_row(
+            &reqwest::Client::new(),
+            &server.uri(),
+            "token",
+            "sheet123",
+            "Sheet1",
+            "id",
+            data.as_object().unwrap(),
+            "USER_ENTERED",
+        ).await.unwrap();
+
+        assert_eq!(result["action"], "updated");
+        assert_eq!(result["row"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_appends_missing_row() {
+        let server = MockServer::start().await;
+        mock_sheet(&server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/sheet123/values/Sheet1%21A1:append"))
+            .and(body_json(json!({ "values": [["3", "gamma", ""]] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "updates": { "updatedRows": 1 } })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let data = json!({ "id": "3", "name": "gamma" });
+        let result = upsert_row(
+            &reqwest::Client::new(),
+            &server.uri(),
+            "token",
+            "sheet123",
+            "Sheet1",
+            "id",
+            data.as_object().unwrap(),
+            "USER_ENTERED",
+        ).await.unwrap();
+
+        assert_eq!(result["action"], "appended");
+        assert_eq!(result["row"], 4);
+    }
+}
@@ -0,0 +1,582 @@
+use super::{param_error, validate_required};
+use crate::http_util::{request_with_policy, shared_client, RequestPolicy};
+use async_trait::async_trait;
+use ghostflow_core::{CircuitBreakerRegistry, GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort};
+use reqwest::RequestBuilder;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+const AUTH_TYPES: [&str; 4] = ["none", "bearer", "basic", "api_key"];
+
+/// Error Apollo-compatible servers return when they do not know a persisted
+/// query hash yet
+const PERSISTED_QUERY_NOT_FOUND: &str = "PersistedQueryNotFound";
+
+#[derive(Debug, Clone)]
+pub struct GraphQLNode {
+    breakers: Arc<CircuitBreakerRegistry>,
+}
+
+impl Default for GraphQLNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hex SHA-256 of the query text, as automatic persisted queries expect.
+fn query_hash(query: &str) -> String {
+    hex::encode(Sha256::digest(query.as_bytes()))
+}
+
+/// Whether the document's operation is a mutation. Mutations are not retried
+/// so a flaky endpoint cannot apply one twice.
+fn is_mutation(query: &str) -> bool {
+    query
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .is_some_and(|line| line.starts_with("mutation"))
+}
+
+/// Request body for one attempt. With `persisted` the query text is left out
+/// and only its hash is sent; `include_query` adds it back for registration.
+fn request_body(params: &Value, query: &str, persisted: bool, include_query: bool) -> Value {
+    let mut body = serde_json::Map::new();
+    if include_query {
+        body.insert("query".to_string(), json!(query));
+    }
+    if let Some(variables) = params.get("variables").filter(|v| !v.is_null()) {
+        body.insert("variables".to_string(), variables.clone());
+    }
+    if let Some(name) = params.get("operation_name").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+        body.insert("operationName".to_string(), json!(name));
+    }
+    if persisted {
+        body.insert(
+            "extensions".to_string(),
+            json!({ "persistedQuery": { "version": 1, "sha256Hash": query_hash(query) } }),
+        );
+    }
+    Value::Object(body)
+}
+
+fn authorize(request: RequestBuilder, params: &Value) -> Result<RequestBuilder> {
+    let text = |key: &str| params.get(key).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+    let mut request = match text("auth_type").unwrap_or("none") {
+        "none" => request,
+        "bearer" => request.bearer_auth(text("token").ok_or_else(|| param_error("GraphQL bearer auth needs a token"))?),
+        "basic" => request.basic_auth(text("username").unwrap_or_default(), text("password")),
+        "api_key" => {
+            let key = text("token").ok_or_else(|| param_error("GraphQL api_key auth needs a token"))?;
+            request.header(text("api_key_header").unwrap_or("X-API-Key"), key)
+        }
+        other => return Err(param_error(format!("Unsupported GraphQL auth_type: {}", other))),
+    };
+
+    if let Some(headers) = params.get("headers").and_then(|v| v.as_object()) {
+        for (name, value) in headers {
+            if let Some(value) = value.as_str() {
+                request = request.header(name, value);
+            }
+        }
+    }
+    Ok(request)
+}
+
+fn error_messages(errors: &[Value]) -> String {
+    errors
+        .iter()
+        .map(|e| e.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn persisted_query_missing(body: &Value) -> bool {
+    body.get("errors").and_then(|e| e.as_array()).is_some_and(|errors| {
+        errors.iter().any(|e| {
+            e.get("message").and_then(|m| m.as_str()) == Some(PERSISTED_QUERY_NOT_FOUND)
+                || e.pointer("/extensions/code").and_then(|c| c.as_str()) == Some("PERSISTED_QUERY_NOT_FOUND")
+        })
+    })
+}
+
+impl GraphQLNode {
+    pub fn new() -> Self {
+        Self {
+            breakers: Arc::new(CircuitBreakerRegistry::new()),
+        }
+    }
+
+    /// Back off from failing hosts using `breakers`
+    pub fn with_circuit_breakers(mut self, breakers: Arc<CircuitBreakerRegistry>) -> Self {
+        self.breakers = breakers;
+        self
+    }
+
+    /// POST one request and return the HTTP status with the decoded body.
+    /// Responses that are not a GraphQL result (no `data` or `errors`) are
+    /// HTTP errors.
+    async fn post(&self, params: &Value, endpoint: &str, body: &Value, policy: &RequestPolicy) -> Result<(u16, Value)> {
+        let request = authorize(shared_client().post(endpoint).json(body), params)?;
+        let response = request_with_policy(request, policy, &self.breakers).await?;
+        let status = response.status();
+        let text = response.text().await.map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+
+        match serde_json::from_str::<Value>(&text) {
+            Ok(body) if body.get("data").is_some() || body.get("errors").is_some() => Ok((status.as_u16(), body)),
+            _ if !status.is_success() => Err(GhostFlowError::NetworkError(format!(
+                "GraphQL endpoint returned HTTP {}: {}",
+                status.as_u16(),
+                text.chars().take(500).collect::<String>()
+            ))),
+            _ => Err(GhostFlowError::NetworkError(format!(
+                "GraphQL endpoint returned HTTP {} without a data or errors field",
+                status.as_u16()
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl Node for GraphQLNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "graphql".to_string(),
+            name: "GraphQL".to_string(),
+            description: "Send a GraphQL query or mutation to an endpoint".to_string(),
+            category: NodeCategory::Integration,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "variables".to_string(),
+                display_name: "Variables".to_string(),
+                description: Some("Values for the operation's variables".to_string()),
+                data_type: DataType::Object,
+                required: false,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "data".to_string(),
+                    display_name: "Data".to_string(),
+                    description: Some("The data field of the response".to_string()),
+                    data_type: DataType::Object,
+                    required: true,
+                },
+                NodePort {
+                    name: "errors".to_string(),
+                    display_name: "Errors".to_string(),
+                    description: Some("GraphQL errors, when fail_on_errors is off".to_string()),
+                    data_type: DataType::Array,
+                    required: false,
+                },
+            ],
+            parameters: vec![
+                NodeParameter {
+                    name: "endpoint".to_string(),
+                    display_name: "Endpoint".to_string(),
+                    description: Some("URL of the GraphQL endpoint".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "query".to_string(),
+                    display_name: "Query".to_string(),
+                    description: Some("Query or mutation document".to_string()),
+                    param_type: ParameterType::Code,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "variables".to_string(),
+                    display_name: "Variables".to_string(),
+                    description: Some("Values for the operation's variables".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "operation_name".to_string(),
+                    display_name: "Operation Name".to_string(),
+                    description: Some("Operation to run when the document defines several".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "auth_type".to_string(),
+                    display_name: "Authentication".to_string(),
+                    description: None,
+                    param_type: ParameterType::Select,
+                    default_value: Some(json!("none")),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "none", "label": "None"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "bearer", "label": "Bearer Token"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "basic", "label": "Basic Auth"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "api_key", "label": "API Key Header"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "token".to_string(),
+                    display_name: "Token".to_string(),
+                    description: Some("Bearer token or API key".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "api_key_header".to_string(),
+                    display_name: "API Key Header".to_string(),
+                    description: Some("Header that carries the API key".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(json!("X-API-Key")),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "username".to_string(),
+                    display_name: "Username".to_string(),
+                    description: None,
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "password".to_string(),
+                    display_name: "Password".to_string(),
+                    description: None,
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "headers".to_string(),
+                    display_name: "Headers".to_string(),
+                    description: Some("Extra request headers".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "persisted_query".to_string(),
+                    display_name: "Persisted Query".to_string(),
+                    description: Some(
+                        "Send the query's SHA-256 hash instead of its text, registering it on a miss".to_string(),
+                    ),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(json!(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "fail_on_errors".to_string(),
+                    display_name: "Fail on GraphQL Errors".to_string(),
+                    description: Some("Fail the node when the response lists errors".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(json!(true)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "timeout".to_string(),
+                    display_name: "Timeout (seconds)".to_string(),
+                    description: None,
+                    param_type: ParameterType::Number,
+                    default_value: Some(json!(30)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("graphql".to_string()),
+            color: Some("#e10098".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)?;
+
+        let auth_type = context.input.get("auth_type").and_then(|v| v.as_str()).unwrap_or("none");
+        if !AUTH_TYPES.contains(&auth_type) {
+            return Err(param_error(format!("Unsupported GraphQL auth_type: {}", auth_type)));
+        }
+        match context.input.get("variables") {
+            None | Some(Value::Null) | Some(Value::Object(_)) => Ok(()),
+            Some(_) => Err(param_error("GraphQL variables must be an object")),
+        }
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let endpoint = params
+            .get("endpoint")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| param_error("GraphQL endpoint is required"))?;
+        let query = params
+            .get("query")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| param_error("GraphQL query is required"))?;
+        let persisted = params.get("persisted_query").and_then(|v| v.as_bool()).unwrap_or(false);
+        let fail_on_errors = params.get("fail_on_errors").and_then(|v| v.as_bool()).unwrap_or(true);
+
+        let mut policy = RequestPolicy {
+            timeout: Duration::from_secs_f64(params.get("timeout").and_then(|v| v.as_f64()).unwrap_or(30.0).max(1.0)),
+            ..RequestPolicy::default()
+        };
+        if is_mutation(query) {
+            policy.max_retries = 0;
+        }
+
+        let (mut status, mut body) =
+            self.post(params, endpoint, &request_body(params, query, persisted, !persisted), &policy).await?;
+        if persisted && persisted_query_missing(&body) {
+            info!("Registering persisted GraphQL query {}", query_hash(query));
+            (status, body) = self.post(params, endpoint, &request_body(params, query, true, true), &policy).await?;
+        }
+
+        let errors = body.get("errors").and_then(|e| e.as_array()).cloned().unwrap_or_default();
+        if fail_on_errors && !errors.is_empty() {
+            return Err(GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: format!("GraphQL errors: {}", error_messages(&errors)),
+            });
+        }
+
+        Ok(json!({
+            "data": body.get("data").cloned().unwrap_or(Value::Null),
+            "errors": errors,
+            "extensions": body.get("extensions").cloned().unwrap_or(Value::Null),
+            "status": status,
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        // Transport failures are retried per request; see is_mutation
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use wiremock::matchers::{body_json, body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const QUERY: &str = "query Host($name: String!) { host(name: $name) { name status } }";
+
+    #[tokio::test]
+    async fn test_query_with_variables_returns_data() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(header("authorization", "Bearer s3cret"))
+            .and(body_json(json!({ "query": QUERY, "variables": { "name": "pve-01" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "host": { "name": "pve-01", "status": "up" } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let output = GraphQLNode::new()
+            .execute(context(json!({
+                "endpoint": format!("{}/graphql", server.uri()),
+                "query": QUERY,
+                "variables": { "name": "pve-01" },
+                "auth_type": "bearer",
+                "token": "s3cret",
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["data"]["host"]["status"], "up");
+        assert_eq!(output["errors"], json!([]));
+        assert_eq!(output["status"], 200);
+    }
+
+    #[tokio::test]
+    async fn test_graphql_errors_fail_distinctly_from_http_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": null,
+                "errors": [{ "message": "Host 'pve-09' not found", "path": ["host"] }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/down"))
+            .respond_with(ResponseTemplate::new(502).set_body_string("Bad Gateway"))
+            .mount(&server)
+            .await;
+        let input = |endpoint: &str| {
+            json!({
+                "endpoint": format!("{}{}", server.uri(), endpoint),
+                "query": QUERY,
+                "variables": { "name": "pve-09" },
+            })
+        };
+
+        let error = GraphQLNode::new().execute(context(input("/graphql"))).await.unwrap_err();
+        match error {
+            GhostFlowError::NodeExecutionError { message, .. } => {
+                assert_eq!(message, "GraphQL errors: Host 'pve-09' not found")
+            }
+            other => panic!("expected a GraphQL error, got {:?}", other),
+        }
+
+        let mut lenient = input("/graphql");
+        lenient["fail_on_errors"] = json!(false);
+        let output = GraphQLNode::new().execute(context(lenient)).await.unwrap();
+        assert_eq!(output["errors"][0]["path"], json!(["host"]));
+
+        let mut down = input("/down");
+        down["query"] = json!("mutation { restart }");
+        let error = GraphQLNode::new().execute(context(down)).await.unwrap_err();
+        assert!(matches!(error, GhostFlowError::NetworkError(ref m) if m.contains("HTTP 502")), "{:?}", error);
+        // Mutations are sent once
+        let attempts = server.received_requests().await.unwrap().iter().filter(|r| r.url.path() == "/down").count();
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_persisted_query_registers_on_miss() {
+        let server = MockServer::start().await;
+        let hash = query_hash(QUERY);
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "query": QUERY })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": { "host": null } })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_json(json!({
+                "variables": { "name": "pve-01" },
+                "extensions": { "persistedQuery": { "version": 1, "sha256Hash": hash } }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "errors": [{ "message": "PersistedQueryNotFound" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let output = GraphQLNode::new()
+            .execute(context(json!({
+                "endpoint": server.uri(),
+                "query": QUERY,
+                "variables": { "name": "pve-01" },
+                "persisted_query": true,
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["data"], json!({ "host": null }));
+    }
+
+    #[test]
+    fn test_detects_mutations() {
+        assert!(is_mutation("# restart\nmutation Restart { restart }"));
+        assert!(!is_mutation(QUERY));
+        assert!(!is_mutation("{ hosts { name } }"));
+    }
+
+    /// HTTP/1.1 server that keeps connections open and answers every request
+    /// with `body`, counting the connections it accepts
+    async fn keep_alive_server(body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let accepted = connections.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buffer = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    loop {
+                        // Answer once the headers and the announced body are in
+                        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                            let headers = String::from_utf8_lossy(&buffer[..end]).to_lowercase();
+                            let length = headers
+                                .lines()
+                                .find_map(|line| line.strip_prefix("content-length:"))
+                                .and_then(|v| v.trim().parse::<usize>().ok())
+                                .unwrap_or(0);
+                            if buffer.len() >= end + 4 + length {
+                                buffer.drain(..end + 4 + length);
+                                let response = format!(
+                                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                                    body.len(),
+                                    body
+                                );
+                                if socket.write_all(response.as_bytes()).await.is_err() {
+                                    return;
+                                }
+                                continue;
+                            }
+                        }
+                        match socket.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(read) => buffer.extend_from_slice(&chunk[..read]),
+                        }
+                    }
+                });
+            }
+        });
+        (format!("http://{}", address), connections)
+    }
+
+    #[tokio::test]
+    async fn test_repeated_executions_reuse_the_shared_client() {
+        let (endpoint, connections) = keep_alive_server(r#"{"data":{"host":{"status":"up"}}}"#).await;
+        let input = json!({ "endpoint": endpoint, "query": QUERY, "variables": { "name": "pve-01" } });
+
+        for _ in 0..3 {
+            let output = GraphQLNode::new().execute(context(input.clone())).await.unwrap();
+            assert_eq!(output["data"]["host"]["status"], "up");
+        }
+
+        // A client per execution would have opened a connection each time
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+}
+
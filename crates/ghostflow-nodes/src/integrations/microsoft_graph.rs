@@ -1,8 +1,13 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use crate::http_util::shared_client;
+use ghostflow_core::{Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MicrosoftGraphEmailNode;
@@ -11,118 +16,145 @@ pub struct MicrosoftGraphEmailNode;
 impl Node for MicrosoftGraphEmailNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "microsoft_graph_email".to_string(),
-            display_name: "Microsoft 365 Email".to_string(),
+            id: "microsoft_graph_email".to_string(),
+            name: "Microsoft 365 Email".to_string(),
             description: "Send and manage emails via Microsoft Graph API".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "access_token".to_string(),
                     display_name: "Access Token".to_string(),
-                    description: "Microsoft Graph API access token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Microsoft Graph API access token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Email operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Email operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("send".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "to".to_string(),
                     display_name: "To".to_string(),
-                    description: "Recipient email addresses (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Recipient email addresses (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "subject".to_string(),
                     display_name: "Subject".to_string(),
-                    description: "Email subject".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Email subject".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "body".to_string(),
                     display_name: "Body".to_string(),
-                    description: "Email body content".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Email body content".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "body_type".to_string(),
                     display_name: "Body Type".to_string(),
-                    description: "Email body type (text or html)".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("Email body type (text or html)".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("html".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "cc".to_string(),
                     display_name: "CC".to_string(),
-                    description: "CC recipients (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("CC recipients (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "importance".to_string(),
                     display_name: "Importance".to_string(),
-                    description: "Email importance level".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("Email importance level".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("normal".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let access_token = context.get_parameter("access_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Access token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let access_token = context.input.get("access_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Access token is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("send".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("send");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = "https://graph.microsoft.com/v1.0";
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "send" => {
-                let to = context.get_parameter("to")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Recipients are required for send operation")?;
+                let to = context.input.get("to")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Recipients are required for send operation"))?;
                 
-                let subject = context.get_parameter("subject")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Subject is required for send operation")?;
+                let subject = context.input.get("subject")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Subject is required for send operation"))?;
                 
-                let body_content = context.get_parameter("body")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Body is required for send operation")?;
+                let body_content = context.input.get("body")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Body is required for send operation"))?;
                 
-                let body_type = context.get_parameter("body_type")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("html".to_string());
+                let body_type = context.input.get("body_type")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("html");
 
-                let importance = context.get_parameter("importance")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("normal".to_string());
+                let importance = context.input.get("importance")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("normal");
 
                 let to_recipients: Vec<serde_json::Value> = to.split(',')
                     .map(|email| json!({
@@ -144,7 +176,7 @@ impl Node for MicrosoftGraphEmailNode {
                     }
                 });
 
-                if let Some(cc) = context.get_parameter("cc").and_then(|v| v.as_string()) {
+                if let Some(cc) = context.input.get("cc").and_then(|v| v.as_str()) {
                     let cc_recipients: Vec<serde_json::Value> = cc.split(',')
                         .map(|email| json!({
                             "emailAddress": {
@@ -161,7 +193,8 @@ impl Node for MicrosoftGraphEmailNode {
                     .header("Content-Type", "application/json")
                     .json(&message)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
                 json!({
                     "success": response.status().is_success(),
@@ -174,9 +207,10 @@ impl Node for MicrosoftGraphEmailNode {
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("$top", "20"), ("$orderby", "receivedDateTime desc")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "get_folders" => {
@@ -184,19 +218,20 @@ impl Node for MicrosoftGraphEmailNode {
                     .get(&format!("{}/me/mailFolders", base_url))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -207,105 +242,143 @@ pub struct MicrosoftTeamsNode;
 impl Node for MicrosoftTeamsNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "microsoft_teams".to_string(),
-            display_name: "Microsoft Teams".to_string(),
+            id: "microsoft_teams".to_string(),
+            name: "Microsoft Teams".to_string(),
             description: "Send messages and manage Teams channels".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "access_token".to_string(),
                     display_name: "Access Token".to_string(),
-                    description: "Microsoft Graph API access token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Microsoft Graph API access token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Teams operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Teams operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("send_message".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "team_id".to_string(),
                     display_name: "Team ID".to_string(),
-                    description: "Microsoft Teams team ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Microsoft Teams team ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "channel_id".to_string(),
                     display_name: "Channel ID".to_string(),
-                    description: "Teams channel ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Teams channel ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "message".to_string(),
                     display_name: "Message".to_string(),
-                    description: "Message content to send".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Message content to send".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "importance".to_string(),
                     display_name: "Importance".to_string(),
-                    description: "Message importance".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: false,
+                    description: Some("Message importance".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("normal".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "content_type".to_string(),
+                    display_name: "Content Type".to_string(),
+                    description: Some("text sends the message as-is, html renders it from Markdown, adaptive_card sends the card parameter".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("text".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "text", "label": "Plain Text"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "html", "label": "HTML (from Markdown)"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "adaptive_card", "label": "Adaptive Card"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "card".to_string(),
+                    display_name: "Adaptive Card".to_string(),
+                    description: Some("Adaptive Card JSON, used when content type is adaptive_card".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let access_token = context.get_parameter("access_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Access token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let access_token = context.input.get("access_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Access token is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("send_message".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("send_message");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = "https://graph.microsoft.com/v1.0";
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "send_message" => {
-                let team_id = context.get_parameter("team_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Team ID is required for send message operation")?;
-                
-                let channel_id = context.get_parameter("channel_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Channel ID is required for send message operation")?;
+                let team_id = context.input.get("team_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Team ID is required for send message operation"))?;
                 
-                let message = context.get_parameter("message")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Message is required for send message operation")?;
+                let channel_id = context.input.get("channel_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Channel ID is required for send message operation"))?;
                 
-                let importance = context.get_parameter("importance")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or("normal".to_string());
+                let importance = context.input.get("importance")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("normal");
 
-                let body = json!({
-                    "body": {
-                        "content": message
-                    },
-                    "importance": importance
-                });
+                let body = teams_message_body(&context.input, importance)?;
 
                 let response = client
                     .post(&format!("{}/teams/{}/channels/{}/messages", base_url, team_id, channel_id))
@@ -313,9 +386,10 @@ impl Node for MicrosoftTeamsNode {
                     .header("Content-Type", "application/json")
                     .json(&body)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "list_teams" => {
@@ -323,33 +397,35 @@ impl Node for MicrosoftTeamsNode {
                     .get(&format!("{}/me/joinedTeams", base_url))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "list_channels" => {
-                let team_id = context.get_parameter("team_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Team ID is required for list channels operation")?;
+                let team_id = context.input.get("team_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Team ID is required for list channels operation"))?;
 
                 let response = client
                     .get(&format!("{}/teams/{}/channels", base_url, team_id))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
     }
 }
 
@@ -360,113 +436,139 @@ pub struct MicrosoftCalendarNode;
 impl Node for MicrosoftCalendarNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "microsoft_calendar".to_string(),
-            display_name: "Microsoft 365 Calendar".to_string(),
+            id: "microsoft_calendar".to_string(),
+            name: "Microsoft 365 Calendar".to_string(),
             description: "Manage calendar events and meetings".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "access_token".to_string(),
                     display_name: "Access Token".to_string(),
-                    description: "Microsoft Graph API access token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Microsoft Graph API access token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Calendar operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Calendar operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("get_events".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "subject".to_string(),
                     display_name: "Subject".to_string(),
-                    description: "Event subject".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Event subject".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "start_time".to_string(),
                     display_name: "Start Time".to_string(),
-                    description: "Event start time (ISO 8601)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Event start time (ISO 8601)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "end_time".to_string(),
                     display_name: "End Time".to_string(),
-                    description: "Event end time (ISO 8601)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Event end time (ISO 8601)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "attendees".to_string(),
                     display_name: "Attendees".to_string(),
-                    description: "Attendee emails (comma-separated)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Attendee emails (comma-separated)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "location".to_string(),
                     display_name: "Location".to_string(),
-                    description: "Event location".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Event location".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let access_token = context.get_parameter("access_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Access token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let access_token = context.input.get("access_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Access token is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("get_events".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("get_events");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
         let base_url = "https://graph.microsoft.com/v1.0";
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "get_events" => {
                 let response = client
                     .get(&format!("{}/me/events", base_url))
                     .header("Authorization", format!("Bearer {}", access_token))
                     .query(&[("$top", "20"), ("$orderby", "start/dateTime")])
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "create_event" => {
-                let subject = context.get_parameter("subject")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Subject is required for create event")?;
+                let subject = context.input.get("subject")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Subject is required for create event"))?;
                 
-                let start_time = context.get_parameter("start_time")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Start time is required for create event")?;
+                let start_time = context.input.get("start_time")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Start time is required for create event"))?;
                 
-                let end_time = context.get_parameter("end_time")
-                    .and_then(|v| v.as_string())
-                    .ok_or("End time is required for create event")?;
+                let end_time = context.input.get("end_time")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("End time is required for create event"))?;
 
                 let mut event = json!({
                     "subject": subject,
@@ -480,13 +582,13 @@ impl Node for MicrosoftCalendarNode {
                     }
                 });
 
-                if let Some(location) = context.get_parameter("location").and_then(|v| v.as_string()) {
+                if let Some(location) = context.input.get("location").and_then(|v| v.as_str()) {
                     event["location"] = json!({
                         "displayName": location
                     });
                 }
 
-                if let Some(attendees) = context.get_parameter("attendees").and_then(|v| v.as_string()) {
+                if let Some(attendees) = context.input.get("attendees").and_then(|v| v.as_str()) {
                     let attendee_list: Vec<serde_json::Value> = attendees.split(',')
                         .map(|email| json!({
                             "emailAddress": {
@@ -504,18 +606,130 @@ impl Node for MicrosoftCalendarNode {
                     .header("Content-Type", "application/json")
                     .json(&event)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result));
-        Ok(outputs)
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result);
+        Ok(Value::Object(outputs))
+    }
+}
+
+const ADAPTIVE_CARD_CONTENT_TYPE: &str = "application/vnd.microsoft.card.adaptive";
+
+/// Teams renders HTML but not Markdown, so Markdown is converted up front.
+fn markdown_to_html(markdown: &str) -> String {
+    let parser = pulldown_cmark::Parser::new_ext(markdown, pulldown_cmark::Options::all());
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    html.trim_end().to_string()
+}
+
+/// Graph `chatMessage` payload for the requested `content_type`. Adaptive
+/// cards travel as an attachment referenced from the HTML body, with the card
+/// JSON serialized into the attachment's `content` string.
+fn teams_message_body(input: &Value, importance: &str) -> Result<Value> {
+    let content_type = input.get("content_type").and_then(|v| v.as_str()).unwrap_or("text");
+    let message = || {
+        input.get("message")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Message is required for send message operation"))
+    };
+
+    let body = match content_type {
+        "text" => json!({
+            "body": { "contentType": "text", "content": message()? },
+            "importance": importance
+        }),
+        "html" => json!({
+            "body": { "contentType": "html", "content": markdown_to_html(message()?) },
+            "importance": importance
+        }),
+        "adaptive_card" => {
+            let card = match input.get("card") {
+                Some(Value::String(raw)) => serde_json::from_str(raw)
+                    .map_err(|e| param_error(format!("Adaptive card is not valid JSON: {}", e)))?,
+                Some(card @ Value::Object(_)) => card.clone(),
+                _ => return Err(param_error("Card is required when content type is adaptive_card")),
+            };
+            let attachment_id = uuid::Uuid::new_v4().simple().to_string();
+            json!({
+                "body": {
+                    "contentType": "html",
+                    "content": format!("<attachment id=\"{}\"></attachment>", attachment_id)
+                },
+                "attachments": [{
+                    "id": attachment_id,
+                    "contentType": ADAPTIVE_CARD_CONTENT_TYPE,
+                    "contentUrl": null,
+                    "content": card.to_string(),
+                    "name": null,
+                    "thumbnailUrl": null
+                }],
+                "importance": importance
+            })
+        }
+        other => return Err(param_error(format!("Unknown content type: {}", other))),
+    };
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_is_converted_to_html() {
+        let body = teams_message_body(
+            &json!({
+                "content_type": "html",
+                "message": "# Daily Report\n\n- **3** VMs restarted\n- [Dashboard](https://grafana.local)"
+            }),
+            "normal",
+        )
+        .unwrap();
+
+        assert_eq!(body["body"]["contentType"], "html");
+        let html = body["body"]["content"].as_str().unwrap();
+        assert!(html.contains("<h1>Daily Report</h1>"), "{}", html);
+        assert!(html.contains("<li><strong>3</strong> VMs restarted</li>"), "{}", html);
+        assert!(html.contains(r#"<a href="https://grafana.local">Dashboard</a>"#), "{}", html);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_text_is_sent_unchanged() {
+        let body = teams_message_body(&json!({ "message": "**not bold**" }), "high").unwrap();
+        assert_eq!(body["body"], json!({ "contentType": "text", "content": "**not bold**" }));
+        assert_eq!(body["importance"], "high");
+    }
+
+    #[test]
+    fn test_adaptive_card_envelope() {
+        let card = json!({
+            "type": "AdaptiveCard",
+            "version": "1.4",
+            "body": [{ "type": "TextBlock", "text": "Backup finished" }]
+        });
+        let body = teams_message_body(&json!({ "content_type": "adaptive_card", "card": card }), "normal").unwrap();
+
+        let attachment = &body["attachments"][0];
+        let id = attachment["id"].as_str().unwrap();
+        assert_eq!(attachment["contentType"], ADAPTIVE_CARD_CONTENT_TYPE);
+        assert_eq!(
+            body["body"]["content"],
+            format!("<attachment id=\"{}\"></attachment>", id)
+        );
+        let content: Value = serde_json::from_str(attachment["content"].as_str().unwrap()).unwrap();
+        assert_eq!(content, card);
+
+        assert!(teams_message_body(&json!({ "content_type": "adaptive_card" }), "normal").is_err());
+    }
+}
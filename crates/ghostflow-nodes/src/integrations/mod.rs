@@ -9,6 +9,13 @@ pub mod wazuh;
 pub mod proxmox;
 pub mod email;
 pub mod database;
+pub mod s3;
+pub mod sqs;
+pub mod github;
+pub mod telegram;
+pub mod sql_server;
+pub mod graphql;
+pub mod sftp;
 
 pub use cloudflare::*;
 pub use microsoft_graph::*;
@@ -20,4 +27,46 @@ pub use azure::*;
 pub use wazuh::*;
 pub use proxmox::*;
 pub use email::*;
-pub use database::*;
\ No newline at end of file
+pub use database::*;
+pub use s3::*;
+pub use sqs::*;
+pub use github::*;
+pub use telegram::*;
+pub use sql_server::*;
+pub use graphql::*;
+pub use sftp::*;
+
+use ghostflow_core::{GhostFlowError, Result};
+use ghostflow_schema::{ExecutionContext, NodeDefinition};
+
+/// Error for a missing or malformed node parameter
+pub(crate) fn param_error(message: impl Into<String>) -> GhostFlowError {
+    GhostFlowError::ValidationError {
+        message: message.into(),
+    }
+}
+
+/// Error for a failed call to an upstream service
+pub(crate) fn network_error(error: impl std::fmt::Display) -> GhostFlowError {
+    GhostFlowError::NetworkError(error.to_string())
+}
+
+/// Check that every required parameter without a default is present in the
+/// node input.
+pub(crate) fn validate_required(definition: &NodeDefinition, context: &ExecutionContext) -> Result<()> {
+    for param in &definition.parameters {
+        if !param.required || param.default_value.is_some() {
+            continue;
+        }
+        match context.input.get(&param.name) {
+            Some(value) if !value.is_null() => {}
+            _ => {
+                return Err(param_error(format!(
+                    "Missing required parameter '{}' for node '{}'",
+                    param.name, definition.id
+                )))
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,143 +1,295 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use crate::http_util::{tls_parameter, TlsConfig};
+use ghostflow_core::{EventBus, ExecutionEvent, GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort, ParameterOption,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ProxmoxVMNode;
+#[derive(Debug, Clone, Default)]
+pub struct ProxmoxVMNode {
+    events: EventBus,
+}
+
+impl ProxmoxVMNode {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish task progress on `events` while waiting for completion
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+}
 
 #[async_trait]
 impl Node for ProxmoxVMNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "proxmox_vm".to_string(),
-            display_name: "Proxmox VM".to_string(),
+            id: "proxmox_vm".to_string(),
+            name: "Proxmox VM".to_string(),
             description: "Manage Proxmox Virtual Machines".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "host".to_string(),
                     display_name: "Proxmox Host".to_string(),
-                    description: "Proxmox server hostname or IP".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Proxmox server hostname or IP; not needed with base_url".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "port".to_string(),
                     display_name: "Port".to_string(),
-                    description: "Proxmox API port".to_string(),
-                    parameter_type: ParameterType::Number,
+                    description: Some("Proxmox API port".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(8006))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "base_url".to_string(),
+                    display_name: "API Base URL".to_string(),
+                    description: Some("Full API URL, e.g. behind a reverse proxy; overrides host and port".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
                     required: false,
-                    default_value: Some(Value::Number(8006.0)),
+                    options: None,
+                    validation: None,
                 },
+                tls_parameter(),
                 NodeParameter {
                     name: "username".to_string(),
                     display_name: "Username".to_string(),
-                    description: "Proxmox username (user@pam or user@pve)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Proxmox username (user@pam or user@pve)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "password".to_string(),
                     display_name: "Password".to_string(),
-                    description: "Proxmox password or API token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Proxmox password or API token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "VM operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("VM operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("list".to_string())),
+                    required: true,
+                    options: Some(
+                        ["list", "get", "start", "stop", "restart", "clone", "snapshot", "backup", "restore"]
+                            .iter()
+                            .map(|op| serde_json::from_value(json!({ "value": op, "label": op })).unwrap())
+                            .collect(),
+                    ),
+                    validation: None,
                 },
                 NodeParameter {
                     name: "node".to_string(),
                     display_name: "Node".to_string(),
-                    description: "Proxmox node name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Proxmox node name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "vmid".to_string(),
                     display_name: "VM ID".to_string(),
-                    description: "Virtual machine ID".to_string(),
-                    parameter_type: ParameterType::Number,
-                    required: false,
+                    description: Some("Virtual machine ID".to_string()),
+                    param_type: ParameterType::Number,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "vm_name".to_string(),
                     display_name: "VM Name".to_string(),
-                    description: "Virtual machine name".to_string(),
-                    parameter_type: ParameterType::String,
+                    description: Some("Virtual machine name".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "storage".to_string(),
+                    display_name: "Storage".to_string(),
+                    description: Some("Target storage for backup, or for the restored disks".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "mode".to_string(),
+                    display_name: "Backup Mode".to_string(),
+                    description: Some("vzdump mode: snapshot, suspend or stop".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("snapshot".to_string())),
+                    required: false,
+                    options: Some(
+                        BACKUP_MODES
+                            .iter()
+                            .map(|m| serde_json::from_value(json!({ "value": m, "label": m })).unwrap())
+                            .collect(),
+                    ),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "compress".to_string(),
+                    display_name: "Compression".to_string(),
+                    description: Some("Backup compression: zstd, gzip, lzo or 0 for none".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("zstd".to_string())),
                     required: false,
+                    options: Some(
+                        BACKUP_COMPRESSION
+                            .iter()
+                            .map(|c| serde_json::from_value(json!({ "value": c, "label": c })).unwrap())
+                            .collect(),
+                    ),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "archive".to_string(),
+                    display_name: "Backup Archive".to_string(),
+                    description: Some("Volume id of the backup to restore, e.g. local:backup/vzdump-qemu-101-2024_01_01-00_00_00.vma.zst".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "force".to_string(),
+                    display_name: "Overwrite Existing VM".to_string(),
+                    description: Some("Allow restore to replace an existing VM with the same ID".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "wait_for_completion".to_string(),
+                    display_name: "Wait for Completion".to_string(),
+                    description: Some("Poll the Proxmox task until it finishes instead of returning its UPID".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "task_timeout".to_string(),
+                    display_name: "Task Timeout".to_string(),
+                    description: Some("Seconds to wait for the task when waiting for completion".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(DEFAULT_TASK_TIMEOUT_SECS))),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "vm_status".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "vm_status".to_string(),
+                    display_name: "Vm Status".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let host = context.get_parameter("host")
-            .and_then(|v| v.as_string())
-            .ok_or("Proxmox host is required")?;
-        
-        let port = context.get_parameter("port")
-            .and_then(|v| v.as_number())
-            .unwrap_or(8006.0) as u16;
-        
-        let username = context.get_parameter("username")
-            .and_then(|v| v.as_string())
-            .ok_or("Username is required")?;
-        
-        let password = context.get_parameter("password")
-            .and_then(|v| v.as_string())
-            .ok_or("Password is required")?;
-        
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("list".to_string());
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
 
-        let client = reqwest::Client::builder()
-            .danger_accept_invalid_certs(true) // Proxmox often uses self-signed certs
-            .build()?;
+    fn supports_connection_test(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self, context: &ExecutionContext) -> Result<String> {
+        test_proxmox_connection(&context.input).await
+    }
+
+    fn dynamic_option_parameters(&self) -> Vec<&'static str> {
+        vec!["node"]
+    }
+
+    async fn load_options(&self, parameter: &str, context: &ExecutionContext) -> Result<Vec<ParameterOption>> {
+        match parameter {
+            "node" => load_node_options(&context.input).await,
+            other => Err(param_error(format!("No options to load for '{}'", other))),
+        }
+    }
 
-        let base_url = format!("https://{}:{}/api2/json", host, port);
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let (client, base_url, username, password) = connection(&context.input)?;
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list");
 
         // Authenticate and get ticket
         let auth_response = client
             .post(&format!("{}/access/ticket", base_url))
             .form(&[
-                ("username", username.as_str()),
-                ("password", password.as_str()),
+                ("username", username),
+                ("password", password),
             ])
             .send()
-            .await?;
+            .await
+            .map_err(network_error)?;
 
-        let auth_data: serde_json::Value = auth_response.json().await?;
+        let auth_data: serde_json::Value = auth_response.json().await.map_err(network_error)?;
         let ticket = auth_data["data"]["ticket"]
             .as_str()
-            .ok_or("Failed to get authentication ticket")?;
+            .ok_or_else(|| GhostFlowError::AuthenticationError {
+                message: "Failed to get authentication ticket".to_string(),
+            })?;
         let csrf_token = auth_data["data"]["CSRFPreventionToken"]
             .as_str()
-            .ok_or("Failed to get CSRF token")?;
+            .ok_or_else(|| GhostFlowError::AuthenticationError {
+                message: "Failed to get CSRF token".to_string(),
+            })?;
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "list" => {
-                let url = if let Some(node) = context.get_parameter("node").and_then(|v| v.as_string()) {
+                let url = if let Some(node) = context.input.get("node").and_then(|v| v.as_str()) {
                     format!("{}/nodes/{}/qemu", base_url, node)
                 } else {
                     // List all VMs across all nodes
@@ -145,9 +297,10 @@ impl Node for ProxmoxVMNode {
                         .get(&format!("{}/nodes", base_url))
                         .header("Cookie", format!("PVEAuthCookie={}", ticket))
                         .send()
-                        .await?;
+                        .await
+                        .map_err(network_error)?;
 
-                    let nodes_data: serde_json::Value = nodes_response.json().await?;
+                    let nodes_data: serde_json::Value = nodes_response.json().await.map_err(network_error)?;
                     let mut all_vms = Vec::new();
 
                     if let Some(nodes) = nodes_data["data"].as_array() {
@@ -157,7 +310,8 @@ impl Node for ProxmoxVMNode {
                                     .get(&format!("{}/nodes/{}/qemu", base_url, node_name))
                                     .header("Cookie", format!("PVEAuthCookie={}", ticket))
                                     .send()
-                                    .await?;
+                                    .await
+                                    .map_err(network_error)?;
 
                                 if let Ok(vms_data) = vms_response.json::<serde_json::Value>().await {
                                     if let Some(vms) = vms_data["data"].as_array() {
@@ -171,11 +325,11 @@ impl Node for ProxmoxVMNode {
                     }
 
                     return Ok({
-                        let mut outputs = HashMap::new();
-                        outputs.insert("result".to_string(), Value::Object(json!({
+                        let mut outputs = serde_json::Map::new();
+                        outputs.insert("result".to_string(), json!({
                             "data": all_vms
-                        })));
-                        outputs
+                        }));
+                        Value::Object(outputs)
                     });
                 };
 
@@ -183,120 +337,122 @@ impl Node for ProxmoxVMNode {
                     .get(&url)
                     .header("Cookie", format!("PVEAuthCookie={}", ticket))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "get" => {
-                let node = context.get_parameter("node")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Node is required for get operation")?;
+                let node = context.input.get("node")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Node is required for get operation"))?;
                 
-                let vmid = context.get_parameter("vmid")
-                    .and_then(|v| v.as_number())
-                    .ok_or("VM ID is required for get operation")? as u32;
+                let vmid = context.input.get("vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("VM ID is required for get operation"))? as u32;
 
                 let response = client
                     .get(&format!("{}/nodes/{}/qemu/{}/status/current", base_url, node, vmid))
                     .header("Cookie", format!("PVEAuthCookie={}", ticket))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "start" => {
-                let node = context.get_parameter("node")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Node is required for start operation")?;
+                let node = context.input.get("node")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Node is required for start operation"))?;
                 
-                let vmid = context.get_parameter("vmid")
-                    .and_then(|v| v.as_number())
-                    .ok_or("VM ID is required for start operation")? as u32;
+                let vmid = context.input.get("vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("VM ID is required for start operation"))? as u32;
 
                 let response = client
                     .post(&format!("{}/nodes/{}/qemu/{}/status/start", base_url, node, vmid))
                     .header("Cookie", format!("PVEAuthCookie={}", ticket))
                     .header("CSRFPreventionToken", csrf_token)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                json!({
-                    "success": response.status().is_success(),
-                    "status": response.status().as_u16(),
-                    "operation": "start",
-                    "vmid": vmid,
-                    "node": node
-                })
+                let mut result = ProxmoxTask::from_response(response, "start")
+                    .await?
+                    .finish(&client, &base_url, ticket, node, &context, &self.events)
+                    .await?;
+                result["vmid"] = json!(vmid);
+                result
             },
             "stop" => {
-                let node = context.get_parameter("node")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Node is required for stop operation")?;
+                let node = context.input.get("node")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Node is required for stop operation"))?;
                 
-                let vmid = context.get_parameter("vmid")
-                    .and_then(|v| v.as_number())
-                    .ok_or("VM ID is required for stop operation")? as u32;
+                let vmid = context.input.get("vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("VM ID is required for stop operation"))? as u32;
 
                 let response = client
                     .post(&format!("{}/nodes/{}/qemu/{}/status/stop", base_url, node, vmid))
                     .header("Cookie", format!("PVEAuthCookie={}", ticket))
                     .header("CSRFPreventionToken", csrf_token)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                json!({
-                    "success": response.status().is_success(),
-                    "status": response.status().as_u16(),
-                    "operation": "stop",
-                    "vmid": vmid,
-                    "node": node
-                })
+                let mut result = ProxmoxTask::from_response(response, "stop")
+                    .await?
+                    .finish(&client, &base_url, ticket, node, &context, &self.events)
+                    .await?;
+                result["vmid"] = json!(vmid);
+                result
             },
             "restart" => {
-                let node = context.get_parameter("node")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Node is required for restart operation")?;
+                let node = context.input.get("node")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Node is required for restart operation"))?;
                 
-                let vmid = context.get_parameter("vmid")
-                    .and_then(|v| v.as_number())
-                    .ok_or("VM ID is required for restart operation")? as u32;
+                let vmid = context.input.get("vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("VM ID is required for restart operation"))? as u32;
 
                 let response = client
                     .post(&format!("{}/nodes/{}/qemu/{}/status/reboot", base_url, node, vmid))
                     .header("Cookie", format!("PVEAuthCookie={}", ticket))
                     .header("CSRFPreventionToken", csrf_token)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                json!({
-                    "success": response.status().is_success(),
-                    "status": response.status().as_u16(),
-                    "operation": "restart",
-                    "vmid": vmid,
-                    "node": node
-                })
+                let mut result = ProxmoxTask::from_response(response, "restart")
+                    .await?
+                    .finish(&client, &base_url, ticket, node, &context, &self.events)
+                    .await?;
+                result["vmid"] = json!(vmid);
+                result
             },
             "clone" => {
-                let node = context.get_parameter("node")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Node is required for clone operation")?;
+                let node = context.input.get("node")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Node is required for clone operation"))?;
                 
-                let vmid = context.get_parameter("vmid")
-                    .and_then(|v| v.as_number())
-                    .ok_or("VM ID is required for clone operation")? as u32;
+                let vmid = context.input.get("vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("VM ID is required for clone operation"))? as u32;
                 
-                let new_vmid = context.get_parameter("new_vmid")
-                    .and_then(|v| v.as_number())
-                    .ok_or("New VM ID is required for clone operation")? as u32;
+                let new_vmid = context.input.get("new_vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("New VM ID is required for clone operation"))? as u32;
 
                 let mut params = vec![
                     ("newid", new_vmid.to_string()),
                 ];
 
-                if let Some(name) = context.get_parameter("vm_name").and_then(|v| v.as_string()) {
-                    params.push(("name", name));
+                if let Some(name) = context.input.get("vm_name").and_then(|v| v.as_str()) {
+                    params.push(("name", name.to_string()));
                 }
 
                 let response = client
@@ -305,28 +461,29 @@ impl Node for ProxmoxVMNode {
                     .header("CSRFPreventionToken", csrf_token)
                     .form(&params)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                json!({
-                    "success": response.status().is_success(),
-                    "status": response.status().as_u16(),
-                    "operation": "clone",
-                    "source_vmid": vmid,
-                    "new_vmid": new_vmid,
-                    "node": node
-                })
+                let mut result = ProxmoxTask::from_response(response, "clone")
+                    .await?
+                    .finish(&client, &base_url, ticket, node, &context, &self.events)
+                    .await?;
+                result["source_vmid"] = json!(vmid);
+                result["new_vmid"] = json!(new_vmid);
+                result
             },
             "snapshot" => {
-                let node = context.get_parameter("node")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Node is required for snapshot operation")?;
+                let node = context.input.get("node")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Node is required for snapshot operation"))?;
                 
-                let vmid = context.get_parameter("vmid")
-                    .and_then(|v| v.as_number())
-                    .ok_or("VM ID is required for snapshot operation")? as u32;
+                let vmid = context.input.get("vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("VM ID is required for snapshot operation"))? as u32;
                 
-                let snapname = context.get_parameter("snapname")
-                    .and_then(|v| v.as_string())
+                let snapname = context.input.get("snapname")
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
                     .unwrap_or_else(|| format!("ghostflow-{}", chrono::Utc::now().timestamp()));
 
                 let response = client
@@ -335,24 +492,74 @@ impl Node for ProxmoxVMNode {
                     .header("CSRFPreventionToken", csrf_token)
                     .form(&[("snapname", &snapname)])
                     .send()
+                    .await
+                    .map_err(network_error)?;
+
+                let mut result = ProxmoxTask::from_response(response, "snapshot")
+                    .await?
+                    .finish(&client, &base_url, ticket, node, &context, &self.events)
                     .await?;
+                result["vmid"] = json!(vmid);
+                result["snapshot_name"] = json!(snapname);
+                result
+            },
+            "backup" => {
+                let node = context.input.get("node")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Node is required for backup operation"))?;
+                
+                let vmid = context.input.get("vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("VM ID is required for backup operation"))? as u32;
 
-                json!({
-                    "success": response.status().is_success(),
-                    "status": response.status().as_u16(),
-                    "operation": "snapshot",
-                    "vmid": vmid,
-                    "snapshot_name": snapname,
-                    "node": node
-                })
+                let response = client
+                    .post(&format!("{}/nodes/{}/vzdump", base_url, node))
+                    .header("Cookie", format!("PVEAuthCookie={}", ticket))
+                    .header("CSRFPreventionToken", csrf_token)
+                    .form(&vzdump_form(vmid, &context.input)?)
+                    .send()
+                    .await
+                    .map_err(network_error)?;
+
+                let mut result = ProxmoxTask::from_response(response, "backup")
+                    .await?
+                    .finish(&client, &base_url, ticket, node, &context, &self.events)
+                    .await?;
+                result["vmid"] = json!(vmid);
+                result
+            },
+            "restore" => {
+                let node = context.input.get("node")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Node is required for restore operation"))?;
+                
+                let vmid = context.input.get("vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("VM ID is required for restore operation"))? as u32;
+
+                let response = client
+                    .post(&format!("{}/nodes/{}/qemu", base_url, node))
+                    .header("Cookie", format!("PVEAuthCookie={}", ticket))
+                    .header("CSRFPreventionToken", csrf_token)
+                    .form(&restore_form(vmid, &context.input)?)
+                    .send()
+                    .await
+                    .map_err(network_error)?;
+
+                let mut result = ProxmoxTask::from_response(response, "restore")
+                    .await?
+                    .finish(&client, &base_url, ticket, node, &context, &self.events)
+                    .await?;
+                result["vmid"] = json!(vmid);
+                result
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
         
         // Extract VM status if available
         if let Some(status_data) = result.get("data") {
@@ -361,7 +568,295 @@ impl Node for ProxmoxVMNode {
             }
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
+    }
+}
+
+/// HTTP client for the node's `tls` settings and the API base URL, from
+/// `base_url` or else the host and port, with the node's account.
+fn connection(input: &Value) -> Result<(reqwest::Client, String, &str, &str)> {
+    let base_url = match input.get("base_url").and_then(|v| v.as_str()).filter(|url| !url.is_empty()) {
+        Some(base_url) => base_url.trim_end_matches('/').to_string(),
+        None => {
+            let host = input.get("host")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| param_error("Proxmox host or base_url is required"))?;
+            let port = input.get("port").and_then(|v| v.as_f64()).unwrap_or(8006.0) as u16;
+            format!("https://{}:{}/api2/json", host, port)
+        }
+    };
+    let username = input.get("username")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Username is required"))?;
+    let password = input.get("password")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Password is required"))?;
+
+    let client = TlsConfig::from_params(input)?.client()?;
+    Ok((client, base_url, username, password))
+}
+
+/// Log in with the node's host and account, then read `/version`.
+async fn test_proxmox_connection(input: &Value) -> Result<String> {
+    let (client, base_url, username, password) = connection(input)?;
+    proxmox_version(&client, &base_url, username, password).await
+}
+
+/// Cluster nodes the node's account can see, for picking a `node`.
+async fn load_node_options(input: &Value) -> Result<Vec<ParameterOption>> {
+    let (client, base_url, username, password) = connection(input)?;
+    node_options(&client, &base_url, username, password).await
+}
+
+/// Ticket for the `PVEAuthCookie` cookie.
+async fn login(client: &reqwest::Client, base_url: &str, username: &str, password: &str) -> Result<String> {
+    let auth_data: Value = client
+        .post(format!("{}/access/ticket", base_url))
+        .form(&[("username", username), ("password", password)])
+        .send()
+        .await
+        .map_err(network_error)?
+        .json()
+        .await
+        .unwrap_or(Value::Null);
+    auth_data["data"]["ticket"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| GhostFlowError::AuthenticationError {
+            message: format!("Proxmox rejected the login for '{}'", username),
+        })
+}
+
+async fn proxmox_version(client: &reqwest::Client, base_url: &str, username: &str, password: &str) -> Result<String> {
+    let ticket = login(client, base_url, username, password).await?;
+
+    let response = client
+        .get(format!("{}/version", base_url))
+        .header("Cookie", format!("PVEAuthCookie={}", ticket))
+        .send()
+        .await
+        .map_err(network_error)?;
+    if !response.status().is_success() {
+        return Err(network_error(format!("Proxmox /version returned {}", response.status())));
+    }
+    let version: Value = response.json().await.map_err(network_error)?;
+    Ok(format!(
+        "Connected to Proxmox VE {}",
+        version["data"]["version"].as_str().unwrap_or("(unknown version)")
+    ))
+}
+
+async fn node_options(
+    client: &reqwest::Client,
+    base_url: &str,
+    username: &str,
+    password: &str,
+) -> Result<Vec<ParameterOption>> {
+    let ticket = login(client, base_url, username, password).await?;
+
+    let response = client
+        .get(format!("{}/nodes", base_url))
+        .header("Cookie", format!("PVEAuthCookie={}", ticket))
+        .send()
+        .await
+        .map_err(network_error)?;
+    if !response.status().is_success() {
+        return Err(network_error(format!("Proxmox /nodes returned {}", response.status())));
+    }
+    let nodes: Value = response.json().await.map_err(network_error)?;
+
+    let mut options: Vec<ParameterOption> = nodes["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|node| {
+            let name = node["node"].as_str()?;
+            let label = match node["status"].as_str() {
+                Some(status) => format!("{} ({})", name, status),
+                None => name.to_string(),
+            };
+            Some(ParameterOption { value: json!(name), label })
+        })
+        .collect();
+    options.sort_by(|a, b| a.label.cmp(&b.label));
+    Ok(options)
+}
+
+const BACKUP_MODES: [&str; 3] = ["snapshot", "suspend", "stop"];
+const BACKUP_COMPRESSION: [&str; 4] = ["zstd", "gzip", "lzo", "0"];
+const DEFAULT_TASK_TIMEOUT_SECS: u64 = 3600;
+const TASK_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Form fields for `POST /nodes/{node}/vzdump`.
+fn vzdump_form(vmid: u32, input: &Value) -> Result<Vec<(&'static str, String)>> {
+    let mode = input.get("mode").and_then(|v| v.as_str()).unwrap_or("snapshot");
+    if !BACKUP_MODES.contains(&mode) {
+        return Err(param_error(format!("Unknown backup mode: {}", mode)));
+    }
+    let compress = match input.get("compress") {
+        Some(Value::String(c)) => c.clone(),
+        Some(Value::Number(n)) => n.to_string(),
+        _ => "zstd".to_string(),
+    };
+    if !BACKUP_COMPRESSION.contains(&compress.as_str()) {
+        return Err(param_error(format!("Unknown backup compression: {}", compress)));
+    }
+
+    let mut form = vec![
+        ("vmid", vmid.to_string()),
+        ("mode", mode.to_string()),
+        ("compress", compress),
+    ];
+    if let Some(storage) = input.get("storage").and_then(|v| v.as_str()) {
+        form.push(("storage", storage.to_string()));
+    }
+    Ok(form)
+}
+
+/// Form fields for restoring a vzdump archive with `POST /nodes/{node}/qemu`.
+fn restore_form(vmid: u32, input: &Value) -> Result<Vec<(&'static str, String)>> {
+    let archive = input.get("archive")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Backup archive is required for restore operation"))?;
+
+    let mut form = vec![
+        ("vmid", vmid.to_string()),
+        ("archive", archive.to_string()),
+    ];
+    if let Some(storage) = input.get("storage").and_then(|v| v.as_str()) {
+        form.push(("storage", storage.to_string()));
+    }
+    if input.get("force").and_then(|v| v.as_bool()).unwrap_or(false) {
+        form.push(("force", "1".to_string()));
+    }
+    Ok(form)
+}
+
+/// The UPID Proxmox returns as `data` when it starts an asynchronous task.
+fn task_upid(body: &Value) -> Option<&str> {
+    body.get("data")
+        .and_then(|v| v.as_str())
+        .filter(|upid| upid.starts_with("UPID:"))
+}
+
+/// An asynchronous Proxmox task started by an API call. Proxmox answers
+/// 200 as soon as the task is queued, so success here only means "started".
+struct ProxmoxTask {
+    operation: &'static str,
+    status: u16,
+    upid: String,
+}
+
+impl ProxmoxTask {
+    async fn from_response(response: reqwest::Response, operation: &'static str) -> Result<Self> {
+        let status = response.status();
+        let body: Value = response.json().await.map_err(network_error)?;
+        if !status.is_success() {
+            return Err(network_error(format!("Proxmox {} failed with {}: {}", operation, status, body)));
+        }
+        let upid = task_upid(&body).ok_or_else(|| {
+            network_error(format!("Proxmox {} response did not include a task id: {}", operation, body))
+        })?;
+        Ok(Self { operation, status: status.as_u16(), upid: upid.to_string() })
+    }
+
+    /// Either hand back the UPID for the caller to track, or wait for the
+    /// task to exit when `wait_for_completion` is set and report its final
+    /// exit status.
+    async fn finish(
+        self,
+        client: &reqwest::Client,
+        base_url: &str,
+        ticket: &str,
+        node: &str,
+        context: &ExecutionContext,
+        events: &EventBus,
+    ) -> Result<Value> {
+        let mut result = json!({
+            "success": true,
+            "status": self.status,
+            "operation": self.operation,
+            "node": node,
+            "upid": self.upid,
+        });
+
+        if context.input.get("wait_for_completion").and_then(|v| v.as_bool()).unwrap_or(false) {
+            let timeout = context.input.get("task_timeout")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_TASK_TIMEOUT_SECS);
+            let status = wait_for_task(
+                client,
+                base_url,
+                ticket,
+                node,
+                &self.upid,
+                std::time::Duration::from_secs(timeout),
+                TASK_POLL_INTERVAL,
+                context,
+                events,
+            )
+            .await?;
+            result["exit_status"] = status["exitstatus"].clone();
+            result["task"] = status;
+        }
+
+        Ok(result)
+    }
+}
+
+/// Poll `/nodes/{node}/tasks/{upid}/status` until the task stops, reporting
+/// each poll on the event bus. Fails if the task exits with anything other
+/// than `OK` or is still running after `timeout`.
+async fn wait_for_task(
+    client: &reqwest::Client,
+    base_url: &str,
+    ticket: &str,
+    node: &str,
+    upid: &str,
+    timeout: std::time::Duration,
+    poll_interval: std::time::Duration,
+    context: &ExecutionContext,
+    events: &EventBus,
+) -> Result<Value> {
+    let url = format!("{}/nodes/{}/tasks/{}/status", base_url, node, urlencoding::encode(upid));
+    let started = std::time::Instant::now();
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Cookie", format!("PVEAuthCookie={}", ticket))
+            .send()
+            .await
+            .map_err(network_error)?;
+        let body: Value = response.json().await.map_err(network_error)?;
+        let task = body.get("data").cloned().unwrap_or(Value::Null);
+        let status = task.get("status").and_then(|v| v.as_str()).unwrap_or("unknown");
+
+        events.publish(ExecutionEvent::NodeProgress {
+            execution_id: context.execution_id,
+            node_id: context.node_id.clone(),
+            message: format!("Proxmox task {} is {}", upid, status),
+            completed: None,
+            total: None,
+        });
+
+        if status == "stopped" {
+            let exit_status = task.get("exitstatus").and_then(|v| v.as_str()).unwrap_or("unknown");
+            if exit_status != "OK" {
+                return Err(GhostFlowError::NodeExecutionError {
+                    node_id: context.node_id.clone(),
+                    message: format!("Proxmox task {} failed: {}", upid, exit_status),
+                });
+            }
+            return Ok(task);
+        }
+
+        if started.elapsed() >= timeout {
+            return Err(GhostFlowError::TimeoutError {
+                timeout_ms: timeout.as_millis() as u64,
+            });
+        }
+        tokio::time::sleep(poll_interval).await;
     }
 }
 
@@ -372,125 +867,171 @@ pub struct ProxmoxContainerNode;
 impl Node for ProxmoxContainerNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "proxmox_container".to_string(),
-            display_name: "Proxmox LXC Container".to_string(),
+            id: "proxmox_container".to_string(),
+            name: "Proxmox LXC Container".to_string(),
             description: "Manage Proxmox LXC Containers".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "host".to_string(),
                     display_name: "Proxmox Host".to_string(),
-                    description: "Proxmox server hostname or IP".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Proxmox server hostname or IP; not needed with base_url".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "port".to_string(),
                     display_name: "Port".to_string(),
-                    description: "Proxmox API port".to_string(),
-                    parameter_type: ParameterType::Number,
+                    description: Some("Proxmox API port".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(8006))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "base_url".to_string(),
+                    display_name: "API Base URL".to_string(),
+                    description: Some("Full API URL, e.g. behind a reverse proxy; overrides host and port".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
                     required: false,
-                    default_value: Some(Value::Number(8006.0)),
+                    options: None,
+                    validation: None,
                 },
+                tls_parameter(),
                 NodeParameter {
                     name: "username".to_string(),
                     display_name: "Username".to_string(),
-                    description: "Proxmox username (user@pam or user@pve)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Proxmox username (user@pam or user@pve)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "password".to_string(),
                     display_name: "Password".to_string(),
-                    description: "Proxmox password or API token".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Proxmox password or API token".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Container operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Container operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("list".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "node".to_string(),
                     display_name: "Node".to_string(),
-                    description: "Proxmox node name".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Proxmox node name".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "vmid".to_string(),
                     display_name: "Container ID".to_string(),
-                    description: "LXC container ID".to_string(),
-                    parameter_type: ParameterType::Number,
-                    required: false,
+                    description: Some("LXC container ID".to_string()),
+                    param_type: ParameterType::Number,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "container_status".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "container_status".to_string(),
+                    display_name: "Container Status".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let host = context.get_parameter("host")
-            .and_then(|v| v.as_string())
-            .ok_or("Proxmox host is required")?;
-        
-        let port = context.get_parameter("port")
-            .and_then(|v| v.as_number())
-            .unwrap_or(8006.0) as u16;
-        
-        let username = context.get_parameter("username")
-            .and_then(|v| v.as_string())
-            .ok_or("Username is required")?;
-        
-        let password = context.get_parameter("password")
-            .and_then(|v| v.as_string())
-            .ok_or("Password is required")?;
-        
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("list".to_string());
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    fn supports_connection_test(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self, context: &ExecutionContext) -> Result<String> {
+        test_proxmox_connection(&context.input).await
+    }
+
+    fn dynamic_option_parameters(&self) -> Vec<&'static str> {
+        vec!["node"]
+    }
 
-        let client = reqwest::Client::builder()
-            .danger_accept_invalid_certs(true)
-            .build()?;
+    async fn load_options(&self, parameter: &str, context: &ExecutionContext) -> Result<Vec<ParameterOption>> {
+        match parameter {
+            "node" => load_node_options(&context.input).await,
+            other => Err(param_error(format!("No options to load for '{}'", other))),
+        }
+    }
 
-        let base_url = format!("https://{}:{}/api2/json", host, port);
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let (client, base_url, username, password) = connection(&context.input)?;
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list");
 
         // Authenticate
         let auth_response = client
             .post(&format!("{}/access/ticket", base_url))
             .form(&[
-                ("username", username.as_str()),
-                ("password", password.as_str()),
+                ("username", username),
+                ("password", password),
             ])
             .send()
-            .await?;
+            .await
+            .map_err(network_error)?;
 
-        let auth_data: serde_json::Value = auth_response.json().await?;
+        let auth_data: serde_json::Value = auth_response.json().await.map_err(network_error)?;
         let ticket = auth_data["data"]["ticket"]
             .as_str()
-            .ok_or("Failed to get authentication ticket")?;
+            .ok_or_else(|| GhostFlowError::AuthenticationError {
+                message: "Failed to get authentication ticket".to_string(),
+            })?;
         let csrf_token = auth_data["data"]["CSRFPreventionToken"]
             .as_str()
-            .ok_or("Failed to get CSRF token")?;
+            .ok_or_else(|| GhostFlowError::AuthenticationError {
+                message: "Failed to get CSRF token".to_string(),
+            })?;
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "list" => {
-                let url = if let Some(node) = context.get_parameter("node").and_then(|v| v.as_string()) {
+                let url = if let Some(node) = context.input.get("node").and_then(|v| v.as_str()) {
                     format!("{}/nodes/{}/lxc", base_url, node)
                 } else {
                     // List all containers across all nodes
@@ -498,9 +1039,10 @@ impl Node for ProxmoxContainerNode {
                         .get(&format!("{}/nodes", base_url))
                         .header("Cookie", format!("PVEAuthCookie={}", ticket))
                         .send()
-                        .await?;
+                        .await
+                        .map_err(network_error)?;
 
-                    let nodes_data: serde_json::Value = nodes_response.json().await?;
+                    let nodes_data: serde_json::Value = nodes_response.json().await.map_err(network_error)?;
                     let mut all_containers = Vec::new();
 
                     if let Some(nodes) = nodes_data["data"].as_array() {
@@ -510,7 +1052,8 @@ impl Node for ProxmoxContainerNode {
                                     .get(&format!("{}/nodes/{}/lxc", base_url, node_name))
                                     .header("Cookie", format!("PVEAuthCookie={}", ticket))
                                     .send()
-                                    .await?;
+                                    .await
+                                    .map_err(network_error)?;
 
                                 if let Ok(containers_data) = containers_response.json::<serde_json::Value>().await {
                                     if let Some(containers) = containers_data["data"].as_array() {
@@ -524,11 +1067,11 @@ impl Node for ProxmoxContainerNode {
                     }
 
                     return Ok({
-                        let mut outputs = HashMap::new();
-                        outputs.insert("result".to_string(), Value::Object(json!({
+                        let mut outputs = serde_json::Map::new();
+                        outputs.insert("result".to_string(), json!({
                             "data": all_containers
-                        })));
-                        outputs
+                        }));
+                        Value::Object(outputs)
                     });
                 };
 
@@ -536,21 +1079,22 @@ impl Node for ProxmoxContainerNode {
                     .get(&url)
                     .header("Cookie", format!("PVEAuthCookie={}", ticket))
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "start" | "stop" | "restart" => {
-                let node = context.get_parameter("node")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Node is required for container operations")?;
+                let node = context.input.get("node")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Node is required for container operations"))?;
                 
-                let vmid = context.get_parameter("vmid")
-                    .and_then(|v| v.as_number())
-                    .ok_or("Container ID is required for container operations")? as u32;
+                let vmid = context.input.get("vmid")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| param_error("Container ID is required for container operations"))? as u32;
 
-                let action = match operation.as_str() {
+                let action = match operation {
                     "restart" => "reboot",
                     op => op,
                 };
@@ -560,7 +1104,8 @@ impl Node for ProxmoxContainerNode {
                     .header("Cookie", format!("PVEAuthCookie={}", ticket))
                     .header("CSRFPreventionToken", csrf_token)
                     .send()
-                    .await?;
+                    .await
+                    .map_err(network_error)?;
 
                 json!({
                     "success": response.status().is_success(),
@@ -571,12 +1116,12 @@ impl Node for ProxmoxContainerNode {
                 })
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
         
         if let Some(status_data) = result.get("data") {
             if let Some(status) = status_data.get("status").and_then(|s| s.as_str()) {
@@ -584,6 +1129,219 @@ impl Node for ProxmoxContainerNode {
             }
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use std::time::Duration;
+    use wiremock::matchers::{header, method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const UPID: &str = "UPID:pve1:0000A1B2:01234567:65A1B2C3:qmstart:101:root@pam:";
+
+    async fn task_server(final_status: Value) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/nodes/pve1/tasks/UPID%3A.+/status$"))
+            .and(header("Cookie", "PVEAuthCookie=ticket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "upid": UPID, "status": "running" }
+            })))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/nodes/pve1/tasks/UPID%3A.+/status$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": final_status })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_connection_reports_version() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/access/ticket$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "ticket": "ticket", "CSRFPreventionToken": "csrf" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/version$"))
+            .and(header("Cookie", "PVEAuthCookie=ticket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "version": "8.2.4", "release": "8.2" }
+            })))
+            .mount(&server)
+            .await;
+
+        let message = proxmox_version(&reqwest::Client::new(), &server.uri(), "root@pam", "secret")
+            .await
+            .unwrap();
+        assert_eq!(message, "Connected to Proxmox VE 8.2.4");
+
+        // base_url replaces host and port, e.g. for a proxy in front of the API
+        let input = json!({ "base_url": format!("{}/", server.uri()), "username": "root@pam", "password": "secret" });
+        assert_eq!(ProxmoxVMNode::new().test_connection(&context(input)).await.unwrap(), message);
+    }
+
+    #[tokio::test]
+    async fn test_node_options_list_cluster_nodes() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/access/ticket$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "ticket": "ticket", "CSRFPreventionToken": "csrf" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/nodes$"))
+            .and(header("Cookie", "PVEAuthCookie=ticket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{ "node": "pve2", "status": "offline" }, { "node": "pve1", "status": "online" }]
+            })))
+            .mount(&server)
+            .await;
+
+        let options = node_options(&reqwest::Client::new(), &server.uri(), "root@pam", "secret")
+            .await
+            .unwrap();
+        let pairs: Vec<(Value, &str)> = options.iter().map(|o| (o.value.clone(), o.label.as_str())).collect();
+        assert_eq!(pairs, vec![(json!("pve1"), "pve1 (online)"), (json!("pve2"), "pve2 (offline)")]);
+    }
+
+    #[tokio::test]
+    async fn test_connection_with_bad_password_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/access/ticket$"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let error = proxmox_version(&reqwest::Client::new(), &server.uri(), "root@pam", "wrong")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, GhostFlowError::AuthenticationError { .. }));
+        // Missing connection details never reach the network
+        assert!(ProxmoxVMNode::new().test_connection(&context(json!({ "username": "root@pam" }))).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_task_to_stop() {
+        let server = task_server(json!({ "upid": UPID, "status": "stopped", "exitstatus": "OK" })).await;
+
+        let task = wait_for_task(
+            &reqwest::Client::new(),
+            &server.uri(),
+            "ticket",
+            "pve1",
+            UPID,
+            Duration::from_secs(5),
+            Duration::from_millis(10),
+            &context(json!({})),
+            &EventBus::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(task["status"], "stopped");
+        assert_eq!(task["exitstatus"], "OK");
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_failed_task_surfaces_exit_status() {
+        let server = task_server(json!({
+            "upid": UPID,
+            "status": "stopped",
+            "exitstatus": "VM 101 already running"
+        }))
+        .await;
+
+        let error = wait_for_task(
+            &reqwest::Client::new(),
+            &server.uri(),
+            "ticket",
+            "pve1",
+            UPID,
+            Duration::from_secs(5),
+            Duration::from_millis(10),
+            &context(json!({})),
+            &EventBus::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.to_string().contains("VM 101 already running"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_task_still_running_times_out() {
+        let server = task_server(json!({ "upid": UPID, "status": "running" })).await;
+
+        let error = wait_for_task(
+            &reqwest::Client::new(),
+            &server.uri(),
+            "ticket",
+            "pve1",
+            UPID,
+            Duration::from_millis(50),
+            Duration::from_millis(10),
+            &context(json!({})),
+            &EventBus::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(error, GhostFlowError::TimeoutError { timeout_ms: 50 }));
+    }
+
+    fn form_value<'a>(form: &'a [(&'static str, String)], key: &str) -> Option<&'a str> {
+        form.iter().find(|(k, _)| *k == key).map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_vzdump_payload() {
+        let form = vzdump_form(101, &json!({ "storage": "pbs", "mode": "stop", "compress": "gzip" })).unwrap();
+        assert_eq!(form_value(&form, "vmid"), Some("101"));
+        assert_eq!(form_value(&form, "storage"), Some("pbs"));
+        assert_eq!(form_value(&form, "mode"), Some("stop"));
+        assert_eq!(form_value(&form, "compress"), Some("gzip"));
+
+        let defaults = vzdump_form(101, &json!({ "compress": 0 })).unwrap();
+        assert_eq!(form_value(&defaults, "mode"), Some("snapshot"));
+        assert_eq!(form_value(&defaults, "compress"), Some("0"));
+        assert_eq!(form_value(&defaults, "storage"), None);
+
+        assert!(vzdump_form(101, &json!({ "mode": "live" })).is_err());
+    }
+
+    #[test]
+    fn test_restore_payload() {
+        let form = restore_form(
+            105,
+            &json!({ "archive": "local:backup/vzdump-qemu-101.vma.zst", "force": true }),
+        )
+        .unwrap();
+        assert_eq!(form_value(&form, "archive"), Some("local:backup/vzdump-qemu-101.vma.zst"));
+        assert_eq!(form_value(&form, "force"), Some("1"));
+
+        assert!(restore_form(105, &json!({})).is_err());
+    }
+
+    #[test]
+    fn test_task_id_extraction() {
+        let body = json!({ "data": "UPID:pve1:0000A1B2:01234567:65A1B2C3:vzdump:101:root@pam:" });
+        assert_eq!(task_upid(&body), Some("UPID:pve1:0000A1B2:01234567:65A1B2C3:vzdump:101:root@pam:"));
+        assert_eq!(task_upid(&json!({ "data": null })), None);
+        assert_eq!(task_upid(&json!({ "data": "not-a-task" })), None);
+    }
+}
@@ -0,0 +1,625 @@
+use super::{param_error, validate_required};
+use aws_sdk_s3::config::{
+    BehaviorVersion, Credentials, Region, RequestChecksumCalculation, ResponseChecksumValidation,
+};
+use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    BinaryData, DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const OPERATIONS: [&str; 5] = ["get_object", "put_object", "list_objects", "delete_object", "presigned_url"];
+
+/// Longest expiry S3 accepts for a SigV4 presigned URL (7 days)
+const MAX_PRESIGN_EXPIRY_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3Node;
+
+/// Access keys either given inline or read from a credential reference: the
+/// name of an execution secret holding
+/// `{"access_key_id": .., "secret_access_key": .., "session_token": ..}`.
+/// Shared by the AWS nodes.
+#[derive(Debug, Deserialize)]
+pub(super) struct AwsCredentials {
+    pub(super) access_key_id: String,
+    pub(super) secret_access_key: String,
+    #[serde(default)]
+    pub(super) session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    pub(super) fn from_context(context: &ExecutionContext) -> Result<Self> {
+        let params = &context.input;
+
+        if let Some(reference) = params.get("credential").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            let secret = context
+                .secrets
+                .get(reference)
+                .ok_or_else(|| param_error(format!("Credential '{}' not found", reference)))?;
+            return serde_json::from_str(secret)
+                .map_err(|e| param_error(format!("Credential '{}' is not an AWS key pair: {}", reference, e)));
+        }
+
+        let text = |key: &str| params.get(key).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+        match (text("access_key_id"), text("secret_access_key")) {
+            (Some(access_key_id), Some(secret_access_key)) => Ok(Self {
+                access_key_id: access_key_id.to_string(),
+                secret_access_key: secret_access_key.to_string(),
+                session_token: text("session_token").map(str::to_string),
+            }),
+            _ => Err(param_error(
+                "AWS requires access_key_id and secret_access_key, or a credential reference",
+            )),
+        }
+    }
+}
+
+fn client(context: &ExecutionContext) -> Result<Client> {
+    let params = &context.input;
+    let credentials = AwsCredentials::from_context(context)?;
+    let region = params.get("region").and_then(|v| v.as_str()).unwrap_or("us-east-1");
+    let endpoint = params.get("endpoint_url").and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+
+    let mut config = aws_sdk_s3::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new(region.to_string()))
+        .credentials_provider(Credentials::new(
+            credentials.access_key_id,
+            credentials.secret_access_key,
+            credentials.session_token,
+            None,
+            "ghostflow",
+        ));
+
+    if let Some(endpoint) = endpoint {
+        // S3-compatible stores (MinIO, Ceph, R2) generally want path-style
+        // addressing and may reject the newer default integrity checksums
+        let path_style = params.get("force_path_style").and_then(|v| v.as_bool()).unwrap_or(true);
+        config = config
+            .endpoint_url(endpoint)
+            .force_path_style(path_style)
+            .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+            .response_checksum_validation(ResponseChecksumValidation::WhenRequired);
+    }
+
+    Ok(Client::from_conf(config.build()))
+}
+
+fn s3_error(error: impl std::error::Error) -> GhostFlowError {
+    GhostFlowError::NetworkError(format!("S3 request failed: {}", DisplayErrorContext(error)))
+}
+
+fn required_str<'a>(params: &'a Value, key: &str) -> Result<&'a str> {
+    params
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| param_error(format!("S3 parameter '{}' is required for this operation", key)))
+}
+
+/// Object payload for `put_object`: binary data from the `file` input, or a
+/// plain `content` string.
+fn upload_payload(params: &Value) -> Result<BinaryData> {
+    if let Some(file) = params.get("file").filter(|v| !v.is_null()) {
+        return BinaryData::from_value(file)
+            .ok_or_else(|| param_error("S3 'file' input must be binary data"));
+    }
+    match params.get("content") {
+        Some(Value::String(text)) => Ok(BinaryData::new(text.as_bytes().to_vec()).with_content_type("text/plain")),
+        Some(other) if !other.is_null() => {
+            Ok(BinaryData::new(other.to_string().into_bytes()).with_content_type("application/json"))
+        }
+        _ => Err(param_error("put_object needs a 'file' input or 'content'")),
+    }
+}
+
+#[async_trait]
+impl Node for S3Node {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "aws_s3".to_string(),
+            name: "AWS S3".to_string(),
+            description: "Read, write, list and delete objects in S3 or S3-compatible storage".to_string(),
+            category: NodeCategory::Integration,
+            version: "1.0.0".to_string(),
+            parameters: vec![
+                NodeParameter {
+                    name: "operation".to_string(),
+                    display_name: "Operation".to_string(),
+                    description: Some("Object operation to perform".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("get_object".to_string())),
+                    required: true,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "get_object", "label": "Get Object"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "put_object", "label": "Put Object"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "list_objects", "label": "List Objects"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "delete_object", "label": "Delete Object"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "presigned_url", "label": "Presigned URL"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "region".to_string(),
+                    display_name: "Region".to_string(),
+                    description: Some("AWS region".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("us-east-1".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "bucket".to_string(),
+                    display_name: "Bucket".to_string(),
+                    description: Some("Bucket name".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "key".to_string(),
+                    display_name: "Key".to_string(),
+                    description: Some("Object key (not used by list_objects)".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "access_key_id".to_string(),
+                    display_name: "Access Key ID".to_string(),
+                    description: Some("AWS access key ID".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "secret_access_key".to_string(),
+                    display_name: "Secret Access Key".to_string(),
+                    description: Some("AWS secret access key".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "session_token".to_string(),
+                    display_name: "Session Token".to_string(),
+                    description: Some("Temporary session token, if using STS credentials".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "credential".to_string(),
+                    display_name: "Credential".to_string(),
+                    description: Some("Name of a stored AWS credential to use instead of inline keys".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "endpoint_url".to_string(),
+                    display_name: "Endpoint URL".to_string(),
+                    description: Some("Custom endpoint for S3-compatible storage (MinIO, R2, ...)".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "force_path_style".to_string(),
+                    display_name: "Path-Style Addressing".to_string(),
+                    description: Some("Use bucket-in-path URLs; defaults to on with a custom endpoint".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "content".to_string(),
+                    display_name: "Content".to_string(),
+                    description: Some("Text to upload when no binary file is connected".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "content_type".to_string(),
+                    display_name: "Content Type".to_string(),
+                    description: Some("MIME type for put_object; overrides the file's own".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "prefix".to_string(),
+                    display_name: "Prefix".to_string(),
+                    description: Some("Only list keys starting with this prefix".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_keys".to_string(),
+                    display_name: "Max Keys".to_string(),
+                    description: Some("Maximum number of keys to list".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(1000)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "continuation_token".to_string(),
+                    display_name: "Continuation Token".to_string(),
+                    description: Some("Token from a previous truncated listing".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "presign_method".to_string(),
+                    display_name: "Presign Method".to_string(),
+                    description: Some("Whether the presigned URL downloads or uploads".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("get".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "get", "label": "GET (download)"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "put", "label": "PUT (upload)"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "expires_in".to_string(),
+                    display_name: "Expires In (seconds)".to_string(),
+                    description: Some("Lifetime of the presigned URL, at most 7 days".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(3600)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            inputs: vec![NodePort {
+                name: "file".to_string(),
+                display_name: "File".to_string(),
+                description: Some("Binary payload for put_object".to_string()),
+                data_type: DataType::Binary,
+                required: false,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "file".to_string(),
+                    display_name: "File".to_string(),
+                    description: Some("Object downloaded by get_object".to_string()),
+                    data_type: DataType::Binary,
+                    required: false,
+                },
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: Some("Operation result".to_string()),
+                    data_type: DataType::Object,
+                    required: true,
+                },
+            ],
+            icon: Some("aws-s3".to_string()),
+            color: Some("#569a31".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)?;
+
+        let operation = context.input.get("operation").and_then(|v| v.as_str()).unwrap_or("get_object");
+        if !OPERATIONS.contains(&operation) {
+            return Err(param_error(format!("Unsupported S3 operation: {}", operation)));
+        }
+        if operation != "list_objects" {
+            required_str(&context.input, "key")?;
+        }
+        AwsCredentials::from_context(context)?;
+        Ok(())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let operation = params.get("operation").and_then(|v| v.as_str()).unwrap_or("get_object");
+        let bucket = required_str(params, "bucket")?;
+        let client = client(&context)?;
+
+        match operation {
+            "get_object" => {
+                let key = required_str(params, "key")?;
+                let output = client.get_object().bucket(bucket).key(key).send().await.map_err(s3_error)?;
+
+                let content_type = output.content_type().map(str::to_string);
+                let etag = output.e_tag().map(str::to_string);
+                let data = output.body.collect().await.map_err(s3_error)?.into_bytes();
+
+                let mut file = BinaryData::new(data.to_vec())
+                    .with_filename(key.rsplit('/').next().unwrap_or(key));
+                if let Some(content_type) = content_type {
+                    file = file.with_content_type(content_type);
+                }
+
+                Ok(json!({
+                    "file": file.to_value(),
+                    "result": {
+                        "bucket": bucket,
+                        "key": key,
+                        "size": data.len(),
+                        "etag": etag,
+                    }
+                }))
+            }
+            "put_object" => {
+                let key = required_str(params, "key")?;
+                let payload = upload_payload(params)?;
+                let content_type = params
+                    .get("content_type")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+                    .or(payload.content_type);
+                let size = payload.data.len();
+
+                let output = client
+                    .put_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .set_content_type(content_type)
+                    .body(ByteStream::from(payload.data))
+                    .send()
+                    .await
+                    .map_err(s3_error)?;
+
+                Ok(json!({
+                    "result": {
+                        "bucket": bucket,
+                        "key": key,
+                        "size": size,
+                        "etag": output.e_tag(),
+                        "version_id": output.version_id(),
+                    }
+                }))
+            }
+            "list_objects" => {
+                let max_keys = params.get("max_keys").and_then(|v| v.as_i64()).unwrap_or(1000) as i32;
+                let output = client
+                    .list_objects_v2()
+                    .bucket(bucket)
+                    .set_prefix(params.get("prefix").and_then(|v| v.as_str()).map(str::to_string))
+                    .set_continuation_token(
+                        params.get("continuation_token").and_then(|v| v.as_str()).map(str::to_string),
+                    )
+                    .max_keys(max_keys)
+                    .send()
+                    .await
+                    .map_err(s3_error)?;
+
+                let objects: Vec<Value> = output
+                    .contents()
+                    .iter()
+                    .map(|object| {
+                        json!({
+                            "key": object.key(),
+                            "size": object.size(),
+                            "etag": object.e_tag(),
+                            "last_modified": object.last_modified().map(|t| t.to_string()),
+                        })
+                    })
+                    .collect();
+
+                Ok(json!({
+                    "result": {
+                        "bucket": bucket,
+                        "count": objects.len(),
+                        "objects": objects,
+                        "is_truncated": output.is_truncated().unwrap_or(false),
+                        "next_continuation_token": output.next_continuation_token(),
+                    }
+                }))
+            }
+            "delete_object" => {
+                let key = required_str(params, "key")?;
+                client.delete_object().bucket(bucket).key(key).send().await.map_err(s3_error)?;
+
+                Ok(json!({
+                    "result": {
+                        "bucket": bucket,
+                        "key": key,
+                        "deleted": true,
+                    }
+                }))
+            }
+            "presigned_url" => {
+                let key = required_str(params, "key")?;
+                let expires_in = params.get("expires_in").and_then(|v| v.as_u64()).unwrap_or(3600);
+                if expires_in == 0 || expires_in > MAX_PRESIGN_EXPIRY_SECS {
+                    return Err(param_error(format!(
+                        "expires_in must be between 1 and {} seconds",
+                        MAX_PRESIGN_EXPIRY_SECS
+                    )));
+                }
+                let presigning = PresigningConfig::expires_in(Duration::from_secs(expires_in)).map_err(s3_error)?;
+
+                let method = params.get("presign_method").and_then(|v| v.as_str()).unwrap_or("get");
+                let request = match method {
+                    "get" => client
+                        .get_object()
+                        .bucket(bucket)
+                        .key(key)
+                        .presigned(presigning)
+                        .await
+                        .map_err(s3_error)?,
+                    "put" => client
+                        .put_object()
+                        .bucket(bucket)
+                        .key(key)
+                        .presigned(presigning)
+                        .await
+                        .map_err(s3_error)?,
+                    other => return Err(param_error(format!("Unsupported presign method: {}", other))),
+                };
+
+                Ok(json!({
+                    "result": {
+                        "bucket": bucket,
+                        "key": key,
+                        "method": request.method(),
+                        "url": request.uri(),
+                        "expires_in": expires_in,
+                    }
+                }))
+            }
+            other => Err(param_error(format!("Unsupported S3 operation: {}", other))),
+        }
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    /// Minimal path-style object store: PUT stores, GET returns what was stored
+    #[derive(Clone, Default)]
+    struct MemoryBucket {
+        objects: Arc<Mutex<HashMap<String, (Vec<u8>, Option<String>)>>>,
+    }
+
+    impl Respond for MemoryBucket {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let path = request.url.path().to_string();
+            let mut objects = self.objects.lock().unwrap();
+            match request.method.as_str() {
+                "PUT" => {
+                    let content_type = request
+                        .headers
+                        .get("content-type")
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string);
+                    objects.insert(path, (request.body.clone(), content_type));
+                    ResponseTemplate::new(200).insert_header("ETag", "\"abc123\"")
+                }
+                "GET" => match objects.get(&path) {
+                    Some((body, content_type)) => {
+                        let mut response = ResponseTemplate::new(200)
+                            .insert_header("ETag", "\"abc123\"")
+                            .set_body_bytes(body.clone());
+                        if let Some(content_type) = content_type {
+                            response = response.insert_header("Content-Type", content_type.as_str());
+                        }
+                        response
+                    }
+                    None => ResponseTemplate::new(404),
+                },
+                _ => ResponseTemplate::new(405),
+            }
+        }
+    }
+
+    fn params(server: &MockServer, operation: &str) -> Value {
+        json!({
+            "operation": operation,
+            "bucket": "backups",
+            "key": "vms/101/config.png",
+            "region": "us-east-1",
+            "endpoint_url": server.uri(),
+            "access_key_id": "AKIDEXAMPLE",
+            "secret_access_key": "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_put_then_get_round_trips_binary() {
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::path_regex("^/backups/.+"))
+            .respond_with(MemoryBucket::default())
+            .mount(&server)
+            .await;
+
+        let payload = BinaryData::new(vec![0u8, 1, 2, 254, 255]).with_content_type("image/png");
+        let mut put = params(&server, "put_object");
+        put["file"] = payload.to_value();
+        let stored = S3Node.execute(context(put)).await.unwrap();
+        assert_eq!(stored["result"]["size"], 5);
+        assert_eq!(stored["result"]["etag"], "\"abc123\"");
+
+        let fetched = S3Node.execute(context(params(&server, "get_object"))).await.unwrap();
+        let file = BinaryData::from_value(&fetched["file"]).unwrap();
+        assert_eq!(file.data, payload.data);
+        assert_eq!(file.content_type.as_deref(), Some("image/png"));
+        assert_eq!(file.filename.as_deref(), Some("config.png"));
+    }
+
+    #[tokio::test]
+    async fn test_presigned_url_is_signed_locally() {
+        let server = MockServer::start().await;
+        let mut input = params(&server, "presigned_url");
+        input["expires_in"] = json!(600);
+
+        let result = S3Node.execute(context(input)).await.unwrap();
+        let url = result["result"]["url"].as_str().unwrap();
+        assert!(url.starts_with(&format!("{}/backups/vms/101/config.png?", server.uri())), "{}", url);
+        assert!(url.contains("X-Amz-Expires=600"));
+        assert!(url.contains("X-Amz-Signature="));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_credential_reference_is_read_from_secrets() {
+        let mut ctx = context(json!({
+            "operation": "delete_object",
+            "bucket": "backups",
+            "key": "old.tar",
+            "credential": "aws-prod",
+        }));
+        assert!(S3Node.validate(&ctx).await.is_err());
+
+        ctx.secrets.insert(
+            "aws-prod".to_string(),
+            json!({ "access_key_id": "AKID", "secret_access_key": "secret" }).to_string(),
+        );
+        assert!(S3Node.validate(&ctx).await.is_ok());
+    }
+}
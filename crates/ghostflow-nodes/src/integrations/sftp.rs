@@ -0,0 +1,857 @@
+use super::{param_error, validate_required};
+use crate::FileNodeConfig;
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    BinaryData, DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use russh::client;
+use russh_keys::key::PublicKey;
+use russh_sftp::client::SftpSession;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+
+const OPERATIONS: [&str; 4] = ["upload", "download", "list", "delete"];
+
+/// Deployment-level settings for the SFTP node. A flow's
+/// `known_hosts_path` is resolved inside `known_hosts_dir`, so trust on
+/// first use cannot append host keys to arbitrary files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SftpNodeConfig {
+    pub known_hosts_dir: PathBuf,
+}
+
+impl Default for SftpNodeConfig {
+    fn default() -> Self {
+        Self {
+            known_hosts_dir: std::env::var("GHOSTFLOW_KNOWN_HOSTS_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("./known_hosts")),
+        }
+    }
+}
+
+impl SftpNodeConfig {
+    /// The flow's `known_hosts_path` inside the configured directory, or
+    /// ~/.ssh/known_hosts when the flow names none
+    async fn known_hosts_path(&self, params: &Value) -> Result<PathBuf> {
+        if let Some(path) = params.get("known_hosts_path").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            return FileNodeConfig::with_root(&self.known_hosts_dir).resolve(path).await;
+        }
+        std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".ssh").join("known_hosts"))
+            .ok_or_else(|| param_error("known_hosts_path is required when HOME is not set"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SftpNode {
+    config: SftpNodeConfig,
+}
+
+impl SftpNode {
+    pub fn new() -> Self {
+        Self {
+            config: SftpNodeConfig::default(),
+        }
+    }
+
+    pub fn with_config(config: SftpNodeConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Default for SftpNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HostKeyMode {
+    /// The server's key must already be in the known_hosts file
+    KnownHosts,
+    /// Record an unknown server's key on first connect; reject a changed key
+    TrustOnFirstUse,
+}
+
+impl HostKeyMode {
+    fn from_params(params: &Value) -> Result<Self> {
+        match params.get("host_key_check").and_then(|v| v.as_str()).unwrap_or("known_hosts") {
+            "known_hosts" => Ok(Self::KnownHosts),
+            "trust_on_first_use" => Ok(Self::TrustOnFirstUse),
+            other => Err(param_error(format!("Unsupported SFTP host_key_check: {}", other))),
+        }
+    }
+}
+
+/// Verifies the server's host key against a known_hosts file. Why a key was
+/// refused is kept in `rejection`, since russh only reports that it was.
+struct HostKeyCheck {
+    host: String,
+    port: u16,
+    mode: HostKeyMode,
+    known_hosts: PathBuf,
+    rejection: Arc<Mutex<Option<String>>>,
+}
+
+impl HostKeyCheck {
+    fn verify(&self, key: &PublicKey) -> std::result::Result<(), String> {
+        let path = self.known_hosts.display();
+        let known = if self.known_hosts.exists() {
+            russh_keys::check_known_hosts_path(&self.host, self.port, key, &self.known_hosts)
+        } else {
+            Ok(false)
+        };
+
+        match known {
+            Ok(true) => Ok(()),
+            Ok(false) if self.mode == HostKeyMode::TrustOnFirstUse => {
+                info!("Trusting new SFTP host key {} for {}:{}", key.fingerprint(), self.host, self.port);
+                russh_keys::learn_known_hosts_path(&self.host, self.port, key, &self.known_hosts)
+                    .map_err(|e| format!("Could not record host key in {}: {}", path, e))
+            }
+            Ok(false) => Err(format!(
+                "Host key {} for {}:{} is not in {}",
+                key.fingerprint(),
+                self.host,
+                self.port,
+                path
+            )),
+            Err(russh_keys::Error::KeyChanged { line }) => Err(format!(
+                "Host key for {}:{} does not match {} line {}; refusing to connect",
+                self.host, self.port, path, line
+            )),
+            Err(e) => Err(format!("Could not read {}: {}", path, e)),
+        }
+    }
+}
+
+#[async_trait]
+impl client::Handler for HostKeyCheck {
+    type Error = russh::Error;
+
+    async fn check_server_key(&mut self, server_public_key: &PublicKey) -> std::result::Result<bool, Self::Error> {
+        match self.verify(server_public_key) {
+            Ok(()) => Ok(true),
+            Err(reason) => {
+                warn!("{}", reason);
+                *self.rejection.lock().unwrap() = Some(reason);
+                Ok(false)
+            }
+        }
+    }
+}
+
+fn sftp_error(error: impl std::fmt::Display) -> GhostFlowError {
+    GhostFlowError::NetworkError(format!("SFTP request failed: {}", error))
+}
+
+fn required_str<'a>(params: &'a Value, key: &str) -> Result<&'a str> {
+    params
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| param_error(format!("SFTP parameter '{}' is required for this operation", key)))
+}
+
+/// Upload payload: binary data from the `file` input, or a plain `content`
+/// string.
+fn upload_payload(params: &Value) -> Result<BinaryData> {
+    if let Some(file) = params.get("file").filter(|v| !v.is_null()) {
+        return BinaryData::from_value(file).ok_or_else(|| param_error("SFTP 'file' input must be binary data"));
+    }
+    match params.get("content") {
+        Some(Value::String(text)) => Ok(BinaryData::new(text.as_bytes().to_vec())),
+        _ => Err(param_error("upload needs a 'file' input or 'content'")),
+    }
+}
+
+/// Connect, verify the host key, authenticate and open the SFTP subsystem.
+async fn connect(config: &SftpNodeConfig, params: &Value) -> Result<(client::Handle<HostKeyCheck>, SftpSession)> {
+    let host = required_str(params, "host")?;
+    let port = params.get("port").and_then(|v| v.as_u64()).unwrap_or(22) as u16;
+    let username = required_str(params, "username")?;
+    let rejection = Arc::new(Mutex::new(None));
+    let check = HostKeyCheck {
+        host: host.to_string(),
+        port,
+        mode: HostKeyMode::from_params(params)?,
+        known_hosts: config.known_hosts_path(params).await?,
+        rejection: rejection.clone(),
+    };
+
+    let config = Arc::new(client::Config::default());
+    let mut session = match client::connect(config, (host, port), check).await {
+        Ok(session) => session,
+        Err(e) => {
+            let reason = rejection.lock().unwrap().take();
+            return Err(match reason {
+                Some(reason) => GhostFlowError::AuthenticationError { message: reason },
+                None => sftp_error(e),
+            });
+        }
+    };
+
+    let text = |key: &str| params.get(key).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+    let authenticated = match (text("private_key"), text("password")) {
+        (Some(pem), _) => {
+            let key = russh_keys::decode_secret_key(pem, text("passphrase"))
+                .map_err(|e| param_error(format!("Invalid SFTP private key: {}", e)))?;
+            session.authenticate_publickey(username, Arc::new(key)).await.map_err(sftp_error)?
+        }
+        (None, Some(password)) => session.authenticate_password(username, password).await.map_err(sftp_error)?,
+        (None, None) => return Err(param_error("SFTP needs a password or a private_key")),
+    };
+    if !authenticated {
+        return Err(GhostFlowError::AuthenticationError {
+            message: format!("SFTP server {} rejected the credentials for {}", host, username),
+        });
+    }
+
+    let channel = session.channel_open_session().await.map_err(sftp_error)?;
+    channel.request_subsystem(true, "sftp").await.map_err(sftp_error)?;
+    let sftp = SftpSession::new(channel.into_stream()).await.map_err(sftp_error)?;
+    Ok((session, sftp))
+}
+
+async fn run_operation(sftp: &SftpSession, operation: &str, params: &Value) -> Result<Value> {
+    match operation {
+        "upload" => {
+            let path = required_str(params, "remote_path")?;
+            let payload = upload_payload(params)?;
+            let mut file = sftp.create(path).await.map_err(sftp_error)?;
+            file.write_all(&payload.data).await.map_err(sftp_error)?;
+            file.shutdown().await.map_err(sftp_error)?;
+
+            Ok(json!({ "result": { "path": path, "size": payload.data.len() } }))
+        }
+        "download" => {
+            let path = required_str(params, "remote_path")?;
+            let data = sftp.read(path).await.map_err(sftp_error)?;
+            let size = data.len();
+            let file = BinaryData::new(data).with_filename(path.rsplit('/').next().unwrap_or(path));
+
+            Ok(json!({ "file": file.to_value(), "result": { "path": path, "size": size } }))
+        }
+        "list" => {
+            let path = params.get("remote_path").and_then(|v| v.as_str()).filter(|s| !s.is_empty()).unwrap_or(".");
+            let mut entries: Vec<Value> = sftp
+                .read_dir(path)
+                .await
+                .map_err(sftp_error)?
+                .filter(|entry| entry.file_name() != "." && entry.file_name() != "..")
+                .map(|entry| {
+                    let metadata = entry.metadata();
+                    json!({
+                        "name": entry.file_name(),
+                        "size": metadata.size,
+                        "is_dir": metadata.is_dir(),
+                        "modified": metadata.mtime,
+                    })
+                })
+                .collect();
+            entries.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+            Ok(json!({ "result": { "path": path, "count": entries.len(), "entries": entries } }))
+        }
+        "delete" => {
+            let path = required_str(params, "remote_path")?;
+            sftp.remove_file(path).await.map_err(sftp_error)?;
+
+            Ok(json!({ "result": { "path": path, "deleted": true } }))
+        }
+        other => Err(param_error(format!("Unsupported SFTP operation: {}", other))),
+    }
+}
+
+#[async_trait]
+impl Node for SftpNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "sftp".to_string(),
+            name: "SFTP".to_string(),
+            description: "Upload, download, list and delete files on an SFTP server".to_string(),
+            category: NodeCategory::Integration,
+            version: "1.0.0".to_string(),
+            parameters: vec![
+                NodeParameter {
+                    name: "operation".to_string(),
+                    display_name: "Operation".to_string(),
+                    description: Some("File operation to perform".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("download".to_string())),
+                    required: true,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "upload", "label": "Upload"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "download", "label": "Download"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "list", "label": "List Directory"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "delete", "label": "Delete"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "host".to_string(),
+                    display_name: "Host".to_string(),
+                    description: None,
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "port".to_string(),
+                    display_name: "Port".to_string(),
+                    description: None,
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(22)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "username".to_string(),
+                    display_name: "Username".to_string(),
+                    description: None,
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "password".to_string(),
+                    display_name: "Password".to_string(),
+                    description: Some("Used when no private key is given".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "private_key".to_string(),
+                    display_name: "Private Key".to_string(),
+                    description: Some("OpenSSH or PEM private key".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "passphrase".to_string(),
+                    display_name: "Key Passphrase".to_string(),
+                    description: None,
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "remote_path".to_string(),
+                    display_name: "Remote Path".to_string(),
+                    description: Some("File to transfer or delete, or directory to list".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "content".to_string(),
+                    display_name: "Content".to_string(),
+                    description: Some("Text to upload when no file is connected".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "host_key_check".to_string(),
+                    display_name: "Host Key Check".to_string(),
+                    description: Some("How the server's host key is verified".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("known_hosts".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "known_hosts", "label": "Known Hosts Only"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "trust_on_first_use", "label": "Trust on First Use"}"#)
+                            .unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "known_hosts_path".to_string(),
+                    display_name: "Known Hosts File".to_string(),
+                    description: Some(
+                        "Relative to the server's known hosts directory; defaults to ~/.ssh/known_hosts".to_string(),
+                    ),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "timeout".to_string(),
+                    display_name: "Timeout (seconds)".to_string(),
+                    description: Some("Limit for connecting and the transfer together".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(60)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            inputs: vec![NodePort {
+                name: "file".to_string(),
+                display_name: "File".to_string(),
+                description: Some("Binary file to upload".to_string()),
+                data_type: DataType::Binary,
+                required: false,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "file".to_string(),
+                    display_name: "File".to_string(),
+                    description: Some("Downloaded file".to_string()),
+                    data_type: DataType::Binary,
+                    required: false,
+                },
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: Some("Operation result".to_string()),
+                    data_type: DataType::Object,
+                    required: true,
+                },
+            ],
+            icon: Some("server".to_string()),
+            color: Some("#4b5563".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)?;
+
+        let params = &context.input;
+        let operation = params.get("operation").and_then(|v| v.as_str()).unwrap_or("download");
+        if !OPERATIONS.contains(&operation) {
+            return Err(param_error(format!("Unsupported SFTP operation: {}", operation)));
+        }
+        if operation != "list" {
+            required_str(params, "remote_path")?;
+        }
+        HostKeyMode::from_params(params)?;
+        Ok(())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let operation = params.get("operation").and_then(|v| v.as_str()).unwrap_or("download");
+        let timeout = Duration::from_secs(params.get("timeout").and_then(|v| v.as_u64()).unwrap_or(60).max(1));
+
+        let work = async {
+            let (session, sftp) = connect(&self.config, params).await?;
+            let outcome = run_operation(&sftp, operation, params).await;
+            let _ = sftp.close().await;
+            let _ = session.disconnect(russh::Disconnect::ByApplication, "", "en").await;
+            outcome
+        };
+        tokio::time::timeout(timeout, work).await.map_err(|_| GhostFlowError::TimeoutError {
+            timeout_ms: timeout.as_millis() as u64,
+        })?
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use russh::server::{self, Auth, Msg, Session};
+    use russh::{Channel, ChannelId};
+    use russh_sftp::protocol::{
+        Attrs, Data, File, FileAttributes, Handle, Name, OpenFlags, Status, StatusCode, Version,
+    };
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::net::SocketAddr;
+    use std::path::Path;
+    use tokio::net::TcpListener;
+    use uuid::Uuid;
+
+    type Files = Arc<Mutex<BTreeMap<String, Vec<u8>>>>;
+
+    /// In-memory SFTP file system: a flat map of absolute paths to contents,
+    /// where directories are implied by the paths.
+    struct MemoryFs {
+        files: Files,
+        handles: HashMap<String, String>,
+        listed: HashSet<String>,
+        next_handle: u32,
+    }
+
+    impl MemoryFs {
+        fn new(files: Files) -> Self {
+            Self {
+                files,
+                handles: HashMap::new(),
+                listed: HashSet::new(),
+                next_handle: 0,
+            }
+        }
+
+        fn handle(&mut self, id: u32, path: String) -> Handle {
+            self.next_handle += 1;
+            let handle = self.next_handle.to_string();
+            self.handles.insert(handle.clone(), path);
+            Handle { id, handle }
+        }
+
+        fn path(&self, handle: &str) -> std::result::Result<String, StatusCode> {
+            self.handles.get(handle).cloned().ok_or(StatusCode::Failure)
+        }
+
+        fn attributes(&self, path: &str) -> std::result::Result<FileAttributes, StatusCode> {
+            let files = self.files.lock().unwrap();
+            if let Some(data) = files.get(path) {
+                return Ok(FileAttributes {
+                    size: Some(data.len() as u64),
+                    permissions: Some(0o100644),
+                    ..Default::default()
+                });
+            }
+            let prefix = format!("{}/", path.trim_end_matches('/'));
+            if files.keys().any(|p| p.starts_with(&prefix)) {
+                return Ok(FileAttributes {
+                    permissions: Some(0o040755),
+                    ..Default::default()
+                });
+            }
+            Err(StatusCode::NoSuchFile)
+        }
+    }
+
+    fn ok(id: u32) -> Status {
+        Status {
+            id,
+            status_code: StatusCode::Ok,
+            error_message: "Ok".to_string(),
+            language_tag: "en-US".to_string(),
+        }
+    }
+
+    #[async_trait]
+    impl russh_sftp::server::Handler for MemoryFs {
+        type Error = StatusCode;
+
+        fn unimplemented(&self) -> Self::Error {
+            StatusCode::OpUnsupported
+        }
+
+        async fn init(&mut self, _version: u32, _extensions: HashMap<String, String>) -> std::result::Result<Version, Self::Error> {
+            Ok(Version::new())
+        }
+
+        async fn realpath(&mut self, id: u32, path: String) -> std::result::Result<Name, Self::Error> {
+            Ok(Name { id, files: vec![File::dummy(path)] })
+        }
+
+        async fn open(
+            &mut self,
+            id: u32,
+            filename: String,
+            pflags: OpenFlags,
+            _attrs: FileAttributes,
+        ) -> std::result::Result<Handle, Self::Error> {
+            {
+                let mut files = self.files.lock().unwrap();
+                let exists = files.contains_key(&filename);
+                if pflags.contains(OpenFlags::CREATE) && (pflags.contains(OpenFlags::TRUNCATE) || !exists) {
+                    files.insert(filename.clone(), Vec::new());
+                } else if !exists {
+                    return Err(StatusCode::NoSuchFile);
+                }
+            }
+            Ok(self.handle(id, filename))
+        }
+
+        async fn read(&mut self, id: u32, handle: String, offset: u64, len: u32) -> std::result::Result<Data, Self::Error> {
+            let path = self.path(&handle)?;
+            let files = self.files.lock().unwrap();
+            let data = files.get(&path).ok_or(StatusCode::NoSuchFile)?;
+            let start = offset as usize;
+            if start >= data.len() {
+                return Err(StatusCode::Eof);
+            }
+            let end = (start + len as usize).min(data.len());
+            Ok(Data { id, data: data[start..end].to_vec() })
+        }
+
+        async fn write(&mut self, id: u32, handle: String, offset: u64, data: Vec<u8>) -> std::result::Result<Status, Self::Error> {
+            let path = self.path(&handle)?;
+            let mut files = self.files.lock().unwrap();
+            let file = files.get_mut(&path).ok_or(StatusCode::NoSuchFile)?;
+            let (start, end) = (offset as usize, offset as usize + data.len());
+            if file.len() < end {
+                file.resize(end, 0);
+            }
+            file[start..end].copy_from_slice(&data);
+            Ok(ok(id))
+        }
+
+        async fn close(&mut self, id: u32, handle: String) -> std::result::Result<Status, Self::Error> {
+            self.handles.remove(&handle);
+            Ok(ok(id))
+        }
+
+        async fn fstat(&mut self, id: u32, handle: String) -> std::result::Result<Attrs, Self::Error> {
+            let path = self.path(&handle)?;
+            Ok(Attrs { id, attrs: self.attributes(&path)? })
+        }
+
+        async fn stat(&mut self, id: u32, path: String) -> std::result::Result<Attrs, Self::Error> {
+            Ok(Attrs { id, attrs: self.attributes(&path)? })
+        }
+
+        async fn lstat(&mut self, id: u32, path: String) -> std::result::Result<Attrs, Self::Error> {
+            Ok(Attrs { id, attrs: self.attributes(&path)? })
+        }
+
+        async fn opendir(&mut self, id: u32, path: String) -> std::result::Result<Handle, Self::Error> {
+            Ok(self.handle(id, path))
+        }
+
+        async fn readdir(&mut self, id: u32, handle: String) -> std::result::Result<Name, Self::Error> {
+            // Everything is returned by the first call; the second ends the listing
+            if !self.listed.insert(handle.clone()) {
+                return Err(StatusCode::Eof);
+            }
+            let prefix = format!("{}/", self.path(&handle)?.trim_end_matches('/'));
+            let files = self.files.lock().unwrap();
+            let entries = files
+                .iter()
+                .filter_map(|(path, data)| {
+                    let name = path.strip_prefix(&prefix).filter(|name| !name.contains('/'))?;
+                    let attrs = FileAttributes {
+                        size: Some(data.len() as u64),
+                        permissions: Some(0o100644),
+                        ..Default::default()
+                    };
+                    Some(File::new(name, attrs))
+                })
+                .collect();
+            Ok(Name { id, files: entries })
+        }
+
+        async fn remove(&mut self, id: u32, filename: String) -> std::result::Result<Status, Self::Error> {
+            match self.files.lock().unwrap().remove(&filename) {
+                Some(_) => Ok(ok(id)),
+                None => Err(StatusCode::NoSuchFile),
+            }
+        }
+    }
+
+    /// SSH side of the embedded server: password auth and the sftp subsystem.
+    struct TestServer {
+        files: Files,
+        channels: HashMap<ChannelId, Channel<Msg>>,
+    }
+
+    #[async_trait]
+    impl server::Handler for TestServer {
+        type Error = russh::Error;
+
+        async fn auth_password(&mut self, user: &str, password: &str) -> std::result::Result<Auth, Self::Error> {
+            Ok(if user == "deploy" && password == "hunter2" {
+                Auth::Accept
+            } else {
+                Auth::Reject { proceed_with_methods: None }
+            })
+        }
+
+        async fn channel_open_session(
+            &mut self,
+            channel: Channel<Msg>,
+            _session: &mut Session,
+        ) -> std::result::Result<bool, Self::Error> {
+            self.channels.insert(channel.id(), channel);
+            Ok(true)
+        }
+
+        async fn subsystem_request(
+            &mut self,
+            channel_id: ChannelId,
+            name: &str,
+            session: &mut Session,
+        ) -> std::result::Result<(), Self::Error> {
+            match self.channels.remove(&channel_id) {
+                Some(channel) if name == "sftp" => {
+                    session.channel_success(channel_id);
+                    tokio::spawn(russh_sftp::server::run(channel.into_stream(), MemoryFs::new(self.files.clone())));
+                }
+                _ => session.channel_failure(channel_id),
+            }
+            Ok(())
+        }
+    }
+
+    async fn start_server(files: Files) -> SocketAddr {
+        let config = Arc::new(server::Config {
+            keys: vec![russh_keys::key::KeyPair::generate_ed25519()],
+            ..Default::default()
+        });
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let handler = TestServer { files: files.clone(), channels: HashMap::new() };
+                let config = config.clone();
+                tokio::spawn(async move {
+                    if let Ok(session) = server::run_stream(config, socket, handler).await {
+                        let _ = session.await;
+                    }
+                });
+            }
+        });
+        address
+    }
+
+    fn known_hosts() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ghostflow-sftp-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("known_hosts")
+    }
+
+    /// Node whose known hosts directory holds `known_hosts`
+    fn node(known_hosts: &Path) -> SftpNode {
+        SftpNode::with_config(SftpNodeConfig {
+            known_hosts_dir: known_hosts.parent().unwrap().to_path_buf(),
+        })
+    }
+
+    fn params(address: SocketAddr, known_hosts: &PathBuf, extra: Value) -> Value {
+        let mut params = json!({
+            "host": address.ip().to_string(),
+            "port": address.port(),
+            "username": "deploy",
+            "password": "hunter2",
+            "host_key_check": "trust_on_first_use",
+            "known_hosts_path": known_hosts.to_string_lossy(),
+        });
+        params.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        params
+    }
+
+    #[tokio::test]
+    async fn test_upload_then_download_round_trips_binary() {
+        let files = Files::default();
+        let address = start_server(files.clone()).await;
+        let known_hosts = known_hosts();
+        let report = BinaryData::new(vec![0u8, 159, 146, 150, 255]).with_filename("report.bin");
+
+        let uploaded = node(&known_hosts)
+            .execute(context(params(
+                address,
+                &known_hosts,
+                json!({ "operation": "upload", "remote_path": "/outbox/report.bin", "file": report.to_value() }),
+            )))
+            .await
+            .unwrap();
+        assert_eq!(uploaded["result"]["size"], 5);
+        // The first connection recorded the host key
+        assert!(std::fs::read_to_string(&known_hosts).unwrap().contains(&format!("{}", address.port())));
+
+        let downloaded = node(&known_hosts)
+            .execute(context(params(
+                address,
+                &known_hosts,
+                json!({ "operation": "download", "remote_path": "/outbox/report.bin" }),
+            )))
+            .await
+            .unwrap();
+        let file = BinaryData::from_value(&downloaded["file"]).unwrap();
+        assert_eq!(file.data, report.data);
+        assert_eq!(file.filename.as_deref(), Some("report.bin"));
+    }
+
+    #[tokio::test]
+    async fn test_list_and_delete() {
+        let files = Files::default();
+        files.lock().unwrap().insert("/outbox/a.csv".to_string(), b"a,b\n".to_vec());
+        files.lock().unwrap().insert("/outbox/b.csv".to_string(), b"c\n".to_vec());
+        let address = start_server(files.clone()).await;
+        let known_hosts = known_hosts();
+
+        let listed = node(&known_hosts)
+            .execute(context(params(address, &known_hosts, json!({ "operation": "list", "remote_path": "/outbox" }))))
+            .await
+            .unwrap();
+        assert_eq!(listed["result"]["count"], 2);
+        assert_eq!(listed["result"]["entries"][0]["name"], "a.csv");
+        assert_eq!(listed["result"]["entries"][0]["size"], 4);
+
+        node(&known_hosts)
+            .execute(context(params(
+                address,
+                &known_hosts,
+                json!({ "operation": "delete", "remote_path": "/outbox/a.csv" }),
+            )))
+            .await
+            .unwrap();
+        assert_eq!(files.lock().unwrap().keys().collect::<Vec<_>>(), vec!["/outbox/b.csv"]);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_host_key_is_rejected_in_known_hosts_mode() {
+        let address = start_server(Files::default()).await;
+        let known_hosts = known_hosts();
+
+        let error = node(&known_hosts)
+            .execute(context(params(
+                address,
+                &known_hosts,
+                json!({ "operation": "list", "remote_path": "/", "host_key_check": "known_hosts" }),
+            )))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, GhostFlowError::AuthenticationError { .. }), "{:?}", error);
+        assert!(error.to_string().contains("is not in"), "{}", error);
+        assert!(!known_hosts.exists());
+    }
+
+    #[tokio::test]
+    async fn test_known_hosts_path_stays_in_the_configured_directory() {
+        let address = start_server(Files::default()).await;
+        let known_hosts = known_hosts();
+        let outside = std::env::temp_dir().join(format!("ghostflow-sftp-{}", Uuid::new_v4()));
+
+        for path in [outside.to_string_lossy().to_string(), "../authorized_keys".to_string()] {
+            let error = node(&known_hosts)
+                .execute(context(params(
+                    address,
+                    &known_hosts,
+                    json!({ "operation": "list", "remote_path": "/", "known_hosts_path": path }),
+                )))
+                .await
+                .unwrap_err();
+            assert!(matches!(error, GhostFlowError::ValidationError { .. }), "{:?}", error);
+        }
+        assert!(!outside.exists());
+    }
+}
@@ -1,270 +1,426 @@
-use ghostflow_core::{Node, NodeDefinition, NodeParameter, ParameterType, Result, Value};
+use super::{network_error, param_error, validate_required};
+use crate::http_util::{request_with_policy, shared_client, RequestPolicy};
+use ghostflow_core::{CircuitBreakerRegistry, GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort, ParameterOption,
+};
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SlackMessageNode;
+const SLACK_API: &str = "https://slack.com/api";
+
+#[derive(Debug, Clone)]
+pub struct SlackMessageNode {
+    breakers: Arc<CircuitBreakerRegistry>,
+}
+
+impl SlackMessageNode {
+    pub fn new() -> Self {
+        Self {
+            breakers: Arc::new(CircuitBreakerRegistry::new()),
+        }
+    }
+
+    /// Back off from failing hosts using `breakers`
+    pub fn with_circuit_breakers(mut self, breakers: Arc<CircuitBreakerRegistry>) -> Self {
+        self.breakers = breakers;
+        self
+    }
+}
+
+impl Default for SlackMessageNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[async_trait]
 impl Node for SlackMessageNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "slack_message".to_string(),
-            display_name: "Slack Message".to_string(),
+            id: "slack_message".to_string(),
+            name: "Slack Message".to_string(),
             description: "Send messages to Slack channels or users".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "bot_token".to_string(),
                     display_name: "Bot Token".to_string(),
-                    description: "Slack bot token (xoxb-...)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Slack bot token (xoxb-...)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "channel".to_string(),
                     display_name: "Channel".to_string(),
-                    description: "Channel ID, name (#general), or user ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Channel ID, name (#general), or user ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "text".to_string(),
                     display_name: "Message Text".to_string(),
-                    description: "Message content".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Message content".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "blocks".to_string(),
                     display_name: "Blocks".to_string(),
-                    description: "Slack Block Kit blocks (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Slack Block Kit blocks (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "attachments".to_string(),
                     display_name: "Attachments".to_string(),
-                    description: "Message attachments (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Message attachments (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "thread_ts".to_string(),
                     display_name: "Thread Timestamp".to_string(),
-                    description: "Thread timestamp to reply to".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Thread timestamp to reply to".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "username".to_string(),
                     display_name: "Username".to_string(),
-                    description: "Override bot username".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Override bot username".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "icon_emoji".to_string(),
                     display_name: "Icon Emoji".to_string(),
-                    description: "Override bot icon with emoji".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Override bot icon with emoji".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "message_ts".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "message_ts".to_string(),
+                    display_name: "Message Ts".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    fn supports_connection_test(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self, context: &ExecutionContext) -> Result<String> {
+        test_bot_token(&context.input).await
+    }
+
+    fn dynamic_option_parameters(&self) -> Vec<&'static str> {
+        vec!["channel"]
+    }
+
+    async fn load_options(&self, parameter: &str, context: &ExecutionContext) -> Result<Vec<ParameterOption>> {
+        match parameter {
+            "channel" => load_channel_options(&self.breakers, &context.input).await,
+            other => Err(param_error(format!("No options to load for '{}'", other))),
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let bot_token = context.get_parameter("bot_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Bot token is required")?;
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let bot_token = context.input.get("bot_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Bot token is required"))?;
         
-        let channel = context.get_parameter("channel")
-            .and_then(|v| v.as_string())
-            .ok_or("Channel is required")?;
+        let channel = context.input.get("channel")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Channel is required"))?;
 
-        let client = reqwest::Client::new();
         let mut body = json!({
             "channel": channel
         });
 
-        if let Some(text) = context.get_parameter("text").and_then(|v| v.as_string()) {
+        if let Some(text) = context.input.get("text").and_then(|v| v.as_str()) {
             body["text"] = json!(text);
         }
 
-        if let Some(blocks) = context.get_parameter("blocks") {
+        if let Some(blocks) = context.input.get("blocks") {
             body["blocks"] = blocks.clone();
         }
 
-        if let Some(attachments) = context.get_parameter("attachments") {
+        if let Some(attachments) = context.input.get("attachments") {
             body["attachments"] = attachments.clone();
         }
 
-        if let Some(thread_ts) = context.get_parameter("thread_ts").and_then(|v| v.as_string()) {
+        if let Some(thread_ts) = context.input.get("thread_ts").and_then(|v| v.as_str()) {
             body["thread_ts"] = json!(thread_ts);
         }
 
-        if let Some(username) = context.get_parameter("username").and_then(|v| v.as_string()) {
+        if let Some(username) = context.input.get("username").and_then(|v| v.as_str()) {
             body["username"] = json!(username);
         }
 
-        if let Some(icon_emoji) = context.get_parameter("icon_emoji").and_then(|v| v.as_string()) {
+        if let Some(icon_emoji) = context.input.get("icon_emoji").and_then(|v| v.as_str()) {
             body["icon_emoji"] = json!(icon_emoji);
         }
 
-        let response = client
-            .post("https://slack.com/api/chat.postMessage")
-            .header("Authorization", format!("Bearer {}", bot_token))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
-
-        let result: serde_json::Value = response.json().await?;
+        let result = post_message(&self.breakers, SLACK_API, bot_token, body, &context).await?;
         
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
         
         if let Some(message_ts) = result.get("ts").and_then(|ts| ts.as_str()) {
             outputs.insert("message_ts".to_string(), Value::String(message_ts.to_string()));
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SlackAlertNode;
+#[derive(Debug, Clone)]
+pub struct SlackAlertNode {
+    breakers: Arc<CircuitBreakerRegistry>,
+}
+
+impl SlackAlertNode {
+    pub fn new() -> Self {
+        Self {
+            breakers: Arc::new(CircuitBreakerRegistry::new()),
+        }
+    }
+
+    /// Back off from failing hosts using `breakers`
+    pub fn with_circuit_breakers(mut self, breakers: Arc<CircuitBreakerRegistry>) -> Self {
+        self.breakers = breakers;
+        self
+    }
+}
+
+impl Default for SlackAlertNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[async_trait]
 impl Node for SlackAlertNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "slack_alert".to_string(),
-            display_name: "Slack Alert".to_string(),
+            id: "slack_alert".to_string(),
+            name: "Slack Alert".to_string(),
             description: "Send formatted alerts to Slack with severity levels".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "bot_token".to_string(),
                     display_name: "Bot Token".to_string(),
-                    description: "Slack bot token (xoxb-...)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Slack bot token (xoxb-...)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "channel".to_string(),
                     display_name: "Channel".to_string(),
-                    description: "Channel ID or name for alerts".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Channel ID or name for alerts".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "alert_type".to_string(),
                     display_name: "Alert Type".to_string(),
-                    description: "Type of alert".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Type of alert".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("info".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "title".to_string(),
                     display_name: "Alert Title".to_string(),
-                    description: "Title of the alert".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Title of the alert".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "message".to_string(),
                     display_name: "Alert Message".to_string(),
-                    description: "Detailed alert message".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Detailed alert message".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "source".to_string(),
                     display_name: "Alert Source".to_string(),
-                    description: "System or service that triggered the alert".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("System or service that triggered the alert".to_string()),
+                    param_type: ParameterType::String,
                     default_value: Some(Value::String("GhostFlow".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "metadata".to_string(),
                     display_name: "Metadata".to_string(),
-                    description: "Additional metadata (JSON)".to_string(),
-                    parameter_type: ParameterType::Json,
-                    required: false,
+                    description: Some("Additional metadata (JSON)".to_string()),
+                    param_type: ParameterType::Object,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "mention_channel".to_string(),
                     display_name: "Mention Channel".to_string(),
-                    description: "Mention @channel for critical alerts".to_string(),
-                    parameter_type: ParameterType::Boolean,
-                    required: false,
+                    description: Some("Mention @channel for critical alerts".to_string()),
+                    param_type: ParameterType::Boolean,
                     default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
-            inputs: vec!["trigger".to_string()],
-            outputs: vec!["result".to_string(), "message_ts".to_string()],
+            inputs: vec![
+                NodePort {
+                    name: "trigger".to_string(),
+                    display_name: "Trigger".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "message_ts".to_string(),
+                    display_name: "Message Ts".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let bot_token = context.get_parameter("bot_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Bot token is required")?;
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    fn supports_connection_test(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self, context: &ExecutionContext) -> Result<String> {
+        test_bot_token(&context.input).await
+    }
+
+    fn dynamic_option_parameters(&self) -> Vec<&'static str> {
+        vec!["channel"]
+    }
+
+    async fn load_options(&self, parameter: &str, context: &ExecutionContext) -> Result<Vec<ParameterOption>> {
+        match parameter {
+            "channel" => load_channel_options(&self.breakers, &context.input).await,
+            other => Err(param_error(format!("No options to load for '{}'", other))),
+        }
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let bot_token = context.input.get("bot_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Bot token is required"))?;
         
-        let channel = context.get_parameter("channel")
-            .and_then(|v| v.as_string())
-            .ok_or("Channel is required")?;
+        let channel = context.input.get("channel")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Channel is required"))?;
         
-        let alert_type = context.get_parameter("alert_type")
-            .and_then(|v| v.as_string())
-            .unwrap_or("info".to_string());
+        let alert_type = context.input.get("alert_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("info");
         
-        let title = context.get_parameter("title")
-            .and_then(|v| v.as_string())
-            .ok_or("Alert title is required")?;
+        let title = context.input.get("title")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Alert title is required"))?;
         
-        let message = context.get_parameter("message")
-            .and_then(|v| v.as_string())
-            .ok_or("Alert message is required")?;
+        let message = context.input.get("message")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Alert message is required"))?;
         
-        let source = context.get_parameter("source")
-            .and_then(|v| v.as_string())
-            .unwrap_or("GhostFlow".to_string());
+        let source = context.input.get("source")
+            .and_then(|v| v.as_str())
+            .unwrap_or("GhostFlow");
         
-        let mention_channel = context.get_parameter("mention_channel")
+        let mention_channel = context.input.get("mention_channel")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        let (color, emoji) = match alert_type.as_str() {
+        let (color, emoji) = match alert_type {
             "critical" => ("#FF0000", ":rotating_light:"),
             "error" => ("#FF6B6B", ":x:"),
             "warning" => ("#FFA500", ":warning:"),
@@ -293,9 +449,9 @@ impl Node for SlackAlertNode {
             })
         ];
 
-        if let Some(metadata) = context.get_parameter("metadata") {
-            if let Value::Object(obj) = metadata {
-                for (key, value) in obj.as_object().unwrap().iter() {
+        if let Some(metadata) = context.input.get("metadata") {
+            if let Some(obj) = metadata.as_object() {
+                for (key, value) in obj.iter() {
                     fields.push(json!({
                         "title": key,
                         "value": value.to_string(),
@@ -327,127 +483,188 @@ impl Node for SlackAlertNode {
             "username": "GhostFlow Alerts"
         });
 
-        let client = reqwest::Client::new();
-        let response = client
-            .post("https://slack.com/api/chat.postMessage")
-            .header("Authorization", format!("Bearer {}", bot_token))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
-
-        let result: serde_json::Value = response.json().await?;
+        let result = post_message(&self.breakers, SLACK_API, bot_token, body, &context).await?;
         
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
         
         if let Some(message_ts) = result.get("ts").and_then(|ts| ts.as_str()) {
             outputs.insert("message_ts".to_string(), Value::String(message_ts.to_string()));
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SlackChannelNode;
+#[derive(Debug, Clone)]
+pub struct SlackChannelNode {
+    breakers: Arc<CircuitBreakerRegistry>,
+}
+
+impl SlackChannelNode {
+    pub fn new() -> Self {
+        Self {
+            breakers: Arc::new(CircuitBreakerRegistry::new()),
+        }
+    }
+
+    /// Back off from failing hosts using `breakers`
+    pub fn with_circuit_breakers(mut self, breakers: Arc<CircuitBreakerRegistry>) -> Self {
+        self.breakers = breakers;
+        self
+    }
+}
+
+impl Default for SlackChannelNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[async_trait]
 impl Node for SlackChannelNode {
     fn definition(&self) -> NodeDefinition {
         NodeDefinition {
-            name: "slack_channel".to_string(),
-            display_name: "Slack Channel Management".to_string(),
+            id: "slack_channel".to_string(),
+            name: "Slack Channel Management".to_string(),
             description: "Manage Slack channels and members".to_string(),
-            category: "integrations".to_string(),
+            category: NodeCategory::Integration,
             version: "1.0.0".to_string(),
             parameters: vec![
                 NodeParameter {
                     name: "bot_token".to_string(),
                     display_name: "Bot Token".to_string(),
-                    description: "Slack bot token (xoxb-...)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: true,
+                    description: Some("Slack bot token (xoxb-...)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "operation".to_string(),
                     display_name: "Operation".to_string(),
-                    description: "Channel operation to perform".to_string(),
-                    parameter_type: ParameterType::Select,
-                    required: true,
+                    description: Some("Channel operation to perform".to_string()),
+                    param_type: ParameterType::Select,
                     default_value: Some(Value::String("list_channels".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "channel_id".to_string(),
                     display_name: "Channel ID".to_string(),
-                    description: "Slack channel ID".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Slack channel ID".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "channel_name".to_string(),
                     display_name: "Channel Name".to_string(),
-                    description: "Channel name (without #)".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Channel name (without #)".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "purpose".to_string(),
                     display_name: "Purpose".to_string(),
-                    description: "Channel purpose/description".to_string(),
-                    parameter_type: ParameterType::String,
-                    required: false,
+                    description: Some("Channel purpose/description".to_string()),
+                    param_type: ParameterType::String,
                     default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
                 NodeParameter {
                     name: "is_private".to_string(),
                     display_name: "Private Channel".to_string(),
-                    description: "Create as private channel".to_string(),
-                    parameter_type: ParameterType::Boolean,
-                    required: false,
+                    description: Some("Create as private channel".to_string()),
+                    param_type: ParameterType::Boolean,
                     default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
                 },
             ],
             inputs: vec![],
-            outputs: vec!["result".to_string(), "channel_info".to_string()],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "channel_info".to_string(),
+                    display_name: "Channel Info".to_string(),
+                    description: None,
+                    data_type: DataType::Any,
+                    required: true,
+                },
+            ],
+            icon: None,
+            color: None,
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)
+    }
+
+    fn supports_connection_test(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self, context: &ExecutionContext) -> Result<String> {
+        test_bot_token(&context.input).await
+    }
+
+    fn dynamic_option_parameters(&self) -> Vec<&'static str> {
+        vec!["channel_id"]
+    }
+
+    async fn load_options(&self, parameter: &str, context: &ExecutionContext) -> Result<Vec<ParameterOption>> {
+        match parameter {
+            "channel_id" => load_channel_options(&self.breakers, &context.input).await,
+            other => Err(param_error(format!("No options to load for '{}'", other))),
         }
     }
 
-    async fn execute(
-        &self,
-        context: ghostflow_core::ExecutionContext,
-    ) -> Result<HashMap<String, Value>> {
-        let bot_token = context.get_parameter("bot_token")
-            .and_then(|v| v.as_string())
-            .ok_or("Bot token is required")?;
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let bot_token = context.input.get("bot_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Bot token is required"))?;
         
-        let operation = context.get_parameter("operation")
-            .and_then(|v| v.as_string())
-            .unwrap_or("list_channels".to_string());
+        let operation = context.input.get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list_channels");
 
-        let client = reqwest::Client::new();
+        let client = shared_client();
 
-        let result = match operation.as_str() {
+        let result = match operation {
             "list_channels" => {
-                let response = client
+                let request = client
                     .get("https://slack.com/api/conversations.list")
                     .header("Authorization", format!("Bearer {}", bot_token))
-                    .query(&[("types", "public_channel,private_channel")])
-                    .send()
-                    .await?;
+                    .query(&[("types", "public_channel,private_channel")]);
+                let response = request_with_policy(request, &RequestPolicy::default(), &self.breakers).await?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "create_channel" => {
-                let channel_name = context.get_parameter("channel_name")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Channel name is required for create operation")?;
+                let channel_name = context.input.get("channel_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Channel name is required for create operation"))?;
                 
-                let is_private = context.get_parameter("is_private")
+                let is_private = context.input.get("is_private")
                     .and_then(|v| v.as_bool())
                     .unwrap_or(false);
 
@@ -456,62 +673,271 @@ impl Node for SlackChannelNode {
                     "is_private": is_private
                 });
 
-                let response = client
+                let request = client
                     .post("https://slack.com/api/conversations.create")
                     .header("Authorization", format!("Bearer {}", bot_token))
                     .header("Content-Type", "application/json")
-                    .json(&body)
-                    .send()
-                    .await?;
+                    .json(&body);
+                let response = request_with_policy(request, &RequestPolicy::default(), &self.breakers).await?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "get_channel_info" => {
-                let channel_id = context.get_parameter("channel_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Channel ID is required for get info operation")?;
+                let channel_id = context.input.get("channel_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Channel ID is required for get info operation"))?;
 
-                let response = client
+                let request = client
                     .get("https://slack.com/api/conversations.info")
                     .header("Authorization", format!("Bearer {}", bot_token))
-                    .query(&[("channel", &channel_id)])
-                    .send()
-                    .await?;
+                    .query(&[("channel", &channel_id)]);
+                let response = request_with_policy(request, &RequestPolicy::default(), &self.breakers).await?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             "archive_channel" => {
-                let channel_id = context.get_parameter("channel_id")
-                    .and_then(|v| v.as_string())
-                    .ok_or("Channel ID is required for archive operation")?;
+                let channel_id = context.input.get("channel_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| param_error("Channel ID is required for archive operation"))?;
 
-                let response = client
+                let request = client
                     .post("https://slack.com/api/conversations.archive")
                     .header("Authorization", format!("Bearer {}", bot_token))
                     .header("Content-Type", "application/json")
                     .json(&json!({
                         "channel": channel_id
-                    }))
-                    .send()
-                    .await?;
+                    }));
+                let response = request_with_policy(request, &RequestPolicy::default(), &self.breakers).await?;
 
-                let data: serde_json::Value = response.json().await?;
+                let data: serde_json::Value = response.json().await.map_err(network_error)?;
                 data
             },
             _ => {
-                return Err(format!("Unknown operation: {}", operation).into());
+                return Err(param_error(format!("Unknown operation: {}", operation)));
             }
         };
 
-        let mut outputs = HashMap::new();
-        outputs.insert("result".to_string(), Value::Object(result.clone()));
+        let mut outputs = serde_json::Map::new();
+        outputs.insert("result".to_string(), result.clone());
         
         if let Some(channel_info) = result.get("channel") {
             outputs.insert("channel_info".to_string(), channel_info.clone().into());
         }
         
-        Ok(outputs)
+        Ok(Value::Object(outputs))
+    }
+}
+
+/// Send `body` with `chat.postMessage`. The node's idempotency key goes out as
+/// `client_msg_id`, which stays the same when the node is retried.
+async fn post_message(
+    breakers: &CircuitBreakerRegistry,
+    api_url: &str,
+    bot_token: &str,
+    mut body: Value,
+    context: &ExecutionContext,
+) -> Result<Value> {
+    body["client_msg_id"] = json!(context.idempotency_key());
+    let request = shared_client()
+        .post(format!("{}/chat.postMessage", api_url))
+        .header("Authorization", format!("Bearer {}", bot_token))
+        .header("Content-Type", "application/json")
+        .json(&body);
+    let response = request_with_policy(request, &RequestPolicy::default(), breakers).await?;
+    response.json().await.map_err(network_error)
+}
+
+/// Confirm the node's bot token with `auth.test`.
+async fn test_bot_token(input: &Value) -> Result<String> {
+    let bot_token = input.get("bot_token")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Bot token is required"))?;
+    auth_test(SLACK_API, bot_token).await
+}
+
+/// Channels the node's bot token can see, for picking a `channel`.
+async fn load_channel_options(breakers: &CircuitBreakerRegistry, input: &Value) -> Result<Vec<ParameterOption>> {
+    let bot_token = input.get("bot_token")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| param_error("Bot token is required"))?;
+    channel_options(breakers, SLACK_API, bot_token).await
+}
+
+/// Every unarchived public and private channel from `conversations.list`,
+/// following its cursor through all pages.
+async fn channel_options(breakers: &CircuitBreakerRegistry, api_url: &str, bot_token: &str) -> Result<Vec<ParameterOption>> {
+    let client = shared_client();
+    let mut options = Vec::new();
+    let mut cursor = String::new();
+    loop {
+        let request = client
+            .get(format!("{}/conversations.list", api_url))
+            .header("Authorization", format!("Bearer {}", bot_token))
+            .query(&[
+                ("types", "public_channel,private_channel"),
+                ("exclude_archived", "true"),
+                ("limit", "200"),
+                ("cursor", cursor.as_str()),
+            ]);
+        let response = request_with_policy(request, &RequestPolicy::default(), breakers).await?;
+        let page: Value = response.json().await.map_err(network_error)?;
+        if page["ok"].as_bool() != Some(true) {
+            return Err(GhostFlowError::AuthenticationError {
+                message: format!("Slack refused to list channels: {}", page["error"].as_str().unwrap_or("unknown error")),
+            });
+        }
+
+        options.extend(page["channels"].as_array().into_iter().flatten().filter_map(|channel| {
+            Some(ParameterOption {
+                value: json!(channel["id"].as_str()?),
+                label: format!("#{}", channel["name"].as_str()?),
+            })
+        }));
+
+        match page["response_metadata"]["next_cursor"].as_str() {
+            Some(next) if !next.is_empty() => cursor = next.to_string(),
+            _ => break,
+        }
+    }
+    options.sort_by(|a, b| a.label.cmp(&b.label));
+    Ok(options)
+}
+
+async fn auth_test(api_url: &str, bot_token: &str) -> Result<String> {
+    let response = shared_client()
+        .post(format!("{}/auth.test", api_url))
+        .header("Authorization", format!("Bearer {}", bot_token))
+        .send()
+        .await
+        .map_err(network_error)?;
+    let result: Value = response.json().await.map_err(network_error)?;
+
+    // Slack answers 200 either way; `ok` says whether the token is valid
+    if result["ok"].as_bool() != Some(true) {
+        return Err(GhostFlowError::AuthenticationError {
+            message: format!("Slack rejected the token: {}", result["error"].as_str().unwrap_or("unknown error")),
+        });
+    }
+    Ok(format!(
+        "Authenticated as {} in {}",
+        result["user"].as_str().unwrap_or("unknown user"),
+        result["team"].as_str().unwrap_or("unknown workspace")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn slack_server(body: Value) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/auth.test"))
+            .and(header("Authorization", "Bearer xoxb-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_passes_auth_test() {
+        let server = slack_server(json!({ "ok": true, "user": "ghostflow", "team": "Ops" })).await;
+
+        let message = auth_test(&server.uri(), "xoxb-test").await.unwrap();
+        assert_eq!(message, "Authenticated as ghostflow in Ops");
+    }
+
+    #[tokio::test]
+    async fn test_revoked_token_fails_auth_test() {
+        let server = slack_server(json!({ "ok": false, "error": "token_revoked" })).await;
+
+        let error = auth_test(&server.uri(), "xoxb-test").await.unwrap_err();
+        assert!(matches!(error, GhostFlowError::AuthenticationError { ref message } if message.contains("token_revoked")));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_channel_options_follow_the_cursor() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.list"))
+            .and(header("Authorization", "Bearer xoxb-test"))
+            .and(query_param("cursor", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "channels": [{ "id": "C02", "name": "soc-alerts" }],
+                "response_metadata": { "next_cursor": "dXNlcjpVMDYxTkZUVDI=" },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.list"))
+            .and(query_param("cursor", "dXNlcjpVMDYxTkZUVDI="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "channels": [{ "id": "C01", "name": "general" }],
+                "response_metadata": { "next_cursor": "" },
+            })))
+            .mount(&server)
+            .await;
+
+        let options = channel_options(&CircuitBreakerRegistry::new(), &server.uri(), "xoxb-test").await.unwrap();
+        let pairs: Vec<(Value, &str)> = options.iter().map(|o| (o.value.clone(), o.label.as_str())).collect();
+        assert_eq!(pairs, vec![(json!("C01"), "#general"), (json!("C02"), "#soc-alerts")]);
+    }
+
+    #[tokio::test]
+    async fn test_channel_options_surface_slack_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.list"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "ok": false, "error": "missing_scope" })))
+            .mount(&server)
+            .await;
+
+        let error = channel_options(&CircuitBreakerRegistry::new(), &server.uri(), "xoxb-test").await.unwrap_err();
+        assert!(error.to_string().contains("missing_scope"), "{}", error);
+
+        let error = SlackMessageNode::new()
+            .load_options("text", &context(json!({ "bot_token": "xoxb-test" })))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("No options"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_retried_message_reuses_client_msg_id() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat.postMessage"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "ok": true, "ts": "1700000000.000100" })))
+            .mount(&server)
+            .await;
+
+        let first = context(json!({}));
+        let retry = ExecutionContext {
+            attempt: 2,
+            ..first.clone()
+        };
+        let breakers = CircuitBreakerRegistry::new();
+        for context in [&first, &retry] {
+            post_message(&breakers, &server.uri(), "xoxb-test", json!({ "channel": "#soc", "text": "hi" }), context)
+                .await
+                .unwrap();
+        }
+
+        let ids: Vec<Value> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| serde_json::from_slice::<Value>(&request.body).unwrap()["client_msg_id"].clone())
+            .collect();
+        assert_eq!(ids, vec![json!(first.idempotency_key()), json!(first.idempotency_key())]);
+    }
+}
@@ -0,0 +1,728 @@
+use super::{network_error, param_error, validate_required};
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    BinaryData, DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tiberius::{AuthMethod, Client, ColumnData, ColumnType, Config, FromSql, Query, Row};
+use tokio::net::TcpStream;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+const OPERATIONS: [&str; 4] = ["query", "insert", "update", "delete"];
+
+/// SQL Server rejects requests with more parameters than this.
+const MAX_PARAMETERS: usize = 2100;
+
+/// Login failed for user.
+const LOGIN_FAILED: u32 = 18456;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqlServerNode;
+
+/// SQL text with the values for its `@P1..@Pn` placeholders, in order.
+#[derive(Debug, PartialEq)]
+struct Statement {
+    sql: String,
+    params: Vec<Value>,
+}
+
+impl Statement {
+    fn placeholder(&mut self, value: Value) -> String {
+        self.params.push(value);
+        format!("@P{}", self.params.len())
+    }
+
+    fn check_limit(self) -> Result<Self> {
+        if self.params.len() > MAX_PARAMETERS {
+            return Err(param_error(format!(
+                "SQL Server allows at most {} parameters per statement, got {}",
+                MAX_PARAMETERS,
+                self.params.len()
+            )));
+        }
+        Ok(self)
+    }
+}
+
+/// `dbo.users` becomes `[dbo].[users]`; brackets already present are kept
+/// and a `]` inside a name is doubled.
+fn quote_table(name: &str) -> Result<String> {
+    if name.trim().is_empty() {
+        return Err(param_error("SQL Server table_name is required"));
+    }
+    Ok(name.split('.').map(quote_identifier).collect::<Vec<_>>().join("."))
+}
+
+fn quote_identifier(name: &str) -> String {
+    let name = name.trim();
+    let name = name.strip_prefix('[').and_then(|n| n.strip_suffix(']')).unwrap_or(name);
+    format!("[{}]", name.replace(']', "]]"))
+}
+
+/// Rows to insert: one object, or an array of objects.
+fn insert_rows(data: Option<&Value>) -> Result<Vec<&Map<String, Value>>> {
+    let rows: Vec<_> = match data {
+        Some(Value::Object(row)) => vec![row],
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| item.as_object().ok_or_else(|| param_error("Each row to insert must be an object")))
+            .collect::<Result<_>>()?,
+        _ => return Err(param_error("Data is required for insert operation")),
+    };
+    if rows.is_empty() || rows.iter().all(|row| row.is_empty()) {
+        return Err(param_error("Data to insert has no columns"));
+    }
+    Ok(rows)
+}
+
+/// Multi-row insert over the union of the rows' columns, in first-seen
+/// order; a row without a column inserts NULL for it.
+fn insert_statement(table: &str, rows: &[&Map<String, Value>]) -> Result<Statement> {
+    let mut columns: Vec<&String> = Vec::new();
+    for key in rows.iter().flat_map(|row| row.keys()) {
+        if !columns.contains(&key) {
+            columns.push(key);
+        }
+    }
+
+    let mut statement = Statement {
+        sql: String::new(),
+        params: Vec::new(),
+    };
+    let values: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let placeholders: Vec<String> = columns
+                .iter()
+                .map(|column| statement.placeholder(row.get(*column).cloned().unwrap_or(Value::Null)))
+                .collect();
+            format!("({})", placeholders.join(", "))
+        })
+        .collect();
+
+    statement.sql = format!(
+        "INSERT INTO {} ({}) VALUES {}",
+        quote_table(table)?,
+        columns.iter().map(|c| quote_identifier(c)).collect::<Vec<_>>().join(", "),
+        values.join(", ")
+    );
+    statement.check_limit()
+}
+
+/// `WHERE` clause of column equalities, so updates and deletes stay
+/// parameterized. A null value matches with `IS NULL`. An empty filter is
+/// refused rather than touching every row.
+fn where_clause(statement: &mut Statement, filter: Option<&Value>, operation: &str) -> Result<String> {
+    let filter = filter
+        .and_then(|v| v.as_object())
+        .filter(|f| !f.is_empty())
+        .ok_or_else(|| param_error(format!("A 'where' object of column values is required for {}", operation)))?;
+
+    let conditions: Vec<String> = filter
+        .iter()
+        .map(|(column, value)| match value {
+            Value::Null => format!("{} IS NULL", quote_identifier(column)),
+            value => format!("{} = {}", quote_identifier(column), statement.placeholder(value.clone())),
+        })
+        .collect();
+    Ok(format!("WHERE {}", conditions.join(" AND ")))
+}
+
+fn update_statement(table: &str, data: Option<&Value>, filter: Option<&Value>) -> Result<Statement> {
+    let data = data
+        .and_then(|v| v.as_object())
+        .filter(|d| !d.is_empty())
+        .ok_or_else(|| param_error("Data is required for update operation"))?;
+
+    let mut statement = Statement {
+        sql: String::new(),
+        params: Vec::new(),
+    };
+    let assignments: Vec<String> = data
+        .iter()
+        .map(|(column, value)| format!("{} = {}", quote_identifier(column), statement.placeholder(value.clone())))
+        .collect();
+    let clause = where_clause(&mut statement, filter, "update")?;
+
+    statement.sql = format!("UPDATE {} SET {} {}", quote_table(table)?, assignments.join(", "), clause);
+    statement.check_limit()
+}
+
+fn delete_statement(table: &str, filter: Option<&Value>) -> Result<Statement> {
+    let mut statement = Statement {
+        sql: String::new(),
+        params: Vec::new(),
+    };
+    let clause = where_clause(&mut statement, filter, "delete")?;
+    statement.sql = format!("DELETE FROM {} {}", quote_table(table)?, clause);
+    statement.check_limit()
+}
+
+fn query_statement(params: &Value) -> Result<Statement> {
+    let sql = params
+        .get("query")
+        .and_then(|v| v.as_str())
+        .filter(|q| !q.trim().is_empty())
+        .ok_or_else(|| param_error("Query is required for query operation"))?;
+    let values = match params.get("parameters") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(values)) => values.clone(),
+        Some(_) => return Err(param_error("Query parameters must be an array bound to @P1, @P2, ...")),
+    };
+    Statement {
+        sql: sql.to_string(),
+        params: values,
+    }
+    .check_limit()
+}
+
+fn statement(operation: &str, params: &Value) -> Result<Statement> {
+    let table = || params.get("table_name").and_then(|v| v.as_str()).unwrap_or("");
+    match operation {
+        "query" => query_statement(params),
+        "insert" => insert_statement(table(), &insert_rows(params.get("data"))?),
+        "update" => update_statement(table(), params.get("data"), params.get("where")),
+        "delete" => delete_statement(table(), params.get("where")),
+        _ => Err(param_error(format!("Unknown operation: {}", operation))),
+    }
+}
+
+/// Bind a JSON value to the next placeholder. Nulls are sent as NVARCHAR,
+/// which SQL Server converts to any column type; objects and arrays are sent
+/// as JSON text for use with `OPENJSON`.
+fn bind(query: &mut Query<'_>, value: &Value) {
+    match value {
+        Value::Null => query.bind(Option::<String>::None),
+        Value::Bool(b) => query.bind(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => query.bind(i),
+            None => query.bind(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => query.bind(s.clone()),
+        other => query.bind(other.to_string()),
+    }
+}
+
+fn authentication(params: &Value) -> Result<AuthMethod> {
+    let username = params.get("username").and_then(|v| v.as_str()).filter(|u| !u.is_empty());
+    let password = params.get("password").and_then(|v| v.as_str()).unwrap_or("");
+    match params.get("auth_type").and_then(|v| v.as_str()).unwrap_or("sql") {
+        "sql" => {
+            let username = username.ok_or_else(|| param_error("Username is required for SQL authentication"))?;
+            Ok(AuthMethod::sql_server(username, password))
+        }
+        "windows" => {
+            let username = username.ok_or_else(|| param_error("A DOMAIN\\user username is required for Windows authentication"))?;
+            windows_authentication(username, password)
+        }
+        "integrated" => integrated_authentication(),
+        other => Err(param_error(format!("Unknown SQL Server auth_type: {}", other))),
+    }
+}
+
+/// NTLM with an explicit DOMAIN\user account. tiberius only implements it
+/// on Windows.
+#[cfg(windows)]
+fn windows_authentication(username: &str, password: &str) -> Result<AuthMethod> {
+    Ok(AuthMethod::windows(username, password))
+}
+
+#[cfg(not(windows))]
+fn windows_authentication(_username: &str, _password: &str) -> Result<AuthMethod> {
+    Err(param_error(
+        "Windows authentication is only available on Windows; use a SQL Server login",
+    ))
+}
+
+/// The identity of the process running GhostFlow.
+#[cfg(windows)]
+fn integrated_authentication() -> Result<AuthMethod> {
+    Ok(AuthMethod::Integrated)
+}
+
+#[cfg(not(windows))]
+fn integrated_authentication() -> Result<AuthMethod> {
+    Err(param_error(
+        "Integrated authentication is only available on Windows; use a SQL Server login",
+    ))
+}
+
+fn config(params: &Value) -> Result<Config> {
+    let connection_string = params
+        .get("connection_string")
+        .and_then(|v| v.as_str())
+        .filter(|c| !c.trim().is_empty());
+
+    let mut config = match connection_string {
+        Some(ado) => Config::from_ado_string(ado)
+            .map_err(|e| param_error(format!("Invalid SQL Server connection string: {}", e)))?,
+        None => {
+            let mut config = Config::new();
+            config.host(params.get("host").and_then(|v| v.as_str()).unwrap_or("localhost"));
+            config.port(params.get("port").and_then(|v| v.as_u64()).unwrap_or(1433) as u16);
+            if let Some(database) = params.get("database").and_then(|v| v.as_str()).filter(|d| !d.is_empty()) {
+                config.database(database);
+            }
+            config.authentication(authentication(params)?);
+            config
+        }
+    };
+    if params.get("trust_server_certificate").and_then(|v| v.as_bool()).unwrap_or(false) {
+        config.trust_cert();
+    }
+    Ok(config)
+}
+
+fn sql_error(error: tiberius::error::Error) -> GhostFlowError {
+    match &error {
+        tiberius::error::Error::Server(token) if token.code() == LOGIN_FAILED => GhostFlowError::AuthenticationError {
+            message: token.message().to_string(),
+        },
+        _ => network_error(error),
+    }
+}
+
+async fn connect(config: Config) -> Result<Client<Compat<TcpStream>>> {
+    let tcp = TcpStream::connect(config.get_addr()).await.map_err(network_error)?;
+    tcp.set_nodelay(true).map_err(network_error)?;
+
+    match Client::connect(config.clone(), tcp.compat_write()).await {
+        // Azure SQL may redirect the login to the node hosting the database
+        Err(tiberius::error::Error::Routing { host, port }) => {
+            let mut config = config;
+            config.host(&host);
+            config.port(port);
+            let tcp = TcpStream::connect(config.get_addr()).await.map_err(network_error)?;
+            tcp.set_nodelay(true).map_err(network_error)?;
+            Client::connect(config, tcp.compat_write()).await.map_err(sql_error)
+        }
+        other => other.map_err(sql_error),
+    }
+}
+
+fn conversion_error(error: tiberius::error::Error) -> GhostFlowError {
+    GhostFlowError::InternalError {
+        message: format!("Unreadable SQL Server value: {}", error),
+    }
+}
+
+/// JSON for one cell. Exact decimals (`decimal`, `numeric`, `money`) become
+/// strings so no precision is lost; `uniqueidentifier` is the hyphenated
+/// GUID; `datetimeoffset` is RFC 3339 with its original offset while the
+/// zone-less date/time types are ISO 8601 without one; binary columns are
+/// binary values.
+fn cell_value(column_type: ColumnType, data: &ColumnData<'static>) -> Result<Value> {
+    let value = match data {
+        ColumnData::U8(v) => json!(v),
+        ColumnData::I16(v) => json!(v),
+        ColumnData::I32(v) => json!(v),
+        ColumnData::I64(v) => json!(v),
+        ColumnData::F32(v) => json!(v),
+        ColumnData::F64(v) if matches!(column_type, ColumnType::Money | ColumnType::Money4) => {
+            json!(v.map(|money| format!("{:.4}", money)))
+        }
+        ColumnData::F64(v) => json!(v),
+        ColumnData::Bit(v) => json!(v),
+        ColumnData::String(v) => json!(v.as_deref()),
+        ColumnData::Guid(v) => json!(v.as_ref().map(|guid| guid.to_string())),
+        ColumnData::Numeric(v) => json!(v.as_ref().map(|n| n.to_string())),
+        ColumnData::Xml(v) => json!(v.as_ref().map(|xml| xml.to_string())),
+        ColumnData::Binary(v) => v.as_ref().map_or(Value::Null, |bytes| BinaryData::new(bytes.to_vec()).to_value()),
+        ColumnData::DateTimeOffset(_) => {
+            json!(DateTime::<FixedOffset>::from_sql(data).map_err(conversion_error)?.map(|d| d.to_rfc3339()))
+        }
+        ColumnData::DateTime(_) | ColumnData::SmallDateTime(_) | ColumnData::DateTime2(_) => json!(NaiveDateTime::from_sql(data)
+            .map_err(conversion_error)?
+            .map(|d| d.format("%Y-%m-%dT%H:%M:%S%.f").to_string())),
+        ColumnData::Date(_) => json!(NaiveDate::from_sql(data).map_err(conversion_error)?.map(|d| d.to_string())),
+        ColumnData::Time(_) => json!(NaiveTime::from_sql(data).map_err(conversion_error)?.map(|t| t.to_string())),
+    };
+    Ok(value)
+}
+
+fn row_to_json(row: &Row) -> Result<Value> {
+    let mut object = Map::new();
+    for (column, data) in row.cells() {
+        object.insert(column.name().to_string(), cell_value(column.column_type(), data)?);
+    }
+    Ok(Value::Object(object))
+}
+
+#[async_trait]
+impl Node for SqlServerNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "sql_server".to_string(),
+            name: "SQL Server".to_string(),
+            description: "Query and modify Microsoft SQL Server databases".to_string(),
+            category: NodeCategory::Integration,
+            version: "1.0.0".to_string(),
+            parameters: vec![
+                NodeParameter {
+                    name: "connection_string".to_string(),
+                    display_name: "Connection String".to_string(),
+                    description: Some("ADO.NET connection string; overrides the individual connection fields".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "host".to_string(),
+                    display_name: "Host".to_string(),
+                    description: Some("Database host".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("localhost".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "port".to_string(),
+                    display_name: "Port".to_string(),
+                    description: Some("Database port".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(json!(1433)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "database".to_string(),
+                    display_name: "Database".to_string(),
+                    description: Some("Database name; the login's default database when empty".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "auth_type".to_string(),
+                    display_name: "Authentication".to_string(),
+                    description: Some("SQL login, a Windows domain account, or the service's own Windows identity".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("sql".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "sql", "label": "SQL Server"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "windows", "label": "Windows (DOMAIN\\user, Windows only)"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "integrated", "label": "Integrated (Windows only)"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "username".to_string(),
+                    display_name: "Username".to_string(),
+                    description: Some("Login name, or DOMAIN\\user for Windows authentication".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "password".to_string(),
+                    display_name: "Password".to_string(),
+                    description: Some("Login password".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "trust_server_certificate".to_string(),
+                    display_name: "Trust Server Certificate".to_string(),
+                    description: Some("Skip TLS certificate validation, e.g. for self-signed development servers".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "operation".to_string(),
+                    display_name: "Operation".to_string(),
+                    description: Some("Database operation to perform".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("query".to_string())),
+                    required: true,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "query", "label": "Query"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "insert", "label": "Insert"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "update", "label": "Update"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "delete", "label": "Delete"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "query".to_string(),
+                    display_name: "SQL Query".to_string(),
+                    description: Some("T-SQL to run; reference parameters as @P1, @P2, ...".to_string()),
+                    param_type: ParameterType::Code,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "parameters".to_string(),
+                    display_name: "Parameters".to_string(),
+                    description: Some("Values for @P1, @P2, ... in order (JSON array)".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "table_name".to_string(),
+                    display_name: "Table Name".to_string(),
+                    description: Some("Table for insert/update/delete, optionally schema-qualified (dbo.users)".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "data".to_string(),
+                    display_name: "Data".to_string(),
+                    description: Some("Row to insert or columns to update; an array of rows inserts them all".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "where".to_string(),
+                    display_name: "Where".to_string(),
+                    description: Some("Column values selecting the rows to update or delete".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            inputs: vec![],
+            outputs: vec![
+                NodePort {
+                    name: "rows".to_string(),
+                    display_name: "Rows".to_string(),
+                    description: Some("Rows of the first result set".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+                NodePort {
+                    name: "affected_rows".to_string(),
+                    display_name: "Affected Rows".to_string(),
+                    description: None,
+                    data_type: DataType::Number,
+                    required: true,
+                },
+            ],
+            icon: Some("database".to_string()),
+            color: Some("#cc2927".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)?;
+        let operation = context.input.get("operation").and_then(|v| v.as_str()).unwrap_or("query");
+        if !OPERATIONS.contains(&operation) {
+            return Err(param_error(format!("Unknown operation: {}", operation)));
+        }
+        config(&context.input)?;
+        statement(operation, &context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let operation = context.input.get("operation").and_then(|v| v.as_str()).unwrap_or("query");
+        let statement = statement(operation, &context.input)?;
+        let mut client = connect(config(&context.input)?).await?;
+
+        let mut query = Query::new(statement.sql.as_str());
+        for value in &statement.params {
+            bind(&mut query, value);
+        }
+
+        let (rows, affected_rows) = if operation == "query" {
+            let rows = query
+                .query(&mut client)
+                .await
+                .map_err(sql_error)?
+                .into_first_result()
+                .await
+                .map_err(sql_error)?;
+            (rows.iter().map(row_to_json).collect::<Result<Vec<_>>>()?, 0)
+        } else {
+            let result = query.execute(&mut client).await.map_err(sql_error)?;
+            (Vec::new(), result.rows_affected().iter().sum::<u64>())
+        };
+
+        Ok(json!({
+            "operation": operation,
+            "row_count": rows.len(),
+            "rows": rows,
+            "affected_rows": affected_rows,
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn supports_connection_test(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self, context: &ExecutionContext) -> Result<String> {
+        let mut client = connect(config(&context.input)?).await?;
+        let row = client
+            .simple_query("SELECT @@VERSION")
+            .await
+            .map_err(sql_error)?
+            .into_row()
+            .await
+            .map_err(sql_error)?;
+        let version = row.as_ref().and_then(|r| r.get::<&str, _>(0)).unwrap_or("SQL Server");
+        Ok(format!("Connected to {}", version.lines().next().unwrap_or(version).trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use tiberius::IntoSql;
+    use uuid::Uuid;
+
+    /// ADO.NET connection string for a throwaway server, e.g. the
+    /// `mcr.microsoft.com/mssql/server` container. Tests that need one are
+    /// ignored by default; run them with `cargo test -- --ignored`.
+    const TEST_SERVER_ENV: &str = "GHOSTFLOW_TEST_MSSQL";
+
+    #[test]
+    fn test_insert_statement_binds_every_value() {
+        let statement = statement(
+            "insert",
+            &json!({
+                "table_name": "dbo.orders",
+                "data": [
+                    { "customer": "acme", "total": 120.5 },
+                    { "customer": "globex", "note": "rush" }
+                ],
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            statement.sql,
+            "INSERT INTO [dbo].[orders] ([customer], [total], [note]) VALUES (@P1, @P2, @P3), (@P4, @P5, @P6)"
+        );
+        assert_eq!(
+            statement.params,
+            vec![json!("acme"), json!(120.5), Value::Null, json!("globex"), Value::Null, json!("rush")]
+        );
+    }
+
+    #[test]
+    fn test_update_and_delete_require_where() {
+        let statement = statement(
+            "update",
+            &json!({
+                "table_name": "users",
+                "data": { "active": false },
+                "where": { "deleted_at": null, "id": 7 },
+            }),
+        )
+        .unwrap();
+        assert_eq!(statement.sql, "UPDATE [users] SET [active] = @P1 WHERE [deleted_at] IS NULL AND [id] = @P2");
+        assert_eq!(statement.params, vec![json!(false), json!(7)]);
+
+        assert!(super::statement("update", &json!({ "table_name": "users", "data": { "active": false } })).is_err());
+        assert!(super::statement("delete", &json!({ "table_name": "users", "where": {} })).is_err());
+    }
+
+    #[test]
+    fn test_identifiers_are_escaped() {
+        assert_eq!(quote_table("sales.[order items]").unwrap(), "[sales].[order items]");
+        assert_eq!(quote_identifier("odd]name"), "[odd]]name]");
+        assert!(quote_table(" ").is_err());
+    }
+
+    #[test]
+    fn test_cells_convert_to_typed_json() {
+        let guid = Uuid::parse_str("6f9619ff-8b86-d011-b42d-00c04fc964ff").unwrap();
+        assert_eq!(cell_value(ColumnType::Guid, &ColumnData::Guid(Some(guid))).unwrap(), json!(guid.to_string()));
+        assert_eq!(cell_value(ColumnType::Money, &ColumnData::F64(Some(19.99))).unwrap(), json!("19.9900"));
+        assert_eq!(cell_value(ColumnType::Floatn, &ColumnData::F64(Some(0.25))).unwrap(), json!(0.25));
+        assert_eq!(cell_value(ColumnType::Intn, &ColumnData::I32(None)).unwrap(), Value::Null);
+
+        let offset = DateTime::parse_from_rfc3339("2024-03-10T08:30:00+05:30").unwrap();
+        let data = offset.into_sql();
+        assert_eq!(cell_value(ColumnType::DatetimeOffsetn, &data).unwrap(), json!("2024-03-10T08:30:00+05:30"));
+    }
+
+    #[tokio::test]
+    async fn test_auth_and_operation_validation() {
+        let node = SqlServerNode;
+        assert!(node
+            .validate(&context(json!({ "operation": "query", "query": "SELECT 1", "username": "sa", "password": "x" })))
+            .await
+            .is_ok());
+        assert!(node
+            .validate(&context(json!({ "operation": "query", "query": "SELECT 1" })))
+            .await
+            .is_err());
+        assert!(node
+            .validate(&context(json!({ "operation": "truncate", "username": "sa" })))
+            .await
+            .is_err());
+        #[cfg(not(windows))]
+        for auth_type in ["windows", "integrated"] {
+            assert!(node
+                .validate(&context(json!({
+                    "operation": "query",
+                    "query": "SELECT 1",
+                    "auth_type": auth_type,
+                    "username": "CORP\\svc",
+                })))
+                .await
+                .is_err());
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a SQL Server instance"]
+    async fn test_parameterized_query_against_server() {
+        let connection_string = std::env::var(TEST_SERVER_ENV).expect("GHOSTFLOW_TEST_MSSQL must be set");
+
+        let output = SqlServerNode
+            .execute(context(json!({
+                "connection_string": connection_string,
+                "operation": "query",
+                "query": "SELECT @P1 AS name, CAST(@P2 AS money) AS price, \
+                          CAST('6F9619FF-8B86-D011-B42D-00C04FC964FF' AS uniqueidentifier) AS id, \
+                          CAST('2024-03-10T08:30:00+05:30' AS datetimeoffset) AS placed_at",
+                "parameters": ["widget", 19.99],
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["row_count"], 1);
+        let row = &output["rows"][0];
+        assert_eq!(row["name"], "widget");
+        assert_eq!(row["price"], "19.9900");
+        assert_eq!(row["id"], "6f9619ff-8b86-d011-b42d-00c04fc964ff");
+        assert_eq!(row["placed_at"], "2024-03-10T08:30:00+05:30");
+    }
+}
@@ -0,0 +1,700 @@
+use super::s3::AwsCredentials;
+use super::{param_error, validate_required};
+use async_trait::async_trait;
+use aws_sdk_sqs::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_sqs::error::DisplayErrorContext;
+use aws_sdk_sqs::types::{DeleteMessageBatchRequestEntry, Message, MessageAttributeValue, MessageSystemAttributeName};
+use aws_sdk_sqs::Client;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+const OPERATIONS: [&str; 3] = ["send_message", "receive_message", "delete_message"];
+
+/// SQS returns at most 10 messages per receive and long-polls for at most 20 seconds
+const MAX_RECEIVE_MESSAGES: i64 = 10;
+const MAX_WAIT_TIME_SECS: i64 = 20;
+
+/// Longest visibility timeout SQS accepts (12 hours)
+const MAX_VISIBILITY_TIMEOUT_SECS: i64 = 12 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqsNode;
+
+fn client(context: &ExecutionContext) -> Result<Client> {
+    let params = &context.input;
+    let credentials = AwsCredentials::from_context(context)?;
+    let region = params.get("region").and_then(|v| v.as_str()).unwrap_or("us-east-1");
+    let endpoint = params.get("endpoint_url").and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+
+    let mut config = aws_sdk_sqs::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new(region.to_string()))
+        .credentials_provider(Credentials::new(
+            credentials.access_key_id,
+            credentials.secret_access_key,
+            credentials.session_token,
+            None,
+            "ghostflow",
+        ));
+    if let Some(endpoint) = endpoint {
+        // ElasticMQ and LocalStack expose the SQS API on a custom endpoint
+        config = config.endpoint_url(endpoint);
+    }
+
+    Ok(Client::from_conf(config.build()))
+}
+
+fn sqs_error(error: impl std::error::Error) -> GhostFlowError {
+    GhostFlowError::NetworkError(format!("SQS request failed: {}", DisplayErrorContext(error)))
+}
+
+fn required_str<'a>(params: &'a Value, key: &str) -> Result<&'a str> {
+    params
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| param_error(format!("SQS parameter '{}' is required for this operation", key)))
+}
+
+fn optional_str(params: &Value, key: &str) -> Option<String> {
+    params.get(key).and_then(|v| v.as_str()).filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+/// Integer parameter that must fall within `min..=max` when given
+fn bounded_i64(params: &Value, key: &str, min: i64, max: i64) -> Result<Option<i64>> {
+    match params.get(key).filter(|v| !v.is_null()) {
+        None => Ok(None),
+        Some(value) => value
+            .as_i64()
+            .filter(|n| (min..=max).contains(n))
+            .map(Some)
+            .ok_or_else(|| param_error(format!("SQS parameter '{}' must be between {} and {}", key, min, max))),
+    }
+}
+
+fn is_fifo(queue_url: &str) -> bool {
+    queue_url.ends_with(".fifo")
+}
+
+/// Message body to send: strings as-is, anything else as JSON
+fn message_body(params: &Value) -> Result<String> {
+    match params.get("message_body") {
+        Some(Value::String(text)) if !text.is_empty() => Ok(text.clone()),
+        Some(other) if !other.is_null() && !other.is_string() => Ok(other.to_string()),
+        _ => Err(param_error("send_message needs a 'message_body'")),
+    }
+}
+
+/// Message attributes from a `{name: value}` object. Numbers become `Number`
+/// attributes and everything else a `String`; `{"data_type": .., "value": ..}`
+/// sets the type explicitly.
+fn message_attributes(params: &Value) -> Result<Option<HashMap<String, MessageAttributeValue>>> {
+    let Some(attributes) = params.get("message_attributes").filter(|v| !v.is_null()) else {
+        return Ok(None);
+    };
+    let attributes = attributes
+        .as_object()
+        .ok_or_else(|| param_error("SQS 'message_attributes' must be an object"))?;
+
+    let mut converted = HashMap::with_capacity(attributes.len());
+    for (name, value) in attributes {
+        let (data_type, value) = match value {
+            Value::Number(n) => ("Number".to_string(), n.to_string()),
+            Value::String(s) => ("String".to_string(), s.clone()),
+            Value::Object(typed) if typed.contains_key("data_type") => {
+                let data_type = typed.get("data_type").and_then(|v| v.as_str()).unwrap_or("String");
+                let value = match typed.get("value") {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(other) => other.to_string(),
+                    None => return Err(param_error(format!("Message attribute '{}' has no value", name))),
+                };
+                (data_type.to_string(), value)
+            }
+            other => ("String".to_string(), other.to_string()),
+        };
+        let attribute = MessageAttributeValue::builder()
+            .data_type(data_type)
+            .string_value(value)
+            .build()
+            .map_err(|e| param_error(format!("Invalid message attribute '{}': {}", name, e)))?;
+        converted.insert(name.clone(), attribute);
+    }
+    Ok(Some(converted))
+}
+
+fn message_to_value(message: &Message) -> Value {
+    let body = message.body().unwrap_or_default();
+    let attributes: Map<String, Value> = message
+        .message_attributes()
+        .into_iter()
+        .flatten()
+        .map(|(name, attribute)| (name.clone(), json!(attribute.string_value())))
+        .collect();
+    let system_attributes: Map<String, Value> = message
+        .attributes()
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| (name.as_str().to_string(), json!(value)))
+        .collect();
+
+    json!({
+        "message_id": message.message_id(),
+        "receipt_handle": message.receipt_handle(),
+        "body": body,
+        // Parsed body, for the common case of JSON payloads
+        "json": serde_json::from_str::<Value>(body).ok(),
+        "message_attributes": attributes,
+        "attributes": system_attributes,
+    })
+}
+
+/// Delete received messages in one batch, returning the ids that failed
+async fn delete_messages(client: &Client, queue_url: &str, messages: &[Message]) -> Result<Vec<Value>> {
+    let entries = messages
+        .iter()
+        .enumerate()
+        .filter_map(|(index, message)| {
+            let handle = message.receipt_handle()?;
+            Some(
+                DeleteMessageBatchRequestEntry::builder()
+                    .id(index.to_string())
+                    .receipt_handle(handle)
+                    .build()
+                    .map_err(sqs_error),
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if entries.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let output = client
+        .delete_message_batch()
+        .queue_url(queue_url)
+        .set_entries(Some(entries))
+        .send()
+        .await
+        .map_err(sqs_error)?;
+
+    Ok(output
+        .failed()
+        .iter()
+        .map(|failure| {
+            let message_id = failure
+                .id()
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| messages.get(index))
+                .and_then(|message| message.message_id());
+            json!({ "message_id": message_id, "code": failure.code(), "message": failure.message() })
+        })
+        .collect())
+}
+
+#[async_trait]
+impl Node for SqsNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "aws_sqs".to_string(),
+            name: "AWS SQS".to_string(),
+            description: "Send, receive and delete messages on an Amazon SQS queue".to_string(),
+            category: NodeCategory::Integration,
+            version: "1.0.0".to_string(),
+            parameters: vec![
+                NodeParameter {
+                    name: "operation".to_string(),
+                    display_name: "Operation".to_string(),
+                    description: Some("Queue operation to perform".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("send_message".to_string())),
+                    required: true,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "send_message", "label": "Send Message"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "receive_message", "label": "Receive Messages"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "delete_message", "label": "Delete Message"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "queue_url".to_string(),
+                    display_name: "Queue URL".to_string(),
+                    description: Some("Full queue URL; FIFO queues end in .fifo".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "region".to_string(),
+                    display_name: "Region".to_string(),
+                    description: Some("AWS region".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("us-east-1".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "access_key_id".to_string(),
+                    display_name: "Access Key ID".to_string(),
+                    description: Some("AWS access key ID".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "secret_access_key".to_string(),
+                    display_name: "Secret Access Key".to_string(),
+                    description: Some("AWS secret access key".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "session_token".to_string(),
+                    display_name: "Session Token".to_string(),
+                    description: Some("Temporary session token, if using STS credentials".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "credential".to_string(),
+                    display_name: "Credential".to_string(),
+                    description: Some("Name of a stored AWS credential to use instead of inline keys".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "endpoint_url".to_string(),
+                    display_name: "Endpoint URL".to_string(),
+                    description: Some("Custom endpoint for SQS-compatible queues (ElasticMQ, LocalStack)".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "message_body".to_string(),
+                    display_name: "Message Body".to_string(),
+                    description: Some("Body for send_message; non-string values are sent as JSON".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "message_attributes".to_string(),
+                    display_name: "Message Attributes".to_string(),
+                    description: Some("Attributes to attach, as an object of name to value".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "message_group_id".to_string(),
+                    display_name: "Message Group ID".to_string(),
+                    description: Some("Ordering group; required for FIFO queues".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "message_deduplication_id".to_string(),
+                    display_name: "Deduplication ID".to_string(),
+                    description: Some("FIFO deduplication token; omit when content-based deduplication is on".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "delay_seconds".to_string(),
+                    display_name: "Delay (seconds)".to_string(),
+                    description: Some("Hide the sent message for this long (standard queues only)".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_messages".to_string(),
+                    display_name: "Max Messages".to_string(),
+                    description: Some("Messages to receive at once, 1 to 10".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(1)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "wait_time_seconds".to_string(),
+                    display_name: "Wait Time (seconds)".to_string(),
+                    description: Some("Long-poll for up to this long when the queue is empty, at most 20".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(MAX_WAIT_TIME_SECS)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "visibility_timeout".to_string(),
+                    display_name: "Visibility Timeout (seconds)".to_string(),
+                    description: Some("Hide received messages from other consumers for this long".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "delete_after_receive".to_string(),
+                    display_name: "Delete After Receive".to_string(),
+                    description: Some(
+                        "Delete messages once received; leave off and use delete_message after processing instead"
+                            .to_string(),
+                    ),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "receipt_handle".to_string(),
+                    display_name: "Receipt Handle".to_string(),
+                    description: Some("Handle of the received message to delete".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            inputs: vec![NodePort {
+                name: "message_body".to_string(),
+                display_name: "Message Body".to_string(),
+                description: Some("Payload for send_message".to_string()),
+                data_type: DataType::Any,
+                required: false,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "messages".to_string(),
+                    display_name: "Messages".to_string(),
+                    description: Some("Messages returned by receive_message".to_string()),
+                    data_type: DataType::Array,
+                    required: false,
+                },
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: Some("Operation result".to_string()),
+                    data_type: DataType::Object,
+                    required: true,
+                },
+            ],
+            icon: Some("aws-sqs".to_string()),
+            color: Some("#ff4f8b".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)?;
+
+        let params = &context.input;
+        let operation = params.get("operation").and_then(|v| v.as_str()).unwrap_or("send_message");
+        let queue_url = required_str(params, "queue_url")?;
+        match operation {
+            "send_message" => {
+                message_body(params)?;
+                message_attributes(params)?;
+                if is_fifo(queue_url) {
+                    required_str(params, "message_group_id")?;
+                    if params.get("delay_seconds").is_some_and(|v| !v.is_null()) {
+                        return Err(param_error("FIFO queues do not support a per-message delay_seconds"));
+                    }
+                }
+                bounded_i64(params, "delay_seconds", 0, 900)?;
+            }
+            "receive_message" => {
+                bounded_i64(params, "max_messages", 1, MAX_RECEIVE_MESSAGES)?;
+                bounded_i64(params, "wait_time_seconds", 0, MAX_WAIT_TIME_SECS)?;
+                bounded_i64(params, "visibility_timeout", 0, MAX_VISIBILITY_TIMEOUT_SECS)?;
+            }
+            "delete_message" => {
+                required_str(params, "receipt_handle")?;
+            }
+            other => return Err(param_error(format!("Unsupported SQS operation: {}", other))),
+        }
+        AwsCredentials::from_context(context)?;
+        Ok(())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let operation = params.get("operation").and_then(|v| v.as_str()).unwrap_or("send_message");
+        let queue_url = required_str(params, "queue_url")?;
+        let client = client(&context)?;
+
+        match operation {
+            "send_message" => {
+                let output = client
+                    .send_message()
+                    .queue_url(queue_url)
+                    .message_body(message_body(params)?)
+                    .set_message_attributes(message_attributes(params)?)
+                    .set_message_group_id(optional_str(params, "message_group_id"))
+                    .set_message_deduplication_id(optional_str(params, "message_deduplication_id"))
+                    .set_delay_seconds(bounded_i64(params, "delay_seconds", 0, 900)?.map(|n| n as i32))
+                    .send()
+                    .await
+                    .map_err(sqs_error)?;
+
+                Ok(json!({
+                    "result": {
+                        "queue_url": queue_url,
+                        "message_id": output.message_id(),
+                        "sequence_number": output.sequence_number(),
+                        "md5_of_message_body": output.md5_of_message_body(),
+                    }
+                }))
+            }
+            "receive_message" => {
+                let max_messages = bounded_i64(params, "max_messages", 1, MAX_RECEIVE_MESSAGES)?.unwrap_or(1);
+                let wait_time = bounded_i64(params, "wait_time_seconds", 0, MAX_WAIT_TIME_SECS)?
+                    .unwrap_or(MAX_WAIT_TIME_SECS);
+                let visibility_timeout = bounded_i64(params, "visibility_timeout", 0, MAX_VISIBILITY_TIMEOUT_SECS)?;
+
+                let output = client
+                    .receive_message()
+                    .queue_url(queue_url)
+                    .max_number_of_messages(max_messages as i32)
+                    .wait_time_seconds(wait_time as i32)
+                    .set_visibility_timeout(visibility_timeout.map(|n| n as i32))
+                    .message_attribute_names("All")
+                    .message_system_attribute_names(MessageSystemAttributeName::All)
+                    .send()
+                    .await
+                    .map_err(sqs_error)?;
+
+                let messages = output.messages();
+                let delete = params.get("delete_after_receive").and_then(|v| v.as_bool()).unwrap_or(false);
+                let delete_failures = if delete {
+                    delete_messages(&client, queue_url, messages).await?
+                } else {
+                    Vec::new()
+                };
+
+                Ok(json!({
+                    "messages": messages.iter().map(message_to_value).collect::<Vec<_>>(),
+                    "result": {
+                        "queue_url": queue_url,
+                        "count": messages.len(),
+                        "deleted": if delete { messages.len() - delete_failures.len() } else { 0 },
+                        "delete_failures": delete_failures,
+                    }
+                }))
+            }
+            "delete_message" => {
+                let receipt_handle = required_str(params, "receipt_handle")?;
+                client
+                    .delete_message()
+                    .queue_url(queue_url)
+                    .receipt_handle(receipt_handle)
+                    .send()
+                    .await
+                    .map_err(sqs_error)?;
+
+                Ok(json!({
+                    "result": {
+                        "queue_url": queue_url,
+                        "deleted": true,
+                    }
+                }))
+            }
+            other => Err(param_error(format!("Unsupported SQS operation: {}", other))),
+        }
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use std::sync::{Arc, Mutex};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    /// Answers the SQS JSON protocol by `X-Amz-Target` action, keeping each
+    /// request body so tests can check what was sent
+    #[derive(Clone, Default)]
+    struct MockQueue {
+        requests: Arc<Mutex<Vec<(String, Value)>>>,
+    }
+
+    impl MockQueue {
+        fn requests(&self, action: &str) -> Vec<Value> {
+            let requests = self.requests.lock().unwrap();
+            requests.iter().filter(|(a, _)| a == action).map(|(_, body)| body.clone()).collect()
+        }
+    }
+
+    impl Respond for MockQueue {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let action = request
+                .headers
+                .get("x-amz-target")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|target| target.strip_prefix("AmazonSQS."))
+                .unwrap_or_default()
+                .to_string();
+            let body: Value = serde_json::from_slice(&request.body).unwrap_or(Value::Null);
+            self.requests.lock().unwrap().push((action.clone(), body.clone()));
+
+            let response = match action.as_str() {
+                "SendMessage" => json!({
+                    "MessageId": "5fea7756-0ea4-451a-a703-a558b933e274",
+                    "SequenceNumber": "18849496460467696128",
+                }),
+                "ReceiveMessage" => json!({
+                    "Messages": [{
+                        "MessageId": "m-1",
+                        "ReceiptHandle": "handle-1",
+                        "Body": "{\"vmid\": 101, \"action\": \"backup\"}",
+                        "Attributes": { "ApproximateReceiveCount": "1" },
+                        "MessageAttributes": { "source": { "DataType": "String", "StringValue": "proxmox" } },
+                    }, {
+                        "MessageId": "m-2",
+                        "ReceiptHandle": "handle-2",
+                        "Body": "plain text",
+                    }]
+                }),
+                "DeleteMessageBatch" => {
+                    let successful: Vec<Value> = body["Entries"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|entry| json!({ "Id": entry["Id"] }))
+                        .collect();
+                    json!({ "Successful": successful, "Failed": [] })
+                }
+                _ => return ResponseTemplate::new(400),
+            };
+            ResponseTemplate::new(200).set_body_raw(response.to_string(), "application/x-amz-json-1.0")
+        }
+    }
+
+    async fn mock_queue() -> (MockServer, MockQueue) {
+        let server = MockServer::start().await;
+        let queue = MockQueue::default();
+        Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(queue.clone())
+            .mount(&server)
+            .await;
+        (server, queue)
+    }
+
+    fn params(server: &MockServer, operation: &str, queue: &str) -> Value {
+        json!({
+            "operation": operation,
+            "queue_url": format!("{}/123456789012/{}", server.uri(), queue),
+            "region": "us-east-1",
+            "endpoint_url": server.uri(),
+            "access_key_id": "AKIDEXAMPLE",
+            "secret_access_key": "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_send_message_to_fifo_queue() {
+        let (server, queue) = mock_queue().await;
+        let mut input = params(&server, "send_message", "jobs.fifo");
+        input["message_body"] = json!({ "vmid": 101, "action": "backup" });
+        input["message_attributes"] = json!({ "source": "proxmox", "priority": 5 });
+        input["message_group_id"] = json!("node-1");
+        input["message_deduplication_id"] = json!("backup-101");
+
+        let node = SqsNode;
+        node.validate(&context(input.clone())).await.unwrap();
+        let output = node.execute(context(input)).await.unwrap();
+        assert_eq!(output["result"]["message_id"], "5fea7756-0ea4-451a-a703-a558b933e274");
+
+        let sent = queue.requests("SendMessage").remove(0);
+        assert_eq!(sent["MessageGroupId"], "node-1");
+        assert_eq!(sent["MessageDeduplicationId"], "backup-101");
+        assert_eq!(serde_json::from_str::<Value>(sent["MessageBody"].as_str().unwrap()).unwrap()["vmid"], 101);
+        assert_eq!(sent["MessageAttributes"]["source"]["DataType"], "String");
+        assert_eq!(sent["MessageAttributes"]["priority"]["DataType"], "Number");
+        assert_eq!(sent["MessageAttributes"]["priority"]["StringValue"], "5");
+    }
+
+    #[tokio::test]
+    async fn test_receive_then_delete() {
+        let (server, queue) = mock_queue().await;
+        let mut input = params(&server, "receive_message", "jobs");
+        input["max_messages"] = json!(10);
+        input["wait_time_seconds"] = json!(5);
+        input["visibility_timeout"] = json!(120);
+        input["delete_after_receive"] = json!(true);
+
+        let output = SqsNode.execute(context(input)).await.unwrap();
+        assert_eq!(output["result"]["count"], 2);
+        assert_eq!(output["result"]["deleted"], 2);
+        assert_eq!(output["messages"][0]["json"]["vmid"], 101);
+        assert_eq!(output["messages"][0]["message_attributes"]["source"], "proxmox");
+        assert_eq!(output["messages"][1]["json"], Value::Null);
+
+        let received = queue.requests("ReceiveMessage").remove(0);
+        assert_eq!(received["MaxNumberOfMessages"], 10);
+        assert_eq!(received["WaitTimeSeconds"], 5);
+        assert_eq!(received["VisibilityTimeout"], 120);
+
+        let deleted = queue.requests("DeleteMessageBatch").remove(0);
+        let handles: Vec<&str> = deleted["Entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["ReceiptHandle"].as_str().unwrap())
+            .collect();
+        assert_eq!(handles, ["handle-1", "handle-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_fifo_send_requires_group_id() {
+        let input = json!({
+            "operation": "send_message",
+            "queue_url": "https://sqs.us-east-1.amazonaws.com/123456789012/jobs.fifo",
+            "message_body": "hello",
+            "access_key_id": "AKID",
+            "secret_access_key": "secret",
+        });
+        let err = SqsNode.validate(&context(input)).await.unwrap_err();
+        assert!(err.to_string().contains("message_group_id"), "{}", err);
+    }
+}
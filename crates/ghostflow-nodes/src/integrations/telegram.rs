@@ -0,0 +1,553 @@
+use super::{network_error, param_error, validate_required};
+use crate::http_util::shared_client;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    BinaryData, DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::warn;
+
+const OPERATIONS: [&str; 3] = ["send_message", "send_photo", "send_document"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramNode;
+
+/// What to attach for `send_photo`/`send_document`: a URL or file_id that
+/// Telegram fetches itself, or bytes from the `file` input to upload.
+enum Attachment {
+    Remote(String),
+    Upload(BinaryData),
+}
+
+/// `reply_markup` for an inline keyboard. Accepts rows of buttons, or a flat
+/// list of buttons which becomes a single row.
+fn inline_keyboard(keyboard: &Value) -> Result<Option<Value>> {
+    let rows = match keyboard {
+        Value::Null => return Ok(None),
+        Value::Array(items) if items.is_empty() => return Ok(None),
+        Value::Array(items) if items.iter().all(Value::is_array) => items.clone(),
+        Value::Array(items) if items.iter().all(Value::is_object) => vec![Value::Array(items.clone())],
+        _ => return Err(param_error("Telegram inline_keyboard must be a list of button rows")),
+    };
+
+    for button in rows.iter().flat_map(|row| row.as_array().into_iter().flatten()) {
+        let has_action = ["url", "callback_data"].iter().any(|key| button.get(key).is_some());
+        if button.get("text").and_then(|v| v.as_str()).is_none() || !has_action {
+            return Err(param_error(
+                "Each Telegram inline keyboard button needs 'text' and a 'url' or 'callback_data'",
+            ));
+        }
+    }
+
+    Ok(Some(json!({ "inline_keyboard": rows })))
+}
+
+/// The JSON fields shared by every operation apart from the payload itself.
+fn common_fields(params: &Value) -> Result<serde_json::Map<String, Value>> {
+    let chat_id = params
+        .get("chat_id")
+        .filter(|v| v.is_string() || v.is_number())
+        .cloned()
+        .ok_or_else(|| param_error("Telegram chat_id is required"))?;
+
+    let mut fields = serde_json::Map::new();
+    fields.insert("chat_id".to_string(), chat_id);
+    if let Some(mode) = params.get("parse_mode").and_then(|v| v.as_str()).filter(|m| !m.is_empty() && *m != "none") {
+        fields.insert("parse_mode".to_string(), json!(mode));
+    }
+    if params.get("disable_notification").and_then(|v| v.as_bool()).unwrap_or(false) {
+        fields.insert("disable_notification".to_string(), json!(true));
+    }
+    if let Some(markup) = inline_keyboard(params.get("inline_keyboard").unwrap_or(&Value::Null))? {
+        fields.insert("reply_markup".to_string(), markup);
+    }
+    Ok(fields)
+}
+
+fn attachment(params: &Value, field: &str) -> Result<Attachment> {
+    if let Some(file) = params.get("file").filter(|v| !v.is_null()) {
+        return BinaryData::from_value(file)
+            .map(Attachment::Upload)
+            .ok_or_else(|| param_error("Telegram 'file' input must be binary data"));
+    }
+    params
+        .get(field)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| Attachment::Remote(s.to_string()))
+        .ok_or_else(|| param_error(format!("Telegram '{}' (URL or file_id) or a 'file' input is required", field)))
+}
+
+/// Build the request for one attempt. Uploads are multipart and cannot be
+/// cloned, so the request is rebuilt for every retry.
+fn build_request(
+    client: &reqwest::Client,
+    url: &str,
+    fields: &serde_json::Map<String, Value>,
+    upload: Option<(&str, &BinaryData)>,
+) -> Result<RequestBuilder> {
+    let Some((field, file)) = upload else {
+        return Ok(client.post(url).json(fields));
+    };
+
+    let mut form = Form::new();
+    for (key, value) in fields {
+        // Multipart fields are strings; nested values such as reply_markup
+        // are sent as JSON text
+        let text = value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string());
+        form = form.text(key.clone(), text);
+    }
+    let mut part = Part::bytes(file.data.clone()).file_name(file.filename.clone().unwrap_or_else(|| field.to_string()));
+    if let Some(content_type) = &file.content_type {
+        part = part.mime_str(content_type).map_err(network_error)?;
+    }
+    Ok(client.post(url).multipart(form.part(field.to_string(), part)))
+}
+
+/// Call a Bot API method, waiting out `429 Too Many Requests` for the
+/// `retry_after` Telegram reports (capped at `max_wait`) up to `max_retries`
+/// times. Returns the `result` of a successful call.
+async fn call_bot_api(
+    client: &reqwest::Client,
+    url: &str,
+    fields: &serde_json::Map<String, Value>,
+    upload: Option<(&str, &BinaryData)>,
+    max_retries: u32,
+    max_wait: Duration,
+    node_id: &str,
+) -> Result<Value> {
+    let mut attempt = 0;
+
+    loop {
+        let response = build_request(client, url, fields, upload)?.send().await.map_err(network_error)?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or_else(|_| json!({}));
+
+        if body.get("ok").and_then(|v| v.as_bool()) == Some(true) {
+            return Ok(body.get("result").cloned().unwrap_or(Value::Null));
+        }
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = Duration::from_secs(
+                body.pointer("/parameters/retry_after").and_then(|v| v.as_u64()).unwrap_or(1),
+            );
+            if attempt < max_retries && retry_after <= max_wait {
+                attempt += 1;
+                warn!(
+                    "Telegram rate limit hit, retry {} of {} in {:?}",
+                    attempt, max_retries, retry_after
+                );
+                tokio::time::sleep(retry_after).await;
+                continue;
+            }
+        }
+
+        let description = body
+            .get("description")
+            .and_then(|v| v.as_str())
+            .unwrap_or("no description");
+        return Err(GhostFlowError::NodeExecutionError {
+            node_id: node_id.to_string(),
+            message: format!("Telegram API error (HTTP {}): {}", status.as_u16(), description),
+        });
+    }
+}
+
+#[async_trait]
+impl Node for TelegramNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "telegram".to_string(),
+            name: "Telegram".to_string(),
+            description: "Send messages, photos and documents with a Telegram bot".to_string(),
+            category: NodeCategory::Integration,
+            version: "1.0.0".to_string(),
+            parameters: vec![
+                NodeParameter {
+                    name: "bot_token".to_string(),
+                    display_name: "Bot Token".to_string(),
+                    description: Some("Token issued by @BotFather".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "operation".to_string(),
+                    display_name: "Operation".to_string(),
+                    description: Some("What to send".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("send_message".to_string())),
+                    required: true,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "send_message", "label": "Send Message"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "send_photo", "label": "Send Photo"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "send_document", "label": "Send Document"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "chat_id".to_string(),
+                    display_name: "Chat ID".to_string(),
+                    description: Some("Chat ID or @channel username".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "text".to_string(),
+                    display_name: "Text".to_string(),
+                    description: Some("Message text for send_message".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "parse_mode".to_string(),
+                    display_name: "Parse Mode".to_string(),
+                    description: Some("How Telegram formats the text or caption".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("none".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "none", "label": "Plain Text"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "Markdown", "label": "Markdown"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "MarkdownV2", "label": "MarkdownV2"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "HTML", "label": "HTML"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "inline_keyboard".to_string(),
+                    display_name: "Inline Keyboard".to_string(),
+                    description: Some("Rows of buttons, each with text and a url or callback_data".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "photo".to_string(),
+                    display_name: "Photo".to_string(),
+                    description: Some("Photo URL or file_id; ignored when a file is connected".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "document".to_string(),
+                    display_name: "Document".to_string(),
+                    description: Some("Document URL or file_id; ignored when a file is connected".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "caption".to_string(),
+                    display_name: "Caption".to_string(),
+                    description: Some("Caption for a photo or document".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "disable_notification".to_string(),
+                    display_name: "Silent".to_string(),
+                    description: Some("Deliver without a notification sound".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "base_url".to_string(),
+                    display_name: "API URL".to_string(),
+                    description: Some("Bot API root; change for a self-hosted Bot API server".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("https://api.telegram.org".to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_retries".to_string(),
+                    display_name: "Rate Limit Retries".to_string(),
+                    description: Some("Times to wait out a 429 before failing".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(3)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_wait".to_string(),
+                    display_name: "Max Rate Limit Wait (seconds)".to_string(),
+                    description: Some("Fail instead of waiting longer than this for a 429".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(60)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            inputs: vec![NodePort {
+                name: "file".to_string(),
+                display_name: "File".to_string(),
+                description: Some("Binary photo or document to upload".to_string()),
+                data_type: DataType::Binary,
+                required: false,
+            }],
+            outputs: vec![NodePort {
+                name: "message_id".to_string(),
+                display_name: "Message ID".to_string(),
+                description: Some("ID of the sent message".to_string()),
+                data_type: DataType::Number,
+                required: true,
+            }],
+            icon: Some("telegram".to_string()),
+            color: Some("#26a5e4".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        validate_required(&self.definition(), context)?;
+
+        let operation = context.input.get("operation").and_then(|v| v.as_str()).unwrap_or("send_message");
+        if !OPERATIONS.contains(&operation) {
+            return Err(param_error(format!("Unsupported Telegram operation: {}", operation)));
+        }
+        inline_keyboard(context.input.get("inline_keyboard").unwrap_or(&Value::Null))?;
+        Ok(())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let token = params
+            .get("bot_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| param_error("Telegram bot token is required"))?;
+        let base_url = params
+            .get("base_url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("https://api.telegram.org")
+            .trim_end_matches('/');
+        let operation = params.get("operation").and_then(|v| v.as_str()).unwrap_or("send_message");
+        let max_retries = params.get("max_retries").and_then(|v| v.as_u64()).unwrap_or(3) as u32;
+        let max_wait = Duration::from_secs(params.get("max_wait").and_then(|v| v.as_u64()).unwrap_or(60));
+
+        let mut fields = common_fields(params)?;
+        let caption = params.get("caption").and_then(|v| v.as_str());
+
+        let (method, payload) = match operation {
+            "send_message" => {
+                let text = params
+                    .get("text")
+                    .and_then(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .ok_or_else(|| param_error("Telegram text is required for send_message"))?;
+                fields.insert("text".to_string(), json!(text));
+                ("sendMessage", None)
+            }
+            "send_photo" => ("sendPhoto", Some(("photo", attachment(params, "photo")?))),
+            "send_document" => ("sendDocument", Some(("document", attachment(params, "document")?))),
+            other => return Err(param_error(format!("Unsupported Telegram operation: {}", other))),
+        };
+
+        let mut upload = None;
+        if let Some((field, attachment)) = &payload {
+            if let Some(caption) = caption {
+                fields.insert("caption".to_string(), json!(caption));
+            }
+            match attachment {
+                Attachment::Remote(reference) => {
+                    fields.insert(field.to_string(), json!(reference));
+                }
+                Attachment::Upload(file) => upload = Some((*field, file)),
+            }
+        }
+
+        let client = shared_client();
+        let url = format!("{}/bot{}/{}", base_url, token, method);
+        let message = call_bot_api(client, &url, &fields, upload, max_retries, max_wait, &context.node_id).await?;
+
+        Ok(json!({
+            "message_id": message.get("message_id").cloned().unwrap_or(Value::Null),
+            "chat_id": message.pointer("/chat/id").cloned().unwrap_or(Value::Null),
+            "result": message,
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        // Rate limits are retried here; a blind retry could post the message twice
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use wiremock::matchers::{body_json, body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sent(message_id: u64) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "ok": true,
+            "result": { "message_id": message_id, "chat": { "id": -100123 } }
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_send_message_payload() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bot123:abc/sendMessage"))
+            .and(body_json(json!({
+                "chat_id": "-100123",
+                "text": "<b>Backup failed</b> on pve-01",
+                "parse_mode": "HTML",
+                "reply_markup": {
+                    "inline_keyboard": [[
+                        { "text": "Retry", "callback_data": "retry:backup" },
+                        { "text": "Runbook", "url": "https://wiki.example.com/backup" }
+                    ]]
+                }
+            })))
+            .respond_with(sent(77))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = TelegramNode
+            .execute(context(json!({
+                "bot_token": "123:abc",
+                "base_url": server.uri(),
+                "chat_id": "-100123",
+                "text": "<b>Backup failed</b> on pve-01",
+                "parse_mode": "HTML",
+                "inline_keyboard": [
+                    { "text": "Retry", "callback_data": "retry:backup" },
+                    { "text": "Runbook", "url": "https://wiki.example.com/backup" }
+                ]
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["message_id"], 77);
+        assert_eq!(result["chat_id"], -100123);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_waits_for_retry_after() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bot123:abc/sendMessage"))
+            .respond_with(ResponseTemplate::new(429).set_body_json(json!({
+                "ok": false,
+                "error_code": 429,
+                "description": "Too Many Requests: retry after 0",
+                "parameters": { "retry_after": 0 }
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bot123:abc/sendMessage"))
+            .respond_with(sent(78))
+            .mount(&server)
+            .await;
+
+        let result = TelegramNode
+            .execute(context(json!({
+                "bot_token": "123:abc",
+                "base_url": server.uri(),
+                "chat_id": 42,
+                "text": "hello"
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["message_id"], 78);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_longer_than_max_wait_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).set_body_json(json!({
+                "ok": false,
+                "error_code": 429,
+                "description": "Too Many Requests: retry after 120",
+                "parameters": { "retry_after": 120 }
+            })))
+            .mount(&server)
+            .await;
+
+        let error = TelegramNode
+            .execute(context(json!({
+                "bot_token": "123:abc",
+                "base_url": server.uri(),
+                "chat_id": 42,
+                "text": "hello"
+            })))
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("retry after 120"), "{}", error);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_document_uploads_binary_input() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bot123:abc/sendDocument"))
+            .and(body_string_contains("filename=\"report.csv\""))
+            .and(body_string_contains("host,status"))
+            .respond_with(sent(79))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let file = BinaryData::new(b"host,status\npve-01,down\n".to_vec())
+            .with_content_type("text/csv")
+            .with_filename("report.csv");
+        let result = TelegramNode
+            .execute(context(json!({
+                "bot_token": "123:abc",
+                "base_url": server.uri(),
+                "operation": "send_document",
+                "chat_id": 42,
+                "caption": "Nightly report",
+                "file": file.to_value()
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["message_id"], 79);
+    }
+}
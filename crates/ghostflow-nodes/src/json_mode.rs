@@ -0,0 +1,142 @@
+use ghostflow_core::{GhostFlowError, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{DataType, ExecutionContext, NodeParameter, NodePort};
+use serde_json::Value;
+use std::future::Future;
+use tracing::warn;
+
+const JSON_INSTRUCTION: &str =
+    "Respond with a single valid JSON document only. Do not include explanations, markdown or code fences.";
+
+const DEFAULT_JSON_RETRIES: u64 = 2;
+
+/// Whether the node was asked for `response_format: json`
+pub(crate) fn json_mode_enabled(params: &Value) -> bool {
+    params.get("response_format").and_then(|v| v.as_str()) == Some("json")
+}
+
+/// Extract a JSON document from model output, tolerating surrounding
+/// whitespace and a markdown code fence.
+pub(crate) fn parse_json_text(text: &str) -> std::result::Result<Value, serde_json::Error> {
+    let mut trimmed = text.trim();
+    if let Some(rest) = trimmed.strip_prefix("```") {
+        let rest = rest.strip_prefix("json").unwrap_or(rest);
+        trimmed = rest.strip_suffix("```").unwrap_or(rest).trim();
+    }
+    serde_json::from_str(trimmed)
+}
+
+/// Run `generate` in JSON mode. The prompt gets a JSON-only instruction and,
+/// when the model's reply (read from `text_field`) fails to parse, the call
+/// is repeated with a corrective reprompt up to `json_retries` times. On
+/// success the parsed document is added to the output under `json`.
+pub(crate) async fn generate_json<F, Fut>(
+    mut context: ExecutionContext,
+    text_field: &str,
+    generate: F,
+) -> Result<Value>
+where
+    F: Fn(ExecutionContext) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    let max_retries = context
+        .input
+        .get("json_retries")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_JSON_RETRIES);
+    let prompt = context
+        .input
+        .get("prompt")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let mut attempt_prompt = format!("{}\n\n{}", prompt, JSON_INSTRUCTION);
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+        context.input["prompt"] = Value::String(attempt_prompt.clone());
+
+        let mut output = generate(context.clone()).await?;
+        let text = output
+            .get(text_field)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        match parse_json_text(&text) {
+            Ok(parsed) => {
+                output["json"] = parsed;
+                output["json_attempts"] = Value::from(attempt);
+                return Ok(output);
+            }
+            Err(e) if attempt <= max_retries => {
+                warn!("Model returned invalid JSON on attempt {}: {}", attempt, e);
+                attempt_prompt = format!(
+                    "{}\n\nYour previous reply was not valid JSON ({}):\n{}\n\n{}",
+                    prompt, e, text, JSON_INSTRUCTION
+                );
+            }
+            Err(e) => {
+                return Err(GhostFlowError::NodeExecutionError {
+                    node_id: context.node_id.clone(),
+                    message: format!(
+                        "Model did not return valid JSON after {} attempts: {}",
+                        attempt, e
+                    ),
+                });
+            }
+        }
+    }
+}
+
+pub(crate) fn json_mode_parameters() -> Vec<NodeParameter> {
+    vec![
+        NodeParameter {
+            name: "response_format".to_string(),
+            display_name: "Response Format".to_string(),
+            description: Some("Require the model to answer with parseable JSON".to_string()),
+            param_type: ParameterType::Select,
+            default_value: Some(Value::String("text".to_string())),
+            required: false,
+            options: Some(vec![
+                serde_json::from_str(r#"{"value": "text", "label": "Text"}"#).unwrap(),
+                serde_json::from_str(r#"{"value": "json", "label": "JSON"}"#).unwrap(),
+            ]),
+            validation: None,
+        },
+        NodeParameter {
+            name: "json_retries".to_string(),
+            display_name: "JSON Retries".to_string(),
+            description: Some("Corrective reprompts to attempt when the reply is not valid JSON".to_string()),
+            param_type: ParameterType::Number,
+            default_value: Some(Value::Number(serde_json::Number::from(DEFAULT_JSON_RETRIES))),
+            required: false,
+            options: None,
+            validation: None,
+        },
+    ]
+}
+
+pub(crate) fn json_output_port() -> NodePort {
+    NodePort {
+        name: "json".to_string(),
+        display_name: "JSON".to_string(),
+        description: Some("Parsed response when response_format is json".to_string()),
+        data_type: DataType::Any,
+        required: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_text_strips_code_fence() {
+        let parsed = parse_json_text("```json\n{\"ok\": true}\n```").unwrap();
+        assert_eq!(parsed, serde_json::json!({"ok": true}));
+        assert!(parse_json_text("Sure! {\"ok\": true}").is_err());
+    }
+}
\ No newline at end of file
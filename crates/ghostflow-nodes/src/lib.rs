@@ -1,15 +1,57 @@
 pub mod http;
+pub mod http_util;
 pub mod control_flow;
+pub mod collect;
+pub mod approval;
+pub mod try_catch;
+pub mod escalation;
 pub mod template;
+pub mod transform;
+pub mod filter;
+pub mod aggregate;
+pub mod diff;
+pub mod delimited;
+pub mod variables;
 pub mod webhook;
 pub mod ollama;
 pub mod ghostllm;
+pub mod llm;
+pub mod conversation;
+pub mod vector_search;
+mod json_mode;
+mod prompt_template;
+mod pagination;
+mod template_functions;
+#[cfg(test)]
+mod test_support;
+pub mod shell;
+pub mod file;
+pub mod script;
 pub mod integrations;
+pub mod registry;
 
 pub use http::*;
+pub use http_util::*;
 pub use control_flow::*;
+pub use collect::*;
+pub use approval::*;
+pub use try_catch::*;
+pub use escalation::*;
 pub use template::*;
+pub use transform::*;
+pub use filter::*;
+pub use aggregate::*;
+pub use diff::*;
+pub use delimited::*;
+pub use variables::*;
 pub use webhook::*;
 pub use ollama::*;
 pub use ghostllm::*;
-pub use integrations::*;
\ No newline at end of file
+pub use llm::*;
+pub use conversation::*;
+pub use vector_search::*;
+pub use shell::*;
+pub use file::*;
+pub use script::*;
+pub use integrations::*;
+pub use registry::*;
\ No newline at end of file
@@ -0,0 +1,829 @@
+use crate::{GhostLLMNode, OllamaNode};
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, NodeRegistry, NodeRunner, Result, Services};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+const DEFAULT_MAX_TOOL_ITERATIONS: u64 = 5;
+
+/// A node the model may call, from one entry of the `tools` parameter:
+/// either a node type, or `{node_type, name, description, parameters}`
+/// where `parameters` are fixed by the flow and hidden from the model.
+struct Tool {
+    name: String,
+    description: Option<String>,
+    node: Arc<dyn Node>,
+    fixed: serde_json::Map<String, Value>,
+}
+
+impl Tool {
+    /// The node as a function in the OpenAI/Ollama `tools` format, with a
+    /// JSON schema built from the node's parameters.
+    fn function_definition(&self) -> Value {
+        let definition = self.node.definition();
+        let mut properties = serde_json::Map::new();
+        let mut required = Vec::new();
+        for param in definition.parameters.iter().filter(|p| !self.fixed.contains_key(&p.name)) {
+            let json_type = match param.param_type {
+                ParameterType::Number => "number",
+                ParameterType::Boolean => "boolean",
+                ParameterType::Object => "object",
+                ParameterType::Array | ParameterType::MultiSelect => "array",
+                _ => "string",
+            };
+            let mut property = json!({ "type": json_type });
+            if let Some(description) = &param.description {
+                property["description"] = Value::String(description.clone());
+            }
+            if let Some(options) = &param.options {
+                property["enum"] = options.iter().map(|o| o.value.clone()).collect();
+            }
+            properties.insert(param.name.clone(), property);
+            if param.required {
+                required.push(param.name.clone());
+            }
+        }
+
+        json!({
+            "type": "function",
+            "function": {
+                "name": self.name,
+                "description": self.description.clone().unwrap_or(definition.description),
+                "parameters": {
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                },
+            },
+        })
+    }
+}
+
+/// Provider-agnostic LLM node. Accepts one set of generation parameters,
+/// forwards them to the selected backend and returns the same
+/// `{text, tokens_used, model, provider}` shape regardless of which one ran,
+/// so templates can switch providers without rewiring downstream nodes.
+///
+/// With `tools`, the model may call other nodes: each call is executed and
+/// its output sent back to the model until it answers without calling one.
+/// Tools run through the engine, with the same parameter checks and
+/// concurrency limits as the nodes of a flow.
+pub struct LlmNode {
+    ollama: Arc<dyn Node>,
+    ghostllm: Arc<dyn Node>,
+    tool_registry: Option<Arc<dyn NodeRegistry>>,
+    services: Services,
+}
+
+impl LlmNode {
+    pub fn new() -> Self {
+        Self::with_backends(Arc::new(OllamaNode::new()), Arc::new(GhostLLMNode::new()))
+    }
+
+    pub fn with_backends(ollama: Arc<dyn Node>, ghostllm: Arc<dyn Node>) -> Self {
+        Self {
+            ollama,
+            ghostllm,
+            tool_registry: None,
+            services: Services::default(),
+        }
+    }
+
+    /// Reach the engine, which runs the tools, through `services`
+    pub fn with_services(mut self, services: Services) -> Self {
+        self.services = services;
+        self
+    }
+
+    /// Look tools up in `registry` instead of the engine's registry, e.g. to
+    /// offer only some node types.
+    pub fn with_tool_registry(mut self, registry: Arc<dyn NodeRegistry>) -> Self {
+        self.tool_registry = Some(registry);
+        self
+    }
+
+    fn provider(context: &ExecutionContext) -> Result<&str> {
+        match context.input.get("provider").and_then(|v| v.as_str()).unwrap_or("ollama") {
+            provider @ ("ollama" | "ghostllm") => Ok(provider),
+            other => Err(GhostFlowError::ValidationError {
+                message: format!("Unsupported LLM provider: {}", other),
+            }),
+        }
+    }
+
+    /// Translate the normalized parameters into the selected backend's own
+    /// parameter names.
+    fn backend_input(provider: &str, params: &Value) -> Value {
+        let mut input = serde_json::Map::new();
+        let prompt = params.get("prompt").and_then(|v| v.as_str()).unwrap_or_default();
+        let system = params.get("system").and_then(|v| v.as_str());
+
+        for key in ["temperature", "max_tokens", "response_format", "json_retries"] {
+            if let Some(value) = params.get(key) {
+                input.insert(key.to_string(), value.clone());
+            }
+        }
+
+        match provider {
+            "ghostllm" => {
+                // GhostLLM has no separate system prompt, so prepend it
+                let prompt = match system {
+                    Some(system) => format!("{}\n\n{}", system, prompt),
+                    None => prompt.to_string(),
+                };
+                input.insert("prompt".to_string(), Value::String(prompt));
+                if let Some(model) = params.get("model") {
+                    input.insert("model_path".to_string(), model.clone());
+                }
+            }
+            _ => {
+                input.insert("prompt".to_string(), Value::String(prompt.to_string()));
+                if let Some(system) = system {
+                    input.insert("system".to_string(), Value::String(system.to_string()));
+                }
+                let model = params
+                    .get("model")
+                    .cloned()
+                    .unwrap_or_else(|| Value::String("llama2".to_string()));
+                input.insert("model".to_string(), model);
+            }
+        }
+
+        Value::Object(input)
+    }
+
+    /// Map a backend's native output onto the shared response shape.
+    fn normalize_output(provider: &str, output: &Value) -> Value {
+        let (text, tokens_used, model) = match provider {
+            "ghostllm" => (
+                output.get("text").cloned(),
+                output.get("tokens_used").and_then(|v| v.as_u64()),
+                output.pointer("/metadata/model_path").cloned(),
+            ),
+            _ => {
+                let eval = output.pointer("/metadata/eval_count").and_then(|v| v.as_u64());
+                let prompt_eval = output
+                    .pointer("/metadata/prompt_eval_count")
+                    .and_then(|v| v.as_u64());
+                let tokens_used = match (eval, prompt_eval) {
+                    (None, None) => None,
+                    (eval, prompt_eval) => Some(eval.unwrap_or(0) + prompt_eval.unwrap_or(0)),
+                };
+                (output.get("response").cloned(), tokens_used, output.get("model").cloned())
+            }
+        };
+
+        let mut normalized = serde_json::json!({
+            "text": text.unwrap_or_else(|| Value::String(String::new())),
+            "tokens_used": tokens_used,
+            "model": model.unwrap_or(Value::Null),
+            "provider": provider,
+        });
+        if let Some(json) = output.get("json") {
+            normalized["json"] = json.clone();
+        }
+        normalized
+    }
+
+    fn runner(&self) -> Result<Arc<dyn NodeRunner>> {
+        self.services.nodes.get().ok_or_else(|| GhostFlowError::ConfigurationError {
+            message: "LLM tools only run inside an engine".to_string(),
+        })
+    }
+
+    fn tools(&self, params: &Value) -> Result<Vec<Tool>> {
+        let invalid = |message: String| GhostFlowError::ValidationError { message };
+        let Some(entries) = params.get("tools").filter(|v| !v.is_null()) else {
+            return Ok(Vec::new());
+        };
+        let runner = self.runner()?;
+        let entries = entries
+            .as_array()
+            .ok_or_else(|| invalid("tools must be a list of node types or tool objects".to_string()))?;
+
+        entries
+            .iter()
+            .map(|entry| {
+                let (node_type, spec) = match entry {
+                    Value::String(node_type) => (node_type.as_str(), None),
+                    Value::Object(spec) => match spec.get("node_type").and_then(|v| v.as_str()) {
+                        Some(node_type) => (node_type, Some(spec)),
+                        None => return Err(invalid("Every tool object needs a node_type".to_string())),
+                    },
+                    _ => return Err(invalid("tools entries must be node types or objects".to_string())),
+                };
+                let node = match &self.tool_registry {
+                    Some(registry) => registry.get_node(node_type),
+                    None => runner.get_node(node_type),
+                }
+                .ok_or_else(|| invalid(format!("Unknown node type for tool: {}", node_type)))?;
+
+                let field = |key: &str| spec.and_then(|s| s.get(key));
+                Ok(Tool {
+                    name: field("name").and_then(|v| v.as_str()).unwrap_or(node_type).to_string(),
+                    description: field("description").and_then(|v| v.as_str()).map(str::to_string),
+                    node,
+                    fixed: field("parameters").and_then(|v| v.as_object()).cloned().unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    /// Run a tool call from the model. Failures are returned as an `error`
+    /// object for the model to see rather than failing the node.
+    async fn call_tool(
+        runner: &dyn NodeRunner,
+        tools: &[Tool],
+        call: &Value,
+        context: &ExecutionContext,
+    ) -> (String, Value, Value) {
+        let name = call.pointer("/function/name").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        // Some models send the arguments as a JSON string
+        let arguments = match call.pointer("/function/arguments") {
+            Some(Value::String(raw)) => serde_json::from_str(raw).unwrap_or_else(|_| json!({})),
+            Some(arguments @ Value::Object(_)) => arguments.clone(),
+            _ => json!({}),
+        };
+
+        let Some(tool) = tools.iter().find(|t| t.name == name) else {
+            warn!("Model called unknown tool '{}'", name);
+            return (name.clone(), arguments, json!({ "error": format!("Unknown tool: {}", name) }));
+        };
+
+        let mut input = arguments.clone();
+        for (key, value) in &tool.fixed {
+            input[key] = value.clone();
+        }
+        let tool_context = ExecutionContext {
+            input,
+            ..context.clone()
+        };
+        info!("Model called tool '{}'", name);
+
+        let result = runner.run_node(tool.node.as_ref(), tool_context).await;
+        let result = result.unwrap_or_else(|e| json!({ "error": e.to_string() }));
+        (name, arguments, result)
+    }
+
+    /// Chat with the model, running the tools it calls and sending back their
+    /// output, for at most `max_tool_iterations` model calls.
+    async fn execute_with_tools(&self, context: ExecutionContext, tools: Vec<Tool>) -> Result<Value> {
+        let max_iterations = context
+            .input
+            .get("max_tool_iterations")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_MAX_TOOL_ITERATIONS);
+        let definitions: Vec<Value> = tools.iter().map(Tool::function_definition).collect();
+        let runner = self.runner()?;
+
+        let mut request = Self::backend_input("ollama", &context.input);
+        let prompt = request.as_object_mut().and_then(|input| input.remove("prompt"));
+        request["operation"] = Value::String("chat".to_string());
+        request["tools"] = Value::Array(definitions);
+
+        let mut messages = vec![json!({ "role": "user", "content": prompt })];
+        let mut calls_made = Vec::new();
+        let mut tokens_used: Option<u64> = None;
+
+        for _ in 0..max_iterations {
+            request["messages"] = Value::Array(messages.clone());
+            let backend_context = ExecutionContext {
+                input: request.clone(),
+                ..context.clone()
+            };
+            let output = self.ollama.execute(backend_context).await?;
+            let mut response = Self::normalize_output("ollama", &output);
+            if let Some(tokens) = response["tokens_used"].as_u64() {
+                tokens_used = Some(tokens_used.unwrap_or(0) + tokens);
+            }
+
+            let tool_calls = output.get("tool_calls").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            if tool_calls.is_empty() {
+                response["tokens_used"] = json!(tokens_used);
+                response["tool_calls"] = Value::Array(calls_made);
+                return Ok(response);
+            }
+
+            messages.push(json!({
+                "role": "assistant",
+                "content": response["text"],
+                "tool_calls": tool_calls,
+            }));
+            for call in &tool_calls {
+                let (name, arguments, result) = Self::call_tool(runner.as_ref(), &tools, call, &context).await;
+                messages.push(json!({
+                    "role": "tool",
+                    "tool_name": name,
+                    "content": result.to_string(),
+                }));
+                calls_made.push(json!({ "name": name, "arguments": arguments, "result": result }));
+            }
+        }
+
+        Err(GhostFlowError::NodeExecutionError {
+            node_id: context.node_id,
+            message: format!("Model was still calling tools after {} iterations", max_iterations),
+        })
+    }
+
+    fn backend(&self, provider: &str) -> &Arc<dyn Node> {
+        match provider {
+            "ghostllm" => &self.ghostllm,
+            _ => &self.ollama,
+        }
+    }
+}
+
+impl Default for LlmNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for LlmNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "llm_generate".to_string(),
+            name: "LLM Generate".to_string(),
+            description: "Generate text with any supported LLM provider".to_string(),
+            category: NodeCategory::Ai,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "prompt".to_string(),
+                display_name: "Prompt".to_string(),
+                description: Some("Input prompt for the model".to_string()),
+                data_type: DataType::String,
+                required: true,
+            }],
+            outputs: vec![NodePort {
+                name: "response".to_string(),
+                display_name: "Response".to_string(),
+                description: Some("Normalized {text, tokens_used, model, provider} response".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            parameters: vec![
+                NodeParameter {
+                    name: "provider".to_string(),
+                    display_name: "Provider".to_string(),
+                    description: Some("Backend that runs the model".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("ollama".to_string())),
+                    required: true,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "ollama", "label": "Ollama"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "ghostllm", "label": "GhostLLM"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "model".to_string(),
+                    display_name: "Model".to_string(),
+                    description: Some("Ollama model name or GhostLLM model path".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "system".to_string(),
+                    display_name: "System Prompt".to_string(),
+                    description: Some("System prompt to set model behavior".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "temperature".to_string(),
+                    display_name: "Temperature".to_string(),
+                    description: Some("Sampling temperature (0.0 to 2.0)".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from_f64(0.7).unwrap())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_tokens".to_string(),
+                    display_name: "Max Tokens".to_string(),
+                    description: Some("Maximum tokens to generate".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(512))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "tools".to_string(),
+                    display_name: "Tools".to_string(),
+                    description: Some("Nodes the model may call: node types, or {node_type, name, description, parameters} with fixed parameters (Ollama only)".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_tool_iterations".to_string(),
+                    display_name: "Max Tool Iterations".to_string(),
+                    description: Some("Most model calls to make while it keeps calling tools".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(DEFAULT_MAX_TOOL_ITERATIONS))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("cpu".to_string()),
+            color: Some("#8b5cf6".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        let provider = Self::provider(context)?;
+
+        if context.input.get("prompt").and_then(|v| v.as_str()).map(|s| s.is_empty()).unwrap_or(true) {
+            return Err(GhostFlowError::ValidationError {
+                message: "Prompt parameter is required and cannot be empty".to_string(),
+            });
+        }
+
+        if !self.tools(&context.input)?.is_empty() {
+            if provider != "ollama" {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Tool calling is not supported by the {} provider", provider),
+                });
+            }
+            if context.input.get("max_tool_iterations").is_some_and(|v| v.as_u64().unwrap_or(0) == 0) {
+                return Err(GhostFlowError::ValidationError {
+                    message: "max_tool_iterations must be a positive number".to_string(),
+                });
+            }
+        }
+
+        let mut backend_context = context.clone();
+        backend_context.input = Self::backend_input(provider, &context.input);
+        self.backend(provider).validate(&backend_context).await
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<serde_json::Value> {
+        let provider = Self::provider(&context)?.to_string();
+        info!("Routing LLM request to provider: {}", provider);
+        let tools = self.tools(&context.input)?;
+        if provider == "ollama" && !tools.is_empty() {
+            return self.execute_with_tools(context, tools).await;
+        }
+
+        let mut backend_context = context.clone();
+        backend_context.input = Self::backend_input(&provider, &context.input);
+
+        let output = self.backend(&provider).execute(backend_context).await?;
+        Ok(Self::normalize_output(&provider, &output))
+    }
+
+    fn supports_retry(&self) -> bool {
+        true
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use crate::test_support::TestEngine;
+
+    /// Backend stand-in that records its input and returns a canned response
+    struct StubBackend {
+        response: Value,
+        seen: std::sync::Mutex<Option<Value>>,
+    }
+
+    impl StubBackend {
+        fn new(response: Value) -> Arc<Self> {
+            Arc::new(Self {
+                response,
+                seen: std::sync::Mutex::new(None),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Node for StubBackend {
+        fn definition(&self) -> NodeDefinition {
+            LlmNode::new().definition()
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+            *self.seen.lock().unwrap() = Some(context.input);
+            Ok(self.response.clone())
+        }
+    }
+
+    fn stubs() -> (Arc<StubBackend>, Arc<StubBackend>) {
+        let ollama = StubBackend::new(serde_json::json!({
+            "model": "llama2",
+            "response": "from ollama",
+            "prompt": "hi",
+            "metadata": {"done": true, "eval_count": 7, "prompt_eval_count": 3}
+        }));
+        let ghostllm = StubBackend::new(serde_json::json!({
+            "text": "from ghostllm",
+            "tokens_used": 10,
+            "prompt": "hi",
+            "metadata": {"model_path": "/models/llama2.gguf", "engine": "GhostLLM"}
+        }));
+        (ollama, ghostllm)
+    }
+
+    fn keys(value: &Value) -> Vec<String> {
+        let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[tokio::test]
+    async fn test_providers_share_output_schema() {
+        let (ollama, ghostllm) = stubs();
+        let node = LlmNode::with_backends(ollama, ghostllm);
+
+        let from_ollama = node
+            .execute(context(serde_json::json!({"provider": "ollama", "prompt": "hi"})))
+            .await
+            .unwrap();
+        let from_ghostllm = node
+            .execute(context(serde_json::json!({"provider": "ghostllm", "prompt": "hi"})))
+            .await
+            .unwrap();
+
+        assert_eq!(keys(&from_ollama), keys(&from_ghostllm));
+        assert_eq!(keys(&from_ollama), vec!["model", "provider", "text", "tokens_used"]);
+
+        assert_eq!(from_ollama["text"], "from ollama");
+        assert_eq!(from_ollama["tokens_used"], 10);
+        assert_eq!(from_ollama["model"], "llama2");
+        assert_eq!(from_ollama["provider"], "ollama");
+
+        assert_eq!(from_ghostllm["text"], "from ghostllm");
+        assert_eq!(from_ghostllm["tokens_used"], 10);
+        assert_eq!(from_ghostllm["model"], "/models/llama2.gguf");
+        assert_eq!(from_ghostllm["provider"], "ghostllm");
+    }
+
+    #[tokio::test]
+    async fn test_params_are_translated_per_provider() {
+        let (ollama, ghostllm) = stubs();
+        let node = LlmNode::with_backends(ollama.clone(), ghostllm.clone());
+        let input = serde_json::json!({
+            "model": "llama2",
+            "system": "Be brief.",
+            "prompt": "hi",
+            "temperature": 0.2,
+        });
+
+        let mut ollama_input = input.clone();
+        ollama_input["provider"] = Value::String("ollama".to_string());
+        node.execute(context(ollama_input)).await.unwrap();
+        let seen = ollama.seen.lock().unwrap().clone().unwrap();
+        assert_eq!(seen["model"], "llama2");
+        assert_eq!(seen["system"], "Be brief.");
+        assert_eq!(seen["temperature"], 0.2);
+
+        let mut ghostllm_input = input;
+        ghostllm_input["provider"] = Value::String("ghostllm".to_string());
+        node.execute(context(ghostllm_input)).await.unwrap();
+        let seen = ghostllm.seen.lock().unwrap().clone().unwrap();
+        assert_eq!(seen["model_path"], "llama2");
+        assert_eq!(seen["prompt"], "Be brief.\n\nhi");
+        assert!(seen.get("system").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_provider_is_rejected() {
+        let node = LlmNode::new();
+        let err = node
+            .validate(&context(serde_json::json!({"provider": "openai", "prompt": "hi"})))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GhostFlowError::ValidationError { .. }));
+    }
+
+    /// Backend stand-in that replies with `responses` in order and records
+    /// every request
+    struct ScriptedBackend {
+        responses: std::sync::Mutex<Vec<Value>>,
+        requests: std::sync::Mutex<Vec<Value>>,
+    }
+
+    impl ScriptedBackend {
+        fn new(mut responses: Vec<Value>) -> Arc<Self> {
+            responses.reverse();
+            Arc::new(Self {
+                responses: std::sync::Mutex::new(responses),
+                requests: std::sync::Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Node for ScriptedBackend {
+        fn definition(&self) -> NodeDefinition {
+            LlmNode::new().definition()
+        }
+
+        async fn validate(&self, _context: &ExecutionContext) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+            self.requests.lock().unwrap().push(context.input);
+            let mut responses = self.responses.lock().unwrap();
+            Ok(match responses.len() {
+                1 => responses[0].clone(),
+                _ => responses.pop().unwrap(),
+            })
+        }
+    }
+
+    /// Tool node that reports the weather for `city` in the given `units`
+    struct WeatherNode;
+
+    #[async_trait]
+    impl Node for WeatherNode {
+        fn definition(&self) -> NodeDefinition {
+            NodeDefinition {
+                id: "weather".to_string(),
+                name: "Weather".to_string(),
+                description: "Current weather for a city".to_string(),
+                category: NodeCategory::Integration,
+                version: "1.0.0".to_string(),
+                inputs: vec![],
+                outputs: vec![],
+                parameters: vec![
+                    NodeParameter {
+                        name: "city".to_string(),
+                        display_name: "City".to_string(),
+                        description: Some("City name".to_string()),
+                        param_type: ParameterType::String,
+                        default_value: None,
+                        required: true,
+                        options: None,
+                        validation: None,
+                    },
+                    NodeParameter {
+                        name: "units".to_string(),
+                        display_name: "Units".to_string(),
+                        description: None,
+                        param_type: ParameterType::String,
+                        default_value: None,
+                        required: false,
+                        options: None,
+                        validation: None,
+                    },
+                ],
+                icon: None,
+                color: None,
+            }
+        }
+
+        async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+            match context.input.get("city").and_then(|v| v.as_str()) {
+                Some(_) => Ok(()),
+                None => Err(GhostFlowError::ValidationError {
+                    message: "city is required".to_string(),
+                }),
+            }
+        }
+
+        async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+            Ok(serde_json::json!({
+                "city": context.input["city"],
+                "temperature": 21,
+                "units": context.input["units"],
+            }))
+        }
+    }
+
+    fn tool_engine() -> Arc<TestEngine> {
+        let mut registry = ghostflow_core::BasicNodeRegistry::new();
+        registry.register_node("weather".to_string(), Arc::new(WeatherNode)).unwrap();
+        TestEngine::new(Arc::new(registry))
+    }
+
+    fn tool_call(city: &str) -> Value {
+        serde_json::json!({
+            "model": "llama3.1",
+            "response": "",
+            "tool_calls": [{"function": {"name": "get_weather", "arguments": {"city": city}}}],
+            "metadata": {"eval_count": 12, "prompt_eval_count": 40}
+        })
+    }
+
+    fn weather_input() -> Value {
+        serde_json::json!({
+            "provider": "ollama",
+            "model": "llama3.1",
+            "prompt": "Should I bring a jacket in Oslo?",
+            "tools": [{"node_type": "weather", "name": "get_weather", "parameters": {"units": "celsius"}}],
+        })
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_result_is_sent_back_for_the_final_answer() {
+        let ollama = ScriptedBackend::new(vec![
+            tool_call("Oslo"),
+            serde_json::json!({
+                "model": "llama3.1",
+                "response": "It is 21°C in Oslo, no jacket needed.",
+                "metadata": {"eval_count": 10, "prompt_eval_count": 60}
+            }),
+        ]);
+        let engine = tool_engine();
+        let node = LlmNode::with_backends(ollama.clone(), StubBackend::new(Value::Null))
+            .with_services(engine.services());
+
+        let ctx = context(weather_input());
+        node.validate(&ctx).await.unwrap();
+        let output = node.execute(ctx).await.unwrap();
+
+        assert_eq!(output["text"], "It is 21°C in Oslo, no jacket needed.");
+        assert_eq!(output["tokens_used"], 122);
+        assert_eq!(output["tool_calls"][0]["name"], "get_weather");
+        assert_eq!(output["tool_calls"][0]["arguments"], serde_json::json!({"city": "Oslo"}));
+        assert_eq!(output["tool_calls"][0]["result"]["units"], "celsius");
+
+        let requests = ollama.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        let function = &requests[0]["tools"][0]["function"];
+        assert_eq!(requests[0]["operation"], "chat");
+        assert_eq!(function["name"], "get_weather");
+        assert_eq!(function["parameters"]["required"], serde_json::json!(["city"]));
+        assert!(function["parameters"]["properties"].get("units").is_none());
+
+        let messages = requests[1]["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0]["content"], "Should I bring a jacket in Oslo?");
+        assert_eq!(messages[1]["role"], "assistant");
+        assert_eq!(messages[2]["role"], "tool");
+        let result: Value = serde_json::from_str(messages[2]["content"].as_str().unwrap()).unwrap();
+        assert_eq!(result["temperature"], 21);
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_is_bounded() {
+        let ollama = ScriptedBackend::new(vec![tool_call("Oslo")]);
+        let engine = tool_engine();
+        let node = LlmNode::with_backends(ollama.clone(), StubBackend::new(Value::Null))
+            .with_services(engine.services());
+        let mut input = weather_input();
+        input["max_tool_iterations"] = serde_json::json!(3);
+
+        let err = node.execute(context(input)).await.unwrap_err();
+
+        assert!(err.to_string().contains("after 3 iterations"), "{}", err);
+        assert_eq!(ollama.requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_tools_require_a_provider_with_function_calling() {
+        let engine = tool_engine();
+        let node = LlmNode::new().with_services(engine.services());
+        let mut input = weather_input();
+        input["provider"] = Value::String("ghostllm".to_string());
+        assert!(node.validate(&context(input)).await.is_err());
+
+        let mut input = weather_input();
+        input["tools"] = serde_json::json!(["missing_node"]);
+        assert!(node.validate(&context(input)).await.is_err());
+
+        let restricted = node.with_tool_registry(Arc::new(ghostflow_core::BasicNodeRegistry::new()));
+        assert!(restricted.validate(&context(weather_input())).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tools_only_run_inside_an_engine() {
+        let ollama = ScriptedBackend::new(vec![tool_call("Oslo")]);
+        let node = LlmNode::with_backends(ollama.clone(), StubBackend::new(Value::Null));
+
+        let err = node.execute(context(weather_input())).await.unwrap_err();
+
+        assert!(matches!(err, GhostFlowError::ConfigurationError { .. }), "{}", err);
+        assert!(ollama.requests.lock().unwrap().is_empty());
+    }
+}
\ No newline at end of file
@@ -1,13 +1,15 @@
 use async_trait::async_trait;
-use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_core::{EventBus, ExecutionEvent, GhostFlowError, Node, Result};
 use ghostflow_schema::{
     DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
 };
 use ghostflow_schema::node::ParameterType;
+use crate::json_mode::{generate_json, json_mode_enabled, json_mode_parameters, json_output_port};
+use crate::prompt_template::{apply_prompt_templates, prompt_template_parameters, render_prompts};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct OllamaRequest {
@@ -19,7 +21,11 @@ struct OllamaRequest {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
     stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keep_alive: Option<Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,27 +34,522 @@ struct OllamaResponse {
     response: String,
     done: bool,
     context: Option<Vec<i32>>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+}
+
+/// Running totals collected while consuming an NDJSON completion stream
+#[derive(Debug, Default)]
+struct StreamAccumulator {
+    text: String,
+    chunks: usize,
+    done: bool,
+    model: Option<String>,
+    eval_count: Option<u64>,
+    prompt_eval_count: Option<u64>,
+    total_duration: Option<u64>,
+    /// Functions the model asked to call, from /api/chat with `tools`
+    tool_calls: Vec<Value>,
+}
+
+impl StreamAccumulator {
+    /// Fold one NDJSON line into the accumulator, returning the text it carried.
+    fn push_line(&mut self, line: &[u8]) -> std::result::Result<Option<String>, String> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        let chunk: Value = serde_json::from_str(line)
+            .map_err(|e| format!("invalid stream chunk: {}", e))?;
+
+        if let Some(err) = chunk.get("error").and_then(|v| v.as_str()) {
+            return Err(err.to_string());
+        }
+
+        if self.model.is_none() {
+            self.model = chunk.get("model").and_then(|v| v.as_str()).map(String::from);
+        }
+
+        // /api/generate streams `response`, /api/chat streams `message.content`
+        let text = chunk
+            .get("response")
+            .or_else(|| chunk.pointer("/message/content"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        self.chunks += 1;
+        self.text.push_str(&text);
+        if let Some(calls) = chunk.pointer("/message/tool_calls").and_then(|v| v.as_array()) {
+            self.tool_calls.extend(calls.iter().cloned());
+        }
+
+        if chunk.get("done").and_then(|v| v.as_bool()).unwrap_or(false) {
+            self.done = true;
+            self.eval_count = chunk.get("eval_count").and_then(|v| v.as_u64());
+            self.prompt_eval_count = chunk.get("prompt_eval_count").and_then(|v| v.as_u64());
+            self.total_duration = chunk.get("total_duration").and_then(|v| v.as_u64());
+        }
+
+        Ok(if text.is_empty() { None } else { Some(text) })
+    }
 }
 
 pub struct OllamaNode {
     client: Client,
     base_url: String,
+    warmup_model: Option<String>,
+    keep_alive: Option<Value>,
+    events: EventBus,
 }
 
 impl OllamaNode {
     pub fn new() -> Self {
-        Self {
-            client: Client::new(),
-            base_url: std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string()),
-        }
+        Self::with_base_url(
+            std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string()),
+        )
     }
 
     pub fn with_base_url(base_url: String) -> Self {
         Self {
             client: Client::new(),
             base_url,
+            warmup_model: None,
+            keep_alive: None,
+            events: EventBus::default(),
+        }
+    }
+
+    /// Publish streamed tokens and model pull progress on `events`
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Load `model` when the node is warmed up at startup
+    pub fn with_warmup_model(mut self, model: impl Into<String>) -> Self {
+        self.warmup_model = Some(model.into());
+        self
+    }
+
+    /// How long Ollama keeps a model loaded after a request, e.g. `"30m"`,
+    /// or `-1` to keep it loaded indefinitely. Sent with warmup and with
+    /// every request that does not set its own `keep_alive`.
+    pub fn with_keep_alive(mut self, keep_alive: impl Into<Value>) -> Self {
+        self.keep_alive = Some(keep_alive.into());
+        self
+    }
+
+    fn keep_alive(&self, params: &Value) -> Option<Value> {
+        params
+            .get("keep_alive")
+            .filter(|v| !v.is_null())
+            .or(self.keep_alive.as_ref())
+            .cloned()
+    }
+}
+
+/// Read an NDJSON response body, handing every complete line to `on_line`.
+/// A connection dropped mid-body surfaces as a `NetworkError` instead of a
+/// silently truncated result.
+async fn read_ndjson<F>(mut response: reqwest::Response, mut on_line: F) -> Result<()>
+where
+    F: FnMut(&[u8]) -> Result<()>,
+{
+    let mut buffer: Vec<u8> = Vec::new();
+
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) => {
+                buffer.extend_from_slice(&chunk);
+                while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=pos).collect();
+                    on_line(&line)?;
+                }
+            }
+            Ok(None) => break,
+            Err(e) => {
+                return Err(GhostFlowError::NetworkError(format!(
+                    "Ollama stream interrupted: {}",
+                    e
+                )))
+            }
         }
     }
+
+    // The final object may not be newline-terminated
+    if !buffer.iter().all(u8::is_ascii_whitespace) {
+        on_line(&buffer)?;
+    }
+
+    Ok(())
+}
+
+/// Ollama reports models with an explicit tag, so `llama2` matches `llama2:latest`.
+fn model_matches(available: &str, requested: &str) -> bool {
+    available == requested
+        || (!requested.contains(':') && available == format!("{}:latest", requested))
+}
+
+impl OllamaNode {
+    /// Make sure `model` is present on the Ollama server before generating.
+    /// Missing models are pulled when `auto_pull` is set, with download
+    /// progress published to the event bus; otherwise the error lists what
+    /// is available so the flow author can pick a valid model.
+    async fn ensure_model(&self, context: &ExecutionContext, model: &str, auto_pull: bool) -> Result<()> {
+        let tags: Value = self.client
+            .get(format!("{}/api/tags", self.base_url))
+            .send()
+            .await
+            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+
+        let available: Vec<String> = tags
+            .get("models")
+            .and_then(|v| v.as_array())
+            .map(|models| {
+                models
+                    .iter()
+                    .filter_map(|m| m.get("name").and_then(|v| v.as_str()))
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if available.iter().any(|name| model_matches(name, model)) {
+            return Ok(());
+        }
+
+        if !auto_pull {
+            return Err(GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: format!(
+                    "Model '{}' is not available on the Ollama server. Available models: {}. Enable auto_pull or run `ollama pull {}`",
+                    model,
+                    if available.is_empty() { "none".to_string() } else { available.join(", ") },
+                    model
+                ),
+            });
+        }
+
+        info!("Pulling missing Ollama model: {}", model);
+
+        let response = self.client
+            .post(format!("{}/api/pull", self.base_url))
+            .json(&serde_json::json!({"name": model, "stream": true}))
+            .send()
+            .await
+            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: format!("Failed to pull model '{}': {}", model, error_text),
+            });
+        }
+
+        let mut succeeded = false;
+        read_ndjson(response, |line| {
+            let line = String::from_utf8_lossy(line);
+            let line = line.trim();
+            if line.is_empty() {
+                return Ok(());
+            }
+            let status: Value = serde_json::from_str(line)?;
+
+            if let Some(err) = status.get("error").and_then(|v| v.as_str()) {
+                return Err(GhostFlowError::NodeExecutionError {
+                    node_id: context.node_id.clone(),
+                    message: format!("Failed to pull model '{}': {}", model, err),
+                });
+            }
+
+            let message = status
+                .get("status")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            succeeded |= message == "success";
+
+            self.events.publish(ExecutionEvent::NodeProgress {
+                execution_id: context.execution_id,
+                node_id: context.node_id.clone(),
+                message,
+                completed: status.get("completed").and_then(|v| v.as_u64()),
+                total: status.get("total").and_then(|v| v.as_u64()),
+            });
+            Ok(())
+        })
+        .await?;
+
+        if !succeeded {
+            return Err(GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: format!("Pull of model '{}' did not complete", model),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Single generate/chat round trip against an already-available model
+    async fn generate(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+
+        let operation = params
+            .get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("generate");
+        let stream = params
+            .get("stream")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let model = params
+            .get("model")
+            .and_then(|v| v.as_str())
+            .unwrap_or("llama2");
+        let format = params
+            .get("format")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        if operation == "chat" || stream {
+            return self.execute_streaming(&context, operation, stream).await;
+        }
+        
+        let prompt = params
+            .get("prompt")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: "Missing prompt parameter".to_string(),
+            })?;
+
+        let system = params
+            .get("system")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let temperature = params
+            .get("temperature")
+            .and_then(|v| v.as_f64())
+            .map(|t| t as f32);
+
+        let max_tokens = params
+            .get("max_tokens")
+            .and_then(|v| v.as_i64())
+            .map(|t| t as i32);
+
+        info!("Generating text with Ollama model: {}", model);
+
+        let request = OllamaRequest {
+            model: model.to_string(),
+            prompt: prompt.to_string(),
+            system,
+            temperature,
+            max_tokens,
+            format,
+            stream: false,
+            keep_alive: self.keep_alive(params),
+        };
+
+        let response = self.client
+            .post(format!("{}/api/generate", self.base_url))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Ollama request failed: {}", e);
+                GhostFlowError::NetworkError(e.to_string())
+            })?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(GhostFlowError::NodeExecutionError {
+                node_id: context.node_id,
+                message: format!("Ollama API error: {}", error_text),
+            });
+        }
+
+        let ollama_response: OllamaResponse = response.json().await
+            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+
+        Ok(serde_json::json!({
+            "model": ollama_response.model,
+            "response": ollama_response.response,
+            "prompt": prompt,
+            "metadata": {
+                "temperature": temperature,
+                "max_tokens": max_tokens,
+                "done": ollama_response.done,
+                "eval_count": ollama_response.eval_count,
+                "prompt_eval_count": ollama_response.prompt_eval_count,
+            }
+        }))
+    }
+
+    /// Run a chat or generate request and consume the NDJSON response line by
+    /// line. When `stream` is set each text chunk is published to the global
+    /// event bus as it arrives; either way the full text and token counts are
+    /// returned once the model reports `done`.
+    async fn execute_streaming(
+        &self,
+        context: &ExecutionContext,
+        operation: &str,
+        stream: bool,
+    ) -> Result<Value> {
+        let params = &context.input;
+        let model = params
+            .get("model")
+            .and_then(|v| v.as_str())
+            .unwrap_or("llama2");
+        let system = params.get("system").and_then(|v| v.as_str());
+        let prompt = params.get("prompt").and_then(|v| v.as_str());
+
+        let mut options = serde_json::Map::new();
+        if let Some(temperature) = params.get("temperature").and_then(|v| v.as_f64()) {
+            options.insert("temperature".to_string(), serde_json::json!(temperature));
+        }
+        if let Some(max_tokens) = params.get("max_tokens").and_then(|v| v.as_i64()) {
+            options.insert("num_predict".to_string(), serde_json::json!(max_tokens));
+        }
+
+        let (endpoint, body) = if operation == "chat" {
+            let mut messages = Vec::new();
+            if let Some(system) = system {
+                messages.push(serde_json::json!({"role": "system", "content": system}));
+            }
+            if let Some(history) = params.get("messages").and_then(|v| v.as_array()) {
+                messages.extend(history.iter().cloned());
+            }
+            // A prompt alongside the history is the next user turn
+            if let Some(prompt) = prompt {
+                messages.push(serde_json::json!({"role": "user", "content": prompt}));
+            }
+            if messages.iter().all(|m| m.get("role").and_then(|r| r.as_str()) == Some("system")) {
+                return Err(GhostFlowError::NodeExecutionError {
+                    node_id: context.node_id.clone(),
+                    message: "Chat operation requires messages or a prompt".to_string(),
+                });
+            }
+            let mut body = serde_json::json!({
+                "model": model,
+                "messages": messages,
+                "stream": stream,
+                "options": options,
+            });
+            if let Some(tools) = params.get("tools").filter(|v| v.is_array()) {
+                body["tools"] = tools.clone();
+            }
+            ("chat", body)
+        } else {
+            let prompt = prompt.ok_or_else(|| GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: "Missing prompt parameter".to_string(),
+            })?;
+            let mut body = serde_json::json!({
+                "model": model,
+                "prompt": prompt,
+                "stream": stream,
+                "options": options,
+            });
+            if let Some(system) = system {
+                body["system"] = Value::String(system.to_string());
+            }
+            ("generate", body)
+        };
+        let mut body = body;
+        if let Some(format) = params.get("format").and_then(|v| v.as_str()) {
+            body["format"] = Value::String(format.to_string());
+        }
+        if let Some(keep_alive) = self.keep_alive(params) {
+            body["keep_alive"] = keep_alive;
+        }
+
+        info!("Running Ollama {} with model: {} (stream: {})", endpoint, model, stream);
+
+        let response = self.client
+            .post(format!("{}/api/{}", self.base_url, endpoint))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Ollama request failed: {}", e);
+                GhostFlowError::NetworkError(e.to_string())
+            })?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: format!("Ollama API error: {}", error_text),
+            });
+        }
+
+        let mut acc = StreamAccumulator::default();
+        let read = read_ndjson(response, |line| {
+            let text = acc
+                .push_line(line)
+                .map_err(|message| GhostFlowError::NodeExecutionError {
+                    node_id: context.node_id.clone(),
+                    message,
+                })?;
+            if let (true, Some(text)) = (stream, text) {
+                self.events.publish(ExecutionEvent::Token {
+                    execution_id: context.execution_id,
+                    node_id: context.node_id.clone(),
+                    text,
+                });
+            }
+            Ok(())
+        })
+        .await;
+
+        if let Err(e) = read {
+            warn!(
+                "Ollama stream failed after {} chunks ({} chars): {}",
+                acc.chunks,
+                acc.text.len(),
+                e
+            );
+            return Err(e);
+        }
+
+        if !acc.done {
+            return Err(GhostFlowError::NetworkError(format!(
+                "Ollama stream ended before completion after {} chunks",
+                acc.chunks
+            )));
+        }
+
+        let mut output = serde_json::json!({
+            "model": acc.model.unwrap_or_else(|| model.to_string()),
+            "operation": operation,
+            "response": acc.text,
+            "prompt": prompt,
+            "metadata": {
+                "temperature": params.get("temperature"),
+                "max_tokens": params.get("max_tokens"),
+                "done": acc.done,
+                "stream": stream,
+                "chunks": acc.chunks,
+                "eval_count": acc.eval_count,
+                "prompt_eval_count": acc.prompt_eval_count,
+                "total_duration": acc.total_duration,
+            }
+        });
+        if !acc.tool_calls.is_empty() {
+            output["tool_calls"] = Value::Array(acc.tool_calls);
+        }
+        Ok(output)
+    }
 }
 
 impl Default for OllamaNode {
@@ -73,14 +574,30 @@ impl Node for OllamaNode {
                 data_type: DataType::String,
                 required: true,
             }],
-            outputs: vec![NodePort {
-                name: "response".to_string(),
-                display_name: "Response".to_string(),
-                description: Some("Model generated response".to_string()),
-                data_type: DataType::Object,
-                required: true,
-            }],
+            outputs: vec![
+                NodePort {
+                    name: "response".to_string(),
+                    display_name: "Response".to_string(),
+                    description: Some("Model generated response".to_string()),
+                    data_type: DataType::Object,
+                    required: true,
+                },
+                json_output_port(),
+            ],
             parameters: vec![
+                NodeParameter {
+                    name: "operation".to_string(),
+                    display_name: "Operation".to_string(),
+                    description: Some("Use the generate or chat endpoint".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("generate".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "generate", "label": "Generate"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "chat", "label": "Chat"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
                 NodeParameter {
                     name: "model".to_string(),
                     display_name: "Model".to_string(),
@@ -121,7 +638,61 @@ impl Node for OllamaNode {
                     options: None,
                     validation: None,
                 },
-            ],
+                NodeParameter {
+                    name: "messages".to_string(),
+                    display_name: "Messages".to_string(),
+                    description: Some("Chat history as [{role, content}] objects (chat operation)".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "tools".to_string(),
+                    display_name: "Tools".to_string(),
+                    description: Some("Function definitions the model may call (chat operation); calls are returned as tool_calls".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "stream".to_string(),
+                    display_name: "Stream".to_string(),
+                    description: Some("Stream tokens to the execution event channel as they are generated".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "keep_alive".to_string(),
+                    display_name: "Keep Alive".to_string(),
+                    description: Some("How long the model stays loaded after this request, e.g. 30m, or -1 for indefinitely".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "auto_pull".to_string(),
+                    display_name: "Auto Pull".to_string(),
+                    description: Some("Pull the model from the Ollama library if it is not installed".to_string()),
+                    param_type: ParameterType::Boolean,
+                    default_value: Some(Value::Bool(false)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ]
+            .into_iter()
+            .chain(prompt_template_parameters())
+            .chain(json_mode_parameters())
+            .collect(),
             icon: Some("cpu".to_string()),
             color: Some("#8b5cf6".to_string()), // Purple for AI
         }
@@ -135,6 +706,7 @@ impl Node for OllamaNode {
                 message: "Model parameter is required".to_string(),
             });
         }
+        let rendered = render_prompts(params)?;
 
         if let Some(temp) = params.get("temperature").and_then(|v| v.as_f64()) {
             if temp < 0.0 || temp > 2.0 {
@@ -144,93 +716,93 @@ impl Node for OllamaNode {
             }
         }
 
+        match params.get("operation").and_then(|v| v.as_str()).unwrap_or("generate") {
+            "generate" => {}
+            "chat" => {
+                if params.get("messages").and_then(|v| v.as_array()).is_none()
+                    && params.get("prompt").and_then(|v| v.as_str()).is_none()
+                    && rendered.prompt.is_none()
+                {
+                    return Err(GhostFlowError::ValidationError {
+                        message: "Chat operation requires messages or a prompt".to_string(),
+                    });
+                }
+            }
+            other => {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Unsupported operation: {}", other),
+                });
+            }
+        }
+
         Ok(())
     }
 
-    async fn execute(&self, context: ExecutionContext) -> Result<serde_json::Value> {
-        let params = &context.input;
-        
-        let prompt = params
-            .get("prompt")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| GhostFlowError::NodeExecutionError {
-                node_id: context.node_id.clone(),
-                message: "Missing prompt parameter".to_string(),
-            })?;
+    async fn execute(&self, mut context: ExecutionContext) -> Result<serde_json::Value> {
+        apply_prompt_templates(&mut context, Some("system"))?;
 
-        let model = params
+        let auto_pull = context
+            .input
+            .get("auto_pull")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let model = context
+            .input
             .get("model")
             .and_then(|v| v.as_str())
-            .unwrap_or("llama2");
+            .unwrap_or("llama2")
+            .to_string();
+        self.ensure_model(&context, &model, auto_pull).await?;
 
-        let system = params
-            .get("system")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
+        if json_mode_enabled(&context.input) {
+            let mut context = context;
+            context.input["format"] = Value::String("json".to_string());
+            return generate_json(context, "response", |ctx| self.generate(ctx)).await;
+        }
 
-        let temperature = params
-            .get("temperature")
-            .and_then(|v| v.as_f64())
-            .map(|t| t as f32);
+        self.generate(context).await
+    }
 
-        let max_tokens = params
-            .get("max_tokens")
-            .and_then(|v| v.as_i64())
-            .map(|t| t as i32);
+    fn supports_retry(&self) -> bool {
+        true
+    }
 
-        info!("Generating text with Ollama model: {}", model);
+    fn is_deterministic(&self) -> bool {
+        false // LLM outputs are non-deterministic
+    }
 
-        let request = OllamaRequest {
-            model: model.to_string(),
-            prompt: prompt.to_string(),
-            system,
-            temperature,
-            max_tokens,
-            stream: false,
+    /// A generate request without a prompt makes Ollama load the model
+    async fn warmup(&self) -> Result<()> {
+        let Some(model) = &self.warmup_model else {
+            return Ok(());
         };
 
+        let mut body = serde_json::json!({"model": model, "stream": false});
+        if let Some(keep_alive) = &self.keep_alive {
+            body["keep_alive"] = keep_alive.clone();
+        }
         let response = self.client
             .post(format!("{}/api/generate", self.base_url))
-            .json(&request)
+            .json(&body)
             .send()
             .await
-            .map_err(|e| {
-                error!("Ollama request failed: {}", e);
-                GhostFlowError::NetworkError(e.to_string())
-            })?;
-
+            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
             let error_text = response.text().await.unwrap_or_default();
             return Err(GhostFlowError::NodeExecutionError {
-                node_id: context.node_id,
-                message: format!("Ollama API error: {}", error_text),
+                node_id: "ollama_generate".to_string(),
+                message: format!("Failed to load model '{}': {}", model, error_text),
             });
         }
 
-        let ollama_response: OllamaResponse = response.json().await
-            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
-
-        Ok(serde_json::json!({
-            "model": ollama_response.model,
-            "response": ollama_response.response,
-            "prompt": prompt,
-            "metadata": {
-                "temperature": temperature,
-                "max_tokens": max_tokens,
-                "done": ollama_response.done,
-            }
-        }))
-    }
-
-    fn supports_retry(&self) -> bool {
-        true
-    }
-
-    fn is_deterministic(&self) -> bool {
-        false // LLM outputs are non-deterministic
+        info!("Loaded Ollama model {}", model);
+        Ok(())
     }
 }
 
+/// Turn one or many texts into embedding vectors. Each text becomes an item
+/// `{id, text, embedding, metadata}` that the vector search node can store
+/// as is.
 pub struct OllamaEmbeddingsNode {
     client: Client,
     base_url: String,
@@ -238,10 +810,80 @@ pub struct OllamaEmbeddingsNode {
 
 impl OllamaEmbeddingsNode {
     pub fn new() -> Self {
+        Self::with_base_url(
+            std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string()),
+        )
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
         Self {
             client: Client::new(),
-            base_url: std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string()),
+            base_url,
+        }
+    }
+
+    /// `text`, or the `texts` list of strings or `{id, text, metadata}`
+    /// objects, as `(id, text, metadata)`. Ids default to the text itself.
+    fn documents(params: &Value) -> Result<Vec<(String, String, Value)>> {
+        let invalid = |message: &str| GhostFlowError::ValidationError {
+            message: message.to_string(),
+        };
+
+        if let Some(text) = params.get("text").and_then(|v| v.as_str()) {
+            return Ok(vec![(text.to_string(), text.to_string(), Value::Null)]);
         }
+        let texts = params
+            .get("texts")
+            .and_then(|v| v.as_array())
+            .filter(|texts| !texts.is_empty())
+            .ok_or_else(|| invalid("Either text or a non-empty texts list is required"))?;
+
+        texts
+            .iter()
+            .map(|item| match item {
+                Value::String(text) => Ok((text.clone(), text.clone(), Value::Null)),
+                Value::Object(fields) => {
+                    let text = fields
+                        .get("text")
+                        .and_then(|v| v.as_str())
+                        .ok_or_else(|| invalid("Every texts entry needs a text field"))?;
+                    let id = match fields.get("id") {
+                        Some(Value::String(id)) => id.clone(),
+                        Some(Value::Number(id)) => id.to_string(),
+                        _ => text.to_string(),
+                    };
+                    Ok((id, text.to_string(), fields.get("metadata").cloned().unwrap_or(Value::Null)))
+                }
+                _ => Err(invalid("texts entries must be strings or objects")),
+            })
+            .collect()
+    }
+
+    async fn embed(&self, model: &str, text: &str) -> Result<Vec<f32>> {
+        let request = EmbeddingsRequest {
+            model: model.to_string(),
+            prompt: text.to_string(),
+        };
+
+        let response = self.client
+            .post(format!("{}/api/embeddings", self.base_url))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(GhostFlowError::NetworkError(format!(
+                "Ollama embeddings request failed with {}: {}",
+                status, body
+            )));
+        }
+
+        let embeddings: EmbeddingsResponse = response.json().await
+            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+        Ok(embeddings.embedding)
     }
 }
 
@@ -272,13 +914,22 @@ impl Node for OllamaEmbeddingsNode {
                 data_type: DataType::String,
                 required: true,
             }],
-            outputs: vec![NodePort {
-                name: "embeddings".to_string(),
-                display_name: "Embeddings".to_string(),
-                description: Some("Vector embeddings".to_string()),
-                data_type: DataType::Array,
-                required: true,
-            }],
+            outputs: vec![
+                NodePort {
+                    name: "embeddings".to_string(),
+                    display_name: "Embeddings".to_string(),
+                    description: Some("Vector embeddings of text".to_string()),
+                    data_type: DataType::Array,
+                    required: false,
+                },
+                NodePort {
+                    name: "items".to_string(),
+                    display_name: "Items".to_string(),
+                    description: Some("One {id, text, embedding, metadata} item per text".to_string()),
+                    data_type: DataType::Array,
+                    required: true,
+                },
+            ],
             parameters: vec![
                 NodeParameter {
                     name: "model".to_string(),
@@ -290,6 +941,16 @@ impl Node for OllamaEmbeddingsNode {
                     options: None,
                     validation: None,
                 },
+                NodeParameter {
+                    name: "texts".to_string(),
+                    display_name: "Texts".to_string(),
+                    description: Some("Texts to embed in one go, as strings or {id, text, metadata} objects".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
             ],
             icon: Some("layers".to_string()),
             color: Some("#8b5cf6".to_string()),
@@ -297,48 +958,38 @@ impl Node for OllamaEmbeddingsNode {
     }
 
     async fn validate(&self, context: &ExecutionContext) -> Result<()> {
-        if context.input.get("text").and_then(|v| v.as_str()).is_none() {
-            return Err(GhostFlowError::ValidationError {
-                message: "Text input is required".to_string(),
-            });
-        }
-        Ok(())
+        Self::documents(&context.input).map(|_| ())
     }
 
     async fn execute(&self, context: ExecutionContext) -> Result<serde_json::Value> {
-        let text = context.input
-            .get("text")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| GhostFlowError::NodeExecutionError {
-                node_id: context.node_id.clone(),
-                message: "Missing text input".to_string(),
-            })?;
-
-        let model = context.input
+        let params = &context.input;
+        let model = params
             .get("model")
             .and_then(|v| v.as_str())
             .unwrap_or("nomic-embed-text");
+        let documents = Self::documents(params)?;
+        info!("Embedding {} texts with {}", documents.len(), model);
 
-        let request = EmbeddingsRequest {
-            model: model.to_string(),
-            prompt: text.to_string(),
-        };
-
-        let response = self.client
-            .post(format!("{}/api/embeddings", self.base_url))
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
-
-        let embeddings: EmbeddingsResponse = response.json().await
-            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+        let mut items = Vec::with_capacity(documents.len());
+        for (id, text, metadata) in documents {
+            let embedding = self.embed(model, &text).await?;
+            items.push(serde_json::json!({
+                "id": id,
+                "text": text,
+                "embedding": embedding,
+                "metadata": metadata,
+            }));
+        }
 
-        Ok(serde_json::json!({
-            "embeddings": embeddings.embedding,
+        let mut output = serde_json::json!({
             "model": model,
-            "dimension": embeddings.embedding.len(),
-        }))
+            "dimension": items[0]["embedding"].as_array().map_or(0, Vec::len),
+            "items": items,
+        });
+        if params.get("text").and_then(|v| v.as_str()).is_some() {
+            output["embeddings"] = output["items"][0]["embedding"].clone();
+        }
+        Ok(output)
     }
 }
 
@@ -346,4 +997,375 @@ impl Default for OllamaEmbeddingsNode {
     fn default() -> Self {
         Self::new()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mount_tags(server: &MockServer, models: &[&str]) {
+        let models: Vec<Value> = models
+            .iter()
+            .map(|name| serde_json::json!({"name": name}))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"models": models})))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_streaming_chat_emits_tokens_and_accumulates() {
+        let server = MockServer::start().await;
+        let ndjson = [
+            r#"{"model":"llama2","message":{"role":"assistant","content":"Hel"},"done":false}"#,
+            r#"{"model":"llama2","message":{"role":"assistant","content":"lo, "},"done":false}"#,
+            r#"{"model":"llama2","message":{"role":"assistant","content":"world"},"done":false}"#,
+            r#"{"model":"llama2","message":{"role":"assistant","content":""},"done":true,"eval_count":3,"prompt_eval_count":12,"total_duration":4200}"#,
+        ]
+        .join("\n");
+
+        mount_tags(&server, &["llama2:latest"]).await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .and(body_partial_json(serde_json::json!({"stream": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(ndjson, "application/x-ndjson"))
+            .mount(&server)
+            .await;
+
+        let bus = EventBus::default();
+        let node = OllamaNode::with_base_url(server.uri()).with_event_bus(bus.clone());
+        let ctx = context(serde_json::json!({
+            "operation": "chat",
+            "model": "llama2",
+            "stream": true,
+            "messages": [{"role": "user", "content": "Say hello"}],
+        }));
+        let execution_id = ctx.execution_id;
+        let mut events = bus.subscribe();
+
+        node.validate(&ctx).await.unwrap();
+        let result = node.execute(ctx).await.unwrap();
+
+        assert_eq!(result["response"], "Hello, world");
+        assert_eq!(result["metadata"]["eval_count"], 3);
+        assert_eq!(result["metadata"]["prompt_eval_count"], 12);
+        assert_eq!(result["metadata"]["chunks"], 4);
+
+        let mut tokens = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let ExecutionEvent::Token { execution_id: id, text, .. } = event {
+                if id == execution_id {
+                    tokens.push(text);
+                }
+            }
+        }
+        assert_eq!(tokens, vec!["Hel", "lo, ", "world"]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_ending_without_done_is_an_error() {
+        let server = MockServer::start().await;
+        let ndjson = concat!(
+            r#"{"model":"llama2","response":"partial","done":false}"#,
+            "\n",
+        );
+
+        mount_tags(&server, &["llama2:latest"]).await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(ndjson, "application/x-ndjson"))
+            .mount(&server)
+            .await;
+
+        let node = OllamaNode::with_base_url(server.uri());
+        let err = node
+            .execute(context(serde_json::json!({
+                "model": "llama2",
+                "prompt": "hi",
+                "stream": true,
+            })))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, GhostFlowError::NetworkError(_)));
+    }
+
+    #[test]
+    fn test_stream_error_chunk_is_surfaced() {
+        let mut acc = StreamAccumulator::default();
+        let err = acc.push_line(br#"{"error":"model 'nope' not found"}"#).unwrap_err();
+        assert!(err.contains("not found"));
+    }
+
+    #[test]
+    fn test_chat_tool_calls_are_collected() {
+        let mut acc = StreamAccumulator::default();
+        acc.push_line(br#"{"message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"weather","arguments":{"city":"Oslo"}}}]},"done":true}"#)
+            .unwrap();
+        assert!(acc.done);
+        assert_eq!(acc.tool_calls.len(), 1);
+        assert_eq!(acc.tool_calls[0]["function"]["arguments"]["city"], "Oslo");
+    }
+
+    #[tokio::test]
+    async fn test_present_model_skips_pull() {
+        let server = MockServer::start().await;
+        mount_tags(&server, &["mistral:7b", "llama2:latest"]).await;
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama2",
+                "response": "hi there",
+                "done": true,
+            })))
+            .mount(&server)
+            .await;
+
+        let node = OllamaNode::with_base_url(server.uri());
+        let result = node
+            .execute(context(serde_json::json!({
+                "model": "llama2",
+                "prompt": "hi",
+                "auto_pull": true,
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["response"], "hi there");
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_lists_available_models() {
+        let server = MockServer::start().await;
+        mount_tags(&server, &["mistral:7b", "codellama:latest"]).await;
+
+        let node = OllamaNode::with_base_url(server.uri());
+        let err = node
+            .execute(context(serde_json::json!({
+                "model": "llama2",
+                "prompt": "hi",
+            })))
+            .await
+            .unwrap_err();
+
+        match err {
+            GhostFlowError::NodeExecutionError { message, .. } => {
+                assert!(message.contains("'llama2' is not available"));
+                assert!(message.contains("mistral:7b, codellama:latest"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auto_pull_streams_progress_then_generates() {
+        let server = MockServer::start().await;
+        mount_tags(&server, &[]).await;
+        let progress = [
+            r#"{"status":"pulling manifest"}"#,
+            r#"{"status":"downloading","digest":"sha256:abc","total":100,"completed":50}"#,
+            r#"{"status":"success"}"#,
+        ]
+        .join("\n");
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .and(body_partial_json(serde_json::json!({"name": "llama2"})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(progress, "application/x-ndjson"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama2",
+                "response": "pulled",
+                "done": true,
+            })))
+            .mount(&server)
+            .await;
+
+        let bus = EventBus::default();
+        let node = OllamaNode::with_base_url(server.uri()).with_event_bus(bus.clone());
+        let ctx = context(serde_json::json!({
+            "model": "llama2",
+            "prompt": "hi",
+            "auto_pull": true,
+        }));
+        let execution_id = ctx.execution_id;
+        let mut events = bus.subscribe();
+
+        let result = node.execute(ctx).await.unwrap();
+        assert_eq!(result["response"], "pulled");
+
+        let mut statuses = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let ExecutionEvent::NodeProgress { execution_id: id, message, .. } = event {
+                if id == execution_id {
+                    statuses.push(message);
+                }
+            }
+        }
+        assert_eq!(statuses, vec!["pulling manifest", "downloading", "success"]);
+    }
+
+    #[tokio::test]
+    async fn test_warmup_loads_model_and_keep_alive_is_sent() {
+        let server = MockServer::start().await;
+        mount_tags(&server, &["llama2:latest"]).await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .and(body_partial_json(serde_json::json!({"model": "llama2", "keep_alive": "30m"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama2",
+                "response": "",
+                "done": true,
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .and(body_partial_json(serde_json::json!({"keep_alive": -1})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama2",
+                "response": "pinned",
+                "done": true,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let node = OllamaNode::with_base_url(server.uri())
+            .with_warmup_model("llama2")
+            .with_keep_alive("30m");
+        node.warmup().await.unwrap();
+
+        node.execute(context(serde_json::json!({"model": "llama2", "prompt": "hi"})))
+            .await
+            .unwrap();
+        let pinned = node
+            .execute(context(serde_json::json!({"model": "llama2", "prompt": "hi", "keep_alive": -1})))
+            .await
+            .unwrap();
+        assert_eq!(pinned["response"], "pinned");
+    }
+
+    #[tokio::test]
+    async fn test_system_prompt_template_is_sent_separately() {
+        let server = MockServer::start().await;
+        mount_tags(&server, &["llama2:latest"]).await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .and(body_partial_json(serde_json::json!({
+                "system": "Answer as a Rust reviewer",
+                "prompt": "Review PR #42",
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama2",
+                "response": "LGTM",
+                "done": true,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let node = OllamaNode::with_base_url(server.uri());
+        let result = node
+            .execute(context(serde_json::json!({
+                "model": "llama2",
+                "language": "Rust",
+                "pr": {"number": 42},
+                "system_prompt": "Answer as a {{input.language}} reviewer",
+                "prompt_template": "Review PR #{{input.pr.number}}",
+            })))
+            .await
+            .unwrap();
+        assert_eq!(result["response"], "LGTM");
+    }
+
+    #[tokio::test]
+    async fn test_json_mode_parses_response() {
+        let server = MockServer::start().await;
+        mount_tags(&server, &["llama2:latest"]).await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .and(body_partial_json(serde_json::json!({"format": "json"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama2",
+                "response": "{\"severity\": \"high\", \"hosts\": 3}",
+                "done": true,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let node = OllamaNode::with_base_url(server.uri());
+        let result = node
+            .execute(context(serde_json::json!({
+                "model": "llama2",
+                "prompt": "Summarize the alert",
+                "response_format": "json",
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["json"], serde_json::json!({"severity": "high", "hosts": 3}));
+        assert_eq!(result["json_attempts"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_json_mode_reprompts_after_malformed_output() {
+        let server = MockServer::start().await;
+        mount_tags(&server, &["llama2:latest"]).await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama2",
+                "response": "Sure! Here is the JSON: {severity: high",
+                "done": true,
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama2",
+                "response": "{\"severity\": \"high\"}",
+                "done": true,
+            })))
+            .mount(&server)
+            .await;
+
+        let node = OllamaNode::with_base_url(server.uri());
+        let result = node
+            .execute(context(serde_json::json!({
+                "model": "llama2",
+                "prompt": "Summarize the alert",
+                "response_format": "json",
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["json"], serde_json::json!({"severity": "high"}));
+        assert_eq!(result["json_attempts"], 2);
+
+        let requests = server.received_requests().await.unwrap();
+        let retry: Value = serde_json::from_slice(&requests.last().unwrap().body).unwrap();
+        assert!(retry["prompt"]
+            .as_str()
+            .unwrap()
+            .contains("previous reply was not valid JSON"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,267 @@
+use ghostflow_core::{GhostFlowError, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Hard ceiling on `max_pages`, whatever the flow asks for
+const PAGE_LIMIT: u32 = 1000;
+
+/// How the HTTP node finds the next page.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PageStrategy {
+    /// Follow the `rel="next"` URL of the `Link` response header
+    LinkHeader,
+    /// Send the value found at `cursor_path` in the body as `cursor_param`.
+    /// A cursor that is itself a URL is requested directly.
+    Cursor { cursor_path: String, cursor_param: String },
+    /// `offset_param` advances by `page_size`, sent as `limit_param`
+    Offset {
+        offset_param: String,
+        limit_param: String,
+        page_size: u64,
+    },
+    /// `page_param` counts up from `start_page`
+    Page {
+        page_param: String,
+        start_page: u64,
+        size_param: Option<String>,
+        page_size: Option<u64>,
+    },
+}
+
+/// The HTTP node's `paginate` parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Pagination {
+    pub strategy: PageStrategy,
+    /// Dotted path to the array of records in each body; empty when the body
+    /// is the array
+    pub items_path: String,
+    pub max_pages: u32,
+    pub max_items: usize,
+}
+
+/// What to request after a page.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum NextPage {
+    /// The node's URL with these query parameters set
+    Query(Vec<(String, String)>),
+    /// An absolute URL from a `Link` header or cursor
+    Url(String),
+    Done,
+}
+
+impl Pagination {
+    /// `None` when `value` is absent, null or `false`. A bare string such as
+    /// `"link_header"` selects a strategy with its defaults.
+    pub fn from_params(value: Option<&Value>) -> Result<Option<Self>> {
+        let config = match value {
+            None | Some(Value::Null) | Some(Value::Bool(false)) => return Ok(None),
+            Some(Value::String(strategy)) => serde_json::json!({ "strategy": strategy }),
+            Some(config @ Value::Object(_)) => config.clone(),
+            Some(_) => return Err(invalid("paginate must be an object or a strategy name")),
+        };
+        let text = |name: &str, default: &str| {
+            config
+                .get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or(default)
+                .to_string()
+        };
+        let number = |name: &str| config.get(name).and_then(|v| v.as_u64());
+
+        let strategy = match config.get("strategy").and_then(|v| v.as_str()) {
+            Some("link_header") => PageStrategy::LinkHeader,
+            Some("cursor") => {
+                let cursor_path = text("cursor_path", "");
+                if cursor_path.is_empty() {
+                    return Err(invalid("cursor pagination needs cursor_path"));
+                }
+                PageStrategy::Cursor {
+                    cursor_path,
+                    cursor_param: text("cursor_param", "cursor"),
+                }
+            }
+            Some("offset") => PageStrategy::Offset {
+                offset_param: text("offset_param", "offset"),
+                limit_param: text("limit_param", "limit"),
+                page_size: number("page_size").unwrap_or(100).max(1),
+            },
+            Some("page") => PageStrategy::Page {
+                page_param: text("page_param", "page"),
+                start_page: number("start_page").unwrap_or(1),
+                size_param: config.get("size_param").and_then(|v| v.as_str()).map(String::from),
+                page_size: number("page_size").filter(|n| *n > 0),
+            },
+            Some(other) => return Err(invalid(&format!("unknown pagination strategy '{}'", other))),
+            None => return Err(invalid("paginate needs a strategy")),
+        };
+
+        Ok(Some(Self {
+            strategy,
+            items_path: text("items_path", ""),
+            max_pages: number("max_pages").unwrap_or(10).clamp(1, PAGE_LIMIT as u64) as u32,
+            max_items: number("max_items").unwrap_or(10_000) as usize,
+        }))
+    }
+
+    /// Query parameters for the first request.
+    pub fn first_page(&self) -> NextPage {
+        match &self.strategy {
+            PageStrategy::LinkHeader | PageStrategy::Cursor { .. } => NextPage::Query(Vec::new()),
+            PageStrategy::Offset { offset_param, limit_param, page_size } => NextPage::Query(vec![
+                (offset_param.clone(), "0".to_string()),
+                (limit_param.clone(), page_size.to_string()),
+            ]),
+            PageStrategy::Page { .. } => self.page_query(0),
+        }
+    }
+
+    /// The records on one page.
+    pub fn items(&self, body: &Value, page: u32) -> Result<Vec<Value>> {
+        match field(body, &self.items_path) {
+            Some(Value::Array(items)) => Ok(items.clone()),
+            None | Some(Value::Null) => Ok(Vec::new()),
+            Some(_) => Err(invalid(&format!(
+                "items_path '{}' is not an array on page {}",
+                self.items_path, page
+            ))),
+        }
+    }
+
+    /// Where to go after `pages` pages, the last of which (`current`) held
+    /// `count` records. An empty page always ends pagination.
+    pub fn next_page(
+        &self,
+        current: &NextPage,
+        pages: u32,
+        headers: &HashMap<String, String>,
+        body: &Value,
+        count: usize,
+    ) -> NextPage {
+        if count == 0 {
+            return NextPage::Done;
+        }
+
+        let next = match &self.strategy {
+            PageStrategy::LinkHeader => headers
+                .get("link")
+                .and_then(|link| next_link(link))
+                .map_or(NextPage::Done, NextPage::Url),
+            PageStrategy::Cursor { cursor_path, cursor_param } => match field(body, cursor_path) {
+                Some(Value::String(cursor)) if cursor.starts_with("http://") || cursor.starts_with("https://") => {
+                    NextPage::Url(cursor.clone())
+                }
+                Some(Value::String(cursor)) if !cursor.is_empty() => {
+                    NextPage::Query(vec![(cursor_param.clone(), cursor.clone())])
+                }
+                Some(Value::Number(cursor)) => NextPage::Query(vec![(cursor_param.clone(), cursor.to_string())]),
+                _ => NextPage::Done,
+            },
+            PageStrategy::Offset { offset_param, limit_param, page_size } => {
+                if (count as u64) < *page_size {
+                    NextPage::Done
+                } else {
+                    NextPage::Query(vec![
+                        (offset_param.clone(), (pages as u64 * page_size).to_string()),
+                        (limit_param.clone(), page_size.to_string()),
+                    ])
+                }
+            }
+            PageStrategy::Page { page_size, .. } => {
+                if page_size.is_some_and(|size| (count as u64) < size) {
+                    NextPage::Done
+                } else {
+                    self.page_query(pages)
+                }
+            }
+        };
+
+        // A server that keeps handing out the same page would loop forever
+        if next == *current {
+            NextPage::Done
+        } else {
+            next
+        }
+    }
+
+    fn page_query(&self, index: u32) -> NextPage {
+        let PageStrategy::Page { page_param, start_page, size_param, page_size } = &self.strategy else {
+            return NextPage::Done;
+        };
+        let mut query = vec![(page_param.clone(), (start_page + index as u64).to_string())];
+        if let (Some(size_param), Some(page_size)) = (size_param, page_size) {
+            query.push((size_param.clone(), page_size.to_string()));
+        }
+        NextPage::Query(query)
+    }
+}
+
+/// The `rel="next"` target of an RFC 8288 `Link` header.
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|link| {
+        let (target, params) = link.trim().split_once(';')?;
+        let target = target.trim().strip_prefix('<')?.strip_suffix('>')?;
+        let is_next = params.split(';').any(|param| {
+            let Some((name, value)) = param.split_once('=') else {
+                return false;
+            };
+            name.trim().eq_ignore_ascii_case("rel")
+                && value.trim().trim_matches('"').split_whitespace().any(|rel| rel.eq_ignore_ascii_case("next"))
+        });
+        is_next.then(|| target.to_string())
+    })
+}
+
+/// Value at a dotted path such as `meta.next`; the value itself for an
+/// empty path.
+fn field<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    path.split('.').try_fold(value, |current, segment| match current {
+        Value::Object(fields) => fields.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+fn invalid(message: &str) -> GhostFlowError {
+    GhostFlowError::ValidationError {
+        message: format!("Invalid pagination: {}", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_next_link_finds_rel_next() {
+        let header = r#"<https://api.example.com/alerts?page=1>; rel="prev", <https://api.example.com/alerts?page=3>; rel="next", <https://api.example.com/alerts?page=9>; rel="last""#;
+        assert_eq!(next_link(header).as_deref(), Some("https://api.example.com/alerts?page=3"));
+        assert_eq!(next_link(r#"<https://api.example.com/alerts?page=9>; rel="last""#), None);
+    }
+
+    #[test]
+    fn test_offset_stops_on_short_page() {
+        let pagination = Pagination::from_params(Some(&json!({ "strategy": "offset", "page_size": 2 })))
+            .unwrap()
+            .unwrap();
+        let headers = HashMap::new();
+        let first = pagination.first_page();
+
+        let second = pagination.next_page(&first, 1, &headers, &json!([1, 2]), 2);
+        assert_eq!(
+            second,
+            NextPage::Query(vec![("offset".into(), "2".into()), ("limit".into(), "2".into())])
+        );
+        assert_eq!(pagination.next_page(&second, 2, &headers, &json!([3]), 1), NextPage::Done);
+    }
+
+    #[test]
+    fn test_invalid_configuration_is_rejected() {
+        assert!(Pagination::from_params(Some(&json!({ "strategy": "cursor" }))).is_err());
+        assert!(Pagination::from_params(Some(&json!({ "strategy": "scroll" }))).is_err());
+        assert!(Pagination::from_params(Some(&json!(false))).unwrap().is_none());
+    }
+}
@@ -0,0 +1,123 @@
+use ghostflow_core::Result;
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{ExecutionContext, NodeParameter};
+use serde_json::Value;
+
+use crate::template::render_template;
+
+/// The `prompt_template` and `system_prompt` parameters rendered against the
+/// node input, which templates see as `input`, e.g. `{{input.ticket.title}}`.
+/// With `strict_variables`, a placeholder with no value is an error instead
+/// of being left as written.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct RenderedPrompts {
+    pub prompt: Option<String>,
+    pub system: Option<String>,
+}
+
+pub(crate) fn render_prompts(params: &Value) -> Result<RenderedPrompts> {
+    let strict = params.get("strict_variables").and_then(|v| v.as_bool()).unwrap_or(false);
+    let data = serde_json::json!({ "input": params });
+    let render = |key: &str| {
+        params
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|template| !template.is_empty())
+            .map(|template| render_template(template, &data, strict))
+            .transpose()
+    };
+
+    Ok(RenderedPrompts {
+        prompt: render("prompt_template")?,
+        system: render("system_prompt")?,
+    })
+}
+
+/// Replace `prompt` with the rendered template. The system prompt goes to
+/// `system_field` for models that take one separately, otherwise it is put
+/// in front of the prompt.
+pub(crate) fn apply_prompt_templates(context: &mut ExecutionContext, system_field: Option<&str>) -> Result<()> {
+    let rendered = render_prompts(&context.input)?;
+    if let Some(prompt) = rendered.prompt {
+        context.input["prompt"] = Value::String(prompt);
+    }
+
+    match (rendered.system, system_field) {
+        (Some(system), Some(field)) => context.input[field] = Value::String(system),
+        (Some(system), None) => {
+            let prompt = context.input.get("prompt").and_then(|v| v.as_str()).unwrap_or_default();
+            context.input["prompt"] = Value::String(format!("{}\n\n{}", system, prompt));
+        }
+        (None, _) => {}
+    }
+    Ok(())
+}
+
+pub(crate) fn prompt_template_parameters() -> Vec<NodeParameter> {
+    vec![
+        NodeParameter {
+            name: "prompt_template".to_string(),
+            display_name: "Prompt Template".to_string(),
+            description: Some("Prompt with {{input.field}} placeholders, filled from the node input. Replaces prompt".to_string()),
+            param_type: ParameterType::String,
+            default_value: None,
+            required: false,
+            options: None,
+            validation: None,
+        },
+        NodeParameter {
+            name: "system_prompt".to_string(),
+            display_name: "System Prompt Template".to_string(),
+            description: Some("Instructions for the model, with the same placeholders as the prompt template".to_string()),
+            param_type: ParameterType::String,
+            default_value: None,
+            required: false,
+            options: None,
+            validation: None,
+        },
+        NodeParameter {
+            name: "strict_variables".to_string(),
+            display_name: "Strict Variables".to_string(),
+            description: Some("Fail when a template placeholder has no value instead of leaving it as written".to_string()),
+            param_type: ParameterType::Boolean,
+            default_value: Some(Value::Bool(false)),
+            required: false,
+            options: None,
+            validation: None,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_prompt_renders_nested_input_fields() {
+        let params = json!({
+            "ticket": { "title": "VPN down", "reporter": { "name": "Sam" } },
+            "prompt_template": "Summarize '{{input.ticket.title}}' reported by {{input.ticket.reporter.name}}",
+            "system_prompt": "You triage {{upper input.ticket.title}} tickets.",
+            "strict_variables": true,
+        });
+
+        let rendered = render_prompts(&params).unwrap();
+        assert_eq!(rendered.prompt.as_deref(), Some("Summarize 'VPN down' reported by Sam"));
+        assert_eq!(rendered.system.as_deref(), Some("You triage VPN DOWN tickets."));
+    }
+
+    #[test]
+    fn test_missing_variable_fails_only_in_strict_mode() {
+        let mut params = json!({
+            "ticket": { "title": "VPN down" },
+            "prompt_template": "Assign to {{input.ticket.assignee}}",
+        });
+        let lenient = render_prompts(&params).unwrap();
+        assert_eq!(lenient.prompt.as_deref(), Some("Assign to {{input.ticket.assignee}}"));
+
+        params["strict_variables"] = json!(true);
+        let message = render_prompts(&params).unwrap_err().to_string();
+        assert!(message.contains("input.ticket.assignee"), "{}", message);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::*;
+use ghostflow_core::{GhostFlowError, Node, NodeRegistry, Result, Services};
+use std::sync::Arc;
+
+/// Every node type shipped with this crate, constructed with its default
+/// configuration and sharing `services` with the engine.
+pub fn builtin_nodes(services: &Services) -> Vec<Arc<dyn Node>> {
+    let events = &services.events;
+    let breakers = &services.circuit_breakers;
+    let llm: Arc<dyn Node> = Arc::new(
+        LlmNode::with_backends(
+            Arc::new(OllamaNode::new().with_event_bus(events.clone())),
+            Arc::new(GhostLLMNode::new().with_event_bus(events.clone())),
+        )
+        .with_services(services.clone()),
+    );
+    vec![
+        // Core
+        Arc::new(HttpRequestNode::new()),
+        Arc::new(IfNode),
+        Arc::new(DelayNode::new().with_cancellations(services.cancellations.clone())),
+        Arc::new(CollectNode),
+        Arc::new(
+            WaitForApprovalNode::new()
+                .with_event_bus(events.clone())
+                .with_cancellations(services.cancellations.clone())
+                .with_approvals(services.approvals.clone()),
+        ),
+        Arc::new(TryCatchNode::new().with_services(services.clone())),
+        Arc::new(EscalationNode::new().with_escalations(services.escalations.clone())),
+        Arc::new(TemplateNode),
+        Arc::new(TransformNode),
+        Arc::new(ScriptNode::new()),
+        Arc::new(FilterNode),
+        Arc::new(AggregateNode),
+        Arc::new(DiffNode::new()),
+        Arc::new(CsvParseNode),
+        Arc::new(CsvWriteNode),
+        Arc::new(SetVariableNode::new().with_variables(services.variables.clone())),
+        Arc::new(GetVariableNode::new().with_variables(services.variables.clone())),
+        Arc::new(WebhookTriggerNode),
+        Arc::new(RespondToWebhookNode::new().with_webhook_responses(services.webhook_responses.clone())),
+        Arc::new(OutboundWebhookNode::new().with_circuit_breakers(breakers.clone())),
+        Arc::new(ShellNode::new()),
+        Arc::new(ReadFileNode::new()),
+        Arc::new(WriteFileNode::new()),
+        // AI
+        Arc::new(OllamaNode::new().with_event_bus(events.clone())),
+        Arc::new(OllamaEmbeddingsNode::new()),
+        Arc::new(GhostLLMNode::new().with_event_bus(events.clone())),
+        llm.clone(),
+        Arc::new(ConversationNode::with_llm(llm).with_conversations(services.conversations.clone())),
+        Arc::new(VectorSearchNode::new().with_vector_indexes(services.vector_indexes.clone())),
+        // Integrations
+        Arc::new(CloudflareDNSNode),
+        Arc::new(CloudflareWAFNode),
+        Arc::new(MicrosoftGraphEmailNode),
+        Arc::new(MicrosoftTeamsNode),
+        Arc::new(MicrosoftCalendarNode),
+        Arc::new(GitLabProjectNode),
+        Arc::new(GitLabIssueNode),
+        Arc::new(GoogleSheetsNode),
+        Arc::new(GoogleSheetsFormulaNode),
+        Arc::new(SlackMessageNode::new().with_circuit_breakers(breakers.clone())),
+        Arc::new(SlackAlertNode::new().with_circuit_breakers(breakers.clone())),
+        Arc::new(SlackChannelNode::new().with_circuit_breakers(breakers.clone())),
+        Arc::new(DiscordWebhookNode),
+        Arc::new(DiscordAlertBotNode),
+        Arc::new(DiscordChatBotNode),
+        Arc::new(AzureVMNode),
+        Arc::new(AzureStorageNode),
+        Arc::new(WazuhApiNode::new()),
+        Arc::new(WazuhAlertProcessorNode),
+        Arc::new(ProxmoxVMNode::new().with_event_bus(events.clone())),
+        Arc::new(ProxmoxContainerNode),
+        Arc::new(SMTPEmailNode),
+        Arc::new(SendGridNode::new().with_circuit_breakers(breakers.clone())),
+        Arc::new(MailgunNode),
+        Arc::new(MailgunWebhookNode),
+        Arc::new(PostgreSQLNode),
+        Arc::new(MySQLNode),
+        Arc::new(MongoDBNode),
+        Arc::new(RedisNode),
+        Arc::new(SqlServerNode),
+        Arc::new(S3Node),
+        Arc::new(SqsNode),
+        Arc::new(GitHubNode),
+        Arc::new(TelegramNode),
+        Arc::new(GraphQLNode::new().with_circuit_breakers(breakers.clone())),
+        Arc::new(SftpNode::new()),
+    ]
+}
+
+/// Register all built-in nodes, keyed by their definition id. Fails if two
+/// nodes claim the same id or the id is already taken in `registry`.
+pub fn register_builtin_nodes<R: NodeRegistry + ?Sized>(registry: &mut R, services: &Services) -> Result<()> {
+    for node in builtin_nodes(services) {
+        let id = node.definition().id;
+        if registry.validate_node_type(&id) {
+            return Err(GhostFlowError::ConfigurationError {
+                message: format!("Node type '{}' is already registered", id),
+            });
+        }
+        registry.register_node(id, node)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ghostflow_core::BasicNodeRegistry;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_registers_every_builtin_node_once() {
+        let mut registry = BasicNodeRegistry::new();
+        register_builtin_nodes(&mut registry, &Services::default()).unwrap();
+
+        let definitions = registry.list_node_definitions();
+        assert_eq!(definitions.len(), 65);
+
+        let ids: HashSet<String> = definitions.iter().map(|d| d.id.clone()).collect();
+        assert_eq!(ids.len(), definitions.len());
+
+        for id in ["http_request", "shell", "ollama_generate", "llm_generate", "slack_message"] {
+            assert!(registry.validate_node_type(id), "missing node type {}", id);
+        }
+    }
+
+    #[test]
+    fn test_definition_export_includes_slack_bot_token() {
+        let mut registry = BasicNodeRegistry::new();
+        register_builtin_nodes(&mut registry, &Services::default()).unwrap();
+
+        let catalog = ghostflow_core::node_definitions_json(&registry).unwrap();
+        let nodes = catalog["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 65);
+
+        let slack = nodes.iter().find(|n| n["id"] == "slack_message").unwrap();
+        assert_eq!(slack["category"], "integration");
+        let bot_token = slack["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"] == "bot_token")
+            .unwrap();
+        assert_eq!(bot_token["required"], true);
+        assert!(bot_token.get("options").is_some());
+        assert!(bot_token.get("validation").is_some());
+    }
+
+    #[test]
+    fn test_registering_twice_is_rejected() {
+        let mut registry = BasicNodeRegistry::new();
+        register_builtin_nodes(&mut registry, &Services::default()).unwrap();
+        assert!(register_builtin_nodes(&mut registry, &Services::default()).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,353 @@
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use mlua::{HookTriggers, Lua, LuaOptions, LuaSerdeExt, StdLib, VmState};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Base library functions that reach the filesystem or load code
+const BLOCKED_GLOBALS: &[&str] = &["dofile", "loadfile", "load", "require", "collectgarbage"];
+
+/// How often, in VM instructions, a running script checks its deadline
+const HOOK_INTERVAL: u32 = 1000;
+
+/// How much `print` output a script may leave in the node output
+const MAX_LOG_BYTES: usize = 64 * 1024;
+
+/// Deployment-level limits for scripts. Flow parameters can shorten the
+/// timeout but never extend it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptNodeConfig {
+    pub default_timeout_ms: u64,
+    pub max_timeout_ms: u64,
+    /// Memory the Lua state may allocate
+    pub max_memory_bytes: usize,
+}
+
+impl Default for ScriptNodeConfig {
+    fn default() -> Self {
+        Self {
+            default_timeout_ms: 1000,
+            max_timeout_ms: 10_000,
+            max_memory_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// Runs an inline Lua script against the node input. The script sees the
+/// input as the global `input` and its `return` value becomes the output.
+/// Only the table, string, math and utf8 libraries are loaded, so scripts
+/// have no file, process or network access; `print` adds a line to the
+/// `logs` output.
+pub struct ScriptNode {
+    config: ScriptNodeConfig,
+}
+
+impl ScriptNode {
+    pub fn new() -> Self {
+        Self {
+            config: ScriptNodeConfig::default(),
+        }
+    }
+
+    pub fn with_config(config: ScriptNodeConfig) -> Self {
+        Self { config }
+    }
+
+    fn timeout_ms(&self, params: &Value) -> u64 {
+        params
+            .get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(self.config.default_timeout_ms)
+            .min(self.config.max_timeout_ms)
+    }
+}
+
+impl Default for ScriptNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn script_param(params: &Value) -> Result<&str> {
+    params
+        .get("script")
+        .and_then(|v| v.as_str())
+        .filter(|script| !script.trim().is_empty())
+        .ok_or_else(|| GhostFlowError::ValidationError {
+            message: "Script is required and must be a string".to_string(),
+        })
+}
+
+fn sandbox(memory_limit: usize) -> mlua::Result<Lua> {
+    let lua = Lua::new_with(
+        StdLib::TABLE | StdLib::STRING | StdLib::MATH | StdLib::UTF8,
+        LuaOptions::default(),
+    )?;
+    lua.set_memory_limit(memory_limit)?;
+    let globals = lua.globals();
+    for name in BLOCKED_GLOBALS {
+        globals.set(*name, mlua::Value::Nil)?;
+    }
+    Ok(lua)
+}
+
+/// Lines a script printed, up to [`MAX_LOG_BYTES`]
+#[derive(Debug, Default)]
+struct Logs {
+    lines: Vec<String>,
+    bytes: usize,
+    truncated: bool,
+}
+
+impl Logs {
+    fn push(&mut self, line: String) {
+        self.bytes += line.len() + 1;
+        if self.bytes > MAX_LOG_BYTES {
+            self.truncated = true;
+        } else {
+            self.lines.push(line);
+        }
+    }
+}
+
+/// Run `script` to completion or until `timeout` passes. Blocking; call it
+/// off the async runtime.
+fn run_script(script: &str, input: &Value, timeout: Duration, memory_limit: usize) -> Result<(Value, Logs)> {
+    let script_error = |e: mlua::Error| GhostFlowError::ValidationError {
+        message: format!("Script failed: {}", e),
+    };
+
+    let lua = sandbox(memory_limit).map_err(script_error)?;
+    lua.globals()
+        .set("input", lua.to_value(input).map_err(script_error)?)
+        .map_err(script_error)?;
+
+    // Like Lua's own print, but into the node output rather than our stdout
+    let logs = Arc::new(Mutex::new(Logs::default()));
+    let sink = logs.clone();
+    let print = lua
+        .create_function(move |lua, args: mlua::Variadic<mlua::Value>| {
+            let tostring: mlua::Function = lua.globals().get("tostring")?;
+            let parts = args
+                .into_iter()
+                .map(|arg| tostring.call(arg))
+                .collect::<mlua::Result<Vec<String>>>()?;
+            sink.lock().unwrap().push(parts.join("\t"));
+            Ok(())
+        })
+        .map_err(script_error)?;
+    lua.globals().set("print", print).map_err(script_error)?;
+
+    let deadline = Instant::now() + timeout;
+    lua.set_hook(HookTriggers::new().every_nth_instruction(HOOK_INTERVAL), move |_, _| {
+        if Instant::now() >= deadline {
+            return Err(mlua::Error::RuntimeError("script timed out".to_string()));
+        }
+        Ok(VmState::Continue)
+    });
+
+    let result = lua
+        .load(script)
+        .set_name("script")
+        .set_mode(mlua::ChunkMode::Text)
+        .eval::<mlua::Value>();
+    match result {
+        Ok(value) => {
+            let value = lua.from_value(value).map_err(script_error)?;
+            let logs = std::mem::take(&mut *logs.lock().unwrap());
+            Ok((value, logs))
+        }
+        Err(_) if Instant::now() >= deadline => Err(GhostFlowError::TimeoutError {
+            timeout_ms: timeout.as_millis() as u64,
+        }),
+        Err(e) => Err(script_error(e)),
+    }
+}
+
+#[async_trait]
+impl Node for ScriptNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "script".to_string(),
+            name: "Script".to_string(),
+            description: "Transform data with an inline Lua script".to_string(),
+            category: NodeCategory::Transform,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "input".to_string(),
+                display_name: "Input".to_string(),
+                description: Some("Available to the script as the global `input`".to_string()),
+                data_type: DataType::Any,
+                required: false,
+            }],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: Some("The value the script returns".to_string()),
+                    data_type: DataType::Any,
+                    required: true,
+                },
+                NodePort {
+                    name: "logs".to_string(),
+                    display_name: "Logs".to_string(),
+                    description: Some("Lines the script printed".to_string()),
+                    data_type: DataType::Array,
+                    required: false,
+                },
+            ],
+            parameters: vec![
+                NodeParameter {
+                    name: "script".to_string(),
+                    display_name: "Script".to_string(),
+                    description: Some("Lua code; its return value is the node output".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("return input".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "timeout_ms".to_string(),
+                    display_name: "Timeout (ms)".to_string(),
+                    description: Some("Stop the script if it runs longer than this".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(json!(self.config.default_timeout_ms)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("code".to_string()),
+            color: Some("#000080".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        let script = script_param(&context.input)?;
+        // Compile without running to catch syntax errors early
+        let lua = sandbox(self.config.max_memory_bytes).map_err(|e| GhostFlowError::InternalError {
+            message: format!("Failed to create script sandbox: {}", e),
+        })?;
+        lua.load(script)
+            .set_name("script")
+            .set_mode(mlua::ChunkMode::Text)
+            .into_function()
+            .map(|_| ())
+            .map_err(|e| GhostFlowError::ValidationError {
+                message: format!("Script does not compile: {}", e),
+            })
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let script = script_param(&context.input)?.to_string();
+        let input = context.input.get("input").cloned().unwrap_or(Value::Null);
+        let timeout = Duration::from_millis(self.timeout_ms(&context.input));
+        let memory_limit = self.config.max_memory_bytes;
+
+        info!("Running script for node {} with a {:?} limit", context.node_id, timeout);
+        let task = tokio::task::spawn_blocking(move || run_script(&script, &input, timeout, memory_limit));
+        // The instruction hook cannot interrupt a long call into C, such as a
+        // pathological string pattern; stop waiting and let that thread finish
+        let (result, logs) = tokio::time::timeout(timeout, task)
+            .await
+            .map_err(|_| GhostFlowError::TimeoutError {
+                timeout_ms: timeout.as_millis() as u64,
+            })?
+            .map_err(|e| GhostFlowError::InternalError {
+                message: format!("Script task failed: {}", e),
+            })??;
+
+        Ok(json!({ "result": result, "logs": logs.lines, "logs_truncated": logs.truncated }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+
+    #[tokio::test]
+    async fn test_script_maps_an_array() {
+        let script = r#"
+            local out = {}
+            for i, alert in ipairs(input.alerts) do
+                out[i] = { id = alert.id, critical = alert.level >= 12 }
+            end
+            return out
+        "#;
+        let output = ScriptNode::new()
+            .execute(context(json!({
+                "script": script,
+                "input": { "alerts": [{ "id": "a1", "level": 14 }, { "id": "a2", "level": 3 }] },
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            output["result"],
+            json!([{ "id": "a1", "critical": true }, { "id": "a2", "critical": false }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_runaway_script_is_stopped_at_timeout() {
+        let started = Instant::now();
+        let err = ScriptNode::new()
+            .execute(context(json!({ "script": "while true do end", "timeout_ms": 100 })))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, GhostFlowError::TimeoutError { timeout_ms: 100 }), "{}", err);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_slow_string_pattern_is_stopped_at_timeout() {
+        // Backtracks for seconds inside string.find, where no hook runs
+        let script = r#"return string.find(string.rep("a", 17), string.rep("a*", 17) .. "b")"#;
+        let started = Instant::now();
+        let err = ScriptNode::new()
+            .execute(context(json!({ "script": script, "timeout_ms": 100 })))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, GhostFlowError::TimeoutError { timeout_ms: 100 }), "{}", err);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_print_goes_to_the_logs_output() {
+        let output = ScriptNode::new()
+            .execute(context(json!({ "script": "print('checked', 3, nil) print('done') return input", "input": 7 })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["result"], 7);
+        assert_eq!(output["logs"], json!(["checked\t3\tnil", "done"]));
+        assert_eq!(output["logs_truncated"], false);
+    }
+
+    #[tokio::test]
+    async fn test_script_has_no_host_access() {
+        let node = ScriptNode::new();
+        for script in ["return io.open('/etc/passwd')", "return os.execute('id')", "return dofile('/etc/passwd')"] {
+            let err = node.execute(context(json!({ "script": script }))).await.unwrap_err();
+            assert!(err.to_string().contains("Script failed"), "{}: {}", script, err);
+        }
+
+        let err = node.validate(&context(json!({ "script": "return {" }))).await.unwrap_err();
+        assert!(err.to_string().contains("does not compile"), "{}", err);
+    }
+}
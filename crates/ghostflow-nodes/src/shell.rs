@@ -0,0 +1,587 @@
+use async_trait::async_trait;
+use ghostflow_core::{read_capped, write_input, CappedOutput, GhostFlowError, Node, ProcessLimits, Result};
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use ghostflow_schema::node::ParameterType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+use tokio::process::{Child, Command};
+use tracing::{error, info, warn};
+
+/// Deployment-level guard for the shell node. Flow parameters can narrow
+/// these lists further but never widen them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellNodeConfig {
+    /// The only executables that may run; when empty the node refuses
+    /// every command.
+    pub allowed_commands: Vec<String>,
+    /// Executables that may never run.
+    pub denied_commands: Vec<String>,
+    pub default_timeout_seconds: u64,
+    /// Output, memory and CPU caps; flow parameters may only lower them
+    #[serde(default)]
+    pub limits: ProcessLimits,
+}
+
+impl Default for ShellNodeConfig {
+    fn default() -> Self {
+        Self {
+            allowed_commands: std::env::var("GHOSTFLOW_SHELL_ALLOWLIST")
+                .map(|list| split_command_list(&list))
+                .unwrap_or_default(),
+            denied_commands: std::env::var("GHOSTFLOW_SHELL_DENYLIST")
+                .map(|list| split_command_list(&list))
+                .unwrap_or_default(),
+            default_timeout_seconds: 60,
+            limits: ProcessLimits::default(),
+        }
+    }
+}
+
+fn split_command_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Runs an arbitrary executable with an argument array
+pub struct ShellNode {
+    config: ShellNodeConfig,
+}
+
+impl ShellNode {
+    pub fn new() -> Self {
+        Self {
+            config: ShellNodeConfig::default(),
+        }
+    }
+
+    pub fn with_config(config: ShellNodeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Resolves `command` and checks it against the lists, returning the
+    /// path that must be executed.
+    fn check_command_allowed(&self, command: &str, params: &Value) -> Result<PathBuf> {
+        let resolved = resolve_command(command).ok_or_else(|| GhostFlowError::ValidationError {
+            message: format!("Command '{}' was not found", command),
+        })?;
+        let param_allowlist = string_list(params.get("allowlist"));
+        let param_denylist = string_list(params.get("denylist")).unwrap_or_default();
+
+        let denied = self.config.denied_commands.iter().chain(param_denylist.iter());
+        for entry in denied {
+            if is_denied(command, &resolved, entry) {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Command '{}' is denied", command),
+                });
+            }
+        }
+
+        for allowlist in [Some(&self.config.allowed_commands), param_allowlist.as_ref()].into_iter().flatten() {
+            if !allowlist.iter().any(|entry| resolve_command(entry).as_ref() == Some(&resolved)) {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Command '{}' is not in the allowlist", command),
+                });
+            }
+        }
+
+        Ok(resolved)
+    }
+}
+
+impl Default for ShellNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Directories bare command names are looked up in. The server's own PATH
+/// is ignored so a writable directory in it cannot shadow a system tool.
+const COMMAND_SEARCH_PATH: &[&str] = &["/usr/local/bin", "/usr/bin", "/bin", "/usr/local/sbin", "/usr/sbin", "/sbin"];
+
+/// Absolute path of an executable. Bare names are looked up in
+/// [`COMMAND_SEARCH_PATH`]; paths get their directory canonicalized. The
+/// file itself is not followed, so multi-call binaries keep their names.
+fn resolve_command(command: &str) -> Option<PathBuf> {
+    if !command.contains('/') {
+        return COMMAND_SEARCH_PATH
+            .iter()
+            .find_map(|dir| resolve_command(&format!("{}/{}", dir, command)));
+    }
+
+    let path = Path::new(command);
+    let name = path.file_name()?;
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.canonicalize().ok()?,
+        _ => std::env::current_dir().ok()?,
+    };
+    let full = dir.join(name);
+    full.is_file().then_some(full)
+}
+
+/// Deny entries also match by file name, so `curl` blocks every copy of it.
+fn is_denied(command: &str, resolved: &Path, entry: &str) -> bool {
+    command == entry
+        || resolved.file_name().map(|name| name == entry).unwrap_or(false)
+        || resolve_command(entry).as_deref() == Some(resolved)
+}
+
+fn string_list(value: Option<&Value>) -> Option<Vec<String>> {
+    value.and_then(|v| v.as_array()).map(|items| {
+        items
+            .iter()
+            .filter_map(|item| item.as_str().map(|s| s.to_string()))
+            .collect()
+    })
+}
+
+/// Extracts the `args` array. Every element is passed to the process as a
+/// single argument, so values containing spaces or commas are preserved.
+fn parse_args(value: Option<&Value>) -> Result<Vec<String>> {
+    match value {
+        None | Some(Value::Null) => Ok(vec![]),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| match item {
+                Value::String(s) => Ok(s.clone()),
+                Value::Number(_) | Value::Bool(_) => Ok(item.to_string()),
+                _ => Err(GhostFlowError::ValidationError {
+                    message: "Arguments must be strings, numbers or booleans".to_string(),
+                }),
+            })
+            .collect(),
+        Some(_) => Err(GhostFlowError::ValidationError {
+            message: "Arguments must be a JSON array".to_string(),
+        }),
+    }
+}
+
+#[async_trait]
+impl Node for ShellNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "shell".to_string(),
+            name: "Shell Command".to_string(),
+            description: "Run an executable and capture its output".to_string(),
+            category: NodeCategory::Action,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "input".to_string(),
+                display_name: "Input".to_string(),
+                description: Some("Data written to the command's stdin as JSON".to_string()),
+                data_type: DataType::Any,
+                required: false,
+            }],
+            outputs: vec![NodePort {
+                name: "result".to_string(),
+                display_name: "Result".to_string(),
+                description: Some("stdout, stderr and exit code".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            parameters: vec![
+                NodeParameter {
+                    name: "command".to_string(),
+                    display_name: "Command".to_string(),
+                    description: Some("Executable to run".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "args".to_string(),
+                    display_name: "Arguments".to_string(),
+                    description: Some("Arguments as a JSON array; each element is passed verbatim".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: Some(Value::Array(vec![])),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "working_dir".to_string(),
+                    display_name: "Working Directory".to_string(),
+                    description: Some("Directory to run the command in".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "timeout_seconds".to_string(),
+                    display_name: "Timeout (seconds)".to_string(),
+                    description: Some("Kill the command if it runs longer than this".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(self.config.default_timeout_seconds))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_output_bytes".to_string(),
+                    display_name: "Max Output (bytes)".to_string(),
+                    description: Some("Truncate stdout and stderr beyond this many bytes each".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(self.config.limits.max_output_bytes))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_memory_mb".to_string(),
+                    display_name: "Memory Limit (MB)".to_string(),
+                    description: Some("Address space the command may use (Linux only)".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_cpu_seconds".to_string(),
+                    display_name: "CPU Time Limit (seconds)".to_string(),
+                    description: Some("Kill the command once it has used this much CPU time (Linux only)".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "allowlist".to_string(),
+                    display_name: "Allowed Commands".to_string(),
+                    description: Some("Only run the command if it appears in this list".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "denylist".to_string(),
+                    display_name: "Denied Commands".to_string(),
+                    description: Some("Refuse to run any command in this list".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("terminal".to_string()),
+            color: Some("#64748b".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        let params = &context.input;
+
+        let command = params
+            .get("command")
+            .and_then(|v| v.as_str())
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| GhostFlowError::ValidationError {
+                message: "Command is required and must be a string".to_string(),
+            })?;
+
+        parse_args(params.get("args"))?;
+        self.check_command_allowed(command, params)?;
+
+        Ok(())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<serde_json::Value> {
+        let params = &context.input;
+        let start_time = std::time::Instant::now();
+
+        let command = params
+            .get("command")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: "Missing command parameter".to_string(),
+            })?;
+
+        // Re-check the guard here so a skipped validate() cannot bypass it
+        let executable = self.check_command_allowed(command, params)?;
+
+        let args = parse_args(params.get("args"))?;
+
+        let working_dir = params.get("working_dir").and_then(|v| v.as_str());
+
+        let timeout_seconds = params
+            .get("timeout_seconds")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(self.config.default_timeout_seconds);
+
+        let limits = self.config.limits.narrowed_by(params);
+
+        info!("Executing shell command: {} {:?}", command, args);
+
+        let mut cmd = Command::new(&executable);
+        cmd.args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        limits.apply(&mut cmd);
+
+        if let Some(dir) = working_dir {
+            cmd.current_dir(dir);
+        }
+
+        let child = cmd.spawn().map_err(|e| {
+            error!("Failed to spawn shell command: {}", e);
+            GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: format!("Command execution failed: {}", e),
+            }
+        })?;
+
+        let input = match params.get("input").filter(|v| !v.is_null()) {
+            Some(input) => Some(serde_json::to_vec(input)?),
+            None => None,
+        };
+
+        let output = wait_or_kill(child, input, Duration::from_secs(timeout_seconds), limits.max_output_bytes)
+            .await
+            .map_err(|e| GhostFlowError::NodeExecutionError {
+                node_id: context.node_id.clone(),
+                message: format!("Command execution failed: {}", e),
+            })?
+            .ok_or(GhostFlowError::TimeoutError {
+                timeout_ms: timeout_seconds * 1000,
+            })?;
+
+        let exit_code = output.status.code().unwrap_or(-1);
+        let stdout = String::from_utf8_lossy(&output.stdout.bytes).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr.bytes).to_string();
+
+        info!("Shell command completed with exit code: {}", exit_code);
+        for (name, stream) in [("stdout", &output.stdout), ("stderr", &output.stderr)] {
+            if stream.truncated {
+                warn!(
+                    "Shell command {} truncated to {} of {} bytes",
+                    name,
+                    stream.bytes.len(),
+                    stream.total_bytes
+                );
+            }
+        }
+
+        Ok(serde_json::json!({
+            "success": exit_code == 0,
+            "stdout": stdout,
+            "stderr": stderr,
+            "stdout_truncated": output.stdout.truncated,
+            "stderr_truncated": output.stderr.truncated,
+            "exit_code": exit_code,
+            "execution_time_ms": start_time.elapsed().as_millis() as u64,
+            "command": {
+                "executable": command,
+                "args": args,
+                "working_dir": working_dir,
+            }
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        true
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+struct CommandOutput {
+    status: ExitStatus,
+    stdout: CappedOutput,
+    stderr: CappedOutput,
+}
+
+/// Feeds `input` to the child and waits for it while draining its pipes,
+/// keeping at most `max_output_bytes` of each. On timeout the child is killed
+/// and reaped, and `None` is returned.
+async fn wait_or_kill(
+    mut child: Child,
+    input: Option<Vec<u8>>,
+    timeout: Duration,
+    max_output_bytes: usize,
+) -> std::io::Result<Option<CommandOutput>> {
+    let stdin_task = tokio::spawn(write_input(child.stdin.take(), input));
+    let stdout_task = tokio::spawn(read_capped(child.stdout.take(), max_output_bytes));
+    let stderr_task = tokio::spawn(read_capped(child.stderr.take(), max_output_bytes));
+
+    let stdin_abort = stdin_task.abort_handle();
+    let finished = tokio::time::timeout(timeout, async { tokio::join!(child.wait(), stdin_task) }).await;
+
+    match finished {
+        Ok((status, written)) => {
+            let status = status?;
+            if let Ok(Err(e)) = written {
+                warn!("Failed to write shell command input: {}", e);
+            }
+            let stdout = stdout_task.await.unwrap_or_default();
+            let stderr = stderr_task.await.unwrap_or_default();
+            Ok(Some(CommandOutput { status, stdout, stderr }))
+        }
+        Err(_) => {
+            child.kill().await?;
+            stdin_abort.abort();
+            stdout_task.abort();
+            stderr_task.abort();
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use uuid::Uuid;
+
+    fn allowing(commands: &[&str]) -> ShellNode {
+        ShellNode::with_config(ShellNodeConfig {
+            allowed_commands: commands.iter().map(|c| c.to_string()).collect(),
+            denied_commands: vec![],
+            default_timeout_seconds: 10,
+            limits: ProcessLimits::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_args_are_passed_verbatim() {
+        let result = allowing(&["printf"])
+            .execute(context(serde_json::json!({
+                "command": "printf",
+                "args": ["%s|", "a,b,c", "with space"],
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["stdout"], "a,b,c|with space|");
+        assert_eq!(result["exit_code"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_input_larger_than_pipe_buffer_does_not_block() {
+        let payload = Value::String("x".repeat(256 * 1024));
+        let result = allowing(&["cat"])
+            .execute(context(serde_json::json!({
+                "command": "cat",
+                "input": payload,
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["exit_code"], 0);
+        assert_eq!(result["stdout"], payload.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_command_outside_allowlist_is_rejected() {
+        let ctx = context(serde_json::json!({
+            "command": "/bin/rm",
+            "args": ["-rf", "/tmp/nothing"],
+            "allowlist": ["echo", "printf"],
+        }));
+
+        let node = allowing(&["rm", "echo", "printf"]);
+        assert!(matches!(node.validate(&ctx).await, Err(GhostFlowError::ValidationError { .. })));
+        assert!(matches!(node.execute(ctx).await, Err(GhostFlowError::ValidationError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_config_denylist_overrides_param_allowlist() {
+        let node = ShellNode::with_config(ShellNodeConfig {
+            allowed_commands: vec!["sh".to_string()],
+            denied_commands: vec!["sh".to_string()],
+            default_timeout_seconds: 10,
+            limits: ProcessLimits::default(),
+        });
+
+        let ctx = context(serde_json::json!({
+            "command": "/bin/sh",
+            "allowlist": ["sh"],
+        }));
+
+        assert!(matches!(node.validate(&ctx).await, Err(GhostFlowError::ValidationError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_commands_are_denied_without_an_allowlist() {
+        let node = ShellNode::with_config(ShellNodeConfig {
+            allowed_commands: vec![],
+            denied_commands: vec![],
+            default_timeout_seconds: 10,
+            limits: ProcessLimits::default(),
+        });
+
+        let ctx = context(serde_json::json!({ "command": "echo" }));
+        assert!(matches!(node.validate(&ctx).await, Err(GhostFlowError::ValidationError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_does_not_cover_copies_elsewhere() {
+        let dir = std::env::temp_dir().join(format!("ghostflow-shell-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let copy = dir.join("echo");
+        std::fs::copy(resolve_command("echo").unwrap(), &copy).unwrap();
+
+        let ctx = context(serde_json::json!({ "command": copy.to_str().unwrap() }));
+        let node = allowing(&["echo"]);
+        assert!(matches!(node.validate(&ctx).await, Err(GhostFlowError::ValidationError { .. })));
+
+        let system_echo = resolve_command("echo").unwrap();
+        let ctx = context(serde_json::json!({ "command": system_echo.to_str().unwrap() }));
+        assert!(node.validate(&ctx).await.is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_output_beyond_cap_is_truncated() {
+        let result = allowing(&["sh"])
+            .execute(context(serde_json::json!({
+                "command": "sh",
+                "args": ["-c", "yes ghostflow | head -c 5000000; echo done >&2"],
+                "max_output_bytes": 4096,
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["exit_code"], 0);
+        assert_eq!(result["stdout"].as_str().unwrap().len(), 4096);
+        assert!(result["stdout"].as_str().unwrap().starts_with("ghostflow\nghostflow\n"));
+        assert_eq!(result["stdout_truncated"], true);
+        assert_eq!(result["stderr"], "done\n");
+        assert_eq!(result["stderr_truncated"], false);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_cpu_limit_kills_busy_command() {
+        let started = std::time::Instant::now();
+        let result = allowing(&["sh"])
+            .execute(context(serde_json::json!({
+                "command": "sh",
+                "args": ["-c", "while :; do :; done"],
+                "max_cpu_seconds": 1,
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(result["success"], false);
+        assert!(started.elapsed() < Duration::from_secs(8));
+    }
+}
@@ -1,12 +1,14 @@
 use async_trait::async_trait;
 use ghostflow_core::{GhostFlowError, Node, Result};
 use ghostflow_schema::{
-    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort, BINARY_KEY,
 };
 use ghostflow_schema::node::ParameterType;
 use serde_json::Value;
 use tracing::info;
 
+use crate::template_functions::Placeholder;
+
 pub struct TemplateNode;
 
 impl TemplateNode {
@@ -48,7 +50,7 @@ impl Node for TemplateNode {
                 NodeParameter {
                     name: "template".to_string(),
                     display_name: "Template".to_string(),
-                    description: Some("Template string with {{variable}} placeholders".to_string()),
+                    description: Some("Template string with {{variable}} placeholders and helpers such as {{upper name}}".to_string()),
                     param_type: ParameterType::String,
                     default_value: Some(Value::String("Hello {{name}}!".to_string())),
                     required: true,
@@ -107,7 +109,7 @@ impl Node for TemplateNode {
         info!("Processing template with {} format", output_format);
 
         // Process the template
-        let result = self.process_template(template, &data)?;
+        let result = render_template(template, &data, false)?;
 
         let output = match output_format {
             "json" => {
@@ -132,34 +134,50 @@ impl Node for TemplateNode {
     }
 }
 
-impl TemplateNode {
-    fn process_template(&self, template: &str, data: &Value) -> Result<String> {
-        let mut result = template.to_string();
-        
-        // Simple template processing - replace {{variable}} with values from data
-        // In a real implementation, you'd use a proper template engine like Handlebars or Tera
-        
-        if let Some(data_obj) = data.as_object() {
-            for (key, value) in data_obj {
-                let placeholder = format!("{{{{{}}}}}", key);
-                let replacement = self.value_to_string(value);
-                result = result.replace(&placeholder, &replacement);
+/// Replace the `{{...}}` placeholders in `template` with values from `data`.
+/// Paths with no value are left as written, or rejected when `strict`.
+pub(crate) fn render_template(template: &str, data: &Value, strict: bool) -> Result<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let expression = &rest[start + 2..start + 2 + end];
+        result.push_str(&rest[..start]);
+
+        // Paths with no value are left as written so they stay visible
+        let value = match Placeholder::parse(expression)? {
+            Some(placeholder) => placeholder.evaluate(data)?,
+            None => None,
+        };
+        match value {
+            Some(value) => result.push_str(&value_to_string(&value)),
+            None if strict => {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Template references missing variable '{}'", expression.trim()),
+                })
             }
+            None => result.push_str(&rest[start..start + 4 + end]),
         }
-        
-        // Handle nested access like {{user.name}} - very basic implementation
-        // TODO: Implement proper nested object access
-        
-        Ok(result)
+        rest = &rest[start + 4 + end..];
     }
-    
-    fn value_to_string(&self, value: &Value) -> String {
-        match value {
-            Value::String(s) => s.clone(),
-            Value::Number(n) => n.to_string(),
-            Value::Bool(b) => b.to_string(),
-            Value::Null => "null".to_string(),
-            Value::Array(_) | Value::Object(_) => serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string()),
-        }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+fn value_to_string(value: &Value) -> String {
+    // Binary payloads render as their base64 content, not the wrapper object
+    if let Some(encoded) = value.get(BINARY_KEY).and_then(|v| v.as_str()) {
+        return encoded.to_string();
+    }
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => "null".to_string(),
+        Value::Array(_) | Value::Object(_) => serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string()),
     }
 }
\ No newline at end of file
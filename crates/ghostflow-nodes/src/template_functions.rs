@@ -0,0 +1,339 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use ghostflow_core::{GhostFlowError, Result};
+use serde_json::{json, Value};
+
+/// Helpers usable as `{{helper arg ...}}` in the template node. Arguments are
+/// data paths such as `alert.level`, quoted strings, numbers, `true`,
+/// `false` or `null`.
+pub(crate) const HELPERS: &[&str] = &[
+    "formatDate", "default", "lower", "upper", "trim", "add", "sub", "mul", "len",
+];
+
+/// One argument of a placeholder.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Arg {
+    Path(String),
+    Literal(Value),
+}
+
+impl Arg {
+    fn resolve(&self, data: &Value) -> Value {
+        match self {
+            Arg::Path(path) => lookup(data, path).cloned().unwrap_or(Value::Null),
+            Arg::Literal(value) => value.clone(),
+        }
+    }
+}
+
+/// The contents of a `{{ ... }}` placeholder.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Placeholder {
+    Value(Arg),
+    Helper { name: String, args: Vec<Arg> },
+}
+
+impl Placeholder {
+    /// `None` for text that is neither a single value nor a helper call, such
+    /// as `{{}}`, which the template keeps as written.
+    pub fn parse(expression: &str) -> Result<Option<Self>> {
+        let is_helper = expression
+            .split_whitespace()
+            .next()
+            .is_some_and(|word| HELPERS.contains(&word));
+        let tokens = match tokenize(expression) {
+            Ok(tokens) => tokens,
+            Err(e) if is_helper => return Err(e),
+            Err(_) => return Ok(None),
+        };
+
+        let mut tokens = tokens.into_iter();
+        let Some(first) = tokens.next() else {
+            return Ok(None);
+        };
+        let args: Vec<Arg> = tokens.map(Token::into_arg).collect();
+        Ok(match first {
+            // A lone helper name is still read as a key, e.g. `{{default}}`
+            Token::Word(name) if is_helper && !args.is_empty() => Some(Placeholder::Helper { name, args }),
+            first if args.is_empty() => Some(Placeholder::Value(first.into_arg())),
+            _ => None,
+        })
+    }
+
+    /// `None` for a plain path with no value in `data`.
+    pub fn evaluate(&self, data: &Value) -> Result<Option<Value>> {
+        match self {
+            Placeholder::Value(Arg::Path(path)) => Ok(lookup(data, path).cloned()),
+            Placeholder::Value(Arg::Literal(value)) => Ok(Some(value.clone())),
+            Placeholder::Helper { name, args } => {
+                let values: Vec<Value> = args.iter().map(|arg| arg.resolve(data)).collect();
+                call(name, &values).map(Some)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+}
+
+impl Token {
+    fn into_arg(self) -> Arg {
+        match self {
+            Token::Quoted(text) => Arg::Literal(Value::String(text)),
+            Token::Word(word) => match word.as_str() {
+                "null" => Arg::Literal(Value::Null),
+                "true" => Arg::Literal(Value::Bool(true)),
+                "false" => Arg::Literal(Value::Bool(false)),
+                _ => match serde_json::from_str::<serde_json::Number>(&word) {
+                    Ok(number) => Arg::Literal(Value::Number(number)),
+                    Err(_) => Arg::Path(word),
+                },
+            },
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some('\\') => text.extend(chars.next()),
+                    Some(ch) if ch == c => break,
+                    Some(ch) => text.push(ch),
+                    None => return Err(template_error(format!("Unterminated string in '{}'", expression.trim()))),
+                }
+            }
+            tokens.push(Token::Quoted(text));
+        } else {
+            let mut word = String::new();
+            while let Some(&ch) = chars.peek() {
+                if ch.is_whitespace() {
+                    break;
+                }
+                word.push(ch);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(tokens)
+}
+
+/// A top-level key as written, else a dotted path such as `user.name` or
+/// `items.0.id`.
+fn lookup<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    if let Some(value) = data.get(path) {
+        return Some(value);
+    }
+    path.split('.').try_fold(data, |current, segment| match current {
+        Value::Object(fields) => fields.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+fn call(name: &str, args: &[Value]) -> Result<Value> {
+    let expect = |count: usize| {
+        if args.len() == count {
+            Ok(())
+        } else {
+            Err(helper_error(
+                name,
+                format!("expects {} argument{}, got {}", count, if count == 1 { "" } else { "s" }, args.len()),
+            ))
+        }
+    };
+
+    match name {
+        "formatDate" => {
+            expect(2)?;
+            let format = args[1]
+                .as_str()
+                .ok_or_else(|| helper_error(name, "needs a format string such as \"%Y-%m-%d\"".to_string()))?;
+            format_date(name, &args[0], format)
+        }
+        "default" => {
+            expect(2)?;
+            Ok(if args[0].is_null() { args[1].clone() } else { args[0].clone() })
+        }
+        "lower" | "upper" | "trim" => {
+            expect(1)?;
+            let text = match &args[0] {
+                Value::String(text) => text.clone(),
+                Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            Ok(Value::String(match name {
+                "lower" => text.to_lowercase(),
+                "upper" => text.to_uppercase(),
+                _ => text.trim().to_string(),
+            }))
+        }
+        "add" | "sub" | "mul" => {
+            expect(2)?;
+            arithmetic(name, &args[0], &args[1])
+        }
+        "len" => {
+            expect(1)?;
+            match &args[0] {
+                Value::String(text) => Ok(json!(text.chars().count())),
+                Value::Array(items) => Ok(json!(items.len())),
+                Value::Object(fields) => Ok(json!(fields.len())),
+                Value::Null => Ok(json!(0)),
+                other => Err(helper_error(name, format!("needs a string, array or object, got {}", other))),
+            }
+        }
+        _ => Err(template_error(format!("Unknown template helper '{}'", name))),
+    }
+}
+
+fn format_date(name: &str, value: &Value, format: &str) -> Result<Value> {
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(helper_error(name, format!("has an invalid format string '{}'", format)));
+    }
+
+    let timestamp = match value {
+        Value::String(text) => parse_date(text)
+            .ok_or_else(|| helper_error(name, format!("cannot read '{}' as a date", text)))?,
+        // Unix time in seconds, or milliseconds when too large to be seconds
+        Value::Number(number) => {
+            let seconds = number
+                .as_i64()
+                .or_else(|| number.as_f64().map(|f| f as i64))
+                .unwrap_or_default();
+            let parsed = if seconds.abs() >= 100_000_000_000 {
+                Utc.timestamp_millis_opt(seconds)
+            } else {
+                Utc.timestamp_opt(seconds, 0)
+            };
+            parsed
+                .single()
+                .ok_or_else(|| helper_error(name, format!("timestamp {} is out of range", number)))?
+        }
+        other => return Err(helper_error(name, format!("needs a date string or Unix timestamp, got {}", other))),
+    };
+
+    Ok(Value::String(timestamp.format_with_items(items.into_iter()).to_string()))
+}
+
+fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(text) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    if let Ok(timestamp) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S") {
+        return Some(timestamp.and_utc());
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|timestamp| timestamp.and_utc())
+}
+
+fn arithmetic(name: &str, left: &Value, right: &Value) -> Result<Value> {
+    let number = |value: &Value| -> Result<serde_json::Number> {
+        match value {
+            Value::Number(number) => Ok(number.clone()),
+            Value::String(text) => serde_json::from_str(text.trim())
+                .map_err(|_| helper_error(name, format!("needs numbers, got \"{}\"", text))),
+            other => Err(helper_error(name, format!("needs numbers, got {}", other))),
+        }
+    };
+    let (left, right) = (number(left)?, number(right)?);
+
+    // Stay in integers while both sides are, so `add 1 2` renders as 3 not 3.0
+    if let (Some(a), Some(b)) = (left.as_i64(), right.as_i64()) {
+        let result = match name {
+            "add" => a.checked_add(b),
+            "sub" => a.checked_sub(b),
+            _ => a.checked_mul(b),
+        };
+        return result
+            .map(|n| json!(n))
+            .ok_or_else(|| helper_error(name, format!("overflowed with {} and {}", a, b)));
+    }
+
+    let (a, b) = (left.as_f64().unwrap_or_default(), right.as_f64().unwrap_or_default());
+    let result = match name {
+        "add" => a + b,
+        "sub" => a - b,
+        _ => a * b,
+    };
+    serde_json::Number::from_f64(result)
+        .map(Value::Number)
+        .ok_or_else(|| helper_error(name, format!("produced a non-finite result from {} and {}", a, b)))
+}
+
+fn helper_error(name: &str, problem: String) -> GhostFlowError {
+    template_error(format!("Template helper '{}' {}", name, problem))
+}
+
+fn template_error(message: String) -> GhostFlowError {
+    GhostFlowError::ValidationError { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(expression: &str, data: Value) -> Result<Option<Value>> {
+        Placeholder::parse(expression)?.unwrap().evaluate(&data)
+    }
+
+    #[test]
+    fn test_format_date_accepts_rfc3339_and_unix_time() {
+        let data = json!({ "alert": { "timestamp": "2024-03-09T17:45:00Z" }, "epoch": 1_700_000_000 });
+
+        assert_eq!(
+            render(r#"formatDate alert.timestamp "%Y-%m-%d""#, data.clone()).unwrap(),
+            Some(json!("2024-03-09"))
+        );
+        assert_eq!(render("formatDate epoch '%d/%m/%Y %H:%M'", data.clone()).unwrap(), Some(json!("14/11/2023 22:13")));
+
+        let err = render(r#"formatDate alert "%Y""#, data).unwrap_err();
+        assert!(err.to_string().contains("Template helper 'formatDate'"), "{}", err);
+    }
+
+    #[test]
+    fn test_default_substitutes_only_for_null() {
+        let data = json!({ "owner": null, "count": 0 });
+
+        assert_eq!(render(r#"default owner "unassigned""#, data.clone()).unwrap(), Some(json!("unassigned")));
+        assert_eq!(render(r#"default missing.path "unassigned""#, data.clone()).unwrap(), Some(json!("unassigned")));
+        assert_eq!(render("default count 5", data).unwrap(), Some(json!(0)));
+    }
+
+    #[test]
+    fn test_helper_argument_errors_name_the_helper() {
+        let err = render(r#"add 1 "two""#, json!({})).unwrap_err();
+        assert!(err.to_string().contains("Template helper 'add' needs numbers"), "{}", err);
+
+        let err = render("upper first last", json!({})).unwrap_err();
+        assert!(err.to_string().contains("Template helper 'upper' expects 1 argument, got 2"), "{}", err);
+
+        // Anything that is not a helper call is left for the template to keep
+        assert_eq!(Placeholder::parse("shout name").unwrap(), None);
+    }
+
+    #[test]
+    fn test_string_and_math_helpers() {
+        let data = json!({ "name": "  Wazuh ", "hits": [1, 2, 3], "level": "7" });
+
+        assert_eq!(render("trim name", data.clone()).unwrap(), Some(json!("Wazuh")));
+        assert_eq!(render("upper name", data.clone()).unwrap(), Some(json!("  WAZUH ")));
+        assert_eq!(render("len hits", data.clone()).unwrap(), Some(json!(3)));
+        assert_eq!(render("add level 3", data.clone()).unwrap(), Some(json!(10)));
+        assert_eq!(render("mul 1.5 2", data).unwrap(), Some(json!(3.0)));
+    }
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use ghostflow_core::{Node, NodeRegistry, NodeRunner, Result, Services};
+use ghostflow_schema::ExecutionContext;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Stands in for the engine that nodes such as try/catch run other nodes
+/// through; runs the nodes of its registry without the engine's checks.
+pub struct TestEngine {
+    registry: Arc<dyn NodeRegistry>,
+}
+
+impl TestEngine {
+    pub fn new(registry: Arc<dyn NodeRegistry>) -> Arc<Self> {
+        Arc::new(Self { registry })
+    }
+
+    /// Services that reach this engine, while it is alive
+    pub fn services(self: &Arc<Self>) -> Services {
+        let services = Services::default();
+        let runner = Arc::downgrade(self);
+        services.nodes.set(runner);
+        services
+    }
+}
+
+#[async_trait]
+impl NodeRunner for TestEngine {
+    fn get_node(&self, node_type: &str) -> Option<Arc<dyn Node>> {
+        self.registry.get_node(node_type)
+    }
+
+    async fn run_node(&self, node: &dyn Node, context: ExecutionContext) -> Result<Value> {
+        node.validate(&context).await?;
+        node.execute(context).await
+    }
+}
+
+/// Context of a node's first attempt in a fresh execution
+pub fn context(input: Value) -> ExecutionContext {
+    ExecutionContext {
+        execution_id: Uuid::new_v4(),
+        flow_id: Uuid::new_v4(),
+        node_id: "node".to_string(),
+        input,
+        variables: HashMap::new(),
+        secrets: HashMap::new(),
+        artifacts: HashMap::new(),
+        node_outputs: HashMap::new(),
+        attempt: 1,
+    }
+}
@@ -0,0 +1,217 @@
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::Value;
+
+/// Reshape JSON with a JMESPath expression, or with a `mappings` object of
+/// `output_field -> expression` pairs for simple picking and renaming.
+pub struct TransformNode;
+
+impl TransformNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TransformNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn compile(expression: &str) -> Result<jmespath::Expression<'static>> {
+    jmespath::compile(expression).map_err(|e| GhostFlowError::ValidationError {
+        message: format!("Invalid JMESPath expression '{}': {}", expression, e),
+    })
+}
+
+fn search(expression: &jmespath::Expression<'_>, data: &Value) -> Result<Value> {
+    let result = expression
+        .search(data.clone())
+        .map_err(|e| GhostFlowError::ValidationError {
+            message: format!("Failed to evaluate '{}': {}", expression, e),
+        })?;
+    Ok(serde_json::to_value(&*result)?)
+}
+
+/// Compile everything up front so validation and execution report the same
+/// errors. Mappings win when both forms are given.
+enum Transform {
+    Expression(jmespath::Expression<'static>),
+    Mappings(Vec<(String, jmespath::Expression<'static>)>),
+}
+
+impl Transform {
+    fn from_params(params: &Value) -> Result<Self> {
+        if let Some(mappings) = params.get("mappings").filter(|v| !v.is_null()) {
+            let mappings = mappings.as_object().ok_or_else(|| GhostFlowError::ValidationError {
+                message: "Mappings must be an object of output field to expression".to_string(),
+            })?;
+            let compiled = mappings
+                .iter()
+                .map(|(field, expression)| {
+                    let expression = expression.as_str().ok_or_else(|| GhostFlowError::ValidationError {
+                        message: format!("Mapping for '{}' must be a string expression", field),
+                    })?;
+                    Ok((field.clone(), compile(expression)?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(Transform::Mappings(compiled));
+        }
+
+        match params.get("expression").and_then(|v| v.as_str()) {
+            Some(expression) => Ok(Transform::Expression(compile(expression)?)),
+            None => Err(GhostFlowError::ValidationError {
+                message: "Either an expression or mappings is required".to_string(),
+            }),
+        }
+    }
+
+    fn apply(&self, data: &Value) -> Result<Value> {
+        match self {
+            Transform::Expression(expression) => search(expression, data),
+            Transform::Mappings(mappings) => {
+                let mut output = serde_json::Map::new();
+                for (field, expression) in mappings {
+                    output.insert(field.clone(), search(expression, data)?);
+                }
+                Ok(Value::Object(output))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Node for TransformNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "transform".to_string(),
+            name: "Transform".to_string(),
+            description: "Reshape JSON with a JMESPath expression or field mappings".to_string(),
+            category: NodeCategory::Transform,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "data".to_string(),
+                display_name: "Data".to_string(),
+                description: Some("JSON document to transform".to_string()),
+                data_type: DataType::Any,
+                required: true,
+            }],
+            outputs: vec![NodePort {
+                name: "result".to_string(),
+                display_name: "Result".to_string(),
+                description: Some("Transformed value".to_string()),
+                data_type: DataType::Any,
+                required: true,
+            }],
+            parameters: vec![
+                NodeParameter {
+                    name: "expression".to_string(),
+                    display_name: "Expression".to_string(),
+                    description: Some("JMESPath expression, e.g. items[?active].{id: id, name: name}".to_string()),
+                    param_type: ParameterType::Code,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "mappings".to_string(),
+                    display_name: "Field Mappings".to_string(),
+                    description: Some("Object of output field to JMESPath expression, e.g. {\"email\": \"user.contact.email\"}".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("shuffle".to_string()),
+            color: Some("#10b981".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        Transform::from_params(&context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let data = context.input.get("data").cloned().unwrap_or(Value::Null);
+        let result = Transform::from_params(&context.input)?.apply(&data)?;
+        Ok(serde_json::json!({ "result": result }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "vm": { "id": 101, "status": { "cpu": 0.42, "state": "running" } },
+            "disks": [
+                { "name": "scsi0", "size_gb": 32 },
+                { "name": "scsi1", "size_gb": 500 }
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_selects_nested_field() {
+        let output = TransformNode::new()
+            .execute(context(json!({ "data": sample(), "expression": "vm.status.state" })))
+            .await
+            .unwrap();
+        assert_eq!(output["result"], "running");
+    }
+
+    #[tokio::test]
+    async fn test_projects_over_array() {
+        let output = TransformNode::new()
+            .execute(context(json!({
+                "data": sample(),
+                "expression": "disks[?size_gb > `100`].name",
+            })))
+            .await
+            .unwrap();
+        assert_eq!(output["result"], json!(["scsi1"]));
+    }
+
+    #[tokio::test]
+    async fn test_mappings_rename_fields() {
+        let output = TransformNode::new()
+            .execute(context(json!({
+                "data": sample(),
+                "mappings": { "vmid": "vm.id", "disk_names": "disks[*].name" },
+            })))
+            .await
+            .unwrap();
+        assert_eq!(output["result"], json!({ "vmid": 101, "disk_names": ["scsi0", "scsi1"] }));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_expression_fails_validation() {
+        let node = TransformNode::new();
+        let error = node
+            .validate(&context(json!({ "data": sample(), "expression": "disks[?size_gb >" })))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, GhostFlowError::ValidationError { .. }));
+        assert!(error.to_string().contains("Invalid JMESPath expression"));
+
+        assert!(node.validate(&context(json!({ "data": {} }))).await.is_err());
+    }
+}
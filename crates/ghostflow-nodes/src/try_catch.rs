@@ -0,0 +1,251 @@
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, NodeRegistry, NodeRunner, Result, Services};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Run another node and turn its failure into output instead of failing the
+/// flow: `{success, result, error}`, where `error` is the same object an
+/// `on_error` edge carries. Wire the error branch with an edge condition
+/// such as `!success`. The wrapped node runs through the engine, with the
+/// same parameter checks and concurrency limits as the nodes of a flow.
+pub struct TryCatchNode {
+    registry: Option<Arc<dyn NodeRegistry>>,
+    services: Services,
+}
+
+impl TryCatchNode {
+    /// Wraps the nodes of the engine it runs in.
+    pub fn new() -> Self {
+        Self {
+            registry: None,
+            services: Services::default(),
+        }
+    }
+
+    /// Wraps the nodes of `registry`, e.g. to include custom node types.
+    pub fn with_registry(registry: Arc<dyn NodeRegistry>) -> Self {
+        Self {
+            registry: Some(registry),
+            services: Services::default(),
+        }
+    }
+
+    /// Reach the engine through `services`
+    pub fn with_services(mut self, services: Services) -> Self {
+        self.services = services;
+        self
+    }
+
+    fn runner(&self) -> Result<Arc<dyn NodeRunner>> {
+        self.services.nodes.get().ok_or_else(|| GhostFlowError::ConfigurationError {
+            message: "try_catch only runs nodes inside an engine".to_string(),
+        })
+    }
+
+    fn wrapped(&self, runner: &dyn NodeRunner, input: &Value) -> Result<Arc<dyn Node>> {
+        let node_type = input
+            .get("node_type")
+            .and_then(|v| v.as_str())
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| GhostFlowError::ValidationError {
+                message: "node_type is required".to_string(),
+            })?;
+        let node = match &self.registry {
+            Some(registry) => registry.get_node(node_type),
+            None => runner.get_node(node_type),
+        };
+        node.ok_or_else(|| GhostFlowError::ValidationError {
+            message: format!("Unknown node type to wrap: {}", node_type),
+        })
+    }
+
+    /// Context for the wrapped node, whose input is the `parameters` object.
+    fn wrapped_context(context: &ExecutionContext) -> Result<ExecutionContext> {
+        let input = match context.input.get("parameters") {
+            None | Some(Value::Null) => json!({}),
+            Some(parameters @ Value::Object(_)) => parameters.clone(),
+            Some(_) => {
+                return Err(GhostFlowError::ValidationError {
+                    message: "parameters must be an object".to_string(),
+                })
+            }
+        };
+        Ok(ExecutionContext {
+            input,
+            ..context.clone()
+        })
+    }
+}
+
+impl Default for TryCatchNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for TryCatchNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "try_catch".to_string(),
+            name: "Try / Catch".to_string(),
+            description: "Run a node and pass its error on as data instead of failing the flow".to_string(),
+            category: NodeCategory::ControlFlow,
+            version: "1.0.0".to_string(),
+            inputs: vec![],
+            outputs: vec![
+                NodePort {
+                    name: "result".to_string(),
+                    display_name: "Result".to_string(),
+                    description: Some("Output of the wrapped node; null when it failed".to_string()),
+                    data_type: DataType::Any,
+                    required: false,
+                },
+                NodePort {
+                    name: "error".to_string(),
+                    display_name: "Error".to_string(),
+                    description: Some("code, message and node_id when the wrapped node failed".to_string()),
+                    data_type: DataType::Object,
+                    required: false,
+                },
+                NodePort {
+                    name: "success".to_string(),
+                    display_name: "Success".to_string(),
+                    description: Some("Whether the wrapped node succeeded".to_string()),
+                    data_type: DataType::Boolean,
+                    required: true,
+                },
+            ],
+            parameters: vec![
+                NodeParameter {
+                    name: "node_type".to_string(),
+                    display_name: "Node Type".to_string(),
+                    description: Some("Type of the node to run, e.g. http_request".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "parameters".to_string(),
+                    display_name: "Parameters".to_string(),
+                    description: Some("Parameters for the wrapped node".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: Some(json!({})),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("shield".to_string()),
+            color: Some("#f59e0b".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        self.wrapped(self.runner()?.as_ref(), &context.input)?;
+        Self::wrapped_context(context).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let runner = self.runner()?;
+        let node = self.wrapped(runner.as_ref(), &context.input)?;
+        let inner = Self::wrapped_context(&context)?;
+
+        match runner.run_node(node.as_ref(), inner).await {
+            Ok(result) => Ok(json!({ "success": true, "result": result, "error": null })),
+            // Cancellation stops the flow; it is not the wrapped node's failure
+            Err(error @ GhostFlowError::Cancelled { .. }) => Err(error),
+            Err(error) => Ok(json!({
+                "success": false,
+                "result": null,
+                "error": error.error_object(&context.node_id),
+            })),
+        }
+    }
+
+    fn supports_retry(&self) -> bool {
+        false
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false // Depends on the wrapped node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use crate::test_support::TestEngine;
+    use crate::{AggregateNode, HttpRequestNode};
+    use ghostflow_core::BasicNodeRegistry;
+
+    fn engine() -> Arc<TestEngine> {
+        let mut registry = BasicNodeRegistry::new();
+        registry.register_node("http_request".to_string(), Arc::new(HttpRequestNode::new())).unwrap();
+        registry.register_node("aggregate".to_string(), Arc::new(AggregateNode)).unwrap();
+        TestEngine::new(Arc::new(registry))
+    }
+
+    fn try_catch(engine: &Arc<TestEngine>) -> TryCatchNode {
+        TryCatchNode::new().with_services(engine.services())
+    }
+
+    #[tokio::test]
+    async fn test_failure_becomes_error_output() {
+        let engine = engine();
+        let output = try_catch(&engine)
+            .execute(context(json!({
+                "node_type": "http_request",
+                "parameters": { "url": "http://127.0.0.1:1/health", "max_retries": 0, "timeout": 2 },
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["success"], false);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"]["code"], "network_error");
+        assert_eq!(output["error"]["node_id"], "node");
+    }
+
+    #[tokio::test]
+    async fn test_success_passes_result_through() {
+        let engine = engine();
+        let output = try_catch(&engine)
+            .execute(context(json!({
+                "node_type": "aggregate",
+                "parameters": { "items": [{ "n": 1 }, { "n": 2 }], "operation": "sum", "field": "n" },
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["success"], true);
+        assert_eq!(output["result"]["result"], 3.0);
+        assert_eq!(output["error"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_node_type_fails_validation() {
+        let engine = engine();
+        let node = try_catch(&engine);
+        assert!(node.validate(&context(json!({ "node_type": "teleport" }))).await.is_err());
+        assert!(node.validate(&context(json!({}))).await.is_err());
+        assert!(node
+            .validate(&context(json!({ "node_type": "aggregate", "parameters": [] })))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_nothing_runs_outside_an_engine() {
+        let input = json!({ "node_type": "aggregate", "parameters": { "items": [], "operation": "count" } });
+        let error = TryCatchNode::new().execute(context(input)).await.unwrap_err();
+        assert!(matches!(error, GhostFlowError::ConfigurationError { .. }), "{}", error);
+    }
+}
@@ -0,0 +1,262 @@
+use async_trait::async_trait;
+use ghostflow_core::{FlowVariableStore, GhostFlowError, Node, Result};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::Value;
+use std::sync::Arc;
+
+fn variable_name(context: &ExecutionContext) -> Result<&str> {
+    let name = context
+        .input
+        .get("name")
+        .and_then(|v| v.as_str())
+        .filter(|name| !name.trim().is_empty())
+        .ok_or_else(|| GhostFlowError::ValidationError {
+            message: "Variable name is required".to_string(),
+        })?;
+    // "input" already carries the flow's trigger payload
+    if name == "input" {
+        return Err(GhostFlowError::ValidationError {
+            message: "Variable name 'input' is reserved".to_string(),
+        });
+    }
+    Ok(name)
+}
+
+fn name_parameter() -> NodeParameter {
+    NodeParameter {
+        name: "name".to_string(),
+        display_name: "Name".to_string(),
+        description: Some("Variable name, scoped to the current execution".to_string()),
+        param_type: ParameterType::String,
+        default_value: None,
+        required: true,
+        options: None,
+        validation: None,
+    }
+}
+
+/// Store a value in the execution's flow-scoped state so any later node can
+/// read it, whether or not an edge connects them.
+pub struct SetVariableNode {
+    variables: Arc<FlowVariableStore>,
+}
+
+impl SetVariableNode {
+    pub fn new() -> Self {
+        Self {
+            variables: Arc::new(FlowVariableStore::new()),
+        }
+    }
+
+    /// Write to `variables`, the store the executor reads from
+    pub fn with_variables(mut self, variables: Arc<FlowVariableStore>) -> Self {
+        self.variables = variables;
+        self
+    }
+}
+
+impl Default for SetVariableNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for SetVariableNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "set_variable".to_string(),
+            name: "Set Variable".to_string(),
+            description: "Store a value in flow-scoped state for later nodes".to_string(),
+            category: NodeCategory::Data,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "value".to_string(),
+                display_name: "Value".to_string(),
+                description: Some("Value to store".to_string()),
+                data_type: DataType::Any,
+                required: false,
+            }],
+            outputs: vec![NodePort {
+                name: "value".to_string(),
+                display_name: "Value".to_string(),
+                description: Some("The stored value, passed through".to_string()),
+                data_type: DataType::Any,
+                required: true,
+            }],
+            parameters: vec![
+                name_parameter(),
+                NodeParameter {
+                    name: "value".to_string(),
+                    display_name: "Value".to_string(),
+                    description: Some("Value to store; null when omitted".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("save".to_string()),
+            color: Some("#0ea5e9".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        variable_name(context).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let name = variable_name(&context)?.to_string();
+        let value = context.input.get("value").cloned().unwrap_or(Value::Null);
+
+        self.variables.set(context.execution_id, name.clone(), value.clone());
+
+        Ok(serde_json::json!({ "name": name, "value": value }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        true
+    }
+}
+
+/// Read a value previously stored with [`SetVariableNode`] in the same
+/// execution.
+pub struct GetVariableNode {
+    variables: Arc<FlowVariableStore>,
+}
+
+impl GetVariableNode {
+    pub fn new() -> Self {
+        Self {
+            variables: Arc::new(FlowVariableStore::new()),
+        }
+    }
+
+    /// Read from `variables`, the store the executor writes to
+    pub fn with_variables(mut self, variables: Arc<FlowVariableStore>) -> Self {
+        self.variables = variables;
+        self
+    }
+}
+
+impl Default for GetVariableNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for GetVariableNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "get_variable".to_string(),
+            name: "Get Variable".to_string(),
+            description: "Read a value from flow-scoped state".to_string(),
+            category: NodeCategory::Data,
+            version: "1.0.0".to_string(),
+            inputs: vec![],
+            outputs: vec![NodePort {
+                name: "value".to_string(),
+                display_name: "Value".to_string(),
+                description: Some("The stored value".to_string()),
+                data_type: DataType::Any,
+                required: true,
+            }],
+            parameters: vec![
+                name_parameter(),
+                NodeParameter {
+                    name: "default_value".to_string(),
+                    display_name: "Default Value".to_string(),
+                    description: Some("Returned when the variable has not been set".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("download".to_string()),
+            color: Some("#0ea5e9".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        variable_name(context).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let name = variable_name(&context)?;
+
+        let value = self
+            .variables
+            .get(context.execution_id, name)
+            .or_else(|| context.variables.get(name).cloned())
+            .or_else(|| context.input.get("default_value").filter(|v| !v.is_null()).cloned())
+            .ok_or_else(|| GhostFlowError::NotFoundError {
+                resource_type: "variable".to_string(),
+                id: name.to_string(),
+            })?;
+
+        Ok(serde_json::json!({ "name": name, "value": value }))
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn context(execution_id: Uuid, input: Value) -> ExecutionContext {
+        ExecutionContext {
+            execution_id,
+            ..crate::test_support::context(input)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_reads_value_set_in_same_execution() {
+        let execution_id = Uuid::new_v4();
+        let variables = Arc::new(FlowVariableStore::new());
+        SetVariableNode::new()
+            .with_variables(variables.clone())
+            .execute(context(execution_id, json!({ "name": "vmid", "value": 101 })))
+            .await
+            .unwrap();
+
+        let get = GetVariableNode::new().with_variables(variables);
+        let output = get
+            .execute(context(execution_id, json!({ "name": "vmid" })))
+            .await
+            .unwrap();
+        assert_eq!(output["value"], 101);
+
+        let other = get.execute(context(Uuid::new_v4(), json!({ "name": "vmid" }))).await;
+        assert!(matches!(other, Err(GhostFlowError::NotFoundError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_get_falls_back_to_default() {
+        let output = GetVariableNode::new()
+            .execute(context(Uuid::new_v4(), json!({ "name": "missing", "default_value": "none" })))
+            .await
+            .unwrap();
+        assert_eq!(output["value"], "none");
+    }
+
+    #[tokio::test]
+    async fn test_reserved_name_is_rejected() {
+        let result = SetVariableNode::new()
+            .validate(&context(Uuid::new_v4(), json!({ "name": "input", "value": 1 })))
+            .await;
+        assert!(matches!(result, Err(GhostFlowError::ValidationError { .. })));
+    }
+}
@@ -0,0 +1,324 @@
+use async_trait::async_trait;
+use ghostflow_core::{GhostFlowError, Node, Result, VectorEntry, VectorIndexStore};
+use ghostflow_schema::node::ParameterType;
+use ghostflow_schema::{
+    DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::info;
+
+const DEFAULT_TOP_K: u64 = 5;
+
+/// Store embeddings in a named [`VectorIndexStore`] index and retrieve the
+/// entries nearest to a query vector by cosine similarity.
+pub struct VectorSearchNode {
+    indexes: Arc<VectorIndexStore>,
+}
+
+impl VectorSearchNode {
+    pub fn new() -> Self {
+        Self {
+            indexes: Arc::new(VectorIndexStore::new()),
+        }
+    }
+
+    /// Keep indexes in `indexes`
+    pub fn with_vector_indexes(mut self, indexes: Arc<VectorIndexStore>) -> Self {
+        self.indexes = indexes;
+        self
+    }
+
+    fn index(params: &Value) -> Result<&str> {
+        params
+            .get("index")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| GhostFlowError::ValidationError {
+                message: "Index name is required".to_string(),
+            })
+    }
+
+    /// `items` as produced by the embeddings node
+    fn entries(params: &Value) -> Result<Vec<VectorEntry>> {
+        let items = params
+            .get("items")
+            .cloned()
+            .ok_or_else(|| GhostFlowError::ValidationError {
+                message: "Upsert requires items with an id and an embedding".to_string(),
+            })?;
+        serde_json::from_value(items).map_err(|e| GhostFlowError::ValidationError {
+            message: format!("Invalid vector items: {}", e),
+        })
+    }
+
+    fn query_vector(params: &Value) -> Result<Vec<f32>> {
+        let embedding = params.get("embedding").cloned().unwrap_or(Value::Null);
+        serde_json::from_value::<Vec<f32>>(embedding)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| GhostFlowError::ValidationError {
+                message: "Query requires an embedding vector".to_string(),
+            })
+    }
+}
+
+impl Default for VectorSearchNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for VectorSearchNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "vector_search".to_string(),
+            name: "Vector Search".to_string(),
+            description: "Store embeddings and find the most similar ones".to_string(),
+            category: NodeCategory::Ai,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "input".to_string(),
+                display_name: "Input".to_string(),
+                description: Some("Items to store, or the embedding to search with".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            outputs: vec![NodePort {
+                name: "matches".to_string(),
+                display_name: "Matches".to_string(),
+                description: Some("Nearest entries with their similarity score, best first".to_string()),
+                data_type: DataType::Array,
+                required: false,
+            }],
+            parameters: vec![
+                NodeParameter {
+                    name: "index".to_string(),
+                    display_name: "Index".to_string(),
+                    description: Some("Name of the vector index, shared across flows".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String("default".to_string())),
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "operation".to_string(),
+                    display_name: "Operation".to_string(),
+                    description: Some("Store items, search with an embedding, or delete ids".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("query".to_string())),
+                    required: true,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "upsert", "label": "Upsert"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "query", "label": "Query"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "delete", "label": "Delete"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "items".to_string(),
+                    display_name: "Items".to_string(),
+                    description: Some("{id, embedding, text, metadata} entries to store".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "embedding".to_string(),
+                    display_name: "Query Embedding".to_string(),
+                    description: Some("Vector to search with".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "top_k".to_string(),
+                    display_name: "Top K".to_string(),
+                    description: Some("Number of matches to return".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::Number(serde_json::Number::from(DEFAULT_TOP_K))),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "min_score".to_string(),
+                    display_name: "Minimum Score".to_string(),
+                    description: Some("Leave out matches less similar than this (-1 to 1)".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "ids".to_string(),
+                    display_name: "IDs".to_string(),
+                    description: Some("Entries to delete".to_string()),
+                    param_type: ParameterType::Array,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("search".to_string()),
+            color: Some("#8b5cf6".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        let params = &context.input;
+        Self::index(params)?;
+        match params.get("operation").and_then(|v| v.as_str()).unwrap_or("query") {
+            "upsert" => Self::entries(params).map(|_| ()),
+            "query" => Self::query_vector(params).map(|_| ()),
+            "delete" => match params.get("ids").and_then(|v| v.as_array()) {
+                Some(_) => Ok(()),
+                None => Err(GhostFlowError::ValidationError {
+                    message: "Delete requires a list of ids".to_string(),
+                }),
+            },
+            other => Err(GhostFlowError::ValidationError {
+                message: format!("Unsupported operation: {}", other),
+            }),
+        }
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<Value> {
+        let params = &context.input;
+        let index = Self::index(params)?;
+        let store = &self.indexes;
+
+        match params.get("operation").and_then(|v| v.as_str()).unwrap_or("query") {
+            "upsert" => {
+                let entries = Self::entries(params)?;
+                let upserted = entries.len();
+                let size = store.upsert(index, entries)?;
+                info!("Stored {} vectors in index '{}'", upserted, index);
+                Ok(serde_json::json!({ "index": index, "upserted": upserted, "size": size }))
+            }
+            "delete" => {
+                let ids: Vec<String> = params
+                    .get("ids")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|id| id.as_str().map(str::to_string))
+                    .collect();
+                let deleted = store.remove(index, &ids);
+                Ok(serde_json::json!({ "index": index, "deleted": deleted, "size": store.len(index) }))
+            }
+            _ => {
+                let vector = Self::query_vector(params)?;
+                let top_k = params.get("top_k").and_then(|v| v.as_u64()).unwrap_or(DEFAULT_TOP_K) as usize;
+                let min_score = params.get("min_score").and_then(|v| v.as_f64()).map(|s| s as f32);
+
+                let mut matches = store.query(index, &vector, top_k)?;
+                if let Some(min_score) = min_score {
+                    matches.retain(|m| m.score >= min_score);
+                }
+                Ok(serde_json::json!({ "index": index, "matches": matches }))
+            }
+        }
+    }
+
+    fn supports_retry(&self) -> bool {
+        true
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false // Results depend on what the index holds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use crate::OllamaEmbeddingsNode;
+    use uuid::Uuid;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Ollama stand-in with a fixed embedding per text
+    async fn embedding_server(embeddings: &[(&str, [f32; 3])]) -> MockServer {
+        let server = MockServer::start().await;
+        for (text, embedding) in embeddings {
+            Mock::given(method("POST"))
+                .and(path("/api/embeddings"))
+                .and(body_partial_json(serde_json::json!({"prompt": text})))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"embedding": embedding})))
+                .mount(&server)
+                .await;
+        }
+        server
+    }
+
+    #[tokio::test]
+    async fn test_embedded_texts_retrieve_their_nearest_neighbour() {
+        let server = embedding_server(&[
+            ("Restart the nginx service", [0.9, 0.1, 0.0]),
+            ("Rotate the database password", [0.0, 0.2, 0.9]),
+            ("Renew the TLS certificate", [0.1, 0.9, 0.2]),
+            ("web server is down", [0.8, 0.2, 0.1]),
+        ])
+        .await;
+        let embeddings = OllamaEmbeddingsNode::with_base_url(server.uri());
+        let search = VectorSearchNode::new();
+        let index = format!("runbooks-{}", Uuid::new_v4());
+
+        let documents = embeddings
+            .execute(context(serde_json::json!({
+                "texts": [
+                    {"id": "nginx", "text": "Restart the nginx service", "metadata": {"team": "web"}},
+                    {"id": "db", "text": "Rotate the database password"},
+                    "Renew the TLS certificate",
+                ],
+            })))
+            .await
+            .unwrap();
+        assert_eq!(documents["dimension"], 3);
+
+        let stored = search
+            .execute(context(serde_json::json!({
+                "index": index,
+                "operation": "upsert",
+                "items": documents["items"],
+            })))
+            .await
+            .unwrap();
+        assert_eq!(stored["size"], 3);
+
+        let query = embeddings
+            .execute(context(serde_json::json!({"text": "web server is down"})))
+            .await
+            .unwrap();
+        let ctx = context(serde_json::json!({
+            "index": index,
+            "embedding": query["embeddings"],
+            "top_k": 1,
+        }));
+        search.validate(&ctx).await.unwrap();
+        let result = search.execute(ctx).await.unwrap();
+
+        let matches = result["matches"].as_array().unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0]["id"], "nginx");
+        assert_eq!(matches[0]["text"], "Restart the nginx service");
+        assert_eq!(matches[0]["metadata"]["team"], "web");
+        assert!(matches[0]["score"].as_f64().unwrap() > 0.9);
+    }
+
+    #[tokio::test]
+    async fn test_query_requires_an_embedding() {
+        let ctx = context(serde_json::json!({"index": "docs", "operation": "query"}));
+        assert!(VectorSearchNode::new().validate(&ctx).await.is_err());
+    }
+}
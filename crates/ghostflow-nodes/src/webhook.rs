@@ -1,11 +1,234 @@
 use async_trait::async_trait;
-use ghostflow_core::{GhostFlowError, Node, Result};
+use ghostflow_core::{
+    CircuitBreakerRegistry, GhostFlowError, Node, ReplayProtection, Result, WebhookResponse, WebhookResponseRegistry,
+};
 use ghostflow_schema::{
     DataType, ExecutionContext, NodeCategory, NodeDefinition, NodeParameter, NodePort,
 };
 use ghostflow_schema::node::ParameterType;
+use hmac::{Hmac, Mac};
 use serde_json::Value;
-use tracing::info;
+use sha1::Sha1;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::http_util::{request_with_policy, RequestPolicy};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureAlgorithm {
+    Sha1,
+    Sha256,
+}
+
+/// How the signature header is laid out and what is signed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureFormat {
+    /// Hex digest of the body, optionally prefixed as in `sha256=…` (GitHub)
+    Hex,
+    /// `t=<unix time>,v1=<hex>` over `<t>.<body>` (Stripe)
+    Stripe,
+    /// `v0=<hex>` over `v0:<timestamp>:<body>`, with the timestamp in
+    /// `X-Slack-Request-Timestamp` (Slack)
+    Slack,
+}
+
+impl SignatureFormat {
+    fn default_header(self) -> &'static str {
+        match self {
+            SignatureFormat::Hex => "X-Hub-Signature-256",
+            SignatureFormat::Stripe => "Stripe-Signature",
+            SignatureFormat::Slack => "X-Slack-Signature",
+        }
+    }
+}
+
+/// Header carrying the request time for [`SignatureFormat::Slack`]
+pub const SLACK_TIMESTAMP_HEADER: &str = "X-Slack-Request-Timestamp";
+
+/// How far a signed timestamp may be from the current time
+pub const DEFAULT_SIGNATURE_TOLERANCE: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// HMAC signing settings for inbound webhooks (GitHub, Stripe, Slack style)
+#[derive(Debug, Clone)]
+pub struct SignatureConfig {
+    pub secret: String,
+    pub header: String,
+    pub algorithm: SignatureAlgorithm,
+    pub format: SignatureFormat,
+    /// Timestamped formats reject signatures older or newer than this
+    pub tolerance: std::time::Duration,
+}
+
+impl SignatureConfig {
+    /// Settings of a webhook trigger node, or `None` when it does not use
+    /// HMAC authentication.
+    pub fn for_trigger(params: &Value) -> Result<Option<Self>> {
+        if params.get("authentication").and_then(|v| v.as_str()) == Some("hmac") {
+            Self::from_params(params).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn from_params(params: &Value) -> Result<Self> {
+        let secret = params
+            .get("secret")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| GhostFlowError::ValidationError {
+                message: "HMAC authentication requires a webhook secret".to_string(),
+            })?;
+
+        let format = match params.get("signature_format").and_then(|v| v.as_str()).unwrap_or("hex") {
+            "hex" => SignatureFormat::Hex,
+            "stripe" => SignatureFormat::Stripe,
+            "slack" => SignatureFormat::Slack,
+            other => {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Unsupported signature format: {}", other),
+                });
+            }
+        };
+
+        let header = params
+            .get("signature_header")
+            .and_then(|v| v.as_str())
+            .filter(|h| !h.is_empty())
+            .unwrap_or(format.default_header());
+
+        let algorithm = match params.get("signature_algorithm").and_then(|v| v.as_str()).unwrap_or("sha256") {
+            "sha256" => SignatureAlgorithm::Sha256,
+            "sha1" => SignatureAlgorithm::Sha1,
+            other => {
+                return Err(GhostFlowError::ValidationError {
+                    message: format!("Unsupported signature algorithm: {}", other),
+                });
+            }
+        };
+
+        if format != SignatureFormat::Hex && algorithm != SignatureAlgorithm::Sha256 {
+            return Err(GhostFlowError::ValidationError {
+                message: "Stripe and Slack signatures use HMAC-SHA256".to_string(),
+            });
+        }
+
+        let tolerance = params
+            .get("signature_tolerance_secs")
+            .and_then(|v| v.as_u64())
+            .map(std::time::Duration::from_secs)
+            .unwrap_or(DEFAULT_SIGNATURE_TOLERANCE);
+
+        Ok(Self {
+            secret: secret.to_string(),
+            header: header.to_string(),
+            algorithm,
+            format,
+            tolerance,
+        })
+    }
+}
+
+/// Verifies an HMAC signature over the raw request body. Call this from the
+/// HTTP handler before triggering the flow; an `AuthenticationError` should be
+/// answered with 401. Digest comparisons are constant-time.
+pub fn verify_signature(
+    config: &SignatureConfig,
+    headers: &HashMap<String, String>,
+    raw_body: &[u8],
+) -> Result<()> {
+    verify_signature_at(config, headers, raw_body, chrono::Utc::now().timestamp())
+}
+
+fn verify_signature_at(
+    config: &SignatureConfig,
+    headers: &HashMap<String, String>,
+    raw_body: &[u8],
+    now: i64,
+) -> Result<()> {
+    let header_value = header(headers, &config.header)?;
+
+    let (timestamp, digests, signed) = match config.format {
+        SignatureFormat::Hex => {
+            let digest = header_value.split_once('=').map(|(_, digest)| digest).unwrap_or(header_value);
+            (None, vec![digest], raw_body.to_vec())
+        }
+        SignatureFormat::Stripe => {
+            let mut timestamp = None;
+            let mut digests = vec![];
+            for (key, value) in header_value.split(',').filter_map(|part| part.trim().split_once('=')) {
+                match key {
+                    "t" => timestamp = Some(value),
+                    "v1" => digests.push(value),
+                    _ => {}
+                }
+            }
+            let timestamp = timestamp.ok_or_else(invalid_signature)?;
+            (Some(timestamp), digests, [timestamp.as_bytes(), b".", raw_body].concat())
+        }
+        SignatureFormat::Slack => {
+            let digest = header_value.strip_prefix("v0=").ok_or_else(invalid_signature)?;
+            let timestamp = header(headers, SLACK_TIMESTAMP_HEADER)?;
+            (Some(timestamp), vec![digest], [b"v0:", timestamp.as_bytes(), b":", raw_body].concat())
+        }
+    };
+
+    if let Some(timestamp) = timestamp {
+        let timestamp: i64 = timestamp.parse().map_err(|_| invalid_signature())?;
+        if now.abs_diff(timestamp) > config.tolerance.as_secs() {
+            warn!("Rejected webhook signed {}s away from now", now - timestamp);
+            return Err(GhostFlowError::AuthenticationError {
+                message: "Webhook signature timestamp is outside the tolerance".to_string(),
+            });
+        }
+    }
+
+    let verified = digests
+        .into_iter()
+        .filter_map(|digest| hex::decode(digest).ok())
+        .any(|signature| hmac_matches(config, &signed, &signature));
+
+    if verified {
+        Ok(())
+    } else {
+        Err(invalid_signature())
+    }
+}
+
+fn header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Result<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+        .ok_or_else(|| GhostFlowError::AuthenticationError {
+            message: format!("Missing signature header {}", name),
+        })
+}
+
+fn invalid_signature() -> GhostFlowError {
+    warn!("Rejected webhook with invalid signature");
+    GhostFlowError::AuthenticationError {
+        message: "Invalid webhook signature".to_string(),
+    }
+}
+
+fn hmac_matches(config: &SignatureConfig, signed: &[u8], signature: &[u8]) -> bool {
+    match config.algorithm {
+        SignatureAlgorithm::Sha256 => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(config.secret.as_bytes())
+                .expect("HMAC accepts keys of any length");
+            mac.update(signed);
+            mac.verify_slice(signature).is_ok()
+        }
+        SignatureAlgorithm::Sha1 => {
+            let mut mac = Hmac::<Sha1>::new_from_slice(config.secret.as_bytes())
+                .expect("HMAC accepts keys of any length");
+            mac.update(signed);
+            mac.verify_slice(signature).is_ok()
+        }
+    }
+}
 
 pub struct WebhookTriggerNode;
 
@@ -89,6 +312,89 @@ impl Node for WebhookTriggerNode {
                     options: None,
                     validation: None,
                 },
+                NodeParameter {
+                    name: "signature_header".to_string(),
+                    display_name: "Signature Header".to_string(),
+                    description: Some(
+                        "Header carrying the HMAC signature; defaults to X-Hub-Signature-256, Stripe-Signature or X-Slack-Signature by format"
+                            .to_string(),
+                    ),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "signature_algorithm".to_string(),
+                    display_name: "Signature Algorithm".to_string(),
+                    description: Some("HMAC digest algorithm".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("sha256".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "sha256", "label": "HMAC-SHA256"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "sha1", "label": "HMAC-SHA1"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "signature_format".to_string(),
+                    display_name: "Signature Format".to_string(),
+                    description: Some("Layout of the signature header and what it signs".to_string()),
+                    param_type: ParameterType::Select,
+                    default_value: Some(Value::String("hex".to_string())),
+                    required: false,
+                    options: Some(vec![
+                        serde_json::from_str(r#"{"value": "hex", "label": "Hex digest (GitHub)"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "stripe", "label": "Stripe (t=…,v1=…)"}"#).unwrap(),
+                        serde_json::from_str(r#"{"value": "slack", "label": "Slack (v0:timestamp:body)"}"#).unwrap(),
+                    ]),
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "signature_tolerance_secs".to_string(),
+                    display_name: "Signature Tolerance (seconds)".to_string(),
+                    description: Some("Reject Stripe and Slack signatures timestamped further than this from now".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(DEFAULT_SIGNATURE_TOLERANCE.as_secs())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "delivery_id_header".to_string(),
+                    display_name: "Delivery ID Header".to_string(),
+                    description: Some(
+                        "Header with a per-delivery id (e.g. X-GitHub-Delivery); repeats are acknowledged but not run"
+                            .to_string(),
+                    ),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "delivery_id_field".to_string(),
+                    display_name: "Delivery ID Field".to_string(),
+                    description: Some("Body field with the delivery id, as a dotted path, when there is no header".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "replay_window_secs".to_string(),
+                    display_name: "Replay Window (seconds)".to_string(),
+                    description: Some("How long a delivery id is remembered".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(ghostflow_core::DEFAULT_REPLAY_WINDOW.as_secs())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
             ],
             icon: Some("webhook".to_string()),
             color: Some("#f97316".to_string()),
@@ -117,6 +423,11 @@ impl Node for WebhookTriggerNode {
             });
         }
 
+        if params.get("authentication").and_then(|v| v.as_str()) == Some("hmac") {
+            SignatureConfig::from_params(params)?;
+        }
+        ReplayProtection::from_params(params)?;
+
         Ok(())
     }
 
@@ -125,6 +436,22 @@ impl Node for WebhookTriggerNode {
         // the webhook data from the HTTP request
         
         let webhook_data = context.input.clone();
+
+        if webhook_data.get("authentication").and_then(|v| v.as_str()) == Some("hmac") {
+            let config = SignatureConfig::from_params(&webhook_data)?;
+            let headers: HashMap<String, String> = webhook_data
+                .get("headers")
+                .and_then(|v| v.as_object())
+                .map(|obj| {
+                    obj.iter()
+                        .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let raw_body = webhook_data.get("raw_body").and_then(|v| v.as_str()).unwrap_or("");
+
+            verify_signature(&config, &headers, raw_body.as_bytes())?;
+        }
         
         info!("Processing webhook trigger with data");
 
@@ -139,4 +466,628 @@ impl Node for WebhookTriggerNode {
     fn is_deterministic(&self) -> bool {
         false // Webhook data can vary
     }
-}
\ No newline at end of file
+}
+
+/// Shapes the HTTP response returned to the caller of a webhook-triggered
+/// flow. Nodes after it keep running once the response is sent.
+pub struct RespondToWebhookNode {
+    webhook_responses: Arc<WebhookResponseRegistry>,
+}
+
+impl RespondToWebhookNode {
+    pub fn new() -> Self {
+        Self {
+            webhook_responses: Arc::new(WebhookResponseRegistry::new()),
+        }
+    }
+
+    /// Deliver responses to the callers waiting in `webhook_responses`
+    pub fn with_webhook_responses(mut self, webhook_responses: Arc<WebhookResponseRegistry>) -> Self {
+        self.webhook_responses = webhook_responses;
+        self
+    }
+
+    fn response_from_params(params: &Value) -> Result<WebhookResponse> {
+        let status = match params.get("status_code") {
+            None | Some(Value::Null) => 200,
+            Some(value) => value
+                .as_u64()
+                .filter(|code| (100..=599).contains(code))
+                .ok_or_else(|| GhostFlowError::ValidationError {
+                    message: format!("Invalid webhook response status code: {}", value),
+                })? as u16,
+        };
+
+        let headers = match params.get("headers") {
+            None | Some(Value::Null) => HashMap::new(),
+            Some(Value::Object(map)) => map
+                .iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (name.clone(), value)
+                })
+                .collect(),
+            Some(_) => {
+                return Err(GhostFlowError::ValidationError {
+                    message: "Webhook response headers must be an object".to_string(),
+                })
+            }
+        };
+
+        Ok(WebhookResponse {
+            status,
+            headers,
+            body: params.get("body").cloned().unwrap_or(Value::Null),
+        })
+    }
+}
+
+impl Default for RespondToWebhookNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Node for RespondToWebhookNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "respond_to_webhook".to_string(),
+            name: "Respond to Webhook".to_string(),
+            description: "Set the status, headers and body returned to the webhook caller".to_string(),
+            category: NodeCategory::Action,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "body".to_string(),
+                display_name: "Body".to_string(),
+                description: Some("Response body, sent as JSON".to_string()),
+                data_type: DataType::Any,
+                required: false,
+            }],
+            outputs: vec![NodePort {
+                name: "response".to_string(),
+                display_name: "Response".to_string(),
+                description: Some("The response and whether a caller received it".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            parameters: vec![
+                NodeParameter {
+                    name: "status_code".to_string(),
+                    display_name: "Status Code".to_string(),
+                    description: Some("HTTP status code".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(200)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "headers".to_string(),
+                    display_name: "Headers".to_string(),
+                    description: Some("Response headers".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "body".to_string(),
+                    display_name: "Body".to_string(),
+                    description: Some("Response body".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("reply".to_string()),
+            color: Some("#f97316".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        Self::response_from_params(&context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<serde_json::Value> {
+        let response = Self::response_from_params(&context.input)?;
+        let responded = self.webhook_responses.respond(context.execution_id, response.clone());
+        if !responded {
+            warn!(
+                "Execution {} has no webhook caller waiting; response not sent",
+                context.execution_id
+            );
+        }
+
+        Ok(serde_json::json!({
+            "responded": responded,
+            "status": response.status,
+            "headers": response.headers,
+            "body": response.body
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false // A response can only be delivered once
+    }
+}
+
+/// Header carrying the HMAC of an outbound webhook body.
+pub const OUTBOUND_SIGNATURE_HEADER: &str = "X-GhostFlow-Signature";
+
+/// Header with an id that stays the same across retries of one delivery,
+/// so receivers can drop duplicates.
+pub const OUTBOUND_DELIVERY_HEADER: &str = "X-GhostFlow-Delivery";
+
+/// `sha256=<hex>` HMAC of `body`, in the format [`verify_signature`]
+/// accepts.
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Redirects are followed up to `max_redirects` times, never from https to
+/// plain http. reqwest drops credentials headers when the host changes.
+fn redirect_policy(max_redirects: usize) -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(move |attempt| {
+        let downgrade = attempt.url().scheme() == "http"
+            && attempt.previous().last().is_some_and(|previous| previous.scheme() == "https");
+        if downgrade {
+            let url = attempt.url().to_string();
+            attempt.error(format!("Refusing to follow redirect from https to {}", url))
+        } else if attempt.previous().len() > max_redirects {
+            attempt.error(format!("Stopped after {} redirects", max_redirects))
+        } else {
+            attempt.follow()
+        }
+    })
+}
+
+/// POSTs a JSON payload signed with HMAC-SHA256, e.g. to tell another
+/// system a flow has finished. 5xx and 429 answers are retried.
+pub struct OutboundWebhookNode {
+    breakers: Arc<CircuitBreakerRegistry>,
+}
+
+impl OutboundWebhookNode {
+    pub fn new() -> Self {
+        Self {
+            breakers: Arc::new(CircuitBreakerRegistry::new()),
+        }
+    }
+
+    /// Back off from failing hosts using `breakers`
+    pub fn with_circuit_breakers(mut self, breakers: Arc<CircuitBreakerRegistry>) -> Self {
+        self.breakers = breakers;
+        self
+    }
+}
+
+impl Default for OutboundWebhookNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct OutboundWebhook<'a> {
+    url: &'a str,
+    secret: &'a str,
+    signature_header: &'a str,
+    headers: Vec<(&'a str, String)>,
+    max_retries: u32,
+    max_redirects: usize,
+    timeout: std::time::Duration,
+}
+
+impl<'a> OutboundWebhook<'a> {
+    fn from_params(params: &'a Value) -> Result<Self> {
+        let invalid = |message: &str| GhostFlowError::ValidationError {
+            message: message.to_string(),
+        };
+
+        let url = params
+            .get("url")
+            .and_then(|v| v.as_str())
+            .filter(|u| u.starts_with("https://") || u.starts_with("http://"))
+            .ok_or_else(|| invalid("Outbound webhook needs an http(s) url"))?;
+        let secret = params
+            .get("secret")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| invalid("Outbound webhook needs a signing secret"))?;
+
+        let headers = match params.get("headers") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Object(map)) => map
+                .iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (name.as_str(), value)
+                })
+                .collect(),
+            Some(_) => return Err(invalid("Outbound webhook headers must be an object")),
+        };
+
+        Ok(Self {
+            url,
+            secret,
+            signature_header: params
+                .get("signature_header")
+                .and_then(|v| v.as_str())
+                .filter(|h| !h.is_empty())
+                .unwrap_or(OUTBOUND_SIGNATURE_HEADER),
+            headers,
+            max_retries: params.get("max_retries").and_then(|v| v.as_u64()).unwrap_or(3) as u32,
+            max_redirects: params.get("max_redirects").and_then(|v| v.as_u64()).unwrap_or(5) as usize,
+            timeout: std::time::Duration::from_secs(
+                params.get("timeout_seconds").and_then(|v| v.as_u64()).unwrap_or(30),
+            ),
+        })
+    }
+}
+
+#[async_trait]
+impl Node for OutboundWebhookNode {
+    fn definition(&self) -> NodeDefinition {
+        NodeDefinition {
+            id: "outbound_webhook".to_string(),
+            name: "Outbound Webhook".to_string(),
+            description: "POST a JSON payload with an HMAC-SHA256 signature header".to_string(),
+            category: NodeCategory::Action,
+            version: "1.0.0".to_string(),
+            inputs: vec![NodePort {
+                name: "payload".to_string(),
+                display_name: "Payload".to_string(),
+                description: Some("JSON body to send".to_string()),
+                data_type: DataType::Any,
+                required: false,
+            }],
+            outputs: vec![NodePort {
+                name: "response".to_string(),
+                display_name: "Response".to_string(),
+                description: Some("Status and body returned by the receiver".to_string()),
+                data_type: DataType::Object,
+                required: true,
+            }],
+            parameters: vec![
+                NodeParameter {
+                    name: "url".to_string(),
+                    display_name: "URL".to_string(),
+                    description: Some("Endpoint to POST to".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "secret".to_string(),
+                    display_name: "Signing Secret".to_string(),
+                    description: Some("Shared secret the receiver uses to verify the signature".to_string()),
+                    param_type: ParameterType::Secret,
+                    default_value: None,
+                    required: true,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "signature_header".to_string(),
+                    display_name: "Signature Header".to_string(),
+                    description: Some("Header carrying sha256=<hex digest of the body>".to_string()),
+                    param_type: ParameterType::String,
+                    default_value: Some(Value::String(OUTBOUND_SIGNATURE_HEADER.to_string())),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "headers".to_string(),
+                    display_name: "Headers".to_string(),
+                    description: Some("Extra request headers".to_string()),
+                    param_type: ParameterType::Object,
+                    default_value: None,
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_retries".to_string(),
+                    display_name: "Max Retries".to_string(),
+                    description: Some("Retries after a 5xx, 429 or connection failure".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(3)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "max_redirects".to_string(),
+                    display_name: "Max Redirects".to_string(),
+                    description: Some("Redirects to follow; https is never downgraded to http".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(5)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+                NodeParameter {
+                    name: "timeout_seconds".to_string(),
+                    display_name: "Timeout (seconds)".to_string(),
+                    description: Some("Per-attempt timeout".to_string()),
+                    param_type: ParameterType::Number,
+                    default_value: Some(Value::from(30)),
+                    required: false,
+                    options: None,
+                    validation: None,
+                },
+            ],
+            icon: Some("send".to_string()),
+            color: Some("#f97316".to_string()),
+        }
+    }
+
+    async fn validate(&self, context: &ExecutionContext) -> Result<()> {
+        OutboundWebhook::from_params(&context.input).map(|_| ())
+    }
+
+    async fn execute(&self, context: ExecutionContext) -> Result<serde_json::Value> {
+        let webhook = OutboundWebhook::from_params(&context.input)?;
+        let payload = context.input.get("payload").cloned().unwrap_or(Value::Null);
+        // Sign the exact bytes that are sent
+        let body = serde_json::to_vec(&payload)?;
+        let signature = sign_payload(webhook.secret, &body);
+        let delivery_id = uuid::Uuid::new_v4().to_string();
+
+        let client = reqwest::Client::builder()
+            .redirect(redirect_policy(webhook.max_redirects))
+            .build()
+            .map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+        let mut request = client
+            .post(webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(webhook.signature_header, &signature)
+            .header(OUTBOUND_DELIVERY_HEADER, &delivery_id);
+        for (name, value) in &webhook.headers {
+            request = request.header(*name, value);
+        }
+
+        let policy = RequestPolicy {
+            max_retries: webhook.max_retries,
+            timeout: webhook.timeout,
+            ..RequestPolicy::default()
+        };
+        let response = request_with_policy(request.body(body), &policy, &self.breakers).await?;
+
+        let status = response.status();
+        let text = response.text().await.map_err(|e| GhostFlowError::NetworkError(e.to_string()))?;
+        if !status.is_success() {
+            return Err(GhostFlowError::NetworkError(format!(
+                "Webhook endpoint {} answered {}: {}",
+                webhook.url, status, text
+            )));
+        }
+        info!("Delivered outbound webhook {} to {}", delivery_id, webhook.url);
+
+        Ok(serde_json::json!({
+            "status": status.as_u16(),
+            "delivery_id": delivery_id,
+            "signature": signature,
+            "body": serde_json::from_str::<Value>(&text).unwrap_or(Value::String(text)),
+        }))
+    }
+
+    fn supports_retry(&self) -> bool {
+        false // Retries happen per request; a node retry would resend a delivered payload
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::context;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const BODY: &[u8] = br#"{"action":"opened","number":42}"#;
+
+    fn config(algorithm: SignatureAlgorithm) -> SignatureConfig {
+        SignatureConfig {
+            secret: "It's a Secret to Everybody".to_string(),
+            header: "X-Hub-Signature-256".to_string(),
+            algorithm,
+            format: SignatureFormat::Hex,
+            tolerance: DEFAULT_SIGNATURE_TOLERANCE,
+        }
+    }
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn test_valid_signature_is_accepted() {
+        let headers = HashMap::from([
+            ("x-hub-signature-256".to_string(), sign("It's a Secret to Everybody", BODY)),
+        ]);
+
+        assert!(verify_signature(&config(SignatureAlgorithm::Sha256), &headers, BODY).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_signature_is_rejected() {
+        let headers = HashMap::from([
+            ("X-Hub-Signature-256".to_string(), sign("wrong secret", BODY)),
+        ]);
+
+        let result = verify_signature(&config(SignatureAlgorithm::Sha256), &headers, BODY);
+        assert!(matches!(result, Err(GhostFlowError::AuthenticationError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_outbound_signature_matches_recomputed_digest() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hooks/ghostflow"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "received": true })))
+            .mount(&server)
+            .await;
+
+        let output = OutboundWebhookNode::new()
+            .execute(context(serde_json::json!({
+                "url": format!("{}/hooks/ghostflow", server.uri()),
+                "secret": "It's a Secret to Everybody",
+                "payload": { "flow": "nightly-backup", "status": "completed" },
+            })))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let request = &requests[0];
+        let header = request.headers.get(OUTBOUND_SIGNATURE_HEADER).unwrap().to_str().unwrap();
+        assert_eq!(header, sign("It's a Secret to Everybody", &request.body));
+        assert_eq!(output["signature"], header);
+        assert_eq!(output["body"]["received"], true);
+
+        // The receiving side accepts it with the inbound verifier
+        let config = SignatureConfig {
+            secret: "It's a Secret to Everybody".to_string(),
+            header: OUTBOUND_SIGNATURE_HEADER.to_string(),
+            algorithm: SignatureAlgorithm::Sha256,
+            format: SignatureFormat::Hex,
+            tolerance: DEFAULT_SIGNATURE_TOLERANCE,
+        };
+        let headers = HashMap::from([(OUTBOUND_SIGNATURE_HEADER.to_string(), header.to_string())]);
+        assert!(verify_signature(&config, &headers, &request.body).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_outbound_retries_server_errors_and_follows_redirects() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/old"))
+            .respond_with(ResponseTemplate::new(308).insert_header("Location", "/new"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/new"))
+            .respond_with(ResponseTemplate::new(502))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/new"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let output = OutboundWebhookNode::new()
+            .execute(context(serde_json::json!({
+                "url": format!("{}/old", server.uri()),
+                "secret": "s3cret",
+                "payload": [1, 2, 3],
+            })))
+            .await
+            .unwrap();
+
+        assert_eq!(output["status"], 204);
+        let deliveries: Vec<_> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|r| r.url.path() == "/new")
+            .collect();
+        assert_eq!(deliveries.len(), 2);
+        // Both attempts carry the same body, signature and delivery id
+        for delivery in &deliveries {
+            assert_eq!(delivery.body, b"[1,2,3]");
+            assert_eq!(delivery.headers.get(OUTBOUND_SIGNATURE_HEADER).unwrap().to_str().unwrap(), sign("s3cret", b"[1,2,3]"));
+            assert_eq!(delivery.headers.get(OUTBOUND_DELIVERY_HEADER).unwrap().to_str().unwrap(), output["delivery_id"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_outbound_requires_url_and_secret() {
+        let node = OutboundWebhookNode::new();
+        assert!(node.validate(&context(serde_json::json!({ "url": "https://example.com/hook" }))).await.is_err());
+        assert!(node.validate(&context(serde_json::json!({ "url": "ftp://example.com", "secret": "x" }))).await.is_err());
+        assert!(node
+            .validate(&context(serde_json::json!({ "url": "https://example.com/hook", "secret": "x" })))
+            .await
+            .is_ok());
+    }
+
+    fn hex_hmac(secret: &str, signed: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(signed);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn test_stripe_signature_is_checked_with_its_timestamp() {
+        let config = SignatureConfig::from_params(&serde_json::json!({
+            "secret": "whsec_test",
+            "signature_format": "stripe",
+        }))
+        .unwrap();
+        assert_eq!(config.header, "Stripe-Signature");
+
+        let signed = [b"1700000000.".as_slice(), BODY].concat();
+        let header = format!("t=1700000000,v1={},v1=00", hex_hmac("whsec_test", &signed));
+        let headers = HashMap::from([("stripe-signature".to_string(), header)]);
+
+        assert!(verify_signature_at(&config, &headers, BODY, 1_700_000_100).is_ok());
+        assert!(verify_signature_at(&config, &headers, b"{}", 1_700_000_100).is_err());
+        assert!(matches!(
+            verify_signature_at(&config, &headers, BODY, 1_700_000_301),
+            Err(GhostFlowError::AuthenticationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_slack_signature_covers_the_request_timestamp() {
+        let config = SignatureConfig::from_params(&serde_json::json!({
+            "secret": "8f742231b10e8888abcd99yyyzzz85a5",
+            "signature_format": "slack",
+            "signature_tolerance_secs": 60,
+        }))
+        .unwrap();
+
+        let signed = [b"v0:1531420618:".as_slice(), BODY].concat();
+        let signature = format!("v0={}", hex_hmac("8f742231b10e8888abcd99yyyzzz85a5", &signed));
+        let headers = HashMap::from([
+            ("X-Slack-Signature".to_string(), signature.clone()),
+            ("X-Slack-Request-Timestamp".to_string(), "1531420618".to_string()),
+        ]);
+        assert!(verify_signature_at(&config, &headers, BODY, 1_531_420_650).is_ok());
+        assert!(verify_signature_at(&config, &headers, BODY, 1_531_420_700).is_err());
+
+        let replayed = HashMap::from([
+            ("X-Slack-Signature".to_string(), signature),
+            ("X-Slack-Request-Timestamp".to_string(), "1531420690".to_string()),
+        ]);
+        assert!(verify_signature_at(&config, &replayed, BODY, 1_531_420_700).is_err());
+    }
+
+    #[test]
+    fn test_missing_signature_header_is_rejected() {
+        let result = verify_signature(&config(SignatureAlgorithm::Sha1), &HashMap::new(), BODY);
+        assert!(matches!(result, Err(GhostFlowError::AuthenticationError { .. })));
+    }
+}
@@ -0,0 +1,99 @@
+use serde_json::{json, Value};
+
+/// Marker key of the canonical JSON form of binary data:
+/// `{"__binary__": "<base64>", "content_type": "...", "filename": "..."}`.
+pub const BINARY_KEY: &str = "__binary__";
+
+/// Binary payload carried between nodes, e.g. a downloaded file or an email
+/// attachment. On the wire it is always the canonical JSON form above.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryData {
+    pub data: Vec<u8>,
+    pub content_type: Option<String>,
+    pub filename: Option<String>,
+}
+
+impl BinaryData {
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            data: data.into(),
+            content_type: None,
+            filename: None,
+        }
+    }
+
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// Whether `value` is in the canonical binary form.
+    pub fn is_binary(value: &Value) -> bool {
+        value
+            .get(BINARY_KEY)
+            .map_or(false, |data| data.is_string())
+    }
+
+    /// Decode the canonical form. Returns `None` for any other value or when
+    /// the payload is not valid base64.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let encoded = value.get(BINARY_KEY)?.as_str()?;
+        let text = |key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+        Some(Self {
+            data: base64::decode(encoded).ok()?,
+            content_type: text("content_type"),
+            filename: text("filename"),
+        })
+    }
+
+    pub fn to_value(&self) -> Value {
+        let mut value = json!({ BINARY_KEY: base64::encode(&self.data) });
+        if let Some(content_type) = &self.content_type {
+            value["content_type"] = json!(content_type);
+        }
+        if let Some(filename) = &self.filename {
+            value["filename"] = json!(filename);
+        }
+        value
+    }
+}
+
+impl From<BinaryData> for Value {
+    fn from(binary: BinaryData) -> Self {
+        binary.to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataType;
+
+    #[test]
+    fn test_binary_round_trips_through_json() {
+        let binary = BinaryData::new(vec![0u8, 159, 146, 150, 255])
+            .with_content_type("application/octet-stream")
+            .with_filename("blob.bin");
+
+        let value = binary.to_value();
+        assert_eq!(value[BINARY_KEY], "AJ+Slv8=");
+
+        let text = serde_json::to_string(&value).unwrap();
+        let parsed: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(BinaryData::from_value(&parsed), Some(binary));
+    }
+
+    #[test]
+    fn test_data_type_binary_accepts_only_canonical_form() {
+        assert!(DataType::Binary.accepts(&BinaryData::new(b"hi".to_vec()).to_value()));
+        assert!(!DataType::Binary.accepts(&json!("aGk=")));
+        assert!(!DataType::Binary.accepts(&json!({ BINARY_KEY: 5 })));
+        assert!(DataType::Any.accepts(&json!("aGk=")));
+        assert!(!DataType::String.accepts(&json!(1)));
+    }
+}
@@ -13,6 +13,12 @@ pub struct FlowExecution {
     pub output_data: Option<serde_json::Value>,
     pub error: Option<ExecutionError>,
     pub node_executions: HashMap<String, NodeExecution>,
+    /// One record per node that ran, in the order the nodes started.
+    #[serde(default)]
+    pub node_records: Vec<NodeExecutionRecord>,
+    /// Per-node findings of a dry run; empty for real executions.
+    #[serde(default)]
+    pub validation_report: Vec<NodeValidationReport>,
     pub started_at: chrono::DateTime<chrono::Utc>,
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
     pub execution_time_ms: Option<u64>,
@@ -28,6 +34,8 @@ pub enum ExecutionStatus {
     Failed,
     Cancelled,
     Retrying,
+    /// The node did not run because none of its incoming edges were active
+    Skipped,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +43,14 @@ pub struct ExecutionTrigger {
     pub trigger_type: String,
     pub source: Option<String>,
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Walk and validate the flow without executing any node.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Dispatch priority, taken from the flow trigger that started the run
+    #[serde(default)]
+    pub priority: i32,    /// Environment supplying flow parameters and credentials, if any
+    #[serde(default)]
+    pub environment: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +67,138 @@ pub struct NodeExecution {
     pub logs: Vec<ExecutionLog>,
 }
 
+/// Timing and outcome of a single node within an execution. `duration_ms`
+/// is measured with a monotonic clock; `finished_at` is derived from it so it
+/// never precedes `started_at`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeExecutionRecord {
+    pub node_id: String,
+    pub status: ExecutionStatus,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub duration_ms: u64,
+    /// Total attempts including the first, so a node that succeeded on its
+    /// second retry reports 3.
+    pub attempts: u32,
+    pub error: Option<String>,
+    /// Parameters the node ran with, after edge values and references were
+    /// resolved. Replays feed these back in unchanged.
+    #[serde(default)]
+    pub input: Option<serde_json::Value>,
+    #[serde(default)]
+    pub output: Option<serde_json::Value>,
+}
+
+/// Snapshot of an unfinished execution, saved before each batch of nodes
+/// starts so the execution can resume in another process. Nodes listed in
+/// `node_records` have finished and are not run again on resume.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionState {
+    pub execution_id: Uuid,
+    pub flow_id: Uuid,
+    pub flow_version: String,
+    pub trigger: ExecutionTrigger,
+    pub input_data: serde_json::Value,
+    /// Output of every node that completed, keyed by node id.
+    #[serde(default)]
+    pub node_outputs: HashMap<String, serde_json::Value>,
+    /// Nodes that failed with error edges wired; their entry in
+    /// `node_outputs` holds the error under `on_error`.
+    #[serde(default)]
+    pub failed_nodes: Vec<String>,
+    /// Nodes that have not finished yet, in the order they will run.
+    #[serde(default)]
+    pub pending_nodes: Vec<String>,
+    /// Flow-scoped variables set so far.
+    #[serde(default)]
+    pub variables: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub node_records: Vec<NodeExecutionRecord>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ExecutionState {
+    /// State of an execution that has not run any node yet.
+    pub fn new(execution: &FlowExecution) -> Self {
+        Self {
+            execution_id: execution.id,
+            flow_id: execution.flow_id,
+            flow_version: execution.flow_version.clone(),
+            trigger: execution.trigger.clone(),
+            input_data: execution.input_data.clone(),
+            node_outputs: HashMap::new(),
+            failed_nodes: Vec::new(),
+            pending_nodes: Vec::new(),
+            variables: HashMap::new(),
+            node_records: Vec::new(),
+            started_at: execution.started_at,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// An execution that failed after its nodes used up their retries, kept so
+/// operators can inspect it and run it again with the same input.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub execution_id: Uuid,
+    pub flow_id: Uuid,
+    pub flow_version: String,
+    /// Node whose failure ended the execution, if a node failed
+    pub failed_node: Option<String>,
+    pub error: String,
+    /// Attempts the failed node made, including the first
+    pub attempts: u32,
+    pub trigger: ExecutionTrigger,
+    pub input_data: serde_json::Value,
+    pub failed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl DeadLetter {
+    /// Dead letter for `execution` if it failed; `None` for any other outcome
+    /// and for dry runs.
+    pub fn from_execution(execution: &FlowExecution) -> Option<Self> {
+        if execution.status != ExecutionStatus::Failed || execution.trigger.dry_run {
+            return None;
+        }
+        let failed = execution
+            .node_records
+            .iter()
+            .rev()
+            .find(|r| r.status == ExecutionStatus::Failed);
+        let error = execution
+            .error
+            .as_ref()
+            .map(|e| e.message.clone())
+            .or_else(|| failed.and_then(|r| r.error.clone()))
+            .unwrap_or_else(|| "Execution failed".to_string());
+
+        Some(Self {
+            execution_id: execution.id,
+            flow_id: execution.flow_id,
+            flow_version: execution.flow_version.clone(),
+            failed_node: failed.map(|r| r.node_id.clone()),
+            error,
+            attempts: failed.map_or(0, |r| r.attempts),
+            trigger: execution.trigger.clone(),
+            input_data: execution.input_data.clone(),
+            failed_at: execution.completed_at.unwrap_or_else(chrono::Utc::now),
+        })
+    }
+}
+
+/// What a dry run found for one node: its parameters after reference
+/// resolution and every problem that would have stopped it from running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeValidationReport {
+    pub node_id: String,
+    pub node_type: String,
+    pub valid: bool,
+    pub parameters: serde_json::Value,
+    pub errors: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionError {
     pub error_type: ErrorType,
@@ -109,6 +257,29 @@ pub struct ExecutionContext {
     pub variables: HashMap<String, serde_json::Value>,
     pub secrets: HashMap<String, String>,
     pub artifacts: HashMap<String, ArtifactReference>,
+    /// Outputs of nodes that have already completed in this execution, keyed
+    /// by node id.
+    #[serde(default)]
+    pub node_outputs: HashMap<String, serde_json::Value>,
+    /// Which try of this node this is, starting at 1 and counting up with
+    /// each retry.
+    #[serde(default = "first_attempt")]
+    pub attempt: u32,
+}
+
+fn first_attempt() -> u32 {
+    1
+}
+
+impl ExecutionContext {
+    /// Key for provider-level idempotency (`Idempotency-Key` headers and the
+    /// like). The same for every retry of a node within one execution, and
+    /// different for every other node or execution, so a retried request the
+    /// provider already handled is not carried out twice.
+    pub fn idempotency_key(&self) -> String {
+        // A UUID fits every provider's format, e.g. Slack's `client_msg_id`
+        Uuid::new_v5(&self.execution_id, self.node_id.as_bytes()).to_string()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
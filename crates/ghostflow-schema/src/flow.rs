@@ -13,9 +13,34 @@ pub struct Flow {
     pub triggers: Vec<FlowTrigger>,
     pub parameters: HashMap<String, FlowParameter>,
     pub secrets: Vec<String>,
+    /// Wall-clock limit for a whole execution. When it passes, running nodes
+    /// are cancelled, no further nodes start, and the execution fails with a
+    /// timeout. Applies alongside each node's own `timeout_ms`.
+    #[serde(default)]
+    pub max_duration_ms: Option<u64>,
+    /// How many executions of this flow may run at once; unlimited when
+    /// unset. `overflow_policy` decides what happens to triggers beyond it.
+    #[serde(default)]
+    pub max_concurrent_executions: Option<u32>,
+    #[serde(default)]
+    pub overflow_policy: OverflowPolicy,
     pub metadata: FlowMetadata,
 }
 
+/// What to do with a trigger that arrives while a flow is already running
+/// `max_concurrent_executions` times.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverflowPolicy {
+    /// Wait for a running execution to finish, in arrival order
+    #[default]
+    Queue,
+    /// Reject the new execution
+    Drop,
+    /// Cancel the oldest running execution and start the new one
+    Replace,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlowNode {
     pub id: String,
@@ -26,8 +51,28 @@ pub struct FlowNode {
     pub position: NodePosition,
     pub retry_config: Option<RetryConfig>,
     pub timeout_ms: Option<u64>,
+    /// Reuse this node's output for identical inputs for this long. Only
+    /// honoured for deterministic nodes.
+    #[serde(default)]
+    pub cache_ttl_ms: Option<u64>,
+    /// Version of the node type the parameters were written for. Older
+    /// versions are upgraded when the flow is deployed; see
+    /// `NodeMigrationRegistry`. Unset means the current version.
+    #[serde(default)]
+    pub node_version: Option<String>,
 }
 
+/// Source port of error edges. When a node with error edges fails, the flow
+/// carries on: the error (`code`, `message`, `node_id`) travels along those
+/// edges and the node's normal edges stay inactive.
+pub const ON_ERROR_PORT: &str = "on_error";
+
+/// Input port through which nodes that accept multiple activations receive
+/// one entry per completed upstream node, in the order they finished. Each
+/// entry holds the `node_id`, the `value` carried by the edge and
+/// `after_ms`, the time since the first upstream node finished.
+pub const ACTIVATIONS_PORT: &str = "activations";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlowEdge {
     pub id: String,
@@ -35,7 +80,31 @@ pub struct FlowEdge {
     pub target_node: String,
     pub source_port: Option<String>,
     pub target_port: Option<String>,
+    /// Expression evaluated against the source node's output, e.g.
+    /// `status == "error"`. The edge only activates when it holds.
+    #[serde(default)]
     pub condition: Option<String>,
+    /// Convert the value to the target port's data type when it does not
+    /// match: strings and numbers, numbers and booleans, and scalars and
+    /// single-element arrays convert both ways.
+    #[serde(default)]
+    pub coerce: bool,
+}
+
+impl FlowEdge {
+    /// Whether this edge leaves its source's [`ON_ERROR_PORT`].
+    pub fn is_error_edge(&self) -> bool {
+        self.source_port.as_deref() == Some(ON_ERROR_PORT)
+    }
+}
+
+/// Immutable snapshot of a flow as saved to storage. Versions are numbered
+/// from 1 per flow; `flow.version` is overwritten with the same number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowVersion {
+    pub version: u32,
+    pub saved_at: chrono::DateTime<chrono::Utc>,
+    pub flow: Flow,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +113,10 @@ pub struct FlowTrigger {
     pub trigger_type: TriggerType,
     pub config: HashMap<String, serde_json::Value>,
     pub enabled: bool,
+    /// Executions started by this trigger are dispatched ahead of queued
+    /// executions with a lower priority. See `FlowRuntime::with_max_concurrency`.
+    #[serde(default)]
+    pub priority: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +128,33 @@ pub enum TriggerType {
     Cron { expression: String, timezone: Option<String> },
     #[serde(rename = "manual")]
     Manual,
+    /// Request `url` every `interval_secs` and start the flow only when the
+    /// response has changed. With `items`, a JMESPath expression selecting a
+    /// list of records, only records not in the previous response are passed
+    /// on; `cursor` is evaluated on each record to identify it (e.g. `id`),
+    /// otherwise whole records are compared.
+    #[serde(rename = "poll")]
+    Poll {
+        url: String,
+        interval_secs: u64,
+        #[serde(default)]
+        items: Option<String>,
+        #[serde(default)]
+        cursor: Option<String>,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+    /// Open a MongoDB change stream on `collection` and start the flow once
+    /// per change event, with the event document as input. `pipeline` holds
+    /// extra aggregation stages such as `{"$match": {"operationType": "insert"}}`.
+    #[serde(rename = "mongodb_change_stream")]
+    MongoChangeStream {
+        connection_string: String,
+        database: String,
+        collection: String,
+        #[serde(default)]
+        pipeline: Vec<serde_json::Value>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,4 +198,12 @@ pub struct FlowMetadata {
     pub created_by: String,
     pub tags: Vec<String>,
     pub category: Option<String>,
+    /// Workspace the flow belongs to; only that workspace may follow its
+    /// executions
+    #[serde(default = "default_workspace_id")]
+    pub workspace_id: String,
+}
+
+fn default_workspace_id() -> String {
+    "default".to_string()
 }
\ No newline at end of file
@@ -5,7 +5,9 @@ use uuid::Uuid;
 pub mod flow;
 pub mod node;
 pub mod execution;
+pub mod binary;
 
 pub use flow::*;
 pub use node::*;
-pub use execution::*;
\ No newline at end of file
+pub use execution::*;
+pub use binary::*;
\ No newline at end of file
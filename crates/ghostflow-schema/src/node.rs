@@ -78,6 +78,23 @@ pub enum DataType {
     Null,
 }
 
+impl DataType {
+    /// Whether `value` is acceptable on a port of this type. `Binary` only
+    /// accepts the canonical [`BinaryData`](crate::BinaryData) form.
+    pub fn accepts(&self, value: &serde_json::Value) -> bool {
+        match self {
+            DataType::Any => true,
+            DataType::String => value.is_string(),
+            DataType::Number => value.is_number(),
+            DataType::Boolean => value.is_boolean(),
+            DataType::Object => value.is_object(),
+            DataType::Array => value.is_array(),
+            DataType::Binary => crate::BinaryData::is_binary(value),
+            DataType::Null => value.is_null(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ParameterType {
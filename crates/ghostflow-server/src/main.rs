@@ -35,7 +35,7 @@ async fn list_flows(State(_state): State<AppState>) -> Result<Json<Value>, Statu
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt::init();
+    ghostflow_engine::init_tracing("ghostflow-server")?;
 
     let state = AppState {};
 
@@ -50,5 +50,6 @@ async fn main() -> anyhow::Result<()> {
     let listener = TcpListener::bind(addr).await?;
     axum::serve(listener, app).await?;
 
+    ghostflow_engine::shutdown_tracing();
     Ok(())
 }
\ No newline at end of file
@@ -5,9 +5,8 @@
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
 use std::ffi::{CStr, CString};
+use std::cell::RefCell;
 use std::os::raw::c_char;
-use std::sync::{Arc, Mutex};
-use std::collections::HashMap;
 
 /// Error types for GhostLLM operations
 #[derive(Debug, Clone)]
@@ -79,10 +78,10 @@ pub struct GhostLLM {
     config: GhostConfig,
 }
 
-// Global storage for callbacks (needed for C FFI)
-lazy_static::lazy_static! {
-    static ref CALLBACK_STORAGE: Arc<Mutex<HashMap<usize, Box<dyn StreamingCallback>>>> = 
-        Arc::new(Mutex::new(HashMap::new()));
+// The C callback carries no user data. `ghost_generate` invokes it on the
+// calling thread before returning, so the active callback is kept per thread.
+thread_local! {
+    static ACTIVE_STREAM: RefCell<Option<Box<dyn FnMut(&str)>>> = RefCell::new(None);
 }
 
 // C callback wrapper
@@ -91,13 +90,28 @@ extern "C" fn stream_callback_wrapper(text: *const c_char, len: usize) {
         return;
     }
     
-    unsafe {
-        let slice = std::slice::from_raw_parts(text as *const u8, len);
-        if let Ok(token_str) = std::str::from_utf8(slice) {
-            // For simplicity in this demo, we'll print the token
-            // In a real implementation, you'd need a way to route this to the correct callback
-            print!("{}", token_str);
+    let slice = unsafe { std::slice::from_raw_parts(text as *const u8, len) };
+    let token = String::from_utf8_lossy(slice);
+    ACTIVE_STREAM.with(|active| {
+        if let Some(callback) = active.borrow_mut().as_mut() {
+            callback(&token);
         }
+    });
+}
+
+/// Clears the thread's active callback when generation ends, even on error
+struct ActiveStreamGuard;
+
+impl ActiveStreamGuard {
+    fn install(callback: Box<dyn FnMut(&str)>) -> Self {
+        ACTIVE_STREAM.with(|active| *active.borrow_mut() = Some(callback));
+        Self
+    }
+}
+
+impl Drop for ActiveStreamGuard {
+    fn drop(&mut self) {
+        ACTIVE_STREAM.with(|active| active.borrow_mut().take());
     }
 }
 
@@ -187,13 +201,14 @@ impl GhostLLM {
         }
     }
     
-    /// Generate text with streaming callback
-    pub fn generate_stream<F>(&self, prompt: &str, mut callback: F) -> Result<GhostGenerationResponse, GhostLLMError>
+    /// Generate text, passing each token to `callback` as it is produced
+    pub fn generate_stream<F>(&self, prompt: &str, callback: F) -> Result<GhostGenerationResponse, GhostLLMError>
     where
         F: FnMut(&str) + Send + 'static,
     {
         let c_prompt = CString::new(prompt)
             .map_err(|_| GhostLLMError::GenerationFailed)?;
+        let _stream = ActiveStreamGuard::install(Box::new(callback));
         
         unsafe {
             let response = ghost_generate(
@@ -284,6 +299,20 @@ mod tests {
         assert!(resp.tokens_used > 0);
     }
     
+    #[test]
+    fn test_stream_tokens_reach_the_callback() {
+        let llm = GhostLLM::new("test_model.gguf").expect("Failed to create LLM");
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let response = llm.generate_stream("Hello", move |token| sender.send(token.to_string()).unwrap());
+        assert!(response.is_ok());
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec!["Stub ", "response"]);
+
+        // Nothing is left routed to the finished stream
+        llm.generate("Hello").unwrap();
+        assert!(ACTIVE_STREAM.with(|active| active.borrow().is_none()));
+    }
+    
     #[test]
     fn test_config_update() {
         let mut llm = GhostLLM::new("test_model.gguf").expect("Failed to create LLM");